@@ -38,11 +38,6 @@ use sui_package_extractor::bytecode::{
 use sui_package_extractor::extract_module_dependency_ids as extract_dependency_addrs;
 use sui_package_extractor::utils::is_framework_address;
 
-use sui_sandbox_core::adapter::{
-    resolve_discovery_package_filter as core_resolve_discovery_package_filter,
-    resolve_required_package_id as core_resolve_required_package_id,
-    ProtocolAdapter as CoreProtocolAdapter,
-};
 use sui_sandbox_core::checkpoint_discovery::{
     build_walrus_client as core_build_walrus_client,
     discover_checkpoint_targets as core_discover_checkpoint_targets,
@@ -62,6 +57,7 @@ use sui_sandbox_core::orchestrator::{
     HistoricalSeriesExecutionOptions as CoreHistoricalSeriesExecutionOptions,
     HistoricalSeriesPoint as CoreHistoricalSeriesPoint, ReplayOrchestrator, ReturnDecodeField,
 };
+use sui_sandbox_core::protocol_registry::ProtocolRegistry as CoreProtocolRegistry;
 use sui_sandbox_core::ptb_universe::{
     run_with_args as core_run_ptb_universe, Args as CorePtbUniverseArgs,
     CheckpointSource as CoreCheckpointSource, DEFAULT_LATEST as CORE_PTB_UNIVERSE_DEFAULT_LATEST,
@@ -97,7 +93,7 @@ use sui_sandbox_core::workflow_runner::{
 use sui_state_fetcher::{
     bcs_codec, build_aliases, checkpoint_to_replay_state, import_replay_states,
     parse_replay_states_file, FileStateProvider, HistoricalStateProvider, ImportSpec, PackageData,
-    ReplayState,
+    ReplayState, ReplayStateFormat,
 };
 
 use sui_transport::graphql::GraphQLClient;
@@ -426,6 +422,7 @@ pub async fn ptb_universe(
         out_dir: out_dir_path.clone(),
         grpc_endpoint: grpc_endpoint_owned.clone(),
         stream_timeout_secs: stream_timeout_val,
+        seed: None,
     };
 
     core_run_ptb_universe(args).map_err(to_napi_err)?;
@@ -2137,8 +2134,13 @@ fn fuzz_function_inner(
     dry_run: bool,
     fetch_deps: bool,
     bytecode_dir: Option<&str>,
+    coverage: bool,
+    corpus_path: Option<&str>,
+    invariants: Vec<String>,
 ) -> Result<serde_json::Value> {
-    use sui_sandbox_core::fuzz::{classify_params, FuzzConfig, FuzzRunner};
+    use sui_sandbox_core::fuzz::{
+        classify_params, FuzzConfig, FuzzCorpus, FuzzRunner, MoveInvariant,
+    };
 
     let (resolver, _loaded) = if let Some(dir) = bytecode_dir {
         // Load modules from local build directory (no network needed)
@@ -2229,10 +2231,30 @@ fn fuzz_function_inner(
         type_args: parsed_type_args,
         fail_fast,
         max_vector_len,
+        coverage_guided: coverage || corpus_path.is_some(),
     };
 
-    let runner = FuzzRunner::new(&resolver);
-    let report = runner.run(target_addr, module, function, &classification, &config)?;
+    let mut runner = FuzzRunner::new(&resolver);
+    for invariant in &invariants {
+        runner = runner.with_invariant(MoveInvariant::parse(invariant)?);
+    }
+    let report = match corpus_path {
+        Some(path) => {
+            let path = std::path::Path::new(path);
+            let mut corpus = FuzzCorpus::load(path)?;
+            let report = runner.run_with_corpus(
+                target_addr,
+                module,
+                function,
+                &classification,
+                &config,
+                &mut corpus,
+            )?;
+            corpus.save(path)?;
+            report
+        }
+        None => runner.run(target_addr, module, function, &classification, &config)?,
+    };
 
     serde_json::to_value(&report).map_err(|e| anyhow!("Failed to serialize fuzz report: {}", e))
 }
@@ -2243,6 +2265,12 @@ fn fuzz_function_inner(
 /// (e.g., "build/my_package") instead of fetching from the network. This enables
 /// fuzzing testnet-only or locally-compiled contracts without network access.
 /// The directory should contain a `bytecode_modules/` subdirectory with `.mv` files.
+///
+/// `coverage` enables coverage-guided mutation. `corpus_path` loads a corpus JSON file
+/// before the run and saves it afterwards so campaigns can resume (implies `coverage`).
+/// `invariants` lists Move functions (`0xPKG::module::function`) called after each successful
+/// iteration with the target's inputs and return values; they are violated if they abort or
+/// return false.
 #[napi]
 pub async fn fuzz_function(
     package_id: String,
@@ -2258,6 +2286,9 @@ pub async fn fuzz_function(
     dry_run: Option<bool>,
     fetch_deps: Option<bool>,
     bytecode_dir: Option<String>,
+    coverage: Option<bool>,
+    corpus_path: Option<String>,
+    invariants: Option<Vec<String>>,
 ) -> napi::Result<serde_json::Value> {
     let actual_seed = seed.map(|v| v as u64).unwrap_or_else(|| {
         SystemTime::now()
@@ -2280,6 +2311,9 @@ pub async fn fuzz_function(
         dry_run.unwrap_or(false),
         fetch_deps.unwrap_or(true),
         bytecode_dir.as_deref(),
+        coverage.unwrap_or(false),
+        corpus_path.as_deref(),
+        invariants.unwrap_or_default(),
     )
     .map_err(to_napi_err)
 }
@@ -279,9 +279,14 @@ pub(crate) fn enable_self_heal_fetchers(
         move |parent, _child_id, _key_type, key_bytes| {
             let parent_hex = parent.to_hex_literal();
             let field = graphql_for_key
-                .find_dynamic_field_by_bcs(&parent_hex, key_bytes, checkpoint, 1000)
-                .ok()
-                .flatten()?;
+                .scan_dynamic_field_by_bcs(
+                    &parent_hex,
+                    key_bytes,
+                    checkpoint,
+                    sui_transport::graphql::dynamic_field_scan_max_pages(),
+                )
+                .ok()?
+                .found()?;
 
             let value_type = field.value_type?;
             let parsed = sui_sandbox_core::types::parse_type_tag(&value_type).ok()?;
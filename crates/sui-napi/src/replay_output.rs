@@ -48,6 +48,7 @@ pub(crate) fn import_state_inner(
         transactions: transactions.map(PathBuf::from),
         objects: objects.map(PathBuf::from),
         packages: packages.map(PathBuf::from),
+        state_format: ReplayStateFormat::Auto,
     };
     let summary = import_replay_states(&cache_dir, &spec)?;
     Ok(serde_json::json!({
@@ -246,6 +247,8 @@ pub(crate) fn build_replay_output(
                 "failed_command_description": effects.failed_command_description,
                 "commands_succeeded": effects.commands_succeeded,
                 "return_values": effects.return_values.iter().map(|v| v.len()).collect::<Vec<_>>(),
+                "abort": effects.error_context.as_ref().and_then(|ctx| ctx.abort_info.as_ref()),
+                "balance_changes": effects.balance_changes,
             });
 
             let comparison = if compare {
@@ -280,6 +283,8 @@ pub(crate) fn build_replay_output(
 
             if let Some(err) = &result.local_error {
                 output["local_error"] = serde_json::json!(err);
+                output["local_error_kind"] =
+                    serde_json::json!(sui_sandbox_core::SandboxError::from_message(err).kind());
             }
             if let Some(diagnostics) = diagnostics {
                 output["diagnostics"] = diagnostics;
@@ -295,6 +300,7 @@ pub(crate) fn build_replay_output(
                 "digest": replay_state.transaction.digest.0,
                 "local_success": false,
                 "local_error": e.to_string(),
+                "local_error_kind": sui_sandbox_core::SandboxError::classify(&e).kind(),
                 "execution_path": execution_path,
                 "commands_executed": 0,
             });
@@ -16,16 +16,14 @@ pub(crate) fn resolve_protocol_package_id(
     protocol: &str,
     package_id: Option<&str>,
 ) -> Result<String> {
-    let parsed = CoreProtocolAdapter::parse(protocol)?;
-    core_resolve_required_package_id(parsed, package_id)
+    CoreProtocolRegistry::load_default()?.resolve_package_id(protocol, package_id)
 }
 
 pub(crate) fn resolve_protocol_discovery_package_filter(
     protocol: &str,
     package_id: Option<&str>,
 ) -> Result<Option<String>> {
-    let parsed = CoreProtocolAdapter::parse(protocol)?;
-    core_resolve_discovery_package_filter(parsed, package_id)
+    CoreProtocolRegistry::load_default()?.resolve_discovery_package_filter(protocol, package_id)
 }
 
 pub(crate) fn discover_checkpoint_targets_inner(
@@ -4,24 +4,26 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::normalization::signature_token_to_json;
+use crate::normalization::{signature_token_to_json, signature_token_to_type_tag};
 use crate::types::{
-    BytecodeBoundsCheckJson, BytecodeConstantJson, BytecodeEnumJson, BytecodeEnumVariantJson,
-    BytecodeFieldJson, BytecodeFunctionBodyJson, BytecodeFunctionInstantiationJson,
+    BytecodeBoundsCheckJson, BytecodeCallTargetJson, BytecodeConstantJson, BytecodeEntrypointJson,
+    BytecodeEntrypointParamJson, BytecodeEnumJson, BytecodeEnumVariantJson, BytecodeFieldJson,
+    BytecodeFunctionBodyJson, BytecodeFunctionCallsJson, BytecodeFunctionInstantiationJson,
     BytecodeFunctionJson, BytecodeFunctionTypeParamJson, BytecodeInstructionJson,
     BytecodeJumpTableJson, BytecodeMetadataJson, BytecodeModuleJson, BytecodePackageInterfaceJson,
-    BytecodeStructInstantiationJson, BytecodeStructJson, BytecodeStructRefJson,
-    BytecodeStructTypeParamJson, LocalBytecodeCounts, LocalBytesCheck, ModuleBytesMismatch,
-    SanityCounts,
+    BytecodePackageTypeJson, BytecodeStructInstantiationJson, BytecodeStructJson,
+    BytecodeStructRefJson, BytecodeStructTypeParamJson, LocalBytecodeCounts, LocalBytesCheck,
+    ModuleBytesMismatch, SanityCounts,
 };
 use crate::utils::{
     bytes_info, bytes_info_sha256_hex, bytes_to_hex_prefixed, canonicalize_json_value, BytesInfo,
 };
 use move_binary_format::check_bounds::BoundsChecker;
 use move_binary_format::file_format::{
-    Ability, AbilitySet, Bytecode, CompiledModule, JumpTableInner, StructFieldInformation,
-    Visibility,
+    Ability, AbilitySet, Bytecode, CompiledModule, DatatypeHandleIndex, JumpTableInner,
+    SignatureToken, StructFieldInformation, Visibility,
 };
+use move_core_types::account_address::AccountAddress;
 
 pub fn module_self_address_hex(module: &CompiledModule) -> String {
     bytes_to_hex_prefixed(module.self_id().address().as_ref())
@@ -463,6 +465,235 @@ pub fn build_bytecode_interface_value_from_compiled_modules(
     Ok((module_names, interface_value))
 }
 
+/// Callable surface of a package: every public or entry function, sorted by
+/// `module::function`, with each parameter classified by how a PTB supplies it.
+pub fn build_entrypoint_catalog(
+    compiled_modules: &[CompiledModule],
+) -> Vec<BytecodeEntrypointJson> {
+    let mut entrypoints = Vec::new();
+    for module in compiled_modules {
+        let module_name = compiled_module_name(module);
+        for def in module.function_defs() {
+            if def.visibility != Visibility::Public && !def.is_entry {
+                continue;
+            }
+            let handle = module.function_handle_at(def.function);
+            let params = module
+                .signature_at(handle.parameters)
+                .0
+                .iter()
+                .enumerate()
+                .map(|(index, tok)| {
+                    let (kind, access) = entrypoint_param_shape(module, tok);
+                    BytecodeEntrypointParamJson {
+                        index,
+                        kind: kind.to_string(),
+                        access: access.to_string(),
+                        type_tag: signature_token_to_type_tag(module, tok),
+                    }
+                })
+                .collect();
+            let returns = module
+                .signature_at(handle.return_)
+                .0
+                .iter()
+                .map(|tok| signature_token_to_type_tag(module, tok))
+                .collect();
+            entrypoints.push(BytecodeEntrypointJson {
+                module: module_name.clone(),
+                function: module.identifier_at(handle.name).to_string(),
+                visibility: visibility_to_string(def.visibility),
+                is_entry: def.is_entry,
+                type_params: handle.type_parameters.len(),
+                params,
+                returns,
+            });
+        }
+    }
+    entrypoints.sort_by(|a, b| (&a.module, &a.function).cmp(&(&b.module, &b.function)));
+    entrypoints
+}
+
+/// Static call graph of a package: for every function definition, sorted by
+/// `module::function`, the distinct functions its body calls (`Call`/`CallGeneric`).
+///
+/// Targets are resolved through the caller's function, module and address handle
+/// tables; native functions have no body and list no calls.
+pub fn build_call_target_catalog(
+    compiled_modules: &[CompiledModule],
+) -> Vec<BytecodeFunctionCallsJson> {
+    let mut catalog = Vec::new();
+    for module in compiled_modules {
+        let module_name = compiled_module_name(module);
+        let self_address = *module.self_id().address();
+        for def in module.function_defs() {
+            let handle = module.function_handle_at(def.function);
+            let mut calls = BTreeSet::new();
+            for instruction in def.code.iter().flat_map(|code| &code.code) {
+                let callee = match instruction {
+                    Bytecode::Call(idx) => *idx,
+                    Bytecode::CallGeneric(idx) => module.function_instantiation_at(*idx).handle,
+                    _ => continue,
+                };
+                let callee = module.function_handle_at(callee);
+                let callee_module = module.module_handle_at(callee.module);
+                let package = *module.address_identifier_at(callee_module.address);
+                calls.insert(BytecodeCallTargetJson {
+                    package: bytes_to_hex_prefixed(package.as_ref()),
+                    module: module.identifier_at(callee_module.name).to_string(),
+                    function: module.identifier_at(callee.name).to_string(),
+                    external: package != self_address,
+                });
+            }
+            catalog.push(BytecodeFunctionCallsJson {
+                module: module_name.clone(),
+                function: module.identifier_at(handle.name).to_string(),
+                calls: calls.into_iter().collect(),
+            });
+        }
+    }
+    catalog.sort_by(|a, b| (&a.module, &a.function).cmp(&(&b.module, &b.function)));
+    catalog
+}
+
+/// Datatypes a package defines, sorted by `module::name`, with the abilities that
+/// decide whether values can be copied, dropped, stored or held as objects.
+pub fn build_package_type_catalog(
+    compiled_modules: &[CompiledModule],
+) -> Vec<BytecodePackageTypeJson> {
+    let mut catalog = Vec::new();
+    for module in compiled_modules {
+        let module_name = compiled_module_name(module);
+        let address = module_self_address_hex(module);
+        let datatypes = module
+            .struct_defs()
+            .iter()
+            .map(|def| {
+                let handle = module.datatype_handle_at(def.struct_handle);
+                (
+                    "struct",
+                    handle,
+                    is_one_time_witness(module, &module_name, def),
+                )
+            })
+            .chain(
+                module
+                    .enum_defs()
+                    .iter()
+                    .map(|def| ("enum", module.datatype_handle_at(def.enum_handle), false)),
+            );
+        for (kind, handle, is_one_time_witness) in datatypes {
+            let name = module.identifier_at(handle.name).to_string();
+            catalog.push(BytecodePackageTypeJson {
+                type_tag: format!("{}::{}::{}", address, module_name, name),
+                module: module_name.clone(),
+                name,
+                kind: kind.to_string(),
+                abilities: ability_set_to_strings(&handle.abilities),
+                type_params: handle
+                    .type_parameters
+                    .iter()
+                    .map(|tp| BytecodeStructTypeParamJson {
+                        constraints: ability_set_to_strings(&tp.constraints),
+                        is_phantom: tp.is_phantom,
+                    })
+                    .collect(),
+                is_object: ability_set_has_key(&handle.abilities),
+                is_one_time_witness,
+            });
+        }
+    }
+    catalog.sort_by(|a, b| (&a.module, &a.name).cmp(&(&b.module, &b.name)));
+    catalog
+}
+
+/// Sui's one-time-witness shape: named after the module in upper case, only `drop`,
+/// no type parameters, and no fields (the compiler emits a single `bool` field).
+fn is_one_time_witness(
+    module: &CompiledModule,
+    module_name: &str,
+    def: &move_binary_format::file_format::StructDefinition,
+) -> bool {
+    let handle = module.datatype_handle_at(def.struct_handle);
+    let no_fields = match &def.field_information {
+        StructFieldInformation::Declared(fields) => match fields.as_slice() {
+            [] => true,
+            [field] => field.signature.0 == SignatureToken::Bool,
+            _ => false,
+        },
+        StructFieldInformation::Native => false,
+    };
+    module.identifier_at(handle.name).as_str() == module_name.to_ascii_uppercase()
+        && handle.abilities == AbilitySet::singleton(Ability::Drop)
+        && handle.type_parameters.is_empty()
+        && no_fields
+}
+
+fn entrypoint_param_shape(
+    module: &CompiledModule,
+    tok: &SignatureToken,
+) -> (&'static str, &'static str) {
+    match tok {
+        SignatureToken::Reference(inner) => (entrypoint_value_kind(module, inner), "ref"),
+        SignatureToken::MutableReference(inner) => {
+            (entrypoint_value_kind(module, inner), "mut_ref")
+        }
+        other => (entrypoint_value_kind(module, other), "by_value"),
+    }
+}
+
+fn entrypoint_value_kind(module: &CompiledModule, tok: &SignatureToken) -> &'static str {
+    match tok {
+        SignatureToken::Bool
+        | SignatureToken::U8
+        | SignatureToken::U16
+        | SignatureToken::U32
+        | SignatureToken::U64
+        | SignatureToken::U128
+        | SignatureToken::U256
+        | SignatureToken::Address => "pure",
+        SignatureToken::Vector(inner) => match entrypoint_value_kind(module, inner) {
+            "pure" => "pure",
+            _ => "value",
+        },
+        SignatureToken::TypeParameter(_) => "type_param",
+        SignatureToken::Datatype(idx) => entrypoint_datatype_kind(module, *idx, &[]),
+        SignatureToken::DatatypeInstantiation(inst) => {
+            let (idx, tys) = &**inst;
+            entrypoint_datatype_kind(module, *idx, tys)
+        }
+        SignatureToken::Signer
+        | SignatureToken::Reference(_)
+        | SignatureToken::MutableReference(_) => "value",
+    }
+}
+
+fn entrypoint_datatype_kind(
+    module: &CompiledModule,
+    idx: DatatypeHandleIndex,
+    type_args: &[SignatureToken],
+) -> &'static str {
+    let handle = module.datatype_handle_at(idx);
+    if ability_set_has_key(&handle.abilities) {
+        return "object";
+    }
+    let module_handle = module.module_handle_at(handle.module);
+    let address = *module.address_identifier_at(module_handle.address);
+    let module_name = module.identifier_at(module_handle.name).as_str();
+    let name = module.identifier_at(handle.name).as_str();
+    match (address, module_name, name) {
+        (AccountAddress::TWO, "tx_context", "TxContext") => "tx_context",
+        (AccountAddress::ONE, "string", "String")
+        | (AccountAddress::ONE, "ascii", "String")
+        | (AccountAddress::TWO, "object", "ID") => "pure",
+        (AccountAddress::ONE, "option", "Option") => match type_args {
+            [inner] if entrypoint_value_kind(module, inner) == "pure" => "pure",
+            _ => "value",
+        },
+        _ => "value",
+    }
+}
+
 pub fn ability_set_has_key(set: &AbilitySet) -> bool {
     set.has_ability(Ability::Key)
 }
@@ -988,7 +1219,8 @@ mod tests {
     use super::*;
     use move_binary_format::file_format::{
         basic_test_module, basic_test_module_with_enum, AbilitySet, AddressIdentifierIndex,
-        Bytecode, Constant, IdentifierIndex, ModuleHandle, SignatureToken,
+        Bytecode, Constant, FunctionHandleIndex, IdentifierIndex, ModuleHandle, ModuleHandleIndex,
+        Signature, SignatureIndex, SignatureToken,
     };
     use move_core_types::account_address::AccountAddress;
     use move_core_types::identifier::Identifier;
@@ -1187,4 +1419,132 @@ mod tests {
         assert_eq!(a, b);
         assert_eq!(a.get("schema_version").and_then(Value::as_u64), Some(2));
     }
+
+    #[test]
+    fn test_build_call_target_catalog_resolves_handles() {
+        let mut module = basic_test_module();
+        let address_idx = AddressIdentifierIndex(module.address_identifiers.len() as u16);
+        module.address_identifiers.push(AccountAddress::TWO);
+        let coin_idx = IdentifierIndex(module.identifiers.len() as u16);
+        module
+            .identifiers
+            .push(Identifier::new("coin").expect("identifier"));
+        let value_idx = IdentifierIndex(module.identifiers.len() as u16);
+        module
+            .identifiers
+            .push(Identifier::new("value").expect("identifier"));
+        let coin_module = ModuleHandleIndex(module.module_handles.len() as u16);
+        module.module_handles.push(ModuleHandle {
+            address: address_idx,
+            name: coin_idx,
+        });
+        let mut value_handle = module.function_handles[0].clone();
+        value_handle.module = coin_module;
+        value_handle.name = value_idx;
+        module.function_handles.push(value_handle);
+
+        if let Some(code) = &mut module.function_defs[0].code {
+            code.code = vec![
+                Bytecode::Call(FunctionHandleIndex(1)),
+                Bytecode::Call(FunctionHandleIndex(0)),
+                Bytecode::Call(FunctionHandleIndex(1)),
+                Bytecode::Ret,
+            ];
+        }
+
+        let self_name = compiled_module_name(&module);
+        let catalog = build_call_target_catalog(&[module]);
+        assert_eq!(catalog.len(), 1);
+        assert_eq!(catalog[0].function, "foo");
+        let calls: Vec<(&str, &str, bool)> = catalog[0]
+            .calls
+            .iter()
+            .map(|c| (c.module.as_str(), c.function.as_str(), c.external))
+            .collect();
+        assert_eq!(
+            calls,
+            vec![(self_name.as_str(), "foo", false), ("coin", "value", true)]
+        );
+        assert_eq!(
+            catalog[0].calls[1].package,
+            bytes_to_hex_prefixed(AccountAddress::TWO.as_ref())
+        );
+    }
+
+    #[test]
+    fn test_build_package_type_catalog_flags_one_time_witness() {
+        let mut module = basic_test_module();
+        let catalog = build_package_type_catalog(std::slice::from_ref(&module));
+        assert_eq!(catalog.len(), 1);
+        assert_eq!(catalog[0].kind, "struct");
+        assert!(!catalog[0].is_object);
+        assert!(!catalog[0].is_one_time_witness);
+
+        let self_name_idx = module.module_handles[module.self_handle_idx().0 as usize].name;
+        module.identifiers[self_name_idx.0 as usize] =
+            Identifier::new("token").expect("identifier");
+        let struct_name_idx = module.datatype_handles[0].name;
+        module.identifiers[struct_name_idx.0 as usize] =
+            Identifier::new("TOKEN").expect("identifier");
+        module.datatype_handles[0].abilities = AbilitySet::singleton(Ability::Drop);
+        if let StructFieldInformation::Declared(fields) =
+            &mut module.struct_defs[0].field_information
+        {
+            fields[0].signature.0 = SignatureToken::Bool;
+        }
+        let catalog = build_package_type_catalog(std::slice::from_ref(&module));
+        assert_eq!(catalog[0].name, "TOKEN");
+        assert_eq!(catalog[0].abilities, vec!["drop"]);
+        assert!(catalog[0].is_one_time_witness);
+        assert!(catalog[0].type_tag.ends_with("::token::TOKEN"));
+
+        module.datatype_handles[0].abilities =
+            AbilitySet::singleton(Ability::Drop).union(AbilitySet::singleton(Ability::Key));
+        let catalog = build_package_type_catalog(std::slice::from_ref(&module));
+        assert!(catalog[0].is_object);
+        assert!(!catalog[0].is_one_time_witness);
+    }
+
+    #[test]
+    fn test_build_entrypoint_catalog_classifies_params() {
+        let mut module = basic_test_module();
+        module.datatype_handles[0].abilities = AbilitySet::singleton(Ability::Key);
+        module.signatures.push(Signature(vec![
+            SignatureToken::MutableReference(Box::new(SignatureToken::Datatype(
+                DatatypeHandleIndex(0),
+            ))),
+            SignatureToken::U64,
+            SignatureToken::Vector(Box::new(SignatureToken::U8)),
+        ]));
+        module
+            .signatures
+            .push(Signature(vec![SignatureToken::Bool]));
+        module.function_handles[0].parameters = SignatureIndex(1);
+        module.function_handles[0].return_ = SignatureIndex(2);
+
+        assert!(build_entrypoint_catalog(std::slice::from_ref(&module)).is_empty());
+
+        module.function_defs[0].visibility = Visibility::Public;
+        let catalog = build_entrypoint_catalog(&[module]);
+        assert_eq!(catalog.len(), 1);
+        let entry = &catalog[0];
+        assert_eq!(entry.function, "foo");
+        assert_eq!(entry.returns, vec!["bool".to_string()]);
+        let shapes: Vec<(&str, &str)> = entry
+            .params
+            .iter()
+            .map(|p| (p.kind.as_str(), p.access.as_str()))
+            .collect();
+        assert_eq!(
+            shapes,
+            vec![
+                ("object", "mut_ref"),
+                ("pure", "by_value"),
+                ("pure", "by_value")
+            ]
+        );
+        assert!(entry.params[0].type_tag.starts_with("&mut 0x"));
+        assert!(entry.params[0].type_tag.ends_with("::Bar"));
+        assert_eq!(entry.params[2].type_tag, "vector<u8>");
+    }
 }
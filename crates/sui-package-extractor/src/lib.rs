@@ -33,7 +33,8 @@ pub mod utils;
 
 // Re-export main types
 pub use bytecode::{
-    build_bytecode_interface_value_from_compiled_modules, extract_module_dependency_ids,
-    read_local_compiled_module_bytes, read_local_compiled_modules,
+    build_bytecode_interface_value_from_compiled_modules, build_call_target_catalog,
+    build_entrypoint_catalog, extract_module_dependency_ids, read_local_compiled_module_bytes,
+    read_local_compiled_modules,
 };
 pub use types::{BytecodeModuleJson, BytecodePackageInterfaceJson};
@@ -239,6 +239,52 @@ pub fn signature_token_to_json(module: &CompiledModule, tok: &SignatureToken) ->
     }
 }
 
+/// Render a signature token as a Move type tag (`0x2::coin::Coin<T0>`, `&mut u64`).
+///
+/// Type parameters are rendered positionally as `T<index>`.
+pub fn signature_token_to_type_tag(module: &CompiledModule, tok: &SignatureToken) -> String {
+    match tok {
+        SignatureToken::Bool => "bool".to_string(),
+        SignatureToken::U8 => "u8".to_string(),
+        SignatureToken::U16 => "u16".to_string(),
+        SignatureToken::U32 => "u32".to_string(),
+        SignatureToken::U64 => "u64".to_string(),
+        SignatureToken::U128 => "u128".to_string(),
+        SignatureToken::U256 => "u256".to_string(),
+        SignatureToken::Address => "address".to_string(),
+        SignatureToken::Signer => "signer".to_string(),
+        SignatureToken::Vector(inner) => {
+            format!("vector<{}>", signature_token_to_type_tag(module, inner))
+        }
+        SignatureToken::Reference(inner) => {
+            format!("&{}", signature_token_to_type_tag(module, inner))
+        }
+        SignatureToken::MutableReference(inner) => {
+            format!("&mut {}", signature_token_to_type_tag(module, inner))
+        }
+        SignatureToken::TypeParameter(idx) => format!("T{}", idx),
+        SignatureToken::Datatype(idx) => {
+            let tref = module_id_for_datatype_handle(module, *idx);
+            format!("{}::{}::{}", tref.address, tref.module, tref.name)
+        }
+        SignatureToken::DatatypeInstantiation(inst) => {
+            let (idx, tys) = &**inst;
+            let tref = module_id_for_datatype_handle(module, *idx);
+            let args: Vec<String> = tys
+                .iter()
+                .map(|t| signature_token_to_type_tag(module, t))
+                .collect();
+            format!(
+                "{}::{}::{}<{}>",
+                tref.address,
+                tref.module,
+                tref.name,
+                args.join(", ")
+            )
+        }
+    }
+}
+
 fn module_id_for_datatype_handle(
     module: &CompiledModule,
     datatype_handle_index: move_binary_format::file_format::DatatypeHandleIndex,
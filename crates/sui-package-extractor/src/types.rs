@@ -71,6 +71,69 @@ pub struct BytecodeFunctionJson {
     pub body: Option<BytecodeFunctionBodyJson>,
 }
 
+/// One callable (entry or public) function in the `entrypoints` catalog.
+#[derive(Debug, Serialize)]
+pub struct BytecodeEntrypointJson {
+    pub module: String,
+    pub function: String,
+    pub visibility: String,
+    pub is_entry: bool,
+    pub type_params: usize,
+    pub params: Vec<BytecodeEntrypointParamJson>,
+    pub returns: Vec<String>,
+}
+
+/// Functions one function statically calls, in the `call_targets` catalog.
+#[derive(Debug, Serialize)]
+pub struct BytecodeFunctionCallsJson {
+    pub module: String,
+    pub function: String,
+    pub calls: Vec<BytecodeCallTargetJson>,
+}
+
+/// A call target resolved through the caller's module/address handle tables.
+///
+/// `package` is the address the bytecode names (the runtime/original package ID for
+/// cross-package calls); `external` is set when it is not the caller's own package.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct BytecodeCallTargetJson {
+    pub package: String,
+    pub module: String,
+    pub function: String,
+    pub external: bool,
+}
+
+/// A datatype a package defines, in the `package_types` catalog.
+///
+/// `kind` is `struct` or `enum`. `is_object` is set for `key` types; a one-time
+/// witness is a struct named after its module in upper case with only `drop`, no
+/// type parameters and no fields (compiled as a single `bool` field).
+#[derive(Debug, Serialize)]
+pub struct BytecodePackageTypeJson {
+    pub module: String,
+    pub name: String,
+    pub type_tag: String,
+    pub kind: String,
+    pub abilities: Vec<String>,
+    pub type_params: Vec<BytecodeStructTypeParamJson>,
+    pub is_object: bool,
+    pub is_one_time_witness: bool,
+}
+
+/// How a PTB supplies an entrypoint parameter.
+///
+/// `kind` is `object`, `pure`, `tx_context` (injected by the runtime),
+/// `type_param`, or `value` (a non-object Move value that must come from
+/// another command's result).
+#[derive(Debug, Serialize)]
+pub struct BytecodeEntrypointParamJson {
+    pub index: usize,
+    pub kind: String,
+    /// `by_value`, `ref`, or `mut_ref`.
+    pub access: String,
+    pub type_tag: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct BytecodeConstantJson {
     pub r#type: Value,
@@ -14,11 +14,14 @@
 use anyhow::Result;
 use move_core_types::account_address::AccountAddress;
 use sui_sandbox_types::{
-    encoding::base64_encode, FetchedTransaction, GasSummary, PtbArgument, PtbCommand,
-    TransactionDigest, TransactionEffectsSummary, TransactionInput, TransactionStatus,
+    encoding::base64_encode, FetchedTransaction, GasPaymentObject, GasSummary, ObjectChangeDetail,
+    ObjectChangeKind, PtbArgument, PtbCommand, TransactionDigest, TransactionEffectsSummary,
+    TransactionInput, TransactionStatus,
 };
 
-use sui_transport::grpc::{GrpcArgument, GrpcCommand, GrpcInput, GrpcTransaction};
+use sui_transport::grpc::{
+    GrpcArgument, GrpcChangedObject, GrpcCommand, GrpcInput, GrpcOwner, GrpcTransaction,
+};
 
 /// Convert a gRPC transaction to the internal FetchedTransaction format.
 ///
@@ -112,6 +115,11 @@ pub fn grpc_to_fetched_transaction(tx: &GrpcTransaction) -> Result<FetchedTransa
             gas_used: GasSummary::default(),
             events_count: 0,
             shared_object_versions: std::collections::HashMap::new(),
+            object_changes: tx
+                .changed_object_details
+                .iter()
+                .filter_map(convert_grpc_changed_object)
+                .collect(),
         }
     });
 
@@ -125,6 +133,45 @@ pub fn grpc_to_fetched_transaction(tx: &GrpcTransaction) -> Result<FetchedTransa
         effects,
         timestamp_ms: tx.timestamp_ms,
         checkpoint: tx.checkpoint,
+        gas_payment: tx
+            .gas_payment
+            .iter()
+            .map(|(object_id, version)| GasPaymentObject {
+                object_id: object_id.clone(),
+                version: *version,
+            })
+            .collect(),
+    })
+}
+
+/// Convert a gRPC changed object to an ObjectChangeDetail.
+///
+/// Objects that neither existed before nor after the transaction (created and then
+/// wrapped or deleted within it) are skipped.
+fn convert_grpc_changed_object(obj: &GrpcChangedObject) -> Option<ObjectChangeDetail> {
+    let change = match (obj.input_exists, obj.output_exists) {
+        (false, true) if obj.id_created => ObjectChangeKind::Created,
+        (false, true) => ObjectChangeKind::Unwrapped,
+        (true, true) => ObjectChangeKind::Mutated,
+        (true, false) if obj.id_deleted => ObjectChangeKind::Deleted,
+        (true, false) => ObjectChangeKind::Wrapped,
+        (false, false) => return None,
+    };
+    let owner = match obj.output_owner.as_ref().filter(|_| obj.output_exists) {
+        Some(GrpcOwner::Address(address)) => Some(format!("address:{}", address)),
+        Some(GrpcOwner::Object(parent)) => Some(format!("object:{}", parent)),
+        Some(GrpcOwner::Shared { .. }) => Some("shared".to_string()),
+        Some(GrpcOwner::Immutable) => Some("immutable".to_string()),
+        Some(GrpcOwner::Unknown) | None => None,
+    };
+    Some(ObjectChangeDetail {
+        object_id: obj.object_id.clone(),
+        change,
+        input_version: obj.input_version.filter(|_| obj.input_exists),
+        output_version: obj.output_version,
+        input_digest: obj.input_digest.clone().filter(|_| obj.input_exists),
+        output_digest: obj.output_digest.clone().filter(|_| obj.output_exists),
+        owner,
     })
 }
 
@@ -365,6 +412,8 @@ mod tests {
             changed_objects: vec![],
             created_objects: vec![],
             unchanged_consensus_objects: vec![],
+            gas_payment: vec![],
+            changed_object_details: vec![],
         };
 
         let fetched = grpc_to_fetched_transaction(&grpc_tx).expect("Should convert");
@@ -402,6 +451,8 @@ mod tests {
             changed_objects: vec![],
             created_objects: vec![],
             unchanged_consensus_objects: vec![],
+            gas_payment: vec![],
+            changed_object_details: vec![],
         };
 
         let fetched = grpc_to_fetched_transaction(&grpc_tx).expect("Should convert");
@@ -413,4 +464,41 @@ mod tests {
             _ => panic!("Expected failure status"),
         }
     }
+
+    #[test]
+    fn test_convert_grpc_changed_object_kinds() {
+        let changed = |input_exists, output_exists, id_created, id_deleted| GrpcChangedObject {
+            object_id: "0xa".to_string(),
+            input_exists,
+            input_version: Some(4),
+            input_digest: Some("in".to_string()),
+            output_exists,
+            output_version: Some(9),
+            output_digest: Some("out".to_string()),
+            output_owner: Some(GrpcOwner::Address("0xb".to_string())),
+            id_created,
+            id_deleted,
+        };
+
+        let created = convert_grpc_changed_object(&changed(false, true, true, false)).unwrap();
+        assert_eq!(created.change, ObjectChangeKind::Created);
+        assert_eq!(created.input_version, None);
+        assert_eq!(created.output_version, Some(9));
+        assert_eq!(created.owner.as_deref(), Some("address:0xb"));
+
+        let mutated = convert_grpc_changed_object(&changed(true, true, false, false)).unwrap();
+        assert_eq!(mutated.change, ObjectChangeKind::Mutated);
+        assert_eq!(mutated.input_version, Some(4));
+
+        let deleted = convert_grpc_changed_object(&changed(true, false, false, true)).unwrap();
+        assert_eq!(deleted.change, ObjectChangeKind::Deleted);
+        assert_eq!(deleted.owner, None);
+        assert_eq!(deleted.output_digest, None);
+
+        let wrapped = convert_grpc_changed_object(&changed(true, false, false, false)).unwrap();
+        assert_eq!(wrapped.change, ObjectChangeKind::Wrapped);
+        let unwrapped = convert_grpc_changed_object(&changed(false, true, false, false)).unwrap();
+        assert_eq!(unwrapped.change, ObjectChangeKind::Unwrapped);
+        assert!(convert_grpc_changed_object(&changed(false, false, true, true)).is_none());
+    }
 }
@@ -878,6 +878,8 @@ mod tests {
             unchanged_consensus_objects: vec![],
             changed_objects: vec![],
             created_objects: vec![],
+            gas_payment: vec![],
+            changed_object_details: vec![],
         };
 
         let versions = collect_historical_versions(&grpc_tx);
@@ -907,6 +909,8 @@ mod tests {
             unchanged_consensus_objects: vec![("0xccc".to_string(), 30)],
             changed_objects: vec![("0xddd".to_string(), 40)],
             created_objects: vec![],
+            gas_payment: vec![],
+            changed_object_details: vec![],
         };
 
         let versions = collect_historical_versions(&grpc_tx);
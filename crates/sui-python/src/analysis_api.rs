@@ -0,0 +1,268 @@
+use super::*;
+
+/// Hydrated transaction handle for repeated offline analysis.
+///
+/// Fetches (or loads) a `ReplayState` once; every accessor is computed lazily
+/// over the cached state so iterating on analysis never refetches from the network.
+#[pyclass(name = "AnalyzedTransaction", module = "sui_sandbox")]
+pub(super) struct AnalyzedTransaction {
+    state: ReplayState,
+    source: String,
+    rpc_url: String,
+    verbose: bool,
+    mm2: Option<serde_json::Value>,
+}
+
+pub(super) fn load_analyzed_state(
+    caller: &str,
+    digest: Option<&str>,
+    checkpoint: Option<u64>,
+    state_file: Option<&str>,
+    verbose: bool,
+) -> Result<(ReplayState, String)> {
+    if let Some(path) = state_file {
+        let state = load_replay_state_from_file(Path::new(path), digest)?;
+        return Ok((state, "state_file".to_string()));
+    }
+    let digest = digest.ok_or_else(|| anyhow!("{} requires digest or state_file", caller))?;
+    let checkpoint = checkpoint
+        .ok_or_else(|| anyhow!("{} requires checkpoint when loading by digest", caller))?;
+    if verbose {
+        eprintln!(
+            "[walrus] fetching checkpoint {} for digest {}",
+            checkpoint, digest
+        );
+    }
+    let checkpoint_data = WalrusClient::mainnet()
+        .get_checkpoint(checkpoint)
+        .context("Failed to fetch checkpoint from Walrus")?;
+    let state = checkpoint_to_replay_state(&checkpoint_data, digest)
+        .context("Failed to convert checkpoint to replay state")?;
+    Ok((state, "walrus".to_string()))
+}
+
+fn build_mm2_report(
+    state: &ReplayState,
+    rpc_url: &str,
+    verbose: bool,
+) -> Result<serde_json::Value> {
+    use sui_sandbox_core::replay_support;
+
+    let pkg_aliases = build_aliases(&state.packages, None, state.checkpoint);
+    let mut resolver = replay_support::hydrate_resolver_from_replay_state(
+        state,
+        &pkg_aliases.linkage_upgrades,
+        &pkg_aliases.aliases,
+    )?;
+    let graphql = GraphQLClient::new(&resolve_graphql_endpoint(rpc_url));
+    let dependency_packages_fetched = replay_support::fetch_dependency_closure(
+        &mut resolver,
+        &graphql,
+        state.checkpoint,
+        verbose,
+    )
+    .unwrap_or(0);
+    let modules: Vec<CompiledModule> = resolver.iter_modules().cloned().collect();
+    let modules_loaded = modules.len();
+    let (mm2_model_ok, mm2_error) = build_mm2_summary_from_modules(modules, verbose);
+    Ok(serde_json::json!({
+        "mm2_model_ok": mm2_model_ok,
+        "mm2_error": mm2_error,
+        "modules_loaded": modules_loaded,
+        "dependency_packages_fetched": dependency_packages_fetched,
+    }))
+}
+
+#[pymethods]
+impl AnalyzedTransaction {
+    /// Hydrate from a replay state file, or from `digest` + `checkpoint` via Walrus.
+    #[new]
+    #[pyo3(signature = (
+        digest=None,
+        *,
+        checkpoint=None,
+        state_file=None,
+        rpc_url="https://fullnode.mainnet.sui.io:443",
+        verbose=false,
+    ))]
+    fn new(
+        py: Python<'_>,
+        digest: Option<&str>,
+        checkpoint: Option<u64>,
+        state_file: Option<&str>,
+        rpc_url: &str,
+        verbose: bool,
+    ) -> PyResult<Self> {
+        let digest_owned = digest.map(ToOwned::to_owned);
+        let state_file_owned = state_file.map(ToOwned::to_owned);
+        let (state, source) = py
+            .allow_threads(move || {
+                load_analyzed_state(
+                    "AnalyzedTransaction",
+                    digest_owned.as_deref(),
+                    checkpoint,
+                    state_file_owned.as_deref(),
+                    verbose,
+                )
+            })
+            .map_err(to_py_err)?;
+        Ok(Self {
+            state,
+            source,
+            rpc_url: rpc_url.to_string(),
+            verbose,
+            mm2: None,
+        })
+    }
+
+    fn digest(&self) -> String {
+        self.state.transaction.digest.0.clone()
+    }
+
+    fn checkpoint(&self) -> Option<u64> {
+        self.state.checkpoint
+    }
+
+    /// Hydration summary (same shape as `analyze_replay` without VM execution).
+    fn summary(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let value = build_analyze_output(
+            &self.state,
+            &self.source,
+            false,
+            true,
+            false,
+            0,
+            0,
+            Some(&GraphQLClient::new(&resolve_graphql_endpoint(
+                &self.rpc_url,
+            ))),
+            self.verbose,
+        )
+        .map_err(to_py_err)?;
+        json_value_to_py(py, &value)
+    }
+
+    /// Per-input rows: kind, object id/version, and whether the object is hydrated.
+    fn inputs(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let value = serde_json::Value::Array(core_describe_replay_inputs(&self.state));
+        json_value_to_py(py, &value)
+    }
+
+    /// Per-command rows: kind, call target, type args, and argument references.
+    fn commands(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let value = serde_json::Value::Array(core_describe_replay_commands(&self.state));
+        json_value_to_py(py, &value)
+    }
+
+    /// PTB dataflow edges plus unused inputs/results.
+    fn dataflow(&self, py: Python<'_>) -> PyResult<PyObject> {
+        json_value_to_py(py, &core_build_replay_dataflow(&self.state))
+    }
+
+    /// Which commands/inputs the replay VM path supports, with a `replayable` verdict.
+    fn feasibility(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let feasibility = core_assess_replay_feasibility(&self.state.transaction);
+        let value = serde_json::to_value(feasibility).map_err(|e| {
+            PyRuntimeError::new_err(format!("Failed to serialize replay feasibility: {}", e))
+        })?;
+        json_value_to_py(py, &value)
+    }
+
+    /// Missing input objects/packages and hydration suggestions.
+    fn missing(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let value = build_replay_diagnostics_py(&self.state).unwrap_or_else(|| {
+            serde_json::json!({
+                "missing_input_objects": [],
+                "missing_packages": [],
+                "suggestions": [],
+            })
+        });
+        json_value_to_py(py, &value)
+    }
+
+    /// MM2 type-model check over the package closure.
+    ///
+    /// Dependency packages are fetched via GraphQL on first call; the result is cached.
+    fn mm2(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        if self.mm2.is_none() {
+            let state = self.state.clone();
+            let rpc_url = self.rpc_url.clone();
+            let verbose = self.verbose;
+            let report = py
+                .allow_threads(move || build_mm2_report(&state, &rpc_url, verbose))
+                .map_err(to_py_err)?;
+            self.mm2 = Some(report);
+        }
+        json_value_to_py(py, self.mm2.as_ref().expect("mm2 report cached"))
+    }
+
+    /// Write the cached replay state to JSON so it can be reloaded via `state_file`.
+    fn save_state(&self, path: &str) -> PyResult<()> {
+        let serialized = serde_json::to_string_pretty(&self.state).map_err(|e| {
+            PyRuntimeError::new_err(format!("Failed to serialize replay state: {}", e))
+        })?;
+        std::fs::write(path, serialized).map_err(|e| {
+            PyRuntimeError::new_err(format!("Failed to write replay state {}: {}", path, e))
+        })?;
+        Ok(())
+    }
+}
+
+/// Check whether a transaction is replayable before running it.
+///
+/// Inspects the transaction's command and input kinds against the replay
+/// capability registry (see `replay_capabilities()`); nothing is executed.
+///
+/// Args:
+///     digest: Transaction digest (optional when state_file contains a single transaction)
+///     checkpoint: Walrus checkpoint containing the transaction (required with digest)
+///     state_file: Replay-state JSON to read the transaction from instead of Walrus
+///     verbose: Enable verbose logging to stderr
+///
+/// Returns: dict with `replayable`, `support` ("supported"|"partial"|"unsupported"),
+/// per-entry `commands`/`inputs` verdicts, and `reasons` for anything not fully supported.
+#[pyfunction]
+#[pyo3(signature = (
+    digest=None,
+    *,
+    checkpoint=None,
+    state_file=None,
+    verbose=false,
+))]
+pub(super) fn replay_feasibility(
+    py: Python<'_>,
+    digest: Option<&str>,
+    checkpoint: Option<u64>,
+    state_file: Option<&str>,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    let digest_owned = digest.map(ToOwned::to_owned);
+    let state_file_owned = state_file.map(ToOwned::to_owned);
+    let value = py
+        .allow_threads(move || {
+            let (state, _) = load_analyzed_state(
+                "replay_feasibility",
+                digest_owned.as_deref(),
+                checkpoint,
+                state_file_owned.as_deref(),
+                verbose,
+            )?;
+            serde_json::to_value(core_assess_replay_feasibility(&state.transaction))
+                .context("Failed to serialize replay feasibility")
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// PTB command and input kinds with their replay support level.
+///
+/// Returns: dict with `commands` and `inputs`, each a list of
+/// `{kind, support, note}` entries.
+#[pyfunction]
+pub(super) fn replay_capabilities(py: Python<'_>) -> PyResult<PyObject> {
+    let value = serde_json::json!({
+        "commands": CORE_REPLAY_COMMAND_CAPABILITIES,
+        "inputs": CORE_REPLAY_INPUT_CAPABILITIES,
+    });
+    json_value_to_py(py, &value)
+}
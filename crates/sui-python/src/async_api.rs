@@ -0,0 +1,534 @@
+//! asyncio variants of the network-bound entry points.
+//!
+//! Each `*_async` function has the signature of its blocking counterpart and
+//! returns an `asyncio.Future` bound to the running event loop. The blocking call
+//! runs on a small shared pool of worker threads (it releases the GIL while
+//! fetching and executing), and its result or exception is handed back to the
+//! loop with `call_soon_threadsafe`, so calls can be awaited concurrently with
+//! `asyncio.gather` without managing an executor. At most
+//! `SUI_SANDBOX_ASYNC_WORKERS` calls run at once (default: the available
+//! parallelism, between 4 and 16); the rest wait in submission order.
+
+use super::*;
+use pyo3::types::PyCFunction;
+use std::sync::{mpsc, Mutex, OnceLock};
+
+type AsyncJob = Box<dyn FnOnce() + Send>;
+
+/// The worker pool shared by every `*_async` call.
+struct AsyncExecutor {
+    jobs: Mutex<mpsc::Sender<AsyncJob>>,
+}
+
+impl AsyncExecutor {
+    fn start(workers: usize) -> Result<Self> {
+        let (jobs, queue) = mpsc::channel::<AsyncJob>();
+        let queue = Arc::new(Mutex::new(queue));
+        for index in 0..workers {
+            let queue = Arc::clone(&queue);
+            std::thread::Builder::new()
+                .name(format!("sui-sandbox-async-{}", index))
+                .spawn(move || loop {
+                    let job = match queue.lock() {
+                        Ok(queue) => queue.recv(),
+                        Err(_) => return,
+                    };
+                    match job {
+                        Ok(job) => job(),
+                        Err(_) => return,
+                    }
+                })
+                .context("failed to start async worker thread")?;
+        }
+        Ok(Self {
+            jobs: Mutex::new(jobs),
+        })
+    }
+
+    fn submit(&self, job: AsyncJob) -> Result<()> {
+        self.jobs
+            .lock()
+            .map_err(|_| anyhow!("async executor lock poisoned"))?
+            .send(job)
+            .map_err(|_| anyhow!("async executor has shut down"))
+    }
+}
+
+/// Worker count from `SUI_SANDBOX_ASYNC_WORKERS`, else the available parallelism
+/// clamped to 4..=16 (the calls are mostly network-bound).
+fn async_worker_count() -> usize {
+    std::env::var("SUI_SANDBOX_ASYNC_WORKERS")
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|workers| *workers > 0)
+        .unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(usize::from)
+                .unwrap_or(4)
+                .clamp(4, 16)
+        })
+}
+
+fn async_executor() -> PyResult<&'static AsyncExecutor> {
+    static EXECUTOR: OnceLock<std::result::Result<AsyncExecutor, String>> = OnceLock::new();
+    EXECUTOR
+        .get_or_init(|| AsyncExecutor::start(async_worker_count()).map_err(|e| format!("{:#}", e)))
+        .as_ref()
+        .map_err(|e| PyRuntimeError::new_err(e.clone()))
+}
+
+/// Collect the named arguments into the kwargs forwarded to the blocking call.
+macro_rules! forwarded_kwargs {
+    ($py:expr, $($name:ident),+ $(,)?) => {{
+        let kwargs = PyDict::new($py);
+        $(kwargs.set_item(stringify!($name), $name)?;)+
+        kwargs
+    }};
+}
+
+/// Queue `function(**kwargs)` on the shared workers and return a future on the
+/// running event loop that resolves to its result.
+fn spawn_on_event_loop(
+    py: Python<'_>,
+    function: Bound<'_, PyCFunction>,
+    kwargs: Bound<'_, PyDict>,
+) -> PyResult<PyObject> {
+    // Raises RuntimeError("no running event loop") when called outside a coroutine.
+    let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+    let future = event_loop.call_method0("create_future")?;
+
+    let function = function.unbind();
+    let kwargs = kwargs.unbind();
+    let event_loop = event_loop.unbind();
+    let result_future = future.clone().unbind();
+    async_executor()?
+        .submit(Box::new(move || {
+            Python::with_gil(|py| {
+                let outcome = function
+                    .bind(py)
+                    .call((), Some(kwargs.bind(py)))
+                    .map(Bound::unbind);
+                let delivered = deliver_outcome(py, event_loop.bind(py), result_future, outcome);
+                if let Err(err) = delivered {
+                    // The loop was closed before the call finished; nobody is awaiting.
+                    err.write_unraisable(py, None);
+                }
+            })
+        }))
+        .map_err(to_py_err)?;
+
+    Ok(future.unbind())
+}
+
+/// Schedule `future.set_result` / `future.set_exception` on the loop's own thread,
+/// skipping futures that were cancelled while the call ran.
+fn deliver_outcome(
+    py: Python<'_>,
+    event_loop: &Bound<'_, PyAny>,
+    future: PyObject,
+    outcome: PyResult<PyObject>,
+) -> PyResult<()> {
+    let callback = PyCFunction::new_closure(py, None, None, move |args, _kwargs| {
+        let py = args.py();
+        let future = future.bind(py);
+        if future.call_method0("done")?.is_truthy()? {
+            return Ok(());
+        }
+        match &outcome {
+            Ok(value) => future.call_method1("set_result", (value.clone_ref(py),))?,
+            Err(err) => {
+                future.call_method1("set_exception", (err.clone_ref(py).into_value(py),))?
+            }
+        };
+        PyResult::Ok(())
+    })?;
+    event_loop.call_method1("call_soon_threadsafe", (callback,))?;
+    Ok(())
+}
+
+/// Async variant of `replay`; takes the same arguments.
+///
+/// Returns: Awaitable resolving to the `replay` result dict.
+#[pyfunction]
+#[pyo3(signature = (
+    digest=None,
+    *,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+    network=None,
+    source="hybrid",
+    checkpoint=None,
+    state_file=None,
+    state_format=None,
+    context_path=None,
+    context_overrides=false,
+    cache_dir=None,
+    profile=None,
+    fetch_strategy=None,
+    vm_only=false,
+    allow_fallback=true,
+    prefetch_depth=3,
+    prefetch_limit=200,
+    auto_system_objects=true,
+    no_prefetch=false,
+    compare=false,
+    analyze_only=false,
+    synthesize_missing=false,
+    self_heal_dynamic_fields=false,
+    analyze_mm2=false,
+    fetch_modules_only=false,
+    object_type_overrides=None,
+    epoch_override=None,
+    protocol_version=None,
+    package_overrides=None,
+    ownership_hints=None,
+    expect_effects_hash=None,
+    strict_object_digests=false,
+    require_comparison=false,
+    expected_effects=None,
+    stub_unknown_natives=false,
+    gas_table=None,
+    use_result_cache=false,
+    result_cache_dir=None,
+    offline=false,
+    apply_checkpoint_prologue=false,
+    instruction_trace=None,
+    instruction_trace_stack=false,
+    decode_events=false,
+    diff_objects=false,
+    gas_profile=false,
+    trace=false,
+    trace_depth=None,
+    reconcile=false,
+    record_requests=None,
+    summary_only=false,
+    verbose=false,
+))]
+pub(super) fn replay_async(
+    py: Python<'_>,
+    digest: Option<&str>,
+    rpc_url: &str,
+    network: Option<&str>,
+    source: &str,
+    checkpoint: Option<u64>,
+    state_file: Option<&str>,
+    state_format: Option<&str>,
+    context_path: Option<&str>,
+    context_overrides: bool,
+    cache_dir: Option<&str>,
+    profile: Option<&str>,
+    fetch_strategy: Option<&str>,
+    vm_only: bool,
+    allow_fallback: bool,
+    prefetch_depth: usize,
+    prefetch_limit: usize,
+    auto_system_objects: bool,
+    no_prefetch: bool,
+    compare: bool,
+    analyze_only: bool,
+    synthesize_missing: bool,
+    self_heal_dynamic_fields: bool,
+    analyze_mm2: bool,
+    fetch_modules_only: bool,
+    object_type_overrides: Option<HashMap<String, Option<Vec<u8>>>>,
+    epoch_override: Option<u64>,
+    protocol_version: Option<u64>,
+    package_overrides: Option<HashMap<String, Vec<Vec<u8>>>>,
+    ownership_hints: Option<HashMap<String, String>>,
+    expect_effects_hash: Option<String>,
+    strict_object_digests: bool,
+    require_comparison: bool,
+    expected_effects: Option<&Bound<'_, PyAny>>,
+    stub_unknown_natives: bool,
+    gas_table: Option<&Bound<'_, PyAny>>,
+    use_result_cache: bool,
+    result_cache_dir: Option<&str>,
+    offline: bool,
+    apply_checkpoint_prologue: bool,
+    instruction_trace: Option<&str>,
+    instruction_trace_stack: bool,
+    decode_events: bool,
+    diff_objects: bool,
+    gas_profile: bool,
+    trace: bool,
+    trace_depth: Option<usize>,
+    reconcile: bool,
+    record_requests: Option<&str>,
+    summary_only: bool,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    let kwargs = forwarded_kwargs!(
+        py,
+        digest,
+        rpc_url,
+        network,
+        source,
+        checkpoint,
+        state_file,
+        state_format,
+        context_path,
+        context_overrides,
+        cache_dir,
+        profile,
+        fetch_strategy,
+        vm_only,
+        allow_fallback,
+        prefetch_depth,
+        prefetch_limit,
+        auto_system_objects,
+        no_prefetch,
+        compare,
+        analyze_only,
+        synthesize_missing,
+        self_heal_dynamic_fields,
+        analyze_mm2,
+        fetch_modules_only,
+        object_type_overrides,
+        epoch_override,
+        protocol_version,
+        package_overrides,
+        ownership_hints,
+        expect_effects_hash,
+        strict_object_digests,
+        require_comparison,
+        expected_effects,
+        stub_unknown_natives,
+        gas_table,
+        use_result_cache,
+        result_cache_dir,
+        offline,
+        apply_checkpoint_prologue,
+        instruction_trace,
+        instruction_trace_stack,
+        decode_events,
+        diff_objects,
+        gas_profile,
+        trace,
+        trace_depth,
+        reconcile,
+        record_requests,
+        summary_only,
+        verbose
+    );
+    spawn_on_event_loop(py, wrap_pyfunction!(replay, py)?, kwargs)
+}
+
+/// Async variant of `replay_many`; takes the same arguments.
+///
+/// Returns: Awaitable resolving to the `replay_many` report dict.
+#[pyfunction]
+#[pyo3(signature = (
+    digests,
+    *,
+    max_concurrency=4,
+    profile=None,
+    source="hybrid",
+    cache_dir=None,
+    allow_fallback=true,
+    prefetch_depth=3,
+    prefetch_limit=200,
+    auto_system_objects=true,
+    compare=false,
+    include_outputs=false,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+))]
+pub(super) fn replay_many_async(
+    py: Python<'_>,
+    digests: Vec<String>,
+    max_concurrency: usize,
+    profile: Option<&str>,
+    source: &str,
+    cache_dir: Option<&str>,
+    allow_fallback: bool,
+    prefetch_depth: usize,
+    prefetch_limit: usize,
+    auto_system_objects: bool,
+    compare: bool,
+    include_outputs: bool,
+    rpc_url: &str,
+) -> PyResult<PyObject> {
+    let kwargs = forwarded_kwargs!(
+        py,
+        digests,
+        max_concurrency,
+        profile,
+        source,
+        cache_dir,
+        allow_fallback,
+        prefetch_depth,
+        prefetch_limit,
+        auto_system_objects,
+        compare,
+        include_outputs,
+        rpc_url
+    );
+    spawn_on_event_loop(py, wrap_pyfunction!(replay_many, py)?, kwargs)
+}
+
+/// Async variant of `fetch_object_bcs`; takes the same arguments.
+///
+/// Returns: Awaitable resolving to the `fetch_object_bcs` result dict.
+#[pyfunction]
+#[pyo3(signature = (
+    object_id,
+    *,
+    version=None,
+    endpoint=None,
+    api_key=None,
+))]
+pub(super) fn fetch_object_bcs_async(
+    py: Python<'_>,
+    object_id: &str,
+    version: Option<u64>,
+    endpoint: Option<&str>,
+    api_key: Option<&str>,
+) -> PyResult<PyObject> {
+    let kwargs = forwarded_kwargs!(py, object_id, version, endpoint, api_key);
+    spawn_on_event_loop(py, wrap_pyfunction!(fetch_object_bcs, py)?, kwargs)
+}
+
+/// Async variant of `fetch_historical_package_bytecodes`; takes the same arguments.
+///
+/// Returns: Awaitable resolving to the `fetch_historical_package_bytecodes` payload.
+#[pyfunction]
+#[pyo3(signature = (
+    package_ids,
+    *,
+    type_refs=vec![],
+    checkpoint=None,
+    endpoint=None,
+    api_key=None,
+))]
+pub(super) fn fetch_historical_package_bytecodes_async(
+    py: Python<'_>,
+    package_ids: Vec<String>,
+    type_refs: Vec<String>,
+    checkpoint: Option<u64>,
+    endpoint: Option<&str>,
+    api_key: Option<&str>,
+) -> PyResult<PyObject> {
+    let kwargs = forwarded_kwargs!(py, package_ids, type_refs, checkpoint, endpoint, api_key);
+    spawn_on_event_loop(
+        py,
+        wrap_pyfunction!(fetch_historical_package_bytecodes, py)?,
+        kwargs,
+    )
+}
+
+/// Async variant of `call_view_function`; takes the same arguments.
+///
+/// Returns: Awaitable resolving to the `call_view_function` result dict.
+#[pyfunction]
+#[pyo3(signature = (
+    package_id,
+    module,
+    function,
+    *,
+    type_args=vec![],
+    object_inputs=vec![],
+    pure_inputs=vec![],
+    child_objects=None,
+    historical_versions=None,
+    fetch_child_objects=false,
+    child_fetcher=None,
+    grpc_endpoint=None,
+    grpc_api_key=None,
+    package_bytecodes=None,
+    fetch_deps=true,
+    fetch_modules_only=false,
+    decode_returns=false,
+))]
+pub(super) fn call_view_function_async(
+    py: Python<'_>,
+    package_id: &str,
+    module: &str,
+    function: &str,
+    type_args: Vec<String>,
+    object_inputs: Vec<Bound<'_, PyDict>>,
+    pure_inputs: Vec<Vec<u8>>,
+    child_objects: Option<Bound<'_, PyDict>>,
+    historical_versions: Option<Bound<'_, PyDict>>,
+    fetch_child_objects: bool,
+    child_fetcher: Option<PyObject>,
+    grpc_endpoint: Option<&str>,
+    grpc_api_key: Option<&str>,
+    package_bytecodes: Option<Bound<'_, PyDict>>,
+    fetch_deps: bool,
+    fetch_modules_only: bool,
+    decode_returns: bool,
+) -> PyResult<PyObject> {
+    let kwargs = forwarded_kwargs!(
+        py,
+        package_id,
+        module,
+        function,
+        type_args,
+        object_inputs,
+        pure_inputs,
+        child_objects,
+        historical_versions,
+        fetch_child_objects,
+        child_fetcher,
+        grpc_endpoint,
+        grpc_api_key,
+        package_bytecodes,
+        fetch_deps,
+        fetch_modules_only,
+        decode_returns
+    );
+    spawn_on_event_loop(py, wrap_pyfunction!(call_view_function, py)?, kwargs)
+}
+
+/// Async variant of `call_view_functions`; takes the same arguments.
+///
+/// Returns: Awaitable resolving to the `call_view_functions` result list.
+#[pyfunction]
+#[pyo3(signature = (
+    package_id,
+    calls,
+    *,
+    shared_object_inputs=vec![],
+    child_objects=None,
+    historical_versions=None,
+    fetch_child_objects=false,
+    child_fetcher=None,
+    grpc_endpoint=None,
+    grpc_api_key=None,
+    package_bytecodes=None,
+    fetch_deps=true,
+    fetch_modules_only=false,
+    decode_returns=false,
+))]
+pub(super) fn call_view_functions_async(
+    py: Python<'_>,
+    package_id: &str,
+    calls: Vec<Bound<'_, PyDict>>,
+    shared_object_inputs: Vec<Bound<'_, PyDict>>,
+    child_objects: Option<Bound<'_, PyDict>>,
+    historical_versions: Option<Bound<'_, PyDict>>,
+    fetch_child_objects: bool,
+    child_fetcher: Option<PyObject>,
+    grpc_endpoint: Option<&str>,
+    grpc_api_key: Option<&str>,
+    package_bytecodes: Option<Bound<'_, PyDict>>,
+    fetch_deps: bool,
+    fetch_modules_only: bool,
+    decode_returns: bool,
+) -> PyResult<PyObject> {
+    let kwargs = forwarded_kwargs!(
+        py,
+        package_id,
+        calls,
+        shared_object_inputs,
+        child_objects,
+        historical_versions,
+        fetch_child_objects,
+        child_fetcher,
+        grpc_endpoint,
+        grpc_api_key,
+        package_bytecodes,
+        fetch_deps,
+        fetch_modules_only,
+        decode_returns
+    );
+    spawn_on_event_loop(py, wrap_pyfunction!(call_view_functions, py)?, kwargs)
+}
@@ -0,0 +1,228 @@
+use super::*;
+
+use sui_sandbox_core::fork::ForkSession as CoreForkSession;
+use sui_sandbox_types::{PtbCommand, TransactionInput};
+
+/// Local fork of historical state that keeps its mutations across PTBs.
+///
+/// Hydrates the packages and objects of a transaction's replay state once, then
+/// executes synthetic PTBs on top of it; each `execute_ptb()` sees the objects as
+/// the previous PTBs left them.
+#[pyclass(name = "ForkSession", module = "sui_sandbox")]
+pub(super) struct ForkSession {
+    pub(super) fork: CoreForkSession,
+}
+
+fn parse_ptb_json<T: serde::de::DeserializeOwned>(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+    what: &str,
+) -> Result<T> {
+    let json = match value.extract::<String>() {
+        Ok(text) => {
+            serde_json::from_str(&text).with_context(|| format!("{} is not valid JSON", what))?
+        }
+        Err(_) => py_json_value(py, value)?,
+    };
+    serde_json::from_value(json)
+        .with_context(|| format!("{} does not match the replay-state schema", what))
+}
+
+pub(super) fn parse_fork_address(value: &str) -> Result<AccountAddress> {
+    AccountAddress::from_hex_literal(value)
+        .map_err(|e| anyhow!("invalid address '{}': {}", value, e))
+}
+
+/// Object ID referenced by a state-file transaction input (None for pure inputs).
+pub(super) fn input_object_id(input: &TransactionInput) -> Option<&str> {
+    match input {
+        TransactionInput::Pure { .. } => None,
+        TransactionInput::Object { object_id, .. }
+        | TransactionInput::SharedObject { object_id, .. }
+        | TransactionInput::ImmutableObject { object_id, .. }
+        | TransactionInput::Receiving { object_id, .. } => Some(object_id),
+    }
+}
+
+/// Run one PTB on `fork` (as `sender` if given) and return its `ForkExecution` as a dict.
+pub(super) fn execute_on_fork(
+    py: Python<'_>,
+    fork: &mut CoreForkSession,
+    inputs: Vec<TransactionInput>,
+    commands: Vec<PtbCommand>,
+    sender: Option<AccountAddress>,
+) -> PyResult<PyObject> {
+    let value = py
+        .allow_threads(move || {
+            let previous = fork.sender();
+            if let Some(sender) = sender {
+                fork.set_sender(sender);
+            }
+            let execution = fork.execute_ptb(&inputs, &commands);
+            fork.set_sender(previous);
+            serde_json::to_value(execution?).context("Failed to serialize fork execution")
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+#[pymethods]
+impl ForkSession {
+    /// Fork from a replay state file, or from `digest` + `checkpoint` via Walrus.
+    #[new]
+    #[pyo3(signature = (
+        digest=None,
+        *,
+        checkpoint=None,
+        state_file=None,
+        verbose=false,
+    ))]
+    fn new(
+        py: Python<'_>,
+        digest: Option<&str>,
+        checkpoint: Option<u64>,
+        state_file: Option<&str>,
+        verbose: bool,
+    ) -> PyResult<Self> {
+        let digest_owned = digest.map(ToOwned::to_owned);
+        let state_file_owned = state_file.map(ToOwned::to_owned);
+        let fork = py
+            .allow_threads(move || {
+                let (state, _) = load_analyzed_state(
+                    "ForkSession",
+                    digest_owned.as_deref(),
+                    checkpoint,
+                    state_file_owned.as_deref(),
+                    verbose,
+                )?;
+                CoreForkSession::from_replay_state(&state)
+            })
+            .map_err(to_py_err)?;
+        Ok(Self { fork })
+    }
+
+    /// Where the fork was taken: `digest`, `checkpoint`, `epoch`, `protocol_version`,
+    /// `timestamp_ms`, and the number of hydrated `objects` and `packages`.
+    fn origin(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let value = serde_json::to_value(self.fork.origin())
+            .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize origin: {}", e)))?;
+        json_value_to_py(py, &value)
+    }
+
+    /// Number of PTBs executed so far (failed ones included).
+    fn executed(&self) -> u64 {
+        self.fork.executed()
+    }
+
+    fn sender(&self) -> String {
+        self.fork.sender().to_hex_literal()
+    }
+
+    /// Sender of subsequent PTBs (default: the origin transaction's sender).
+    fn set_sender(&mut self, sender: &str) -> PyResult<()> {
+        let sender = parse_fork_address(sender).map_err(to_py_err)?;
+        self.fork.set_sender(sender);
+        Ok(())
+    }
+
+    /// Execute a PTB against the fork's current state.
+    ///
+    /// `inputs` and `commands` are lists (or JSON strings) in the state-file
+    /// `transaction.inputs` / `transaction.commands` schema. Object inputs are
+    /// resolved by ID from the fork, so their `version`/`digest` may be stale.
+    ///
+    /// Returns: dict with `index`, `success`, `error`, `failed_command_index`,
+    /// `gas_used` (plus a `gas_summary` cost breakdown when available),
+    /// `created`/`mutated`/`deleted`/`wrapped`/`unwrapped` ids, event
+    /// types under `events`, and the new `versions` of created and mutated objects
+    #[pyo3(signature = (inputs, commands, *, sender=None))]
+    fn execute_ptb(
+        &mut self,
+        py: Python<'_>,
+        inputs: &Bound<'_, PyAny>,
+        commands: &Bound<'_, PyAny>,
+        sender: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let inputs: Vec<TransactionInput> =
+            parse_ptb_json(py, inputs, "inputs").map_err(to_py_err)?;
+        let commands: Vec<PtbCommand> =
+            parse_ptb_json(py, commands, "commands").map_err(to_py_err)?;
+        let sender = sender
+            .map(parse_fork_address)
+            .transpose()
+            .map_err(to_py_err)?;
+        execute_on_fork(py, &mut self.fork, inputs, commands, sender)
+    }
+
+    /// Current state of an object (`object_id`, `version`, `type_tag`, `bcs_base64`,
+    /// `is_shared`, `is_immutable`), or None if the fork doesn't hold it.
+    fn get_object(&self, py: Python<'_>, object_id: &str) -> PyResult<PyObject> {
+        let id = parse_fork_address(object_id).map_err(to_py_err)?;
+        let Some(object) = self.fork.get_object(&id) else {
+            return Ok(py.None());
+        };
+        let value = serde_json::json!({
+            "object_id": object.id.to_hex_literal(),
+            "version": object.version,
+            "type_tag": object.type_tag.to_canonical_string(true),
+            "bcs_base64": base64::engine::general_purpose::STANDARD.encode(&object.bcs_bytes),
+            "is_shared": object.is_shared,
+            "is_immutable": object.is_immutable,
+        });
+        json_value_to_py(py, &value)
+    }
+
+    /// Insert or overwrite an object in the fork.
+    ///
+    /// `type_tag` and `version` default to the existing object's; a new object
+    /// requires `type_tag` and starts at version 1.
+    #[pyo3(signature = (
+        object_id,
+        bcs_bytes,
+        *,
+        type_tag=None,
+        version=None,
+        is_shared=false,
+        is_immutable=false,
+    ))]
+    fn set_object(
+        &mut self,
+        object_id: &str,
+        bcs_bytes: Vec<u8>,
+        type_tag: Option<&str>,
+        version: Option<u64>,
+        is_shared: bool,
+        is_immutable: bool,
+    ) -> PyResult<()> {
+        let id = parse_fork_address(object_id).map_err(to_py_err)?;
+        let existing = self.fork.get_object(&id);
+        let type_tag = match type_tag {
+            Some(type_str) => {
+                sui_sandbox_core::simulation::SimulationEnvironment::parse_type_string(type_str)
+                    .ok_or_else(|| {
+                        PyRuntimeError::new_err(format!("invalid type tag '{}'", type_str))
+                    })?
+            }
+            None => existing
+                .map(|object| object.type_tag.clone())
+                .ok_or_else(|| {
+                    PyRuntimeError::new_err(format!(
+                        "object {} is not in the fork; provide type_tag",
+                        object_id
+                    ))
+                })?,
+        };
+        let version = version
+            .or_else(|| existing.map(|object| object.version))
+            .unwrap_or(1);
+        self.fork.env_mut().add_object_with_version_and_status(
+            id,
+            bcs_bytes,
+            type_tag,
+            version,
+            is_shared,
+            is_immutable,
+        );
+        Ok(())
+    }
+}
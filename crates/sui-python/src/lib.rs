@@ -3,16 +3,21 @@
 //!
 //! **All functions are standalone** — `pip install sui-sandbox` is all you need:
 //! - `extract_interface`: Extract full Move package interface from bytecode or GraphQL
+//! - `extract_call_targets`: Static per-function Move call targets of a package
+//! - `package_types`: Structs/enums of a package with abilities and type parameters
 //! - `get_latest_checkpoint`: Get latest Walrus checkpoint number
 //! - `get_checkpoint`: Fetch and summarize a Walrus checkpoint
+//! - `subscribe_checkpoints`: Tail live checkpoints via gRPC with reconnect and resume
 //! - `doctor`: Run endpoint/environment preflight checks
 //! - `session_status` / `session_reset` / `session_clean`: CLI-parity session lifecycle APIs
 //! - `snapshot_save` / `snapshot_load` / `snapshot_list` / `snapshot_delete`: Snapshot lifecycle APIs
 //! - `ptb_universe`: Run checkpoint-source PTB universe generation/execution
 //! - `discover_checkpoint_targets`: Discover digest/package Move-call targets from checkpoints
 //! - `fetch_object_bcs`: Fetch object BCS (optionally at historical version) via gRPC
+//! - `list_dynamic_fields`: List (and decode) a parent object's dynamic fields via GraphQL
 //! - `fetch_historical_package_bytecodes`: Fetch checkpoint-pinned package bytecodes via gRPC
 //! - `fetch_package_bytecodes`: Fetch package bytecodes via GraphQL
+//! - `stream_package_modules`: Iterate a package's modules page by page via GraphQL
 //! - `context_prepare` / `prepare_package_context`: Fetch package closure for two-step replay flows
 //! - `context_run` / `adapter_run` / `protocol_run`: First-class replay orchestration wrappers
 //! - `context_discover` / `adapter_discover` / `protocol_discover`: Replay target discovery helpers
@@ -22,59 +27,77 @@
 //! - `pipeline_run` / `workflow_run`: Execute typed specs natively from Python
 //! - `pipeline_run_inline` / `workflow_run_inline`: Execute typed specs from in-memory Python objects
 //! - `OrchestrationSession`: In-memory prepared context + replay helper for interactive workflows
+//! - `AnalyzedTransaction`: Hydrate a replay state once and query inputs/commands/dataflow/MM2 lazily
+//! - `parse_type` / `canonicalize_type`: Validate and canonicalize Move type strings
 //! - `json_to_bcs`: Convert Sui object JSON to BCS bytes
 //! - `transaction_json_to_bcs`: Convert Snowflake/canonical TransactionData JSON to BCS bytes
+//! - `transaction_json_to_bcs_verbose`: Same, with a report of the normalizations applied
+//! - `simulate_transaction`: Dry-run unsigned TransactionData against hydrated state
 //! - `call_view_function`: Execute a Move view function in the local VM
+//! - `call_view_functions`: Execute several view functions over one shared object set
+//! - `build_call_inputs`: Build `call_view_function` inputs from JSON args via the function ABI
 //! - `historical_view_from_versions`: Generic historical view execution from versions snapshots
 //! - `historical_series_from_points`: Execute historical view requests across checkpoint/version series
 //! - `historical_decode_returns_typed`: Decode historical command return values by type tags
 //! - `historical_decode_with_schema`: Decode historical command return values via named schema
 //! - `fuzz_function`: Fuzz a Move function with random inputs
 //! - `replay`: Replay historical transactions (with optional analysis-only mode)
+//! - `replay_from_cassette`: Re-run a replay recorded with `record_requests` without network
 //! - `replay_transaction`: Opinionated replay helper with compact signature
 //! - `analyze_replay` / `replay_analyze`: Replay hydration/readiness analysis
 //! - `replay_effects`: Replay execution summary with effects-focused output
 //! - `classify_replay_result`: Structured replay failure classification and hints
+//! - `bench_replay`: Replay throughput/latency benchmark over a fixed digest set
+//! - `replay_many`: Batch replay with bounded concurrency, a shared fetch cache and an aggregated report
+//! - `replay_checkpoint`: Replay a whole Walrus checkpoint in order and reconcile every transaction
 //! - `dynamic_field_diagnostics`: Compare hydration with/without DF prefetch and report gaps
 //! - `import_state`: Import replay data files into local cache
+//! - `cache_stats` / `cache_clear`: Inspect and prune the local replay cache
 //! - `deserialize_transaction`: Decode raw transaction BCS
 //! - `deserialize_package`: Decode raw package BCS
+//! - `compute_package_id`: Predict the package id of newly published bytecode
+//! - `replay_async` / `replay_many_async` / `fetch_object_bcs_async` /
+//!   `fetch_historical_package_bytecodes_async` / `call_view_function_async` /
+//!   `call_view_functions_async`: asyncio variants returning awaitables
 
 #![allow(clippy::too_many_arguments)]
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{anyhow, Context, Result};
 use base64::Engine;
 use move_binary_format::CompiledModule;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::language_storage::{ModuleId, TypeTag};
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use pyo3::types::{PyAny, PyBytes, PyDict};
 
 use sui_package_extractor::bytecode::{
-    build_bytecode_interface_value_from_compiled_modules, read_local_compiled_modules,
+    build_bytecode_interface_value_from_compiled_modules, build_call_target_catalog,
+    build_entrypoint_catalog, build_package_type_catalog, read_local_compiled_modules,
     resolve_local_package_id,
 };
 use sui_package_extractor::extract_module_dependency_ids as extract_dependency_addrs;
 use sui_package_extractor::utils::is_framework_address;
-use sui_sandbox_core::adapter::{
-    resolve_discovery_package_filter as core_resolve_discovery_package_filter,
-    resolve_required_package_id as core_resolve_required_package_id,
-    ProtocolAdapter as CoreProtocolAdapter,
-};
 use sui_sandbox_core::checkpoint_discovery::{
     build_walrus_client as core_build_walrus_client,
+    checkpoint_ptb_targets as core_checkpoint_ptb_targets,
     discover_checkpoint_targets as core_discover_checkpoint_targets,
+    normalize_package_id as core_normalize_package_id,
+    resolve_discovery_checkpoints as core_resolve_discovery_checkpoints,
     resolve_replay_target_from_discovery as core_resolve_replay_target_from_discovery,
     WalrusArchiveNetwork as CoreWalrusArchiveNetwork,
 };
+use sui_sandbox_core::checkpoint_replay::{
+    replay_checkpoint as core_replay_checkpoint,
+    CheckpointReplayOptions as CoreCheckpointReplayOptions,
+};
 use sui_sandbox_core::context_contract::{
     context_packages_from_package_map, decode_context_package_modules, decode_context_packages,
     parse_context_payload, ContextPackage, ContextPayloadV2,
@@ -86,8 +109,11 @@ use sui_sandbox_core::historical_view::{
 };
 use sui_sandbox_core::orchestrator::{
     HistoricalSeriesExecutionOptions as CoreHistoricalSeriesExecutionOptions,
-    HistoricalSeriesPoint as CoreHistoricalSeriesPoint, ReplayOrchestrator, ReturnDecodeField,
+    HistoricalSeriesPoint as CoreHistoricalSeriesPoint,
+    ReplayBatchOptions as CoreReplayBatchOptions, ReplayOrchestrator, ReturnDecodeField,
 };
+use sui_sandbox_core::protocol_decoders::ProtocolDecoderRegistry as CoreProtocolDecoderRegistry;
+use sui_sandbox_core::protocol_registry::ProtocolRegistry as CoreProtocolRegistry;
 use sui_sandbox_core::ptb_universe::{
     run_with_args as core_run_ptb_universe, Args as CorePtbUniverseArgs,
     CheckpointSource as CoreCheckpointSource, DEFAULT_LATEST as CORE_PTB_UNIVERSE_DEFAULT_LATEST,
@@ -96,16 +122,32 @@ use sui_sandbox_core::ptb_universe::{
     DEFAULT_TOP_PACKAGES as CORE_PTB_UNIVERSE_DEFAULT_TOP_PACKAGES,
 };
 use sui_sandbox_core::replay_reporting::{
+    assess_replay_feasibility as core_assess_replay_feasibility,
+    assess_replay_fidelity as core_assess_replay_fidelity,
+    attach_move_call_return_types as core_attach_move_call_return_types,
     build_replay_analysis_summary as core_build_replay_analysis_summary,
+    build_replay_dataflow as core_build_replay_dataflow,
     build_replay_diagnostics as core_build_replay_diagnostics,
     classify_replay_output as core_classify_replay_output,
+    describe_replay_commands as core_describe_replay_commands,
+    describe_replay_inputs as core_describe_replay_inputs,
+    diff_replay_outputs as core_diff_replay_outputs,
     missing_input_objects_from_state as core_missing_input_objects_from_state,
+    probe_missing_input_existence as core_probe_missing_input_existence,
+    summarize_replay_output as core_summarize_replay_output,
     ReplayDiagnosticsOptions as CoreReplayDiagnosticsOptions,
+    ReplayFidelitySignals as CoreReplayFidelitySignals, SynthesisSummary as CoreSynthesisSummary,
+    REPLAY_COMMAND_CAPABILITIES as CORE_REPLAY_COMMAND_CAPABILITIES,
+    REPLAY_INPUT_CAPABILITIES as CORE_REPLAY_INPUT_CAPABILITIES,
 };
 use sui_sandbox_core::resolver::ModuleProvider;
 use sui_sandbox_core::simulation::{
     CoinMetadata, PersistentState, StateMetadata, SUI_COIN_TYPE, SUI_DECIMALS, SUI_SYMBOL,
 };
+use sui_sandbox_core::tx_replay::{
+    local_effects_fingerprint as core_local_effects_fingerprint,
+    onchain_effects_fingerprint as core_onchain_effects_fingerprint,
+};
 use sui_sandbox_core::utilities::unresolved_package_dependencies_for_modules;
 use sui_sandbox_core::vm::SimulationConfig;
 use sui_sandbox_core::workflow::{
@@ -117,35 +159,57 @@ use sui_sandbox_core::workflow_adapter::{
     build_builtin_workflow, BuiltinWorkflowInput, BuiltinWorkflowTemplate,
 };
 use sui_sandbox_core::workflow_runner::{
-    run_prepared_workflow_steps, WorkflowPreparedStep, WorkflowStepExecution,
+    run_prepared_workflow_steps_parallel, WorkflowPreparedStep, WorkflowRunReport,
+    WorkflowStepExecution,
 };
 use sui_state_fetcher::{
-    bcs_codec, build_aliases, checkpoint_to_replay_state, import_replay_states,
-    parse_replay_states_file, FileStateProvider, HistoricalStateProvider, ImportSpec, PackageData,
-    ReplayState,
+    bcs_codec, build_aliases, checkpoint_to_replay_state, clear_replay_cache, export_replay_states,
+    fetch_replay_state_via_jsonrpc, import_replay_states, read_replay_states_file,
+    replay_cache_stats, CacheClearSpec, FetchMemo, FileStateProvider, HistoricalStateProvider,
+    ImportSpec, PackageData, ReplayState, ReplayStateFormat,
 };
 use sui_transport::graphql::GraphQLClient;
-use sui_transport::grpc::{resolve_historical_endpoint_and_api_key, GrpcClient, GrpcOwner};
-use sui_transport::network::resolve_graphql_endpoint;
+use sui_transport::grpc::{
+    global_pool as global_grpc_pool, resolve_historical_endpoint_and_api_key, GrpcOwner,
+};
+use sui_transport::jsonrpc::JsonRpcClient;
+use sui_transport::latency::{LatencyRecorder, LatencySummary};
+use sui_transport::network::{resolve_graphql_endpoint, Network};
 use sui_transport::walrus::WalrusClient;
 
+mod analysis_api;
+mod async_api;
+mod fork_api;
 mod module_registration;
+mod ptb_builder_api;
 mod replay_api;
 mod replay_core;
 mod replay_output;
 mod session_api;
+mod simulate_api;
+mod subscription_api;
 mod transport_helpers;
 mod workflow_api;
 mod workflow_native;
+use analysis_api::{
+    load_analyzed_state, replay_capabilities, replay_feasibility, AnalyzedTransaction,
+};
+use async_api::*;
+use fork_api::ForkSession;
 use module_registration::register_module;
+use ptb_builder_api::PtbBuilder;
 use replay_api::*;
 use replay_core::*;
 use replay_output::{
-    build_analyze_replay_output, build_replay_output, classify_replay_output,
-    deserialize_package_inner, deserialize_transaction_inner, import_state_inner,
-    load_replay_state_from_file,
+    attach_missing_input_existence_py, build_analyze_output, build_analyze_replay_output,
+    build_replay_diagnostics_py, build_replay_output, cache_clear_inner, cache_stats_inner,
+    classify_replay_output, deserialize_package_inner, deserialize_transaction_inner,
+    export_state_inner, import_state_inner, load_replay_state_from_file,
+    load_replay_state_from_file_as,
 };
 use session_api::*;
+use simulate_api::simulate_transaction;
+use subscription_api::{subscribe_checkpoints, CheckpointSubscription};
 use transport_helpers::*;
 use workflow_api::*;
 use workflow_native::*;
@@ -174,6 +238,10 @@ fn default_local_cache_dir() -> PathBuf {
     sandbox_home_dir().join("cache").join("local")
 }
 
+fn default_result_cache_dir() -> PathBuf {
+    sandbox_home_dir().join("cache").join("results")
+}
+
 fn default_state_file_path() -> PathBuf {
     sandbox_home_dir().join("state.json")
 }
@@ -315,6 +383,64 @@ fn decode_package_module_bytes(value: &Bound<'_, pyo3::PyAny>) -> PyResult<Vec<V
     ))
 }
 
+/// Parse a `package_bytecodes` argument: either `{package_id: [module bytes]}` or the
+/// full payload returned by `fetch_historical_package_bytecodes`.
+fn parse_package_bytecodes(
+    package_bytecodes: Option<&Bound<'_, PyDict>>,
+) -> PyResult<PackageBytecodes> {
+    let mut parsed = PackageBytecodes::default();
+    let Some(pb) = package_bytecodes else {
+        return Ok(parsed);
+    };
+    let packages_dict: Bound<'_, PyDict> = if let Some(packages_any) = pb.get_item("packages")? {
+        parsed.historical_payload = true;
+        packages_any.extract()?
+    } else {
+        pb.clone()
+    };
+
+    for (key, value) in packages_dict.iter() {
+        let pkg_id: String = key.extract()?;
+        let bytecodes = decode_package_module_bytes(&value)?;
+        parsed.packages.insert(pkg_id, bytecodes);
+    }
+
+    if let Some(aliases_any) = pb.get_item("aliases")? {
+        parsed.aliases = aliases_any.extract().map_err(|_| {
+            PyRuntimeError::new_err(
+                "package_bytecodes.aliases must be Dict[str, str] (storage -> runtime)",
+            )
+        })?;
+    }
+    if let Some(linkage_any) = pb.get_item("linkage_upgrades")? {
+        parsed.linkage_upgrades = linkage_any.extract().map_err(|_| {
+            PyRuntimeError::new_err(
+                "package_bytecodes.linkage_upgrades must be Dict[str, str] (runtime -> storage)",
+            )
+        })?;
+    }
+    if let Some(runtime_ids_any) = pb.get_item("package_runtime_ids")? {
+        parsed.package_runtime_ids = runtime_ids_any.extract().map_err(|_| {
+            PyRuntimeError::new_err(
+                "package_bytecodes.package_runtime_ids must be Dict[str, str] (storage -> runtime)",
+            )
+        })?;
+    }
+    if let Some(pkg_linkage_any) = pb.get_item("package_linkage")? {
+        parsed.package_linkage = pkg_linkage_any.extract().map_err(|_| {
+            PyRuntimeError::new_err(
+                "package_bytecodes.package_linkage must be Dict[str, Dict[str, str]]",
+            )
+        })?;
+    }
+    if let Some(pkg_versions_any) = pb.get_item("package_versions")? {
+        parsed.package_versions = pkg_versions_any.extract().map_err(|_| {
+            PyRuntimeError::new_err("package_bytecodes.package_versions must be Dict[str, int]")
+        })?;
+    }
+    Ok(parsed)
+}
+
 fn context_packages_to_package_data(
     packages: &[ContextPackage],
 ) -> Result<HashMap<AccountAddress, PackageData>> {
@@ -455,9 +581,10 @@ fn fetch_object_bcs_inner(
     let (grpc_endpoint, grpc_api_key) = resolve_grpc_endpoint_and_key(endpoint, api_key);
     let object_id_owned = object_id.to_string();
 
-    let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-    let object = rt.block_on(async {
-        let grpc = GrpcClient::with_api_key(&grpc_endpoint, grpc_api_key)
+    let pool = global_grpc_pool();
+    let object = pool.block_on(async {
+        let grpc = pool
+            .client(&grpc_endpoint, grpc_api_key)
             .await
             .context("Failed to create gRPC client")?;
         grpc.get_object_at_version(&object_id_owned, version)
@@ -504,6 +631,137 @@ fn fetch_object_bcs_inner(
     }))
 }
 
+fn list_dynamic_fields_inner(
+    parent_id: &str,
+    checkpoint: Option<u64>,
+    limit: Option<usize>,
+    decode: bool,
+    max_depth: usize,
+    endpoint: Option<&str>,
+) -> Result<serde_json::Value> {
+    let graphql_endpoint =
+        resolve_graphql_endpoint(endpoint.unwrap_or("https://fullnode.mainnet.sui.io:443"));
+    let graphql = GraphQLClient::new(&graphql_endpoint);
+    let listing = graphql
+        .list_dynamic_fields_nested(parent_id, checkpoint, limit, max_depth)
+        .with_context(|| format!("Failed to list dynamic fields of {}", parent_id))?;
+
+    let warnings = WarningLog::new(true);
+    let resolver = if decode {
+        let mut type_refs = BTreeSet::new();
+        collect_dynamic_field_types(&listing, &mut type_refs);
+        let type_refs: Vec<String> = type_refs.into_iter().collect();
+        // The framework is preloaded, so only packages named in `type_refs` are fetched.
+        match build_resolver_with_deps_via(&graphql, "0x2", &type_refs, checkpoint, &warnings) {
+            Ok((resolver, _)) => Some(resolver),
+            Err(e) => {
+                warnings.push(
+                    WarningCategory::Decode,
+                    format!("failed to load struct layouts: {:#}", e),
+                );
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut output = dynamic_field_listing_json(&listing, resolver.as_ref());
+    output["parent_id"] = serde_json::json!(parent_id);
+    output["checkpoint"] = serde_json::json!(checkpoint);
+    output["endpoint_used"] = serde_json::json!(graphql_endpoint);
+    warnings.attach(&mut output);
+    Ok(output)
+}
+
+/// Key and value types of every field in `listing` and its nested listings.
+fn collect_dynamic_field_types(
+    listing: &sui_transport::graphql::DynamicFieldListing,
+    out: &mut BTreeSet<String>,
+) {
+    for field in &listing.fields {
+        out.insert(field.name_type.clone());
+        out.extend(field.value_type.clone());
+    }
+    for nested in listing.nested.values() {
+        collect_dynamic_field_types(nested, out);
+    }
+}
+
+/// Decode BCS (base64) of `type_tag` with struct layouts from `resolver`; errors are
+/// kept as strings so one bad field does not fail the listing.
+fn decode_dynamic_field_part(
+    resolver: &sui_sandbox_core::resolver::LocalModuleResolver,
+    type_tag: Option<&str>,
+    bcs_base64: Option<&str>,
+) -> std::result::Result<serde_json::Value, String> {
+    let (Some(type_tag), Some(bcs_base64)) = (type_tag, bcs_base64) else {
+        return Err("missing type or BCS".to_string());
+    };
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(bcs_base64)
+        .map_err(|e| format!("invalid base64: {}", e))?;
+    ReplayOrchestrator::decode_bcs_with_layout(&bytes, type_tag, resolver)
+        .map_err(|e| format!("{:#}", e))
+}
+
+fn dynamic_field_listing_json(
+    listing: &sui_transport::graphql::DynamicFieldListing,
+    resolver: Option<&sui_sandbox_core::resolver::LocalModuleResolver>,
+) -> serde_json::Value {
+    let fields: Vec<serde_json::Value> = listing
+        .fields
+        .iter()
+        .map(|field| {
+            let mut entry = serde_json::json!({
+                "key_type": field.name_type,
+                "key_bcs": field.name_bcs,
+                "key_json": field.name_json,
+                "value_type": field.value_type,
+                "value_bcs": field.value_bcs,
+                "object_id": field.object_id,
+                "version": field.version,
+                "digest": field.digest,
+            });
+            if let Some(resolver) = resolver {
+                let parts = [
+                    (
+                        "key",
+                        Some(field.name_type.as_str()),
+                        field.name_bcs.as_deref(),
+                    ),
+                    (
+                        "value",
+                        field.value_type.as_deref(),
+                        field.value_bcs.as_deref(),
+                    ),
+                ];
+                for (name, type_tag, bcs_base64) in parts {
+                    match decode_dynamic_field_part(resolver, type_tag, bcs_base64) {
+                        Ok(decoded) => entry[name] = decoded,
+                        Err(err) => {
+                            entry[format!("{}_decode_error", name)] = serde_json::json!(err)
+                        }
+                    }
+                }
+            }
+            if let Some(nested) = field
+                .nested_collection_id()
+                .and_then(|id| listing.nested.get(&id))
+            {
+                entry["nested"] = dynamic_field_listing_json(nested, resolver);
+            }
+            entry
+        })
+        .collect();
+
+    serde_json::json!({
+        "count": fields.len(),
+        "truncated": listing.truncated,
+        "fields": fields,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // json_to_bcs (native)
 // ---------------------------------------------------------------------------
@@ -525,163 +783,471 @@ fn transaction_json_to_bcs_inner(transaction_json: &str) -> Result<Vec<u8>> {
     bcs_codec::transaction_json_to_bcs(transaction_json)
 }
 
+fn compute_package_id_inner(
+    modules: &[Vec<u8>],
+    tx_digest: Option<&str>,
+    tx_bytes: Option<&[u8]>,
+    ids_created: u64,
+) -> Result<serde_json::Value> {
+    let digest = match (tx_digest, tx_bytes) {
+        (Some(digest), None) => digest.to_string(),
+        (None, Some(raw)) => bcs_codec::deserialize_transaction_data(raw)?
+            .digest()
+            .to_string(),
+        _ => return Err(anyhow!("provide exactly one of tx_digest or tx_bytes")),
+    };
+    let derivation =
+        sui_sandbox_core::utilities::compute_package_id(modules, &digest, ids_created)?;
+    Ok(serde_json::json!({
+        "package_id": derivation.package_id.to_hex_literal(),
+        "tx_digest": derivation.tx_digest,
+        "ids_created": derivation.ids_created,
+        "modules": derivation.modules,
+    }))
+}
+
+/// Convert each payload independently; errors are kept per item as formatted strings.
+fn transaction_json_to_bcs_batch_inner(
+    transaction_jsons: &[String],
+) -> Vec<std::result::Result<Vec<u8>, String>> {
+    transaction_jsons
+        .iter()
+        .map(|tx_json| transaction_json_to_bcs_inner(tx_json).map_err(|e| format!("{:#}", e)))
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // call_view_function (native)
 // ---------------------------------------------------------------------------
 
-fn call_view_function_inner(
-    package_id: &str,
-    module: &str,
-    function: &str,
+/// How a `call_view_function` object input is passed to the Move call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewObjectMode {
+    /// `&T` (default for non-shared inputs).
+    ImmRef,
+    /// Shared object, by `&T` or `&mut T`.
+    Shared { mutable: bool },
+    /// `T` by value, e.g. a `Coin<T>` that the function consumes.
+    Owned,
+}
+
+#[derive(Debug, Clone)]
+struct ViewObjectInput {
+    object_id: String,
+    bcs_bytes: Vec<u8>,
+    type_tag: String,
+    mode: ViewObjectMode,
+    /// Argument position in the call; `None` places the input before the pure inputs.
+    arg_index: Option<usize>,
+}
+
+/// Build a by-value `Coin<coin_type>` input with the given balance.
+///
+/// Coin layout is `{ id: UID, balance: Balance<T> }`, i.e. 32 id bytes followed by a
+/// little-endian u64. Without an explicit `object_id`, a deterministic placeholder id
+/// derived from the input position is used.
+fn synthesize_view_coin_input(
+    object_id: Option<String>,
+    coin_type: Option<&str>,
+    balance: u64,
+    input_index: usize,
+) -> Result<ViewObjectInput> {
+    let coin_type = coin_type.unwrap_or("0x2::sui::SUI");
+    let inner = sui_sandbox_core::types::parse_type_tag(coin_type)
+        .with_context(|| format!("invalid coin_type: {}", coin_type))?;
+    let id = match object_id.as_deref() {
+        Some(id) => AccountAddress::from_hex_literal(id)
+            .with_context(|| format!("invalid object_id: {}", id))?,
+        None => {
+            let mut raw = [0u8; AccountAddress::LENGTH];
+            raw[..4].copy_from_slice(b"coin");
+            raw[AccountAddress::LENGTH - 8..].copy_from_slice(&(input_index as u64).to_be_bytes());
+            AccountAddress::new(raw)
+        }
+    };
+    let mut bcs_bytes = Vec::with_capacity(AccountAddress::LENGTH + 8);
+    bcs_bytes.extend_from_slice(id.as_ref());
+    bcs_bytes.extend_from_slice(&balance.to_le_bytes());
+    Ok(ViewObjectInput {
+        object_id: id.to_hex_literal(),
+        bcs_bytes,
+        type_tag: sui_sandbox_core::well_known::types::coin_of(inner).to_canonical_string(true),
+        mode: ViewObjectMode::Owned,
+        arg_index: None,
+    })
+}
+
+/// Ask a Python `child_fetcher(parent_id, child_id)` callable for a child object.
+///
+/// Re-acquires the GIL (the VM runs with it released). `None`, a malformed return value,
+/// or an exception all fall through to the next child source; the latter two are reported
+/// as `[child_fetch]` warnings.
+fn call_py_child_fetcher(
+    callback: &PyObject,
+    parent: AccountAddress,
+    child: AccountAddress,
+    warnings: &WarningLog,
+) -> Option<(TypeTag, Vec<u8>)> {
+    let child_id = child.to_hex_literal();
+    let returned = Python::with_gil(|py| -> PyResult<Option<(String, Vec<u8>)>> {
+        let value = callback.call1(py, (parent.to_hex_literal(), child_id.clone()))?;
+        if value.is_none(py) {
+            return Ok(None);
+        }
+        value.extract(py).map(Some)
+    });
+    let (type_tag_str, bcs) = match returned {
+        Ok(found) => found?,
+        Err(err) => {
+            warnings.push(
+                WarningCategory::ChildFetch,
+                format!("child_fetcher failed for child {}: {}", child_id, err),
+            );
+            return None;
+        }
+    };
+    match sui_sandbox_core::types::parse_type_tag(&type_tag_str) {
+        Ok(type_tag) => Some((type_tag, bcs)),
+        Err(err) => {
+            warnings.push(
+                WarningCategory::ChildFetch,
+                format!(
+                    "child_fetcher returned invalid type tag '{}' for child {}: {:#}",
+                    type_tag_str, child_id, err
+                ),
+            );
+            None
+        }
+    }
+}
+
+/// Modules declaring the struct types that appear in `tag`.
+fn collect_struct_module_ids(tag: &TypeTag, out: &mut Vec<ModuleId>) {
+    match tag {
+        TypeTag::Struct(st) => {
+            out.push(st.module_id());
+            for param in &st.type_params {
+                collect_struct_module_ids(param, out);
+            }
+        }
+        TypeTag::Vector(inner) => collect_struct_module_ids(inner, out),
+        _ => {}
+    }
+}
+
+/// One Move call in a view-function batch.
+#[derive(Debug, Clone)]
+struct ViewCall {
+    package_id: String,
+    module: String,
+    function: String,
     type_args: Vec<String>,
-    object_inputs: Vec<(String, Vec<u8>, String, bool, bool)>,
     pure_inputs: Vec<Vec<u8>>,
+}
+
+/// A parsed `package_bytecodes` argument: module bytecode per package plus the
+/// linkage metadata carried by `fetch_historical_package_bytecodes` payloads.
+#[derive(Debug, Default)]
+struct PackageBytecodes {
+    packages: HashMap<String, Vec<Vec<u8>>>,
+    aliases: HashMap<String, String>,
+    linkage_upgrades: HashMap<String, String>,
+    package_runtime_ids: HashMap<String, String>,
+    package_linkage: HashMap<String, HashMap<String, String>>,
+    package_versions: HashMap<String, u64>,
+    /// Parsed from a full historical payload, which already carries the package set.
+    historical_payload: bool,
+}
+
+impl PackageBytecodes {
+    /// Load the packages into `resolver` and apply their aliases and linkage, returning
+    /// the addresses loaded. When both the original and the upgraded storage package
+    /// are present, the original's bytes are skipped so upgraded bytecode wins.
+    fn load_into(
+        &self,
+        resolver: &mut sui_sandbox_core::resolver::LocalModuleResolver,
+    ) -> Result<HashSet<AccountAddress>> {
+        let mut loaded_packages = HashSet::new();
+        let mut skipped_original_packages: HashSet<&String> = HashSet::new();
+        for (original, upgraded) in &self.linkage_upgrades {
+            if original != upgraded
+                && self.packages.contains_key(original)
+                && self.packages.contains_key(upgraded)
+            {
+                skipped_original_packages.insert(original);
+            }
+        }
+
+        let mut package_entries: Vec<(&String, &Vec<Vec<u8>>)> = self.packages.iter().collect();
+        package_entries.sort_by(|a, b| a.0.cmp(b.0));
+        for (pkg_id_str, module_bytecodes) in package_entries {
+            if skipped_original_packages.contains(pkg_id_str) {
+                continue;
+            }
+            let addr = AccountAddress::from_hex_literal(pkg_id_str)
+                .with_context(|| format!("invalid package address: {}", pkg_id_str))?;
+            if is_framework_address(&addr) {
+                continue;
+            }
+            let modules: Vec<(String, Vec<u8>)> = module_bytecodes
+                .iter()
+                .enumerate()
+                .map(|(i, bytes)| {
+                    if let Ok(compiled) = CompiledModule::deserialize_with_defaults(bytes) {
+                        let name = compiled.self_id().name().to_string();
+                        (name, bytes.clone())
+                    } else {
+                        (format!("module_{}", i), bytes.clone())
+                    }
+                })
+                .collect();
+            resolver.add_package_modules_at(modules, Some(addr))?;
+            loaded_packages.insert(addr);
+        }
+
+        // Apply explicit package metadata from historical fetchers:
+        // - aliases: storage -> runtime (bytecode) IDs
+        // - linkage_upgrades: runtime -> storage upgrades
+        // - package_runtime_ids + package_linkage: per-package linkage tables
+        for (storage_str, runtime_str) in &self.aliases {
+            let storage = AccountAddress::from_hex_literal(storage_str)
+                .with_context(|| format!("invalid package alias storage id: {}", storage_str))?;
+            let runtime = AccountAddress::from_hex_literal(runtime_str)
+                .with_context(|| format!("invalid package alias runtime id: {}", runtime_str))?;
+            resolver.add_address_alias(storage, runtime);
+        }
+
+        for (original_str, upgraded_str) in &self.linkage_upgrades {
+            let original = AccountAddress::from_hex_literal(original_str)
+                .with_context(|| format!("invalid linkage original id: {}", original_str))?;
+            let upgraded = AccountAddress::from_hex_literal(upgraded_str)
+                .with_context(|| format!("invalid linkage upgraded id: {}", upgraded_str))?;
+            resolver.add_linkage_upgrade(original, upgraded);
+        }
+
+        for (storage_str, linkage_entries) in &self.package_linkage {
+            if skipped_original_packages.contains(storage_str) {
+                continue;
+            }
+            let storage = AccountAddress::from_hex_literal(storage_str)
+                .with_context(|| format!("invalid linkage storage id: {}", storage_str))?;
+            let runtime = if let Some(runtime_str) = self.package_runtime_ids.get(storage_str) {
+                AccountAddress::from_hex_literal(runtime_str)
+                    .with_context(|| format!("invalid package runtime id: {}", runtime_str))?
+            } else {
+                storage
+            };
+
+            let mut linkage_map: HashMap<AccountAddress, AccountAddress> = HashMap::new();
+            for (dep_runtime_str, dep_storage_str) in linkage_entries {
+                let dep_runtime = AccountAddress::from_hex_literal(dep_runtime_str)
+                    .with_context(|| format!("invalid dep runtime id: {}", dep_runtime_str))?;
+                let dep_storage = AccountAddress::from_hex_literal(dep_storage_str)
+                    .with_context(|| format!("invalid dep storage id: {}", dep_storage_str))?;
+                linkage_map.insert(dep_runtime, dep_storage);
+            }
+            resolver.add_package_linkage(storage, runtime, &linkage_map);
+        }
+
+        for (storage_str, runtime_str) in &self.package_runtime_ids {
+            if skipped_original_packages.contains(storage_str) {
+                continue;
+            }
+            if self.package_linkage.contains_key(storage_str) {
+                continue;
+            }
+            let storage = AccountAddress::from_hex_literal(storage_str)
+                .with_context(|| format!("invalid package runtime storage id: {}", storage_str))?;
+            let runtime = AccountAddress::from_hex_literal(runtime_str)
+                .with_context(|| format!("invalid package runtime id: {}", runtime_str))?;
+            resolver.add_package_linkage(storage, runtime, &HashMap::new());
+        }
+
+        Ok(loaded_packages)
+    }
+}
+
+/// Everything the calls of a view-function batch share: the object inputs, child
+/// sources and package set the resolver and VM are built from.
+struct ViewCallEnv {
+    object_inputs: Vec<ViewObjectInput>,
     child_objects: HashMap<String, Vec<(String, Vec<u8>, String)>>,
     historical_versions: HashMap<String, u64>,
     fetch_child_objects: bool,
     grpc_endpoint: Option<String>,
     grpc_api_key: Option<String>,
-    package_bytecodes: HashMap<String, Vec<Vec<u8>>>,
-    package_aliases: HashMap<String, String>,
-    linkage_upgrades: HashMap<String, String>,
-    package_runtime_ids: HashMap<String, String>,
-    package_linkage: HashMap<String, HashMap<String, String>>,
-    package_versions: HashMap<String, u64>,
+    package_bytecodes: PackageBytecodes,
     fetch_deps: bool,
-) -> Result<serde_json::Value> {
-    use sui_sandbox_core::ptb::{Argument, Command, ObjectInput, PTBExecutor};
+    fetch_modules_only: bool,
+    py_child_fetcher: Option<PyObject>,
+    decode_returns: bool,
+}
+
+/// The result of one call in a batch, with the child-fetch stats and warnings
+/// collected up to the end of that call.
+struct ViewCallOutcome {
+    result: Result<serde_json::Value>,
+    child_fetches: serde_json::Value,
+    warnings: Vec<String>,
+}
+
+impl ViewCallOutcome {
+    /// The call's output with `child_fetches` and `warnings` attached. Execution
+    /// errors propagate unless `isolate_errors` is set, which reports them as a
+    /// failed call instead.
+    fn into_output(self, isolate_errors: bool) -> Result<serde_json::Value> {
+        let mut output = match self.result {
+            Ok(output) => output,
+            Err(err) if isolate_errors => serde_json::json!({
+                "success": false,
+                "error": format!("{:#}", err),
+                "return_values": [],
+                "return_type_tags": [],
+                "gas_used": 0,
+            }),
+            Err(err) => return Err(err),
+        };
+        output["child_fetches"] = self.child_fetches;
+        output["warnings"] = serde_json::json!(self.warnings);
+        Ok(output)
+    }
+}
+
+fn call_view_function_inner(call: ViewCall, env: ViewCallEnv) -> Result<serde_json::Value> {
+    run_view_calls(std::slice::from_ref(&call), env)?
+        .pop()
+        .context("view call produced no result")?
+        .into_output(false)
+}
+
+/// Run `calls` in order against one resolver and VM. A failing call is reported
+/// in its own result and does not stop the calls after it.
+fn call_view_functions_inner(
+    calls: Vec<ViewCall>,
+    env: ViewCallEnv,
+) -> Result<Vec<serde_json::Value>> {
+    run_view_calls(&calls, env)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, outcome)| {
+            let mut output = outcome.into_output(true)?;
+            output["index"] = serde_json::json!(index);
+            Ok(output)
+        })
+        .collect()
+}
+
+/// Build the resolver and VM for `env` once, then execute each call with its own
+/// PTB. Setup failures fail the whole batch; per-call failures are returned in
+/// that call's outcome.
+fn run_view_calls(calls: &[ViewCall], env: ViewCallEnv) -> Result<Vec<ViewCallOutcome>> {
     use sui_sandbox_core::vm::{SimulationConfig, VMHarness};
 
+    let ViewCallEnv {
+        object_inputs,
+        child_objects,
+        historical_versions,
+        fetch_child_objects,
+        grpc_endpoint,
+        grpc_api_key,
+        package_bytecodes,
+        fetch_deps,
+        fetch_modules_only,
+        py_child_fetcher,
+        decode_returns,
+    } = env;
+    let warnings = WarningLog::new(true);
+
     // 1. Build LocalModuleResolver with sui framework
     let mut resolver = sui_sandbox_core::resolver::LocalModuleResolver::with_sui_framework()?;
 
     // 2. Load provided package bytecodes
-    let mut loaded_packages = HashSet::new();
+    let mut loaded_packages = package_bytecodes.load_into(&mut resolver)?;
     loaded_packages.insert(AccountAddress::from_hex_literal("0x1").unwrap());
     loaded_packages.insert(AccountAddress::from_hex_literal("0x2").unwrap());
     loaded_packages.insert(AccountAddress::from_hex_literal("0x3").unwrap());
 
-    // If both original and upgraded storage packages are present, skip loading
-    // the original package bytes so upgraded bytecode wins deterministically.
-    let mut skipped_original_packages: HashSet<String> = HashSet::new();
-    for (original, upgraded) in &linkage_upgrades {
-        if original != upgraded
-            && package_bytecodes.contains_key(original)
-            && package_bytecodes.contains_key(upgraded)
-        {
-            skipped_original_packages.insert(original.clone());
-        }
-    }
+    // 3. If fetch_deps, resolve transitive dependencies via GraphQL. With
+    //    fetch_modules_only, only the modules reachable from the call are fetched.
+    if fetch_deps && fetch_modules_only {
+        let graphql_endpoint = resolve_graphql_endpoint("https://fullnode.mainnet.sui.io:443");
+        let graphql = GraphQLClient::new(&graphql_endpoint);
 
-    let mut package_entries: Vec<(&String, &Vec<Vec<u8>>)> = package_bytecodes.iter().collect();
-    package_entries.sort_by(|a, b| a.0.cmp(b.0));
-    for (pkg_id_str, module_bytecodes) in package_entries {
-        if skipped_original_packages.contains(pkg_id_str) {
-            continue;
-        }
-        let addr = AccountAddress::from_hex_literal(pkg_id_str)
-            .with_context(|| format!("invalid package address: {}", pkg_id_str))?;
-        if is_framework_address(&addr) {
-            continue;
-        }
-        let modules: Vec<(String, Vec<u8>)> = module_bytecodes
-            .iter()
-            .enumerate()
-            .map(|(i, bytes)| {
-                if let Ok(compiled) = CompiledModule::deserialize_with_defaults(bytes) {
-                    let name = compiled.self_id().name().to_string();
-                    (name, bytes.clone())
-                } else {
-                    (format!("module_{}", i), bytes.clone())
+        let mut targets = Vec::new();
+        let mut roots = Vec::new();
+        for call in calls {
+            // An invalid target fails only its own call, when it executes.
+            let (Ok(target_addr), Ok(target_module)) = (
+                AccountAddress::from_hex_literal(&call.package_id),
+                Identifier::new(call.module.as_str()),
+            ) else {
+                continue;
+            };
+            roots.push(ModuleId::new(target_addr, target_module));
+            targets.push((target_addr, call));
+            for type_str in &call.type_args {
+                if let Ok(tag) = sui_sandbox_core::types::parse_type_tag(type_str) {
+                    collect_struct_module_ids(&tag, &mut roots);
                 }
-            })
-            .collect();
-        resolver.add_package_modules_at(modules, Some(addr))?;
-        loaded_packages.insert(addr);
-    }
-
-    // Apply explicit package metadata from historical fetchers:
-    // - aliases: storage -> runtime (bytecode) IDs
-    // - linkage_upgrades: runtime -> storage upgrades
-    // - package_runtime_ids + package_linkage: per-package linkage tables
-    for (storage_str, runtime_str) in &package_aliases {
-        let storage = AccountAddress::from_hex_literal(storage_str)
-            .with_context(|| format!("invalid package alias storage id: {}", storage_str))?;
-        let runtime = AccountAddress::from_hex_literal(runtime_str)
-            .with_context(|| format!("invalid package alias runtime id: {}", runtime_str))?;
-        resolver.add_address_alias(storage, runtime);
-    }
-
-    for (original_str, upgraded_str) in &linkage_upgrades {
-        let original = AccountAddress::from_hex_literal(original_str)
-            .with_context(|| format!("invalid linkage original id: {}", original_str))?;
-        let upgraded = AccountAddress::from_hex_literal(upgraded_str)
-            .with_context(|| format!("invalid linkage upgraded id: {}", upgraded_str))?;
-        resolver.add_linkage_upgrade(original, upgraded);
-    }
-
-    for (storage_str, linkage_entries) in &package_linkage {
-        if skipped_original_packages.contains(storage_str) {
-            continue;
-        }
-        let storage = AccountAddress::from_hex_literal(storage_str)
-            .with_context(|| format!("invalid linkage storage id: {}", storage_str))?;
-        let runtime = if let Some(runtime_str) = package_runtime_ids.get(storage_str) {
-            AccountAddress::from_hex_literal(runtime_str)
-                .with_context(|| format!("invalid package runtime id: {}", runtime_str))?
-        } else {
-            storage
-        };
-
-        let mut linkage_map: HashMap<AccountAddress, AccountAddress> = HashMap::new();
-        for (dep_runtime_str, dep_storage_str) in linkage_entries {
-            let dep_runtime = AccountAddress::from_hex_literal(dep_runtime_str)
-                .with_context(|| format!("invalid dep runtime id: {}", dep_runtime_str))?;
-            let dep_storage = AccountAddress::from_hex_literal(dep_storage_str)
-                .with_context(|| format!("invalid dep storage id: {}", dep_storage_str))?;
-            linkage_map.insert(dep_runtime, dep_storage);
+            }
         }
-        resolver.add_package_linkage(storage, runtime, &linkage_map);
-    }
-
-    for (storage_str, runtime_str) in &package_runtime_ids {
-        if skipped_original_packages.contains(storage_str) {
-            continue;
+        for input in &object_inputs {
+            if let Ok(tag) = sui_sandbox_core::types::parse_type_tag(&input.type_tag) {
+                collect_struct_module_ids(&tag, &mut roots);
+            }
         }
-        if package_linkage.contains_key(storage_str) {
-            continue;
+        let fetched = sui_sandbox_core::replay_support::fetch_module_closure(
+            &mut resolver,
+            &graphql,
+            &roots,
+            None,
+            false,
+        )?;
+        let mut reported = HashSet::new();
+        for (target_addr, call) in targets {
+            if fetched == 0
+                && resolver
+                    .get_module_by_addr_name(&target_addr, &call.module)
+                    .is_none()
+                && reported.insert((target_addr, call.module.as_str()))
+            {
+                warnings.push(
+                    WarningCategory::Deps,
+                    format!(
+                        "module {}::{} could not be fetched",
+                        call.package_id, call.module
+                    ),
+                );
+            }
         }
-        let storage = AccountAddress::from_hex_literal(storage_str)
-            .with_context(|| format!("invalid package runtime storage id: {}", storage_str))?;
-        let runtime = AccountAddress::from_hex_literal(runtime_str)
-            .with_context(|| format!("invalid package runtime id: {}", runtime_str))?;
-        resolver.add_package_linkage(storage, runtime, &HashMap::new());
-    }
-
-    // 3. If fetch_deps, resolve transitive dependencies via GraphQL
-    if fetch_deps {
+    } else if fetch_deps {
         let graphql_endpoint = resolve_graphql_endpoint("https://fullnode.mainnet.sui.io:443");
         let graphql = GraphQLClient::new(&graphql_endpoint);
 
         let mut to_fetch: VecDeque<AccountAddress> = VecDeque::new();
 
-        let target_addr = AccountAddress::from_hex_literal(package_id)
-            .with_context(|| format!("invalid target package: {}", package_id))?;
-        if !loaded_packages.contains(&target_addr) {
-            to_fetch.push_back(target_addr);
-        }
+        for call in calls {
+            // An invalid target fails only its own call, when it executes.
+            if let Ok(target_addr) = AccountAddress::from_hex_literal(&call.package_id) {
+                if !loaded_packages.contains(&target_addr) {
+                    to_fetch.push_back(target_addr);
+                }
+            }
 
-        for ta_str in &type_args {
-            for pkg_id in sui_sandbox_core::utilities::extract_package_ids_from_type(ta_str) {
-                if let Ok(addr) = AccountAddress::from_hex_literal(&pkg_id) {
-                    if !loaded_packages.contains(&addr) && !is_framework_address(&addr) {
-                        to_fetch.push_back(addr);
+            for ta_str in &call.type_args {
+                for pkg_id in sui_sandbox_core::utilities::extract_package_ids_from_type(ta_str) {
+                    if let Ok(addr) = AccountAddress::from_hex_literal(&pkg_id) {
+                        if !loaded_packages.contains(&addr) && !is_framework_address(&addr) {
+                            to_fetch.push_back(addr);
+                        }
                     }
                 }
             }
         }
-        for (_, _, type_tag_str, _, _) in &object_inputs {
-            for pkg_id in sui_sandbox_core::utilities::extract_package_ids_from_type(type_tag_str) {
+        for input in &object_inputs {
+            for pkg_id in
+                sui_sandbox_core::utilities::extract_package_ids_from_type(&input.type_tag)
+            {
                 if let Ok(addr) = AccountAddress::from_hex_literal(&pkg_id) {
                     if !loaded_packages.contains(&addr) && !is_framework_address(&addr) {
                         to_fetch.push_back(addr);
@@ -690,7 +1256,7 @@ fn call_view_function_inner(
             }
         }
 
-        for module_bytecodes in package_bytecodes.values() {
+        for module_bytecodes in package_bytecodes.packages.values() {
             let modules: Vec<(String, Vec<u8>)> = module_bytecodes
                 .iter()
                 .enumerate()
@@ -712,10 +1278,13 @@ fn call_view_function_inner(
             }
             rounds += 1;
             if rounds > MAX_DEP_ROUNDS {
-                eprintln!(
-                    "Warning: dependency resolution hit max depth ({} packages fetched), \
-                     stopping. Some transitive deps may be missing.",
-                    MAX_DEP_ROUNDS
+                warnings.push(
+                    WarningCategory::Deps,
+                    format!(
+                        "dependency resolution hit max depth ({} packages fetched), stopping; \
+                         some transitive deps may be missing",
+                        MAX_DEP_ROUNDS
+                    ),
                 );
                 break;
             }
@@ -735,7 +1304,10 @@ fn call_view_function_inner(
                     }
                 }
                 Err(e) => {
-                    eprintln!("Warning: failed to fetch package {}: {:#}", hex, e);
+                    warnings.push(
+                        WarningCategory::Deps,
+                        format!("failed to fetch package {}: {:#}", hex, e),
+                    );
                 }
             }
         }
@@ -745,7 +1317,7 @@ fn call_view_function_inner(
     let config = SimulationConfig::default();
     let mut vm = VMHarness::with_config(&resolver, false, config)?;
     let mut alias_map: HashMap<AccountAddress, AccountAddress> = HashMap::new();
-    for (storage_str, runtime_str) in &package_aliases {
+    for (storage_str, runtime_str) in &package_bytecodes.aliases {
         let storage = AccountAddress::from_hex_literal(storage_str)
             .with_context(|| format!("invalid alias storage id: {}", storage_str))?;
         let runtime = AccountAddress::from_hex_literal(runtime_str)
@@ -758,13 +1330,17 @@ fn call_view_function_inner(
         alias_map = resolver.get_all_aliases().into_iter().collect();
     }
     if !alias_map.is_empty() {
-        vm.set_address_aliases_with_versions(alias_map, package_versions.clone());
+        vm.set_address_aliases_with_versions(alias_map, package_bytecodes.package_versions.clone());
     }
 
     // 5. Set up child fetcher:
     //    - static preloaded children (if provided)
-    //    - optional on-demand gRPC fetch for missing child objects
-    if !child_objects.is_empty() || fetch_child_objects {
+    //    - optional Python `child_fetcher` callable
+    //    - optional on-demand gRPC fetch for missing child objects, memoized per
+    //      (child, version) so repeated loads across commands hit the network once
+    let child_fetch_memo: Arc<FetchMemo<(AccountAddress, Option<u64>), (TypeTag, Vec<u8>)>> =
+        Arc::new(FetchMemo::new());
+    if !child_objects.is_empty() || fetch_child_objects || py_child_fetcher.is_some() {
         let mut child_map: HashMap<(AccountAddress, AccountAddress), (TypeTag, Vec<u8>)> =
             HashMap::new();
         for (parent_id_str, children) in &child_objects {
@@ -796,8 +1372,10 @@ fn call_view_function_inner(
 
         let child_map = Arc::new(child_map);
         let historical_versions_for_fetcher = Arc::new(historical_versions.clone());
-        let fetcher: sui_sandbox_core::sandbox_runtime::ChildFetcherFn =
-            Box::new(move |parent, child| {
+        let warnings_for_fetcher = warnings.clone();
+        let child_fetch_memo_for_fetcher = Arc::clone(&child_fetch_memo);
+        let fetcher: sui_sandbox_core::sandbox_runtime::ChildFetcherFn = Box::new(
+            move |parent, child| {
                 let debug_child_fetch =
                     std::env::var("SUI_CHILD_FETCH_DEBUG").ok().as_deref() == Some("1");
                 if let Some(found) = child_map.get(&(parent, child)).cloned() {
@@ -811,89 +1389,217 @@ fn call_view_function_inner(
                     return Some(found);
                 }
 
+                if let Some(callback) = py_child_fetcher.as_ref() {
+                    if let Some(found) =
+                        call_py_child_fetcher(callback, parent, child, &warnings_for_fetcher)
+                    {
+                        if debug_child_fetch {
+                            eprintln!(
+                                "[py_child_fetcher] HIT callback parent={} child={}",
+                                parent.to_hex_literal(),
+                                child.to_hex_literal()
+                            );
+                        }
+                        return Some(found);
+                    }
+                }
+
                 let grpc_cfg = grpc_child_config.as_ref()?;
                 let child_id_str = child.to_hex_literal();
                 let historical_version =
                     historical_versions_for_fetcher.get(&child_id_str).copied();
-                if debug_child_fetch {
-                    eprintln!(
-                        "[py_child_fetcher] FETCH parent={} child={} version_hint={:?}",
-                        parent.to_hex_literal(),
-                        child_id_str,
-                        historical_version
-                    );
-                }
+                child_fetch_memo_for_fetcher
+                    .get_or_fetch((child, historical_version), || {
+                        if debug_child_fetch {
+                            eprintln!(
+                                "[py_child_fetcher] FETCH parent={} child={} version_hint={:?}",
+                                parent.to_hex_literal(),
+                                child_id_str,
+                                historical_version
+                            );
+                        }
 
-                let rt = tokio::runtime::Runtime::new().ok()?;
-                let fetched = rt.block_on(async {
-                    let client = GrpcClient::with_api_key(&grpc_cfg.0, grpc_cfg.1.clone())
-                        .await
-                        .ok()?;
-                    client
-                        .get_object_at_version(&child_id_str, historical_version)
-                        .await
-                        .ok()
-                        .flatten()
-                });
-                if fetched.is_none() {
-                    if debug_child_fetch {
-                        eprintln!(
-                            "[py_child_fetcher] MISS grpc child={} version_hint={:?}",
-                            child_id_str, historical_version
-                        );
-                    }
-                    return None;
-                }
-                let object = fetched?;
-                if debug_child_fetch && (object.type_string.is_none() || object.bcs.is_none()) {
-                    eprintln!(
-                        "[py_child_fetcher] MISS payload child={} has_type={} has_bcs={}",
-                        child_id_str,
-                        object.type_string.is_some(),
-                        object.bcs.is_some()
-                    );
-                }
+                        let pool = global_grpc_pool();
+                        let fetched = pool.block_on(async {
+                            let client = pool.client(&grpc_cfg.0, grpc_cfg.1.clone()).await?;
+                            client
+                                .get_object_at_version(&child_id_str, historical_version)
+                                .await
+                        });
+                        let fetched = match fetched {
+                            Ok(fetched) => fetched,
+                            Err(e) if is_object_not_found(&e) => None,
+                            Err(e) => {
+                                warnings_for_fetcher.push(
+                                    WarningCategory::ChildFetch,
+                                    format!(
+                                        "child object {} (version hint {:?}) fetch failed via gRPC: {:#}",
+                                        child_id_str, historical_version, e
+                                    ),
+                                );
+                                return Err(e);
+                            }
+                        };
+                        let Some(object) = fetched else {
+                            if debug_child_fetch {
+                                eprintln!(
+                                    "[py_child_fetcher] MISS grpc child={} version_hint={:?}",
+                                    child_id_str, historical_version
+                                );
+                            }
+                            warnings_for_fetcher.push(
+                                WarningCategory::ChildFetch,
+                                format!(
+                                    "child object {} (version hint {:?}) not found via gRPC",
+                                    child_id_str, historical_version
+                                ),
+                            );
+                            return Ok(None);
+                        };
+                        if debug_child_fetch
+                            && (object.type_string.is_none() || object.bcs.is_none())
+                        {
+                            eprintln!(
+                                "[py_child_fetcher] MISS payload child={} has_type={} has_bcs={}",
+                                child_id_str,
+                                object.type_string.is_some(),
+                                object.bcs.is_some()
+                            );
+                        }
 
-                let type_tag_str = object.type_string?;
-                let bcs = object.bcs?;
-                let type_tag = sui_sandbox_core::types::parse_type_tag(&type_tag_str).ok()?;
-                if debug_child_fetch {
-                    eprintln!(
-                        "[py_child_fetcher] HIT grpc child={} type={}",
-                        child_id_str, type_tag_str
-                    );
-                }
-                Some((type_tag, bcs))
-            });
+                        let (Some(type_tag_str), Some(bcs)) = (object.type_string, object.bcs)
+                        else {
+                            return Ok(None);
+                        };
+                        let Ok(type_tag) = sui_sandbox_core::types::parse_type_tag(&type_tag_str)
+                        else {
+                            return Ok(None);
+                        };
+                        if debug_child_fetch {
+                            eprintln!(
+                                "[py_child_fetcher] HIT grpc child={} type={}",
+                                child_id_str, type_tag_str
+                            );
+                        }
+                        Ok(Some((type_tag, bcs)))
+                    })
+                    .ok()
+                    .flatten()
+            },
+        );
         vm.set_child_fetcher(fetcher);
     }
 
-    // 6. Build PTB and execute
-    let mut executor = PTBExecutor::new(&mut vm);
-
-    let mut input_indices = Vec::new();
-    for (obj_id_str, bcs_bytes, type_tag_str, is_shared, mutable) in &object_inputs {
-        let id = AccountAddress::from_hex_literal(obj_id_str)
-            .with_context(|| format!("invalid object_id: {}", obj_id_str))?;
-        let type_tag = sui_sandbox_core::types::parse_type_tag(type_tag_str)
-            .with_context(|| format!("invalid type tag: {}", type_tag_str))?;
-        let obj_version = historical_versions.get(obj_id_str).copied();
+    // 6. Execute each call with its own PTB over the shared object inputs
+    let setup_warnings = warnings.entries();
+    let mut outcomes = Vec::with_capacity(calls.len());
+    for call in calls {
+        let first_call_warning = warnings.entries().len();
+        let result = execute_view_call(
+            &mut vm,
+            &resolver,
+            &object_inputs,
+            &historical_versions,
+            call,
+            decode_returns,
+        );
+        let mut call_warnings = setup_warnings.clone();
+        call_warnings.extend(warnings.entries().into_iter().skip(first_call_warning));
+        outcomes.push(ViewCallOutcome {
+            result,
+            child_fetches: serde_json::json!(child_fetch_memo.stats()),
+            warnings: call_warnings,
+        });
+    }
+    Ok(outcomes)
+}
 
-        let obj_input = if *is_shared {
-            ObjectInput::Shared {
+/// Order a view call's arguments as indices into its inputs (object inputs first, then
+/// pure inputs). Object inputs with an `arg_index` take that position; the other slots
+/// are filled by the remaining object inputs and then the pure inputs, each in order.
+fn view_call_argument_order(
+    object_positions: &[Option<usize>],
+    pure_count: usize,
+) -> Result<Vec<usize>> {
+    let total = object_positions.len() + pure_count;
+    let mut slots: Vec<Option<usize>> = vec![None; total];
+    for (input, position) in object_positions.iter().enumerate() {
+        let Some(position) = *position else { continue };
+        match slots.get_mut(position) {
+            Some(slot @ None) => *slot = Some(input),
+            Some(Some(_)) => {
+                return Err(anyhow!(
+                    "object input {}: arg_index {} is already taken",
+                    input,
+                    position
+                ))
+            }
+            None => {
+                return Err(anyhow!(
+                    "object input {}: arg_index {} is out of range for {} argument(s)",
+                    input,
+                    position,
+                    total
+                ))
+            }
+        }
+    }
+    let mut rest = object_positions
+        .iter()
+        .enumerate()
+        .filter(|(_, position)| position.is_none())
+        .map(|(input, _)| input)
+        .chain(object_positions.len()..total);
+    Ok(slots
+        .into_iter()
+        .map(|slot| slot.or_else(|| rest.next()).unwrap_or_default())
+        .collect())
+}
+
+fn execute_view_call(
+    vm: &mut sui_sandbox_core::vm::VMHarness<'_>,
+    resolver: &sui_sandbox_core::resolver::LocalModuleResolver,
+    object_inputs: &[ViewObjectInput],
+    historical_versions: &HashMap<String, u64>,
+    call: &ViewCall,
+    decode_returns: bool,
+) -> Result<serde_json::Value> {
+    use sui_sandbox_core::ptb::{Argument, Command, ObjectInput, PTBExecutor};
+
+    // Object inputs are added first, then this call's pure inputs; arguments follow
+    // `view_call_argument_order`.
+    let mut executor = PTBExecutor::new(vm);
+
+    let mut input_indices = Vec::new();
+    for input in object_inputs {
+        let obj_id_str = &input.object_id;
+        let id = AccountAddress::from_hex_literal(obj_id_str)
+            .with_context(|| format!("invalid object_id: {}", obj_id_str))?;
+        let type_tag = sui_sandbox_core::types::parse_type_tag(&input.type_tag)
+            .with_context(|| format!("invalid type tag: {}", input.type_tag))?;
+        let obj_version = historical_versions.get(obj_id_str).copied();
+        let bytes = input.bcs_bytes.clone();
+
+        let obj_input = match input.mode {
+            ViewObjectMode::Shared { mutable } => ObjectInput::Shared {
                 id,
-                bytes: bcs_bytes.clone(),
+                bytes,
                 type_tag: Some(type_tag),
                 version: obj_version,
-                mutable: *mutable,
-            }
-        } else {
-            ObjectInput::ImmRef {
+                mutable,
+            },
+            ViewObjectMode::Owned => ObjectInput::Owned {
                 id,
-                bytes: bcs_bytes.clone(),
+                bytes,
                 type_tag: Some(type_tag),
                 version: obj_version,
-            }
+            },
+            ViewObjectMode::ImmRef => ObjectInput::ImmRef {
+                id,
+                bytes,
+                type_tag: Some(type_tag),
+                version: obj_version,
+            },
         };
 
         let idx = executor
@@ -902,7 +1608,7 @@ fn call_view_function_inner(
         input_indices.push(idx);
     }
 
-    for pure_bytes in &pure_inputs {
+    for pure_bytes in &call.pure_inputs {
         let idx = executor
             .add_pure_input(pure_bytes.clone())
             .context("add pure input")?;
@@ -910,30 +1616,31 @@ fn call_view_function_inner(
     }
 
     let mut parsed_type_args = Vec::new();
-    for ta_str in &type_args {
+    for ta_str in &call.type_args {
         let tt = sui_sandbox_core::types::parse_type_tag(ta_str)
             .with_context(|| format!("invalid type arg: {}", ta_str))?;
         parsed_type_args.push(tt);
     }
 
-    let args: Vec<Argument> = (0..input_indices.len() as u16)
-        .map(Argument::Input)
+    let object_positions: Vec<Option<usize>> =
+        object_inputs.iter().map(|input| input.arg_index).collect();
+    let args: Vec<Argument> = view_call_argument_order(&object_positions, call.pure_inputs.len())?
+        .into_iter()
+        .map(|input| Argument::Input(input_indices[input]))
         .collect();
 
-    let target_addr = AccountAddress::from_hex_literal(package_id)
-        .with_context(|| format!("invalid package address: {}", package_id))?;
+    let target_addr = AccountAddress::from_hex_literal(&call.package_id)
+        .with_context(|| format!("invalid package address: {}", call.package_id))?;
     let commands = vec![Command::MoveCall {
         package: target_addr,
-        module: Identifier::new(module).context("invalid module name")?,
-        function: Identifier::new(function).context("invalid function name")?,
+        module: Identifier::new(call.module.as_str()).context("invalid module name")?,
+        function: Identifier::new(call.function.as_str()).context("invalid function name")?,
         type_args: parsed_type_args,
         args,
     }];
 
-    // 7. Execute
     let effects = executor.execute_commands(&commands)?;
 
-    // 8. Build result
     let return_values: Vec<Vec<String>> = effects
         .return_values
         .iter()
@@ -956,12 +1663,118 @@ fn call_view_function_inner(
         })
         .collect();
 
-    Ok(serde_json::json!({
+    let mut output = serde_json::json!({
         "success": effects.success,
         "error": effects.error,
         "return_values": return_values,
         "return_type_tags": return_type_tags,
         "gas_used": effects.gas_used,
+    });
+    if decode_returns {
+        let decoded =
+            ReplayOrchestrator::decode_command_return_values_with_layouts(&output, 0, resolver)?;
+        output["decoded_returns"] =
+            serde_json::to_value(decoded).context("serialize decoded return values")?;
+    }
+    Ok(output)
+}
+
+// ---------------------------------------------------------------------------
+// plan_child_fetches (native — static bytecode analysis)
+// ---------------------------------------------------------------------------
+
+fn plan_child_fetches_inner(
+    package_id: &str,
+    module: &str,
+    function: &str,
+    type_args: Vec<String>,
+    package_bytecodes: PackageBytecodes,
+    fetch_deps: bool,
+) -> Result<serde_json::Value> {
+    use sui_sandbox_core::mm2::bytecode_analyzer::has_unresolved_params;
+    use sui_sandbox_core::mm2::FieldAccessPredictor;
+
+    let warnings = WarningLog::new(true);
+    let target_addr = AccountAddress::from_hex_literal(package_id)
+        .with_context(|| format!("invalid package address: {}", package_id))?;
+
+    // 1. Build the same resolver call_view_function would execute against.
+    let mut resolver = if package_bytecodes.packages.is_empty() && fetch_deps {
+        build_resolver_with_deps(package_id, &type_args, &warnings)?.0
+    } else {
+        sui_sandbox_core::resolver::LocalModuleResolver::with_sui_framework()?
+    };
+    package_bytecodes.load_into(&mut resolver)?;
+
+    // 2. Resolve the target through aliases so upgraded packages map to their
+    //    bytecode (runtime) address.
+    let compiled_module = resolver
+        .get_module_by_addr_name(&target_addr, module)
+        .ok_or_else(|| anyhow!("Module '{}::{}' not found", package_id, module))?;
+    let runtime_addr = *compiled_module.self_id().address();
+    let has_function = compiled_module.function_defs().iter().any(|def| {
+        compiled_module
+            .identifier_at(compiled_module.function_handle_at(def.function).name)
+            .as_str()
+            == function
+    });
+    if !has_function {
+        return Err(anyhow!(
+            "Function '{}::{}::{}' not found",
+            package_id,
+            module,
+            function
+        ));
+    }
+
+    // 3. Walk the call graph for dynamic_field::* sinks reachable from the target.
+    let mut predictor = FieldAccessPredictor::with_call_graph();
+    for compiled in resolver.iter_modules() {
+        predictor.load_module(compiled.clone());
+    }
+    let predictions = predictor.predict_accesses(&runtime_addr, module, function, &type_args);
+
+    let mut seen: HashSet<(String, String, &'static str)> = HashSet::new();
+    let mut accesses = Vec::new();
+    let mut predicted_child_fetches = 0usize;
+    let mut unresolved = 0usize;
+    for access in predictions {
+        if !seen.insert((
+            access.key_type.clone(),
+            access.value_type.clone(),
+            access.kind.as_str(),
+        )) {
+            continue;
+        }
+        let requires_existing_child = access.kind.reads_existing_child();
+        if requires_existing_child {
+            predicted_child_fetches += 1;
+        }
+        if has_unresolved_params(&access.key_type) || has_unresolved_params(&access.value_type) {
+            unresolved += 1;
+        }
+        accesses.push(serde_json::json!({
+            "key_type": access.key_type,
+            "value_type": access.value_type,
+            "kind": access.kind.as_str(),
+            "mutating": access.kind.is_mutating(),
+            "requires_existing_child": requires_existing_child,
+            "confidence": access.confidence.as_str(),
+            "source_function": access.source_function,
+            "transitive": access.is_transitive,
+        }));
+    }
+
+    Ok(serde_json::json!({
+        "package_id": package_id,
+        "runtime_id": runtime_addr.to_hex_literal(),
+        "module": module,
+        "function": function,
+        "type_args": type_args,
+        "accesses": accesses,
+        "predicted_child_fetches": predicted_child_fetches,
+        "unresolved": unresolved,
+        "warnings": warnings.entries(),
     }))
 }
 
@@ -995,9 +1808,10 @@ fn fetch_historical_package_bytecodes_inner(
     let (grpc_endpoint, grpc_api_key) = resolve_grpc_endpoint_and_key(endpoint, api_key);
     let graphql_endpoint = resolve_graphql_endpoint("https://fullnode.mainnet.sui.io:443");
 
-    let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-    let packages = rt.block_on(async {
-        let grpc = GrpcClient::with_api_key(&grpc_endpoint, grpc_api_key)
+    let pool = global_grpc_pool();
+    let packages = pool.block_on(async {
+        let grpc = pool
+            .client(&grpc_endpoint, grpc_api_key)
             .await
             .context("Failed to create gRPC client")?;
         let graphql = GraphQLClient::new(&graphql_endpoint);
@@ -1069,71 +1883,128 @@ fn fetch_historical_package_bytecodes_inner(
     }))
 }
 
+/// Optional knobs for `fetch_package_bytecodes` / `prepare_package_context`.
+#[derive(Debug, Clone, Copy, Default)]
+struct PackageFetchOptions {
+    /// Also emit framework package bytecode (0x1/0x2/0x3 plus any other
+    /// framework package in the dependency closure).
+    include_framework: bool,
+    /// Pin every fetched package, framework included, to the version that was
+    /// live at this checkpoint.
+    checkpoint: Option<u64>,
+}
+
+fn encode_package_modules(modules: &[(String, Vec<u8>)]) -> serde_json::Value {
+    let bytecodes: Vec<String> = modules
+        .iter()
+        .map(|(_, bytes)| base64::engine::general_purpose::STANDARD.encode(bytes))
+        .collect();
+    serde_json::json!(bytecodes)
+}
+
 fn fetch_package_bytecodes_inner(
     package_id: &str,
     resolve_deps: bool,
+    options: PackageFetchOptions,
 ) -> Result<serde_json::Value> {
     let graphql_endpoint = resolve_graphql_endpoint("https://fullnode.mainnet.sui.io:443");
-    let graphql = GraphQLClient::new(&graphql_endpoint);
+    fetch_package_bytecodes_with(
+        &GraphQLClient::new(&graphql_endpoint),
+        package_id,
+        resolve_deps,
+        options,
+    )
+}
+
+/// [`fetch_package_bytecodes_inner`] against an explicit GraphQL client.
+fn fetch_package_bytecodes_with(
+    graphql: &GraphQLClient,
+    package_id: &str,
+    resolve_deps: bool,
+    options: PackageFetchOptions,
+) -> Result<serde_json::Value> {
+    let warnings = WarningLog::new(true);
 
     let mut packages = serde_json::Map::new();
+    let mut framework_deps: BTreeSet<AccountAddress> = BTreeSet::new();
+    let root = AccountAddress::from_hex_literal(package_id)
+        .with_context(|| format!("invalid package address: {}", package_id))?;
 
     if resolve_deps {
-        let mut to_fetch: VecDeque<AccountAddress> = VecDeque::new();
-        let mut visited = HashSet::new();
-        let root = AccountAddress::from_hex_literal(package_id)
-            .with_context(|| format!("invalid package address: {}", package_id))?;
-        for fw in ["0x1", "0x2", "0x3"] {
-            let fw_addr = AccountAddress::from_hex_literal(fw).unwrap();
-            if fw_addr != root {
-                visited.insert(fw_addr);
-            }
-        }
-        to_fetch.push_back(root);
+        let framework = ["0x1", "0x2", "0x3"]
+            .into_iter()
+            .map(|fw| AccountAddress::from_hex_literal(fw).unwrap())
+            .filter(|fw_addr| *fw_addr != root);
+        let mut walk = DependencyWalk::new(framework);
+        walk.push_root(root);
 
         const MAX_DEP_ROUNDS: usize = 20;
         let mut rounds = 0;
-        while let Some(addr) = to_fetch.pop_front() {
-            if visited.contains(&addr) || (is_framework_address(&addr) && addr != root) {
+        while let Some(addr) = walk.next() {
+            if is_framework_address(&addr) && addr != root {
                 continue;
             }
             rounds += 1;
             if rounds > MAX_DEP_ROUNDS {
-                eprintln!(
-                    "Warning: dependency resolution hit max depth ({} packages fetched), stopping.",
-                    MAX_DEP_ROUNDS
+                warnings.push(
+                    WarningCategory::Deps,
+                    format!(
+                        "dependency resolution hit max depth ({} packages fetched), stopping",
+                        MAX_DEP_ROUNDS
+                    ),
                 );
                 break;
             }
-            visited.insert(addr);
 
             let hex = addr.to_hex_literal();
-            let modules = fetch_package_modules(&graphql, &hex)?;
-            let bytecodes: Vec<String> = modules
-                .iter()
-                .map(|(_, bytes)| base64::engine::general_purpose::STANDARD.encode(bytes))
-                .collect();
+            let modules = fetch_package_modules_pinned(graphql, &hex, options.checkpoint)?;
             let dep_addrs = extract_dependency_addrs(&modules);
-            packages.insert(hex, serde_json::json!(bytecodes));
+            if let Some(runtime_addr) = package_runtime_id(&modules) {
+                walk.record_runtime_id(addr, runtime_addr);
+            }
+            packages.insert(hex, encode_package_modules(&modules));
 
             for dep_addr in dep_addrs {
-                if !visited.contains(&dep_addr) && !is_framework_address(&dep_addr) {
-                    to_fetch.push_back(dep_addr);
+                if is_framework_address(&dep_addr) {
+                    framework_deps.insert(dep_addr);
+                } else {
+                    walk.push_dependency(addr, dep_addr);
                 }
             }
         }
+        for cycle in walk.cycle_descriptions() {
+            warnings.push(
+                WarningCategory::Deps,
+                format!("dependency cycle detected: {}", cycle),
+            );
+        }
     } else {
-        let modules = fetch_package_modules(&graphql, package_id)?;
-        let bytecodes: Vec<String> = modules
-            .iter()
-            .map(|(_, bytes)| base64::engine::general_purpose::STANDARD.encode(bytes))
-            .collect();
-        packages.insert(package_id.to_string(), serde_json::json!(bytecodes));
+        let modules = fetch_package_modules_pinned(graphql, package_id, options.checkpoint)?;
+        packages.insert(package_id.to_string(), encode_package_modules(&modules));
+    }
+
+    if options.include_framework {
+        for fw in ["0x1", "0x2", "0x3"] {
+            framework_deps.insert(AccountAddress::from_hex_literal(fw).unwrap());
+        }
+        for fw_addr in framework_deps {
+            let hex = fw_addr.to_hex_literal();
+            if fw_addr == root || packages.contains_key(&hex) {
+                continue;
+            }
+            let modules = fetch_package_modules_pinned(graphql, &hex, options.checkpoint)
+                .with_context(|| format!("fetch framework package {}", hex))?;
+            packages.insert(hex, encode_package_modules(&modules));
+        }
     }
 
     Ok(serde_json::json!({
         "packages": packages,
         "count": packages.len(),
+        "checkpoint": options.checkpoint,
+        "include_framework": options.include_framework,
+        "warnings": warnings.entries(),
+        "graphql_retries": graphql.retry_stats(),
     }))
 }
 
@@ -1141,8 +2012,9 @@ fn prepare_package_context_inner(
     package_id: &str,
     resolve_deps: bool,
     output_path: Option<&str>,
+    options: PackageFetchOptions,
 ) -> Result<serde_json::Value> {
-    let fetched = fetch_package_bytecodes_inner(package_id, resolve_deps)?;
+    let fetched = fetch_package_bytecodes_inner(package_id, resolve_deps, options)?;
     let generated_at_ms = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
@@ -1200,12 +2072,18 @@ fn fuzz_function_inner(
     max_vector_len: usize,
     dry_run: bool,
     fetch_deps: bool,
+    coverage: bool,
+    corpus_path: Option<&str>,
+    invariants: Vec<String>,
 ) -> Result<serde_json::Value> {
-    use sui_sandbox_core::fuzz::{classify_params, FuzzConfig, FuzzRunner};
+    use sui_sandbox_core::fuzz::{
+        classify_params, FuzzConfig, FuzzCorpus, FuzzRunner, MoveInvariant,
+    };
 
     // 1. Build resolver and fetch deps
+    let warnings = WarningLog::new(true);
     let (resolver, _loaded) = if fetch_deps {
-        build_resolver_with_deps(package_id, &type_args)?
+        build_resolver_with_deps(package_id, &type_args, &warnings)?
     } else {
         let r = sui_sandbox_core::resolver::LocalModuleResolver::with_sui_framework()?;
         let mut loaded = HashSet::new();
@@ -1245,6 +2123,7 @@ fn fuzz_function_inner(
             "target": target,
             "classification": classification,
             "verdict": if classification.is_fully_fuzzable { "FULLY_FUZZABLE" } else { "NOT_FUZZABLE" },
+            "warnings": warnings.entries(),
         }));
     }
 
@@ -1258,6 +2137,7 @@ fn fuzz_function_inner(
                 "Function has {} object and {} unfuzzable parameter(s)",
                 classification.object_count, classification.unfuzzable_count
             ),
+            "warnings": warnings.entries(),
         }));
     }
 
@@ -1276,13 +2156,36 @@ fn fuzz_function_inner(
         type_args: parsed_type_args,
         fail_fast,
         max_vector_len,
+        coverage_guided: coverage || corpus_path.is_some(),
     };
 
-    // 7. Run fuzzer
-    let runner = FuzzRunner::new(&resolver);
-    let report = runner.run(target_addr, module, function, &classification, &config)?;
+    // 7. Run fuzzer (resuming from and saving the corpus when given)
+    let mut runner = FuzzRunner::new(&resolver);
+    for invariant in &invariants {
+        runner = runner.with_invariant(MoveInvariant::parse(invariant)?);
+    }
+    let report = match corpus_path {
+        Some(path) => {
+            let path = Path::new(path);
+            let mut corpus = FuzzCorpus::load(path)?;
+            let report = runner.run_with_corpus(
+                target_addr,
+                module,
+                function,
+                &classification,
+                &config,
+                &mut corpus,
+            )?;
+            corpus.save(path)?;
+            report
+        }
+        None => runner.run(target_addr, module, function, &classification, &config)?,
+    };
 
-    serde_json::to_value(&report).map_err(|e| anyhow!("Failed to serialize fuzz report: {}", e))
+    let mut output = serde_json::to_value(&report)
+        .map_err(|e| anyhow!("Failed to serialize fuzz report: {}", e))?;
+    warnings.attach(&mut output);
+    Ok(output)
 }
 
 // ---------------------------------------------------------------------------
@@ -1327,6 +2230,7 @@ fn get_checkpoint(py: Python<'_>, checkpoint: u64) -> PyResult<PyObject> {
     out_dir=None,
     grpc_endpoint=None,
     stream_timeout_secs=CORE_PTB_UNIVERSE_DEFAULT_STREAM_TIMEOUT_SECS,
+    seed=None,
 ))]
 fn ptb_universe(
     py: Python<'_>,
@@ -1337,6 +2241,7 @@ fn ptb_universe(
     out_dir: Option<&str>,
     grpc_endpoint: Option<&str>,
     stream_timeout_secs: u64,
+    seed: Option<u64>,
 ) -> PyResult<PyObject> {
     let source_parsed = CoreCheckpointSource::parse(source).map_err(to_py_err)?;
     let out_dir_path = PathBuf::from(
@@ -1358,6 +2263,7 @@ fn ptb_universe(
         out_dir: out_dir_path.clone(),
         grpc_endpoint: grpc_endpoint_owned.clone(),
         stream_timeout_secs,
+        seed,
     };
 
     py.allow_threads(move || core_run_ptb_universe(args))
@@ -1371,6 +2277,7 @@ fn ptb_universe(
         "max_ptbs": max_ptbs,
         "grpc_endpoint": grpc_endpoint_owned,
         "stream_timeout_secs": stream_timeout_secs,
+        "seed": seed,
         "out_dir": out_dir_path.display().to_string(),
         "artifacts": {
             "summary": out_dir_path.join("universe_summary.json").display().to_string(),
@@ -1388,9 +2295,9 @@ fn ptb_universe(
 /// Returns digests + package/module/function call summaries for programmable
 /// transactions across one or more checkpoints.
 ///
-/// By default this uses Walrus mainnet. Set `walrus_network="testnet"` or
-/// pass both `walrus_caching_url` and `walrus_aggregator_url` for custom
-/// archive endpoints.
+/// By default this uses Walrus mainnet. Set `network="testnet"` (or the older
+/// `walrus_network="testnet"`) or pass both `walrus_caching_url` and
+/// `walrus_aggregator_url` for custom archive endpoints.
 #[pyfunction]
 #[pyo3(signature = (
     *,
@@ -1399,6 +2306,7 @@ fn ptb_universe(
     package_id=None,
     include_framework=false,
     limit=200,
+    network=None,
     walrus_network="mainnet",
     walrus_caching_url=None,
     walrus_aggregator_url=None,
@@ -1410,10 +2318,12 @@ fn discover_checkpoint_targets(
     package_id: Option<&str>,
     include_framework: bool,
     limit: usize,
+    network: Option<&str>,
     walrus_network: &str,
     walrus_caching_url: Option<&str>,
     walrus_aggregator_url: Option<&str>,
 ) -> PyResult<PyObject> {
+    let network = network.map(Network::parse).transpose().map_err(to_py_err)?;
     let checkpoint_owned = checkpoint.map(ToOwned::to_owned);
     let package_id_owned = package_id.map(ToOwned::to_owned);
     let walrus_network_owned = walrus_network.to_string();
@@ -1427,6 +2337,7 @@ fn discover_checkpoint_targets(
                 package_id_owned.as_deref(),
                 include_framework,
                 limit,
+                network.as_ref(),
                 &walrus_network_owned,
                 walrus_caching_url_owned.as_deref(),
                 walrus_aggregator_url_owned.as_deref(),
@@ -1482,6 +2393,7 @@ fn protocol_discover(
                 filter.as_deref(),
                 include_framework,
                 limit,
+                None,
                 &walrus_network_owned,
                 walrus_caching_url_owned.as_deref(),
                 walrus_aggregator_url_owned.as_deref(),
@@ -1602,6 +2514,53 @@ fn fetch_object_bcs(
     json_value_to_py(py, &value)
 }
 
+/// List every dynamic field of a parent object with key and value BCS.
+///
+/// Pages through the parent's fields via GraphQL, optionally at `checkpoint`.
+/// With `limit`, stops after that many entries and sets `truncated` when more
+/// fields remain. With `decode`, keys and values are decoded field by field
+/// using struct layouts from the packages that define their types. With
+/// `recursive`, tables and bags stored as values are listed under `nested`,
+/// up to `max_depth` levels deep.
+#[pyfunction]
+#[pyo3(signature = (
+    parent_id,
+    *,
+    checkpoint=None,
+    limit=None,
+    decode=true,
+    recursive=false,
+    max_depth=3,
+    endpoint=None,
+))]
+fn list_dynamic_fields(
+    py: Python<'_>,
+    parent_id: &str,
+    checkpoint: Option<u64>,
+    limit: Option<usize>,
+    decode: bool,
+    recursive: bool,
+    max_depth: usize,
+    endpoint: Option<&str>,
+) -> PyResult<PyObject> {
+    let parent_id_owned = parent_id.to_string();
+    let endpoint_owned = endpoint.map(|s| s.to_string());
+    let max_depth = if recursive { max_depth } else { 0 };
+    let value = py
+        .allow_threads(move || {
+            list_dynamic_fields_inner(
+                &parent_id_owned,
+                checkpoint,
+                limit,
+                decode,
+                max_depth,
+                endpoint_owned.as_deref(),
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
 /// Extract the full interface JSON for a Sui Move package.
 ///
 /// Returns the complete interface with all modules, structs, functions,
@@ -1610,14 +2569,25 @@ fn fetch_object_bcs(
 /// Provide either `package_id` (fetched via GraphQL) or `bytecode_dir`
 /// (local directory with `bytecode_modules/*.mv`), but not both.
 ///
+/// With `include_entrypoints=True` the result also carries an `entrypoints`
+/// list: every public/entry function with parameter type tags, whether each
+/// parameter is an object, pure value, or injected `TxContext`, and return types.
+///
 /// Standalone — no CLI binary needed.
 #[pyfunction]
-#[pyo3(signature = (*, package_id=None, bytecode_dir=None, rpc_url="https://fullnode.mainnet.sui.io:443"))]
+#[pyo3(signature = (
+    *,
+    package_id=None,
+    bytecode_dir=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+    include_entrypoints=false,
+))]
 fn extract_interface(
     py: Python<'_>,
     package_id: Option<&str>,
     bytecode_dir: Option<&str>,
     rpc_url: &str,
+    include_entrypoints: bool,
 ) -> PyResult<PyObject> {
     let pkg_id_owned = package_id.map(|s| s.to_string());
     let bytecode_dir_owned = bytecode_dir.map(|s| s.to_string());
@@ -1628,12 +2598,111 @@ fn extract_interface(
                 pkg_id_owned.as_deref(),
                 bytecode_dir_owned.as_deref(),
                 &rpc_url_owned,
+                include_entrypoints,
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// Extract the functions each of a package's functions statically calls.
+///
+/// Walks every function body for `Call`/`CallGeneric` and resolves the target
+/// through the module's function, module and address handle tables, giving a
+/// static call graph at function granularity (`extract_dependency_addrs` only
+/// gives package-level dependencies).
+///
+/// Provide either `package_id` (fetched via GraphQL) or `bytecode_dir`
+/// (local directory with `bytecode_modules/*.mv`), but not both.
+///
+/// Returns: Dict with `package_id`, `functions` (per function: `module`, `function`
+/// and `calls`, each `{package, module, function, external}`), the distinct
+/// `external_targets` and the `external_packages` they live in
+#[pyfunction]
+#[pyo3(signature = (
+    package_id=None,
+    *,
+    bytecode_dir=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+))]
+fn extract_call_targets(
+    py: Python<'_>,
+    package_id: Option<&str>,
+    bytecode_dir: Option<&str>,
+    rpc_url: &str,
+) -> PyResult<PyObject> {
+    let pkg_id_owned = package_id.map(|s| s.to_string());
+    let bytecode_dir_owned = bytecode_dir.map(|s| s.to_string());
+    let rpc_url_owned = rpc_url.to_string();
+    let value = py
+        .allow_threads(move || {
+            extract_call_targets_inner(
+                pkg_id_owned.as_deref(),
+                bytecode_dir_owned.as_deref(),
+                &rpc_url_owned,
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// List the structs and enums a package defines.
+///
+/// Each entry carries the declared abilities (`copy`, `drop`, `store`, `key`),
+/// the type parameters with their ability constraints and phantom flag, whether
+/// the type is an object (`key`), and whether it has the one-time-witness shape
+/// (named after its module in upper case, `drop` only, no type parameters or fields).
+///
+/// Provide either `package_id` (fetched via GraphQL) or `bytecode_dir`
+/// (local directory with `bytecode_modules/*.mv`), but not both.
+///
+/// Returns: Dict with `package_id`, `types` (per type: `module`, `name`, `type_tag`,
+/// `kind`, `abilities`, `type_params`, `is_object`, `is_one_time_witness`) and `counts`
+#[pyfunction]
+#[pyo3(signature = (
+    package_id=None,
+    *,
+    bytecode_dir=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+))]
+fn package_types(
+    py: Python<'_>,
+    package_id: Option<&str>,
+    bytecode_dir: Option<&str>,
+    rpc_url: &str,
+) -> PyResult<PyObject> {
+    let pkg_id_owned = package_id.map(|s| s.to_string());
+    let bytecode_dir_owned = bytecode_dir.map(|s| s.to_string());
+    let rpc_url_owned = rpc_url.to_string();
+    let value = py
+        .allow_threads(move || {
+            package_types_inner(
+                pkg_id_owned.as_deref(),
+                bytecode_dir_owned.as_deref(),
+                &rpc_url_owned,
             )
         })
         .map_err(to_py_err)?;
     json_value_to_py(py, &value)
 }
 
+/// The full node to use for `network`: its default when `rpc_url` is the mainnet default,
+/// otherwise `rpc_url`, which must not name a different public network.
+fn resolve_network_rpc_url<'a>(network: &'a Network, rpc_url: &'a str) -> Result<&'a str> {
+    if rpc_url == Network::Mainnet.rpc_url() {
+        return Ok(network.rpc_url());
+    }
+    match Network::from_url(rpc_url) {
+        Some(url_network) if &url_network != network => Err(anyhow!(
+            "rpc_url {} is a {} endpoint but network='{}'",
+            rpc_url,
+            url_network,
+            network
+        )),
+        _ => Ok(rpc_url),
+    }
+}
+
 /// Replay a historical Sui transaction locally with the Move VM.
 ///
 /// Standalone — no CLI binary needed. All data is fetched directly.
@@ -1643,15 +2712,24 @@ fn extract_interface(
 /// without executing.
 ///
 /// When `checkpoint` is provided, uses Walrus as data source (no API key needed).
-/// Otherwise uses gRPC/hybrid (requires `SUI_GRPC_API_KEY` env var).
+/// Otherwise uses gRPC/hybrid (requires `SUI_GRPC_API_KEY` env var). With
+/// `source="jsonrpc"`, state is hydrated from `rpc_url` via plain JSON-RPC.
 ///
 /// Args:
 ///     digest: Transaction digest to replay
 ///     rpc_url: Sui RPC endpoint
-///     source: Data source — "hybrid", "grpc", or "walrus"
+///     network: "mainnet", "testnet" or "devnet". Selects the network's full node (unless
+///         `rpc_url` is set to another endpoint), GraphQL, gRPC and Walrus endpoints; devnet
+///         has no Walrus archive, so `checkpoint`/`source="walrus"` fail there
+///     source: Data source — "hybrid", "grpc", "walrus", or "jsonrpc"
 ///     checkpoint: Walrus checkpoint number (auto-uses walrus, no API key needed)
+///     state_format: Format of `state_file` — "auto" (default: binary snapshot if the file
+///         starts with the snapshot header, JSON otherwise), "json" or "binary"
 ///     allow_fallback: Allow fallback to secondary data sources
 ///     context_path: Optional prepared package context JSON from prepare_package_context(...)
+///     context_overrides: Let context_path packages replace fetched packages with the same id
+///         (default: context only fills packages the fetch did not return). Replays with a
+///         context report `package_sources` (`context`, `context_replaced`, `fetched` ids)
 ///     profile: Runtime defaults profile ("safe"|"balanced"|"fast")
 ///     fetch_strategy: Dynamic-field fetch strategy ("eager"|"full")
 ///     vm_only: Disable fallback paths and force VM-only behavior
@@ -1664,6 +2742,83 @@ fn extract_interface(
 ///     synthesize_missing: Retry with synthetic object bytes when inputs are missing
 ///     self_heal_dynamic_fields: Enable dynamic field child fetchers during VM execution
 ///     analyze_mm2: Build MM2 type-model diagnostics (analyze-only mode)
+///     fetch_modules_only: Fetch only the dependency modules reachable from the
+///         transaction's Move calls, one module at a time, instead of whole packages
+///         (`dependency_fetch_mode="graphql_module_closure"`)
+///     object_type_overrides: Map of type tag -> None (drop) or BCS bytes (substitute)
+///         applied to matching objects before execution, for fault-injection replays
+///     epoch_override: Execute as if in this epoch (protocol version is kept; a mismatch
+///         with the epoch's on-chain protocol version is reported as an `[epoch]` warning)
+///     protocol_version: Execute with this protocol version's framework, feature flags and
+///         gas schedule. By default the transaction epoch's protocol version is used
+///         (looked up over GraphQL for Walrus and JSON-RPC sources). The effective
+///         version is reported under `execution_path.protocol_version`.
+///     package_overrides: Map of package id -> list of module bytecode substituted for the
+///         package before execution (id, version, and linkage are kept). Modules must be
+///         compiled at the package's original id. Reported under `package_overrides`.
+///     ownership_hints: Map of object id -> "shared" / "owned" / "immutable" that overrides
+///         the inferred ownership of the object's transaction input before execution. Making
+///         an input shared needs "shared:<initial_shared_version>:mutable|readonly".
+///         Reported under `ownership_hints`; hints that contradict the hydrated object
+///         metadata are flagged `contradicted` and raise an `[ownership]` warning
+///     expect_effects_hash: Expected `local_effects_fingerprint` from a recorded baseline run.
+///         Sets `verified` and a `verification` block (with a field `diff` against on-chain
+///         effects on mismatch); combine with `state_file` for offline regression checks
+///     strict_object_digests: Fail when a supplied object's digest differs from the
+///         transaction's object reference (default: report a `[digest]` warning)
+///     require_comparison: With `compare=True`, fail when no on-chain effects were available
+///         to compare against instead of reporting `comparison_unavailable`
+///     expected_effects: With `compare=True`, on-chain effects (dict or JSON string, in the
+///         state-file `transaction.effects` schema) to compare against instead of the effects
+///         the fetched or loaded transaction carries, e.g. from your own indexer. Execution
+///         still uses the transaction's own effects. Reported under `expected_effects`
+///     stub_unknown_natives: Degraded analysis mode: natives that cannot run locally return
+///         zero values instead of aborting. Each is listed under `stubbed_natives`; results
+///         after the first stubbed call are not faithful
+///     gas_table: Custom gas cost table (dict or JSON string) with optional
+///         `instruction_tiers` / `stack_height_tiers` / `stack_size_tiers` (threshold -> cost)
+///         and `natives` (native cost name -> cost). Unspecified entries keep the protocol
+///         default; coverage is reported under `execution_path.gas_table`, with entries the
+///         replay cannot charge under `unapplied`
+///     use_result_cache: Memoize the executed output keyed by the replay state's content
+///         hash plus every execution-affecting parameter; an identical replay returns the
+///         stored output without running the VM. Reported under `result_cache` (`hit`, `key`)
+///     result_cache_dir: Result cache directory (default: `~/.sui-sandbox/cache/results`)
+///     offline: Strict no-network mode. Requires `state_file` or `cache_dir`; every package
+///         and input object must come from the state or `context_path`. Anything that would
+///         be fetched fails with an `offline mode: cannot fetch ...` error listing it
+///     apply_checkpoint_prologue: Before executing, apply the output objects of the system
+///         transactions preceding the target in its checkpoint (consensus commit prologue,
+///         authenticator state, randomness), reconstructed from the Walrus checkpoint; the
+///         Clock takes the consensus commit timestamp. Reported under `checkpoint_prologue`
+///     instruction_trace: Record an instruction-level VM trace and write it to this path as
+///         JSON lines (`enter` / `instruction` / `exit` / `error` entries naming module,
+///         function and bytecode offset). Large and slow; off by default. Counts are
+///         reported under `instruction_trace`
+///     instruction_trace_stack: With `instruction_trace`, also record the operand-stack
+///         depth and the values each instruction pushes
+///     decode_events: Decode each emitted event's BCS payload with its struct layout from
+///         the loaded modules and add it as `fields` to the `effects.events` entry (which
+///         already carries `type` and `sender`); undecodable events get `decode_error`
+///     diff_objects: Decode each mutated object before and after execution with its struct
+///         layout and report a field-level diff under `object_diffs`
+///     gas_profile: Attribute gas to each PTB command and Move function and report it
+///         under `gas_profile` (computation/storage totals, per-command costs and the
+///         10 functions with the most self gas)
+///     trace: Record the Move call tree of every PTB command and return it under `trace`:
+///         one entry per command with `calls`, each call carrying `module`, `function`,
+///         `type_args`, rendered `args` and `returns`, `gas_used`, `error` and `children`
+///     trace_depth: With `trace`, keep calls up to this depth (the command's entry
+///         function is depth 1); deeper calls are only counted in `omitted_calls`
+///     reconcile: Compare local and on-chain effects object by object (IDs, versions,
+///         owners) and report each mismatch under `reconciliation` with a `kind` such as
+///         `missing-create`, `spurious-mutation` or `version-skew`, plus per-kind `counts`
+///     record_requests: Record every gRPC/GraphQL/Walrus request and its response, with the
+///         replay options, to this cassette file (API keys redacted), written even if the
+///         replay fails. Re-run it without network via `replay_from_cassette(path)`.
+///         Reported under `cassette`
+///     summary_only: Return only `digest`, `local_success`, `gas_used`,
+///         `failed_command_index`, a short `error`, `category` and `fidelity` (for bulk runs)
 ///     verbose: Enable verbose logging to stderr
 ///
 /// Returns: dict replay envelope. In `analyze_only=True` mode, `analysis` contains
@@ -1673,10 +2828,13 @@ fn extract_interface(
     digest=None,
     *,
     rpc_url="https://fullnode.mainnet.sui.io:443",
+    network=None,
     source="hybrid",
     checkpoint=None,
     state_file=None,
+    state_format=None,
     context_path=None,
+    context_overrides=false,
     cache_dir=None,
     profile=None,
     fetch_strategy=None,
@@ -1691,16 +2849,45 @@ fn extract_interface(
     synthesize_missing=false,
     self_heal_dynamic_fields=false,
     analyze_mm2=false,
+    fetch_modules_only=false,
+    object_type_overrides=None,
+    epoch_override=None,
+    protocol_version=None,
+    package_overrides=None,
+    ownership_hints=None,
+    expect_effects_hash=None,
+    strict_object_digests=false,
+    require_comparison=false,
+    expected_effects=None,
+    stub_unknown_natives=false,
+    gas_table=None,
+    use_result_cache=false,
+    result_cache_dir=None,
+    offline=false,
+    apply_checkpoint_prologue=false,
+    instruction_trace=None,
+    instruction_trace_stack=false,
+    decode_events=false,
+    diff_objects=false,
+    gas_profile=false,
+    trace=false,
+    trace_depth=None,
+    reconcile=false,
+    record_requests=None,
+    summary_only=false,
     verbose=false,
 ))]
 fn replay(
     py: Python<'_>,
     digest: Option<&str>,
     rpc_url: &str,
+    network: Option<&str>,
     source: &str,
     checkpoint: Option<u64>,
     state_file: Option<&str>,
+    state_format: Option<&str>,
     context_path: Option<&str>,
+    context_overrides: bool,
     cache_dir: Option<&str>,
     profile: Option<&str>,
     fetch_strategy: Option<&str>,
@@ -1715,114 +2902,448 @@ fn replay(
     synthesize_missing: bool,
     self_heal_dynamic_fields: bool,
     analyze_mm2: bool,
+    fetch_modules_only: bool,
+    object_type_overrides: Option<HashMap<String, Option<Vec<u8>>>>,
+    epoch_override: Option<u64>,
+    protocol_version: Option<u64>,
+    package_overrides: Option<HashMap<String, Vec<Vec<u8>>>>,
+    ownership_hints: Option<HashMap<String, String>>,
+    expect_effects_hash: Option<String>,
+    strict_object_digests: bool,
+    require_comparison: bool,
+    expected_effects: Option<&Bound<'_, PyAny>>,
+    stub_unknown_natives: bool,
+    gas_table: Option<&Bound<'_, PyAny>>,
+    use_result_cache: bool,
+    result_cache_dir: Option<&str>,
+    offline: bool,
+    apply_checkpoint_prologue: bool,
+    instruction_trace: Option<&str>,
+    instruction_trace_stack: bool,
+    decode_events: bool,
+    diff_objects: bool,
+    gas_profile: bool,
+    trace: bool,
+    trace_depth: Option<usize>,
+    reconcile: bool,
+    record_requests: Option<&str>,
+    summary_only: bool,
     verbose: bool,
 ) -> PyResult<PyObject> {
-    let digest_owned = digest.map(|s| s.to_string());
-    let rpc_url_owned = rpc_url.to_string();
-    let source_owned = source.to_string();
-    let state_file_owned = state_file.map(PathBuf::from);
-    let context_path_owned = context_path.map(PathBuf::from);
-    let cache_dir_owned = cache_dir.map(PathBuf::from);
-    let profile_owned = profile.map(ToOwned::to_owned);
-    let fetch_strategy_owned = fetch_strategy.map(ToOwned::to_owned);
-    let value = py
-        .allow_threads(move || {
-            let profile = parse_replay_profile(profile_owned.as_deref())?;
-            let _profile_env = workflow_apply_profile_env(profile);
-            let fetch_strategy = parse_replay_fetch_strategy(fetch_strategy_owned.as_deref())?;
-            let allow_fallback = if vm_only { false } else { allow_fallback };
-            let no_prefetch = no_prefetch || fetch_strategy == WorkflowFetchStrategy::Eager;
-
-            let digest = digest_owned.as_deref();
-            let source_is_local = source_owned.eq_ignore_ascii_case("local");
-            let use_local_cache = source_is_local || cache_dir_owned.is_some();
-            let context_packages = if let Some(path) = context_path_owned.as_ref() {
-                Some(load_context_packages_from_file(path)?)
-            } else {
-                None
-            };
-
-            if state_file_owned.is_some() && use_local_cache {
-                return Err(anyhow!(
-                    "state_file cannot be combined with cache_dir/source='local'"
-                ));
-            }
-
-            if let Some(state_path) = state_file_owned.as_ref() {
-                let replay_state = load_replay_state_from_file(state_path, digest)?;
-                return replay_loaded_state_inner(
-                    replay_state,
-                    "state_file",
-                    "state_json",
-                    context_packages.as_ref(),
-                    allow_fallback,
-                    auto_system_objects,
-                    self_heal_dynamic_fields,
-                    vm_only,
-                    compare,
-                    analyze_only,
-                    synthesize_missing,
-                    analyze_mm2,
-                    &rpc_url_owned,
-                    verbose,
-                );
-            }
-
-            if use_local_cache {
-                let digest = digest.ok_or_else(|| {
-                    anyhow!("digest is required when replaying from cache_dir/source='local'")
-                })?;
-                let cache_dir = cache_dir_owned
-                    .clone()
-                    .unwrap_or_else(default_local_cache_dir);
-                let provider = FileStateProvider::new(&cache_dir).with_context(|| {
-                    format!("Failed to open local replay cache {}", cache_dir.display())
-                })?;
-                let replay_state = provider.get_state(digest)?;
-                return replay_loaded_state_inner(
-                    replay_state,
-                    &source_owned,
-                    "local_cache",
-                    context_packages.as_ref(),
-                    allow_fallback,
-                    auto_system_objects,
-                    self_heal_dynamic_fields,
-                    vm_only,
-                    compare,
-                    analyze_only,
-                    synthesize_missing,
-                    analyze_mm2,
-                    &rpc_url_owned,
-                    verbose,
-                );
-            }
-
-            let digest = digest.ok_or_else(|| anyhow!("digest is required"))?;
-            replay_inner(
-                digest,
-                &rpc_url_owned,
-                &source_owned,
-                checkpoint,
-                context_packages.as_ref(),
-                allow_fallback,
-                prefetch_depth,
-                prefetch_limit,
-                auto_system_objects,
-                no_prefetch,
-                synthesize_missing,
-                self_heal_dynamic_fields,
-                vm_only,
-                compare,
-                analyze_only,
-                analyze_mm2,
-                verbose,
-            )
-        })
-        .map_err(to_py_err)?;
+    if expect_effects_hash.is_some() && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "expect_effects_hash requires execution; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if require_comparison && !compare {
+        return Err(PyRuntimeError::new_err(
+            "require_comparison only applies with compare=True",
+        ));
+    }
+    if expected_effects.is_some() && !compare {
+        return Err(PyRuntimeError::new_err(
+            "expected_effects only applies with compare=True",
+        ));
+    }
+    if state_format.is_some() && state_file.is_none() {
+        return Err(PyRuntimeError::new_err(
+            "state_format only applies with state_file",
+        ));
+    }
+    let state_format = state_format
+        .map(str::parse::<ReplayStateFormat>)
+        .transpose()
+        .map_err(to_py_err)?
+        .unwrap_or_default();
+    if summary_only && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "summary_only summarizes execution; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if use_result_cache && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "use_result_cache caches execution results; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if result_cache_dir.is_some() && !use_result_cache {
+        return Err(PyRuntimeError::new_err(
+            "result_cache_dir only applies with use_result_cache=True",
+        ));
+    }
+    if instruction_trace.is_some() && (analyze_only || use_result_cache) {
+        return Err(PyRuntimeError::new_err(
+            "instruction_trace records execution; it cannot be combined with analyze_only=True or use_result_cache=True",
+        ));
+    }
+    if instruction_trace_stack && instruction_trace.is_none() {
+        return Err(PyRuntimeError::new_err(
+            "instruction_trace_stack only applies with instruction_trace",
+        ));
+    }
+    if decode_events && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "decode_events decodes executed events; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if diff_objects && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "diff_objects diffs executed object state; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if gas_profile && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "gas_profile profiles execution; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if trace && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "trace records executed calls; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if trace_depth.is_some() && !trace {
+        return Err(PyRuntimeError::new_err(
+            "trace_depth only applies with trace=True",
+        ));
+    }
+    if reconcile && analyze_only {
+        return Err(PyRuntimeError::new_err(
+            "reconcile compares executed effects; it cannot be combined with analyze_only=True",
+        ));
+    }
+    if record_requests.is_some()
+        && (state_file.is_some()
+            || cache_dir.is_some()
+            || context_path.is_some()
+            || offline
+            || source.eq_ignore_ascii_case("local")
+            || source.eq_ignore_ascii_case("jsonrpc"))
+    {
+        return Err(PyRuntimeError::new_err(
+            "record_requests records a network replay over gRPC/GraphQL/Walrus; it cannot be \
+             combined with state_file, cache_dir, context_path, offline=True or source='local'/'jsonrpc'",
+        ));
+    }
+    let network = network.map(Network::parse).transpose().map_err(to_py_err)?;
+    let rpc_url = match &network {
+        Some(network) => resolve_network_rpc_url(network, rpc_url).map_err(to_py_err)?,
+        None => rpc_url,
+    };
+    let packages = package_overrides
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(id, modules)| {
+            AccountAddress::from_hex_literal(&id)
+                .with_context(|| format!("invalid package_overrides id: {}", id))
+                .map(|address| (address, modules))
+        })
+        .collect::<Result<HashMap<_, _>>>()
+        .map_err(to_py_err)?;
+    let mut hints = sui_sandbox_core::replay_support::ReplayOwnershipHints::default();
+    for (id, ownership) in ownership_hints.unwrap_or_default() {
+        let address = AccountAddress::from_hex_literal(&id)
+            .with_context(|| format!("invalid ownership_hints id: {}", id))
+            .map_err(to_py_err)?;
+        hints
+            .insert_spec(address, &ownership)
+            .with_context(|| format!("invalid ownership_hints entry for {}", id))
+            .map_err(to_py_err)?;
+    }
+    let extras = ReplayExtras {
+        object_overrides: sui_sandbox_core::replay_support::ReplayObjectTypeOverrides {
+            rules: object_type_overrides
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+        },
+        epoch_override,
+        protocol_version,
+        package_overrides: sui_sandbox_core::replay_support::ReplayPackageOverrides { packages },
+        ownership_hints: hints,
+        context_overrides,
+        expect_effects_hash,
+        strict_object_digests,
+        require_comparison,
+        expected_effects: expected_effects
+            .map(|value| parse_expected_effects(py, value))
+            .transpose()
+            .map_err(to_py_err)?,
+        stub_unknown_natives,
+        gas_table: gas_table
+            .map(|value| parse_gas_table(py, value))
+            .transpose()
+            .map_err(to_py_err)?,
+        result_cache: use_result_cache.then(|| ReplayResultCache {
+            dir: result_cache_dir
+                .map(PathBuf::from)
+                .unwrap_or_else(default_result_cache_dir),
+        }),
+        offline,
+        apply_checkpoint_prologue,
+        instruction_trace: instruction_trace.map(PathBuf::from),
+        instruction_trace_stack,
+        decode_events,
+        diff_objects,
+        gas_profile,
+        call_trace: trace.then_some(sui_sandbox_core::call_trace::CallTraceOptions {
+            max_depth: trace_depth,
+        }),
+        reconcile,
+        network,
+        fetch_modules_only,
+        ..ReplayExtras::default()
+    };
+    let request = ReplayRequest {
+        digest: digest.map(ToOwned::to_owned),
+        rpc_url: rpc_url.to_string(),
+        source: source.to_string(),
+        checkpoint,
+        state_file: state_file.map(PathBuf::from),
+        state_format,
+        context_path: context_path.map(PathBuf::from),
+        cache_dir: cache_dir.map(PathBuf::from),
+        record_requests: record_requests.map(PathBuf::from),
+        profile: profile.map(ToOwned::to_owned),
+        fetch_strategy: fetch_strategy.map(ToOwned::to_owned),
+        vm_only,
+        allow_fallback,
+        prefetch_depth,
+        prefetch_limit,
+        auto_system_objects,
+        no_prefetch,
+        compare,
+        analyze_only,
+        synthesize_missing,
+        self_heal_dynamic_fields,
+        analyze_mm2,
+        verbose,
+    };
+    let value = run_replay(py, request, extras)?;
+    if summary_only {
+        return json_value_to_py(py, &core_summarize_replay_output(&value));
+    }
+    json_value_to_py(py, &value)
+}
+
+/// Target, source and fetch/execution flags of a replay; per-call options (overrides,
+/// caching, reporting) are carried by [`ReplayExtras`].
+struct ReplayRequest {
+    digest: Option<String>,
+    rpc_url: String,
+    source: String,
+    checkpoint: Option<u64>,
+    state_file: Option<PathBuf>,
+    state_format: ReplayStateFormat,
+    context_path: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    record_requests: Option<PathBuf>,
+    profile: Option<String>,
+    fetch_strategy: Option<String>,
+    vm_only: bool,
+    allow_fallback: bool,
+    prefetch_depth: usize,
+    prefetch_limit: usize,
+    auto_system_objects: bool,
+    no_prefetch: bool,
+    compare: bool,
+    analyze_only: bool,
+    synthesize_missing: bool,
+    self_heal_dynamic_fields: bool,
+    analyze_mm2: bool,
+    verbose: bool,
+}
+
+/// Run a validated replay request with the GIL released, dispatching to the state-file,
+/// local-cache, recording or network path.
+fn run_replay(
+    py: Python<'_>,
+    request: ReplayRequest,
+    extras: ReplayExtras,
+) -> PyResult<serde_json::Value> {
+    let ReplayRequest {
+        digest: digest_owned,
+        rpc_url: rpc_url_owned,
+        source: source_owned,
+        checkpoint,
+        state_file: state_file_owned,
+        state_format,
+        context_path: context_path_owned,
+        cache_dir: cache_dir_owned,
+        record_requests: record_requests_owned,
+        profile: profile_owned,
+        fetch_strategy: fetch_strategy_owned,
+        vm_only,
+        allow_fallback,
+        prefetch_depth,
+        prefetch_limit,
+        auto_system_objects,
+        no_prefetch,
+        compare,
+        analyze_only,
+        synthesize_missing,
+        self_heal_dynamic_fields,
+        analyze_mm2,
+        verbose,
+    } = request;
+    py.allow_threads(move || {
+        let profile = parse_replay_profile(profile_owned.as_deref())?;
+        let _profile_env = workflow_apply_profile_env(profile);
+        let fetch_strategy = parse_replay_fetch_strategy(fetch_strategy_owned.as_deref())?;
+        let allow_fallback = if vm_only { false } else { allow_fallback };
+        let no_prefetch = no_prefetch || fetch_strategy == WorkflowFetchStrategy::Eager;
+
+        let digest = digest_owned.as_deref();
+        let source_is_local = source_owned.eq_ignore_ascii_case("local");
+        let use_local_cache = source_is_local || cache_dir_owned.is_some();
+        let context_packages = if let Some(path) = context_path_owned.as_ref() {
+            Some(load_context_packages_from_file(path)?)
+        } else {
+            None
+        };
+
+        if state_file_owned.is_some() && use_local_cache {
+            return Err(anyhow!(
+                "state_file cannot be combined with cache_dir/source='local'"
+            ));
+        }
+
+        if let Some(state_path) = state_file_owned.as_ref() {
+            let replay_state = load_replay_state_from_file_as(state_path, digest, state_format)?;
+            return replay_loaded_state_inner(
+                replay_state,
+                "state_file",
+                "state_json",
+                context_packages.as_ref(),
+                allow_fallback,
+                auto_system_objects,
+                self_heal_dynamic_fields,
+                vm_only,
+                compare,
+                analyze_only,
+                synthesize_missing,
+                analyze_mm2,
+                &rpc_url_owned,
+                verbose,
+                &extras,
+            );
+        }
+
+        if use_local_cache {
+            let digest = digest.ok_or_else(|| {
+                anyhow!("digest is required when replaying from cache_dir/source='local'")
+            })?;
+            let cache_dir = cache_dir_owned
+                .clone()
+                .unwrap_or_else(default_local_cache_dir);
+            let provider = FileStateProvider::new(&cache_dir).with_context(|| {
+                format!("Failed to open local replay cache {}", cache_dir.display())
+            })?;
+            let replay_state = provider.get_state(digest)?;
+            return replay_loaded_state_inner(
+                replay_state,
+                &source_owned,
+                "local_cache",
+                context_packages.as_ref(),
+                allow_fallback,
+                auto_system_objects,
+                self_heal_dynamic_fields,
+                vm_only,
+                compare,
+                analyze_only,
+                synthesize_missing,
+                analyze_mm2,
+                &rpc_url_owned,
+                verbose,
+                &extras,
+            );
+        }
+
+        let digest = digest.ok_or_else(|| anyhow!("digest is required"))?;
+        if let Some(path) = record_requests_owned.as_ref() {
+            let params = CassetteReplayParams {
+                digest: digest.to_string(),
+                rpc_url: rpc_url_owned.clone(),
+                source: source_owned.clone(),
+                checkpoint,
+                allow_fallback,
+                prefetch_depth,
+                prefetch_limit,
+                auto_system_objects,
+                no_prefetch,
+                synthesize_missing,
+                self_heal_dynamic_fields,
+                vm_only,
+                compare,
+                analyze_only,
+                analyze_mm2,
+                extras,
+            };
+            return replay_recording_requests(&params, path, verbose);
+        }
+        replay_inner(
+            digest,
+            &rpc_url_owned,
+            &source_owned,
+            checkpoint,
+            context_packages.as_ref(),
+            allow_fallback,
+            prefetch_depth,
+            prefetch_limit,
+            auto_system_objects,
+            no_prefetch,
+            synthesize_missing,
+            self_heal_dynamic_fields,
+            vm_only,
+            compare,
+            analyze_only,
+            analyze_mm2,
+            verbose,
+            &extras,
+        )
+    })
+    .map_err(to_py_err)
+}
+
+/// Re-run a replay recorded with `replay(..., record_requests=path)` without network.
+///
+/// Every gRPC/GraphQL/Walrus fetch is served from the cassette; a request the cassette
+/// has no recording for fails instead of reaching the network. The digest, source,
+/// fetch/execution flags and replay options (overrides, hints, expected effects, gas
+/// table, protocol version, ...) come from the recording. Result caching and
+/// `instruction_trace` are per call and not recorded.
+///
+/// Args:
+///     path: Cassette file written by `record_requests`
+///     summary_only: Return the compact replay summary (see `replay`)
+///     verbose: Enable verbose logging to stderr
+///
+/// Returns: dict replay envelope (as `replay`), with `cassette` (`path`, `mode`, `interactions`)
+#[pyfunction]
+#[pyo3(signature = (path, *, summary_only=false, verbose=false))]
+fn replay_from_cassette(
+    py: Python<'_>,
+    path: &str,
+    summary_only: bool,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    let path_owned = PathBuf::from(path);
+    let value = py
+        .allow_threads(move || replay_from_cassette_inner(&path_owned, verbose))
+        .map_err(to_py_err)?;
+    if summary_only {
+        return json_value_to_py(py, &core_summarize_replay_output(&value));
+    }
     json_value_to_py(py, &value)
 }
 
 /// Import replay data files into a local replay cache directory.
+///
+/// Args:
+///     state: Replay state file (JSON, or a binary snapshot from `export_state`)
+///     transactions: Transactions input file (JSON/JSONL/CSV)
+///     objects: Objects input file (JSON/JSONL/CSV)
+///     packages: Packages input file (JSON/JSONL/CSV)
+///     cache_dir: Cache directory (default: `~/.sui-sandbox/cache/local`)
+///     format: Format of `state` — "auto" (default), "json" or "binary"
 #[pyfunction]
 #[pyo3(signature = (
     *,
@@ -1831,6 +3352,7 @@ fn replay(
     objects=None,
     packages=None,
     cache_dir=None,
+    format=None,
 ))]
 fn import_state(
     py: Python<'_>,
@@ -1839,7 +3361,16 @@ fn import_state(
     objects: Option<&str>,
     packages: Option<&str>,
     cache_dir: Option<&str>,
+    format: Option<&str>,
 ) -> PyResult<PyObject> {
+    if format.is_some() && state.is_none() {
+        return Err(PyRuntimeError::new_err("format only applies with state"));
+    }
+    let state_format = format
+        .map(str::parse::<ReplayStateFormat>)
+        .transpose()
+        .map_err(to_py_err)?
+        .unwrap_or_default();
     let state_owned = state.map(|s| s.to_string());
     let transactions_owned = transactions.map(|s| s.to_string());
     let objects_owned = objects.map(|s| s.to_string());
@@ -1853,6 +3384,86 @@ fn import_state(
                 objects_owned.as_deref(),
                 packages_owned.as_deref(),
                 cache_owned.as_deref(),
+                state_format,
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// Convert a replay state file into a binary snapshot.
+///
+/// Snapshots hold the same states as JSON replay-state files as zstd-compressed BCS
+/// behind a versioned header, and load much faster. Every API that takes a replay-state
+/// file (`replay(state_file=...)`, `import_state(state=...)`) accepts them.
+///
+/// Args:
+///     state: Replay state file (JSON or snapshot)
+///     output: Snapshot file to write
+///     digest: Export only this transaction from a multi-state file
+///
+/// Returns: dict with `output`, `states`, `digests` and `bytes`
+#[pyfunction]
+#[pyo3(signature = (state, output, *, digest=None))]
+fn export_state(
+    py: Python<'_>,
+    state: &str,
+    output: &str,
+    digest: Option<&str>,
+) -> PyResult<PyObject> {
+    let state_owned = PathBuf::from(state);
+    let output_owned = PathBuf::from(output);
+    let digest_owned = digest.map(ToOwned::to_owned);
+    let value = py
+        .allow_threads(move || {
+            export_state_inner(&state_owned, &output_owned, digest_owned.as_deref())
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// Report counts and sizes of cached states/objects/packages in a local replay cache.
+///
+/// Args:
+///     cache_dir: Cache directory (default: `~/.sui-sandbox/cache/local`)
+///
+/// Returns: Dict with `states`, `objects`, `packages`, byte totals and `unreadable` files
+#[pyfunction]
+#[pyo3(signature = (cache_dir=None))]
+fn cache_stats(py: Python<'_>, cache_dir: Option<&str>) -> PyResult<PyObject> {
+    let cache_owned = cache_dir.map(|s| s.to_string());
+    let value = py
+        .allow_threads(move || cache_stats_inner(cache_owned.as_deref()))
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// Prune cached replay states from a local replay cache.
+///
+/// With no filters every cached state is removed; filters combine with AND.
+///
+/// Args:
+///     cache_dir: Cache directory (default: `~/.sui-sandbox/cache/local`)
+///     older_than_days: Only remove states last written at least this many days ago
+///     digest: Only remove the state for this transaction digest
+///
+/// Returns: Dict with `removed` entries (digest/path/bytes), `bytes_removed`, `states_remaining`
+#[pyfunction]
+#[pyo3(signature = (cache_dir=None, *, older_than_days=None, digest=None))]
+fn cache_clear(
+    py: Python<'_>,
+    cache_dir: Option<&str>,
+    older_than_days: Option<f64>,
+    digest: Option<&str>,
+) -> PyResult<PyObject> {
+    let cache_owned = cache_dir.map(|s| s.to_string());
+    let digest_owned = digest.map(|s| s.to_string());
+    let value = py
+        .allow_threads(move || {
+            cache_clear_inner(
+                cache_owned.as_deref(),
+                older_than_days,
+                digest_owned.as_deref(),
             )
         })
         .map_err(to_py_err)?;
@@ -1877,6 +3488,83 @@ fn deserialize_package(py: Python<'_>, bcs: Vec<u8>) -> PyResult<PyObject> {
     json_value_to_py(py, &value)
 }
 
+/// Compute the package id Sui assigns to newly published bytecode.
+///
+/// `modules` is `List[bytes]` or `List[str]` (base64); every module must deserialize
+/// and be compiled at the `0x0` self-address placeholder. The id is
+/// `derive_id(tx_digest, ids_created)`, so it depends on the whole publishing
+/// transaction (sender, gas, inputs), not on the sender alone: pass either the
+/// `tx_digest` or the unsigned `tx_bytes` (TransactionData BCS) to derive it from.
+/// `ids_created` counts object ids the transaction created before the Publish
+/// command (0 when Publish comes first).
+///
+/// Upgrades derive the new storage id the same way, but the package keeps its
+/// original (runtime) id, which is what type tags continue to reference.
+#[pyfunction]
+#[pyo3(signature = (modules, *, tx_digest=None, tx_bytes=None, ids_created=0))]
+fn compute_package_id(
+    py: Python<'_>,
+    modules: &Bound<'_, PyAny>,
+    tx_digest: Option<&str>,
+    tx_bytes: Option<Vec<u8>>,
+    ids_created: u64,
+) -> PyResult<PyObject> {
+    let module_bytes = decode_package_module_bytes(modules)?;
+    let digest_owned = tx_digest.map(|s| s.to_string());
+    let value = py
+        .allow_threads(move || {
+            compute_package_id_inner(
+                &module_bytes,
+                digest_owned.as_deref(),
+                tx_bytes.as_deref(),
+                ids_created,
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+fn canonical_type_string(type_str: &str, long_addresses: bool) -> Result<String> {
+    let tag = sui_sandbox_core::types::parse_type_tag(type_str)
+        .with_context(|| format!("invalid Move type string: {}", type_str))?;
+    Ok(if long_addresses {
+        tag.to_canonical_string(true)
+    } else {
+        sui_sandbox_core::types::format_type_tag(&tag)
+    })
+}
+
+/// Parse a Move type string and return its canonical form.
+///
+/// Addresses are normalized to short `0x`-prefixed hex at every nesting level, so
+/// `0x0000...0002::coin::Coin<0x2::sui::SUI>` and `0x2::coin::Coin<0x02::sui::SUI>`
+/// both become `0x2::coin::Coin<0x2::sui::SUI>`.
+///
+/// Args:
+///     type_str: Move type string (primitive, vector, or struct with type arguments)
+///
+/// Returns: Canonical type string. Raises on malformed input.
+#[pyfunction]
+fn parse_type(type_str: &str) -> PyResult<String> {
+    canonical_type_string(type_str, false).map_err(to_py_err)
+}
+
+/// Canonicalize a Move type string.
+///
+/// Same round-trip as `parse_type`; with `long_addresses=True` every address is
+/// rendered as full 32-byte hex, the form used in on-chain object types.
+///
+/// Args:
+///     type_str: Move type string
+///     long_addresses: Render addresses as 64 hex digits instead of the short form
+///
+/// Returns: Canonical type string. Raises on malformed input.
+#[pyfunction]
+#[pyo3(signature = (type_str, *, long_addresses=false))]
+fn canonicalize_type(type_str: &str, long_addresses: bool) -> PyResult<String> {
+    canonical_type_string(type_str, long_addresses).map_err(to_py_err)
+}
+
 /// Convert Sui object JSON to BCS bytes using struct layouts from bytecode.
 ///
 /// Standalone — no CLI binary needed.
@@ -1922,69 +3610,116 @@ fn transaction_json_to_bcs<'py>(
     Ok(PyBytes::new(py, &bcs_bytes))
 }
 
-/// Execute a view function via local Move VM.
+/// Convert TRANSACTION_JSON to BCS and report how the input was normalized.
 ///
-/// Standalone — no CLI binary needed.
-///
-/// Args:
-///     package_id: Package containing the view function
-///     module: Module name
-///     function: Function name
-///     type_args: List of type argument strings (e.g., ["0x2::sui::SUI"])
-///     object_inputs: List of dicts with keys: object_id, bcs_bytes, type_tag
-///         optional: is_shared/mutable, or legacy owner ("immutable"|"shared"|"address_owned")
-///     pure_inputs: List of BCS-encoded pure argument bytes
-///     child_objects: Dict mapping parent_id -> list of {child_id, bcs_bytes, type_tag}
-///     historical_versions: Optional object_id -> version map for on-demand child fetches
-///     fetch_child_objects: If True, fetch child objects on-demand via gRPC
-///     grpc_endpoint: Optional gRPC endpoint override for child fetches
-///     grpc_api_key: Optional gRPC API key override for child fetches
-///     package_bytecodes: Either:
-///         - Dict[package_id -> list[module_bytes or module_base64]]
-///         - Full payload returned by fetch_historical_package_bytecodes(...)
-///     fetch_deps: If True, automatically resolve transitive deps via GraphQL
+/// Use this to debug exports that produce wrong BCS: every rewrite applied on the
+/// way to canonical `TransactionData` JSON is listed, along with the fields that
+/// looked like they needed one but did not match a known shape.
 ///
-/// Returns: Dict with success, error, return_values, return_type_tags, gas_used
+/// Returns: Dict with `bcs` (bytes), `normalizations` (each `path` as a JSON pointer,
+/// `kind`, `before`, `after`) and `uncertain` (each `path`, `value`, `reason`)
 #[pyfunction]
-#[pyo3(signature = (
-    package_id,
-    module,
-    function,
-    *,
-    type_args=vec![],
-    object_inputs=vec![],
-    pure_inputs=vec![],
-    child_objects=None,
-    historical_versions=None,
-    fetch_child_objects=false,
-    grpc_endpoint=None,
-    grpc_api_key=None,
-    package_bytecodes=None,
-    fetch_deps=true,
-))]
-fn call_view_function(
+#[pyo3(signature = (transaction_json))]
+fn transaction_json_to_bcs_verbose(py: Python<'_>, transaction_json: &str) -> PyResult<PyObject> {
+    let tx_json_owned = transaction_json.to_string();
+    let (bcs_bytes, report) = py
+        .allow_threads(move || bcs_codec::transaction_json_to_bcs_with_report(&tx_json_owned))
+        .map_err(to_py_err)?;
+    let report = serde_json::to_value(report)
+        .context("serialize normalization report")
+        .map_err(to_py_err)?;
+    let result = PyDict::new(py);
+    result.set_item("bcs", PyBytes::new(py, &bcs_bytes))?;
+    result.set_item(
+        "normalizations",
+        json_value_to_py(py, &report["normalizations"])?,
+    )?;
+    result.set_item("uncertain", json_value_to_py(py, &report["uncertain"])?)?;
+    Ok(result.into())
+}
+
+/// Convert many TRANSACTION_JSON payloads to raw transaction BCS in one native call.
+///
+/// Per-item failures do not fail the batch: each result is a dict with `index`, `ok`,
+/// `bcs` (bytes, or None on error) and `error` (None on success), in input order.
+#[pyfunction]
+#[pyo3(signature = (transaction_jsons))]
+fn transaction_json_to_bcs_batch(
     py: Python<'_>,
-    package_id: &str,
-    module: &str,
-    function: &str,
-    type_args: Vec<String>,
-    object_inputs: Vec<Bound<'_, PyDict>>,
-    pure_inputs: Vec<Vec<u8>>,
-    child_objects: Option<Bound<'_, PyDict>>,
-    historical_versions: Option<Bound<'_, PyDict>>,
+    transaction_jsons: Vec<String>,
+) -> PyResult<Vec<PyObject>> {
+    let results = py.allow_threads(move || transaction_json_to_bcs_batch_inner(&transaction_jsons));
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let item = PyDict::new(py);
+            item.set_item("index", index)?;
+            item.set_item("ok", result.is_ok())?;
+            match result {
+                Ok(bcs_bytes) => {
+                    item.set_item("bcs", PyBytes::new(py, &bcs_bytes))?;
+                    item.set_item("error", py.None())?;
+                }
+                Err(error) => {
+                    item.set_item("bcs", py.None())?;
+                    item.set_item("error", error)?;
+                }
+            }
+            Ok(item.into())
+        })
+        .collect()
+}
+
+/// Parse the object inputs, child sources and package set shared by the calls of
+/// `call_view_function` / `call_view_functions`.
+fn parse_view_call_env(
+    object_inputs: &[Bound<'_, PyDict>],
+    child_objects: Option<&Bound<'_, PyDict>>,
+    historical_versions: Option<&Bound<'_, PyDict>>,
     fetch_child_objects: bool,
+    child_fetcher: Option<PyObject>,
     grpc_endpoint: Option<&str>,
     grpc_api_key: Option<&str>,
-    package_bytecodes: Option<Bound<'_, PyDict>>,
+    package_bytecodes: Option<&Bound<'_, PyDict>>,
     fetch_deps: bool,
-) -> PyResult<PyObject> {
+    fetch_modules_only: bool,
+    decode_returns: bool,
+) -> PyResult<ViewCallEnv> {
     // Parse object_inputs from Python dicts
-    let mut parsed_obj_inputs: Vec<(String, Vec<u8>, String, bool, bool)> = Vec::new();
-    for dict in &object_inputs {
-        let obj_id: String = dict
+    let mut parsed_obj_inputs: Vec<ViewObjectInput> = Vec::new();
+    for (input_index, dict) in object_inputs.iter().enumerate() {
+        let obj_id: Option<String> = dict
             .get_item("object_id")?
-            .ok_or_else(|| PyRuntimeError::new_err("missing 'object_id' in object_inputs"))?
-            .extract()?;
+            .map(|v| v.extract())
+            .transpose()?;
+        let arg_index: Option<usize> = dict
+            .get_item("arg_index")?
+            .map(|v| v.extract())
+            .transpose()?;
+
+        // Synthesized coin: no real bytes, just a type and a balance.
+        if let Some(balance) = dict.get_item("coin_balance")? {
+            if dict.get_item("bcs_bytes")?.is_some() {
+                return Err(PyRuntimeError::new_err(
+                    "object_inputs entry cannot set both 'bcs_bytes' and 'coin_balance'",
+                ));
+            }
+            let balance: u64 = balance.extract()?;
+            let coin_type: Option<String> = dict
+                .get_item("coin_type")?
+                .map(|v| v.extract())
+                .transpose()?;
+            let mut input =
+                synthesize_view_coin_input(obj_id, coin_type.as_deref(), balance, input_index)
+                    .map_err(to_py_err)?;
+            input.arg_index = arg_index;
+            parsed_obj_inputs.push(input);
+            continue;
+        }
+
+        let obj_id = obj_id
+            .ok_or_else(|| PyRuntimeError::new_err("missing 'object_id' in object_inputs"))?;
         let bcs_bytes: Vec<u8> = dict
             .get_item("bcs_bytes")?
             .ok_or_else(|| PyRuntimeError::new_err("missing 'bcs_bytes' in object_inputs"))?
@@ -1996,6 +3731,11 @@ fn call_view_function(
 
         let explicit_is_shared = dict.get_item("is_shared")?;
         let explicit_mutable = dict.get_item("mutable")?;
+        let by_value: bool = dict
+            .get_item("by_value")?
+            .map(|v| v.extract())
+            .transpose()?
+            .unwrap_or(false);
         let owner = dict
             .get_item("owner")?
             .map(|v| v.extract::<String>())
@@ -2009,9 +3749,10 @@ fn call_view_function(
             .as_ref()
             .map(|v| v.extract().unwrap_or(false))
             .unwrap_or(false);
+        let mut owned = by_value;
 
         // Backward-compatible alias used in earlier examples:
-        // owner = "immutable" | "shared" | "address_owned"
+        // owner = "immutable" | "shared" | "address_owned"; "owned" passes by value.
         if explicit_is_shared.is_none() {
             if let Some(owner) = owner {
                 match owner.trim().to_ascii_lowercase().as_str() {
@@ -2025,20 +3766,42 @@ fn call_view_function(
                     "immutable" | "address_owned" => {
                         is_shared = false;
                     }
+                    "owned" => {
+                        is_shared = false;
+                        owned = true;
+                    }
                     other => {
                         return Err(PyRuntimeError::new_err(format!(
-                            "invalid 'owner' in object_inputs: {other} (expected immutable|shared|address_owned)"
+                            "invalid 'owner' in object_inputs: {other} (expected immutable|shared|address_owned|owned)"
                         )));
                     }
                 }
             }
         }
-        parsed_obj_inputs.push((obj_id, bcs_bytes, type_tag, is_shared, mutable));
+        if owned && is_shared {
+            return Err(PyRuntimeError::new_err(format!(
+                "object input {obj_id} cannot be both shared and passed by value"
+            )));
+        }
+        let mode = if is_shared {
+            ViewObjectMode::Shared { mutable }
+        } else if owned {
+            ViewObjectMode::Owned
+        } else {
+            ViewObjectMode::ImmRef
+        };
+        parsed_obj_inputs.push(ViewObjectInput {
+            object_id: obj_id,
+            bcs_bytes,
+            type_tag,
+            mode,
+            arg_index,
+        });
     }
 
     // Parse child_objects from Python dict
     let mut parsed_children: HashMap<String, Vec<(String, Vec<u8>, String)>> = HashMap::new();
-    if let Some(ref co) = child_objects {
+    if let Some(co) = child_objects {
         for (key, value) in co.iter() {
             let parent_id: String = key.extract()?;
             let children_list: Vec<Bound<'_, PyDict>> = value.extract()?;
@@ -2064,7 +3827,7 @@ fn call_view_function(
 
     // Parse historical_versions map from Python dict
     let mut parsed_historical_versions: HashMap<String, u64> = HashMap::new();
-    if let Some(ref hv) = historical_versions {
+    if let Some(hv) = historical_versions {
         for (key, value) in hv.iter() {
             let object_id: String = key.extract()?;
             let version: u64 = value.extract()?;
@@ -2072,229 +3835,649 @@ fn call_view_function(
         }
     }
 
-    // Parse package_bytecodes from Python dict
-    let mut parsed_pkg_bytes: HashMap<String, Vec<Vec<u8>>> = HashMap::new();
-    let mut parsed_package_aliases: HashMap<String, String> = HashMap::new();
-    let mut parsed_linkage_upgrades: HashMap<String, String> = HashMap::new();
-    let mut parsed_package_runtime_ids: HashMap<String, String> = HashMap::new();
-    let mut parsed_package_linkage: HashMap<String, HashMap<String, String>> = HashMap::new();
-    let mut parsed_package_versions: HashMap<String, u64> = HashMap::new();
-    let mut historical_payload_mode = false;
-    if let Some(ref pb) = package_bytecodes {
-        let packages_dict: Bound<'_, PyDict> =
-            if let Some(packages_any) = pb.get_item("packages")? {
-                historical_payload_mode = true;
-                packages_any.extract()?
-            } else {
-                pb.clone()
-            };
-
-        for (key, value) in packages_dict.iter() {
-            let pkg_id: String = key.extract()?;
-            let bytecodes = decode_package_module_bytes(&value)?;
-            parsed_pkg_bytes.insert(pkg_id, bytecodes);
-        }
-
-        if let Some(aliases_any) = pb.get_item("aliases")? {
-            parsed_package_aliases = aliases_any.extract().map_err(|_| {
-                PyRuntimeError::new_err(
-                    "package_bytecodes.aliases must be Dict[str, str] (storage -> runtime)",
-                )
-            })?;
-        }
-        if let Some(linkage_any) = pb.get_item("linkage_upgrades")? {
-            parsed_linkage_upgrades = linkage_any.extract().map_err(|_| {
-                PyRuntimeError::new_err(
-                    "package_bytecodes.linkage_upgrades must be Dict[str, str] (runtime -> storage)",
-                )
-            })?;
-        }
-        if let Some(runtime_ids_any) = pb.get_item("package_runtime_ids")? {
-            parsed_package_runtime_ids = runtime_ids_any.extract().map_err(|_| {
-                PyRuntimeError::new_err(
-                    "package_bytecodes.package_runtime_ids must be Dict[str, str] (storage -> runtime)",
-                )
-            })?;
-        }
-        if let Some(pkg_linkage_any) = pb.get_item("package_linkage")? {
-            parsed_package_linkage = pkg_linkage_any.extract().map_err(|_| {
-                PyRuntimeError::new_err(
-                    "package_bytecodes.package_linkage must be Dict[str, Dict[str, str]]",
-                )
-            })?;
-        }
-        if let Some(pkg_versions_any) = pb.get_item("package_versions")? {
-            parsed_package_versions = pkg_versions_any.extract().map_err(|_| {
-                PyRuntimeError::new_err("package_bytecodes.package_versions must be Dict[str, int]")
-            })?;
-        }
-    }
-
-    // Release GIL during VM execution
-    let pkg_id_owned = package_id.to_string();
-    let module_owned = module.to_string();
-    let function_owned = function.to_string();
-    let grpc_endpoint_owned = grpc_endpoint.map(|s| s.to_string());
-    let grpc_api_key_owned = grpc_api_key.map(|s| s.to_string());
-    let effective_fetch_deps = if historical_payload_mode {
-        false
-    } else {
-        fetch_deps
-    };
-    let value = py
-        .allow_threads(move || {
-            call_view_function_inner(
-                &pkg_id_owned,
-                &module_owned,
-                &function_owned,
-                type_args,
-                parsed_obj_inputs,
-                pure_inputs,
-                parsed_children,
-                parsed_historical_versions,
-                fetch_child_objects,
-                grpc_endpoint_owned,
-                grpc_api_key_owned,
-                parsed_pkg_bytes,
-                parsed_package_aliases,
-                parsed_linkage_upgrades,
-                parsed_package_runtime_ids,
-                parsed_package_linkage,
-                parsed_package_versions,
-                effective_fetch_deps,
-            )
-        })
-        .map_err(to_py_err)?;
-
-    json_value_to_py(py, &value)
+    let package_bytecodes = parse_package_bytecodes(package_bytecodes)?;
+
+    Ok(ViewCallEnv {
+        object_inputs: parsed_obj_inputs,
+        child_objects: parsed_children,
+        historical_versions: parsed_historical_versions,
+        fetch_child_objects,
+        grpc_endpoint: grpc_endpoint.map(|s| s.to_string()),
+        grpc_api_key: grpc_api_key.map(|s| s.to_string()),
+        // Historical payloads already carry the full package set.
+        fetch_deps: fetch_deps && !package_bytecodes.historical_payload,
+        package_bytecodes,
+        fetch_modules_only,
+        py_child_fetcher: child_fetcher,
+        decode_returns,
+    })
 }
 
-/// Execute a generic historical Move view function from a versions snapshot.
+/// Execute a view function via local Move VM.
 ///
-/// Protocol-specific logic (object selection, type args, decoding) should be
-/// authored by callers on top of this generic primitive.
+/// Standalone — no CLI binary needed.
+///
+/// Args:
+///     package_id: Package containing the view function
+///     module: Module name
+///     function: Function name
+///     type_args: List of type argument strings (e.g., ["0x2::sui::SUI"])
+///     object_inputs: List of dicts with keys: object_id, bcs_bytes, type_tag
+///         optional: is_shared/mutable, by_value, or legacy owner
+///         ("immutable"|"shared"|"address_owned"|"owned"), and arg_index
+///         (argument position; default: objects first, then pure inputs).
+///         Synthesized coin: {coin_balance, coin_type="0x2::sui::SUI", object_id=None}
+///         builds a by-value Coin<coin_type> without real bytes.
+///     pure_inputs: List of BCS-encoded pure argument bytes
+///     child_objects: Dict mapping parent_id -> list of {child_id, bcs_bytes, type_tag}
+///     historical_versions: Optional object_id -> version map for on-demand child fetches
+///     fetch_child_objects: If True, fetch child objects on-demand via gRPC
+///     child_fetcher: Optional callable (parent_id, child_id) -> (type_tag, bcs_bytes) | None,
+///         consulted after child_objects and before the gRPC fetch
+///     grpc_endpoint: Optional gRPC endpoint override for child fetches
+///     grpc_api_key: Optional gRPC API key override for child fetches
+///     package_bytecodes: Either:
+///         - Dict[package_id -> list[module_bytes or module_base64]]
+///         - Full payload returned by fetch_historical_package_bytecodes(...)
+///     fetch_deps: If True, automatically resolve transitive deps via GraphQL
+///     fetch_modules_only: With fetch_deps, fetch only the target module and the modules it
+///         (transitively) references, one module at a time, instead of whole packages.
+///         Cuts bandwidth for wide packages; each module comes with its package's
+///         linkage table, so upgraded dependencies resolve to their storage ids
+///     decode_returns: If True, add `decoded_returns`: per return value `{index, type_tag,
+///         value, raw_base64, raw_hex}` decoded with the loaded struct layouts, so structs
+///         become field dicts and `vector<T>` a list of decoded `T`
+///
+/// Returns: Dict with success, error, return_values, return_type_tags, gas_used,
+///          child_fetches ({fetched, memo_hits} for on-demand gRPC child loads),
+///          and `decoded_returns` (None if execution failed) when requested
 #[pyfunction]
 #[pyo3(signature = (
-    *,
-    versions_file,
     package_id,
     module,
     function,
-    required_objects,
+    *,
     type_args=vec![],
-    package_roots=vec![],
-    type_refs=vec![],
-    fetch_child_objects=true,
+    object_inputs=vec![],
+    pure_inputs=vec![],
+    child_objects=None,
+    historical_versions=None,
+    fetch_child_objects=false,
+    child_fetcher=None,
     grpc_endpoint=None,
     grpc_api_key=None,
+    package_bytecodes=None,
+    fetch_deps=true,
+    fetch_modules_only=false,
+    decode_returns=false,
 ))]
-fn historical_view_from_versions(
+fn call_view_function(
     py: Python<'_>,
-    versions_file: &str,
     package_id: &str,
     module: &str,
     function: &str,
-    required_objects: Vec<String>,
     type_args: Vec<String>,
-    package_roots: Vec<String>,
-    type_refs: Vec<String>,
+    object_inputs: Vec<Bound<'_, PyDict>>,
+    pure_inputs: Vec<Vec<u8>>,
+    child_objects: Option<Bound<'_, PyDict>>,
+    historical_versions: Option<Bound<'_, PyDict>>,
     fetch_child_objects: bool,
+    child_fetcher: Option<PyObject>,
     grpc_endpoint: Option<&str>,
     grpc_api_key: Option<&str>,
+    package_bytecodes: Option<Bound<'_, PyDict>>,
+    fetch_deps: bool,
+    fetch_modules_only: bool,
+    decode_returns: bool,
 ) -> PyResult<PyObject> {
-    let versions_file_owned = versions_file.to_string();
-    let package_id_owned = package_id.to_string();
-    let module_owned = module.to_string();
-    let function_owned = function.to_string();
-    let endpoint_owned = grpc_endpoint.map(ToOwned::to_owned);
-    let api_key_owned = grpc_api_key.map(ToOwned::to_owned);
+    let env = parse_view_call_env(
+        &object_inputs,
+        child_objects.as_ref(),
+        historical_versions.as_ref(),
+        fetch_child_objects,
+        child_fetcher,
+        grpc_endpoint,
+        grpc_api_key,
+        package_bytecodes.as_ref(),
+        fetch_deps,
+        fetch_modules_only,
+        decode_returns,
+    )?;
+    let call = ViewCall {
+        package_id: package_id.to_string(),
+        module: module.to_string(),
+        function: function.to_string(),
+        type_args,
+        pure_inputs,
+    };
 
+    // Release GIL during VM execution
     let value = py
-        .allow_threads(move || {
-            let versions_path = PathBuf::from(&versions_file_owned);
-            let request = CoreHistoricalViewRequest {
-                package_id: package_id_owned,
-                module: module_owned,
-                function: function_owned,
-                type_args,
-                required_objects,
-                package_roots,
-                type_refs,
-                fetch_child_objects,
-            };
-            let output = core_execute_historical_view_from_versions(
-                &versions_path,
-                &request,
-                endpoint_owned.as_deref(),
-                api_key_owned.as_deref(),
-            )?;
-            serde_json::to_value(output).context("Failed to serialize historical view output")
-        })
+        .allow_threads(move || call_view_function_inner(call, env))
         .map_err(to_py_err)?;
 
     json_value_to_py(py, &value)
 }
 
-/// Execute a historical view request across labeled checkpoint/version points.
+/// Execute several view functions against one shared object set.
 ///
-/// `points` is a JSON-serializable list of:
-/// `{ "checkpoint": int, "versions": {"0x...": version}, "label": str|None, "metadata": any|None }`.
+/// The resolver, VM and child fetcher are built once and each call runs in order
+/// with its own PTB: the shared object inputs first, then the call's pure inputs.
+/// A call that fails (bad arguments, missing module, VM error) is reported in its
+/// own result and does not stop the others.
+///
+/// Args:
+///     package_id: Default package for calls that do not set their own
+///     calls: List of dicts with keys: module, function, optional type_args
+///         (list of type strings), pure_inputs (list of BCS bytes) and package_id
+///     shared_object_inputs: Object inputs passed to every call, same shape as
+///         call_view_function's object_inputs
+///     child_objects, historical_versions, fetch_child_objects, child_fetcher,
+///     grpc_endpoint, grpc_api_key, package_bytecodes, fetch_deps,
+///     fetch_modules_only, decode_returns: As in call_view_function, shared by all calls
+///
+/// Returns: List of result dicts in call order, each shaped like call_view_function's
+///          result plus `index`. A call that could not be executed has success=False,
+///          its error message, and empty return_values.
 #[pyfunction]
 #[pyo3(signature = (
+    package_id,
+    calls,
     *,
-    points,
+    shared_object_inputs=vec![],
+    child_objects=None,
+    historical_versions=None,
+    fetch_child_objects=false,
+    child_fetcher=None,
+    grpc_endpoint=None,
+    grpc_api_key=None,
+    package_bytecodes=None,
+    fetch_deps=true,
+    fetch_modules_only=false,
+    decode_returns=false,
+))]
+fn call_view_functions(
+    py: Python<'_>,
+    package_id: &str,
+    calls: Vec<Bound<'_, PyDict>>,
+    shared_object_inputs: Vec<Bound<'_, PyDict>>,
+    child_objects: Option<Bound<'_, PyDict>>,
+    historical_versions: Option<Bound<'_, PyDict>>,
+    fetch_child_objects: bool,
+    child_fetcher: Option<PyObject>,
+    grpc_endpoint: Option<&str>,
+    grpc_api_key: Option<&str>,
+    package_bytecodes: Option<Bound<'_, PyDict>>,
+    fetch_deps: bool,
+    fetch_modules_only: bool,
+    decode_returns: bool,
+) -> PyResult<PyObject> {
+    let mut parsed_calls = Vec::with_capacity(calls.len());
+    for (index, call) in calls.iter().enumerate() {
+        let required = |key: &str| -> PyResult<String> {
+            call.get_item(key)?
+                .ok_or_else(|| {
+                    PyRuntimeError::new_err(format!("calls[{}] is missing '{}'", index, key))
+                })?
+                .extract()
+        };
+        parsed_calls.push(ViewCall {
+            package_id: call
+                .get_item("package_id")?
+                .map(|v| v.extract())
+                .transpose()?
+                .unwrap_or_else(|| package_id.to_string()),
+            module: required("module")?,
+            function: required("function")?,
+            type_args: call
+                .get_item("type_args")?
+                .map(|v| v.extract())
+                .transpose()?
+                .unwrap_or_default(),
+            pure_inputs: call
+                .get_item("pure_inputs")?
+                .map(|v| v.extract())
+                .transpose()?
+                .unwrap_or_default(),
+        });
+    }
+    let env = parse_view_call_env(
+        &shared_object_inputs,
+        child_objects.as_ref(),
+        historical_versions.as_ref(),
+        fetch_child_objects,
+        child_fetcher,
+        grpc_endpoint,
+        grpc_api_key,
+        package_bytecodes.as_ref(),
+        fetch_deps,
+        fetch_modules_only,
+        decode_returns,
+    )?;
+
+    let results = py
+        .allow_threads(move || call_view_functions_inner(parsed_calls, env))
+        .map_err(to_py_err)?;
+
+    json_value_to_py(py, &serde_json::Value::Array(results))
+}
+
+/// Predict which dynamic-field children a view function will touch, without executing it.
+///
+/// Statically walks the function's bytecode (and everything it calls) for
+/// `dynamic_field::*` / `dynamic_object_field::*` calls and substitutes the
+/// concrete `type_args`, so callers can preload children before
+/// `call_view_function` instead of discovering them one abort at a time.
+///
+/// Args:
+///     package_id: Package containing the view function
+///     module: Module name
+///     function: Function name
+///     type_args: List of type argument strings (e.g., ["0x2::sui::SUI"])
+///     package_bytecodes: Same shapes accepted by call_view_function
+///     fetch_deps: If True and package_bytecodes is omitted, resolve the package
+///         and its transitive deps via GraphQL
+///
+/// Returns: Dict with runtime_id, accesses (key_type, value_type, kind, mutating,
+///          requires_existing_child, confidence, source_function, transitive),
+///          predicted_child_fetches, unresolved, warnings
+#[pyfunction]
+#[pyo3(signature = (
     package_id,
     module,
     function,
-    required_objects,
+    *,
     type_args=vec![],
-    package_roots=vec![],
-    type_refs=vec![],
-    fetch_child_objects=true,
-    schema=None,
-    command_index=0,
-    grpc_endpoint=None,
-    grpc_api_key=None,
-    max_concurrency=1,
+    package_bytecodes=None,
+    fetch_deps=true,
 ))]
-fn historical_series_from_points(
+fn plan_child_fetches(
     py: Python<'_>,
-    points: &Bound<'_, PyAny>,
     package_id: &str,
     module: &str,
     function: &str,
-    required_objects: Vec<String>,
     type_args: Vec<String>,
-    package_roots: Vec<String>,
-    type_refs: Vec<String>,
-    fetch_child_objects: bool,
-    schema: Option<&Bound<'_, PyAny>>,
-    command_index: usize,
-    grpc_endpoint: Option<&str>,
-    grpc_api_key: Option<&str>,
-    max_concurrency: usize,
+    package_bytecodes: Option<Bound<'_, PyDict>>,
+    fetch_deps: bool,
 ) -> PyResult<PyObject> {
-    let points_json = py_json_value(py, points).map_err(to_py_err)?;
-    let parsed_points: Vec<CoreHistoricalSeriesPoint> = serde_json::from_value(points_json)
-        .map_err(|e| to_py_err(anyhow!("invalid historical series points payload: {}", e)))?;
-    let schema_fields: Option<Vec<ReturnDecodeField>> = schema
-        .map(|value| {
-            py_json_value(py, value).and_then(|json| {
-                serde_json::from_value(json)
-                    .map_err(|e| anyhow!("invalid historical series schema: {}", e))
-            })
-        })
-        .transpose()
-        .map_err(to_py_err)?;
+    let package_bytecodes = parse_package_bytecodes(package_bytecodes.as_ref())?;
 
-    let package_id_owned = package_id.to_string();
+    let pkg_id_owned = package_id.to_string();
     let module_owned = module.to_string();
     let function_owned = function.to_string();
-    let endpoint_owned = grpc_endpoint.map(ToOwned::to_owned);
-    let api_key_owned = grpc_api_key.map(ToOwned::to_owned);
-
     let value = py
-        .allow_threads(move || -> Result<serde_json::Value> {
+        .allow_threads(move || {
+            plan_child_fetches_inner(
+                &pkg_id_owned,
+                &module_owned,
+                &function_owned,
+                type_args,
+                package_bytecodes,
+                fetch_deps,
+            )
+        })
+        .map_err(to_py_err)?;
+
+    json_value_to_py(py, &value)
+}
+
+fn build_call_inputs_inner(
+    package_id: &str,
+    module: &str,
+    function: &str,
+    args: Vec<serde_json::Value>,
+    type_args: Vec<String>,
+    checkpoint: Option<u64>,
+    graphql_endpoint: &str,
+) -> Result<serde_json::Value> {
+    use sui_sandbox_core::utilities::{
+        parse_type_tag, plan_call_inputs, rewrite_type_tag, CallArgumentKind,
+    };
+    use sui_transport::graphql::ObjectOwner;
+
+    let warnings = WarningLog::new(true);
+    let target_addr = AccountAddress::from_hex_literal(package_id)
+        .with_context(|| format!("invalid package address: {}", package_id))?;
+    let parsed_type_args = type_args
+        .iter()
+        .map(|t| parse_type_tag(t).ok_or_else(|| anyhow!("invalid type argument: {}", t)))
+        .collect::<Result<Vec<TypeTag>>>()?;
+
+    // 1. Resolve the ABI from the package and its transitive deps.
+    let graphql = GraphQLClient::new(graphql_endpoint);
+    let (resolver, _) =
+        build_resolver_with_deps_via(&graphql, package_id, &type_args, checkpoint, &warnings)?;
+    let aliases = resolver.get_all_aliases();
+    let runtime_addr = aliases.get(&target_addr).copied().unwrap_or(target_addr);
+    let module_id = ModuleId::new(
+        runtime_addr,
+        Identifier::new(module).with_context(|| format!("invalid module name: {}", module))?,
+    );
+    let modules: Vec<CompiledModule> = resolver.iter_modules().cloned().collect();
+    let plan = plan_call_inputs(&modules, &module_id, function, &parsed_type_args, &args)?;
+
+    // 2. Fetch object inputs and check their types against the ABI. Each object
+    //    input carries its `arg_index`, so call_view_function keeps parameter order.
+    let mut object_inputs = Vec::new();
+    let mut pure_inputs = Vec::new();
+    let mut arguments = Vec::new();
+    for arg in &plan.arguments {
+        let mut summary = serde_json::json!({
+            "index": arg.index,
+            "type_tag": arg.type_tag,
+            "kind": arg.kind.as_str(),
+        });
+        if let Some(bytes) = &arg.pure_bytes {
+            pure_inputs.push(serde_json::json!(bytes));
+            arguments.push(summary);
+            continue;
+        }
+        let Some(id) = arg.object_id else { continue };
+        let id_hex = id.to_hex_literal();
+        let object = match checkpoint {
+            Some(cp) => graphql.fetch_object_at_checkpoint(&id_hex, cp),
+            None => graphql.fetch_object(&id_hex),
+        }
+        .with_context(|| {
+            format!(
+                "argument {} ({}): failed to fetch object {}",
+                arg.index, arg.type_tag, id_hex
+            )
+        })?;
+        let object_type = object
+            .type_string
+            .as_deref()
+            .and_then(parse_type_tag)
+            .map(|tag| rewrite_type_tag(tag, &aliases).to_canonical_string(true))
+            .ok_or_else(|| {
+                anyhow!(
+                    "argument {} ({}): object {} has no Move type",
+                    arg.index,
+                    arg.type_tag,
+                    id_hex
+                )
+            })?;
+        if object_type != arg.type_tag {
+            return Err(anyhow!(
+                "argument {} ({}): object {} has type {}",
+                arg.index,
+                arg.type_tag,
+                id_hex,
+                object_type
+            ));
+        }
+        let bcs_base64 = object.bcs_base64.as_deref().ok_or_else(|| {
+            anyhow!(
+                "argument {} ({}): object {} has no BCS contents",
+                arg.index,
+                arg.type_tag,
+                id_hex
+            )
+        })?;
+        let bcs_bytes = base64::engine::general_purpose::STANDARD
+            .decode(bcs_base64)
+            .with_context(|| {
+                format!(
+                    "argument {}: invalid BCS base64 for object {}",
+                    arg.index, id_hex
+                )
+            })?;
+        let is_shared = matches!(object.owner, ObjectOwner::Shared { .. });
+        if matches!(object.owner, ObjectOwner::Immutable)
+            && arg.kind != CallArgumentKind::ObjectImmRef
+        {
+            return Err(anyhow!(
+                "argument {} ({}): immutable object {} can only be passed by immutable reference",
+                arg.index,
+                arg.type_tag,
+                id_hex
+            ));
+        }
+        if is_shared && arg.kind == CallArgumentKind::ObjectByValue {
+            return Err(anyhow!(
+                "argument {} ({}): shared object {} cannot be passed by value",
+                arg.index,
+                arg.type_tag,
+                id_hex
+            ));
+        }
+        summary["object_id"] = serde_json::json!(id_hex);
+        summary["version"] = serde_json::json!(object.version);
+        object_inputs.push(serde_json::json!({
+            "object_id": id_hex,
+            "bcs_bytes": bcs_bytes,
+            "type_tag": arg.type_tag,
+            "is_shared": is_shared,
+            "mutable": arg.kind != CallArgumentKind::ObjectImmRef,
+            "by_value": arg.kind == CallArgumentKind::ObjectByValue,
+            "arg_index": arg.index,
+        }));
+        arguments.push(summary);
+    }
+
+    Ok(serde_json::json!({
+        "package_id": package_id,
+        "module": module,
+        "function": function,
+        "type_args": type_args,
+        "object_inputs": object_inputs,
+        "pure_inputs": pure_inputs,
+        "arguments": arguments,
+        "takes_tx_context": plan.takes_tx_context,
+        "checkpoint": checkpoint,
+    }))
+}
+
+/// Build ready-to-execute `call_view_function` inputs from JSON arguments.
+///
+/// Looks up the function ABI (package + transitive deps via GraphQL), checks the
+/// type and value argument counts, converts each pure argument to BCS via the
+/// bytecode layouts, and fetches each object argument by ID (at `checkpoint` if
+/// given), checking its type against the parameter. Errors name the offending
+/// argument index and expected type. A trailing `TxContext` parameter is omitted.
+///
+/// Args:
+///     package_id: Package containing the function
+///     module: Module name
+///     function: Function name
+///     args_json: List of arguments, or a JSON string of one, in parameter order.
+///         Objects are given as object ID strings; pure values use the same JSON
+///         shapes as `json_to_bcs` (numbers or strings for integers, hex/base64 or
+///         lists for `vector<u8>`, field dicts for structs)
+///     type_args: List of type argument strings (e.g., ["0x2::sui::SUI"])
+///     checkpoint: Fetch packages and object inputs as of this checkpoint instead of latest
+///     rpc_url: Sui RPC endpoint; the GraphQL endpoint is derived from it
+///     network: "mainnet", "testnet" or "devnet". Selects the network's full node (unless
+///         `rpc_url` is set to another endpoint) and its GraphQL endpoint
+///
+/// Returns: Dict with object_inputs (each with its arg_index) and pure_inputs
+///          (pass straight to call_view_function), arguments (index, type_tag,
+///          kind, plus object_id/version for objects), type_args, takes_tx_context
+#[pyfunction]
+#[pyo3(signature = (
+    package_id,
+    module,
+    function,
+    args_json,
+    type_args=vec![],
+    *,
+    checkpoint=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+    network=None,
+))]
+fn build_call_inputs(
+    py: Python<'_>,
+    package_id: &str,
+    module: &str,
+    function: &str,
+    args_json: &Bound<'_, PyAny>,
+    type_args: Vec<String>,
+    checkpoint: Option<u64>,
+    rpc_url: &str,
+    network: Option<&str>,
+) -> PyResult<PyObject> {
+    let network = network.map(Network::parse).transpose().map_err(to_py_err)?;
+    let rpc_url = match &network {
+        Some(network) => resolve_network_rpc_url(network, rpc_url).map_err(to_py_err)?,
+        None => rpc_url,
+    };
+    let graphql_endpoint = resolve_graphql_endpoint(rpc_url);
+    let args = match args_json.extract::<String>() {
+        Ok(text) => serde_json::from_str(&text).context("args_json is not valid JSON"),
+        Err(_) => py_json_value(py, args_json),
+    }
+    .and_then(|value| match value {
+        serde_json::Value::Array(items) => Ok(items),
+        other => Err(anyhow!("args_json must be a list, got {}", other)),
+    })
+    .map_err(to_py_err)?;
+
+    let pkg_id_owned = package_id.to_string();
+    let module_owned = module.to_string();
+    let function_owned = function.to_string();
+    let value = py
+        .allow_threads(move || {
+            build_call_inputs_inner(
+                &pkg_id_owned,
+                &module_owned,
+                &function_owned,
+                args,
+                type_args,
+                checkpoint,
+                &graphql_endpoint,
+            )
+        })
+        .map_err(to_py_err)?;
+
+    json_value_to_py(py, &value)
+}
+
+/// Execute a generic historical Move view function from a versions snapshot.
+///
+/// Protocol-specific logic (object selection, type args, decoding) should be
+/// authored by callers on top of this generic primitive.
+#[pyfunction]
+#[pyo3(signature = (
+    *,
+    versions_file,
+    package_id,
+    module,
+    function,
+    required_objects,
+    type_args=vec![],
+    package_roots=vec![],
+    type_refs=vec![],
+    fetch_child_objects=true,
+    grpc_endpoint=None,
+    grpc_api_key=None,
+))]
+fn historical_view_from_versions(
+    py: Python<'_>,
+    versions_file: &str,
+    package_id: &str,
+    module: &str,
+    function: &str,
+    required_objects: Vec<String>,
+    type_args: Vec<String>,
+    package_roots: Vec<String>,
+    type_refs: Vec<String>,
+    fetch_child_objects: bool,
+    grpc_endpoint: Option<&str>,
+    grpc_api_key: Option<&str>,
+) -> PyResult<PyObject> {
+    let versions_file_owned = versions_file.to_string();
+    let package_id_owned = package_id.to_string();
+    let module_owned = module.to_string();
+    let function_owned = function.to_string();
+    let endpoint_owned = grpc_endpoint.map(ToOwned::to_owned);
+    let api_key_owned = grpc_api_key.map(ToOwned::to_owned);
+
+    let value = py
+        .allow_threads(move || {
+            let versions_path = PathBuf::from(&versions_file_owned);
+            let request = CoreHistoricalViewRequest {
+                package_id: package_id_owned,
+                module: module_owned,
+                function: function_owned,
+                type_args,
+                required_objects,
+                package_roots,
+                type_refs,
+                fetch_child_objects,
+            };
+            let output = core_execute_historical_view_from_versions(
+                &versions_path,
+                &request,
+                endpoint_owned.as_deref(),
+                api_key_owned.as_deref(),
+            )?;
+            serde_json::to_value(output).context("Failed to serialize historical view output")
+        })
+        .map_err(to_py_err)?;
+
+    json_value_to_py(py, &value)
+}
+
+/// Execute a historical view request across labeled checkpoint/version points.
+///
+/// `points` is a JSON-serializable list of:
+/// `{ "checkpoint": int, "versions": {"0x...": version}, "label": str|None, "metadata": any|None }`.
+#[pyfunction]
+#[pyo3(signature = (
+    *,
+    points,
+    package_id,
+    module,
+    function,
+    required_objects,
+    type_args=vec![],
+    package_roots=vec![],
+    type_refs=vec![],
+    fetch_child_objects=true,
+    schema=None,
+    command_index=0,
+    grpc_endpoint=None,
+    grpc_api_key=None,
+    max_concurrency=1,
+))]
+fn historical_series_from_points(
+    py: Python<'_>,
+    points: &Bound<'_, PyAny>,
+    package_id: &str,
+    module: &str,
+    function: &str,
+    required_objects: Vec<String>,
+    type_args: Vec<String>,
+    package_roots: Vec<String>,
+    type_refs: Vec<String>,
+    fetch_child_objects: bool,
+    schema: Option<&Bound<'_, PyAny>>,
+    command_index: usize,
+    grpc_endpoint: Option<&str>,
+    grpc_api_key: Option<&str>,
+    max_concurrency: usize,
+) -> PyResult<PyObject> {
+    let points_json = py_json_value(py, points).map_err(to_py_err)?;
+    let parsed_points: Vec<CoreHistoricalSeriesPoint> = serde_json::from_value(points_json)
+        .map_err(|e| to_py_err(anyhow!("invalid historical series points payload: {}", e)))?;
+    let schema_fields: Option<Vec<ReturnDecodeField>> = schema
+        .map(|value| {
+            py_json_value(py, value).and_then(|json| {
+                serde_json::from_value(json)
+                    .map_err(|e| anyhow!("invalid historical series schema: {}", e))
+            })
+        })
+        .transpose()
+        .map_err(to_py_err)?;
+
+    let package_id_owned = package_id.to_string();
+    let module_owned = module.to_string();
+    let function_owned = function.to_string();
+    let endpoint_owned = grpc_endpoint.map(ToOwned::to_owned);
+    let api_key_owned = grpc_api_key.map(ToOwned::to_owned);
+
+    let value = py
+        .allow_threads(move || -> Result<serde_json::Value> {
             let request = CoreHistoricalViewRequest {
                 package_id: package_id_owned,
                 module: module_owned,
@@ -2404,6 +4587,29 @@ fn py_json_value(py: Python<'_>, value: &Bound<'_, PyAny>) -> Result<serde_json:
     serde_json::from_str(&dumped).context("invalid JSON payload")
 }
 
+/// Parse a `gas_table` argument given as a dict or a JSON string.
+fn parse_gas_table(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+) -> Result<sui_sandbox_core::gas::CustomGasTable> {
+    let json = match value.extract::<String>() {
+        Ok(text) => text,
+        Err(_) => py_json_value(py, value)?.to_string(),
+    };
+    sui_sandbox_core::gas::CustomGasTable::from_json_str(&json)
+}
+
+fn parse_expected_effects(
+    py: Python<'_>,
+    value: &Bound<'_, PyAny>,
+) -> Result<sui_sandbox_core::replay_support::ReplayExpectedEffects> {
+    let json = match value.extract::<String>() {
+        Ok(text) => serde_json::from_str(&text).context("expected_effects is not valid JSON")?,
+        Err(_) => py_json_value(py, value)?,
+    };
+    sui_sandbox_core::replay_support::ReplayExpectedEffects::from_json(&json)
+}
+
 fn decode_historical_result_with_raw_fallback<T, F>(
     raw: &serde_json::Value,
     mut decode: F,
@@ -2536,33 +4742,93 @@ fn historical_decode_with_schema(
     }
 }
 
-/// Fetch historical package bytecodes with transitive dependency resolution.
-///
-/// Standalone — no CLI binary needed.
+/// Decode historical-view return values with a built-in protocol decoder.
 ///
-/// Args:
-///     package_ids: Root package IDs to fetch
-///     type_refs: Optional type strings to infer additional package roots
-///     checkpoint: Optional checkpoint to pin historical package versions
-///     endpoint: Optional gRPC endpoint override
-///     api_key: Optional gRPC API key override
+/// `protocol` is matched case-insensitively (e.g. `"deepbook"`, `"cetus"`).
+/// `decoder` selects a `module::function` (or bare function name) and may be
+/// omitted when the protocol has a single decoder.
 ///
-/// Returns: Dict with packages (pkg_id -> [base64 module bytes]), count, endpoint_used
+/// Returns `{protocol, decoder, decoded}`, or `None` when execution failed or no
+/// command return values are available.
 #[pyfunction]
-#[pyo3(signature = (
-    package_ids,
-    *,
-    type_refs=vec![],
-    checkpoint=None,
-    endpoint=None,
-    api_key=None,
-))]
-fn fetch_historical_package_bytecodes(
+#[pyo3(signature = (protocol, result, *, decoder=None, command_index=0))]
+fn protocol_decode(
     py: Python<'_>,
-    package_ids: Vec<String>,
-    type_refs: Vec<String>,
-    checkpoint: Option<u64>,
-    endpoint: Option<&str>,
+    protocol: &str,
+    result: &Bound<'_, PyAny>,
+    decoder: Option<&str>,
+    command_index: usize,
+) -> PyResult<Option<PyObject>> {
+    let raw = py_json_value(py, result).map_err(to_py_err)?;
+    let registry = CoreProtocolDecoderRegistry::builtin();
+    let decoded = decode_historical_result_with_raw_fallback(&raw, |candidate| {
+        registry.decode(protocol, candidate, decoder, command_index)
+    })
+    .map_err(to_py_err)?;
+    match decoded {
+        Some(value) => json_value_to_py(py, &value).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// List built-in protocol decoders.
+///
+/// Returns: Dict of protocol name -> list of `{module, function, description, fields}`.
+#[pyfunction]
+fn protocol_decoders(py: Python<'_>) -> PyResult<PyObject> {
+    let registry = CoreProtocolDecoderRegistry::builtin();
+    let listing: serde_json::Map<String, serde_json::Value> = registry
+        .protocols()
+        .into_iter()
+        .map(|protocol| {
+            let decoders = serde_json::to_value(registry.decoders(protocol))
+                .unwrap_or(serde_json::Value::Null);
+            (protocol.to_string(), decoders)
+        })
+        .collect();
+    json_value_to_py(py, &serde_json::Value::Object(listing))
+}
+
+/// List protocol adapters: the built-ins plus definitions loaded from the adapter
+/// config dir (`SUI_SANDBOX_PROTOCOLS_DIR`, default `~/.sui-sandbox/protocols`).
+///
+/// Returns: List of `{name, description, package_ids, required_objects, template,
+/// keywords, source}`.
+#[pyfunction]
+fn protocol_adapters(py: Python<'_>) -> PyResult<PyObject> {
+    let registry = CoreProtocolRegistry::load_default().map_err(to_py_err)?;
+    let listing = serde_json::to_value(registry.adapters())
+        .map_err(|e| to_py_err(anyhow!("failed to serialize protocol adapters: {}", e)))?;
+    json_value_to_py(py, &listing)
+}
+
+/// Fetch historical package bytecodes with transitive dependency resolution.
+///
+/// Standalone — no CLI binary needed.
+///
+/// Args:
+///     package_ids: Root package IDs to fetch
+///     type_refs: Optional type strings to infer additional package roots
+///     checkpoint: Optional checkpoint to pin historical package versions
+///     endpoint: Optional gRPC endpoint override
+///     api_key: Optional gRPC API key override
+///
+/// Returns: Dict with packages (pkg_id -> [base64 module bytes]), count, endpoint_used
+#[pyfunction]
+#[pyo3(signature = (
+    package_ids,
+    *,
+    type_refs=vec![],
+    checkpoint=None,
+    endpoint=None,
+    api_key=None,
+))]
+fn fetch_historical_package_bytecodes(
+    py: Python<'_>,
+    package_ids: Vec<String>,
+    type_refs: Vec<String>,
+    checkpoint: Option<u64>,
+    endpoint: Option<&str>,
     api_key: Option<&str>,
 ) -> PyResult<PyObject> {
     let endpoint_owned = endpoint.map(|s| s.to_string());
@@ -2588,22 +4854,73 @@ fn fetch_historical_package_bytecodes(
 /// Args:
 ///     package_id: The package to fetch
 ///     resolve_deps: If True, recursively fetch all dependency packages
+///     include_framework: If True, also include framework package bytecode
+///         (0x1/0x2/0x3 and any framework deps) so the output is self-contained
+///     checkpoint: Optional checkpoint to pin every fetched package (framework
+///         included) to the version live at that checkpoint
 ///
-/// Returns: Dict with packages (pkg_id -> [base64 module bytes]) and count
+/// Returns: Dict with packages (pkg_id -> [base64 module bytes]) and count,
+///          plus graphql_retries (retries, rate_limited, exhausted) from the
+///          client's transient-failure retry policy
 #[pyfunction]
-#[pyo3(signature = (package_id, *, resolve_deps=true))]
+#[pyo3(signature = (package_id, *, resolve_deps=true, include_framework=false, checkpoint=None))]
 fn fetch_package_bytecodes(
     py: Python<'_>,
     package_id: &str,
     resolve_deps: bool,
+    include_framework: bool,
+    checkpoint: Option<u64>,
 ) -> PyResult<PyObject> {
     let pkg_id_owned = package_id.to_string();
+    let options = PackageFetchOptions {
+        include_framework,
+        checkpoint,
+    };
     let value = py
-        .allow_threads(move || fetch_package_bytecodes_inner(&pkg_id_owned, resolve_deps))
+        .allow_threads(move || fetch_package_bytecodes_inner(&pkg_id_owned, resolve_deps, options))
         .map_err(to_py_err)?;
     json_value_to_py(py, &value)
 }
 
+/// Iterator over a package's modules, fetched from GraphQL one page at a time.
+///
+/// Yields `(name, bytecode)` tuples; returned by [`stream_package_modules`].
+#[pyclass(name = "PackageModuleIterator", module = "sui_sandbox")]
+pub(super) struct PackageModuleIterator {
+    stream: sui_transport::PackageModuleStream,
+}
+
+#[pymethods]
+impl PackageModuleIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<(String, Py<PyBytes>)>> {
+        let stream = &mut self.stream;
+        match py.allow_threads(|| stream.next()) {
+            Some(Ok((name, bytes))) => Ok(Some((name, PyBytes::new(py, &bytes).unbind()))),
+            Some(Err(err)) => Err(to_py_err(err)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Stream a package's modules as `(name, bytecode)` tuples.
+///
+/// Pages are fetched from GraphQL lazily as the iterator advances, so decoding or
+/// analysing one module overlaps with fetching the rest and memory stays bounded
+/// to one page (50 modules). `fetch_package_bytecodes` remains the all-at-once form.
+#[pyfunction]
+#[pyo3(signature = (package_id, *, endpoint=None))]
+fn stream_package_modules(package_id: &str, endpoint: Option<&str>) -> PackageModuleIterator {
+    let graphql_endpoint =
+        resolve_graphql_endpoint(endpoint.unwrap_or("https://fullnode.mainnet.sui.io:443"));
+    PackageModuleIterator {
+        stream: GraphQLClient::new(&graphql_endpoint).stream_package_modules(package_id),
+    }
+}
+
 /// Prepare a generic package context by fetching package bytecodes (+deps by default).
 ///
 /// This is step 1 of a simple two-step developer flow:
@@ -2614,24 +4931,41 @@ fn fetch_package_bytecodes(
 ///     package_id: Root package id (0x...)
 ///     resolve_deps: If True, fetch transitive dependency closure (default: True)
 ///     output_path: Optional JSON path to persist the context payload
+///     include_framework: If True, also store framework package bytecode so the
+///         context can be replayed without a network connection
+///     checkpoint: Optional checkpoint to pin every package (framework included) to
 ///
 /// Returns: Dict with `package_id`, `packages`, and `count`
 #[pyfunction]
-#[pyo3(signature = (package_id, *, resolve_deps=true, output_path=None))]
+#[pyo3(signature = (
+    package_id,
+    *,
+    resolve_deps=true,
+    output_path=None,
+    include_framework=false,
+    checkpoint=None,
+))]
 fn prepare_package_context(
     py: Python<'_>,
     package_id: &str,
     resolve_deps: bool,
     output_path: Option<&str>,
+    include_framework: bool,
+    checkpoint: Option<u64>,
 ) -> PyResult<PyObject> {
     let package_id_owned = package_id.to_string();
     let output_path_owned = output_path.map(|s| s.to_string());
+    let options = PackageFetchOptions {
+        include_framework,
+        checkpoint,
+    };
     let value = py
         .allow_threads(move || {
             prepare_package_context_inner(
                 &package_id_owned,
                 resolve_deps,
                 output_path_owned.as_deref(),
+                options,
             )
         })
         .map_err(to_py_err)?;
@@ -2663,7 +4997,12 @@ fn protocol_prepare(
         .allow_threads(move || {
             let resolved =
                 resolve_protocol_package_id(&protocol_owned, package_id_owned.as_deref())?;
-            prepare_package_context_inner(&resolved, resolve_deps, output_path_owned.as_deref())
+            prepare_package_context_inner(
+                &resolved,
+                resolve_deps,
+                output_path_owned.as_deref(),
+                PackageFetchOptions::default(),
+            )
         })
         .map_err(to_py_err)?;
     json_value_to_py(py, &value)
@@ -2671,14 +5010,30 @@ fn protocol_prepare(
 
 /// Canonical alias for `prepare_package_context`.
 #[pyfunction]
-#[pyo3(signature = (package_id, *, resolve_deps=true, output_path=None))]
+#[pyo3(signature = (
+    package_id,
+    *,
+    resolve_deps=true,
+    output_path=None,
+    include_framework=false,
+    checkpoint=None,
+))]
 fn context_prepare(
     py: Python<'_>,
     package_id: &str,
     resolve_deps: bool,
     output_path: Option<&str>,
+    include_framework: bool,
+    checkpoint: Option<u64>,
 ) -> PyResult<PyObject> {
-    prepare_package_context(py, package_id, resolve_deps, output_path)
+    prepare_package_context(
+        py,
+        package_id,
+        resolve_deps,
+        output_path,
+        include_framework,
+        checkpoint,
+    )
 }
 
 /// Canonical alias for `protocol_prepare`.
@@ -2702,26 +5057,51 @@ fn adapter_prepare(
 
 /// Interactive two-step flow helper for Python.
 ///
-/// Keeps prepared package context in memory and reuses it across replays.
-#[pyclass(name = "OrchestrationSession", module = "sui_sandbox")]
+/// Keeps prepared package context in memory and reuses it across replays. The
+/// session is safe to share across threads: the context is an immutable snapshot
+/// swapped atomically by `prepare()` / `load_context()`, and each `replay()` call
+/// writes it to its own temp file, so one prepared session can serve concurrent
+/// replays.
+#[pyclass(name = "OrchestrationSession", module = "sui_sandbox", frozen)]
 struct OrchestrationSession {
-    context: Option<serde_json::Value>,
+    state: std::sync::RwLock<OrchestrationSessionState>,
+}
+
+#[derive(Default)]
+struct OrchestrationSessionState {
+    context: Option<Arc<serde_json::Value>>,
     package_id: Option<String>,
 }
 
+impl OrchestrationSession {
+    fn read_state(&self) -> std::sync::RwLockReadGuard<'_, OrchestrationSessionState> {
+        self.state
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn set_context(&self, context: serde_json::Value, package_id: Option<String>) {
+        let mut state = self
+            .state
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        state.context = Some(Arc::new(context));
+        state.package_id = package_id;
+    }
+}
+
 #[pymethods]
 impl OrchestrationSession {
     #[new]
     fn new() -> Self {
         Self {
-            context: None,
-            package_id: None,
+            state: std::sync::RwLock::new(OrchestrationSessionState::default()),
         }
     }
 
     #[pyo3(signature = (package_id, *, resolve_deps=true, output_path=None))]
     fn prepare(
-        &mut self,
+        &self,
         py: Python<'_>,
         package_id: &str,
         resolve_deps: bool,
@@ -2735,15 +5115,15 @@ impl OrchestrationSession {
                     &package_id_owned,
                     resolve_deps,
                     output_path_owned.as_deref(),
+                    PackageFetchOptions::default(),
                 )
             })
             .map_err(to_py_err)?;
-        self.package_id = Some(package_id.to_string());
-        self.context = Some(value.clone());
+        self.set_context(value.clone(), Some(package_id.to_string()));
         json_value_to_py(py, &value)
     }
 
-    fn load_context(&mut self, py: Python<'_>, context_path: &str) -> PyResult<PyObject> {
+    fn load_context(&self, py: Python<'_>, context_path: &str) -> PyResult<PyObject> {
         let path = PathBuf::from(context_path);
         let raw = std::fs::read_to_string(&path)
             .with_context(|| format!("Failed to read context file {}", path.display()))
@@ -2751,16 +5131,16 @@ impl OrchestrationSession {
         let value: serde_json::Value = serde_json::from_str(&raw)
             .with_context(|| format!("Invalid context JSON in {}", path.display()))
             .map_err(to_py_err)?;
-        self.package_id = value
+        let package_id = value
             .get("package_id")
             .and_then(serde_json::Value::as_str)
             .map(ToOwned::to_owned);
-        self.context = Some(value.clone());
+        self.set_context(value.clone(), package_id);
         json_value_to_py(py, &value)
     }
 
     fn save_context(&self, context_path: &str) -> PyResult<()> {
-        let value = self.context.as_ref().ok_or_else(|| {
+        let value = self.read_state().context.clone().ok_or_else(|| {
             PyRuntimeError::new_err(
                 "OrchestrationSession has no context; call prepare() or load_context()",
             )
@@ -2777,7 +5157,7 @@ impl OrchestrationSession {
                 })?;
             }
         }
-        let serialized = serde_json::to_string_pretty(value).map_err(|e| {
+        let serialized = serde_json::to_string_pretty(value.as_ref()).map_err(|e| {
             PyRuntimeError::new_err(format!("Failed to serialize context payload: {}", e))
         })?;
         std::fs::write(&path, serialized).map_err(|e| {
@@ -2791,16 +5171,17 @@ impl OrchestrationSession {
     }
 
     fn has_context(&self) -> bool {
-        self.context.is_some()
+        self.read_state().context.is_some()
     }
 
     fn package_id(&self) -> Option<String> {
-        self.package_id.clone()
+        self.read_state().package_id.clone()
     }
 
     fn context(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
-        match self.context.as_ref() {
-            Some(value) => Ok(Some(json_value_to_py(py, value)?)),
+        let context = self.read_state().context.clone();
+        match context {
+            Some(value) => Ok(Some(json_value_to_py(py, &value)?)),
             None => Ok(None),
         }
     }
@@ -2860,23 +5241,29 @@ impl OrchestrationSession {
         analyze_mm2: bool,
         verbose: bool,
     ) -> PyResult<PyObject> {
-        let context_tmp = match self.context.as_ref() {
-            Some(value) => Some(write_temp_context_file(value).map_err(to_py_err)?),
+        // Snapshot the context so a concurrent prepare()/load_context() cannot change
+        // it mid-replay; the temp file is private to this call and removed on drop.
+        let (context, package_id) = {
+            let state = self.read_state();
+            (state.context.clone(), state.package_id.clone())
+        };
+        let context_tmp = match context {
+            Some(value) => Some(write_temp_context_file(&value).map_err(to_py_err)?),
             None => None,
         };
-        let result = replay_transaction(
+        replay_transaction(
             py,
             digest,
             checkpoint,
             discover_latest,
             if discover_latest.is_some() {
-                self.package_id.as_deref()
+                package_id.as_deref()
             } else {
                 None
             },
             source,
             state_file,
-            context_tmp.as_ref().and_then(|p| p.to_str()),
+            context_tmp.as_ref().and_then(|p| p.path().to_str()),
             cache_dir,
             walrus_network,
             walrus_caching_url,
@@ -2896,11 +5283,7 @@ impl OrchestrationSession {
             self_heal_dynamic_fields,
             analyze_mm2,
             verbose,
-        );
-        if let Some(path) = context_tmp {
-            let _ = std::fs::remove_file(path);
-        }
-        result
+        )
     }
 }
 
@@ -2925,9 +5308,19 @@ impl OrchestrationSession {
 ///     max_vector_len: Max length for generated vectors (default: 32)
 ///     dry_run: Only analyze signature, don't execute (default: False)
 ///     fetch_deps: Auto-resolve transitive deps via GraphQL (default: True)
+///     coverage: Coverage-guided mode; mutate inputs that reach new basic
+///         blocks (default: False)
+///     corpus_path: Corpus JSON file loaded before and saved after the run so
+///         campaigns can resume; implies coverage (default: None)
+///     invariants: Move functions ("0xPKG::module::function") that must hold
+///         after every successful iteration. Each is called with the target's
+///         inputs followed by its return values and is violated if it aborts
+///         or returns False (default: [])
 ///
 /// Returns: Dict with target, total_iterations, seed, outcomes, gas_profile,
-///          interesting_cases, etc. If dry_run=True, returns classification only.
+///          interesting_cases, etc. (plus coverage for coverage-guided runs).
+///          Violations are listed in outcomes.invariant_violations.
+///          If dry_run=True, returns classification only.
 #[pyfunction]
 #[pyo3(signature = (
     package_id,
@@ -2943,6 +5336,9 @@ impl OrchestrationSession {
     max_vector_len=32,
     dry_run=false,
     fetch_deps=true,
+    coverage=false,
+    corpus_path=None,
+    invariants=vec![],
 ))]
 fn fuzz_function(
     py: Python<'_>,
@@ -2958,6 +5354,9 @@ fn fuzz_function(
     max_vector_len: usize,
     dry_run: bool,
     fetch_deps: bool,
+    coverage: bool,
+    corpus_path: Option<String>,
+    invariants: Vec<String>,
 ) -> PyResult<PyObject> {
     let actual_seed = seed.unwrap_or_else(|| {
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -2986,6 +5385,9 @@ fn fuzz_function(
                 max_vector_len,
                 dry_run,
                 fetch_deps,
+                coverage,
+                corpus_path.as_deref(),
+                invariants,
             )
         })
         .map_err(to_py_err)?;
@@ -3385,6 +5787,7 @@ mod tests {
                 None,
                 None,
                 None,
+                ReplayStateFormat::Auto,
             )
             .expect("import synthetic replay state");
 
@@ -3440,6 +5843,7 @@ mod tests {
                 None,
                 None,
                 None,
+                ReplayStateFormat::Auto,
             )
             .expect("import synthetic replay state");
 
@@ -3490,6 +5894,7 @@ mod tests {
             "<inline>".to_string(),
             true,
             false,
+            1,
             None,
             "https://fullnode.mainnet.sui.io:443",
             "mainnet",
@@ -3581,6 +5986,1248 @@ mod tests {
         assert!(parsed.contains_key(&key));
         let _ = fs::remove_file(&tmp);
     }
+
+    #[test]
+    fn transaction_json_to_bcs_batch_isolates_item_errors() {
+        use sui_types::base_types::SuiAddress;
+        use sui_types::transaction::{ProgrammableTransaction, TransactionData, TransactionKind};
+
+        let tx_data = TransactionData::new_with_gas_coins(
+            TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![],
+                commands: vec![],
+            }),
+            SuiAddress::from(AccountAddress::ONE),
+            vec![],
+            123,
+            9,
+        );
+        let valid = serde_json::to_string(&tx_data).expect("serialize tx json");
+
+        let results = transaction_json_to_bcs_batch_inner(&[
+            "not json".to_string(),
+            valid,
+            "{\"V1\": {}}".to_string(),
+        ]);
+        assert_eq!(results.len(), 3);
+        assert!(!results[0].as_ref().unwrap_err().is_empty());
+        assert!(!results[1].as_ref().expect("valid item converts").is_empty());
+        assert!(results[2].is_err());
+    }
+
+    #[test]
+    fn transaction_kind_check_rejects_system_transactions_and_warns_when_unknown() {
+        let mut replay_state =
+            load_replay_state_from_file(&synthetic_state_fixture(), None).expect("state");
+        let warnings = WarningLog::new(false);
+
+        // A transaction with commands is programmable without a lookup.
+        ensure_programmable_transaction(&replay_state, None, None, &warnings).unwrap();
+        assert!(warnings.entries().is_empty());
+
+        // Without commands and without a way to look the kind up, warn instead of
+        // silently treating it as programmable.
+        replay_state.transaction.commands.clear();
+        ensure_programmable_transaction(&replay_state, None, None, &warnings).unwrap();
+        assert_eq!(warnings.count(WarningCategory::TxKind), 1);
+
+        let err = check_programmable_kind(
+            "0xd1",
+            Ok("ConsensusCommitPrologueV3".to_string()),
+            &warnings,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("ConsensusCommitPrologueV3"));
+        check_programmable_kind("0xd1", Ok("ProgrammableTransaction".to_string()), &warnings)
+            .unwrap();
+        check_programmable_kind("0xd1", Err(anyhow!("request timed out")), &warnings).unwrap();
+        assert_eq!(warnings.count(WarningCategory::TxKind), 2);
+        assert!(warnings.entries()[1].contains("request timed out"));
+    }
+
+    #[test]
+    fn cassette_metadata_round_trips_replay_options() {
+        use sui_sandbox_core::replay_support::{ObjectOwnershipHint, SharedInputHint};
+
+        let package = AccountAddress::from_hex_literal("0x42").unwrap();
+        let object = AccountAddress::from_hex_literal("0x5").unwrap();
+        let mut extras = ReplayExtras {
+            protocol_version: Some(70),
+            gas_profile: true,
+            network: Some(Network::Testnet),
+            instruction_trace: Some(PathBuf::from("trace.jsonl")),
+            ..ReplayExtras::default()
+        };
+        extras
+            .object_overrides
+            .rules
+            .insert("0x2::clock::Clock".to_string(), Some(vec![1, 2, 3]));
+        extras
+            .object_overrides
+            .rules
+            .insert("0x2::coin::Coin".to_string(), None);
+        extras
+            .package_overrides
+            .packages
+            .insert(package, vec![vec![0xa1, 0x1c, 0xeb, 0x0b]]);
+        extras
+            .ownership_hints
+            .hints
+            .insert(object, ObjectOwnershipHint::Shared);
+        extras.ownership_hints.shared_inputs.insert(
+            object,
+            SharedInputHint {
+                initial_shared_version: 5,
+                mutable: true,
+            },
+        );
+        let params = CassetteReplayParams {
+            digest: "digest".to_string(),
+            rpc_url: "https://fullnode.testnet.sui.io:443".to_string(),
+            source: "hybrid".to_string(),
+            checkpoint: None,
+            allow_fallback: true,
+            prefetch_depth: 3,
+            prefetch_limit: 200,
+            auto_system_objects: true,
+            no_prefetch: false,
+            synthesize_missing: false,
+            self_heal_dynamic_fields: false,
+            vm_only: false,
+            compare: true,
+            analyze_only: false,
+            analyze_mm2: false,
+            extras,
+        };
+
+        let metadata = serde_json::to_value(&params).unwrap();
+        assert_eq!(
+            metadata["extras"]["object_overrides"]["rules"]["0x2::clock::Clock"],
+            "AQID"
+        );
+        let restored: CassetteReplayParams = serde_json::from_value(metadata.clone()).unwrap();
+        let extras = &restored.extras;
+        assert_eq!(extras.protocol_version, Some(70));
+        assert!(extras.gas_profile);
+        assert_eq!(extras.network, Some(Network::Testnet));
+        assert_eq!(
+            extras.object_overrides.rules,
+            params.extras.object_overrides.rules
+        );
+        assert_eq!(
+            extras.package_overrides.packages,
+            params.extras.package_overrides.packages
+        );
+        assert_eq!(
+            extras.ownership_hints.hints[&object],
+            ObjectOwnershipHint::Shared
+        );
+        assert_eq!(
+            extras.ownership_hints.shared_inputs[&object].initial_shared_version,
+            5
+        );
+        assert!(
+            extras.instruction_trace.is_none(),
+            "local output paths are not recorded"
+        );
+
+        // Cassettes recorded before options were stored still load, with defaults.
+        let mut legacy = metadata;
+        legacy.as_object_mut().unwrap().remove("extras");
+        let legacy: CassetteReplayParams = serde_json::from_value(legacy).unwrap();
+        assert!(!legacy.extras.gas_profile);
+        assert!(legacy.extras.object_overrides.rules.is_empty());
+    }
+
+    #[test]
+    fn synthesize_view_coin_input_builds_owned_coin() {
+        let coin = synthesize_view_coin_input(None, None, 1_000, 2).expect("coin");
+        assert_eq!(coin.mode, ViewObjectMode::Owned);
+        assert_eq!(coin.bcs_bytes.len(), 40);
+        assert_eq!(&coin.bcs_bytes[32..], &1_000u64.to_le_bytes());
+        assert!(coin.type_tag.contains("::coin::Coin<"));
+        assert!(coin.type_tag.ends_with("::sui::SUI>"));
+        let id = AccountAddress::from_hex_literal(&coin.object_id).unwrap();
+        assert_eq!(&coin.bcs_bytes[..32], id.as_ref());
+
+        let other = synthesize_view_coin_input(None, None, 1, 3).expect("coin");
+        assert_ne!(coin.object_id, other.object_id);
+        assert!(synthesize_view_coin_input(None, Some("not a type"), 1, 0).is_err());
+    }
+
+    #[test]
+    fn view_call_arguments_follow_object_arg_index() {
+        // Inputs: objects 0 and 1, then pure inputs 2 and 3.
+        assert_eq!(
+            view_call_argument_order(&[None, None], 2).unwrap(),
+            vec![0, 1, 2, 3]
+        );
+        // (u64, &Pool, u8, &Clock): objects at parameters 1 and 3.
+        assert_eq!(
+            view_call_argument_order(&[Some(1), Some(3)], 2).unwrap(),
+            vec![2, 0, 3, 1]
+        );
+        // Unindexed objects fill the free slots before pure inputs.
+        assert_eq!(
+            view_call_argument_order(&[Some(2), None], 1).unwrap(),
+            vec![1, 2, 0]
+        );
+        assert!(view_call_argument_order(&[Some(1), Some(1)], 1).is_err());
+        assert!(view_call_argument_order(&[Some(2)], 1).is_err());
+    }
+
+    #[test]
+    fn view_call_batch_isolates_failing_calls() {
+        let call = |module: &str, function: &str| ViewCall {
+            package_id: "0x1".to_string(),
+            module: module.to_string(),
+            function: function.to_string(),
+            type_args: vec!["u64".to_string()],
+            pure_inputs: vec![],
+        };
+        let env = ViewCallEnv {
+            object_inputs: vec![],
+            child_objects: HashMap::new(),
+            historical_versions: HashMap::new(),
+            fetch_child_objects: false,
+            grpc_endpoint: None,
+            grpc_api_key: None,
+            package_bytecodes: PackageBytecodes::default(),
+            fetch_deps: false,
+            fetch_modules_only: false,
+            py_child_fetcher: None,
+            decode_returns: false,
+        };
+        let results = call_view_functions_inner(
+            vec![
+                call("option", "none"),
+                call("no_such_module", "none"),
+                call("option", "none"),
+            ],
+            env,
+        )
+        .expect("batch setup");
+
+        assert_eq!(results.len(), 3);
+        for (index, result) in results.iter().enumerate() {
+            assert_eq!(result["index"], index);
+            assert!(result["warnings"].is_array());
+        }
+        assert_eq!(results[0]["success"], true);
+        assert_eq!(results[0]["return_values"][0].as_array().unwrap().len(), 1);
+        assert_eq!(results[1]["success"], false);
+        assert!(!results[1]["error"].is_null());
+        assert_eq!(results[2]["success"], true);
+    }
+
+    #[test]
+    fn view_call_batch_isolates_invalid_targets_when_fetching_deps() {
+        let call = |package_id: &str, module: &str| ViewCall {
+            package_id: package_id.to_string(),
+            module: module.to_string(),
+            function: "none".to_string(),
+            type_args: vec!["u64".to_string()],
+            pure_inputs: vec![],
+        };
+        let env = ViewCallEnv {
+            object_inputs: vec![],
+            child_objects: HashMap::new(),
+            historical_versions: HashMap::new(),
+            fetch_child_objects: false,
+            grpc_endpoint: None,
+            grpc_api_key: None,
+            package_bytecodes: PackageBytecodes::default(),
+            fetch_deps: true,
+            fetch_modules_only: false,
+            py_child_fetcher: None,
+            decode_returns: false,
+        };
+        let results = call_view_functions_inner(
+            vec![
+                call("not-a-package", "option"),
+                call("0x1", "not a module"),
+                call("0x1", "option"),
+            ],
+            env,
+        )
+        .expect("invalid targets must not fail the batch");
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["success"], false);
+        assert!(results[0]["error"]
+            .as_str()
+            .unwrap()
+            .contains("not-a-package"));
+        assert_eq!(results[1]["success"], false);
+        assert_eq!(results[2]["success"], true);
+    }
+
+    #[test]
+    fn dependency_walk_merges_upgrade_aliases_and_reports_cycles() {
+        let addr = |hex: &str| AccountAddress::from_hex_literal(hex).unwrap();
+        let (storage, runtime, lib) = (addr("0xb2"), addr("0xa1"), addr("0xc3"));
+        let mut walk = DependencyWalk::new([addr("0x2")]);
+
+        // An upgraded package references its own original id and a library.
+        walk.push_root(storage);
+        assert_eq!(walk.next(), Some(storage));
+        walk.record_runtime_id(storage, runtime);
+        walk.push_dependency(storage, runtime);
+        walk.push_dependency(storage, lib);
+        walk.push_dependency(storage, addr("0x2"));
+
+        // The library links the original version, which is the same node as the upgrade
+        // but not a cycle back to it.
+        assert_eq!(walk.next(), Some(lib));
+        walk.push_dependency(lib, runtime);
+        assert_eq!(walk.next(), None);
+        assert!(walk.cycle_descriptions().is_empty());
+
+        // Diamond: two packages sharing a dependency revisit it without a cycle.
+        let (top, left, right, shared) = (addr("0xd1"), addr("0xd2"), addr("0xd3"), addr("0xd4"));
+        let mut walk = DependencyWalk::default();
+        walk.push_root(top);
+        assert_eq!(walk.next(), Some(top));
+        walk.push_dependency(top, left);
+        walk.push_dependency(top, right);
+        assert_eq!(walk.next(), Some(left));
+        walk.push_dependency(left, shared);
+        assert_eq!(walk.next(), Some(right));
+        walk.push_dependency(right, shared);
+        walk.push_dependency(right, left);
+        assert_eq!(walk.next(), Some(shared));
+        assert_eq!(walk.next(), None);
+        assert!(walk.cycle_descriptions().is_empty());
+
+        // A package linking back to its dependent by storage id is a real cycle.
+        let (a, b) = (addr("0xe1"), addr("0xe2"));
+        let mut walk = DependencyWalk::default();
+        walk.push_root(a);
+        assert_eq!(walk.next(), Some(a));
+        walk.push_dependency(a, b);
+        assert_eq!(walk.next(), Some(b));
+        walk.push_dependency(b, a);
+        assert_eq!(walk.cycle_descriptions(), vec!["0xe1 -> 0xe2 -> 0xe1"]);
+    }
+
+    #[test]
+    fn warning_log_clones_share_entries_and_attach_to_output() {
+        let warnings = WarningLog::new(false);
+        let in_fetcher = warnings.clone();
+        warnings.push(WarningCategory::Deps, "failed to fetch package 0xabc");
+        in_fetcher.push(
+            WarningCategory::SelfHeal,
+            format!("no dynamic field under parent {}", "0x1"),
+        );
+
+        let mut output = json!({ "local_success": true });
+        warnings.attach(&mut output);
+        assert_eq!(
+            output["warnings"],
+            json!([
+                "[deps] failed to fetch package 0xabc",
+                "[self_heal] no dynamic field under parent 0x1"
+            ])
+        );
+
+        let mut empty = json!({});
+        WarningLog::default().attach(&mut empty);
+        assert_eq!(empty["warnings"], json!([]));
+    }
+
+    #[test]
+    fn warning_log_fidelity_signals_follow_categories() {
+        let warnings = WarningLog::new(false);
+        warnings.push(
+            WarningCategory::SelfHeal,
+            "synthesized nothing; parent missing",
+        );
+        assert_eq!(
+            warnings.fidelity_signals(),
+            CoreReplayFidelitySignals::default()
+        );
+
+        warnings.push(
+            WarningCategory::SelfHealSynthesized,
+            "synthesized value for dynamic field under parent 0x5 (type u64)",
+        );
+        warnings.push(WarningCategory::Clock, "transaction timestamp unavailable");
+        let signals = warnings.fidelity_signals();
+        assert_eq!(signals.self_healed_values, 1);
+        assert!(signals.clock_defaulted);
+        assert_eq!(
+            warnings.entries()[1],
+            "[self_heal] synthesized value for dynamic field under parent 0x5 (type u64)"
+        );
+    }
+
+    #[test]
+    fn replay_result_cache_keys_on_state_and_settings() {
+        let replay_state = load_replay_state_from_file(&synthetic_state_fixture(), None)
+            .expect("load synthetic replay state");
+        let temp_dir = unique_temp_dir("sui_python_result_cache");
+        let cache = ReplayResultCache {
+            dir: temp_dir.clone(),
+        };
+        let extras = ReplayExtras::default();
+        let settings = json!({ "compare": false });
+
+        let entry = cache.entry(&replay_state, settings.clone(), &extras);
+        let warnings = WarningLog::new(false);
+        assert!(entry.load(&warnings).is_none());
+        let mut output = json!({ "local_success": true });
+        entry.store(&mut output);
+        assert_eq!(output["result_cache"]["hit"], false);
+        let cached = entry.load(&warnings).expect("stored entry");
+        assert_eq!(cached["local_success"], true);
+        assert_eq!(cached["result_cache"]["hit"], true);
+
+        let same = cache.entry(&replay_state, settings.clone(), &extras);
+        assert_eq!(same.key, entry.key);
+        let epoch = ReplayExtras {
+            epoch_override: Some(1),
+            ..ReplayExtras::default()
+        };
+        assert_ne!(
+            cache.entry(&replay_state, settings.clone(), &epoch).key,
+            entry.key
+        );
+        let pinned = ReplayExtras {
+            protocol_version: Some(replay_state.protocol_version),
+            ..ReplayExtras::default()
+        };
+        assert_ne!(
+            cache.entry(&replay_state, settings.clone(), &pinned).key,
+            entry.key
+        );
+        let mut upgraded = replay_state.clone();
+        upgraded.protocol_version += 1;
+        assert_ne!(
+            cache.entry(&upgraded, settings.clone(), &extras).key,
+            entry.key
+        );
+        assert_ne!(
+            cache
+                .entry(&replay_state, json!({ "compare": true }), &extras)
+                .key,
+            entry.key
+        );
+        let mut clock = replay_state.clone();
+        clock.transaction.timestamp_ms = Some(clock.transaction.timestamp_ms.unwrap_or(0) + 1);
+        assert_ne!(cache.entry(&clock, settings, &extras).key, entry.key);
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn network_selects_default_rpc_url_and_rejects_conflicts() {
+        let mainnet_default = Network::Mainnet.rpc_url();
+        assert_eq!(
+            resolve_network_rpc_url(&Network::Testnet, mainnet_default).unwrap(),
+            Network::Testnet.rpc_url()
+        );
+        assert_eq!(
+            resolve_network_rpc_url(&Network::Testnet, "http://127.0.0.1:9000").unwrap(),
+            "http://127.0.0.1:9000"
+        );
+        assert!(
+            resolve_network_rpc_url(&Network::Testnet, "https://fullnode.devnet.sui.io:443")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn replay_many_reports_per_digest_outcomes() {
+        let fixture = synthetic_state_fixture();
+        let digest = load_replay_state_from_file(&fixture, None)
+            .expect("load synthetic replay state")
+            .transaction
+            .digest
+            .0;
+        let cache_dir = unique_temp_dir("sui_python_replay_many");
+        import_replay_states(
+            &cache_dir,
+            &ImportSpec {
+                state: Some(fixture),
+                ..ImportSpec::default()
+            },
+        )
+        .expect("import fixture into local cache");
+        let extras = ReplayExtras {
+            offline: true,
+            ..ReplayExtras::default()
+        };
+        let options = ReplayManyOptions {
+            max_concurrency: 2,
+            allow_fallback: true,
+            prefetch_depth: 3,
+            prefetch_limit: 200,
+            auto_system_objects: true,
+            compare: false,
+            include_outputs: false,
+        };
+
+        let digests = vec![digest.clone(), "MissingDigest".to_string(), digest.clone()];
+        let report = replay_many_inner(
+            &digests,
+            ReplayAbSource::LocalCache(&cache_dir),
+            &options,
+            "http://127.0.0.1:9",
+            &extras,
+        )
+        .expect("replay many");
+        assert_eq!(report["runs"][0]["digest"], digest);
+        assert_eq!(report["runs"][0]["success"], true);
+        assert!(report["runs"][0].get("output").is_none());
+        assert_eq!(report["runs"][1]["success"], false);
+        assert!(report["runs"][1]["error"].is_string());
+        assert_eq!(report["summary"]["total"], 3);
+        assert_eq!(report["summary"]["success"], 2);
+        assert_eq!(report["summary"]["errored"], 1);
+        assert_eq!(report["summary"]["max_concurrency"], 2);
+        assert!(report["shared_cache"]["packages"].is_u64());
+        // Both replays of the fixture forked one shared framework resolver.
+        assert_eq!(report["shared_cache"]["framework_versions"], 1);
+
+        assert!(replay_many_inner(
+            &[],
+            ReplayAbSource::LocalCache(&cache_dir),
+            &options,
+            "http://127.0.0.1:9",
+            &extras,
+        )
+        .is_err());
+        let _ = fs::remove_dir_all(&cache_dir);
+    }
+
+    #[test]
+    fn bench_replay_reports_throughput_and_cache_hits() {
+        let fixture = synthetic_state_fixture();
+        let digest = load_replay_state_from_file(&fixture, None)
+            .expect("load synthetic replay state")
+            .transaction
+            .digest
+            .0;
+        let temp_dir = unique_temp_dir("sui_python_bench_replay");
+        let extras = ReplayExtras {
+            offline: true,
+            result_cache: Some(ReplayResultCache {
+                dir: temp_dir.clone(),
+            }),
+            ..ReplayExtras::default()
+        };
+        let options = ReplayBenchOptions {
+            profile: WorkflowReplayProfile::Balanced,
+            concurrency: 2,
+            repeats: 3,
+            warmup: 1,
+        };
+
+        let report = bench_replay_inner(
+            &[digest.clone()],
+            ReplayAbSource::StateFile(&fixture),
+            &options,
+            "http://127.0.0.1:9",
+            &extras,
+        )
+        .expect("bench replay");
+        assert_eq!(report["replays"], 3);
+        assert_eq!(report["succeeded"], 3);
+        assert_eq!(report["latency"]["count"], 3);
+        assert_eq!(report["per_digest"][0]["digest"], digest);
+        assert_eq!(
+            report["result_cache"]["hits"], 3,
+            "warmup pass fills the cache"
+        );
+        assert_eq!(report["fetches"]["total"], 0);
+        assert!(report["throughput_per_sec"].as_f64().unwrap() > 0.0);
+
+        let no_repeats = ReplayBenchOptions {
+            repeats: 0,
+            ..options
+        };
+        assert!(bench_replay_inner(
+            &[digest],
+            ReplayAbSource::StateFile(&fixture),
+            &no_repeats,
+            "http://127.0.0.1:9",
+            &extras,
+        )
+        .is_err());
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn traced_replay_bypasses_result_cache() {
+        let replay_state = load_replay_state_from_file(&synthetic_state_fixture(), None)
+            .expect("load synthetic replay state");
+        let temp_dir = unique_temp_dir("sui_python_traced_result_cache");
+        let run = |instruction_trace: Option<PathBuf>| {
+            let extras = ReplayExtras {
+                offline: true,
+                result_cache: Some(ReplayResultCache {
+                    dir: temp_dir.join("results"),
+                }),
+                instruction_trace,
+                ..ReplayExtras::default()
+            };
+            replay_loaded_state_inner(
+                replay_state.clone(),
+                "state_file",
+                "state_json",
+                None,
+                false,
+                true,
+                false,
+                true,
+                false,
+                false,
+                false,
+                false,
+                "http://127.0.0.1:9",
+                false,
+                &extras,
+            )
+            .expect("offline replay")
+        };
+
+        assert_eq!(run(None)["result_cache"]["hit"], false);
+        assert_eq!(run(None)["result_cache"]["hit"], true);
+
+        let trace_path = temp_dir.join("trace.jsonl");
+        let traced = run(Some(trace_path.clone()));
+        assert!(trace_path.exists());
+        assert!(traced.get("result_cache").is_none(), "{traced}");
+        assert_eq!(
+            traced["instruction_trace"]["path"],
+            trace_path.display().to_string()
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn offline_replay_runs_from_state_and_refuses_fetches() {
+        let replay_state = load_replay_state_from_file(&synthetic_state_fixture(), None)
+            .expect("load synthetic replay state");
+        let offline = ReplayExtras {
+            offline: true,
+            ..ReplayExtras::default()
+        };
+        let run = |state: ReplayState, self_heal: bool| {
+            replay_loaded_state_inner(
+                state,
+                "state_file",
+                "state_json",
+                None,
+                false,
+                true,
+                self_heal,
+                true,
+                false,
+                false,
+                false,
+                false,
+                "http://127.0.0.1:9",
+                false,
+                &offline,
+            )
+        };
+
+        let output = run(replay_state.clone(), false).expect("offline replay");
+        assert_eq!(output["local_success"], true);
+
+        let err = run(replay_state.clone(), true).unwrap_err().to_string();
+        assert!(err.starts_with("offline mode: cannot fetch dynamic fields"));
+
+        let mut unstamped = replay_state;
+        unstamped.checkpoint = Some(42);
+        let err = run(unstamped, false).unwrap_err().to_string();
+        assert!(err.contains("timestamp of checkpoint 42"));
+
+        let err = replay_inner(
+            "digest",
+            "http://127.0.0.1:9",
+            "hybrid",
+            None,
+            None,
+            false,
+            0,
+            0,
+            true,
+            true,
+            false,
+            false,
+            true,
+            false,
+            false,
+            false,
+            false,
+            &offline,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.starts_with("offline mode: cannot fetch replay state for digest"));
+    }
+
+    #[test]
+    fn expect_effects_hash_verifies_offline_replay_against_baseline() {
+        use sui_sandbox_core::tx_replay::{
+            GasSummary, TransactionEffectsSummary, TransactionStatus,
+        };
+
+        let replay_state = load_replay_state_from_file(&synthetic_state_fixture(), None)
+            .expect("load synthetic replay state");
+        let run = |state: ReplayState, expect_effects_hash: Option<&str>| {
+            let extras = ReplayExtras {
+                offline: true,
+                expect_effects_hash: expect_effects_hash.map(str::to_string),
+                ..ReplayExtras::default()
+            };
+            replay_loaded_state_inner(
+                state,
+                "state_file",
+                "state_json",
+                None,
+                false,
+                true,
+                false,
+                true,
+                false,
+                false,
+                false,
+                false,
+                "http://127.0.0.1:9",
+                false,
+                &extras,
+            )
+            .expect("offline replay")
+        };
+
+        let baseline = run(replay_state.clone(), None);
+        assert!(baseline.get("verified").is_none());
+        let fingerprint = baseline["local_effects_fingerprint"]
+            .as_str()
+            .expect("local_effects_fingerprint")
+            .to_string();
+
+        let matched = run(replay_state.clone(), Some(&fingerprint));
+        assert_eq!(matched["verified"], true);
+        assert_eq!(
+            matched["verification"]["local_effects_fingerprint"],
+            fingerprint.as_str()
+        );
+        assert!(matched["verification"].get("diff").is_none());
+
+        // A recorded run whose effects created an object this replay does not.
+        let mut diverged = replay_state;
+        diverged.transaction.effects = Some(TransactionEffectsSummary {
+            status: TransactionStatus::Success,
+            created: vec!["0xdead".to_string()],
+            mutated: vec![],
+            deleted: vec![],
+            wrapped: vec![],
+            unwrapped: vec![],
+            gas_used: GasSummary::default(),
+            events_count: 0,
+            shared_object_versions: HashMap::new(),
+            object_changes: Vec::new(),
+        });
+        let mismatched = run(diverged, Some("0xnot-the-baseline"));
+        assert_eq!(mismatched["verified"], false);
+        let verification = &mismatched["verification"];
+        assert_eq!(verification["expected_effects_hash"], "0xnot-the-baseline");
+        assert_eq!(
+            verification["local_effects_fingerprint"],
+            fingerprint.as_str()
+        );
+        assert_eq!(verification["diff_against"], "onchain");
+        let diff = verification["diff"].as_array().expect("diff");
+        assert!(
+            diff.iter().any(|field| field["field"] == "created"),
+            "{verification}"
+        );
+    }
+
+    #[test]
+    fn expected_effects_only_change_the_comparison() {
+        use sui_sandbox_core::replay_support::ReplayExpectedEffects;
+        use sui_sandbox_core::tx_replay::{
+            GasSummary, TransactionEffectsSummary, TransactionStatus,
+        };
+
+        let mut replay_state = load_replay_state_from_file(&synthetic_state_fixture(), None)
+            .expect("load synthetic replay state");
+        replay_state.transaction.effects = Some(TransactionEffectsSummary {
+            status: TransactionStatus::Success,
+            created: vec![],
+            mutated: vec![],
+            deleted: vec![],
+            wrapped: vec![],
+            unwrapped: vec![],
+            gas_used: GasSummary::default(),
+            events_count: 0,
+            shared_object_versions: HashMap::new(),
+            object_changes: Vec::new(),
+        });
+        let run = |expected_effects: Option<ReplayExpectedEffects>| {
+            let extras = ReplayExtras {
+                offline: true,
+                expected_effects,
+                ..ReplayExtras::default()
+            };
+            replay_loaded_state_inner(
+                replay_state.clone(),
+                "state_file",
+                "state_json",
+                None,
+                false,
+                true,
+                false,
+                true,
+                true,
+                false,
+                false,
+                false,
+                "http://127.0.0.1:9",
+                false,
+                &extras,
+            )
+            .expect("offline replay")
+        };
+
+        let own = run(None);
+        assert_eq!(own["comparison"]["created_match"], true, "{own}");
+        assert!(own.get("expected_effects").is_none());
+
+        let expected = ReplayExpectedEffects::from_json(&serde_json::json!({
+            "status": "Success",
+            "created": ["0xdead"],
+            "mutated": [],
+            "deleted": [],
+            "wrapped": [],
+            "unwrapped": [],
+            "gas_used": {
+                "computation_cost": 0,
+                "storage_cost": 0,
+                "storage_rebate": 0,
+                "non_refundable_storage_fee": 0
+            },
+            "events_count": 0,
+            "shared_object_versions": { "0x6": 9 }
+        }))
+        .expect("valid expected effects");
+        let supplied = run(Some(expected));
+        assert_eq!(supplied["comparison"]["created_match"], false, "{supplied}");
+        assert_eq!(
+            supplied["expected_effects"]["replaced_fetched_effects"],
+            true
+        );
+        // Execution never sees the supplied effects.
+        assert_eq!(supplied["effects"], own["effects"]);
+        assert_eq!(
+            supplied["local_effects_fingerprint"],
+            own["local_effects_fingerprint"]
+        );
+    }
+
+    #[test]
+    fn require_comparison_raises_a_distinct_error_kind() {
+        let replay_state = load_replay_state_from_file(&synthetic_state_fixture(), None)
+            .expect("load synthetic replay state");
+        let replayed = json!({ "local_success": true, "effects": {} });
+
+        let warnings = WarningLog::default();
+        let mut output = replayed.clone();
+        check_comparison_available(
+            &mut output,
+            &replay_state,
+            "state_json",
+            Some("state_file"),
+            false,
+            &warnings,
+        )
+        .expect("comparison is optional");
+        assert_eq!(
+            output["comparison_unavailable"]["reason"],
+            "no_effects_in_state"
+        );
+        assert_eq!(warnings.count(WarningCategory::Compare), 1);
+
+        let warnings = WarningLog::default();
+        let mut output = replayed;
+        let err = check_comparison_available(
+            &mut output,
+            &replay_state,
+            "state_json",
+            Some("state_file"),
+            true,
+            &warnings,
+        )
+        .unwrap_err();
+        let kind = sui_sandbox_core::SandboxError::classify(&err).kind();
+        assert_eq!(kind.code, "comparison_unavailable");
+        assert!(err
+            .to_string()
+            .starts_with("comparison unavailable (no_effects_in_state)"));
+        assert!(output.get("comparison_unavailable").is_none());
+        assert_eq!(warnings.count(WarningCategory::Compare), 0);
+    }
+
+    /// Bytecode for `address::name`, optionally referencing `dependency` (which the
+    /// resolver then reports as missing until it is loaded).
+    fn module_with_dependency(
+        address: AccountAddress,
+        name: &str,
+        dependency: Option<(AccountAddress, &str)>,
+    ) -> Vec<u8> {
+        use move_binary_format::file_format::{
+            empty_module, AddressIdentifierIndex, IdentifierIndex, ModuleHandle,
+        };
+        use move_binary_format::file_format_common::VERSION_6;
+
+        let mut module = empty_module();
+        module.version = VERSION_6;
+        module.identifiers[0] = Identifier::new(name).unwrap();
+        module.address_identifiers[0] = address;
+        if let Some((dep_address, dep_name)) = dependency {
+            module.address_identifiers.push(dep_address);
+            module.identifiers.push(Identifier::new(dep_name).unwrap());
+            module.module_handles.push(ModuleHandle {
+                address: AddressIdentifierIndex(1),
+                name: IdentifierIndex(1),
+            });
+        }
+        let mut bytes = Vec::new();
+        module
+            .serialize_with_version(VERSION_6, &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn dependency_closure_walk_follows_linkage_to_storage_ids() {
+        let app = AccountAddress::from_hex_literal("0xa11").unwrap();
+        let lib = AccountAddress::from_hex_literal("0xb22").unwrap();
+        let util = AccountAddress::from_hex_literal("0xc33").unwrap();
+        let util_v2 = AccountAddress::from_hex_literal("0xc34").unwrap();
+        let package = |address, modules, linkage: &[(AccountAddress, AccountAddress)]| {
+            sui_state_fetcher::PackageData {
+                address,
+                version: 1,
+                modules,
+                linkage: linkage.iter().copied().collect(),
+                original_id: None,
+            }
+        };
+
+        let mut resolver = sui_sandbox_core::resolver::LocalModuleResolver::new();
+        resolver
+            .add_package_modules(vec![(
+                "app".to_string(),
+                module_with_dependency(app, "app", Some((lib, "lib"))),
+            )])
+            .unwrap();
+
+        // `lib` links the upgraded `util`, so `util` must be asked for at 0xc34.
+        let mut requested = Vec::new();
+        let fetched = fetch_missing_dependencies_with(&mut resolver, |storage_id| {
+            requested.push(storage_id);
+            Ok(if storage_id == lib {
+                let bytes = module_with_dependency(lib, "lib", Some((util, "util")));
+                Some(package(
+                    lib,
+                    vec![("lib".to_string(), bytes)],
+                    &[(util, util_v2)],
+                ))
+            } else if storage_id == util_v2 {
+                let bytes = module_with_dependency(util, "util", None);
+                Some(package(util_v2, vec![("util".to_string(), bytes)], &[]))
+            } else {
+                None
+            })
+        })
+        .unwrap();
+
+        assert_eq!(fetched, 2);
+        assert_eq!(requested, vec![lib, util_v2]);
+        assert!(resolver.get_missing_dependencies().is_empty());
+        assert_eq!(resolver.get_alias(&util_v2), Some(util));
+
+        // A package the transport cannot serve stays missing and is not retried.
+        let mut resolver = sui_sandbox_core::resolver::LocalModuleResolver::new();
+        resolver
+            .add_package_modules(vec![(
+                "app".to_string(),
+                module_with_dependency(app, "app", Some((lib, "lib"))),
+            )])
+            .unwrap();
+        let mut calls = 0;
+        let fetched = fetch_missing_dependencies_with(&mut resolver, |_| {
+            calls += 1;
+            Ok(None)
+        })
+        .unwrap();
+        assert_eq!((fetched, calls), (0, 1));
+        assert!(resolver.get_missing_dependencies().contains(&lib));
+
+        let err = fetch_missing_dependencies_with(&mut resolver, |_| {
+            Err(anyhow!("gRPC error fetching object"))
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("gRPC error"));
+    }
+
+    #[test]
+    fn canonical_type_string_normalizes_nested_addresses() {
+        let long = "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<0x02::sui::SUI>";
+        assert_eq!(
+            canonical_type_string(long, false).unwrap(),
+            "0x2::coin::Coin<0x2::sui::SUI>"
+        );
+        assert_eq!(
+            canonical_type_string("vector<0x2::sui::SUI>", true).unwrap(),
+            "vector<0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI>"
+        );
+        assert_eq!(canonical_type_string(" u64 ", false).unwrap(), "u64");
+        assert!(canonical_type_string("0x2::coin::Coin<", false).is_err());
+    }
+
+    #[test]
+    fn temp_context_files_are_private_per_call() {
+        let payload = json!({ "package_id": "0x2", "packages": [] });
+        let files: Vec<TempContextFile> = std::thread::scope(|scope| {
+            let writers: Vec<_> = (0..32)
+                .map(|_| scope.spawn(|| write_temp_context_file(&payload)))
+                .collect();
+            writers
+                .into_iter()
+                .map(|writer| {
+                    writer
+                        .join()
+                        .expect("writer thread")
+                        .expect("write temp context")
+                })
+                .collect()
+        });
+        let paths: HashSet<PathBuf> = files.iter().map(|f| f.path().to_path_buf()).collect();
+        assert_eq!(paths.len(), files.len());
+        for file in &files {
+            let written: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(file.path()).expect("read temp context"))
+                    .expect("temp context json");
+            assert_eq!(written, payload);
+        }
+        drop(files);
+        assert!(paths.iter().all(|path| !path.exists()));
+    }
+
+    #[test]
+    fn async_variants_resolve_on_the_running_loop() {
+        Python::with_gil(|py| {
+            let globals = PyDict::new(py);
+            let batch = wrap_pyfunction!(call_view_functions_async, py).expect("wrap");
+            globals
+                .set_item("call_view_functions_async", batch)
+                .unwrap();
+            py.run(
+                pyo3::ffi::c_str!(
+                    r#"
+import asyncio
+import inspect
+
+params = inspect.signature(call_view_functions_async).parameters
+assert list(params)[:3] == ["package_id", "calls", "shared_object_inputs"], params
+assert params["fetch_deps"].kind is inspect.Parameter.KEYWORD_ONLY
+
+async def main():
+    calls = [{"module": "option", "function": "none", "type_args": ["u64"]}]
+    results = await asyncio.gather(
+        *(call_view_functions_async("0x1", calls, fetch_deps=False) for _ in range(3))
+    )
+    assert [r[0]["success"] for r in results] == [True, True, True], results
+    try:
+        await call_view_functions_async("0x1", [{"function": "none"}])
+    except RuntimeError as err:
+        assert "missing 'module'" in str(err), err
+    else:
+        raise AssertionError("expected RuntimeError")
+
+asyncio.run(main())
+try:
+    call_view_functions_async("0x1", [])
+except RuntimeError:
+    pass
+else:
+    raise AssertionError("expected RuntimeError outside an event loop")
+"#
+                ),
+                Some(&globals),
+                None,
+            )
+            .expect("async variants");
+        });
+    }
+
+    #[test]
+    fn flow_session_serves_concurrent_replays() {
+        let state_file = synthetic_state_fixture();
+        let state_file = state_file.to_str().expect("utf8 fixture path");
+        let context_file =
+            unique_temp_dir("sui_python_flow_session_context").with_extension("json");
+        let context =
+            json!({ "version": 2, "package_id": "0x2", "with_deps": false, "packages": [] });
+        fs::write(&context_file, context.to_string()).expect("write context");
+
+        let session = Python::with_gil(|py| -> PyResult<Py<OrchestrationSession>> {
+            let session = Py::new(py, OrchestrationSession::new())?;
+            session
+                .get()
+                .load_context(py, context_file.to_str().expect("utf8 context path"))?;
+            Ok(session)
+        })
+        .expect("session with context");
+
+        let digests: Vec<String> = std::thread::scope(|scope| {
+            let replays: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(|| {
+                        Python::with_gil(|py| {
+                            let output = session
+                                .get()
+                                .replay(
+                                    py,
+                                    None,
+                                    None,
+                                    None,
+                                    None,
+                                    Some(state_file),
+                                    None,
+                                    "mainnet",
+                                    None,
+                                    None,
+                                    "https://fullnode.mainnet.sui.io:443",
+                                    None,
+                                    None,
+                                    false,
+                                    true,
+                                    3,
+                                    200,
+                                    true,
+                                    false,
+                                    false,
+                                    false,
+                                    false,
+                                    false,
+                                    false,
+                                    false,
+                                )
+                                .expect("concurrent session replay");
+                            let output =
+                                py_json_value(py, output.bind(py)).expect("replay output json");
+                            assert_eq!(output["local_success"], true);
+                            output["digest"].as_str().unwrap_or_default().to_string()
+                        })
+                    })
+                })
+                .collect();
+            replays
+                .into_iter()
+                .map(|replay| replay.join().expect("replay thread"))
+                .collect()
+        });
+
+        assert!(!digests[0].is_empty());
+        assert!(digests.iter().all(|digest| digest == &digests[0]));
+        let _ = fs::remove_file(&context_file);
+    }
+
+    #[test]
+    fn simulation_reports_effects_and_gas_estimate_of_hydrated_state() {
+        use sui_sandbox_types::{
+            FetchedTransaction, PtbArgument, PtbCommand, TransactionDigest, TransactionInput,
+        };
+        let sender = AccountAddress::from_hex_literal("0xa11ce").unwrap();
+        let coin = AccountAddress::from_hex_literal("0xc0ffee").unwrap();
+        let mut coin_bytes = coin.to_vec();
+        coin_bytes.extend_from_slice(&1_000u64.to_le_bytes());
+        let state = ReplayState {
+            transaction: FetchedTransaction {
+                digest: TransactionDigest::new("simulated"),
+                sender,
+                gas_budget: 50_000_000,
+                gas_price: 1_000,
+                commands: vec![
+                    PtbCommand::SplitCoins {
+                        coin: PtbArgument::Input { index: 0 },
+                        amounts: vec![PtbArgument::Input { index: 1 }],
+                    },
+                    PtbCommand::TransferObjects {
+                        objects: vec![PtbArgument::Result { index: 0 }],
+                        address: PtbArgument::Input { index: 2 },
+                    },
+                ],
+                inputs: vec![
+                    TransactionInput::Object {
+                        object_id: coin.to_hex_literal(),
+                        version: 10,
+                        digest: String::new(),
+                    },
+                    TransactionInput::Pure {
+                        bytes: 300u64.to_le_bytes().to_vec(),
+                    },
+                    TransactionInput::Pure {
+                        bytes: sender.to_vec(),
+                    },
+                ],
+                effects: None,
+                timestamp_ms: Some(1_700_000_000_000),
+                checkpoint: Some(100),
+                gas_payment: vec![],
+            },
+            objects: HashMap::from([(
+                coin,
+                sui_state_fetcher::VersionedObject {
+                    id: coin,
+                    version: 10,
+                    digest: None,
+                    type_tag: Some("0x2::coin::Coin<0x2::sui::SUI>".to_string()),
+                    bcs_bytes: coin_bytes,
+                    is_shared: false,
+                    is_immutable: false,
+                },
+            )]),
+            packages: HashMap::new(),
+            protocol_version: 0,
+            epoch: 300,
+            reference_gas_price: Some(1_000),
+            checkpoint: Some(100),
+        };
+
+        let output = simulate_api::simulate_replay_state(&state).expect("simulate");
+        assert_eq!(output["checkpoint"], 100);
+        assert_eq!(output["epoch"], 300);
+        assert!(output["protocol_version"].is_null());
+        assert_eq!(output["objects_loaded"], 1);
+        assert_eq!(output["effects"]["success"], true, "{}", output["effects"]);
+        assert_eq!(
+            output["effects"]["created"].as_array().map(Vec::len),
+            Some(1)
+        );
+        assert!(output["effects"]["mutated"]
+            .as_array()
+            .is_some_and(|ids| ids.iter().any(|id| id == &json!(coin.to_hex_literal()))));
+
+        let gas = &output["gas_estimate"];
+        let computation = gas["computation_cost"].as_u64().expect("computation cost");
+        let storage = gas["storage_cost"].as_u64().expect("storage cost");
+        let rebate = gas["storage_rebate"].as_u64().expect("storage rebate");
+        assert!(computation > 0);
+        assert_eq!(
+            gas["net_gas_cost"].as_i64(),
+            Some(computation as i64 + storage as i64 - rebate as i64)
+        );
+        assert_eq!(gas["within_budget"], true);
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -3,6 +3,8 @@ use super::*;
 pub(crate) fn register_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     m.add_function(wrap_pyfunction!(extract_interface, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_call_targets, m)?)?;
+    m.add_function(wrap_pyfunction!(package_types, m)?)?;
     m.add_function(wrap_pyfunction!(get_latest_checkpoint, m)?)?;
     m.add_function(wrap_pyfunction!(get_checkpoint, m)?)?;
     m.add_function(wrap_pyfunction!(doctor, m)?)?;
@@ -23,24 +25,48 @@ pub(crate) fn register_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(pipeline_auto, m)?)?;
     m.add_function(wrap_pyfunction!(pipeline_run, m)?)?;
     m.add_function(wrap_pyfunction!(pipeline_run_inline, m)?)?;
+    m.add_function(wrap_pyfunction!(pipeline_watch, m)?)?;
     m.add_function(wrap_pyfunction!(workflow_validate, m)?)?;
     m.add_function(wrap_pyfunction!(workflow_init, m)?)?;
     m.add_function(wrap_pyfunction!(workflow_auto, m)?)?;
     m.add_function(wrap_pyfunction!(workflow_run, m)?)?;
     m.add_function(wrap_pyfunction!(workflow_run_inline, m)?)?;
+    m.add_function(wrap_pyfunction!(workflow_watch, m)?)?;
     m.add_function(wrap_pyfunction!(fetch_object_bcs, m)?)?;
+    m.add_function(wrap_pyfunction!(fetch_object_bcs_async, m)?)?;
+    m.add_function(wrap_pyfunction!(list_dynamic_fields, m)?)?;
     m.add_function(wrap_pyfunction!(fetch_historical_package_bytecodes, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        fetch_historical_package_bytecodes_async,
+        m
+    )?)?;
     m.add_function(wrap_pyfunction!(import_state, m)?)?;
+    m.add_function(wrap_pyfunction!(export_state, m)?)?;
+    m.add_function(wrap_pyfunction!(cache_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(cache_clear, m)?)?;
     m.add_function(wrap_pyfunction!(deserialize_transaction, m)?)?;
     m.add_function(wrap_pyfunction!(deserialize_package, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_package_id, m)?)?;
     m.add_function(wrap_pyfunction!(fetch_package_bytecodes, m)?)?;
+    m.add_function(wrap_pyfunction!(stream_package_modules, m)?)?;
+    m.add_function(wrap_pyfunction!(subscribe_checkpoints, m)?)?;
     m.add_function(wrap_pyfunction!(prepare_package_context, m)?)?;
     m.add_function(wrap_pyfunction!(context_prepare, m)?)?;
     m.add_function(wrap_pyfunction!(protocol_prepare, m)?)?;
     m.add_function(wrap_pyfunction!(adapter_prepare, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_type, m)?)?;
+    m.add_function(wrap_pyfunction!(canonicalize_type, m)?)?;
     m.add_function(wrap_pyfunction!(json_to_bcs, m)?)?;
     m.add_function(wrap_pyfunction!(transaction_json_to_bcs, m)?)?;
+    m.add_function(wrap_pyfunction!(transaction_json_to_bcs_verbose, m)?)?;
+    m.add_function(wrap_pyfunction!(transaction_json_to_bcs_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate_transaction, m)?)?;
     m.add_function(wrap_pyfunction!(call_view_function, m)?)?;
+    m.add_function(wrap_pyfunction!(call_view_functions, m)?)?;
+    m.add_function(wrap_pyfunction!(call_view_function_async, m)?)?;
+    m.add_function(wrap_pyfunction!(call_view_functions_async, m)?)?;
+    m.add_function(wrap_pyfunction!(plan_child_fetches, m)?)?;
+    m.add_function(wrap_pyfunction!(build_call_inputs, m)?)?;
     m.add_function(wrap_pyfunction!(historical_view_from_versions, m)?)?;
     m.add_function(wrap_pyfunction!(historical_series_from_points, m)?)?;
     m.add_function(wrap_pyfunction!(historical_series_from_files, m)?)?;
@@ -48,13 +74,26 @@ pub(crate) fn register_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(historical_decode_return_u64s, m)?)?;
     m.add_function(wrap_pyfunction!(historical_decode_returns_typed, m)?)?;
     m.add_function(wrap_pyfunction!(historical_decode_with_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(protocol_decode, m)?)?;
+    m.add_function(wrap_pyfunction!(protocol_decoders, m)?)?;
+    m.add_function(wrap_pyfunction!(protocol_adapters, m)?)?;
     m.add_function(wrap_pyfunction!(fuzz_function, m)?)?;
     m.add_function(wrap_pyfunction!(replay, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_async, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_from_cassette, m)?)?;
     m.add_function(wrap_pyfunction!(replay_transaction, m)?)?;
+    m.add_function(wrap_pyfunction!(discover_and_replay, m)?)?;
     m.add_function(wrap_pyfunction!(analyze_replay, m)?)?;
     m.add_function(wrap_pyfunction!(replay_analyze, m)?)?;
     m.add_function(wrap_pyfunction!(replay_effects, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_ab, m)?)?;
+    m.add_function(wrap_pyfunction!(bench_replay, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_many, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_many_async, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_checkpoint, m)?)?;
     m.add_function(wrap_pyfunction!(classify_replay_result, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_feasibility, m)?)?;
+    m.add_function(wrap_pyfunction!(replay_capabilities, m)?)?;
     m.add_function(wrap_pyfunction!(dynamic_field_diagnostics, m)?)?;
     m.add_function(wrap_pyfunction!(context_replay, m)?)?;
     m.add_function(wrap_pyfunction!(context_run, m)?)?;
@@ -64,5 +103,10 @@ pub(crate) fn register_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     let orchestration_session = m.getattr("OrchestrationSession")?;
     m.add("FlowSession", orchestration_session.clone())?;
     m.add("ContextSession", orchestration_session)?;
+    m.add_class::<AnalyzedTransaction>()?;
+    m.add_class::<ForkSession>()?;
+    m.add_class::<PtbBuilder>()?;
+    m.add_class::<PackageModuleIterator>()?;
+    m.add_class::<CheckpointSubscription>()?;
     Ok(())
 }
@@ -0,0 +1,463 @@
+use super::*;
+
+use fork_api::{execute_on_fork, input_object_id, parse_fork_address};
+use pyo3::types::PyBool;
+use sui_sandbox_core::fork::ForkSession as CoreForkSession;
+use sui_sandbox_core::utilities::encode_pure_argument;
+use sui_sandbox_types::{
+    FetchedTransaction, PtbArgument, PtbCommand, TransactionDigest, TransactionInput,
+};
+use sui_state_fetcher::VersionedObject;
+
+/// Gas budget of PTBs executed against a FlowSession (no origin transaction to inherit from).
+const FLOW_GAS_BUDGET: u64 = 50_000_000_000;
+const FLOW_GAS_PRICE: u64 = 1_000;
+
+/// Composes a multi-command PTB (SplitCoins, MergeCoins, MoveCall, TransferObjects,
+/// MakeMoveVec) with result-argument wiring.
+///
+/// Input and command methods return argument handles (`{"type": "Input", ...}`,
+/// `{"type": "Result", ...}`) to pass to later commands; `nested()` selects one
+/// value of a multi-value result. `build()` returns the PTB in the state-file
+/// `inputs`/`commands` schema accepted by `ForkSession.execute_ptb()`.
+#[pyclass(name = "PtbBuilder", module = "sui_sandbox")]
+#[derive(Default)]
+pub(super) struct PtbBuilder {
+    inputs: Vec<TransactionInput>,
+    commands: Vec<PtbCommand>,
+    /// Object contents given to `object()`, used to hydrate a FlowSession fork.
+    objects: Vec<VersionedObject>,
+}
+
+fn argument_to_py(py: Python<'_>, argument: &PtbArgument) -> PyResult<PyObject> {
+    let value = serde_json::to_value(argument)
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize argument: {}", e)))?;
+    json_value_to_py(py, &value)
+}
+
+impl PtbBuilder {
+    fn push_input(&mut self, input: TransactionInput) -> Result<PtbArgument> {
+        let index = u16::try_from(self.inputs.len())
+            .map_err(|_| anyhow!("PTB has too many inputs (max {})", u16::MAX))?;
+        self.inputs.push(input);
+        Ok(PtbArgument::Input { index })
+    }
+
+    fn push_command(&mut self, command: PtbCommand) -> Result<PtbArgument> {
+        let index = u16::try_from(self.commands.len())
+            .map_err(|_| anyhow!("PTB has too many commands (max {})", u16::MAX))?;
+        self.commands.push(command);
+        Ok(PtbArgument::Result { index })
+    }
+
+    /// Parse an argument handle; ints become pure `u64` inputs and strings pure
+    /// `address` inputs.
+    fn argument(
+        &mut self,
+        py: Python<'_>,
+        value: &Bound<'_, PyAny>,
+        what: &str,
+    ) -> Result<PtbArgument> {
+        if !value.is_instance_of::<PyBool>() {
+            if let Ok(amount) = value.extract::<u64>() {
+                return self.push_input(TransactionInput::Pure {
+                    bytes: amount.to_le_bytes().to_vec(),
+                });
+            }
+        }
+        if let Ok(address) = value.extract::<String>() {
+            let address = parse_fork_address(&address).with_context(|| what.to_string())?;
+            return self.push_input(TransactionInput::Pure {
+                bytes: address.to_vec(),
+            });
+        }
+        let argument: PtbArgument = serde_json::from_value(py_json_value(py, value)?)
+            .with_context(|| {
+                format!(
+                    "{} must be an argument handle from PtbBuilder, an int (u64) or an address",
+                    what
+                )
+            })?;
+        let in_range = match &argument {
+            PtbArgument::Input { index } => usize::from(*index) < self.inputs.len(),
+            PtbArgument::Result { index } | PtbArgument::NestedResult { index, .. } => {
+                usize::from(*index) < self.commands.len()
+            }
+            PtbArgument::GasCoin => true,
+        };
+        if !in_range {
+            return Err(anyhow!(
+                "{} refers to an input or command this PtbBuilder doesn't have",
+                what
+            ));
+        }
+        Ok(argument)
+    }
+
+    fn arguments(
+        &mut self,
+        py: Python<'_>,
+        values: &Bound<'_, PyAny>,
+        what: &str,
+    ) -> Result<Vec<PtbArgument>> {
+        values
+            .try_iter()
+            .with_context(|| format!("{} must be a list", what))?
+            .enumerate()
+            .map(|(i, value)| self.argument(py, &value?, &format!("{}[{}]", what, i)))
+            .collect()
+    }
+
+    /// One-shot fork with the session's prepared packages and the objects given to
+    /// `object()`, which are owned by `sender`.
+    fn flow_fork(
+        &self,
+        session: &OrchestrationSession,
+        sender: AccountAddress,
+    ) -> Result<CoreForkSession> {
+        let context = session.read_state().context.clone().ok_or_else(|| {
+            anyhow!("FlowSession has no context; call prepare() or load_context()")
+        })?;
+        let parsed =
+            parse_context_payload(&context).context("Invalid FlowSession context payload")?;
+        let packages = context_packages_to_package_data(&parsed.packages)?;
+        for input in &self.inputs {
+            if let Some(object_id) = input_object_id(input) {
+                let id = parse_fork_address(object_id)?;
+                if !self.objects.iter().any(|object| object.id == id) {
+                    return Err(anyhow!(
+                        "object {} has no contents; pass bcs_bytes and type_tag to object() \
+                         to execute against a FlowSession",
+                        object_id
+                    ));
+                }
+            }
+        }
+        let state = ReplayState {
+            transaction: FetchedTransaction {
+                digest: TransactionDigest("ptb-builder".to_string()),
+                sender,
+                gas_budget: FLOW_GAS_BUDGET,
+                gas_price: FLOW_GAS_PRICE,
+                commands: vec![],
+                inputs: vec![],
+                effects: None,
+                timestamp_ms: None,
+                checkpoint: None,
+                gas_payment: vec![],
+            },
+            objects: self
+                .objects
+                .iter()
+                .map(|object| (object.id, object.clone()))
+                .collect(),
+            packages,
+            protocol_version: 0,
+            epoch: 0,
+            reference_gas_price: None,
+            checkpoint: None,
+        };
+        CoreForkSession::from_replay_state(&state)
+    }
+}
+
+#[pymethods]
+impl PtbBuilder {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pure input: raw BCS `bytes`, or a JSON-shaped value encoded as `type_tag`
+    /// (e.g. `pure(100, "u64")`, `pure("0x2", "address")`, `pure([1, 2], "vector<u8>")`).
+    #[pyo3(signature = (value, type_tag=None))]
+    fn pure(
+        &mut self,
+        py: Python<'_>,
+        value: &Bound<'_, PyAny>,
+        type_tag: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let bytes = match type_tag {
+            None => value.extract::<Vec<u8>>().map_err(|_| {
+                PyRuntimeError::new_err("pure() needs type_tag unless value is BCS bytes")
+            })?,
+            Some(type_str) => {
+                let tag = sui_sandbox_core::types::parse_type_tag(type_str).map_err(|e| {
+                    PyRuntimeError::new_err(format!("invalid type tag '{}': {}", type_str, e))
+                })?;
+                let json = py_json_value(py, value).map_err(to_py_err)?;
+                encode_pure_argument(&tag, &json, &[]).map_err(to_py_err)?
+            }
+        };
+        let argument = self
+            .push_input(TransactionInput::Pure { bytes })
+            .map_err(to_py_err)?;
+        argument_to_py(py, &argument)
+    }
+
+    /// Object input (owned by default). Repeating an object ID returns its existing handle.
+    ///
+    /// `version` is only a hint: ForkSession resolves objects by ID to their
+    /// current version. `bcs_bytes` and `type_tag` give the object's contents when
+    /// executing against a FlowSession, which holds packages but no objects.
+    #[pyo3(signature = (
+        object_id,
+        *,
+        shared=false,
+        mutable=true,
+        immutable=false,
+        receiving=false,
+        version=None,
+        bcs_bytes=None,
+        type_tag=None,
+    ))]
+    fn object(
+        &mut self,
+        py: Python<'_>,
+        object_id: &str,
+        shared: bool,
+        mutable: bool,
+        immutable: bool,
+        receiving: bool,
+        version: Option<u64>,
+        bcs_bytes: Option<Vec<u8>>,
+        type_tag: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let id = parse_fork_address(object_id).map_err(to_py_err)?;
+        if [shared, immutable, receiving]
+            .iter()
+            .filter(|&&f| f)
+            .count()
+            > 1
+        {
+            return Err(PyRuntimeError::new_err(
+                "object() accepts at most one of shared, immutable, receiving",
+            ));
+        }
+        let existing = self.inputs.iter().position(|input| {
+            input_object_id(input)
+                .and_then(|existing| parse_fork_address(existing).ok())
+                .is_some_and(|existing| existing == id)
+        });
+        if let Some(index) = existing {
+            return argument_to_py(
+                py,
+                &PtbArgument::Input {
+                    index: index as u16,
+                },
+            );
+        }
+
+        let object_id = id.to_hex_literal();
+        let input_version = version.unwrap_or(0);
+        let input = if shared {
+            TransactionInput::SharedObject {
+                object_id,
+                initial_shared_version: input_version,
+                mutable,
+            }
+        } else if immutable {
+            TransactionInput::ImmutableObject {
+                object_id,
+                version: input_version,
+                digest: String::new(),
+            }
+        } else if receiving {
+            TransactionInput::Receiving {
+                object_id,
+                version: input_version,
+                digest: String::new(),
+            }
+        } else {
+            TransactionInput::Object {
+                object_id,
+                version: input_version,
+                digest: String::new(),
+            }
+        };
+        if let Some(bcs_bytes) = bcs_bytes {
+            let type_tag = type_tag.ok_or_else(|| {
+                PyRuntimeError::new_err("object() needs type_tag together with bcs_bytes")
+            })?;
+            self.objects.push(VersionedObject {
+                id,
+                version: version.unwrap_or(1),
+                digest: None,
+                type_tag: Some(type_tag.to_string()),
+                bcs_bytes,
+                is_shared: shared,
+                is_immutable: immutable,
+            });
+        }
+        let argument = self.push_input(input).map_err(to_py_err)?;
+        argument_to_py(py, &argument)
+    }
+
+    /// The gas coin.
+    fn gas(&self, py: Python<'_>) -> PyResult<PyObject> {
+        argument_to_py(py, &PtbArgument::GasCoin)
+    }
+
+    /// Value `index` of a multi-value command result.
+    fn nested(&self, py: Python<'_>, result: &Bound<'_, PyAny>, index: u16) -> PyResult<PyObject> {
+        let argument: PtbArgument =
+            serde_json::from_value(py_json_value(py, result).map_err(to_py_err)?)
+                .map_err(|_| PyRuntimeError::new_err("nested() needs a result handle"))?;
+        let PtbArgument::Result { index: command } = argument else {
+            return Err(PyRuntimeError::new_err("nested() needs a result handle"));
+        };
+        argument_to_py(
+            py,
+            &PtbArgument::NestedResult {
+                index: command,
+                result_index: index,
+            },
+        )
+    }
+
+    /// SplitCoins; use `nested(result, i)` for the i-th new coin.
+    fn split_coins(
+        &mut self,
+        py: Python<'_>,
+        coin: &Bound<'_, PyAny>,
+        amounts: &Bound<'_, PyAny>,
+    ) -> PyResult<PyObject> {
+        let coin = self.argument(py, coin, "coin").map_err(to_py_err)?;
+        let amounts = self.arguments(py, amounts, "amounts").map_err(to_py_err)?;
+        let result = self
+            .push_command(PtbCommand::SplitCoins { coin, amounts })
+            .map_err(to_py_err)?;
+        argument_to_py(py, &result)
+    }
+
+    fn merge_coins(
+        &mut self,
+        py: Python<'_>,
+        destination: &Bound<'_, PyAny>,
+        sources: &Bound<'_, PyAny>,
+    ) -> PyResult<PyObject> {
+        let destination = self
+            .argument(py, destination, "destination")
+            .map_err(to_py_err)?;
+        let sources = self.arguments(py, sources, "sources").map_err(to_py_err)?;
+        let result = self
+            .push_command(PtbCommand::MergeCoins {
+                destination,
+                sources,
+            })
+            .map_err(to_py_err)?;
+        argument_to_py(py, &result)
+    }
+
+    fn transfer_objects(
+        &mut self,
+        py: Python<'_>,
+        objects: &Bound<'_, PyAny>,
+        address: &Bound<'_, PyAny>,
+    ) -> PyResult<PyObject> {
+        let objects = self.arguments(py, objects, "objects").map_err(to_py_err)?;
+        let address = self.argument(py, address, "address").map_err(to_py_err)?;
+        let result = self
+            .push_command(PtbCommand::TransferObjects { objects, address })
+            .map_err(to_py_err)?;
+        argument_to_py(py, &result)
+    }
+
+    #[pyo3(signature = (package, module, function, args=None, type_args=None))]
+    fn move_call(
+        &mut self,
+        py: Python<'_>,
+        package: &str,
+        module: &str,
+        function: &str,
+        args: Option<&Bound<'_, PyAny>>,
+        type_args: Option<Vec<String>>,
+    ) -> PyResult<PyObject> {
+        let arguments = match args {
+            Some(args) => self.arguments(py, args, "args").map_err(to_py_err)?,
+            None => vec![],
+        };
+        let result = self
+            .push_command(PtbCommand::MoveCall {
+                package: package.to_string(),
+                module: module.to_string(),
+                function: function.to_string(),
+                type_arguments: type_args.unwrap_or_default(),
+                arguments,
+            })
+            .map_err(to_py_err)?;
+        argument_to_py(py, &result)
+    }
+
+    #[pyo3(signature = (elements, type_tag=None))]
+    fn make_move_vec(
+        &mut self,
+        py: Python<'_>,
+        elements: &Bound<'_, PyAny>,
+        type_tag: Option<String>,
+    ) -> PyResult<PyObject> {
+        let elements = self
+            .arguments(py, elements, "elements")
+            .map_err(to_py_err)?;
+        let result = self
+            .push_command(PtbCommand::MakeMoveVec {
+                type_arg: type_tag,
+                elements,
+            })
+            .map_err(to_py_err)?;
+        argument_to_py(py, &result)
+    }
+
+    /// The PTB so far as `{"inputs": [...], "commands": [...]}`.
+    fn build(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let value = serde_json::json!({
+            "inputs": self.inputs,
+            "commands": self.commands,
+        });
+        json_value_to_py(py, &value)
+    }
+
+    /// Execute the PTB against a ForkSession (mutating it) or a prepared FlowSession.
+    ///
+    /// A FlowSession runs the PTB once on a fresh fork of its packages plus the
+    /// objects given to `object()` with `bcs_bytes`; nothing persists afterwards.
+    ///
+    /// Returns: the same dict as `ForkSession.execute_ptb()`
+    #[pyo3(signature = (target, *, sender=None))]
+    fn execute(
+        &self,
+        py: Python<'_>,
+        target: &Bound<'_, PyAny>,
+        sender: Option<&str>,
+    ) -> PyResult<PyObject> {
+        let sender = sender
+            .map(parse_fork_address)
+            .transpose()
+            .map_err(to_py_err)?;
+        if let Ok(fork) = target.downcast::<ForkSession>() {
+            let mut fork = fork.borrow_mut();
+            return execute_on_fork(
+                py,
+                &mut fork.fork,
+                self.inputs.clone(),
+                self.commands.clone(),
+                sender,
+            );
+        }
+        if let Ok(session) = target.downcast::<OrchestrationSession>() {
+            let mut fork = self
+                .flow_fork(session.get(), sender.unwrap_or(AccountAddress::ZERO))
+                .map_err(to_py_err)?;
+            return execute_on_fork(
+                py,
+                &mut fork,
+                self.inputs.clone(),
+                self.commands.clone(),
+                sender,
+            );
+        }
+        Err(PyRuntimeError::new_err(
+            "execute() target must be a ForkSession or FlowSession",
+        ))
+    }
+}
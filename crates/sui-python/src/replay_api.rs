@@ -7,7 +7,7 @@ use super::*;
 ///     checkpoint: Optional checkpoint (if provided and source is omitted, source defaults to walrus)
 ///     discover_latest: Auto-discover digest from latest N checkpoints (requires discover_package_id)
 ///     discover_package_id: Package filter used for discovery when discover_latest is set
-///     source: "hybrid", "grpc", or "walrus" (default: inferred)
+///     source: "hybrid", "grpc", "walrus", or "jsonrpc" (default: inferred)
 ///     state_file: Optional replay-state JSON for deterministic local input data
 ///     context_path: Optional prepared package context JSON to pre-seed package bytecode
 ///     cache_dir: Optional local replay cache when source="local"
@@ -109,17 +109,18 @@ pub(super) fn replay_transaction(
             "hybrid".to_string()
         }
     });
-    replay(
-        py,
-        effective_digest.as_deref(),
-        rpc_url,
-        &source_owned,
-        effective_checkpoint,
-        state_file,
-        context_path,
-        cache_dir,
-        profile,
-        fetch_strategy,
+    let request = ReplayRequest {
+        digest: effective_digest,
+        rpc_url: rpc_url.to_string(),
+        source: source_owned,
+        checkpoint: effective_checkpoint,
+        state_file: state_file.map(PathBuf::from),
+        state_format: ReplayStateFormat::default(),
+        context_path: context_path.map(PathBuf::from),
+        cache_dir: cache_dir.map(PathBuf::from),
+        record_requests: None,
+        profile: profile.map(ToOwned::to_owned),
+        fetch_strategy: fetch_strategy.map(ToOwned::to_owned),
         vm_only,
         allow_fallback,
         prefetch_depth,
@@ -132,7 +133,219 @@ pub(super) fn replay_transaction(
         self_heal_dynamic_fields,
         analyze_mm2,
         verbose,
-    )
+    };
+    let value = run_replay(py, request, ReplayExtras::default())?;
+    json_value_to_py(py, &value)
+}
+
+/// Discover recent transactions that call a package and replay each one.
+///
+/// Checkpoints are scanned one at a time and every matching PTB is replayed from the
+/// already-fetched checkpoint against a package context prepared once up front. Dependency
+/// packages fetched for one transaction are cached and reused by the rest, so the package
+/// closure is not re-fetched per transaction. A failed replay is recorded on its result
+/// entry and does not stop the run.
+///
+/// Args:
+///     package_id: Package whose Move calls select the transactions
+///     latest: Scan the latest N checkpoints, oldest first (max 500)
+///     max_transactions: Stop after replaying this many transactions
+///     include_framework: Also match framework Move calls
+///     resolve_deps: Include the package's dependency closure in the shared context
+///     walrus_network: Walrus network ("mainnet" or "testnet")
+///     walrus_caching_url: Optional custom Walrus caching endpoint (requires walrus_aggregator_url)
+///     walrus_aggregator_url: Optional custom Walrus aggregator endpoint (requires walrus_caching_url)
+///     rpc_url: Sui RPC endpoint (GraphQL dependency fetches)
+///     vm_only: Disable fallback paths and force VM-only behavior
+///     allow_fallback: Allow fallback hydration paths
+///     compare: Compare local execution with on-chain effects
+///     synthesize_missing: Retry with synthetic object bytes when inputs are missing
+///     self_heal_dynamic_fields: Enable dynamic field self-healing during VM execution
+///     on_result: Optional callable invoked with each result entry as soon as it completes;
+///         an exception raised by the callable aborts the run
+///     verbose: Verbose replay logging
+///
+/// Returns: dict with `results` (one entry per replayed transaction: `checkpoint`, `digest`,
+///     `move_calls`, and either `replay` or `error`) plus scan and outcome counters;
+///     `cached_packages` counts the dependency packages shared across the batch.
+#[pyfunction]
+#[pyo3(signature = (
+    package_id,
+    latest=10,
+    *,
+    max_transactions=None,
+    include_framework=false,
+    resolve_deps=true,
+    walrus_network="mainnet",
+    walrus_caching_url=None,
+    walrus_aggregator_url=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+    vm_only=false,
+    allow_fallback=true,
+    compare=false,
+    synthesize_missing=false,
+    self_heal_dynamic_fields=false,
+    on_result=None,
+    verbose=false,
+))]
+pub(super) fn discover_and_replay(
+    py: Python<'_>,
+    package_id: &str,
+    latest: u64,
+    max_transactions: Option<usize>,
+    include_framework: bool,
+    resolve_deps: bool,
+    walrus_network: &str,
+    walrus_caching_url: Option<&str>,
+    walrus_aggregator_url: Option<&str>,
+    rpc_url: &str,
+    vm_only: bool,
+    allow_fallback: bool,
+    compare: bool,
+    synthesize_missing: bool,
+    self_heal_dynamic_fields: bool,
+    on_result: Option<PyObject>,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    if max_transactions == Some(0) {
+        return Err(PyRuntimeError::new_err(
+            "max_transactions must be greater than zero",
+        ));
+    }
+    let package_filter = core_normalize_package_id(package_id).map_err(to_py_err)?;
+    let network = parse_walrus_archive_network(walrus_network).map_err(to_py_err)?;
+    let fetch_latency = Arc::new(LatencyRecorder::new());
+    let walrus = build_walrus_client(network, walrus_caching_url, walrus_aggregator_url)
+        .map_err(to_py_err)?
+        .with_latency_recorder(Arc::clone(&fetch_latency));
+    let allow_fallback = if vm_only { false } else { allow_fallback };
+
+    let checkpoints = py
+        .allow_threads(|| core_resolve_discovery_checkpoints(&walrus, None, Some(latest)))
+        .map_err(to_py_err)?;
+    let context_packages = py
+        .allow_threads(|| {
+            let prepared = prepare_package_context_inner(
+                &package_filter,
+                resolve_deps,
+                None,
+                PackageFetchOptions::default(),
+            )?;
+            let parsed = parse_context_payload(&prepared)?;
+            context_packages_to_package_data(&parsed.packages)
+        })
+        .map_err(to_py_err)?;
+
+    let shared_cache = Arc::new(sui_state_fetcher::VersionedCache::new());
+
+    let mut results = Vec::new();
+    let mut checkpoint_errors = Vec::new();
+    let mut checkpoints_scanned = 0usize;
+    let mut targets_discovered = 0usize;
+    let mut succeeded = 0usize;
+    let mut limit_reached = false;
+
+    'scan: for &checkpoint in &checkpoints {
+        checkpoints_scanned += 1;
+        let fetched = py.allow_threads(|| {
+            walrus
+                .get_checkpoint(checkpoint)
+                .with_context(|| format!("failed to fetch checkpoint {}", checkpoint))
+        });
+        let checkpoint_data = match fetched {
+            Ok(data) => Arc::new(data),
+            Err(err) => {
+                checkpoint_errors.push(serde_json::json!({
+                    "checkpoint": checkpoint,
+                    "error": format!("{:#}", err),
+                }));
+                continue;
+            }
+        };
+        let targets = core_checkpoint_ptb_targets(
+            checkpoint,
+            &checkpoint_data,
+            Some(&package_filter),
+            include_framework,
+        );
+        targets_discovered += targets.len();
+
+        for target in targets {
+            let extras = ReplayExtras {
+                checkpoint_data: Some(Arc::clone(&checkpoint_data)),
+                shared_state_cache: Some(Arc::clone(&shared_cache)),
+                ..ReplayExtras::default()
+            };
+            let replayed = py.allow_threads(|| {
+                replay_inner(
+                    &target.digest,
+                    rpc_url,
+                    "walrus",
+                    Some(checkpoint),
+                    Some(&context_packages),
+                    allow_fallback,
+                    3,
+                    200,
+                    true,
+                    false,
+                    synthesize_missing,
+                    self_heal_dynamic_fields,
+                    vm_only,
+                    compare,
+                    false,
+                    false,
+                    verbose,
+                    &extras,
+                )
+            });
+
+            let mut entry = serde_json::json!({
+                "checkpoint": checkpoint,
+                "digest": target.digest,
+                "move_calls": target.move_calls,
+            });
+            match replayed {
+                Ok(output) => {
+                    if output
+                        .get("local_success")
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or(false)
+                    {
+                        succeeded += 1;
+                    }
+                    entry["replay"] = output;
+                }
+                Err(err) => entry["error"] = serde_json::json!(format!("{:#}", err)),
+            }
+            if let Some(callback) = on_result.as_ref() {
+                callback.call1(py, (json_value_to_py(py, &entry)?,))?;
+            }
+            results.push(entry);
+
+            if max_transactions.is_some_and(|max| results.len() >= max) {
+                limit_reached = true;
+                break 'scan;
+            }
+        }
+    }
+
+    let mut value = serde_json::json!({
+        "success": true,
+        "package_id": package_filter,
+        "checkpoints": checkpoints,
+        "checkpoints_scanned": checkpoints_scanned,
+        "checkpoint_errors": checkpoint_errors,
+        "context_packages": context_packages.len(),
+        "cached_packages": shared_cache.package_count(),
+        "targets_discovered": targets_discovered,
+        "replayed": results.len(),
+        "succeeded": succeeded,
+        "failed": results.len() - succeeded,
+        "limit_reached": limit_reached,
+        "results": results,
+    });
+    attach_fetch_latency(&mut value, &fetch_latency);
+    json_value_to_py(py, &value)
 }
 
 /// Analyze replay hydration/readiness only (CLI parity for `analyze replay`).
@@ -420,6 +633,363 @@ pub(super) fn replay_effects(
     json_value_to_py(py, &out)
 }
 
+/// Replay one transaction under two configurations and diff the results (A/B harness).
+///
+/// Args:
+///     digest: Transaction digest (optional when state_file contains a single transaction)
+///     config_a: Dict for run A (keys: label, context_path, vm_only, allow_fallback,
+///         auto_system_objects, synthesize_missing, self_heal_dynamic_fields)
+///     config_b: Dict for run B (same keys as config_a)
+///     source: Replay source for network fetches ("hybrid", "grpc", "walrus", "jsonrpc", "local")
+///     checkpoint: Optional checkpoint for Walrus-backed fetches
+///     state_file: Optional replay-state JSON shared by both runs
+///     cache_dir: Optional local replay cache shared by both runs
+///     rpc_url: Sui RPC endpoint
+///     verbose: Verbose replay logging
+///
+/// Returns: Dict with `a`/`b` (label + replay output) and `diff` (effects comparison,
+/// return-value diffs, gas delta, `identical`)
+#[pyfunction]
+#[pyo3(signature = (
+    digest=None,
+    config_a=None,
+    config_b=None,
+    *,
+    source="hybrid",
+    checkpoint=None,
+    state_file=None,
+    cache_dir=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+    verbose=false,
+))]
+pub(super) fn replay_ab(
+    py: Python<'_>,
+    digest: Option<&str>,
+    config_a: Option<&Bound<'_, PyAny>>,
+    config_b: Option<&Bound<'_, PyAny>>,
+    source: &str,
+    checkpoint: Option<u64>,
+    state_file: Option<&str>,
+    cache_dir: Option<&str>,
+    rpc_url: &str,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    let parse = |config: Option<&Bound<'_, PyAny>>, side: &str| -> Result<ReplayAbConfig> {
+        let raw = config.map(|c| py_json_value(py, c)).transpose()?;
+        ReplayAbConfig::from_json(raw, side)
+    };
+    let config_a = parse(config_a, "a").map_err(to_py_err)?;
+    let config_b = parse(config_b, "b").map_err(to_py_err)?;
+    let digest_owned = digest.map(ToOwned::to_owned);
+    let source_owned = source.to_string();
+    let state_file_owned = state_file.map(PathBuf::from);
+    let cache_dir_owned = cache_dir.map(PathBuf::from);
+    let rpc_url_owned = rpc_url.to_string();
+
+    let value = py
+        .allow_threads(move || {
+            let use_local_cache =
+                source_owned.eq_ignore_ascii_case("local") || cache_dir_owned.is_some();
+            if state_file_owned.is_some() && use_local_cache {
+                return Err(anyhow!(
+                    "state_file cannot be combined with cache_dir/source='local'"
+                ));
+            }
+            let local_cache_dir = use_local_cache.then(|| {
+                cache_dir_owned
+                    .clone()
+                    .unwrap_or_else(default_local_cache_dir)
+            });
+            let state_source = if let Some(path) = state_file_owned.as_deref() {
+                ReplayAbSource::StateFile(path)
+            } else if let Some(dir) = local_cache_dir.as_deref() {
+                ReplayAbSource::LocalCache(dir)
+            } else {
+                ReplayAbSource::Network {
+                    source: &source_owned,
+                    checkpoint,
+                }
+            };
+            replay_ab_inner(
+                digest_owned.as_deref(),
+                state_source,
+                &config_a,
+                &config_b,
+                &rpc_url_owned,
+                verbose,
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// Benchmark replay throughput over a fixed digest set.
+///
+/// Args:
+///     digests: Transaction digests replayed once per pass
+///     profile: Runtime defaults profile ("safe"|"balanced"|"fast")
+///     concurrency: Worker threads replaying in parallel
+///     repeats: Measured passes over `digests`
+///     warmup: Unmeasured passes run first (connections, caches, JIT-like warm paths)
+///     source: Replay source for network fetches ("hybrid", "grpc", "walrus", "jsonrpc", "local")
+///     checkpoint: Optional checkpoint for Walrus-backed fetches
+///     state_file: Optional replay-state JSON holding every digest (parsed once up front)
+///     cache_dir: Optional local replay cache to replay from
+///     use_result_cache: Replay through the result cache (reports hit rate)
+///     result_cache_dir: Result cache directory (default: `~/.sui-sandbox/cache/results`)
+///     rpc_url: Sui RPC endpoint
+///
+/// Returns: Dict with `throughput_per_sec`, `wall_ms`, overall and `per_digest` `latency`
+/// (mean/p50/p95/p99/max ms), `result_cache` hits, `fetches` (total, per replay, by kind)
+/// and `errors`, for regression tracking across runs
+#[pyfunction]
+#[pyo3(signature = (
+    digests,
+    *,
+    profile=None,
+    concurrency=1,
+    repeats=3,
+    warmup=1,
+    source="hybrid",
+    checkpoint=None,
+    state_file=None,
+    cache_dir=None,
+    use_result_cache=false,
+    result_cache_dir=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+))]
+pub(super) fn bench_replay(
+    py: Python<'_>,
+    digests: Vec<String>,
+    profile: Option<&str>,
+    concurrency: usize,
+    repeats: usize,
+    warmup: usize,
+    source: &str,
+    checkpoint: Option<u64>,
+    state_file: Option<&str>,
+    cache_dir: Option<&str>,
+    use_result_cache: bool,
+    result_cache_dir: Option<&str>,
+    rpc_url: &str,
+) -> PyResult<PyObject> {
+    if result_cache_dir.is_some() && !use_result_cache {
+        return Err(PyRuntimeError::new_err(
+            "result_cache_dir only applies with use_result_cache=True",
+        ));
+    }
+    let profile = parse_replay_profile(profile).map_err(to_py_err)?;
+    let options = ReplayBenchOptions {
+        profile,
+        concurrency,
+        repeats,
+        warmup,
+    };
+    let extras = ReplayExtras {
+        result_cache: use_result_cache.then(|| ReplayResultCache {
+            dir: result_cache_dir
+                .map(PathBuf::from)
+                .unwrap_or_else(default_result_cache_dir),
+        }),
+        ..ReplayExtras::default()
+    };
+    let source_owned = source.to_string();
+    let state_file_owned = state_file.map(PathBuf::from);
+    let cache_dir_owned = cache_dir.map(PathBuf::from);
+    let rpc_url_owned = rpc_url.to_string();
+
+    let value = py
+        .allow_threads(move || {
+            let _profile_env = workflow_apply_profile_env(profile);
+            let use_local_cache =
+                source_owned.eq_ignore_ascii_case("local") || cache_dir_owned.is_some();
+            if state_file_owned.is_some() && use_local_cache {
+                return Err(anyhow!(
+                    "state_file cannot be combined with cache_dir/source='local'"
+                ));
+            }
+            let local_cache_dir = use_local_cache.then(|| {
+                cache_dir_owned
+                    .clone()
+                    .unwrap_or_else(default_local_cache_dir)
+            });
+            let state_source = if let Some(path) = state_file_owned.as_deref() {
+                ReplayAbSource::StateFile(path)
+            } else if let Some(dir) = local_cache_dir.as_deref() {
+                ReplayAbSource::LocalCache(dir)
+            } else {
+                ReplayAbSource::Network {
+                    source: &source_owned,
+                    checkpoint,
+                }
+            };
+            bench_replay_inner(&digests, state_source, &options, &rpc_url_owned, &extras)
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// Replay many transactions with bounded parallelism and one shared fetch cache.
+///
+/// Args:
+///     digests: Transaction digests to replay
+///     max_concurrency: Worker threads replaying in parallel
+///     profile: Runtime defaults profile ("safe"|"balanced"|"fast")
+///     source: Replay source ("hybrid", "grpc", "walrus", "jsonrpc", "local")
+///     cache_dir: Optional local replay cache to replay from
+///     allow_fallback: Allow fallback hydration paths
+///     prefetch_depth: Dynamic field prefetch depth
+///     prefetch_limit: Dynamic field prefetch limit
+///     auto_system_objects: Auto inject Clock/Random if missing
+///     compare: Compare local execution with on-chain effects
+///     include_outputs: Keep each replay's full result dict under `runs[i].output`
+///     rpc_url: Sui RPC endpoint
+///
+/// Returns: Dict with `runs` (per digest, in input order: `digest`, `success`,
+/// `elapsed_ms`, `error`), `summary` (`total`, `success`, `failed`, `errored`,
+/// `success_rate`, `wall_ms`, `average_elapsed_ms`, `max_elapsed_ms`) and
+/// `shared_cache` (packages/objects held by the cache the replays shared, plus the
+/// `framework_versions` their shared resolver loaded)
+#[pyfunction]
+#[pyo3(signature = (
+    digests,
+    *,
+    max_concurrency=4,
+    profile=None,
+    source="hybrid",
+    cache_dir=None,
+    allow_fallback=true,
+    prefetch_depth=3,
+    prefetch_limit=200,
+    auto_system_objects=true,
+    compare=false,
+    include_outputs=false,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+))]
+pub(super) fn replay_many(
+    py: Python<'_>,
+    digests: Vec<String>,
+    max_concurrency: usize,
+    profile: Option<&str>,
+    source: &str,
+    cache_dir: Option<&str>,
+    allow_fallback: bool,
+    prefetch_depth: usize,
+    prefetch_limit: usize,
+    auto_system_objects: bool,
+    compare: bool,
+    include_outputs: bool,
+    rpc_url: &str,
+) -> PyResult<PyObject> {
+    let profile = parse_replay_profile(profile).map_err(to_py_err)?;
+    let options = ReplayManyOptions {
+        max_concurrency,
+        allow_fallback,
+        prefetch_depth,
+        prefetch_limit,
+        auto_system_objects,
+        compare,
+        include_outputs,
+    };
+    let source_owned = source.to_string();
+    let cache_dir_owned = cache_dir.map(PathBuf::from);
+    let rpc_url_owned = rpc_url.to_string();
+
+    let value = py
+        .allow_threads(move || {
+            let _profile_env = workflow_apply_profile_env(profile);
+            let use_local_cache =
+                source_owned.eq_ignore_ascii_case("local") || cache_dir_owned.is_some();
+            let local_cache_dir = use_local_cache.then(|| {
+                cache_dir_owned
+                    .clone()
+                    .unwrap_or_else(default_local_cache_dir)
+            });
+            let state_source = match local_cache_dir.as_deref() {
+                Some(dir) => ReplayAbSource::LocalCache(dir),
+                None => ReplayAbSource::Network {
+                    source: &source_owned,
+                    checkpoint: None,
+                },
+            };
+            replay_many_inner(
+                &digests,
+                state_source,
+                &options,
+                &rpc_url_owned,
+                &ReplayExtras::default(),
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
+/// Replay every transaction of a Walrus checkpoint in order and reconcile all effects.
+///
+/// Transactions share one evolving object store: an object written by an earlier
+/// transaction in the checkpoint is read from its local output rather than from the
+/// checkpoint, so divergences carry forward. System transactions are not executed.
+///
+/// Args:
+///     checkpoint: Checkpoint sequence number
+///     compare: Reconcile each transaction's local effects with its on-chain effects
+///     protocol_version: Execute with this protocol version's framework, feature flags and
+///         gas schedule instead of the checkpoint epoch's version (looked up over GraphQL;
+///         the checkpoint loader's default when the lookup fails)
+///     walrus_network: Walrus network ("mainnet" or "testnet")
+///     walrus_caching_url: Optional custom Walrus caching endpoint (requires walrus_aggregator_url)
+///     walrus_aggregator_url: Optional custom Walrus aggregator endpoint (requires walrus_caching_url)
+///     verbose: Log each transaction's outcome
+///
+/// Returns: Dict with `transactions` (per transaction, in checkpoint order: `index`, `digest`,
+/// `kind`, `executed`, `local_success`, `on_chain_success`, `chained_inputs`,
+/// `diverged_inputs`, `error`, `reconciliation`), `summary` (`executed`, `skipped`,
+/// `errored`, `local_success`, `status_mismatches`, `effects_matched`, `mismatch_counts`, ...),
+/// `store_objects`, `protocol_version` (null when the loader's default was used) and `warnings`
+#[pyfunction]
+#[pyo3(signature = (
+    checkpoint,
+    *,
+    compare=true,
+    protocol_version=None,
+    walrus_network="mainnet",
+    walrus_caching_url=None,
+    walrus_aggregator_url=None,
+    verbose=false,
+))]
+pub(super) fn replay_checkpoint(
+    py: Python<'_>,
+    checkpoint: u64,
+    compare: bool,
+    protocol_version: Option<u64>,
+    walrus_network: &str,
+    walrus_caching_url: Option<&str>,
+    walrus_aggregator_url: Option<&str>,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    let network = parse_walrus_archive_network(walrus_network).map_err(to_py_err)?;
+    let walrus = build_walrus_client(network, walrus_caching_url, walrus_aggregator_url)
+        .map_err(to_py_err)?;
+    let rpc_url = match network {
+        CoreWalrusArchiveNetwork::Mainnet => Network::Mainnet.rpc_url(),
+        CoreWalrusArchiveNetwork::Testnet => Network::Testnet.rpc_url(),
+    };
+    let graphql = GraphQLClient::new(&resolve_graphql_endpoint(rpc_url));
+    let value = py
+        .allow_threads(move || {
+            replay_checkpoint_inner(
+                checkpoint,
+                walrus,
+                &graphql,
+                compare,
+                protocol_version,
+                verbose,
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
 /// Classify replay output into structured failure categories and retry hints.
 #[pyfunction]
 pub(super) fn classify_replay_result(
@@ -758,6 +1328,7 @@ pub(super) fn protocol_run_impl(
                 &resolved_package_id,
                 resolve_deps,
                 context_path_owned.as_deref(),
+                PackageFetchOptions::default(),
             )
         })
         .map_err(to_py_err)?;
@@ -769,9 +1340,9 @@ pub(super) fn protocol_run_impl(
     };
     let effective_context = context_path
         .map(ToOwned::to_owned)
-        .or_else(|| context_tmp.as_ref().map(|p| p.display().to_string()));
+        .or_else(|| context_tmp.as_ref().map(|p| p.path().display().to_string()));
 
-    let result = replay_transaction(
+    replay_transaction(
         py,
         digest,
         checkpoint,
@@ -805,11 +1376,7 @@ pub(super) fn protocol_run_impl(
         self_heal_dynamic_fields,
         analyze_mm2,
         verbose,
-    );
-    if let Some(path) = context_tmp {
-        let _ = std::fs::remove_file(path);
-    }
-    result
+    )
 }
 
 #[pyfunction]
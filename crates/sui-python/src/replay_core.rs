@@ -1,50 +1,405 @@
 use super::*;
 
+/// Category tag of a [`WarningLog`] entry, rendered as the `[category]` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum WarningCategory {
+    ChildFetch,
+    Clock,
+    Compare,
+    Decode,
+    Deps,
+    Digest,
+    Epoch,
+    Gas,
+    Native,
+    ObjectDiffs,
+    Ownership,
+    Protocol,
+    Reconciliation,
+    ResultCache,
+    SelfHeal,
+    /// A self-heal that synthesized a dynamic field value; rendered as `self_heal`.
+    SelfHealSynthesized,
+    Synthesis,
+    Trace,
+    TxKind,
+}
+
+impl WarningCategory {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::ChildFetch => "child_fetch",
+            Self::Clock => "clock",
+            Self::Compare => "compare",
+            Self::Decode => "decode",
+            Self::Deps => "deps",
+            Self::Digest => "digest",
+            Self::Epoch => "epoch",
+            Self::Gas => "gas",
+            Self::Native => "native",
+            Self::ObjectDiffs => "object_diffs",
+            Self::Ownership => "ownership",
+            Self::Protocol => "protocol",
+            Self::Reconciliation => "reconciliation",
+            Self::ResultCache => "result_cache",
+            Self::SelfHeal | Self::SelfHealSynthesized => "self_heal",
+            Self::Synthesis => "synthesis",
+            Self::Trace => "trace",
+            Self::TxKind => "tx_kind",
+        }
+    }
+}
+
+/// Non-fatal warnings collected during one operation and returned as `warnings` in its JSON.
+///
+/// Entries are formatted `"[category] message"`. Clones share the same buffer so the log
+/// can be moved into VM fetcher callbacks; `echo` mirrors each entry to stderr.
+#[derive(Debug, Clone, Default)]
+pub(super) struct WarningLog {
+    entries: Arc<std::sync::Mutex<Vec<(WarningCategory, String)>>>,
+    echo: bool,
+}
+
+impl WarningLog {
+    pub(super) fn new(echo: bool) -> Self {
+        Self {
+            entries: Arc::default(),
+            echo,
+        }
+    }
+
+    pub(super) fn push(&self, category: WarningCategory, message: impl std::fmt::Display) {
+        let entry = format!("[{}] {}", category.as_str(), message);
+        if self.echo {
+            eprintln!("Warning: {}", entry);
+        }
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push((category, entry));
+        }
+    }
+
+    pub(super) fn entries(&self) -> Vec<String> {
+        self.entries
+            .lock()
+            .map(|entries| entries.iter().map(|(_, entry)| entry.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of entries pushed under `category`.
+    pub(super) fn count(&self, category: WarningCategory) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.iter().filter(|(c, _)| *c == category).count())
+            .unwrap_or(0)
+    }
+
+    /// Fidelity-relevant events recorded in this log.
+    pub(super) fn fidelity_signals(&self) -> CoreReplayFidelitySignals {
+        CoreReplayFidelitySignals {
+            self_healed_values: self.count(WarningCategory::SelfHealSynthesized),
+            clock_defaulted: self.count(WarningCategory::Clock) > 0,
+        }
+    }
+
+    /// Insert the collected entries as `output["warnings"]` (always present, possibly empty).
+    pub(super) fn attach(&self, output: &mut serde_json::Value) {
+        if let Some(object) = output.as_object_mut() {
+            object.insert("warnings".to_string(), serde_json::json!(self.entries()));
+        }
+    }
+}
+
+/// Packages a `context_path` merge placed into the replay state.
+#[derive(Debug, Default)]
+pub(super) struct ContextPackageMerge {
+    /// Context packages the fetch did not return.
+    pub(super) inserted: Vec<AccountAddress>,
+    /// Fetched packages replaced by their context copy (`context_overrides=True`).
+    pub(super) replaced: Vec<AccountAddress>,
+}
+
+impl ContextPackageMerge {
+    pub(super) fn merged(&self) -> usize {
+        self.inserted.len() + self.replaced.len()
+    }
+
+    /// `package_sources` report: which replay packages came from context vs fetch.
+    fn sources_json(&self, replay_state: &ReplayState) -> serde_json::Value {
+        let fetched = sorted_hex_ids(
+            replay_state
+                .packages
+                .keys()
+                .filter(|id| !self.inserted.contains(*id) && !self.replaced.contains(*id)),
+        );
+        serde_json::json!({
+            "context": sorted_hex_ids(self.inserted.iter().chain(&self.replaced)),
+            "context_replaced": sorted_hex_ids(self.replaced.iter()),
+            "fetched": fetched,
+        })
+    }
+}
+
+fn sorted_hex_ids<'a>(ids: impl Iterator<Item = &'a AccountAddress>) -> Vec<String> {
+    let mut out: Vec<String> = ids.map(|id| id.to_hex_literal()).collect();
+    out.sort();
+    out
+}
+
+/// Merge prepared-context packages into the replay state.
+///
+/// By default context only fills gaps; with `overrides` a context package also
+/// replaces the fetched copy with the same id.
 pub(super) fn merge_context_packages(
     replay_state: &mut ReplayState,
     context_packages: &HashMap<AccountAddress, PackageData>,
-) -> usize {
-    let mut inserted = 0usize;
+    overrides: bool,
+) -> ContextPackageMerge {
+    let mut merge = ContextPackageMerge::default();
     for (address, package) in context_packages {
-        if replay_state.packages.contains_key(address) {
+        let present = replay_state.packages.contains_key(address);
+        if present && !overrides {
             continue;
         }
         replay_state.packages.insert(*address, package.clone());
-        inserted += 1;
+        if present {
+            merge.replaced.push(*address);
+        } else {
+            merge.inserted.push(*address);
+        }
     }
-    inserted
+    merge
+}
+
+/// A prepared context written to its own temp file for one replay; removed on drop.
+pub(super) struct TempContextFile {
+    path: PathBuf,
 }
 
-pub(super) fn write_temp_context_file(payload: &serde_json::Value) -> Result<PathBuf> {
+impl TempContextFile {
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempContextFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Write `payload` to a fresh temp file. Names carry the pid, a timestamp and a
+/// per-process sequence number, and the file is created exclusively, so concurrent
+/// replays never share or clobber a context file.
+pub(super) fn write_temp_context_file(payload: &serde_json::Value) -> Result<TempContextFile> {
+    static SEQUENCE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
     let path = std::env::temp_dir().join(format!(
-        "sui_sandbox_flow_context_{}_{}.json",
+        "sui_sandbox_flow_context_{}_{}_{}.json",
         std::process::id(),
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
-            .as_nanos()
+            .as_nanos(),
+        SEQUENCE.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
     ));
-    std::fs::write(&path, serde_json::to_string(payload)?)
-        .with_context(|| format!("Failed to write temp context file {}", path.display()))?;
-    Ok(path)
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .with_context(|| format!("Failed to create temp context file {}", path.display()))?;
+    let temp = TempContextFile { path };
+    serde_json::to_writer(&mut file, payload)
+        .with_context(|| format!("Failed to write temp context file {}", temp.path.display()))?;
+    Ok(temp)
 }
 
 /// Fetch a package's modules via GraphQL, returning (module_name, bytecode_bytes) pairs.
+///
+/// Collects [`GraphQLClient::stream_package_modules`].
 pub(super) fn fetch_package_modules(
     graphql: &GraphQLClient,
     package_id: &str,
 ) -> Result<Vec<(String, Vec<u8>)>> {
+    graphql
+        .stream_package_modules(package_id)
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("fetch package {}", package_id))
+}
+
+/// Like [`fetch_package_modules`], but when `checkpoint` is set the package is
+/// fetched at the version that was live at that checkpoint.
+pub(super) fn fetch_package_modules_pinned(
+    graphql: &GraphQLClient,
+    package_id: &str,
+    checkpoint: Option<u64>,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let Some(checkpoint) = checkpoint else {
+        return fetch_package_modules(graphql, package_id);
+    };
     let pkg = graphql
-        .fetch_package(package_id)
-        .with_context(|| format!("fetch package {}", package_id))?;
+        .fetch_package_at_checkpoint(package_id, checkpoint)
+        .with_context(|| format!("fetch package {} at checkpoint {}", package_id, checkpoint))?;
     sui_transport::decode_graphql_modules(package_id, &pkg.modules)
 }
 
+/// Runtime (original) id of a fetched package, read from its first module's self address.
+pub(super) fn package_runtime_id(modules: &[(String, Vec<u8>)]) -> Option<AccountAddress> {
+    modules.iter().find_map(|(_, bytes)| {
+        CompiledModule::deserialize_with_defaults(bytes)
+            .ok()
+            .map(|module| *module.self_id().address())
+    })
+}
+
+/// Breadth-first frontier for package dependency resolution.
+///
+/// Bytecode refers to packages by runtime (original) id while upgraded packages are
+/// fetched by storage id. Once a fetch reveals a package's runtime id, both ids are the
+/// same node, so a package and its upgrade are never fetched as two frontier entries.
+/// Edges back to a node on the path that first reached the current package are
+/// recorded as cycles, unless they name the node by its aliased runtime id: that is a
+/// dependency on an older version of the package, not a cycle. Revisits through other
+/// paths (diamonds) are not cycles either.
+#[derive(Debug, Default)]
+pub(super) struct DependencyWalk {
+    queue: VecDeque<AccountAddress>,
+    queued: HashSet<AccountAddress>,
+    visited: HashSet<AccountAddress>,
+    /// Runtime id -> storage id for fetched packages whose ids differ.
+    aliases: HashMap<AccountAddress, AccountAddress>,
+    /// Node -> node whose dependency edge first queued it.
+    parents: HashMap<AccountAddress, AccountAddress>,
+    cycles: Vec<Vec<AccountAddress>>,
+}
+
+impl DependencyWalk {
+    /// Start a walk where `loaded` packages are already available and never fetched.
+    pub(super) fn new(loaded: impl IntoIterator<Item = AccountAddress>) -> Self {
+        Self {
+            visited: loaded.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    fn node(&self, addr: AccountAddress) -> AccountAddress {
+        self.aliases.get(&addr).copied().unwrap_or(addr)
+    }
+
+    fn is_known(&self, addr: AccountAddress) -> bool {
+        let node = self.node(addr);
+        self.visited.contains(&node) || self.queued.contains(&node)
+    }
+
+    /// Queue a package with no parent (the target, or a package named in a type string).
+    pub(super) fn push_root(&mut self, addr: AccountAddress) {
+        if !self.is_known(addr) {
+            self.queued.insert(addr);
+            self.queue.push_back(addr);
+        }
+    }
+
+    /// Pop the next package to fetch, marking it visited. Entries that became
+    /// aliases of an already-fetched package while queued are skipped.
+    pub(super) fn next(&mut self) -> Option<AccountAddress> {
+        while let Some(addr) = self.queue.pop_front() {
+            let node = self.node(addr);
+            if self.visited.insert(node) {
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    /// Record that `fetched` resolved to bytecode at `runtime`, aliasing the two ids.
+    pub(super) fn record_runtime_id(&mut self, fetched: AccountAddress, runtime: AccountAddress) {
+        if fetched != runtime && !self.visited.contains(&runtime) {
+            self.aliases.insert(runtime, fetched);
+        }
+    }
+
+    /// Follow the dependency edge `from -> dep`, queueing `dep` if it is new.
+    pub(super) fn push_dependency(&mut self, from: AccountAddress, dep: AccountAddress) {
+        let from = self.node(from);
+        let linked = dep;
+        let dep = self.node(dep);
+        if dep == from {
+            // Intra-package reference, or an upgrade linking back to its original id.
+            return;
+        }
+        if self.is_known(dep) {
+            if linked != dep {
+                // Linked by original id: an older version of `dep`, already covered.
+                return;
+            }
+            if let Some(cycle) = self.path_to_ancestor(from, dep) {
+                self.cycles.push(cycle);
+            }
+            return;
+        }
+        self.parents.insert(dep, from);
+        self.queued.insert(dep);
+        self.queue.push_back(dep);
+    }
+
+    /// Path `ancestor -> .. -> from -> ancestor` if `ancestor` is on the path that first
+    /// reached `from`. The parent chain is bounded by the number of nodes seen so far.
+    fn path_to_ancestor(
+        &self,
+        from: AccountAddress,
+        ancestor: AccountAddress,
+    ) -> Option<Vec<AccountAddress>> {
+        let mut path = vec![from];
+        let mut current = from;
+        for _ in 0..=self.parents.len() {
+            let parent = *self.parents.get(&current)?;
+            path.push(parent);
+            if parent == ancestor {
+                path.reverse();
+                path.push(ancestor);
+                return Some(path);
+            }
+            current = parent;
+        }
+        None
+    }
+
+    /// Dependency cycles found so far, formatted `0xa -> 0xb -> 0xa`.
+    pub(super) fn cycle_descriptions(&self) -> Vec<String> {
+        self.cycles
+            .iter()
+            .map(|cycle| {
+                cycle
+                    .iter()
+                    .map(AccountAddress::to_hex_literal)
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            })
+            .collect()
+    }
+}
+
 /// Build a LocalModuleResolver with the Sui framework loaded, then fetch a target
 /// package and its transitive dependencies via GraphQL.
 pub(super) fn build_resolver_with_deps(
     package_id: &str,
     extra_type_refs: &[String],
+    warnings: &WarningLog,
+) -> Result<(
+    sui_sandbox_core::resolver::LocalModuleResolver,
+    HashSet<AccountAddress>,
+)> {
+    let graphql_endpoint = resolve_graphql_endpoint("https://fullnode.mainnet.sui.io:443");
+    let graphql = GraphQLClient::new(&graphql_endpoint);
+    build_resolver_with_deps_via(&graphql, package_id, extra_type_refs, None, warnings)
+}
+
+/// [`build_resolver_with_deps`] against an existing GraphQL client. When `checkpoint`
+/// is set, packages are fetched at the versions live at that checkpoint.
+pub(super) fn build_resolver_with_deps_via(
+    graphql: &GraphQLClient,
+    package_id: &str,
+    extra_type_refs: &[String],
+    checkpoint: Option<u64>,
+    warnings: &WarningLog,
 ) -> Result<(
     sui_sandbox_core::resolver::LocalModuleResolver,
     HashSet<AccountAddress>,
@@ -55,22 +410,17 @@ pub(super) fn build_resolver_with_deps(
         loaded_packages.insert(AccountAddress::from_hex_literal(fw).unwrap());
     }
 
-    let graphql_endpoint = resolve_graphql_endpoint("https://fullnode.mainnet.sui.io:443");
-    let graphql = GraphQLClient::new(&graphql_endpoint);
-
-    let mut to_fetch: VecDeque<AccountAddress> = VecDeque::new();
+    let mut walk = DependencyWalk::new(loaded_packages.iter().copied());
     let target_addr = AccountAddress::from_hex_literal(package_id)
         .with_context(|| format!("invalid target package: {}", package_id))?;
-    if !loaded_packages.contains(&target_addr) {
-        to_fetch.push_back(target_addr);
-    }
+    walk.push_root(target_addr);
 
     // Also fetch packages referenced in type strings
     for type_str in extra_type_refs {
         for pkg_id in sui_sandbox_core::utilities::extract_package_ids_from_type(type_str) {
             if let Ok(addr) = AccountAddress::from_hex_literal(&pkg_id) {
-                if !loaded_packages.contains(&addr) && !is_framework_address(&addr) {
-                    to_fetch.push_back(addr);
+                if !is_framework_address(&addr) {
+                    walk.push_root(addr);
                 }
             }
         }
@@ -78,41 +428,52 @@ pub(super) fn build_resolver_with_deps(
 
     // BFS fetch dependencies
     const MAX_DEP_ROUNDS: usize = 8;
-    let mut visited = loaded_packages.clone();
     let mut rounds = 0;
-    while let Some(addr) = to_fetch.pop_front() {
-        if visited.contains(&addr) || is_framework_address(&addr) {
+    while let Some(addr) = walk.next() {
+        if is_framework_address(&addr) {
             continue;
         }
         rounds += 1;
         if rounds > MAX_DEP_ROUNDS {
-            eprintln!(
-                "Warning: dependency resolution hit max depth ({} packages fetched), \
-                 stopping. Some transitive deps may be missing.",
-                MAX_DEP_ROUNDS
+            warnings.push(
+                WarningCategory::Deps,
+                format!(
+                    "dependency resolution hit max depth ({} packages fetched), stopping; \
+                     some transitive deps may be missing",
+                    MAX_DEP_ROUNDS
+                ),
             );
             break;
         }
-        visited.insert(addr);
 
         let hex = addr.to_hex_literal();
-        match fetch_package_modules(&graphql, &hex) {
+        match fetch_package_modules_pinned(graphql, &hex, checkpoint) {
             Ok(modules) => {
                 let dep_addrs = extract_dependency_addrs(&modules);
-                resolver.load_package_at(modules, addr)?;
+                let runtime_addr = resolver.load_package_at(modules, addr)?;
+                walk.record_runtime_id(addr, runtime_addr);
                 loaded_packages.insert(addr);
 
                 for dep_addr in dep_addrs {
-                    if !visited.contains(&dep_addr) && !is_framework_address(&dep_addr) {
-                        to_fetch.push_back(dep_addr);
+                    if !is_framework_address(&dep_addr) {
+                        walk.push_dependency(addr, dep_addr);
                     }
                 }
             }
             Err(e) => {
-                eprintln!("Warning: failed to fetch package {}: {:#}", hex, e);
+                warnings.push(
+                    WarningCategory::Deps,
+                    format!("failed to fetch package {}: {:#}", hex, e),
+                );
             }
         }
     }
+    for cycle in walk.cycle_descriptions() {
+        warnings.push(
+            WarningCategory::Deps,
+            format!("dependency cycle detected: {}", cycle),
+        );
+    }
 
     Ok((resolver, loaded_packages))
 }
@@ -124,10 +485,11 @@ pub(super) fn synthesize_missing_inputs_py(
     resolver: &sui_sandbox_core::resolver::LocalModuleResolver,
     aliases: &HashMap<AccountAddress, AccountAddress>,
     graphql: &GraphQLClient,
-    verbose: bool,
-) -> Result<Vec<String>> {
+    warnings: &WarningLog,
+) -> Result<CoreSynthesisSummary> {
+    let mut summary = CoreSynthesisSummary::default();
     if missing.is_empty() {
-        return Ok(Vec::new());
+        return Ok(summary);
     }
 
     let modules: Vec<CompiledModule> = resolver.iter_modules().cloned().collect();
@@ -138,10 +500,25 @@ pub(super) fn synthesize_missing_inputs_py(
         .map_err(|e| anyhow!("failed to build type model: {}", e))?;
     let mut synthesizer = sui_sandbox_core::mm2::TypeSynthesizer::new(&type_model);
 
-    let mut logs = Vec::new();
     for entry in missing {
         let object_id = entry.object_id.as_str();
         let version = entry.version;
+        // Framework singletons (0x5 system state, Clock, Random, DenyList) carry chain
+        // state that defaults would misrepresent; they must come from history.
+        if AccountAddress::from_hex_literal(object_id)
+            .map(|id| sui_sandbox_types::is_system_object(&id))
+            .unwrap_or(false)
+        {
+            warnings.push(
+                WarningCategory::Synthesis,
+                format!(
+                    "framework object={} version={} is not synthesized; it must be hydrated \
+                     at its historical version",
+                    object_id, version
+                ),
+            );
+            continue;
+        }
         let mut type_string = graphql
             .fetch_object_at_version(object_id, version)
             .ok()
@@ -154,12 +531,13 @@ pub(super) fn synthesize_missing_inputs_py(
             });
 
         let Some(type_str) = type_string.take() else {
-            if verbose {
-                logs.push(format!(
-                    "missing_type object={} version={} (skipped)",
+            warnings.push(
+                WarningCategory::Synthesis,
+                format!(
+                    "missing type for object={} version={}; skipped",
                     object_id, version
-                ));
-            }
+                ),
+            );
             continue;
         };
 
@@ -185,13 +563,10 @@ pub(super) fn synthesize_missing_inputs_py(
         }
         version_map.insert(normalized.clone(), version);
 
-        logs.push(format!(
-            "synthesized object={} version={} type={} stub={} ({})",
-            normalized, version, synth_type, result.is_stub, result.description
-        ));
+        summary.record(&synth_type, result.is_stub);
     }
 
-    Ok(logs)
+    Ok(summary)
 }
 
 pub(super) fn build_mm2_summary_from_modules(
@@ -251,39 +626,114 @@ pub(super) fn enable_self_heal_fetchers(
     max_version: u64,
     aliases: &HashMap<AccountAddress, AccountAddress>,
     modules: &[CompiledModule],
+    warnings: &WarningLog,
 ) {
+    use sui_transport::graphql::DynamicFieldScan;
+
     let graphql_for_versioned = graphql.clone();
+    let warnings_for_versioned = warnings.clone();
+    // The lookup is pinned to `checkpoint`, so the child id alone identifies the version.
+    let child_memo: FetchMemo<AccountAddress, (TypeTag, Vec<u8>, u64)> = FetchMemo::new();
     harness.set_versioned_child_fetcher(Box::new(move |_parent, child_id| {
-        let child_hex = child_id.to_hex_literal();
-        let object = checkpoint
-            .and_then(|cp| {
-                graphql_for_versioned
-                    .fetch_object_at_checkpoint(&child_hex, cp)
-                    .ok()
-            })
-            .or_else(|| graphql_for_versioned.fetch_object(&child_hex).ok())?;
+        child_memo
+            .get_or_fetch(child_id, || {
+                let child_hex = child_id.to_hex_literal();
+                let fetched = match checkpoint.and_then(|cp| {
+                    graphql_for_versioned
+                        .fetch_object_at_checkpoint(&child_hex, cp)
+                        .ok()
+                }) {
+                    Some(object) => Ok(object),
+                    None => graphql_for_versioned.fetch_object(&child_hex),
+                };
+                let object = match fetched {
+                    Ok(object) => object,
+                    Err(e) if is_object_not_found(&e) => {
+                        warnings_for_versioned.push(
+                            WarningCategory::SelfHeal,
+                            format!("child object {} not found via GraphQL", child_hex),
+                        );
+                        return Ok(None);
+                    }
+                    Err(e) => {
+                        warnings_for_versioned.push(
+                            WarningCategory::SelfHeal,
+                            format!("child object {} fetch failed: {:#}", child_hex, e),
+                        );
+                        return Err(e);
+                    }
+                };
 
-        if object.version > max_version {
-            return None;
-        }
-        let (type_str, bcs_b64) = (object.type_string?, object.bcs_base64?);
-        let bytes = base64::engine::general_purpose::STANDARD
-            .decode(bcs_b64.as_bytes())
-            .ok()?;
-        let tag = sui_sandbox_core::types::parse_type_tag(&type_str).ok()?;
-        Some((tag, bytes, object.version))
+                if object.version > max_version {
+                    warnings_for_versioned.push(
+                        WarningCategory::SelfHeal,
+                        format!(
+                            "child object {} version {} is newer than replay max version {}; skipped",
+                            child_hex, object.version, max_version
+                        ),
+                    );
+                    return Ok(None);
+                }
+                let (Some(type_str), Some(bcs_b64)) = (object.type_string, object.bcs_base64)
+                else {
+                    return Ok(None);
+                };
+                let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(bcs_b64.as_bytes())
+                else {
+                    return Ok(None);
+                };
+                let Ok(tag) = sui_sandbox_core::types::parse_type_tag(&type_str) else {
+                    return Ok(None);
+                };
+                Ok(Some((tag, bytes, object.version)))
+            })
+            .ok()
+            .flatten()
     }));
 
     let graphql_for_key = graphql.clone();
     let aliases_for_key = aliases.clone();
     let modules_for_synth = Arc::new(modules.to_vec());
+    let warnings_for_key = warnings.clone();
     harness.set_key_based_child_fetcher(Box::new(
         move |parent, _child_id, _key_type, key_bytes| {
             let parent_hex = parent.to_hex_literal();
-            let field = graphql_for_key
-                .find_dynamic_field_by_bcs(&parent_hex, key_bytes, checkpoint, 1000)
-                .ok()
-                .flatten()?;
+            let field = match graphql_for_key.scan_dynamic_field_by_bcs(
+                &parent_hex,
+                key_bytes,
+                checkpoint,
+                sui_transport::graphql::dynamic_field_scan_max_pages(),
+            ) {
+                Ok(DynamicFieldScan::Found(field)) => field,
+                Ok(DynamicFieldScan::Absent) => {
+                    warnings_for_key.push(
+                        WarningCategory::SelfHeal,
+                        format!("no dynamic field under parent {} matched key", parent_hex),
+                    );
+                    return None;
+                }
+                Ok(DynamicFieldScan::Truncated { pages_scanned }) => {
+                    warnings_for_key.push(
+                        WarningCategory::SelfHeal,
+                        format!(
+                            "dynamic field lookup under parent {} stopped after {} pages \
+                             (SUI_DF_SCAN_MAX_PAGES); the key may lie beyond the bound",
+                            parent_hex, pages_scanned
+                        ),
+                    );
+                    return None;
+                }
+                Err(err) => {
+                    warnings_for_key.push(
+                        WarningCategory::SelfHeal,
+                        format!(
+                            "dynamic field lookup under parent {} failed: {:#}",
+                            parent_hex, err
+                        ),
+                    );
+                    return None;
+                }
+            };
 
             let value_type = field.value_type?;
             let parsed = sui_sandbox_core::types::parse_type_tag(&value_type).ok()?;
@@ -298,6 +748,13 @@ pub(super) fn enable_self_heal_fetchers(
             }
 
             let synth_type = sui_sandbox_core::types::format_type_tag(&rewritten);
+            warnings_for_key.push(
+                WarningCategory::SelfHealSynthesized,
+                format!(
+                    "synthesized value for dynamic field under parent {} (type {})",
+                    parent_hex, synth_type
+                ),
+            );
             let type_model =
                 sui_sandbox_core::mm2::TypeModel::from_modules(modules_for_synth.as_ref().clone())
                     .ok()?;
@@ -321,11 +778,13 @@ pub(super) fn enable_self_heal_fetchers(
 // extract_interface (native)
 // ---------------------------------------------------------------------------
 
-pub(super) fn extract_interface_inner(
+/// Load a package's compiled modules from `bytecode_dir` or, by `package_id`, from
+/// GraphQL. Exactly one of the two must be given.
+fn load_package_compiled_modules(
     package_id: Option<&str>,
     bytecode_dir: Option<&str>,
     rpc_url: &str,
-) -> Result<serde_json::Value> {
+) -> Result<(String, Vec<CompiledModule>)> {
     if package_id.is_none() && bytecode_dir.is_none() {
         return Err(anyhow!(
             "Either package_id or bytecode_dir must be provided"
@@ -337,39 +796,1480 @@ pub(super) fn extract_interface_inner(
         ));
     }
 
-    if let Some(dir) = bytecode_dir {
-        let dir_path = PathBuf::from(dir);
-        let compiled = read_local_compiled_modules(&dir_path)?;
-        let pkg_id = resolve_local_package_id(&dir_path)?;
-        let (_, interface_value) =
-            build_bytecode_interface_value_from_compiled_modules(&pkg_id, &compiled)?;
-        return Ok(interface_value);
+    if let Some(dir) = bytecode_dir {
+        let dir_path = PathBuf::from(dir);
+        let compiled = read_local_compiled_modules(&dir_path)?;
+        Ok((resolve_local_package_id(&dir_path)?, compiled))
+    } else {
+        let pkg_id_str = package_id.unwrap();
+        let graphql_endpoint = resolve_graphql_endpoint(rpc_url);
+        let graphql = GraphQLClient::new(&graphql_endpoint);
+        let pkg = graphql
+            .fetch_package(pkg_id_str)
+            .with_context(|| format!("fetch package {}", pkg_id_str))?;
+
+        let raw_modules = sui_transport::decode_graphql_modules(pkg_id_str, &pkg.modules)?;
+        let compiled_modules: Vec<CompiledModule> = raw_modules
+            .into_iter()
+            .map(|(name, bytes)| {
+                CompiledModule::deserialize_with_defaults(&bytes)
+                    .map_err(|e| anyhow!("deserialize {}::{}: {:?}", pkg_id_str, name, e))
+            })
+            .collect::<Result<_>>()?;
+        Ok((pkg_id_str.to_string(), compiled_modules))
+    }
+}
+
+pub(super) fn extract_interface_inner(
+    package_id: Option<&str>,
+    bytecode_dir: Option<&str>,
+    rpc_url: &str,
+    include_entrypoints: bool,
+) -> Result<serde_json::Value> {
+    let (pkg_id, compiled_modules) =
+        load_package_compiled_modules(package_id, bytecode_dir, rpc_url)?;
+
+    let (_, mut interface_value) =
+        build_bytecode_interface_value_from_compiled_modules(&pkg_id, &compiled_modules)?;
+    if include_entrypoints {
+        let entrypoints = build_entrypoint_catalog(&compiled_modules);
+        interface_value["entrypoints"] =
+            serde_json::to_value(entrypoints).context("serialize entrypoint catalog")?;
+    }
+    Ok(interface_value)
+}
+
+pub(super) fn extract_call_targets_inner(
+    package_id: Option<&str>,
+    bytecode_dir: Option<&str>,
+    rpc_url: &str,
+) -> Result<serde_json::Value> {
+    let (pkg_id, compiled_modules) =
+        load_package_compiled_modules(package_id, bytecode_dir, rpc_url)?;
+    let functions = build_call_target_catalog(&compiled_modules);
+    let external_targets: BTreeSet<(&str, &str, &str)> = functions
+        .iter()
+        .flat_map(|function| &function.calls)
+        .filter(|call| call.external)
+        .map(|call| {
+            (
+                call.package.as_str(),
+                call.module.as_str(),
+                call.function.as_str(),
+            )
+        })
+        .collect();
+    let external_packages: BTreeSet<&str> = external_targets
+        .iter()
+        .map(|(package, _, _)| *package)
+        .collect();
+    Ok(serde_json::json!({
+        "package_id": pkg_id,
+        "functions": functions,
+        "external_targets": external_targets
+            .iter()
+            .map(|(package, module, function)| serde_json::json!({
+                "package": package,
+                "module": module,
+                "function": function,
+            }))
+            .collect::<Vec<_>>(),
+        "external_packages": external_packages,
+    }))
+}
+
+pub(super) fn package_types_inner(
+    package_id: Option<&str>,
+    bytecode_dir: Option<&str>,
+    rpc_url: &str,
+) -> Result<serde_json::Value> {
+    let (pkg_id, compiled_modules) =
+        load_package_compiled_modules(package_id, bytecode_dir, rpc_url)?;
+    let types = build_package_type_catalog(&compiled_modules);
+    let objects = types.iter().filter(|ty| ty.is_object).count();
+    let one_time_witnesses = types.iter().filter(|ty| ty.is_one_time_witness).count();
+    Ok(serde_json::json!({
+        "package_id": pkg_id,
+        "types": types,
+        "counts": {
+            "types": types.len(),
+            "objects": objects,
+            "one_time_witnesses": one_time_witnesses,
+        },
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// replay (native — unified analyze + execute)
+// ---------------------------------------------------------------------------
+
+/// Optional replay knobs that don't change the hydration path.
+///
+/// Kept separate from the positional flags so new options don't ripple through
+/// every `replay_inner` / `replay_loaded_state_inner` call site. Serialized into
+/// request cassettes so playback replays with the recorded options; fields that
+/// point at local resources are skipped.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub(super) struct ReplayExtras {
+    /// Drop/substitute objects by type before execution (fault injection).
+    pub(super) object_overrides: sui_sandbox_core::replay_support::ReplayObjectTypeOverrides,
+    /// Swap in locally-built bytecode for packages before execution.
+    pub(super) package_overrides: sui_sandbox_core::replay_support::ReplayPackageOverrides,
+    /// Force objects to be treated as shared, owned or immutable before execution.
+    pub(super) ownership_hints: sui_sandbox_core::replay_support::ReplayOwnershipHints,
+    /// Let `context_path` packages replace fetched packages with the same id.
+    pub(super) context_overrides: bool,
+    /// Emit `effects.return_value_bytes` (base64 per value) for byte-level diffs.
+    pub(super) include_return_bytes: bool,
+    /// Decode each emitted event's BCS payload into `effects.events[i].fields`.
+    pub(super) decode_events: bool,
+    /// Report decoded before/after state and field changes of mutated objects under
+    /// `object_diffs`.
+    pub(super) diff_objects: bool,
+    /// Attribute gas to PTB commands and Move functions and report it under
+    /// `gas_profile`.
+    pub(super) gas_profile: bool,
+    /// Record the Move call tree of every command and report it under `trace`.
+    pub(super) call_trace: Option<sui_sandbox_core::call_trace::CallTraceOptions>,
+    /// Reconcile local and on-chain effects object by object and report the
+    /// classified mismatches under `reconciliation`.
+    pub(super) reconcile: bool,
+    /// Execute as if in this epoch instead of the transaction's own.
+    pub(super) epoch_override: Option<u64>,
+    /// Execute with this protocol version's framework, feature flags and gas schedule
+    /// instead of those of the transaction's epoch.
+    pub(super) protocol_version: Option<u64>,
+    /// Golden-file verify mode: expected `local_effects_fingerprint` of this replay.
+    pub(super) expect_effects_hash: Option<String>,
+    /// Fail instead of warning when a supplied object's digest disagrees with the
+    /// transaction's object reference.
+    pub(super) strict_object_digests: bool,
+    /// With `compare`, fail when no on-chain effects were available to compare against.
+    pub(super) require_comparison: bool,
+    /// Caller-supplied on-chain effects the comparison diffs against instead of the
+    /// effects carried by the fetched or loaded transaction.
+    pub(super) expected_effects: Option<sui_sandbox_core::replay_support::ReplayExpectedEffects>,
+    /// Let natives that cannot run locally return zero values (degraded analysis mode).
+    pub(super) stub_unknown_natives: bool,
+    /// Custom gas cost table charged instead of the protocol schedule where specified.
+    pub(super) gas_table: Option<sui_sandbox_core::gas::CustomGasTable>,
+    /// Checkpoint already fetched by the caller; reused by the Walrus path instead of
+    /// downloading it again when the sequence number matches.
+    #[serde(skip)]
+    pub(super) checkpoint_data: Option<Arc<sui_types::full_checkpoint_content::CheckpointData>>,
+    /// Memoize executed replay outputs keyed by state content and execution settings.
+    #[serde(skip)]
+    pub(super) result_cache: Option<ReplayResultCache>,
+    /// Strict offline mode: never open a network connection; anything the replay would
+    /// fetch is an error instead.
+    #[serde(skip)]
+    pub(super) offline: bool,
+    /// Apply the effects of the system transactions that precede the target in its
+    /// checkpoint (consensus commit prologue, authenticator state) before executing it.
+    pub(super) apply_checkpoint_prologue: bool,
+    /// Write an instruction-level VM trace (JSON lines) to this path.
+    #[serde(skip)]
+    pub(super) instruction_trace: Option<PathBuf>,
+    /// Include operand-stack depth and pushed values in the instruction trace.
+    pub(super) instruction_trace_stack: bool,
+    /// Object/package cache shared by the gRPC/hybrid hydration and the dependency
+    /// closure of every replay in a batch, so packages fetched for one transaction are
+    /// reused by the next.
+    #[serde(skip)]
+    pub(super) shared_state_cache: Option<Arc<sui_state_fetcher::VersionedCache>>,
+    /// Framework resolvers and fetched dependency packages/modules shared by every
+    /// replay in a batch, so each replay forks them instead of rebuilding its resolver
+    /// and dependency closure from scratch.
+    #[serde(skip)]
+    pub(super) shared_resolver: Option<Arc<replay_support::SharedReplayResolver>>,
+    /// Network to hydrate from; `None` keeps the mainnet/environment defaults.
+    pub(super) network: Option<Network>,
+    /// Fetch only the dependency modules reachable from the transaction's Move calls,
+    /// one module at a time, instead of whole packages.
+    pub(super) fetch_modules_only: bool,
+    /// Cassette every GraphQL, gRPC and Walrus client of this replay records to or
+    /// plays back from.
+    #[serde(skip)]
+    pub(super) cassette: Option<Arc<sui_transport::cassette::Cassette>>,
+}
+
+impl ReplayExtras {
+    /// Walrus archive client for the selected network (mainnet by default).
+    fn walrus_client(&self) -> Result<WalrusClient> {
+        let walrus = self
+            .network
+            .as_ref()
+            .map_or_else(|| Ok(WalrusClient::mainnet()), WalrusClient::for_network)?;
+        Ok(match &self.cassette {
+            Some(cassette) => walrus.with_cassette(Arc::clone(cassette)),
+            None => walrus,
+        })
+    }
+
+    /// GraphQL client for `endpoint`, through the replay's cassette if any.
+    fn graphql_client(&self, endpoint: &str) -> GraphQLClient {
+        let graphql = GraphQLClient::new(endpoint);
+        match &self.cassette {
+            Some(cassette) => graphql.with_cassette(Arc::clone(cassette)),
+            None => graphql,
+        }
+    }
+
+    /// Historical gRPC endpoint and API key for the selected network.
+    fn historical_grpc_endpoint(&self) -> (String, Option<String>) {
+        match &self.network {
+            Some(network) => {
+                sui_transport::grpc::historical_endpoint_and_api_key_for_network(network)
+            }
+            None => sui_transport::grpc::historical_endpoint_and_api_key_from_env(),
+        }
+    }
+
+    /// The extras that can change an executed replay's output, for the result-cache key.
+    fn result_cache_settings(&self) -> serde_json::Value {
+        let object_overrides: std::collections::BTreeMap<&String, Option<String>> = self
+            .object_overrides
+            .rules
+            .iter()
+            .map(|(type_tag, bytes)| {
+                let bytes = bytes
+                    .as_ref()
+                    .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes));
+                (type_tag, bytes)
+            })
+            .collect();
+        serde_json::json!({
+            "object_overrides": object_overrides,
+            "include_return_bytes": self.include_return_bytes,
+            "decode_events": self.decode_events,
+            "diff_objects": self.diff_objects,
+            "gas_profile": self.gas_profile,
+            "call_trace": self.call_trace,
+            "reconcile": self.reconcile,
+            "epoch_override": self.epoch_override,
+            "protocol_version": self.protocol_version,
+            "expect_effects_hash": self.expect_effects_hash,
+            "strict_object_digests": self.strict_object_digests,
+            "require_comparison": self.require_comparison,
+            "expected_effects": self.expected_effects.as_ref().map(|expected| &expected.effects),
+            "stub_unknown_natives": self.stub_unknown_natives,
+            "gas_table": self.gas_table,
+            "fetch_modules_only": self.fetch_modules_only,
+        })
+    }
+
+    fn execution_trace_options(
+        &self,
+    ) -> Option<sui_sandbox_core::execution_trace::ExecutionTraceOptions> {
+        self.instruction_trace.as_ref().map(|_| {
+            sui_sandbox_core::execution_trace::ExecutionTraceOptions {
+                stack: self.instruction_trace_stack,
+                ..Default::default()
+            }
+        })
+    }
+}
+
+/// On-disk memo of executed replay outputs (`<dir>/<key>.json`).
+///
+/// The key hashes [`ReplayState::content_hash`] together with every setting that can
+/// change the output, so overrides, a gas table or a different clock timestamp miss
+/// the cache instead of returning a stale result. Package overrides, context packages
+/// ownership hints, the backfilled clock and the protocol version are already part of
+/// the state when the key is computed.
+#[derive(Debug, Clone)]
+pub(super) struct ReplayResultCache {
+    pub(super) dir: PathBuf,
+}
+
+/// One replay's slot in a [`ReplayResultCache`].
+#[derive(Debug)]
+pub(super) struct ReplayResultCacheEntry {
+    pub(super) key: String,
+    path: PathBuf,
+}
+
+impl ReplayResultCache {
+    /// Locate the entry for `replay_state` executed with `settings` (call-site flags)
+    /// and `extras`.
+    pub(super) fn entry(
+        &self,
+        replay_state: &ReplayState,
+        settings: serde_json::Value,
+        extras: &ReplayExtras,
+    ) -> ReplayResultCacheEntry {
+        let key_material = serde_json::json!({
+            "sandbox_version": env!("CARGO_PKG_VERSION"),
+            "state": replay_state.content_hash(),
+            "settings": settings,
+            "extras": extras.result_cache_settings(),
+        });
+        let key = sui_package_extractor::utils::bytes_to_hex(
+            &sui_package_extractor::utils::sha256_32(key_material.to_string().as_bytes()),
+        );
+        let path = self.dir.join(format!("{}.json", key));
+        ReplayResultCacheEntry { key, path }
+    }
+}
+
+impl ReplayResultCacheEntry {
+    /// The cached output, marked as a hit; `None` on a miss or an unreadable entry.
+    pub(super) fn load(&self, warnings: &WarningLog) -> Option<serde_json::Value> {
+        let bytes = std::fs::read(&self.path).ok()?;
+        match serde_json::from_slice::<serde_json::Value>(&bytes) {
+            Ok(mut output) => {
+                output["result_cache"] = self.report(true, None);
+                Some(output)
+            }
+            Err(err) => {
+                warnings.push(
+                    WarningCategory::ResultCache,
+                    format!("ignoring unreadable entry {}: {}", self.path.display(), err),
+                );
+                None
+            }
+        }
+    }
+
+    /// Store a freshly computed output and mark it as a miss.
+    pub(super) fn store(&self, output: &mut serde_json::Value) {
+        let error = std::fs::create_dir_all(self.path.parent().unwrap_or(Path::new(".")))
+            .and_then(|_| std::fs::write(&self.path, output.to_string()))
+            .err()
+            .map(|err| format!("failed to write {}: {}", self.path.display(), err));
+        output["result_cache"] = self.report(false, error);
+    }
+
+    fn report(&self, hit: bool, error: Option<String>) -> serde_json::Value {
+        let mut report = serde_json::json!({
+            "hit": hit,
+            "key": self.key,
+            "path": self.path.display().to_string(),
+        });
+        if let Some(error) = error {
+            report["error"] = serde_json::json!(error);
+        }
+        report
+    }
+}
+
+/// Error for a fetch that strict offline mode refuses to make.
+fn offline_fetch_error(what: impl std::fmt::Display) -> anyhow::Error {
+    anyhow!("offline mode: cannot fetch {}", what)
+}
+
+/// In offline mode, fail with everything execution would otherwise have to fetch:
+/// dependency packages missing from the resolver and input objects missing from the
+/// replay state.
+fn ensure_offline_state_complete(
+    resolver: &sui_sandbox_core::resolver::LocalModuleResolver,
+    replay_state: &ReplayState,
+    maps: &sui_sandbox_core::replay_support::ReplayObjectMaps,
+) -> Result<()> {
+    let mut missing_packages: Vec<String> = resolver
+        .get_missing_dependencies()
+        .into_iter()
+        .map(|address| address.to_hex_literal())
+        .collect();
+    missing_packages.sort();
+    let missing_objects: Vec<String> = sui_sandbox_core::tx_replay::find_missing_input_objects(
+        &replay_state.transaction,
+        &maps.cached_objects,
+    )
+    .into_iter()
+    .map(|object| format!("{}@{}", object.object_id, object.version))
+    .collect();
+    let mut missing = Vec::new();
+    if !missing_packages.is_empty() {
+        missing.push(format!(
+            "{} package(s) [{}]",
+            missing_packages.len(),
+            missing_packages.join(", ")
+        ));
+    }
+    if !missing_objects.is_empty() {
+        missing.push(format!(
+            "{} input object(s) [{}]",
+            missing_objects.len(),
+            missing_objects.join(", ")
+        ));
+    }
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(offline_fetch_error(format!(
+        "{}; add them to the state file or context",
+        missing.join(" and ")
+    )))
+}
+
+/// Surface digest mismatches from object map construction: an error in strict mode,
+/// `[digest]` warnings otherwise. Referenced objects with no digest to check are
+/// always reported as `[digest]` warnings.
+fn check_object_digests(
+    maps: &sui_sandbox_core::replay_support::ReplayObjectMaps,
+    strict: bool,
+    warnings: &WarningLog,
+) -> Result<()> {
+    if strict {
+        maps.ensure_object_digests_match()?;
+    }
+    for mismatch in &maps.digest_mismatches {
+        warnings.push(WarningCategory::Digest, mismatch);
+    }
+    for object in &maps.unverified_digests {
+        warnings.push(
+            WarningCategory::Digest,
+            format!(
+                "object {} has no digest; its bytes were not checked against the transaction reference",
+                object
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// Why `compare=True` produced no `comparison`; `None` when it did (or compare is off).
+///
+/// `loaded_from` names the replay-state origin (`state_file`, `local_cache`, ...) when
+/// the state was not fetched from `source`.
+fn comparison_unavailable(
+    output: &serde_json::Value,
+    replay_state: &ReplayState,
+    source: &str,
+    loaded_from: Option<&str>,
+) -> Option<serde_json::Value> {
+    if output.get("comparison").is_some() {
+        return None;
+    }
+    let (reason, detail) = if output.get("effects").is_none() {
+        (
+            "replay_failed",
+            format!(
+                "replay produced no local effects: {}",
+                output
+                    .get("local_error")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("unknown error")
+            ),
+        )
+    } else if replay_state.transaction.effects.is_some() {
+        (
+            "comparison_skipped",
+            "on-chain effects were present but no comparison was produced".to_string(),
+        )
+    } else if let Some(origin) = loaded_from {
+        (
+            "no_effects_in_state",
+            format!("{} replay state carries no on-chain effects", origin),
+        )
+    } else {
+        (
+            "source_without_effects",
+            format!(
+                "source `{}` returned the transaction without on-chain effects \
+                 (the effects fetch failed or the source does not provide them)",
+                source
+            ),
+        )
+    };
+    Some(serde_json::json!({ "reason": reason, "detail": detail }))
+}
+
+/// Record `comparison_unavailable` for `compare=True` replays that could not compare.
+///
+/// Under `require_comparison` this is an error instead, raised as
+/// [`sui_sandbox_core::SandboxError::ComparisonUnavailable`] so strict checks cannot
+/// pass vacuously and callers can tell it apart from a failed replay.
+pub(super) fn check_comparison_available(
+    output: &mut serde_json::Value,
+    replay_state: &ReplayState,
+    source: &str,
+    loaded_from: Option<&str>,
+    require: bool,
+    warnings: &WarningLog,
+) -> Result<()> {
+    let Some(unavailable) = comparison_unavailable(output, replay_state, source, loaded_from)
+    else {
+        return Ok(());
+    };
+    let reason = unavailable["reason"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let detail = unavailable["detail"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    if require {
+        return Err(
+            sui_sandbox_core::SandboxError::ComparisonUnavailable { reason, detail }.into(),
+        );
+    }
+    warnings.push(
+        WarningCategory::Compare,
+        format!("comparison unavailable ({}): {}", reason, detail),
+    );
+    output["comparison_unavailable"] = unavailable;
+    Ok(())
+}
+
+/// Base64-encode each command's return values from a successful replay.
+fn encode_return_value_bytes(
+    replay_result: &Result<sui_sandbox_core::tx_replay::ReplayExecution>,
+) -> Option<serde_json::Value> {
+    let execution = replay_result.as_ref().ok()?;
+    let encoded: Vec<Vec<String>> = execution
+        .effects
+        .return_values
+        .iter()
+        .map(|values| {
+            values
+                .iter()
+                .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+                .collect()
+        })
+        .collect();
+    Some(serde_json::json!(encoded))
+}
+
+fn attach_return_value_bytes(output: &mut serde_json::Value, encoded: Option<serde_json::Value>) {
+    if let (Some(encoded), Some(effects)) = (encoded, output.get_mut("effects")) {
+        effects["return_value_bytes"] = encoded;
+    }
+}
+
+/// Resolver over the replay state's packages, forked from the batch's shared framework
+/// resolver when there is one.
+fn hydrate_replay_resolver(
+    replay_state: &ReplayState,
+    pkg_aliases: &sui_state_fetcher::PackageAliases,
+    extras: &ReplayExtras,
+) -> Result<sui_sandbox_core::resolver::LocalModuleResolver> {
+    match &extras.shared_resolver {
+        Some(shared) => shared.hydrate(
+            replay_state,
+            &pkg_aliases.linkage_upgrades,
+            &pkg_aliases.aliases,
+        ),
+        None => replay_support::hydrate_resolver_from_replay_state(
+            replay_state,
+            &pkg_aliases.linkage_upgrades,
+            &pkg_aliases.aliases,
+        ),
+    }
+}
+
+/// Resolver over the replay state's packages, for decoding values in the output.
+fn output_decode_resolver(
+    replay_state: &ReplayState,
+) -> Result<sui_sandbox_core::resolver::LocalModuleResolver> {
+    let pkg_aliases = build_aliases(&replay_state.packages, None, replay_state.checkpoint);
+    replay_support::hydrate_resolver_from_replay_state(
+        replay_state,
+        &pkg_aliases.linkage_upgrades,
+        &pkg_aliases.aliases,
+    )
+}
+
+/// Type tag and BCS payload of each event emitted by a successful replay.
+fn collect_event_payloads(
+    replay_result: &Result<sui_sandbox_core::tx_replay::ReplayExecution>,
+) -> Option<Vec<(String, Vec<u8>)>> {
+    let execution = replay_result.as_ref().ok()?;
+    Some(
+        execution
+            .effects
+            .events
+            .iter()
+            .map(|event| (event.type_tag.clone(), event.data.clone()))
+            .collect(),
+    )
+}
+
+/// Add the decoded `fields` of each entry in `effects.events`.
+///
+/// Struct layouts come from the replay state's packages (plus the framework at the
+/// transaction's protocol version); an event that cannot be decoded gets a
+/// `decode_error` instead.
+fn attach_decoded_events(
+    output: &mut serde_json::Value,
+    replay_state: &ReplayState,
+    payloads: Option<Vec<(String, Vec<u8>)>>,
+) {
+    let Some(payloads) = payloads else {
+        return;
+    };
+    let Some(events) = output
+        .get_mut("effects")
+        .and_then(|effects| effects.get_mut("events"))
+        .and_then(serde_json::Value::as_array_mut)
+    else {
+        return;
+    };
+    let resolver = output_decode_resolver(replay_state);
+    for (event, (type_tag, bytes)) in events.iter_mut().zip(payloads) {
+        let decoded = match &resolver {
+            Ok(resolver) => ReplayOrchestrator::decode_bcs_with_layout(&bytes, &type_tag, resolver),
+            Err(err) => Err(anyhow!("build module resolver: {:#}", err)),
+        };
+        match decoded {
+            Ok(fields) => event["fields"] = fields,
+            Err(err) => event["decode_error"] = serde_json::json!(format!("{:#}", err)),
+        }
+    }
+}
+
+/// Decoded before/after state and field-level diff of each object the replay mutated.
+fn collect_object_diffs(
+    replay_state: &ReplayState,
+    replay_result: &Result<sui_sandbox_core::tx_replay::ReplayExecution>,
+    warnings: &WarningLog,
+) -> Option<serde_json::Value> {
+    let execution = replay_result.as_ref().ok()?;
+    match output_decode_resolver(replay_state) {
+        Ok(resolver) => Some(serde_json::json!(
+            sui_sandbox_core::object_diff::build_object_diffs(
+                replay_state,
+                &execution.effects,
+                &resolver
+            )
+        )),
+        Err(err) => {
+            warnings.push(
+                WarningCategory::ObjectDiffs,
+                format!("build module resolver: {:#}", err),
+            );
+            None
+        }
+    }
+}
+
+fn attach_object_diffs(output: &mut serde_json::Value, diffs: Option<serde_json::Value>) {
+    if let Some(diffs) = diffs {
+        output["object_diffs"] = diffs;
+    }
+}
+
+/// Per-command and per-function gas breakdown, present when the harness profiled gas.
+fn collect_gas_profile(
+    replay_result: &Result<sui_sandbox_core::tx_replay::ReplayExecution>,
+) -> Option<serde_json::Value> {
+    let profile = replay_result.as_ref().ok()?.effects.gas_profile.as_ref()?;
+    Some(serde_json::json!(profile))
+}
+
+fn attach_gas_profile(output: &mut serde_json::Value, profile: Option<serde_json::Value>) {
+    if let Some(profile) = profile {
+        output["gas_profile"] = profile;
+    }
+}
+
+/// Move call tree of each command, present when the harness recorded call traces.
+fn collect_call_trace(
+    replay_result: &Result<sui_sandbox_core::tx_replay::ReplayExecution>,
+) -> Option<serde_json::Value> {
+    let trace = replay_result.as_ref().ok()?.effects.call_trace.as_ref()?;
+    Some(serde_json::json!(trace))
+}
+
+fn attach_call_trace(output: &mut serde_json::Value, trace: Option<serde_json::Value>) {
+    if let Some(trace) = trace {
+        output["trace"] = trace;
+    }
+}
+
+/// Object-by-object reconciliation of the local effects against the on-chain (or
+/// caller-supplied expected) effects.
+fn collect_reconciliation(
+    replay_state: &ReplayState,
+    replay_result: &Result<sui_sandbox_core::tx_replay::ReplayExecution>,
+    warnings: &WarningLog,
+) -> Option<serde_json::Value> {
+    let execution = replay_result.as_ref().ok()?;
+    let Some(on_chain) = replay_state.transaction.effects.as_ref() else {
+        warnings.push(
+            WarningCategory::Reconciliation,
+            "no on-chain effects available to reconcile against",
+        );
+        return None;
+    };
+    let gas_objects: Vec<String> = replay_state
+        .transaction
+        .gas_payment
+        .iter()
+        .map(|gas| gas.object_id.clone())
+        .collect();
+    Some(serde_json::json!(
+        sui_sandbox_core::effects_reconciliation::reconcile_effects(
+            on_chain,
+            &execution.effects,
+            &gas_objects
+        )
+    ))
+}
+
+fn attach_reconciliation(
+    output: &mut serde_json::Value,
+    reconciliation: Option<serde_json::Value>,
+) {
+    if let Some(reconciliation) = reconciliation {
+        output["reconciliation"] = reconciliation;
+    }
+}
+
+/// Compare the replay's `local_effects_fingerprint` with `expected` (golden-file verify mode).
+///
+/// On mismatch, `diff` lists the fingerprint fields that differ from the on-chain
+/// effects when the transaction carries them, so the divergence is visible without
+/// the baseline run at hand.
+fn build_effects_verification(
+    expected: &str,
+    replay_state: &ReplayState,
+    replay_result: &Result<sui_sandbox_core::tx_replay::ReplayExecution>,
+) -> serde_json::Value {
+    let tx = &replay_state.transaction;
+    let execution = replay_result.as_ref().ok();
+    let local = execution.map(|execution| core_local_effects_fingerprint(tx, &execution.effects));
+    let actual = local.as_ref().map(|fingerprint| fingerprint.digest());
+    let verified = actual.as_deref() == Some(expected.trim());
+    let mut verification = serde_json::json!({
+        "verified": verified,
+        "expected_effects_hash": expected,
+        "local_effects_fingerprint": actual,
+    });
+    if verified {
+        return verification;
+    }
+    if let Some(local) = local.as_ref() {
+        if let Some(baseline) = core_onchain_effects_fingerprint(tx) {
+            verification["diff_against"] = serde_json::json!("onchain");
+            verification["diff"] = serde_json::json!(local.diff(&baseline));
+        }
+    }
+    verification
+}
+
+fn attach_effects_verification(
+    output: &mut serde_json::Value,
+    verification: Option<serde_json::Value>,
+) {
+    if let Some(verification) = verification {
+        output["verified"] = verification["verified"].clone();
+        output["verification"] = verification;
+    }
+}
+
+/// Attach `stubbed_natives` and a `[native]` warning per stubbed native.
+fn attach_stubbed_natives(
+    output: &mut serde_json::Value,
+    stubbed: &[sui_sandbox_core::natives::StubbedNative],
+    warnings: &WarningLog,
+) {
+    if stubbed.is_empty() {
+        return;
+    }
+    for native in stubbed {
+        warnings.push(
+            WarningCategory::Native,
+            format!(
+                "stubbed {} native {} ({} call(s)); results after this call are not faithful",
+                native.reason, native.function, native.calls
+            ),
+        );
+    }
+    if let Ok(value) = serde_json::to_value(stubbed) {
+        output["stubbed_natives"] = value;
+    }
+}
+
+/// Write the instruction trace to `path` and attach `instruction_trace` (path + counts).
+fn attach_instruction_trace(
+    output: &mut serde_json::Value,
+    log: Option<&sui_sandbox_core::execution_trace::ExecutionTraceLog>,
+    path: Option<&Path>,
+    warnings: &WarningLog,
+) {
+    let (Some(log), Some(path)) = (log, path) else {
+        return;
+    };
+    let summary = log.summary();
+    if summary.dropped_instructions > 0 {
+        warnings.push(
+            WarningCategory::Trace,
+            format!(
+                "instruction trace truncated: {} instruction(s) not recorded",
+                summary.dropped_instructions
+            ),
+        );
+    }
+    let mut value = serde_json::to_value(&summary).unwrap_or_else(|_| serde_json::json!({}));
+    match log.write_jsonl(path) {
+        Ok(_) => value["path"] = serde_json::json!(path.display().to_string()),
+        Err(e) => warnings.push(
+            WarningCategory::Trace,
+            format!("failed to write instruction trace: {:#}", e),
+        ),
+    }
+    output["instruction_trace"] = value;
+}
+
+/// Surface `fidelity` (faithful/degraded + approximations) at the top level of the envelope.
+///
+/// Must run after `object_overrides` is attached; self-heal and Clock signals come from `warnings`.
+fn attach_replay_fidelity(output: &mut serde_json::Value, warnings: &WarningLog) {
+    let fidelity = core_assess_replay_fidelity(output, &warnings.fidelity_signals());
+    if let Ok(value) = serde_json::to_value(fidelity) {
+        output["fidelity"] = value;
+    }
+}
+
+/// Build the simulation config, replacing the epoch when `epoch_override` is set.
+///
+/// The replay keeps the transaction's protocol version, so an override into an epoch that
+/// ran a different protocol version is reported as an `[epoch]` warning. Without
+/// `graphql` (offline replays) the epoch's protocol version is not checked.
+fn build_simulation_config_with_epoch(
+    replay_state: &ReplayState,
+    epoch_override: Option<u64>,
+    graphql: Option<&GraphQLClient>,
+    warnings: &WarningLog,
+) -> SimulationConfig {
+    let config = sui_sandbox_core::replay_support::build_simulation_config(replay_state);
+    let Some(epoch) = epoch_override else {
+        return config;
+    };
+    let Some(graphql) = graphql else {
+        return config.with_epoch(epoch);
+    };
+    if epoch != replay_state.epoch && replay_state.protocol_version > 0 {
+        match graphql.fetch_epoch_protocol_version(epoch) {
+            Ok(Some(version)) if version != replay_state.protocol_version => warnings.push(
+                WarningCategory::Epoch,
+                format!(
+                    "epoch {} ran protocol version {}, but replay uses protocol version {} \
+                     from epoch {}",
+                    epoch, version, replay_state.protocol_version, replay_state.epoch
+                ),
+            ),
+            Ok(Some(_)) => {}
+            Ok(None) => warnings.push(
+                WarningCategory::Epoch,
+                format!(
+                    "epoch {} is not known on-chain; protocol version {} kept",
+                    epoch, replay_state.protocol_version
+                ),
+            ),
+            Err(e) => warnings.push(
+                WarningCategory::Epoch,
+                format!(
+                    "could not verify protocol version for epoch {}: {:#}",
+                    epoch, e
+                ),
+            ),
+        }
+    }
+    config.with_epoch(epoch)
+}
+
+/// Pin the replay to `override_version` when set. Otherwise, with `graphql`, look up
+/// the protocol version of the transaction's epoch for states that do not carry it
+/// (Walrus, JSON-RPC, state files); without it the state's version is kept, with a
+/// warning when the state has none.
+fn apply_protocol_version(
+    replay_state: &mut ReplayState,
+    override_version: Option<u64>,
+    graphql: Option<&GraphQLClient>,
+    warnings: &WarningLog,
+) {
+    if let Some(version) = override_version {
+        replay_state.protocol_version = version;
+        return;
+    }
+    let Some(graphql) = graphql else {
+        if replay_state.protocol_version == 0 {
+            warnings.push(
+                WarningCategory::Protocol,
+                format!(
+                    "protocol version of epoch {} not looked up; replay uses the default \
+                     protocol version {}",
+                    replay_state.epoch,
+                    sui_sandbox_core::vm::DEFAULT_PROTOCOL_VERSION
+                ),
+            );
+        }
+        return;
+    };
+    if sui_sandbox_core::replay_support::backfill_replay_protocol_version(replay_state, graphql)
+        .is_none()
+    {
+        warnings.push(
+            WarningCategory::Protocol,
+            format!(
+                "protocol version of epoch {} unavailable; replay uses protocol version {}",
+                replay_state.epoch, replay_state.protocol_version
+            ),
+        );
+    }
+}
+
+/// Echo the protocol version the VM ran with (and any override) in `execution_path`.
+fn attach_effective_protocol_version(
+    output: &mut serde_json::Value,
+    replay_state: &ReplayState,
+    override_version: Option<u64>,
+) {
+    if let Some(path) = output
+        .get_mut("execution_path")
+        .and_then(|p| p.as_object_mut())
+    {
+        let effective = match replay_state.protocol_version {
+            0 => sui_sandbox_core::vm::DEFAULT_PROTOCOL_VERSION,
+            version => version,
+        };
+        path.insert("protocol_version".to_string(), serde_json::json!(effective));
+        path.insert(
+            "protocol_version_override".to_string(),
+            serde_json::json!(override_version),
+        );
+    }
+}
+
+/// Echo the epoch the VM ran with (and any override) in `execution_path`.
+fn attach_effective_epoch(
+    output: &mut serde_json::Value,
+    replay_state: &ReplayState,
+    epoch_override: Option<u64>,
+) {
+    if let Some(path) = output
+        .get_mut("execution_path")
+        .and_then(|p| p.as_object_mut())
+    {
+        path.insert(
+            "epoch".to_string(),
+            serde_json::json!(epoch_override.unwrap_or(replay_state.epoch)),
+        );
+        path.insert(
+            "epoch_override".to_string(),
+            serde_json::json!(epoch_override),
+        );
+    }
+}
+
+/// Report under `execution_path.gas_table` which gas table entries were custom, with a
+/// `[gas]` warning for each custom entry the replay could not charge.
+fn attach_gas_table_coverage(
+    output: &mut serde_json::Value,
+    coverage: Option<sui_sandbox_core::gas::GasTableCoverage>,
+    warnings: &WarningLog,
+) {
+    let Some(coverage) = coverage else {
+        return;
+    };
+    for (entry, reason) in &coverage.unapplied {
+        warnings.push(
+            WarningCategory::Gas,
+            format!("custom gas table entry {} not applied: {}", entry, reason),
+        );
+    }
+    if let (Some(path), Ok(coverage)) = (
+        output
+            .get_mut("execution_path")
+            .and_then(|p| p.as_object_mut()),
+        serde_json::to_value(coverage),
+    ) {
+        path.insert("gas_table".to_string(), coverage);
+    }
+}
+
+/// Attach the object-override report to a replay envelope when anything was overridden.
+fn attach_object_override_report(
+    output: &mut serde_json::Value,
+    report: &sui_sandbox_core::replay_support::ReplayObjectOverrideReport,
+) {
+    if report.dropped.is_empty() && report.replaced.is_empty() {
+        return;
+    }
+    if let Ok(value) = serde_json::to_value(report) {
+        output["object_overrides"] = value;
+    }
+}
+
+/// Apply `package_overrides` to the replay state and return the report to attach.
+fn apply_package_overrides(
+    replay_state: &mut ReplayState,
+    overrides: &sui_sandbox_core::replay_support::ReplayPackageOverrides,
+    verbose: bool,
+) -> Result<Vec<sui_sandbox_core::replay_support::ReplayPackageOverride>> {
+    if overrides.is_empty() {
+        return Ok(Vec::new());
+    }
+    let report = overrides
+        .apply(replay_state)
+        .context("Failed to apply package overrides")?;
+    if verbose {
+        for entry in &report {
+            eprintln!(
+                "[package_overrides] {} {} with {} module(s)",
+                if entry.replaced { "replaced" } else { "added" },
+                entry.package_id,
+                entry.modules.len()
+            );
+        }
+    }
+    Ok(report)
+}
+
+/// Apply `ownership_hints` to the replay state, warning about hints that contradict the
+/// hydrated object metadata.
+fn apply_ownership_hints(
+    replay_state: &mut ReplayState,
+    hints: &sui_sandbox_core::replay_support::ReplayOwnershipHints,
+    warnings: &WarningLog,
+) -> Result<Vec<sui_sandbox_core::replay_support::ReplayOwnershipHintReport>> {
+    if hints.is_empty() {
+        return Ok(Vec::new());
+    }
+    let report = hints.apply(replay_state)?;
+    for entry in &report {
+        if let (true, Some(hydrated)) = (entry.contradicted, entry.hydrated) {
+            warnings.push(
+                WarningCategory::Ownership,
+                format!(
+                    "{} hinted {} but hydrated metadata says {}",
+                    entry.object_id,
+                    entry.hint.as_str(),
+                    hydrated.as_str()
+                ),
+            );
+        }
+    }
+    Ok(report)
+}
+
+/// Effects the replay compares against: the caller's `expected_effects` when
+/// supplied, otherwise the effects the transaction carries.
+fn comparison_effects<'a>(
+    replay_state: &'a ReplayState,
+    extras: &'a ReplayExtras,
+) -> Option<&'a sui_sandbox_core::tx_replay::TransactionEffectsSummary> {
+    extras
+        .expected_effects
+        .as_ref()
+        .map(|expected| &expected.effects)
+        .or(replay_state.transaction.effects.as_ref())
+}
+
+/// Install `expected_effects` as the transaction's on-chain effects for the reports
+/// built after execution (reconciliation, verification, `comparison_unavailable`).
+/// Called once the replay has run, so execution never sees them. Returns
+/// `Some(replaced)` when effects were supplied, `replaced` telling whether the
+/// transaction already carried effects of its own.
+fn apply_expected_effects(
+    replay_state: &mut ReplayState,
+    extras: &ReplayExtras,
+    verbose: bool,
+) -> Option<bool> {
+    let expected = extras.expected_effects.as_ref()?;
+    let replaced = expected.apply(replay_state);
+    if verbose {
+        eprintln!(
+            "[expected_effects] compared against supplied effects{}",
+            if replaced {
+                " (instead of the fetched effects)"
+            } else {
+                ""
+            }
+        );
+    }
+    Some(replaced)
+}
+
+/// Dependency closure outcome: packages fetched, the transport that served them
+/// (`dependency_fetch_mode`), and whether GraphQL turned out to be unreachable.
+struct DependencyClosure {
+    fetched: usize,
+    mode: &'static str,
+    graphql_unreachable: bool,
+}
+
+/// Fetch the resolver's dependency closure via GraphQL, falling back to gRPC package
+/// fetches when the GraphQL endpoint is unreachable. Failures are recorded as warnings.
+/// Packages (and modules) in the batch's shared resolver, or else in its shared state
+/// cache, are reused, and GraphQL fetches are stored there for later replays.
+///
+/// With `module_roots`, only the modules reachable from those roots are fetched, one
+/// module at a time; the gRPC fallback still fetches whole packages.
+fn fetch_dependency_closure_with_fallback(
+    resolver: &mut sui_sandbox_core::resolver::LocalModuleResolver,
+    graphql: &GraphQLClient,
+    checkpoint: Option<u64>,
+    extras: &ReplayExtras,
+    module_roots: Option<&[ModuleId]>,
+    verbose: bool,
+    warnings: &WarningLog,
+) -> DependencyClosure {
+    let shared = extras.shared_resolver.as_deref();
+    let (result, mode) = match module_roots {
+        Some(roots) => (
+            match shared {
+                Some(shared) => {
+                    shared.fetch_module_closure(resolver, graphql, roots, checkpoint, verbose)
+                }
+                None => sui_sandbox_core::replay_support::fetch_module_closure(
+                    resolver, graphql, roots, checkpoint, verbose,
+                ),
+            },
+            "graphql_module_closure",
+        ),
+        None => (
+            match shared {
+                Some(shared) => {
+                    shared.fetch_dependency_closure(resolver, graphql, checkpoint, verbose)
+                }
+                None => sui_sandbox_core::replay_support::fetch_dependency_closure_cached(
+                    resolver,
+                    graphql,
+                    checkpoint,
+                    extras.shared_state_cache.as_deref(),
+                    verbose,
+                ),
+            },
+            "graphql_dependency_closure",
+        ),
+    };
+    let err = match result {
+        Ok(fetched) => {
+            return DependencyClosure {
+                fetched,
+                mode,
+                graphql_unreachable: false,
+            }
+        }
+        Err(err) => err,
+    };
+    if !GraphQLClient::is_unreachable_error(&err) {
+        warnings.push(
+            WarningCategory::Deps,
+            format!("dependency closure fetch failed: {:#}", err),
+        );
+        return DependencyClosure {
+            fetched: 0,
+            mode,
+            graphql_unreachable: false,
+        };
+    }
+    if verbose {
+        eprintln!(
+            "[deps] GraphQL unreachable ({:#}); fetching closure via gRPC",
+            err
+        );
+    }
+    match fetch_dependency_closure_via_grpc(resolver, graphql.cassette()) {
+        Ok(fetched) => {
+            warnings.push(
+                WarningCategory::Deps,
+                format!(
+                    "GraphQL unreachable; dependency closure served by gRPC at latest package versions ({} package(s))",
+                    fetched
+                ),
+            );
+            DependencyClosure {
+                fetched,
+                mode: "grpc_dependency_closure",
+                graphql_unreachable: true,
+            }
+        }
+        Err(grpc_err) => {
+            warnings.push(
+                WarningCategory::Deps,
+                format!(
+                    "dependency closure fetch failed: GraphQL unreachable ({:#}); gRPC fallback failed ({:#})",
+                    err, grpc_err
+                ),
+            );
+            DependencyClosure {
+                fetched: 0,
+                mode: "grpc_dependency_closure",
+                graphql_unreachable: true,
+            }
+        }
+    }
+}
+
+/// Fetch the resolver's missing dependencies (and their transitive deps) over gRPC
+/// alone, for when GraphQL is unreachable.
+///
+/// gRPC cannot resolve a package at a checkpoint, so each package is fetched at its
+/// latest version; upgraded dependencies are followed through the linkage tables of
+/// the packages already fetched. Requests go through `cassette`, the one the GraphQL
+/// client being replaced records to.
+fn fetch_dependency_closure_via_grpc(
+    resolver: &mut sui_sandbox_core::resolver::LocalModuleResolver,
+    cassette: Option<&Arc<sui_transport::cassette::Cassette>>,
+) -> Result<usize> {
+    if resolver.get_missing_dependencies().is_empty() {
+        return Ok(0);
+    }
+    let (grpc_endpoint, api_key) = sui_transport::grpc::historical_endpoint_and_api_key_from_env();
+    let pool = sui_transport::grpc::global_pool();
+    let grpc = pool
+        .block_on(pool.client_with_cassette(&grpc_endpoint, api_key, cassette.cloned()))
+        .context("Failed to create gRPC client")?;
+    fetch_missing_dependencies_with(resolver, |storage_id| {
+        let id = storage_id.to_hex_literal();
+        let object = pool
+            .block_on(grpc.get_object(&id))
+            .with_context(|| format!("Failed to fetch package {} via gRPC", id))?;
+        object
+            .filter(|object| object.package_modules.is_some())
+            .map(|object| sui_state_fetcher::grpc_object_to_package(&object, storage_id))
+            .transpose()
+    })
+}
+
+/// Walk the resolver's missing dependencies to a fixed point, loading each package
+/// `fetch` returns (by storage id) and registering its linkage so the next round asks
+/// for upgraded dependencies at their storage ids. Returns the packages loaded.
+pub(super) fn fetch_missing_dependencies_with(
+    resolver: &mut sui_sandbox_core::resolver::LocalModuleResolver,
+    mut fetch: impl FnMut(AccountAddress) -> Result<Option<sui_state_fetcher::PackageData>>,
+) -> Result<usize> {
+    const MAX_ROUNDS: usize = 8;
+    let mut fetched = 0usize;
+    let mut seen: HashSet<AccountAddress> = HashSet::new();
+    for _ in 0..MAX_ROUNDS {
+        let pending: Vec<AccountAddress> = resolver
+            .get_missing_dependencies()
+            .into_iter()
+            .filter(|addr| seen.insert(*addr))
+            .collect();
+        if pending.is_empty() {
+            break;
+        }
+        for runtime_id in pending {
+            let storage_id = resolver
+                .get_linkage_upgrade(&runtime_id)
+                .unwrap_or(runtime_id);
+            let Some(pkg) = fetch(storage_id)? else {
+                continue;
+            };
+            if pkg.modules.is_empty() {
+                continue;
+            }
+            resolver.add_linkage_upgrades(pkg.linkage);
+            resolver
+                .add_package_modules_at(pkg.modules, Some(storage_id))
+                .with_context(|| {
+                    format!("Failed to load package {}", storage_id.to_hex_literal())
+                })?;
+            fetched += 1;
+        }
+    }
+    Ok(fetched)
+}
+
+/// Attach the per-type `synthesis` breakdown when any input object was synthesized.
+fn attach_synthesis_summary(output: &mut serde_json::Value, summary: &CoreSynthesisSummary) {
+    if summary.is_empty() {
+        return;
+    }
+    if let Ok(value) = serde_json::to_value(summary) {
+        output["synthesis"] = value;
+    }
+}
+
+/// Attach the package-override report to a replay envelope when anything was overridden.
+fn attach_package_override_report(
+    output: &mut serde_json::Value,
+    report: &[sui_sandbox_core::replay_support::ReplayPackageOverride],
+) {
+    if report.is_empty() {
+        return;
+    }
+    if let Ok(value) = serde_json::to_value(report) {
+        output["package_overrides"] = value;
+    }
+}
+
+/// Reject system and consensus transactions before execution. They carry no PTB, so
+/// they would otherwise fail much later with an unrelated error. Transactions with
+/// commands are programmable and need no lookup; for the rest the kind comes from the
+/// Walrus checkpoint when available, else from GraphQL (`None` when offline).
+pub(super) fn ensure_programmable_transaction(
+    replay_state: &ReplayState,
+    checkpoint_data: Option<&Arc<sui_types::full_checkpoint_content::CheckpointData>>,
+    graphql: Option<&GraphQLClient>,
+    warnings: &WarningLog,
+) -> Result<()> {
+    if !replay_state.transaction.commands.is_empty() {
+        return Ok(());
+    }
+    let digest = &replay_state.transaction.digest.0;
+    let checkpoint_kind = checkpoint_data
+        .and_then(|data| {
+            data.transactions
+                .iter()
+                .find(|tx| tx.transaction.digest().to_string() == *digest)
+        })
+        .map(|tx| {
+            tx.transaction
+                .data()
+                .transaction_data()
+                .kind()
+                .name()
+                .to_string()
+        });
+    let kind = match (checkpoint_kind, graphql) {
+        (Some(kind), _) => Ok(kind),
+        (None, Some(graphql)) => graphql.fetch_transaction_kind(digest),
+        (None, None) => Err(anyhow!("offline, no checkpoint data")),
+    };
+    check_programmable_kind(digest, kind, warnings)
+}
+
+/// Fail for a non-programmable transaction `kind`; a kind that could not be determined
+/// is let through with a `[tx_kind]` warning.
+pub(super) fn check_programmable_kind(
+    digest: &str,
+    kind: Result<String>,
+    warnings: &WarningLog,
+) -> Result<()> {
+    match kind {
+        Ok(kind) if kind != "ProgrammableTransaction" => Err(anyhow!(
+            "transaction {} is a {}, not a ProgrammableTransaction; only programmable \
+             transactions can be replayed (system and consensus transactions are executed \
+             by validators, not submitted as PTBs)",
+            digest,
+            kind
+        )),
+        Ok(_) => Ok(()),
+        Err(err) => {
+            warnings.push(
+                WarningCategory::TxKind,
+                format!(
+                    "transaction {} has no commands and its kind could not be determined \
+                     ({:#}); replaying it as a programmable transaction",
+                    digest, err
+                ),
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Apply the system transactions that precede the target in its checkpoint (consensus
+/// commit prologue, authenticator state, randomness) to the replay state.
+///
+/// Uses `checkpoint_data` when it is the transaction's checkpoint, otherwise downloads the
+/// checkpoint from Walrus. Returns the `checkpoint_prologue` report to attach.
+fn apply_checkpoint_prologue_context(
+    replay_state: &mut ReplayState,
+    checkpoint_data: Option<&Arc<sui_types::full_checkpoint_content::CheckpointData>>,
+    extras: &ReplayExtras,
+    fetch_latency: Option<&Arc<LatencyRecorder>>,
+    verbose: bool,
+) -> Result<Option<serde_json::Value>> {
+    if !extras.apply_checkpoint_prologue {
+        return Ok(None);
+    }
+    let digest = replay_state.transaction.digest.0.clone();
+    let checkpoint = replay_state
+        .checkpoint
+        .or(replay_state.transaction.checkpoint)
+        .ok_or_else(|| {
+            anyhow!(
+                "apply_checkpoint_prologue requires the checkpoint of {}, which is unknown",
+                digest
+            )
+        })?;
+    let cached = checkpoint_data
+        .or(extras.checkpoint_data.as_ref())
+        .filter(|data| data.checkpoint_summary.sequence_number == checkpoint);
+    let checkpoint_data = match cached {
+        Some(data) => Arc::clone(data),
+        None if extras.offline => {
+            return Err(offline_fetch_error(format!(
+                "checkpoint {} from Walrus for apply_checkpoint_prologue",
+                checkpoint
+            )));
+        }
+        None => {
+            let mut walrus = extras.walrus_client()?;
+            if let Some(recorder) = fetch_latency {
+                walrus = walrus.with_latency_recorder(Arc::clone(recorder));
+            }
+            Arc::new(walrus.get_checkpoint(checkpoint).with_context(|| {
+                format!("Failed to fetch checkpoint {} from Walrus", checkpoint)
+            })?)
+        }
+    };
+    let applied =
+        sui_state_fetcher::apply_checkpoint_prologue(&checkpoint_data, &digest, replay_state)
+            .context("Failed to apply checkpoint prologue")?;
+    if verbose {
+        for tx in &applied {
+            eprintln!(
+                "[prologue] applied {} {} ({} object(s))",
+                tx.kind,
+                tx.digest,
+                tx.objects.len()
+            );
+        }
+    }
+    Ok(Some(serde_json::json!({
+        "checkpoint": checkpoint,
+        "timestamp_ms": replay_state.transaction.timestamp_ms,
+        "transactions": applied,
+    })))
+}
+
+/// Attach the `checkpoint_prologue` report when the prologue was applied.
+fn attach_checkpoint_prologue(output: &mut serde_json::Value, report: Option<serde_json::Value>) {
+    if let Some(report) = report {
+        output["checkpoint_prologue"] = report;
     }
+}
 
-    let pkg_id_str = package_id.unwrap();
-    let graphql_endpoint = resolve_graphql_endpoint(rpc_url);
-    let graphql = GraphQLClient::new(&graphql_endpoint);
-    let pkg = graphql
-        .fetch_package(pkg_id_str)
-        .with_context(|| format!("fetch package {}", pkg_id_str))?;
+/// Attach the ownership-hint report to a replay envelope when hints were given.
+fn attach_ownership_hint_report(
+    output: &mut serde_json::Value,
+    report: &[sui_sandbox_core::replay_support::ReplayOwnershipHintReport],
+) {
+    if report.is_empty() {
+        return;
+    }
+    if let Ok(value) = serde_json::to_value(report) {
+        output["ownership_hints"] = value;
+    }
+}
 
-    let raw_modules = sui_transport::decode_graphql_modules(pkg_id_str, &pkg.modules)?;
-    let compiled_modules: Vec<CompiledModule> = raw_modules
-        .into_iter()
-        .map(|(name, bytes)| {
-            CompiledModule::deserialize_with_defaults(&bytes)
-                .map_err(|e| anyhow!("deserialize {}::{}: {:?}", pkg_id_str, name, e))
-        })
-        .collect::<Result<_>>()?;
+fn attach_expected_effects(output: &mut serde_json::Value, replaced: Option<bool>) {
+    if let Some(replaced) = replaced {
+        output["expected_effects"] = serde_json::json!({
+            "supplied": true,
+            "replaced_fetched_effects": replaced,
+        });
+    }
+}
 
-    let (_, interface_value) =
-        build_bytecode_interface_value_from_compiled_modules(pkg_id_str, &compiled_modules)?;
-    Ok(interface_value)
+/// Attach the context-vs-fetch `package_sources` report when a context was merged.
+fn attach_package_sources(output: &mut serde_json::Value, sources: Option<serde_json::Value>) {
+    if let Some(sources) = sources {
+        output["package_sources"] = sources;
+    }
 }
 
-// ---------------------------------------------------------------------------
-// replay (native — unified analyze + execute)
-// ---------------------------------------------------------------------------
+/// Attach `fetch_latency` (per-kind request latency distribution) when anything was fetched.
+pub(super) fn attach_fetch_latency(output: &mut serde_json::Value, recorder: &LatencyRecorder) {
+    if recorder.is_empty() {
+        return;
+    }
+    if let Ok(value) = serde_json::to_value(recorder.summary()) {
+        output["fetch_latency"] = value;
+    }
+}
 
 pub(super) fn replay_inner(
     digest: &str,
@@ -389,45 +2289,92 @@ pub(super) fn replay_inner(
     analyze_only: bool,
     analyze_mm2: bool,
     verbose: bool,
+    extras: &ReplayExtras,
 ) -> Result<serde_json::Value> {
     use sui_sandbox_core::replay_support;
     use sui_sandbox_core::tx_replay::{self, EffectsReconcilePolicy};
 
+    if extras.offline {
+        return Err(offline_fetch_error(format!(
+            "replay state for {} from source '{}'; replay from state_file or cache_dir",
+            digest, source
+        )));
+    }
+
+    let warnings = WarningLog::new(verbose);
+    let fetch_latency = Arc::new(LatencyRecorder::new());
+
     // ---------------------------------------------------------------
     // 1. Fetch ReplayState
     // ---------------------------------------------------------------
     let mut replay_state: ReplayState;
     let graphql_client: GraphQLClient;
     let effective_source: String;
+    let mut existence_probe: Option<sui_transport::grpc::GrpcClient> = None;
+    let mut walrus_checkpoint = None;
 
-    if let Some(cp) = checkpoint {
+    if source.eq_ignore_ascii_case("jsonrpc") {
+        // JSON-RPC path — plain full node endpoint, no gRPC/GraphQL needed for hydration
+        if verbose {
+            eprintln!(
+                "[jsonrpc] fetching replay state for {} from {}",
+                digest, rpc_url
+            );
+        }
+        let jsonrpc = JsonRpcClient::new(rpc_url);
+        replay_state = fetch_replay_state_via_jsonrpc(&jsonrpc, digest)
+            .context("Failed to fetch replay state via JSON-RPC")?;
+        let gql_endpoint = resolve_graphql_endpoint(rpc_url);
+        graphql_client = extras.graphql_client(&gql_endpoint);
+        effective_source = "jsonrpc".to_string();
+    } else if let Some(cp) = checkpoint {
         // Walrus path — no API key needed
         if verbose {
             eprintln!("[walrus] fetching checkpoint {} for digest {}", cp, digest);
         }
-        let checkpoint_data = WalrusClient::mainnet()
-            .get_checkpoint(cp)
-            .context("Failed to fetch checkpoint from Walrus")?;
+        let checkpoint_data = match extras
+            .checkpoint_data
+            .as_ref()
+            .filter(|data| data.checkpoint_summary.sequence_number == cp)
+        {
+            Some(data) => Arc::clone(data),
+            None => Arc::new(
+                extras
+                    .walrus_client()?
+                    .with_latency_recorder(Arc::clone(&fetch_latency))
+                    .get_checkpoint(cp)
+                    .context("Failed to fetch checkpoint from Walrus")?,
+            ),
+        };
         replay_state = checkpoint_to_replay_state(&checkpoint_data, digest)
             .context("Failed to convert checkpoint to replay state")?;
+        walrus_checkpoint = Some(checkpoint_data);
         let gql_endpoint = resolve_graphql_endpoint(rpc_url);
-        graphql_client = GraphQLClient::new(&gql_endpoint);
+        graphql_client = extras.graphql_client(&gql_endpoint);
         effective_source = "walrus".to_string();
     } else {
         // gRPC/hybrid path — requires API key
-        let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+        let rt = sui_transport::grpc::global_pool();
 
         let gql_endpoint = resolve_graphql_endpoint(rpc_url);
-        graphql_client = GraphQLClient::new(&gql_endpoint);
+        graphql_client = extras.graphql_client(&gql_endpoint);
 
-        let (grpc_endpoint, api_key) =
-            sui_transport::grpc::historical_endpoint_and_api_key_from_env();
+        let (grpc_endpoint, api_key) = extras.historical_grpc_endpoint();
 
         let provider = rt.block_on(async {
-            let grpc = sui_transport::grpc::GrpcClient::with_api_key(&grpc_endpoint, api_key)
+            let grpc = rt
+                .client_with_cassette(&grpc_endpoint, api_key, extras.cassette.clone())
                 .await
                 .context("Failed to create gRPC client")?;
-            let mut provider = HistoricalStateProvider::with_clients(grpc, graphql_client.clone());
+            existence_probe = Some(grpc.clone());
+            let mut provider = HistoricalStateProvider::with_clients(grpc, graphql_client.clone())
+                .with_latency_recorder(Arc::clone(&fetch_latency));
+            if let Some(cache) = &extras.shared_state_cache {
+                provider = provider.with_cache(Arc::clone(cache));
+            }
+            if let Some(cassette) = &extras.cassette {
+                provider = provider.with_cassette(Arc::clone(cassette));
+            }
 
             // Enable Walrus for hybrid/walrus sources
             if source == "walrus" || source == "hybrid" {
@@ -455,16 +2402,56 @@ pub(super) fn replay_inner(
         })?;
         effective_source = source.to_string();
     }
+    ensure_programmable_transaction(
+        &replay_state,
+        walrus_checkpoint.as_ref(),
+        Some(&graphql_client),
+        &warnings,
+    )?;
 
+    let mut package_sources = None;
     if let Some(context_packages) = context_packages {
-        let merged = merge_context_packages(&mut replay_state, context_packages);
-        if verbose && merged > 0 {
+        let merge = merge_context_packages(
+            &mut replay_state,
+            context_packages,
+            extras.context_overrides,
+        );
+        if verbose && merge.merged() > 0 {
             eprintln!(
-                "[context] merged {} package(s) from prepared context before replay",
-                merged
+                "[context] merged {} package(s) from prepared context before replay ({} replaced)",
+                merge.merged(),
+                merge.replaced.len()
             );
         }
+        package_sources = Some(merge.sources_json(&replay_state));
     }
+    let package_override_report =
+        apply_package_overrides(&mut replay_state, &extras.package_overrides, verbose)?;
+    let ownership_hint_report =
+        apply_ownership_hints(&mut replay_state, &extras.ownership_hints, &warnings)?;
+    let checkpoint_prologue = apply_checkpoint_prologue_context(
+        &mut replay_state,
+        walrus_checkpoint.as_ref(),
+        extras,
+        Some(&fetch_latency),
+        verbose,
+    )?;
+
+    if replay_support::backfill_replay_clock_timestamp(&mut replay_state, Some(&graphql_client))
+        .is_none()
+    {
+        warnings.push(
+            WarningCategory::Clock,
+            "transaction timestamp unavailable; Clock uses the default base timestamp",
+        );
+    }
+    let epoch_lookup = matches!(effective_source.as_str(), "walrus" | "jsonrpc");
+    apply_protocol_version(
+        &mut replay_state,
+        extras.protocol_version,
+        epoch_lookup.then_some(&graphql_client),
+        &warnings,
+    );
 
     if verbose {
         eprintln!(
@@ -480,6 +2467,32 @@ pub(super) fn replay_inner(
         );
     }
 
+    // A cache hit returns before execution, so traced replays always run.
+    let result_cache = extras
+        .result_cache
+        .as_ref()
+        .filter(|_| !analyze_only && extras.instruction_trace.is_none())
+        .map(|cache| {
+            let settings = serde_json::json!({
+                "source": source,
+                "effective_source": effective_source,
+                "vm_only": vm_only,
+                "allow_fallback": allow_fallback,
+                "auto_system_objects": auto_system_objects,
+                "prefetch": [!no_prefetch, prefetch_depth, prefetch_limit],
+                "self_heal_dynamic_fields": self_heal_dynamic_fields,
+                "compare": compare,
+                "synthesize_missing": synthesize_missing,
+            });
+            cache.entry(&replay_state, settings, extras)
+        });
+    if let Some(output) = result_cache
+        .as_ref()
+        .and_then(|entry| entry.load(&warnings))
+    {
+        return Ok(output);
+    }
+
     // ---------------------------------------------------------------
     // 2. Analyze-only: return state summary without VM execution
     // ---------------------------------------------------------------
@@ -494,24 +2507,27 @@ pub(super) fn replay_inner(
             !no_prefetch,
             prefetch_depth,
             prefetch_limit,
+            (!extras.offline).then_some(&graphql_client),
             verbose,
         )?;
         if analyze_mm2 {
             let pkg_aliases = build_aliases(&replay_state.packages, None, replay_state.checkpoint);
-            let mut resolver = replay_support::hydrate_resolver_from_replay_state(
-                &replay_state,
-                &pkg_aliases.linkage_upgrades,
-                &pkg_aliases.aliases,
-            )?;
-            let _ = replay_support::fetch_dependency_closure(
+            let mut resolver = hydrate_replay_resolver(&replay_state, &pkg_aliases, extras)?;
+            fetch_dependency_closure_with_fallback(
                 &mut resolver,
                 &graphql_client,
                 replay_state.checkpoint,
+                extras,
+                None,
                 verbose,
+                &warnings,
             );
             let modules: Vec<CompiledModule> = resolver.iter_modules().cloned().collect();
             attach_mm2_summary_fields(&mut output, modules, verbose);
         }
+        attach_package_sources(&mut output, package_sources);
+        attach_fetch_latency(&mut output, &fetch_latency);
+        warnings.attach(&mut output);
         return Ok(output);
     }
 
@@ -524,43 +2540,64 @@ pub(super) fn replay_inner(
         replay_state.checkpoint,
     );
 
-    let mut resolver = replay_support::hydrate_resolver_from_replay_state(
-        &replay_state,
-        &pkg_aliases.linkage_upgrades,
-        &pkg_aliases.aliases,
-    )?;
+    let mut resolver = hydrate_replay_resolver(&replay_state, &pkg_aliases, extras)?;
 
-    let fetched_deps = replay_support::fetch_dependency_closure(
+    let module_roots = extras
+        .fetch_modules_only
+        .then(|| replay_support::transaction_module_roots(&replay_state.transaction.commands));
+    let closure = fetch_dependency_closure_with_fallback(
         &mut resolver,
         &graphql_client,
         replay_state.checkpoint,
+        extras,
+        module_roots.as_deref(),
         verbose,
-    )
-    .unwrap_or(0);
-    if verbose && fetched_deps > 0 {
-        eprintln!("[deps] fetched {} dependency packages", fetched_deps);
+        &warnings,
+    );
+    if verbose && closure.fetched > 0 {
+        eprintln!("[deps] fetched {} dependency packages", closure.fetched);
     }
 
     let mut maps = replay_support::build_replay_object_maps(&replay_state, &pkg_aliases.versions);
-    replay_support::maybe_patch_replay_objects(
+    check_object_digests(&maps, extras.strict_object_digests, &warnings)?;
+    let override_report = replay_support::maybe_patch_replay_objects_with_overrides(
         &resolver,
         &replay_state,
         &pkg_aliases.versions,
         &pkg_aliases.aliases,
         &mut maps,
+        &extras.object_overrides,
         verbose,
     );
 
-    let config = replay_support::build_simulation_config(&replay_state);
+    let config = build_simulation_config_with_epoch(
+        &replay_state,
+        extras.epoch_override,
+        Some(&graphql_client),
+        &warnings,
+    );
+    let config = config
+        .with_stub_unknown_natives(extras.stub_unknown_natives)
+        .with_gas_table(extras.gas_table.clone())
+        .with_execution_trace(extras.execution_trace_options())
+        .with_gas_profile(extras.gas_profile)
+        .with_call_trace(extras.call_trace.clone());
     let mut harness = sui_sandbox_core::vm::VMHarness::with_config(&resolver, false, config)?;
     harness
         .set_address_aliases_with_versions(pkg_aliases.aliases.clone(), maps.versions_str.clone());
+    if self_heal_dynamic_fields && closure.graphql_unreachable {
+        warnings.push(
+            WarningCategory::SelfHeal,
+            "GraphQL unreachable; dynamic-field self-heal skipped",
+        );
+    }
+    let self_heal_dynamic_fields = self_heal_dynamic_fields && !closure.graphql_unreachable;
     if self_heal_dynamic_fields {
         let max_version = maps.version_map.values().copied().max().unwrap_or(0);
         let modules: Vec<CompiledModule> = resolver.iter_modules().cloned().collect();
         if !modules.is_empty() {
             let graphql_endpoint = resolve_graphql_endpoint(rpc_url);
-            let graphql = GraphQLClient::new(&graphql_endpoint);
+            let graphql = extras.graphql_client(&graphql_endpoint);
             enable_self_heal_fetchers(
                 &mut harness,
                 &graphql,
@@ -568,6 +2605,7 @@ pub(super) fn replay_inner(
                 max_version,
                 &pkg_aliases.aliases,
                 &modules,
+                &warnings,
             );
         }
     }
@@ -582,13 +2620,15 @@ pub(super) fn replay_inner(
                 max_version,
                 &pkg_aliases.aliases,
                 &modules,
+                &warnings,
             );
         }
     }
 
     let reconcile_policy = EffectsReconcilePolicy::Strict;
-    let mut replay_result = tx_replay::replay_with_version_tracking_with_policy_with_effects(
+    let mut replay_result = tx_replay::replay_with_version_tracking_against_effects(
         &replay_state.transaction,
+        comparison_effects(&replay_state, extras),
         &mut harness,
         &maps.cached_objects,
         &pkg_aliases.aliases,
@@ -596,6 +2636,7 @@ pub(super) fn replay_inner(
         reconcile_policy,
     );
     let mut synthetic_inputs = 0usize;
+    let mut synthesis = CoreSynthesisSummary::default();
     if synthesize_missing
         && replay_result
             .as_ref()
@@ -612,10 +2653,11 @@ pub(super) fn replay_inner(
                 &resolver,
                 &pkg_aliases.aliases,
                 &graphql_client,
-                verbose,
+                &warnings,
             ) {
-                Ok(logs) => {
-                    synthetic_inputs = logs.len();
+                Ok(summary) => {
+                    synthetic_inputs = summary.synthesized();
+                    synthesis = summary;
                     if verbose && synthetic_inputs > 0 {
                         eprintln!(
                             "[replay_fallback] synthesized {} missing input object(s)",
@@ -623,30 +2665,55 @@ pub(super) fn replay_inner(
                         );
                     }
                     if synthetic_inputs > 0 {
-                        replay_result =
-                            tx_replay::replay_with_version_tracking_with_policy_with_effects(
-                                &replay_state.transaction,
-                                &mut harness,
-                                &maps.cached_objects,
-                                &pkg_aliases.aliases,
-                                Some(&maps.versions_str),
-                                reconcile_policy,
-                            );
+                        replay_result = tx_replay::replay_with_version_tracking_against_effects(
+                            &replay_state.transaction,
+                            comparison_effects(&replay_state, extras),
+                            &mut harness,
+                            &maps.cached_objects,
+                            &pkg_aliases.aliases,
+                            Some(&maps.versions_str),
+                            reconcile_policy,
+                        );
                     }
                 }
                 Err(err) => {
-                    if verbose {
-                        eprintln!("[replay_fallback] synthesis failed: {}", err);
-                    }
+                    warnings.push(
+                        WarningCategory::Synthesis,
+                        format!("synthesis failed: {:#}", err),
+                    );
                 }
             }
         }
     }
 
+    let expected_effects = apply_expected_effects(&mut replay_state, extras, verbose);
+
     // ---------------------------------------------------------------
     // 4. Build output JSON
     // ---------------------------------------------------------------
-    build_replay_output(
+    let return_value_bytes = extras
+        .include_return_bytes
+        .then(|| encode_return_value_bytes(&replay_result))
+        .flatten();
+    let event_payloads = extras
+        .decode_events
+        .then(|| collect_event_payloads(&replay_result))
+        .flatten();
+    let object_diffs = extras
+        .diff_objects
+        .then(|| collect_object_diffs(&replay_state, &replay_result, &warnings))
+        .flatten();
+    let gas_profile = collect_gas_profile(&replay_result);
+    let call_trace = collect_call_trace(&replay_result);
+    let reconciliation = extras
+        .reconcile
+        .then(|| collect_reconciliation(&replay_state, &replay_result, &warnings))
+        .flatten();
+    let verification = extras
+        .expect_effects_hash
+        .as_deref()
+        .map(|expected| build_effects_verification(expected, &replay_state, &replay_result));
+    let mut output = build_replay_output(
         &replay_state,
         replay_result,
         source,
@@ -657,11 +2724,59 @@ pub(super) fn replay_inner(
         !no_prefetch,
         prefetch_depth,
         prefetch_limit,
-        "graphql_dependency_closure",
-        fetched_deps,
+        closure.mode,
+        closure.fetched,
         synthetic_inputs,
         compare,
-    )
+    )?;
+    if let Some(grpc) = existence_probe {
+        attach_missing_input_existence_py(&mut output, &replay_state, &grpc);
+    }
+    if compare {
+        check_comparison_available(
+            &mut output,
+            &replay_state,
+            &effective_source,
+            None,
+            extras.require_comparison,
+            &warnings,
+        )?;
+    }
+    attach_effective_epoch(&mut output, &replay_state, extras.epoch_override);
+    attach_effective_protocol_version(&mut output, &replay_state, extras.protocol_version);
+    attach_gas_table_coverage(
+        &mut output,
+        harness.config().gas_table_coverage(),
+        &warnings,
+    );
+    attach_object_override_report(&mut output, &override_report);
+    attach_package_override_report(&mut output, &package_override_report);
+    attach_ownership_hint_report(&mut output, &ownership_hint_report);
+    attach_expected_effects(&mut output, expected_effects);
+    attach_checkpoint_prologue(&mut output, checkpoint_prologue);
+    attach_package_sources(&mut output, package_sources);
+    attach_fetch_latency(&mut output, &fetch_latency);
+    attach_return_value_bytes(&mut output, return_value_bytes);
+    attach_decoded_events(&mut output, &replay_state, event_payloads);
+    attach_object_diffs(&mut output, object_diffs);
+    attach_gas_profile(&mut output, gas_profile);
+    attach_call_trace(&mut output, call_trace);
+    attach_reconciliation(&mut output, reconciliation);
+    attach_effects_verification(&mut output, verification);
+    attach_synthesis_summary(&mut output, &synthesis);
+    attach_stubbed_natives(&mut output, &harness.stubbed_natives(), &warnings);
+    attach_instruction_trace(
+        &mut output,
+        harness.execution_trace(),
+        extras.instruction_trace.as_deref(),
+        &warnings,
+    );
+    warnings.attach(&mut output);
+    attach_replay_fidelity(&mut output, &warnings);
+    if let Some(entry) = result_cache {
+        entry.store(&mut output);
+    }
+    Ok(output)
 }
 
 pub(super) fn replay_loaded_state_inner(
@@ -679,18 +2794,103 @@ pub(super) fn replay_loaded_state_inner(
     analyze_mm2: bool,
     rpc_url: &str,
     verbose: bool,
+    extras: &ReplayExtras,
 ) -> Result<serde_json::Value> {
     use sui_sandbox_core::replay_support;
     use sui_sandbox_core::tx_replay::{self, EffectsReconcilePolicy};
 
+    if extras.offline && self_heal_dynamic_fields {
+        return Err(offline_fetch_error(
+            "dynamic fields on demand (self_heal_dynamic_fields=True)",
+        ));
+    }
+    if extras.offline && synthesize_missing {
+        return Err(offline_fetch_error(
+            "object types for input synthesis (synthesize_missing=True)",
+        ));
+    }
+
+    let warnings = WarningLog::new(verbose);
+    let graphql =
+        (!extras.offline).then(|| extras.graphql_client(&resolve_graphql_endpoint(rpc_url)));
+    ensure_programmable_transaction(
+        &replay_state,
+        extras.checkpoint_data.as_ref(),
+        graphql.as_ref(),
+        &warnings,
+    )?;
+
+    let mut package_sources = None;
     if let Some(context_packages) = context_packages {
-        let merged = merge_context_packages(&mut replay_state, context_packages);
-        if verbose && merged > 0 {
+        let merge = merge_context_packages(
+            &mut replay_state,
+            context_packages,
+            extras.context_overrides,
+        );
+        if verbose && merge.merged() > 0 {
             eprintln!(
-                "[context] merged {} package(s) from prepared context before replay",
-                merged
+                "[context] merged {} package(s) from prepared context before replay ({} replaced)",
+                merge.merged(),
+                merge.replaced.len()
             );
         }
+        package_sources = Some(merge.sources_json(&replay_state));
+    }
+    let package_override_report =
+        apply_package_overrides(&mut replay_state, &extras.package_overrides, verbose)?;
+    let ownership_hint_report =
+        apply_ownership_hints(&mut replay_state, &extras.ownership_hints, &warnings)?;
+    let checkpoint_prologue =
+        apply_checkpoint_prologue_context(&mut replay_state, None, extras, None, verbose)?;
+
+    if extras.offline && replay_state.transaction.timestamp_ms.is_none() {
+        if let Some(checkpoint) = replay_state.checkpoint {
+            return Err(offline_fetch_error(format!(
+                "the timestamp of checkpoint {} for the Clock; set transaction.timestamp_ms \
+                 in the state",
+                checkpoint
+            )));
+        }
+    }
+    if replay_support::backfill_replay_clock_timestamp(&mut replay_state, graphql.as_ref())
+        .is_none()
+    {
+        warnings.push(
+            WarningCategory::Clock,
+            "transaction timestamp unavailable; Clock uses the default base timestamp",
+        );
+    }
+    let epoch_lookup = replay_state.protocol_version == 0;
+    apply_protocol_version(
+        &mut replay_state,
+        extras.protocol_version,
+        graphql.as_ref().filter(|_| epoch_lookup),
+        &warnings,
+    );
+
+    // A cache hit returns before execution, so traced replays always run.
+    let result_cache = extras
+        .result_cache
+        .as_ref()
+        .filter(|_| !analyze_only && extras.instruction_trace.is_none())
+        .map(|cache| {
+            let settings = serde_json::json!({
+                "source": requested_source,
+                "effective_source": effective_source,
+                "vm_only": vm_only,
+                "allow_fallback": allow_fallback,
+                "auto_system_objects": auto_system_objects,
+                "self_heal_dynamic_fields": self_heal_dynamic_fields,
+                "compare": compare,
+                "synthesize_missing": synthesize_missing,
+            });
+            cache.entry(&replay_state, settings, extras)
+        });
+    if let Some(output) = result_cache
+        .as_ref()
+        .and_then(|entry| entry.load(&warnings))
+    {
+        return Ok(output);
     }
 
     if analyze_only {
@@ -704,39 +2904,58 @@ pub(super) fn replay_loaded_state_inner(
             false,
             0,
             0,
+            graphql.as_ref(),
             verbose,
         )?;
         if analyze_mm2 {
             let pkg_aliases = build_aliases(&replay_state.packages, None, replay_state.checkpoint);
-            let resolver = replay_support::hydrate_resolver_from_replay_state(
-                &replay_state,
-                &pkg_aliases.linkage_upgrades,
-                &pkg_aliases.aliases,
-            )?;
+            let resolver = hydrate_replay_resolver(&replay_state, &pkg_aliases, extras)?;
             let modules: Vec<CompiledModule> = resolver.iter_modules().cloned().collect();
             attach_mm2_summary_fields(&mut output, modules, verbose);
         }
+        attach_package_sources(&mut output, package_sources);
+        warnings.attach(&mut output);
         return Ok(output);
     }
 
     let pkg_aliases = build_aliases(&replay_state.packages, None, replay_state.checkpoint);
-    let resolver = replay_support::hydrate_resolver_from_replay_state(
-        &replay_state,
-        &pkg_aliases.linkage_upgrades,
-        &pkg_aliases.aliases,
-    )?;
+    let resolver = hydrate_replay_resolver(&replay_state, &pkg_aliases, extras)?;
 
     let mut maps = replay_support::build_replay_object_maps(&replay_state, &pkg_aliases.versions);
-    replay_support::maybe_patch_replay_objects(
+    check_object_digests(&maps, extras.strict_object_digests, &warnings)?;
+    if extras.offline {
+        ensure_offline_state_complete(&resolver, &replay_state, &maps)?;
+    }
+    let override_report = replay_support::maybe_patch_replay_objects_with_overrides(
         &resolver,
         &replay_state,
         &pkg_aliases.versions,
         &pkg_aliases.aliases,
         &mut maps,
+        &extras.object_overrides,
         verbose,
     );
 
-    let config = replay_support::build_simulation_config(&replay_state);
+    if let Some(epoch) = extras.epoch_override.filter(|_| extras.offline) {
+        if epoch != replay_state.epoch && replay_state.protocol_version > 0 {
+            return Err(offline_fetch_error(format!(
+                "the protocol version of epoch {} to verify epoch_override",
+                epoch
+            )));
+        }
+    }
+    let config = build_simulation_config_with_epoch(
+        &replay_state,
+        extras.epoch_override,
+        graphql.as_ref(),
+        &warnings,
+    );
+    let config = config
+        .with_stub_unknown_natives(extras.stub_unknown_natives)
+        .with_gas_table(extras.gas_table.clone())
+        .with_execution_trace(extras.execution_trace_options())
+        .with_gas_profile(extras.gas_profile)
+        .with_call_trace(extras.call_trace.clone());
     let mut harness = sui_sandbox_core::vm::VMHarness::with_config(&resolver, false, config)?;
     harness
         .set_address_aliases_with_versions(pkg_aliases.aliases.clone(), maps.versions_str.clone());
@@ -745,7 +2964,7 @@ pub(super) fn replay_loaded_state_inner(
         let modules: Vec<CompiledModule> = resolver.iter_modules().cloned().collect();
         if !modules.is_empty() {
             let graphql_endpoint = resolve_graphql_endpoint(rpc_url);
-            let graphql = GraphQLClient::new(&graphql_endpoint);
+            let graphql = extras.graphql_client(&graphql_endpoint);
             enable_self_heal_fetchers(
                 &mut harness,
                 &graphql,
@@ -753,12 +2972,14 @@ pub(super) fn replay_loaded_state_inner(
                 max_version,
                 &pkg_aliases.aliases,
                 &modules,
+                &warnings,
             );
         }
     }
 
-    let mut replay_result = tx_replay::replay_with_version_tracking_with_policy_with_effects(
+    let mut replay_result = tx_replay::replay_with_version_tracking_against_effects(
         &replay_state.transaction,
+        comparison_effects(&replay_state, extras),
         &mut harness,
         &maps.cached_objects,
         &pkg_aliases.aliases,
@@ -766,6 +2987,7 @@ pub(super) fn replay_loaded_state_inner(
         EffectsReconcilePolicy::Strict,
     );
     let mut synthetic_inputs = 0usize;
+    let mut synthesis = CoreSynthesisSummary::default();
     if synthesize_missing
         && replay_result
             .as_ref()
@@ -776,7 +2998,7 @@ pub(super) fn replay_loaded_state_inner(
             tx_replay::find_missing_input_objects(&replay_state.transaction, &maps.cached_objects);
         if !missing.is_empty() {
             let graphql_endpoint = resolve_graphql_endpoint(rpc_url);
-            let graphql = GraphQLClient::new(&graphql_endpoint);
+            let graphql = extras.graphql_client(&graphql_endpoint);
             match synthesize_missing_inputs_py(
                 &missing,
                 &mut maps.cached_objects,
@@ -784,10 +3006,11 @@ pub(super) fn replay_loaded_state_inner(
                 &resolver,
                 &pkg_aliases.aliases,
                 &graphql,
-                verbose,
+                &warnings,
             ) {
-                Ok(logs) => {
-                    synthetic_inputs = logs.len();
+                Ok(summary) => {
+                    synthetic_inputs = summary.synthesized();
+                    synthesis = summary;
                     if verbose && synthetic_inputs > 0 {
                         eprintln!(
                             "[replay_fallback] synthesized {} missing input object(s)",
@@ -795,27 +3018,51 @@ pub(super) fn replay_loaded_state_inner(
                         );
                     }
                     if synthetic_inputs > 0 {
-                        replay_result =
-                            tx_replay::replay_with_version_tracking_with_policy_with_effects(
-                                &replay_state.transaction,
-                                &mut harness,
-                                &maps.cached_objects,
-                                &pkg_aliases.aliases,
-                                Some(&maps.versions_str),
-                                EffectsReconcilePolicy::Strict,
-                            );
+                        replay_result = tx_replay::replay_with_version_tracking_against_effects(
+                            &replay_state.transaction,
+                            comparison_effects(&replay_state, extras),
+                            &mut harness,
+                            &maps.cached_objects,
+                            &pkg_aliases.aliases,
+                            Some(&maps.versions_str),
+                            EffectsReconcilePolicy::Strict,
+                        );
                     }
                 }
                 Err(err) => {
-                    if verbose {
-                        eprintln!("[replay_fallback] synthesis failed: {}", err);
-                    }
+                    warnings.push(
+                        WarningCategory::Synthesis,
+                        format!("synthesis failed: {:#}", err),
+                    );
                 }
             }
         }
     }
 
-    build_replay_output(
+    let expected_effects = apply_expected_effects(&mut replay_state, extras, verbose);
+    let return_value_bytes = extras
+        .include_return_bytes
+        .then(|| encode_return_value_bytes(&replay_result))
+        .flatten();
+    let event_payloads = extras
+        .decode_events
+        .then(|| collect_event_payloads(&replay_result))
+        .flatten();
+    let object_diffs = extras
+        .diff_objects
+        .then(|| collect_object_diffs(&replay_state, &replay_result, &warnings))
+        .flatten();
+    let gas_profile = collect_gas_profile(&replay_result);
+    let call_trace = collect_call_trace(&replay_result);
+    let reconciliation = extras
+        .reconcile
+        .then(|| collect_reconciliation(&replay_state, &replay_result, &warnings))
+        .flatten();
+    let verification = extras
+        .expect_effects_hash
+        .as_deref()
+        .map(|expected| build_effects_verification(expected, &replay_state, &replay_result));
+    let mut output = build_replay_output(
         &replay_state,
         replay_result,
         requested_source,
@@ -830,9 +3077,783 @@ pub(super) fn replay_loaded_state_inner(
         0,
         synthetic_inputs,
         compare,
-    )
+    )?;
+    if compare {
+        check_comparison_available(
+            &mut output,
+            &replay_state,
+            requested_source,
+            Some(effective_source),
+            extras.require_comparison,
+            &warnings,
+        )?;
+    }
+    attach_effective_epoch(&mut output, &replay_state, extras.epoch_override);
+    attach_effective_protocol_version(&mut output, &replay_state, extras.protocol_version);
+    attach_gas_table_coverage(
+        &mut output,
+        harness.config().gas_table_coverage(),
+        &warnings,
+    );
+    attach_object_override_report(&mut output, &override_report);
+    attach_package_override_report(&mut output, &package_override_report);
+    attach_ownership_hint_report(&mut output, &ownership_hint_report);
+    attach_expected_effects(&mut output, expected_effects);
+    attach_checkpoint_prologue(&mut output, checkpoint_prologue);
+    attach_package_sources(&mut output, package_sources);
+    attach_return_value_bytes(&mut output, return_value_bytes);
+    attach_decoded_events(&mut output, &replay_state, event_payloads);
+    attach_object_diffs(&mut output, object_diffs);
+    attach_gas_profile(&mut output, gas_profile);
+    attach_call_trace(&mut output, call_trace);
+    attach_reconciliation(&mut output, reconciliation);
+    attach_effects_verification(&mut output, verification);
+    attach_synthesis_summary(&mut output, &synthesis);
+    attach_stubbed_natives(&mut output, &harness.stubbed_natives(), &warnings);
+    attach_instruction_trace(
+        &mut output,
+        harness.execution_trace(),
+        extras.instruction_trace.as_deref(),
+        &warnings,
+    );
+    warnings.attach(&mut output);
+    attach_replay_fidelity(&mut output, &warnings);
+    if let Some(entry) = result_cache {
+        entry.store(&mut output);
+    }
+    Ok(output)
+}
+
+// ---------------------------------------------------------------------------
+// replay_ab (A/B harness)
+// ---------------------------------------------------------------------------
+
+/// One side of an A/B replay. Unset fields fall back to the `replay()` defaults.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct ReplayAbConfig {
+    pub(super) label: Option<String>,
+    /// Prepared package context (e.g. upgraded bytecode) merged before execution.
+    pub(super) context_path: Option<PathBuf>,
+    pub(super) vm_only: Option<bool>,
+    pub(super) allow_fallback: Option<bool>,
+    pub(super) auto_system_objects: Option<bool>,
+    pub(super) synthesize_missing: Option<bool>,
+    pub(super) self_heal_dynamic_fields: Option<bool>,
+}
+
+impl ReplayAbConfig {
+    pub(super) fn from_json(value: Option<serde_json::Value>, side: &str) -> Result<Self> {
+        match value {
+            None | Some(serde_json::Value::Null) => Ok(Self::default()),
+            Some(value) => serde_json::from_value(value)
+                .with_context(|| format!("invalid replay_ab config_{}", side)),
+        }
+    }
+}
+
+/// Where `replay_ab` loads the transaction state from.
+pub(super) enum ReplayAbSource<'a> {
+    StateFile(&'a Path),
+    LocalCache(&'a Path),
+    Network {
+        source: &'a str,
+        checkpoint: Option<u64>,
+    },
+}
+
+/// Replay `digest` under two configurations and diff effects, return values and gas.
+///
+/// File/cache sources load the state once and execute it twice; network sources
+/// fetch it per run (historical data is pinned, so both runs see the same inputs).
+pub(super) fn replay_ab_inner(
+    digest: Option<&str>,
+    state_source: ReplayAbSource<'_>,
+    config_a: &ReplayAbConfig,
+    config_b: &ReplayAbConfig,
+    rpc_url: &str,
+    verbose: bool,
+) -> Result<serde_json::Value> {
+    let shared_state = match &state_source {
+        ReplayAbSource::StateFile(path) => Some((
+            load_replay_state_from_file(path, digest)?,
+            "state_file",
+            "state_json",
+        )),
+        ReplayAbSource::LocalCache(cache_dir) => {
+            let digest = digest.ok_or_else(|| {
+                anyhow!("digest is required when replaying from cache_dir/source='local'")
+            })?;
+            let provider = FileStateProvider::new(cache_dir).with_context(|| {
+                format!("Failed to open local replay cache {}", cache_dir.display())
+            })?;
+            Some((provider.get_state(digest)?, "local", "local_cache"))
+        }
+        ReplayAbSource::Network { .. } => None,
+    };
+
+    let extras = ReplayExtras {
+        include_return_bytes: true,
+        ..ReplayExtras::default()
+    };
+    let run = |config: &ReplayAbConfig| -> Result<serde_json::Value> {
+        let context_packages = config
+            .context_path
+            .as_deref()
+            .map(load_context_packages_from_file)
+            .transpose()?;
+        let vm_only = config.vm_only.unwrap_or(false);
+        let allow_fallback = !vm_only && config.allow_fallback.unwrap_or(true);
+        let auto_system_objects = config.auto_system_objects.unwrap_or(true);
+        let synthesize_missing = config.synthesize_missing.unwrap_or(false);
+        let self_heal_dynamic_fields = config.self_heal_dynamic_fields.unwrap_or(false);
+
+        if let Some((state, requested_source, effective_source)) = &shared_state {
+            return replay_loaded_state_inner(
+                state.clone(),
+                requested_source,
+                effective_source,
+                context_packages.as_ref(),
+                allow_fallback,
+                auto_system_objects,
+                self_heal_dynamic_fields,
+                vm_only,
+                false,
+                false,
+                synthesize_missing,
+                false,
+                rpc_url,
+                verbose,
+                &extras,
+            );
+        }
+        let ReplayAbSource::Network { source, checkpoint } = &state_source else {
+            unreachable!("file and cache sources load a shared state");
+        };
+        let digest = digest.ok_or_else(|| anyhow!("digest is required"))?;
+        replay_inner(
+            digest,
+            rpc_url,
+            source,
+            *checkpoint,
+            context_packages.as_ref(),
+            allow_fallback,
+            3,
+            200,
+            auto_system_objects,
+            false,
+            synthesize_missing,
+            self_heal_dynamic_fields,
+            vm_only,
+            false,
+            false,
+            false,
+            verbose,
+            &extras,
+        )
+    };
+
+    let output_a = run(config_a).context("replay_ab run A failed")?;
+    let output_b = run(config_b).context("replay_ab run B failed")?;
+    let diff = core_diff_replay_outputs(&output_a, &output_b);
+
+    Ok(serde_json::json!({
+        "digest": output_a.get("digest").cloned().unwrap_or(serde_json::Value::Null),
+        "a": {
+            "label": config_a.label.as_deref().unwrap_or("a"),
+            "output": output_a,
+        },
+        "b": {
+            "label": config_b.label.as_deref().unwrap_or("b"),
+            "output": output_b,
+        },
+        "diff": serde_json::to_value(diff)?,
+    }))
+}
+
+// ---------------------------------------------------------------------------
+// bench_replay (throughput harness)
+// ---------------------------------------------------------------------------
+
+/// Shape of a `bench_replay` run; the state source and extras are passed separately.
+#[derive(Debug, Clone)]
+pub(super) struct ReplayBenchOptions {
+    pub(super) profile: WorkflowReplayProfile,
+    /// Worker threads replaying in parallel.
+    pub(super) concurrency: usize,
+    /// Measured passes over the digest set.
+    pub(super) repeats: usize,
+    /// Unmeasured passes run first to warm connections and caches.
+    pub(super) warmup: usize,
+}
+
+/// Outcome of one measured replay.
+struct ReplayBenchSample {
+    digest_index: usize,
+    elapsed: Duration,
+    output: Result<serde_json::Value>,
+}
+
+/// Replay `digests` `warmup + repeats` times with `concurrency` workers and report
+/// throughput, latency distribution, result-cache hits and fetch counts of the measured
+/// passes. Failed replays are counted, not fatal.
+pub(super) fn bench_replay_inner(
+    digests: &[String],
+    state_source: ReplayAbSource<'_>,
+    options: &ReplayBenchOptions,
+    rpc_url: &str,
+    extras: &ReplayExtras,
+) -> Result<serde_json::Value> {
+    if digests.is_empty() {
+        return Err(anyhow!("bench_replay requires at least one digest"));
+    }
+    if options.repeats == 0 {
+        return Err(anyhow!("bench_replay repeats must be at least 1"));
+    }
+    let concurrency = options.concurrency.max(1);
+
+    // State files are parsed once so the measurement covers replay, not JSON parsing.
+    let file_states = match &state_source {
+        ReplayAbSource::StateFile(path) => digests
+            .iter()
+            .map(|digest| load_replay_state_from_file(path, Some(digest)))
+            .collect::<Result<Vec<_>>>()?,
+        _ => Vec::new(),
+    };
+    let provider = match &state_source {
+        ReplayAbSource::LocalCache(cache_dir) => {
+            Some(FileStateProvider::new(cache_dir).with_context(|| {
+                format!("Failed to open local replay cache {}", cache_dir.display())
+            })?)
+        }
+        _ => None,
+    };
+
+    let run_one = |digest_index: usize| -> Result<serde_json::Value> {
+        let digest = &digests[digest_index];
+        match &state_source {
+            ReplayAbSource::StateFile(_) => replay_loaded_state_inner(
+                file_states[digest_index].clone(),
+                "state_file",
+                "state_json",
+                None,
+                true,
+                true,
+                false,
+                false,
+                false,
+                false,
+                false,
+                false,
+                rpc_url,
+                false,
+                extras,
+            ),
+            ReplayAbSource::LocalCache(_) => {
+                let provider = provider.as_ref().expect("local cache provider is opened");
+                replay_loaded_state_inner(
+                    provider.get_state(digest)?,
+                    "local",
+                    "local_cache",
+                    None,
+                    true,
+                    true,
+                    false,
+                    false,
+                    false,
+                    false,
+                    false,
+                    false,
+                    rpc_url,
+                    false,
+                    extras,
+                )
+            }
+            ReplayAbSource::Network { source, checkpoint } => replay_inner(
+                digest,
+                rpc_url,
+                source,
+                *checkpoint,
+                None,
+                true,
+                3,
+                200,
+                true,
+                false,
+                false,
+                false,
+                false,
+                false,
+                false,
+                false,
+                false,
+                extras,
+            ),
+        }
+    };
+
+    // Runs every job on a pool of `concurrency` workers pulling from a shared counter.
+    let run_pass = |jobs: &[usize]| -> Vec<ReplayBenchSample> {
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let samples = std::sync::Mutex::new(Vec::with_capacity(jobs.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..concurrency.min(jobs.len()) {
+                scope.spawn(|| loop {
+                    let slot = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    let Some(&digest_index) = jobs.get(slot) else {
+                        break;
+                    };
+                    let start = Instant::now();
+                    let output = run_one(digest_index);
+                    let sample = ReplayBenchSample {
+                        digest_index,
+                        elapsed: start.elapsed(),
+                        output,
+                    };
+                    samples
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .push(sample);
+                });
+            }
+        });
+        samples.into_inner().unwrap_or_else(|e| e.into_inner())
+    };
+
+    let pass: Vec<usize> = (0..digests.len()).collect();
+    for _ in 0..options.warmup {
+        run_pass(&pass);
+    }
+    let measured: Vec<usize> = pass.repeat(options.repeats);
+    let start = Instant::now();
+    let samples = run_pass(&measured);
+    let wall = start.elapsed();
+
+    Ok(summarize_replay_bench(
+        digests,
+        options,
+        concurrency,
+        &samples,
+        wall,
+        extras.result_cache.is_some(),
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// replay_many (batch replay)
+// ---------------------------------------------------------------------------
+
+/// Hydration and reporting knobs for a `replay_many` batch.
+#[derive(Debug, Clone)]
+pub(super) struct ReplayManyOptions {
+    /// Worker threads replaying in parallel.
+    pub(super) max_concurrency: usize,
+    pub(super) allow_fallback: bool,
+    pub(super) prefetch_depth: usize,
+    pub(super) prefetch_limit: usize,
+    pub(super) auto_system_objects: bool,
+    pub(super) compare: bool,
+    /// Keep each replay's full output in its run entry.
+    pub(super) include_outputs: bool,
+}
+
+/// Replay `digests` with bounded parallelism through one shared object/package cache
+/// and one shared resolver (framework per protocol version plus fetched dependencies),
+/// and report per-digest outcomes plus aggregate stats. Failed replays are recorded,
+/// not fatal.
+pub(super) fn replay_many_inner(
+    digests: &[String],
+    state_source: ReplayAbSource<'_>,
+    options: &ReplayManyOptions,
+    rpc_url: &str,
+    extras: &ReplayExtras,
+) -> Result<serde_json::Value> {
+    if digests.is_empty() {
+        return Err(anyhow!("replay_many requires at least one digest"));
+    }
+    let provider = match &state_source {
+        ReplayAbSource::LocalCache(cache_dir) => {
+            Some(FileStateProvider::new(cache_dir).with_context(|| {
+                format!("Failed to open local replay cache {}", cache_dir.display())
+            })?)
+        }
+        ReplayAbSource::StateFile(_) => {
+            return Err(anyhow!(
+                "replay_many replays from the network or a local cache, not a state file"
+            ))
+        }
+        ReplayAbSource::Network { .. } => None,
+    };
+    let shared_cache = Arc::new(sui_state_fetcher::VersionedCache::new());
+    let shared_resolver = Arc::new(replay_support::SharedReplayResolver::new(Arc::clone(
+        &shared_cache,
+    )));
+    let extras = ReplayExtras {
+        shared_state_cache: Some(Arc::clone(&shared_cache)),
+        shared_resolver: Some(Arc::clone(&shared_resolver)),
+        ..extras.clone()
+    };
+
+    let batch_options = CoreReplayBatchOptions {
+        max_concurrency: Some(options.max_concurrency.max(1)),
+    };
+    let report =
+        ReplayOrchestrator::replay_many(digests, &batch_options, |digest| match &state_source {
+            ReplayAbSource::LocalCache(_) => {
+                let provider = provider.as_ref().expect("local cache provider is opened");
+                replay_loaded_state_inner(
+                    provider.get_state(digest)?,
+                    "local",
+                    "local_cache",
+                    None,
+                    options.allow_fallback,
+                    options.auto_system_objects,
+                    false,
+                    false,
+                    options.compare,
+                    false,
+                    false,
+                    false,
+                    rpc_url,
+                    false,
+                    &extras,
+                )
+            }
+            ReplayAbSource::Network { source, checkpoint } => replay_inner(
+                digest,
+                rpc_url,
+                source,
+                *checkpoint,
+                None,
+                options.allow_fallback,
+                options.prefetch_depth,
+                options.prefetch_limit,
+                options.auto_system_objects,
+                false,
+                false,
+                false,
+                false,
+                options.compare,
+                false,
+                false,
+                false,
+                &extras,
+            ),
+            ReplayAbSource::StateFile(_) => unreachable!("rejected above"),
+        })?;
+
+    let mut value = serde_json::to_value(&report).context("serialize replay_many report")?;
+    if !options.include_outputs {
+        if let Some(runs) = value
+            .get_mut("runs")
+            .and_then(serde_json::Value::as_array_mut)
+        {
+            for run in runs {
+                if let Some(run) = run.as_object_mut() {
+                    run.remove("output");
+                }
+            }
+        }
+    }
+    value["shared_cache"] = serde_json::json!({
+        "packages": shared_cache.package_count(),
+        "objects": shared_cache.object_count(),
+        "framework_versions": shared_resolver.stats().framework_versions,
+    });
+    Ok(value)
+}
+
+// ---------------------------------------------------------------------------
+// replay_checkpoint (whole-checkpoint replay)
+// ---------------------------------------------------------------------------
+
+/// Replay every transaction of a Walrus checkpoint in order against one evolving
+/// object store and report per-transaction outcomes plus aggregate counts.
+pub(super) fn replay_checkpoint_inner(
+    checkpoint: u64,
+    walrus: WalrusClient,
+    graphql: &GraphQLClient,
+    compare: bool,
+    protocol_version: Option<u64>,
+    verbose: bool,
+) -> Result<serde_json::Value> {
+    let fetch_latency = Arc::new(LatencyRecorder::new());
+    let checkpoint_data = walrus
+        .with_latency_recorder(Arc::clone(&fetch_latency))
+        .get_checkpoint(checkpoint)
+        .with_context(|| format!("Failed to fetch checkpoint {} from Walrus", checkpoint))?;
+    let warnings = WarningLog::new(verbose);
+    let protocol_version = protocol_version.or_else(|| {
+        let epoch = checkpoint_data.checkpoint_summary.epoch;
+        let reason = match graphql.fetch_epoch_protocol_version(epoch) {
+            Ok(Some(version)) => return Some(version),
+            Ok(None) => String::new(),
+            Err(e) => format!(" ({:#})", e),
+        };
+        warnings.push(
+            WarningCategory::Protocol,
+            format!(
+                "protocol version of epoch {} unavailable{}; replay uses the checkpoint \
+                 loader's protocol version",
+                epoch, reason
+            ),
+        );
+        None
+    });
+    let report = core_replay_checkpoint(
+        &checkpoint_data,
+        &CoreCheckpointReplayOptions {
+            compare,
+            protocol_version,
+            verbose,
+        },
+    );
+    let mut value = serde_json::to_value(&report).context("serialize replay_checkpoint report")?;
+    value["protocol_version"] = serde_json::json!(protocol_version);
+    attach_fetch_latency(&mut value, &fetch_latency);
+    warnings.attach(&mut value);
+    Ok(value)
+}
+
+fn summarize_replay_bench(
+    digests: &[String],
+    options: &ReplayBenchOptions,
+    concurrency: usize,
+    samples: &[ReplayBenchSample],
+    wall: Duration,
+    result_cache_enabled: bool,
+) -> serde_json::Value {
+    let mut succeeded = 0usize;
+    let mut cache_hits = 0usize;
+    let mut fetches: std::collections::BTreeMap<String, u64> = std::collections::BTreeMap::new();
+    let mut errors: std::collections::BTreeMap<(usize, String), usize> =
+        std::collections::BTreeMap::new();
+    for sample in samples {
+        let output = match &sample.output {
+            Ok(output) => output,
+            Err(e) => {
+                *errors
+                    .entry((sample.digest_index, format!("{:#}", e)))
+                    .or_default() += 1;
+                continue;
+            }
+        };
+        if output.get("local_success") == Some(&serde_json::json!(true)) {
+            succeeded += 1;
+        }
+        if output
+            .get("result_cache")
+            .and_then(|cache| cache.get("hit"))
+            .and_then(serde_json::Value::as_bool)
+            == Some(true)
+        {
+            cache_hits += 1;
+        }
+        if let Some(kinds) = output
+            .get("fetch_latency")
+            .and_then(serde_json::Value::as_object)
+        {
+            for (kind, summary) in kinds {
+                let count = summary.get("count").and_then(serde_json::Value::as_u64);
+                *fetches.entry(kind.clone()).or_default() += count.unwrap_or(0);
+            }
+        }
+    }
+
+    let durations: Vec<Duration> = samples.iter().map(|sample| sample.elapsed).collect();
+    let per_digest: Vec<serde_json::Value> = digests
+        .iter()
+        .enumerate()
+        .map(|(index, digest)| {
+            let mine: Vec<&ReplayBenchSample> = samples
+                .iter()
+                .filter(|sample| sample.digest_index == index)
+                .collect();
+            let durations: Vec<Duration> = mine.iter().map(|sample| sample.elapsed).collect();
+            let succeeded = mine
+                .iter()
+                .filter(|sample| {
+                    sample.output.as_ref().is_ok_and(|output| {
+                        output.get("local_success") == Some(&serde_json::json!(true))
+                    })
+                })
+                .count();
+            serde_json::json!({
+                "digest": digest,
+                "runs": mine.len(),
+                "succeeded": succeeded,
+                "latency": LatencySummary::from_samples(&durations),
+            })
+        })
+        .collect();
+
+    let wall_secs = wall.as_secs_f64();
+    let total_fetches: u64 = fetches.values().sum();
+    serde_json::json!({
+        "sandbox_version": env!("CARGO_PKG_VERSION"),
+        "profile": options.profile.as_cli_value(),
+        "concurrency": concurrency,
+        "repeats": options.repeats,
+        "warmup": options.warmup,
+        "digests": digests.len(),
+        "replays": samples.len(),
+        "succeeded": succeeded,
+        "failed": samples.len() - succeeded,
+        "wall_ms": wall.as_nanos() as f64 / 1_000_000.0,
+        "throughput_per_sec": if wall_secs > 0.0 {
+            samples.len() as f64 / wall_secs
+        } else {
+            0.0
+        },
+        "latency": LatencySummary::from_samples(&durations),
+        "per_digest": per_digest,
+        "result_cache": result_cache_enabled.then(|| serde_json::json!({
+            "hits": cache_hits,
+            "misses": samples.len() - cache_hits,
+            "hit_rate": cache_hits as f64 / samples.len().max(1) as f64,
+        })),
+        "fetches": {
+            "total": total_fetches,
+            "per_replay": total_fetches as f64 / samples.len().max(1) as f64,
+            "by_kind": fetches,
+        },
+        "errors": errors
+            .into_iter()
+            .map(|((index, error), count)| serde_json::json!({
+                "digest": digests[index],
+                "error": error,
+                "count": count,
+            }))
+            .collect::<Vec<_>>(),
+    })
 }
 
 // ---------------------------------------------------------------------------
 // get_latest_checkpoint (native — Walrus)
 // ---------------------------------------------------------------------------
+
+/// Replay parameters and options stored in a request cassette's metadata, so
+/// `replay_from_cassette` re-issues exactly the recorded requests.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(super) struct CassetteReplayParams {
+    pub(super) digest: String,
+    pub(super) rpc_url: String,
+    pub(super) source: String,
+    pub(super) checkpoint: Option<u64>,
+    pub(super) allow_fallback: bool,
+    pub(super) prefetch_depth: usize,
+    pub(super) prefetch_limit: usize,
+    pub(super) auto_system_objects: bool,
+    pub(super) no_prefetch: bool,
+    pub(super) synthesize_missing: bool,
+    pub(super) self_heal_dynamic_fields: bool,
+    pub(super) vm_only: bool,
+    pub(super) compare: bool,
+    pub(super) analyze_only: bool,
+    pub(super) analyze_mm2: bool,
+    /// Absent from cassettes recorded before options were stored.
+    #[serde(default)]
+    pub(super) extras: ReplayExtras,
+}
+
+impl CassetteReplayParams {
+    /// Replay with every client going through `cassette`.
+    fn run(
+        &self,
+        cassette: &Arc<sui_transport::cassette::Cassette>,
+        verbose: bool,
+    ) -> Result<serde_json::Value> {
+        let extras = ReplayExtras {
+            cassette: Some(Arc::clone(cassette)),
+            ..self.extras.clone()
+        };
+        replay_inner(
+            &self.digest,
+            &self.rpc_url,
+            &self.source,
+            self.checkpoint,
+            None,
+            self.allow_fallback,
+            self.prefetch_depth,
+            self.prefetch_limit,
+            self.auto_system_objects,
+            self.no_prefetch,
+            self.synthesize_missing,
+            self.self_heal_dynamic_fields,
+            self.vm_only,
+            self.compare,
+            self.analyze_only,
+            self.analyze_mm2,
+            verbose,
+            &extras,
+        )
+    }
+}
+
+fn attach_cassette_report(
+    output: &mut serde_json::Value,
+    cassette: &sui_transport::cassette::Cassette,
+    mode: &str,
+) {
+    if let Some(obj) = output.as_object_mut() {
+        obj.insert(
+            "cassette".to_string(),
+            serde_json::json!({
+                "path": cassette.path().display().to_string(),
+                "mode": mode,
+                "interactions": cassette.len(),
+            }),
+        );
+    }
+}
+
+/// Run a network replay while recording every GraphQL, gRPC and Walrus request to a
+/// cassette at `path`. The cassette is written even when the replay fails, so the
+/// failure can be reproduced offline with [`replay_from_cassette_inner`].
+pub(super) fn replay_recording_requests(
+    params: &CassetteReplayParams,
+    path: &Path,
+    verbose: bool,
+) -> Result<serde_json::Value> {
+    use sui_transport::cassette::Cassette;
+
+    let metadata = serde_json::to_value(params).context("serialize cassette metadata")?;
+    let recorder = Arc::new(Cassette::record(path, metadata));
+    let result = params.run(&recorder, verbose);
+    let saved = recorder.save();
+    let mut output = result.with_context(|| {
+        format!(
+            "replay failed; requests recorded to {} for replay_from_cassette",
+            path.display()
+        )
+    })?;
+    saved?;
+    if verbose {
+        eprintln!(
+            "[cassette] recorded {} request(s) to {}",
+            recorder.len(),
+            path.display()
+        );
+    }
+    attach_cassette_report(&mut output, &recorder, "record");
+    Ok(output)
+}
+
+/// Re-run a replay recorded with `record_requests`, serving every fetch from the
+/// cassette with the recorded replay options. Requests missing from the cassette fail
+/// instead of reaching the network.
+pub(super) fn replay_from_cassette_inner(path: &Path, verbose: bool) -> Result<serde_json::Value> {
+    use sui_transport::cassette::Cassette;
+
+    let cassette = Arc::new(Cassette::load(path)?);
+    let params: CassetteReplayParams = serde_json::from_value(cassette.metadata().clone())
+        .with_context(|| {
+            format!(
+                "cassette {} has no replay parameters; record it with replay(record_requests=...)",
+                path.display()
+            )
+        })?;
+    let mut output = params.run(&cassette, verbose)?;
+    attach_cassette_report(&mut output, &cassette, "playback");
+    Ok(output)
+}
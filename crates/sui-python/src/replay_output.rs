@@ -4,7 +4,15 @@ pub(crate) fn load_replay_state_from_file(
     path: &Path,
     digest: Option<&str>,
 ) -> Result<ReplayState> {
-    let states = parse_replay_states_file(path)?;
+    load_replay_state_from_file_as(path, digest, ReplayStateFormat::Auto)
+}
+
+pub(crate) fn load_replay_state_from_file_as(
+    path: &Path,
+    digest: Option<&str>,
+    format: ReplayStateFormat,
+) -> Result<ReplayState> {
+    let states = read_replay_states_file(path, format)?;
     if states.is_empty() {
         return Err(anyhow!(
             "Replay state file '{}' did not contain any states",
@@ -38,6 +46,7 @@ pub(crate) fn import_state_inner(
     objects: Option<&str>,
     packages: Option<&str>,
     cache_dir: Option<&str>,
+    state_format: ReplayStateFormat,
 ) -> Result<serde_json::Value> {
     let cache_dir = cache_dir
         .map(PathBuf::from)
@@ -48,6 +57,7 @@ pub(crate) fn import_state_inner(
         transactions: transactions.map(PathBuf::from),
         objects: objects.map(PathBuf::from),
         packages: packages.map(PathBuf::from),
+        state_format,
     };
     let summary = import_replay_states(&cache_dir, &spec)?;
     Ok(serde_json::json!({
@@ -59,6 +69,65 @@ pub(crate) fn import_state_inner(
     }))
 }
 
+pub(crate) fn export_state_inner(
+    state: &Path,
+    output: &Path,
+    digest: Option<&str>,
+) -> Result<serde_json::Value> {
+    let states = match digest {
+        Some(digest) => vec![load_replay_state_from_file(state, Some(digest))?],
+        None => read_replay_states_file(state, ReplayStateFormat::Auto)?,
+    };
+    export_replay_states(&states, output)?;
+    let bytes = std::fs::metadata(output)
+        .with_context(|| format!("Failed to stat {}", output.display()))?
+        .len();
+    Ok(serde_json::json!({
+        "output": output,
+        "states": states.len(),
+        "digests": states
+            .iter()
+            .map(|state| state.transaction.digest.0.clone())
+            .collect::<Vec<_>>(),
+        "bytes": bytes,
+    }))
+}
+
+pub(crate) fn cache_stats_inner(cache_dir: Option<&str>) -> Result<serde_json::Value> {
+    let cache_dir = cache_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(default_local_cache_dir);
+    let stats = replay_cache_stats(&cache_dir)?;
+    serde_json::to_value(stats).context("Failed to serialize cache stats")
+}
+
+pub(crate) fn cache_clear_inner(
+    cache_dir: Option<&str>,
+    older_than_days: Option<f64>,
+    digest: Option<&str>,
+) -> Result<serde_json::Value> {
+    let cache_dir = cache_dir
+        .map(PathBuf::from)
+        .unwrap_or_else(default_local_cache_dir);
+    let older_than = older_than_days
+        .map(|days| {
+            if !days.is_finite() || days < 0.0 {
+                return Err(anyhow!(
+                    "older_than_days must be a non-negative number, got {}",
+                    days
+                ));
+            }
+            Ok(std::time::Duration::from_secs_f64(days * 86_400.0))
+        })
+        .transpose()?;
+    let spec = CacheClearSpec {
+        older_than,
+        digest: digest.map(ToOwned::to_owned),
+    };
+    let summary = clear_replay_cache(&cache_dir, &spec)?;
+    serde_json::to_value(summary).context("Failed to serialize cache clear summary")
+}
+
 pub(crate) fn deserialize_transaction_inner(raw_bcs: &[u8]) -> Result<serde_json::Value> {
     let decoded = bcs_codec::deserialize_transaction(raw_bcs, "decoded_tx", None, None, None)?;
     serde_json::to_value(decoded).context("Failed to serialize decoded transaction")
@@ -69,6 +138,81 @@ pub(crate) fn deserialize_package_inner(raw_bcs: &[u8]) -> Result<serde_json::Va
     serde_json::to_value(decoded).context("Failed to serialize decoded package")
 }
 
+/// Gas cost summary in the on-chain shape plus the net `total` (may be negative).
+fn gas_summary_json(gas: &sui_sandbox_types::GasSummary) -> serde_json::Value {
+    serde_json::json!({
+        "computation_cost": gas.computation_cost,
+        "storage_cost": gas.storage_cost,
+        "storage_rebate": gas.storage_rebate,
+        "non_refundable_storage_fee": gas.non_refundable_storage_fee,
+        "total": gas.total(),
+    })
+}
+
+/// Per-event provenance: emitting Move call (package + module), type tag and sender.
+fn event_provenance_json(events: &[sui_sandbox_core::natives::EmittedEvent]) -> serde_json::Value {
+    events
+        .iter()
+        .map(|event| {
+            serde_json::json!({
+                "sequence": event.sequence,
+                "type": event.type_tag,
+                "package_id": event.package_id.map(|id| id.to_hex_literal()),
+                "transaction_module": event.transaction_module,
+                "sender": event.sender.to_hex_literal(),
+                "command_index": event.command_index,
+            })
+        })
+        .collect()
+}
+
+/// Annotate MoveCall summaries with their declared return types.
+///
+/// Resolves against the replay state's packages, fetching missing call-target packages
+/// via `graphql` (none offline); anything still unresolved is reported as `"unknown"`.
+fn attach_move_call_return_types_py(
+    summary: &mut serde_json::Value,
+    replay_state: &ReplayState,
+    graphql: Option<&GraphQLClient>,
+) {
+    let commands = &replay_state.transaction.commands;
+    let pkg_aliases = build_aliases(&replay_state.packages, None, replay_state.checkpoint);
+    let Ok(mut resolver) = sui_sandbox_core::replay_support::hydrate_resolver_from_replay_state(
+        replay_state,
+        &pkg_aliases.linkage_upgrades,
+        &pkg_aliases.aliases,
+    ) else {
+        core_attach_move_call_return_types(summary, commands, |_, _, _| None);
+        return;
+    };
+
+    let mut missing_targets = Vec::new();
+    for cmd in commands {
+        if let sui_sandbox_types::PtbCommand::MoveCall { package, .. } = cmd {
+            if let Ok(addr) = AccountAddress::from_hex_literal(package) {
+                if !is_framework_address(&addr)
+                    && !replay_state.packages.contains_key(&addr)
+                    && !missing_targets.contains(&addr)
+                {
+                    missing_targets.push(addr);
+                }
+            }
+        }
+    }
+    if let Some(graphql) = graphql {
+        for addr in missing_targets {
+            if let Ok(modules) = fetch_package_modules(graphql, &addr.to_hex_literal()) {
+                let _ = resolver.add_package_modules_at(modules, Some(addr));
+            }
+        }
+    }
+
+    core_attach_move_call_return_types(summary, commands, |package, module, function| {
+        let addr = AccountAddress::from_hex_literal(package).ok()?;
+        resolver.get_function_return_types(&addr, module, function)
+    });
+}
+
 /// Build JSON output for analyze-only mode (no VM execution).
 pub(crate) fn build_analyze_output(
     replay_state: &sui_state_fetcher::ReplayState,
@@ -78,6 +222,7 @@ pub(crate) fn build_analyze_output(
     dynamic_field_prefetch: bool,
     prefetch_depth: usize,
     prefetch_limit: usize,
+    graphql: Option<&GraphQLClient>,
     verbose: bool,
 ) -> Result<serde_json::Value> {
     let mut result = core_build_replay_analysis_summary(
@@ -90,6 +235,7 @@ pub(crate) fn build_analyze_output(
         prefetch_limit,
         verbose,
     );
+    attach_move_call_return_types_py(&mut result, replay_state, graphql);
     if let Some(diag) = build_replay_diagnostics_py(replay_state) {
         result["missing_inputs"] = diag
             .get("missing_input_objects")
@@ -122,6 +268,7 @@ pub(crate) fn build_analyze_replay_output(
     dynamic_field_prefetch: bool,
     prefetch_depth: usize,
     prefetch_limit: usize,
+    graphql: Option<&GraphQLClient>,
     verbose: bool,
 ) -> Result<serde_json::Value> {
     let analysis = build_analyze_output(
@@ -132,6 +279,7 @@ pub(crate) fn build_analyze_replay_output(
         dynamic_field_prefetch,
         prefetch_depth,
         prefetch_limit,
+        graphql,
         verbose,
     )?;
 
@@ -169,6 +317,9 @@ pub(crate) fn build_analyze_replay_output(
     Ok(output)
 }
 
+const MISSING_INPUT_MESSAGE: &str =
+    "Missing input objects detected; provide full object state via state_file or better hydration source.";
+
 pub(crate) fn build_replay_diagnostics_py(replay_state: &ReplayState) -> Option<serde_json::Value> {
     core_build_replay_diagnostics(
         replay_state,
@@ -176,8 +327,7 @@ pub(crate) fn build_replay_diagnostics_py(replay_state: &ReplayState) -> Option<
         |address| replay_state.packages.contains_key(address),
         CoreReplayDiagnosticsOptions {
             allow_fallback: true,
-            missing_input_message:
-                "Missing input objects detected; provide full object state via state_file or better hydration source.",
+            missing_input_message: MISSING_INPUT_MESSAGE,
             missing_package_message:
                 "Missing package bytecode detected; prepare a package context and replay with context_path.",
             fallback_message: "",
@@ -186,6 +336,69 @@ pub(crate) fn build_replay_diagnostics_py(replay_state: &ReplayState) -> Option<
     .and_then(|diagnostics| serde_json::to_value(diagnostics).ok())
 }
 
+/// Probe `diagnostics.missing_input_objects` over gRPC and split them into
+/// `nonexistent_input_objects` (bad version), `unhydrated_input_objects` (hydration gap)
+/// and `archive_version_gaps` (the version existed but the endpoint has not archived it).
+///
+/// The generic missing-input suggestion is replaced once every missing input is classified;
+/// probe failures leave the diagnostics as they were.
+pub(crate) fn attach_missing_input_existence_py(
+    output: &mut serde_json::Value,
+    replay_state: &ReplayState,
+    grpc: &sui_transport::grpc::GrpcClient,
+) {
+    let Some(diagnostics) = output.get_mut("diagnostics") else {
+        return;
+    };
+    let missing: Vec<String> = diagnostics
+        .get("missing_input_objects")
+        .and_then(serde_json::Value::as_array)
+        .map(|ids| {
+            ids.iter()
+                .filter_map(|id| id.as_str().map(ToOwned::to_owned))
+                .collect()
+        })
+        .unwrap_or_default();
+    if missing.is_empty() {
+        return;
+    }
+
+    let rt = global_grpc_pool();
+    let mut existence =
+        core_probe_missing_input_existence(replay_state, &missing, |id, version| {
+            rt.block_on(grpc.object_exists_at(id, version)).ok()
+        });
+    existence.separate_archive_gaps(replay_state, |id, version| {
+        Some(grpc.missing_version_error(id, version, true))
+    });
+    if existence.is_empty() {
+        return;
+    }
+
+    let classified =
+        existence.nonexistent.len() + existence.unhydrated.len() + existence.archive_gaps.len();
+    let mut suggestions: Vec<serde_json::Value> = diagnostics
+        .get("suggestions")
+        .and_then(serde_json::Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if classified == missing.len() {
+        suggestions.retain(|s| s.as_str() != Some(MISSING_INPUT_MESSAGE));
+    }
+    suggestions.extend(
+        existence
+            .suggestions()
+            .into_iter()
+            .map(serde_json::Value::from),
+    );
+    diagnostics["nonexistent_input_objects"] = serde_json::json!(existence.nonexistent);
+    diagnostics["unhydrated_input_objects"] = serde_json::json!(existence.unhydrated);
+    if !existence.archive_gaps.is_empty() {
+        diagnostics["archive_version_gaps"] = serde_json::json!(existence.archive_gaps);
+    }
+    diagnostics["suggestions"] = serde_json::Value::Array(suggestions);
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn build_replay_output(
     replay_state: &sui_state_fetcher::ReplayState,
@@ -232,6 +445,7 @@ pub(crate) fn build_replay_output(
                 "success": effects.success,
                 "error": effects.error,
                 "gas_used": effects.gas_used,
+                "gas_summary": effects.gas_summary.as_ref().map(gas_summary_json),
                 "created": effects.created.iter().map(|id| id.to_hex_literal()).collect::<Vec<_>>(),
                 "mutated": effects.mutated.iter().map(|id| id.to_hex_literal()).collect::<Vec<_>>(),
                 "deleted": effects.deleted.iter().map(|id| id.to_hex_literal()).collect::<Vec<_>>(),
@@ -240,10 +454,13 @@ pub(crate) fn build_replay_output(
                 "transferred": effects.transferred.iter().map(|id| id.to_hex_literal()).collect::<Vec<_>>(),
                 "received": effects.received.iter().map(|id| id.to_hex_literal()).collect::<Vec<_>>(),
                 "events_count": effects.events.len(),
+                "events": event_provenance_json(&effects.events),
                 "failed_command_index": effects.failed_command_index,
                 "failed_command_description": effects.failed_command_description,
                 "commands_succeeded": effects.commands_succeeded,
                 "return_values": effects.return_values.iter().map(|v| v.len()).collect::<Vec<_>>(),
+                "abort": effects.error_context.as_ref().and_then(|ctx| ctx.abort_info.as_ref()),
+                "balance_changes": effects.balance_changes,
             });
 
             let comparison = if compare {
@@ -261,6 +478,11 @@ pub(crate) fn build_replay_output(
                             "unknown"
                         },
                         "local_status": if result.local_success { "success" } else { "failed" },
+                        "on_chain_gas_summary": replay_state
+                            .transaction
+                            .effects
+                            .as_ref()
+                            .map(|on_chain| gas_summary_json(&on_chain.gas_used)),
                         "notes": c.notes,
                     })
                 })
@@ -276,8 +498,19 @@ pub(crate) fn build_replay_output(
                 "commands_executed": result.commands_executed,
             });
 
+            let local_fingerprint =
+                core_local_effects_fingerprint(&replay_state.transaction, effects).digest();
+            if let Some(onchain) = core_onchain_effects_fingerprint(&replay_state.transaction) {
+                let onchain = onchain.digest();
+                output["effects_fingerprint_match"] =
+                    serde_json::json!(onchain == local_fingerprint);
+                output["onchain_effects_fingerprint"] = serde_json::json!(onchain);
+            }
+            output["local_effects_fingerprint"] = serde_json::json!(local_fingerprint);
             if let Some(err) = &result.local_error {
                 output["local_error"] = serde_json::json!(err);
+                output["local_error_kind"] =
+                    serde_json::json!(sui_sandbox_core::SandboxError::from_message(err).kind());
             }
             if let Some(diagnostics) = diagnostics {
                 output["diagnostics"] = diagnostics;
@@ -293,6 +526,7 @@ pub(crate) fn build_replay_output(
                 "digest": replay_state.transaction.digest.0,
                 "local_success": false,
                 "local_error": e.to_string(),
+                "local_error_kind": sui_sandbox_core::SandboxError::classify(&e).kind(),
                 "execution_path": execution_path,
                 "commands_executed": 0,
             });
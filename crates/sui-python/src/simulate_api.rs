@@ -0,0 +1,277 @@
+use super::*;
+
+use fork_api::input_object_id;
+use sui_sandbox_core::fork::ForkSession as CoreForkSession;
+use sui_sandbox_core::utilities::extract_package_ids_from_type_tag;
+use sui_sandbox_types::{FetchedTransaction, PtbCommand};
+use sui_state_fetcher::VersionedObject;
+use sui_transport::graphql::ObjectOwner;
+use sui_types::transaction::TransactionData;
+
+/// Decode unsigned `TransactionData` given as raw BCS bytes, base64 BCS, JSON text, or a dict.
+fn decode_transaction_data(py: Python<'_>, tx: &Bound<'_, PyAny>) -> Result<TransactionData> {
+    if let Ok(bytes) = tx.downcast::<PyBytes>() {
+        return bcs_codec::deserialize_transaction_data(bytes.as_bytes());
+    }
+    if let Ok(text) = tx.extract::<String>() {
+        let trimmed = text.trim();
+        if trimmed.starts_with('{') {
+            return bcs_codec::deserialize_transaction_data_json_str(trimmed);
+        }
+        return bcs_codec::deserialize_transaction_data_base64(trimmed);
+    }
+    let value = py_json_value(py, tx)?;
+    bcs_codec::deserialize_transaction_data_json_value(&value)
+}
+
+fn simulate_transaction_inner(
+    tx_data: TransactionData,
+    checkpoint: Option<u64>,
+    rpc_url: &str,
+    verbose: bool,
+) -> Result<serde_json::Value> {
+    let transaction = bcs_codec::transaction_data_to_fetched_transaction(
+        &tx_data,
+        "simulated",
+        None,
+        None,
+        checkpoint,
+    );
+    if transaction.commands.is_empty() {
+        return Err(anyhow!(
+            "only programmable transactions with at least one command can be simulated"
+        ));
+    }
+
+    let graphql = GraphQLClient::new(&resolve_graphql_endpoint(rpc_url));
+    let warnings = WarningLog::new(verbose);
+
+    // 1. Input objects as of `checkpoint` (or latest).
+    let mut objects = HashMap::new();
+    let mut package_ids = BTreeSet::new();
+    for object_id in transaction.inputs.iter().filter_map(input_object_id) {
+        let object = match checkpoint {
+            Some(cp) => graphql.fetch_object_at_checkpoint(object_id, cp),
+            None => graphql.fetch_object(object_id),
+        }
+        .with_context(|| format!("failed to fetch input object {}", object_id))?;
+        let bcs_base64 = object
+            .bcs_base64
+            .as_deref()
+            .ok_or_else(|| anyhow!("input object {} has no BCS contents", object_id))?;
+        let id = AccountAddress::from_hex_literal(object_id)
+            .with_context(|| format!("invalid object ID: {}", object_id))?;
+        if let Some(tag) = object
+            .type_string
+            .as_deref()
+            .and_then(sui_sandbox_core::utilities::parse_type_tag)
+        {
+            package_ids.extend(extract_package_ids_from_type_tag(&tag));
+        }
+        objects.insert(
+            id,
+            VersionedObject {
+                id,
+                version: object.version,
+                digest: object.digest,
+                type_tag: object.type_string,
+                bcs_bytes: base64::engine::general_purpose::STANDARD
+                    .decode(bcs_base64)
+                    .with_context(|| format!("invalid BCS base64 for object {}", object_id))?,
+                is_shared: matches!(object.owner, ObjectOwner::Shared { .. }),
+                is_immutable: matches!(object.owner, ObjectOwner::Immutable),
+            },
+        );
+    }
+
+    // 2. Called packages, type-argument packages and object-type packages, with deps.
+    for command in &transaction.commands {
+        if let PtbCommand::MoveCall {
+            package,
+            type_arguments,
+            ..
+        } = command
+        {
+            package_ids.insert(
+                AccountAddress::from_hex_literal(package)
+                    .with_context(|| format!("invalid package address: {}", package))?,
+            );
+            for type_arg in type_arguments {
+                if let Some(tag) = sui_sandbox_core::utilities::parse_type_tag(type_arg) {
+                    package_ids.extend(extract_package_ids_from_type_tag(&tag));
+                }
+            }
+        }
+    }
+    let mut packages = HashMap::new();
+    for package_id in package_ids {
+        if is_framework_address(&package_id) || packages.contains_key(&package_id) {
+            continue;
+        }
+        let hex = package_id.to_hex_literal();
+        let fetched = fetch_package_bytecodes_with(
+            &graphql,
+            &hex,
+            true,
+            PackageFetchOptions {
+                include_framework: false,
+                checkpoint,
+            },
+        )
+        .with_context(|| format!("failed to fetch package {}", hex))?;
+        let fetched_packages = fetched
+            .get("packages")
+            .ok_or_else(|| anyhow!("fetch package output missing `packages` field"))?;
+        for (address, package) in decode_context_packages_value(fetched_packages)? {
+            packages.entry(address).or_insert(package);
+        }
+    }
+    if verbose {
+        eprintln!(
+            "[simulate] hydrated {} object(s) and {} package(s){}",
+            objects.len(),
+            packages.len(),
+            checkpoint
+                .map(|cp| format!(" at checkpoint {}", cp))
+                .unwrap_or_default()
+        );
+    }
+
+    // 3. The epoch of `checkpoint` (or of the latest checkpoint), so the transaction
+    //    executes under that epoch's protocol version and reference gas price.
+    let at = checkpoint
+        .map(|cp| format!("checkpoint {}", cp))
+        .unwrap_or_else(|| "the latest checkpoint".to_string());
+    let (epoch_context, lookup_error) = match graphql.fetch_checkpoint_epoch(checkpoint) {
+        Ok(context) => (context, None),
+        Err(err) => (None, Some(err)),
+    };
+    let protocol_version = epoch_context
+        .as_ref()
+        .and_then(|context| context.protocol_version);
+    if protocol_version.is_none() {
+        let reason = lookup_error
+            .map(|err| format!(" ({:#})", err))
+            .unwrap_or_default();
+        warnings.push(
+            WarningCategory::Protocol,
+            format!(
+                "protocol version of {}'s epoch unavailable{}; simulating with the default",
+                at, reason
+            ),
+        );
+    }
+    let timestamp_ms = epoch_context
+        .as_ref()
+        .and_then(|context| context.timestamp_ms)
+        .or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|elapsed| elapsed.as_millis() as u64)
+        });
+    let state = ReplayState {
+        transaction: FetchedTransaction {
+            timestamp_ms,
+            ..transaction
+        },
+        objects,
+        packages,
+        protocol_version: protocol_version.unwrap_or(0),
+        epoch: epoch_context
+            .as_ref()
+            .and_then(|context| context.epoch)
+            .unwrap_or(0),
+        reference_gas_price: epoch_context.and_then(|context| context.reference_gas_price),
+        checkpoint,
+    };
+    let mut output = simulate_replay_state(&state)?;
+    warnings.attach(&mut output);
+    Ok(output)
+}
+
+/// Execute the hydrated transaction of `state` once on a throwaway fork of that state
+/// and report its effects and gas estimate.
+pub(super) fn simulate_replay_state(state: &ReplayState) -> Result<serde_json::Value> {
+    let mut fork = CoreForkSession::from_replay_state(state)?;
+    let execution = fork.execute_ptb(&state.transaction.inputs, &state.transaction.commands)?;
+
+    let gas_budget = state.transaction.gas_budget;
+    let gas_estimate = execution.gas_summary.as_ref().map(|summary| {
+        serde_json::json!({
+            "computation_cost": summary.computation_cost,
+            "storage_cost": summary.storage_cost,
+            "storage_rebate": summary.storage_rebate,
+            "non_refundable_storage_fee": summary.non_refundable_storage_fee,
+            "net_gas_cost": summary.total(),
+            // The budget must cover computation + storage before the rebate is applied.
+            "within_budget": summary.computation_cost.saturating_add(summary.storage_cost)
+                <= gas_budget,
+        })
+    });
+    Ok(serde_json::json!({
+        "sender": state.transaction.sender.to_hex_literal(),
+        "checkpoint": state.checkpoint,
+        "epoch": state.epoch,
+        "protocol_version": (state.protocol_version > 0).then_some(state.protocol_version),
+        "gas_budget": gas_budget,
+        "gas_price": state.transaction.gas_price,
+        "objects_loaded": state.objects.len(),
+        "packages_loaded": state.packages.len(),
+        "effects": execution,
+        "gas_estimate": gas_estimate,
+    }))
+}
+
+/// Dry-run unsigned `TransactionData` locally, without a fullnode.
+///
+/// The other half of `transaction_json_to_bcs`: fetches the transaction's input
+/// objects as of `checkpoint` (latest if omitted) plus the packages it calls and
+/// their dependencies via GraphQL, then executes it in the local VM under the
+/// protocol version and reference gas price of that checkpoint's epoch. Gas
+/// payment coins are not fetched; the sandbox synthesizes a gas coin. Framework
+/// packages are the bundled ones.
+///
+/// Args:
+///     tx: TransactionData as raw BCS bytes, base64 BCS string, JSON string, or dict
+///         (the JSON shapes accepted by `transaction_json_to_bcs`)
+///     checkpoint: Hydrate input objects and packages as of this checkpoint
+///     rpc_url: Sui RPC endpoint; the GraphQL endpoint is derived from it
+///     network: "mainnet", "testnet" or "devnet". Selects the network's full node (unless
+///         `rpc_url` is set to another endpoint) and its GraphQL endpoint
+///     verbose: Print hydration progress to stderr
+///
+/// Returns: Dict with sender, checkpoint, epoch, protocol_version (None when it could
+///          not be resolved), gas_budget, gas_price, objects_loaded, packages_loaded,
+///          effects (success, error, created/mutated/deleted ids, events, gas_used, ...),
+///          gas_estimate (computation/storage costs, rebate, net_gas_cost,
+///          within_budget; None without accurate gas) and warnings
+#[pyfunction]
+#[pyo3(signature = (
+    tx,
+    *,
+    checkpoint=None,
+    rpc_url="https://fullnode.mainnet.sui.io:443",
+    network=None,
+    verbose=false,
+))]
+pub(super) fn simulate_transaction(
+    py: Python<'_>,
+    tx: &Bound<'_, PyAny>,
+    checkpoint: Option<u64>,
+    rpc_url: &str,
+    network: Option<&str>,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    let network = network.map(Network::parse).transpose().map_err(to_py_err)?;
+    let rpc_url = match &network {
+        Some(network) => resolve_network_rpc_url(network, rpc_url).map_err(to_py_err)?,
+        None => rpc_url,
+    }
+    .to_string();
+    let tx_data = decode_transaction_data(py, tx).map_err(to_py_err)?;
+    let value = py
+        .allow_threads(move || simulate_transaction_inner(tx_data, checkpoint, &rpc_url, verbose))
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
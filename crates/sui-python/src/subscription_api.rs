@@ -0,0 +1,151 @@
+use super::*;
+
+use sui_transport::grpc::{
+    CheckpointSubscription as CoreCheckpointSubscription, GrpcCheckpoint, SubscriptionConfig,
+};
+
+/// Streaming needs a live fullnode; the archive endpoint used for history does not stream.
+const LIVE_GRPC_ENDPOINT: &str = "https://fullnode.mainnet.sui.io:443";
+
+/// How long `__next__` waits before checking for Ctrl-C.
+const SIGNAL_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Live checkpoint iterator returned by [`subscribe_checkpoints`].
+///
+/// Yields one dict per checkpoint, in order and without gaps across reconnects.
+#[pyclass(name = "CheckpointSubscription", module = "sui_sandbox")]
+pub(super) struct CheckpointSubscription {
+    inner: CoreCheckpointSubscription,
+    ptb_only: bool,
+}
+
+fn checkpoint_to_json(checkpoint: &GrpcCheckpoint, ptb_only: bool) -> serde_json::Value {
+    let transactions: Vec<serde_json::Value> = checkpoint
+        .transactions
+        .iter()
+        .filter(|tx| !ptb_only || tx.is_ptb())
+        .map(|tx| {
+            serde_json::json!({
+                "digest": tx.digest,
+                "sender": tx.sender,
+                "is_ptb": tx.is_ptb(),
+                "commands": tx.commands.len(),
+                "inputs": tx.inputs.len(),
+                "status": tx.status,
+                "gas_budget": tx.gas_budget,
+                "gas_price": tx.gas_price,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "checkpoint": checkpoint.sequence_number,
+        "digest": checkpoint.digest,
+        "epoch": checkpoint.epoch,
+        "timestamp_ms": checkpoint.timestamp_ms,
+        "transaction_count": checkpoint.transactions.len(),
+        "transactions": transactions,
+        "object_count": checkpoint.objects.len(),
+    })
+}
+
+#[pymethods]
+impl CheckpointSubscription {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let pool = global_grpc_pool();
+        loop {
+            let inner = &mut self.inner;
+            let next = py.allow_threads(|| {
+                pool.block_on(async {
+                    tokio::time::timeout(SIGNAL_CHECK_INTERVAL, inner.next()).await
+                })
+            });
+            match next {
+                Ok(Some(Ok(checkpoint))) => {
+                    let value = checkpoint_to_json(&checkpoint, self.ptb_only);
+                    return json_value_to_py(py, &value).map(Some);
+                }
+                Ok(Some(Err(err))) => return Err(to_py_err(err)),
+                Ok(None) => return Ok(None),
+                Err(_) => py.check_signals()?,
+            }
+        }
+    }
+
+    /// Sequence number of the next checkpoint to be yielded (None before the first
+    /// one when subscribed from the live tip). Pass it as `from_seq` to resume.
+    fn cursor(&self) -> Option<u64> {
+        self.inner.cursor()
+    }
+
+    /// Number of reconnects so far.
+    fn reconnects(&self) -> u64 {
+        self.inner.reconnects()
+    }
+}
+
+/// Tail checkpoints from a live gRPC fullnode.
+///
+/// A background task keeps the subscription alive: it reconnects with exponential
+/// backoff, skips checkpoints already yielded, and backfills any gap (after a
+/// reconnect, or when `from_seq` is behind the tip) so checkpoints arrive in order.
+/// At most `buffer` checkpoints are held ahead of the consumer; a slow consumer
+/// pauses the server stream instead of growing memory.
+///
+/// Args:
+///     from_seq: First checkpoint to yield (default: the live tip). Use a previous
+///         subscription's `cursor()` to resume
+///     endpoint: gRPC endpoint (default: mainnet fullnode)
+///     api_key: Optional API key for the endpoint
+///     buffer: Checkpoints buffered ahead of the consumer (default: 64)
+///     max_reconnects: Consecutive failed reconnects before giving up; None retries
+///         forever (default: 10)
+///     ptb_only: Only list programmable transactions in each checkpoint
+///
+/// Returns: Iterator of dicts with checkpoint, digest, epoch, timestamp_ms,
+///          transaction_count, object_count and transactions (digest, sender,
+///          is_ptb, commands, inputs, status, gas_budget, gas_price). A checkpoint
+///          that cannot be backfilled, or giving up on reconnects, raises.
+#[pyfunction]
+#[pyo3(signature = (
+    from_seq=None,
+    *,
+    endpoint=None,
+    api_key=None,
+    buffer=64,
+    max_reconnects=Some(10),
+    ptb_only=false,
+))]
+pub(super) fn subscribe_checkpoints(
+    py: Python<'_>,
+    from_seq: Option<u64>,
+    endpoint: Option<&str>,
+    api_key: Option<&str>,
+    buffer: usize,
+    max_reconnects: Option<u32>,
+    ptb_only: bool,
+) -> PyResult<CheckpointSubscription> {
+    let endpoint = endpoint.unwrap_or(LIVE_GRPC_ENDPOINT).to_string();
+    let api_key = api_key.map(ToOwned::to_owned);
+    let config = SubscriptionConfig {
+        buffer,
+        max_reconnect_attempts: max_reconnects,
+        ..SubscriptionConfig::default()
+    };
+    let pool = global_grpc_pool();
+    let inner = py
+        .allow_threads(|| {
+            pool.block_on(async {
+                let client = pool
+                    .client(&endpoint, api_key)
+                    .await
+                    .with_context(|| format!("Failed to connect to {}", endpoint))?;
+                Ok::<_, anyhow::Error>(client.subscribe_checkpoints_from(from_seq, config))
+            })
+        })
+        .map_err(to_py_err)?;
+    Ok(CheckpointSubscription { inner, ptb_only })
+}
@@ -12,20 +12,27 @@ pub(crate) fn build_walrus_client(
     core_build_walrus_client(network, caching_url, aggregator_url)
 }
 
+/// Whether `err` is the transport's answer that the object does not exist (as
+/// opposed to a failed request).
+pub(crate) fn is_object_not_found(err: &anyhow::Error) -> bool {
+    matches!(
+        sui_transport::TransportError::find(err),
+        Some(sui_transport::TransportError::ObjectNotFound { .. })
+    )
+}
+
 pub(crate) fn resolve_protocol_package_id(
     protocol: &str,
     package_id: Option<&str>,
 ) -> Result<String> {
-    let parsed = CoreProtocolAdapter::parse(protocol)?;
-    core_resolve_required_package_id(parsed, package_id)
+    CoreProtocolRegistry::load_default()?.resolve_package_id(protocol, package_id)
 }
 
 pub(crate) fn resolve_protocol_discovery_package_filter(
     protocol: &str,
     package_id: Option<&str>,
 ) -> Result<Option<String>> {
-    let parsed = CoreProtocolAdapter::parse(protocol)?;
-    core_resolve_discovery_package_filter(parsed, package_id)
+    CoreProtocolRegistry::load_default()?.resolve_discovery_package_filter(protocol, package_id)
 }
 
 pub(crate) fn discover_checkpoint_targets_inner(
@@ -34,12 +41,23 @@ pub(crate) fn discover_checkpoint_targets_inner(
     package_id: Option<&str>,
     include_framework: bool,
     limit: usize,
+    network: Option<&Network>,
     walrus_network: &str,
     walrus_caching_url: Option<&str>,
     walrus_aggregator_url: Option<&str>,
 ) -> Result<serde_json::Value> {
-    let network = parse_walrus_archive_network(walrus_network)?;
-    let walrus = build_walrus_client(network, walrus_caching_url, walrus_aggregator_url)?;
+    let fetch_latency = Arc::new(LatencyRecorder::new());
+    let walrus = match network {
+        Some(network) if walrus_caching_url.is_none() && walrus_aggregator_url.is_none() => {
+            WalrusClient::for_network(network)?
+        }
+        _ => build_walrus_client(
+            parse_walrus_archive_network(walrus_network)?,
+            walrus_caching_url,
+            walrus_aggregator_url,
+        )?,
+    }
+    .with_latency_recorder(Arc::clone(&fetch_latency));
     let output = core_discover_checkpoint_targets(
         &walrus,
         checkpoint,
@@ -48,7 +66,10 @@ pub(crate) fn discover_checkpoint_targets_inner(
         include_framework,
         limit,
     )?;
-    serde_json::to_value(output).context("failed to serialize checkpoint discovery output")
+    let mut value =
+        serde_json::to_value(output).context("failed to serialize checkpoint discovery output")?;
+    attach_fetch_latency(&mut value, &fetch_latency);
+    Ok(value)
 }
 
 pub(crate) fn resolve_replay_target_from_discovery(
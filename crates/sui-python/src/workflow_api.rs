@@ -255,7 +255,7 @@ pub(super) fn workflow_auto(
                     reason: None,
                 }
             } else {
-                infer_workflow_template_from_modules(&module_names)
+                infer_workflow_template_from_modules(&module_names)?
             };
             let template = inference.template;
 
@@ -405,12 +405,23 @@ pub(super) fn workflow_auto(
 ///
 /// Supports replay, analyze_replay, and command steps without shelling out to
 /// `sui-sandbox pipeline run` (compatibility alias: `workflow run`).
+///
+/// `explain=True` returns the resolved plan instead of running: per step, the
+/// effective source/profile (with the env vars the profile applies), each
+/// setting's origin (`step`/`defaults`/`builtin`), the target and any Walrus
+/// discovery the run would perform (not performed), the interpolated command,
+/// and `problems` that would fail the step.
+///
+/// Steps run as a dependency graph (`depends_on`); `max_parallel` bounds how
+/// many independent steps run at once. Reports keep spec order.
 #[pyfunction]
 #[pyo3(signature = (
     spec_path,
     *,
     dry_run=false,
+    explain=false,
     continue_on_error=false,
+    max_parallel=1,
     report_path=None,
     rpc_url="https://archive.mainnet.sui.io:443",
     walrus_network="mainnet",
@@ -422,7 +433,9 @@ pub(super) fn workflow_run(
     py: Python<'_>,
     spec_path: &str,
     dry_run: bool,
+    explain: bool,
     continue_on_error: bool,
+    max_parallel: usize,
     report_path: Option<&str>,
     rpc_url: &str,
     walrus_network: &str,
@@ -441,11 +454,19 @@ pub(super) fn workflow_run(
         .allow_threads(move || {
             let spec_path = PathBuf::from(&spec_path_owned);
             let spec = WorkflowSpec::load_from_path(&spec_path)?;
+            if explain {
+                return workflow_explain_spec_inner(
+                    &spec,
+                    spec_path.display().to_string(),
+                    report_path_owned,
+                );
+            }
             workflow_run_spec_inner(
                 spec,
                 spec_path.display().to_string(),
                 dry_run,
                 continue_on_error,
+                max_parallel,
                 report_path_owned,
                 &rpc_url_owned,
                 &walrus_network_owned,
@@ -461,12 +482,16 @@ pub(super) fn workflow_run(
 /// Run a typed workflow spec directly from an in-memory Python object (dict/list).
 ///
 /// This avoids writing temporary spec files for ad-hoc or notebook workflows.
+/// `explain=True` returns the resolved plan without executing, and `max_parallel`
+/// runs independent steps concurrently (see `workflow_run`).
 #[pyfunction]
 #[pyo3(signature = (
     spec,
     *,
     dry_run=false,
+    explain=false,
     continue_on_error=false,
+    max_parallel=1,
     report_path=None,
     rpc_url="https://archive.mainnet.sui.io:443",
     walrus_network="mainnet",
@@ -478,7 +503,9 @@ pub(super) fn workflow_run_inline(
     py: Python<'_>,
     spec: &Bound<'_, PyAny>,
     dry_run: bool,
+    explain: bool,
     continue_on_error: bool,
+    max_parallel: usize,
     report_path: Option<&str>,
     rpc_url: &str,
     walrus_network: &str,
@@ -495,11 +522,19 @@ pub(super) fn workflow_run_inline(
 
     let value = py
         .allow_threads(move || {
+            if explain {
+                return workflow_explain_spec_inner(
+                    &inline_spec,
+                    "<inline>".to_string(),
+                    report_path_owned,
+                );
+            }
             workflow_run_spec_inner(
                 inline_spec,
                 "<inline>".to_string(),
                 dry_run,
                 continue_on_error,
+                max_parallel,
                 report_path_owned,
                 &rpc_url_owned,
                 &walrus_network_owned,
@@ -512,6 +547,82 @@ pub(super) fn workflow_run_inline(
     json_value_to_py(py, &value)
 }
 
+/// Re-run a workflow spec for each new Walrus transaction touching `package_id`.
+///
+/// Each poll scans checkpoints after the cursor (`<spec>.watch.json` by default),
+/// skips digests already run, and retargets the spec's replay/analyze_replay steps
+/// at every new match. Returns a summary of the runs. With `max_polls=None` this
+/// blocks until the process is stopped.
+#[pyfunction]
+#[pyo3(signature = (
+    spec_path,
+    package_id,
+    *,
+    cursor_path=None,
+    interval_secs=30,
+    max_checkpoints_per_poll=20,
+    from_checkpoint=None,
+    max_polls=None,
+    continue_on_error=false,
+    max_parallel=1,
+    report_dir=None,
+    rpc_url="https://archive.mainnet.sui.io:443",
+    walrus_network="mainnet",
+    walrus_caching_url=None,
+    walrus_aggregator_url=None,
+    verbose=false,
+))]
+pub(super) fn workflow_watch(
+    py: Python<'_>,
+    spec_path: &str,
+    package_id: &str,
+    cursor_path: Option<&str>,
+    interval_secs: u64,
+    max_checkpoints_per_poll: u64,
+    from_checkpoint: Option<u64>,
+    max_polls: Option<usize>,
+    continue_on_error: bool,
+    max_parallel: usize,
+    report_dir: Option<&str>,
+    rpc_url: &str,
+    walrus_network: &str,
+    walrus_caching_url: Option<&str>,
+    walrus_aggregator_url: Option<&str>,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    let spec_path_owned = PathBuf::from(spec_path);
+    let package_id_owned = package_id.to_string();
+    let cursor_path_owned = cursor_path.map(PathBuf::from);
+    let report_dir_owned = report_dir.map(PathBuf::from);
+    let rpc_url_owned = rpc_url.to_string();
+    let walrus_network_owned = walrus_network.to_string();
+    let walrus_caching_owned = walrus_caching_url.map(ToOwned::to_owned);
+    let walrus_aggregator_owned = walrus_aggregator_url.map(ToOwned::to_owned);
+
+    let value = py
+        .allow_threads(move || {
+            workflow_watch_inner(
+                &spec_path_owned,
+                &package_id_owned,
+                cursor_path_owned,
+                interval_secs,
+                max_checkpoints_per_poll,
+                from_checkpoint,
+                max_polls,
+                continue_on_error,
+                max_parallel,
+                report_dir_owned,
+                &rpc_url_owned,
+                &walrus_network_owned,
+                walrus_caching_owned.as_deref(),
+                walrus_aggregator_owned.as_deref(),
+                verbose,
+            )
+        })
+        .map_err(to_py_err)?;
+    json_value_to_py(py, &value)
+}
+
 /// Canonical alias for `workflow_validate`.
 #[pyfunction]
 pub(super) fn pipeline_validate(py: Python<'_>, spec_path: &str) -> PyResult<PyObject> {
@@ -622,7 +733,9 @@ pub(super) fn pipeline_auto(
     spec_path,
     *,
     dry_run=false,
+    explain=false,
     continue_on_error=false,
+    max_parallel=1,
     report_path=None,
     rpc_url="https://archive.mainnet.sui.io:443",
     walrus_network="mainnet",
@@ -634,7 +747,9 @@ pub(super) fn pipeline_run(
     py: Python<'_>,
     spec_path: &str,
     dry_run: bool,
+    explain: bool,
     continue_on_error: bool,
+    max_parallel: usize,
     report_path: Option<&str>,
     rpc_url: &str,
     walrus_network: &str,
@@ -646,7 +761,9 @@ pub(super) fn pipeline_run(
         py,
         spec_path,
         dry_run,
+        explain,
         continue_on_error,
+        max_parallel,
         report_path,
         rpc_url,
         walrus_network,
@@ -662,7 +779,9 @@ pub(super) fn pipeline_run(
     spec,
     *,
     dry_run=false,
+    explain=false,
     continue_on_error=false,
+    max_parallel=1,
     report_path=None,
     rpc_url="https://archive.mainnet.sui.io:443",
     walrus_network="mainnet",
@@ -674,7 +793,9 @@ pub(super) fn pipeline_run_inline(
     py: Python<'_>,
     spec: &Bound<'_, PyAny>,
     dry_run: bool,
+    explain: bool,
     continue_on_error: bool,
+    max_parallel: usize,
     report_path: Option<&str>,
     rpc_url: &str,
     walrus_network: &str,
@@ -686,7 +807,9 @@ pub(super) fn pipeline_run_inline(
         py,
         spec,
         dry_run,
+        explain,
         continue_on_error,
+        max_parallel,
         report_path,
         rpc_url,
         walrus_network,
@@ -695,3 +818,61 @@ pub(super) fn pipeline_run_inline(
         verbose,
     )
 }
+
+/// Canonical alias for `workflow_watch`.
+#[pyfunction]
+#[pyo3(signature = (
+    spec_path,
+    package_id,
+    *,
+    cursor_path=None,
+    interval_secs=30,
+    max_checkpoints_per_poll=20,
+    from_checkpoint=None,
+    max_polls=None,
+    continue_on_error=false,
+    max_parallel=1,
+    report_dir=None,
+    rpc_url="https://archive.mainnet.sui.io:443",
+    walrus_network="mainnet",
+    walrus_caching_url=None,
+    walrus_aggregator_url=None,
+    verbose=false,
+))]
+pub(super) fn pipeline_watch(
+    py: Python<'_>,
+    spec_path: &str,
+    package_id: &str,
+    cursor_path: Option<&str>,
+    interval_secs: u64,
+    max_checkpoints_per_poll: u64,
+    from_checkpoint: Option<u64>,
+    max_polls: Option<usize>,
+    continue_on_error: bool,
+    max_parallel: usize,
+    report_dir: Option<&str>,
+    rpc_url: &str,
+    walrus_network: &str,
+    walrus_caching_url: Option<&str>,
+    walrus_aggregator_url: Option<&str>,
+    verbose: bool,
+) -> PyResult<PyObject> {
+    workflow_watch(
+        py,
+        spec_path,
+        package_id,
+        cursor_path,
+        interval_secs,
+        max_checkpoints_per_poll,
+        from_checkpoint,
+        max_polls,
+        continue_on_error,
+        max_parallel,
+        report_dir,
+        rpc_url,
+        walrus_network,
+        walrus_caching_url,
+        walrus_aggregator_url,
+        verbose,
+    )
+}
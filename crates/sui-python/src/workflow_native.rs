@@ -1,13 +1,14 @@
 use super::*;
 use sui_sandbox_core::workflow_planner::{
     apply_workflow_profile_env as core_apply_workflow_profile_env,
-    infer_workflow_template_from_modules as core_infer_workflow_template_from_modules,
+    infer_workflow_template_with_registry as core_infer_workflow_template_with_registry,
     parse_builtin_workflow_template as core_parse_builtin_workflow_template,
     parse_workflow_fetch_strategy as core_parse_workflow_fetch_strategy,
     parse_workflow_profile as core_parse_workflow_profile,
     short_package_id as core_short_package_id,
     summarize_failure_output as core_summarize_failure_output,
     workflow_build_step_command as core_workflow_build_step_command,
+    workflow_explain_step as core_workflow_explain_step,
     workflow_step_kind as core_workflow_step_kind, workflow_step_label as core_workflow_step_label,
     WorkflowEnvGuard as CoreWorkflowEnvGuard,
     WorkflowTemplateInference as CoreWorkflowTemplateInference,
@@ -17,6 +18,9 @@ use sui_sandbox_core::workflow_planner::{
     workflow_build_analyze_replay_command as core_workflow_build_analyze_replay_command,
     workflow_build_replay_command as core_workflow_build_replay_command,
 };
+use sui_sandbox_core::workflow_watch::{
+    default_watch_cursor_path, watch_workflow, WorkflowWatchConfig,
+};
 
 pub(crate) fn parse_workflow_template(template: &str) -> Result<BuiltinWorkflowTemplate> {
     core_parse_builtin_workflow_template(template)
@@ -80,7 +84,7 @@ pub(crate) fn probe_package_modules_for_workflow(package_id: &str) -> Result<(us
 pub(crate) fn probe_dependency_closure_for_workflow(
     package_id: &str,
 ) -> Result<(usize, Vec<String>)> {
-    let fetched = fetch_package_bytecodes_inner(package_id, true)?;
+    let fetched = fetch_package_bytecodes_inner(package_id, true, PackageFetchOptions::default())?;
     let packages_value = fetched
         .get("packages")
         .ok_or_else(|| anyhow!("fetch package probe output missing `packages` field"))?;
@@ -104,8 +108,12 @@ pub(crate) type WorkflowTemplateInference = CoreWorkflowTemplateInference;
 
 pub(crate) fn infer_workflow_template_from_modules(
     module_names: &[String],
-) -> WorkflowTemplateInference {
-    core_infer_workflow_template_from_modules(module_names)
+) -> Result<WorkflowTemplateInference> {
+    let registry = CoreProtocolRegistry::load_default()?;
+    Ok(core_infer_workflow_template_with_registry(
+        module_names,
+        &registry,
+    ))
 }
 
 #[derive(Debug, Clone)]
@@ -130,6 +138,7 @@ pub(crate) fn discover_latest_target_for_workflow(
         Some(package_id),
         false,
         1,
+        None,
         walrus_network,
         walrus_caching_url,
         walrus_aggregator_url,
@@ -211,6 +220,7 @@ pub(crate) fn workflow_discover_target_for_replay(
         None,
         false,
         1,
+        None,
         walrus_network,
         walrus_caching_url,
         walrus_aggregator_url,
@@ -331,7 +341,7 @@ pub(crate) fn workflow_execute_command_step(
     if program == "analyze" && normalized.get(1).is_some_and(|value| value == "package") {
         let package_id = workflow_parse_flag_value(&normalized, "--package-id")
             .ok_or_else(|| anyhow!("`analyze package` requires --package-id"))?;
-        let interface = extract_interface_inner(Some(&package_id), None, rpc_url)?;
+        let interface = extract_interface_inner(Some(&package_id), None, rpc_url, false)?;
         let module_names = workflow_extract_interface_module_names(&interface);
         let list_modules = normalized.iter().any(|value| value == "--list-modules");
         return Ok(WorkflowRunStepExecution {
@@ -535,6 +545,7 @@ pub(crate) fn workflow_execute_replay_step(
             false,
             rpc_url,
             verbose,
+            &ReplayExtras::default(),
         )?
     } else if source == WorkflowSource::Local {
         let digest = digest
@@ -563,6 +574,7 @@ pub(crate) fn workflow_execute_replay_step(
             false,
             rpc_url,
             verbose,
+            &ReplayExtras::default(),
         )?
     } else {
         replay_inner(
@@ -585,6 +597,7 @@ pub(crate) fn workflow_execute_replay_step(
             false,
             false,
             verbose,
+            &ReplayExtras::default(),
         )?
     };
 
@@ -699,6 +712,7 @@ pub(crate) fn workflow_execute_analyze_replay_step(
             mm2_enabled,
             rpc_url,
             verbose,
+            &ReplayExtras::default(),
         )?
     } else {
         replay_inner(
@@ -719,6 +733,7 @@ pub(crate) fn workflow_execute_analyze_replay_step(
             true,
             mm2_enabled,
             verbose,
+            &ReplayExtras::default(),
         )?
     };
     let local_success = output
@@ -756,12 +771,56 @@ pub(crate) fn write_workflow_run_report(path: &Path, report: &serde_json::Value)
     Ok(())
 }
 
+/// Resolved per-step plan for `explain=True`; nothing is executed or discovered.
+pub(crate) fn workflow_explain_spec_inner(
+    spec: &WorkflowSpec,
+    spec_label: String,
+    report_path: Option<String>,
+) -> Result<serde_json::Value> {
+    let steps = spec
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| core_workflow_explain_step(&spec.defaults, step, idx + 1))
+        .collect::<Vec<_>>();
+    let problem_steps = steps
+        .iter()
+        .filter(|step| {
+            step.get("problems")
+                .and_then(serde_json::Value::as_array)
+                .is_some_and(|problems| !problems.is_empty())
+        })
+        .count();
+    let discovery_steps = steps
+        .iter()
+        .filter(|step| !step["discovery"].is_null())
+        .count();
+    let mut plan = serde_json::json!({
+        "spec_file": spec_label,
+        "name": spec.name,
+        "description": spec.description,
+        "explain": true,
+        "total_steps": steps.len(),
+        "discovery_steps": discovery_steps,
+        "problem_steps": problem_steps,
+        "defaults": spec.defaults,
+        "steps": steps,
+    });
+    if let Some(path) = report_path.as_deref() {
+        let report_path = PathBuf::from(path);
+        write_workflow_run_report(&report_path, &plan)?;
+        plan["report_file"] = serde_json::json!(report_path.display().to_string());
+    }
+    Ok(plan)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn workflow_run_spec_inner(
     spec: WorkflowSpec,
     spec_label: String,
     dry_run: bool,
     continue_on_error: bool,
+    max_parallel: usize,
     report_path: Option<String>,
     rpc_url: &str,
     walrus_network: &str,
@@ -769,6 +828,47 @@ pub(crate) fn workflow_run_spec_inner(
     walrus_aggregator_url: Option<&str>,
     verbose: bool,
 ) -> Result<serde_json::Value> {
+    let report_struct = workflow_run_spec_report(
+        &spec,
+        spec_label,
+        dry_run,
+        continue_on_error,
+        max_parallel,
+        rpc_url,
+        walrus_network,
+        walrus_caching_url,
+        walrus_aggregator_url,
+        verbose,
+    );
+    let mut report = serde_json::to_value(&report_struct)?;
+
+    if let Some(path) = report_path.as_deref() {
+        let report_path = PathBuf::from(path);
+        write_workflow_run_report(&report_path, &report)?;
+        if let Some(object) = report.as_object_mut() {
+            object.insert(
+                "report_file".to_string(),
+                serde_json::json!(report_path.display().to_string()),
+            );
+        }
+    }
+
+    Ok(report)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn workflow_run_spec_report(
+    spec: &WorkflowSpec,
+    spec_label: String,
+    dry_run: bool,
+    continue_on_error: bool,
+    max_parallel: usize,
+    rpc_url: &str,
+    walrus_network: &str,
+    walrus_caching_url: Option<&str>,
+    walrus_aggregator_url: Option<&str>,
+    verbose: bool,
+) -> WorkflowRunReport {
     let prepared_steps = spec
         .steps
         .iter()
@@ -784,12 +884,13 @@ pub(crate) fn workflow_run_spec_inner(
         })
         .collect::<Vec<_>>();
 
-    let report_struct = run_prepared_workflow_steps(
+    run_prepared_workflow_steps_parallel(
         spec_label,
-        &spec,
+        spec,
         prepared_steps,
         dry_run,
         continue_on_error,
+        max_parallel,
         |step, prepared| {
             if verbose {
                 eprintln!(
@@ -841,20 +942,83 @@ pub(crate) fn workflow_run_spec_inner(
                 error,
             })
         },
-    );
+    )
+}
 
-    let mut report = serde_json::to_value(&report_struct)?;
+/// Watch Walrus for new transactions touching `package_id` and run the spec for
+/// each; see `sui_sandbox_core::workflow_watch`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn workflow_watch_inner(
+    spec_path: &Path,
+    package_id: &str,
+    cursor_path: Option<PathBuf>,
+    interval_secs: u64,
+    max_checkpoints_per_poll: u64,
+    from_checkpoint: Option<u64>,
+    max_polls: Option<usize>,
+    continue_on_error: bool,
+    max_parallel: usize,
+    report_dir: Option<PathBuf>,
+    rpc_url: &str,
+    walrus_network: &str,
+    walrus_caching_url: Option<&str>,
+    walrus_aggregator_url: Option<&str>,
+    verbose: bool,
+) -> Result<serde_json::Value> {
+    let spec = WorkflowSpec::load_from_path(spec_path)?;
+    let walrus = core_build_walrus_client(
+        CoreWalrusArchiveNetwork::parse(walrus_network)?,
+        walrus_caching_url,
+        walrus_aggregator_url,
+    )?;
+    let cursor_path = cursor_path.unwrap_or_else(|| default_watch_cursor_path(spec_path));
+    let config = WorkflowWatchConfig {
+        poll_interval: std::time::Duration::from_secs(interval_secs),
+        max_checkpoints_per_poll,
+        start_checkpoint: from_checkpoint,
+        max_polls,
+        ..WorkflowWatchConfig::new(package_id)
+    };
+    let spec_label = spec_path.display().to_string();
 
-    if let Some(path) = report_path.as_deref() {
-        let report_path = PathBuf::from(path);
-        write_workflow_run_report(&report_path, &report)?;
-        if let Some(object) = report.as_object_mut() {
-            object.insert(
-                "report_file".to_string(),
-                serde_json::json!(report_path.display().to_string()),
+    let summary = watch_workflow(
+        &walrus,
+        &spec,
+        &config,
+        Some(&cursor_path),
+        |target, target_spec| {
+            if verbose {
+                eprintln!(
+                    "[workflow-watch] checkpoint {} tx {}",
+                    target.checkpoint, target.digest
+                );
+            }
+            let report = workflow_run_spec_report(
+                target_spec,
+                spec_label.clone(),
+                false,
+                continue_on_error,
+                max_parallel,
+                rpc_url,
+                walrus_network,
+                walrus_caching_url,
+                walrus_aggregator_url,
+                verbose,
             );
-        }
-    }
+            if let Some(dir) = report_dir.as_ref() {
+                let path = dir.join(format!("{}_{}.json", target.checkpoint, target.digest));
+                write_workflow_run_report(&path, &serde_json::to_value(&report)?)?;
+            }
+            Ok(report)
+        },
+    )?;
 
-    Ok(report)
+    let mut value = serde_json::to_value(&summary)?;
+    if let Some(object) = value.as_object_mut() {
+        object.insert(
+            "cursor_file".to_string(),
+            serde_json::json!(cursor_path.display().to_string()),
+        );
+    }
+    Ok(value)
 }
@@ -1,9 +1,20 @@
-from typing import Any, Dict, List, Optional
+# Generated by scripts/generate_python_stubs.py from the pyo3 bindings in
+# crates/sui-python/src. Do not edit by hand; rerun the script instead.
+
+from typing import Any, Callable, Dict, List, Optional, Tuple
 
 __version__: str
 
 
 class OrchestrationSession:
+    """Interactive two-step flow helper for Python.
+
+    Keeps prepared package context in memory and reuses it across replays. The
+    session is safe to share across threads: the context is an immutable snapshot
+    swapped atomically by `prepare()` / `load_context()`, and each `replay()` call
+    writes it to its own temp file, so one prepared session can serve concurrent
+    replays.
+    """
     def __init__(self) -> None: ...
     def prepare(
         self,
@@ -53,18 +64,280 @@ class FlowSession(OrchestrationSession): ...
 class ContextSession(OrchestrationSession): ...
 
 
+class AnalyzedTransaction:
+    """Hydrated transaction handle for repeated offline analysis.
+
+    Fetches (or loads) a `ReplayState` once; every accessor is computed lazily
+    over the cached state so iterating on analysis never refetches from the network.
+    """
+    def __init__(
+        self,
+        digest: Optional[str] = ...,
+        *,
+        checkpoint: Optional[int] = ...,
+        state_file: Optional[str] = ...,
+        rpc_url: str = ...,
+        verbose: bool = ...,
+    ) -> None:
+        """Hydrate from a replay state file, or from `digest` + `checkpoint` via Walrus."""
+    def digest(self) -> str: ...
+    def checkpoint(self) -> Optional[int]: ...
+    def summary(self) -> Dict[str, Any]:
+        """Hydration summary (same shape as `analyze_replay` without VM execution)."""
+    def inputs(self) -> List[Dict[str, Any]]:
+        """Per-input rows: kind, object id/version, and whether the object is hydrated."""
+    def commands(self) -> List[Dict[str, Any]]:
+        """Per-command rows: kind, call target, type args, and argument references."""
+    def dataflow(self) -> Dict[str, Any]:
+        """PTB dataflow edges plus unused inputs/results."""
+    def feasibility(self) -> Dict[str, Any]:
+        """Which commands/inputs the replay VM path supports, with a `replayable` verdict."""
+    def missing(self) -> Dict[str, Any]:
+        """Missing input objects/packages and hydration suggestions."""
+    def mm2(self) -> Dict[str, Any]:
+        """MM2 type-model check over the package closure.
+
+        Dependency packages are fetched via GraphQL on first call; the result is cached.
+        """
+    def save_state(self, path: str) -> None:
+        """Write the cached replay state to JSON so it can be reloaded via `state_file`."""
+
+
+class ForkSession:
+    """Local fork of historical state that keeps its mutations across PTBs.
+
+    Hydrates the packages and objects of a transaction's replay state once, then
+    executes synthetic PTBs on top of it; each `execute_ptb()` sees the objects as
+    the previous PTBs left them.
+    """
+    def __init__(
+        self,
+        digest: Optional[str] = ...,
+        *,
+        checkpoint: Optional[int] = ...,
+        state_file: Optional[str] = ...,
+        verbose: bool = ...,
+    ) -> None:
+        """Fork from a replay state file, or from `digest` + `checkpoint` via Walrus."""
+    def origin(self) -> Dict[str, Any]:
+        """Where the fork was taken: `digest`, `checkpoint`, `epoch`, `protocol_version`,
+        `timestamp_ms`, and the number of hydrated `objects` and `packages`.
+        """
+    def executed(self) -> int:
+        """Number of PTBs executed so far (failed ones included)."""
+    def sender(self) -> str: ...
+    def set_sender(self, sender: str) -> None:
+        """Sender of subsequent PTBs (default: the origin transaction's sender)."""
+    def execute_ptb(
+        self,
+        inputs: List[Dict[str, Any]],
+        commands: List[Dict[str, Any]],
+        *,
+        sender: Optional[str] = ...,
+    ) -> Dict[str, Any]:
+        """Execute a PTB against the fork's current state.
+
+        `inputs` and `commands` are lists (or JSON strings) in the state-file
+        `transaction.inputs` / `transaction.commands` schema. Object inputs are
+        resolved by ID from the fork, so their `version`/`digest` may be stale.
+
+        Returns: dict with `index`, `success`, `error`, `failed_command_index`,
+        `gas_used` (plus a `gas_summary` cost breakdown when available),
+        `created`/`mutated`/`deleted`/`wrapped`/`unwrapped` ids, event
+        types under `events`, and the new `versions` of created and mutated objects
+        """
+    def get_object(self, object_id: str) -> Optional[Dict[str, Any]]:
+        """Current state of an object (`object_id`, `version`, `type_tag`, `bcs_base64`,
+        `is_shared`, `is_immutable`), or None if the fork doesn't hold it.
+        """
+    def set_object(
+        self,
+        object_id: str,
+        bcs_bytes: bytes,
+        *,
+        type_tag: Optional[str] = ...,
+        version: Optional[int] = ...,
+        is_shared: bool = ...,
+        is_immutable: bool = ...,
+    ) -> None:
+        """Insert or overwrite an object in the fork.
+
+        `type_tag` and `version` default to the existing object's; a new object
+        requires `type_tag` and starts at version 1.
+        """
+
+
+class PtbBuilder:
+    """Composes a multi-command PTB (SplitCoins, MergeCoins, MoveCall, TransferObjects,
+    MakeMoveVec) with result-argument wiring.
+
+    Input and command methods return argument handles (`{"type": "Input", ...}`,
+    `{"type": "Result", ...}`) to pass to later commands; `nested()` selects one
+    value of a multi-value result. `build()` returns the PTB in the state-file
+    `inputs`/`commands` schema accepted by `ForkSession.execute_ptb()`.
+    """
+    def __init__(self) -> None: ...
+    def pure(self, value: Any, type_tag: Optional[str] = ...) -> Dict[str, Any]:
+        """Pure input: raw BCS `bytes`, or a JSON-shaped value encoded as `type_tag`
+        (e.g. `pure(100, "u64")`, `pure("0x2", "address")`, `pure([1, 2], "vector<u8>")`).
+        """
+    def object(
+        self,
+        object_id: str,
+        *,
+        shared: bool = ...,
+        mutable: bool = ...,
+        immutable: bool = ...,
+        receiving: bool = ...,
+        version: Optional[int] = ...,
+        bcs_bytes: Optional[bytes] = ...,
+        type_tag: Optional[str] = ...,
+    ) -> Dict[str, Any]:
+        """Object input (owned by default). Repeating an object ID returns its existing handle.
+
+        `version` is only a hint: ForkSession resolves objects by ID to their
+        current version. `bcs_bytes` and `type_tag` give the object's contents when
+        executing against a FlowSession, which holds packages but no objects.
+        """
+    def gas(self) -> Dict[str, Any]:
+        """The gas coin."""
+    def nested(self, result: Any, index: int) -> Dict[str, Any]:
+        """Value `index` of a multi-value command result."""
+    def split_coins(self, coin: Any, amounts: List[Any]) -> Dict[str, Any]:
+        """SplitCoins; use `nested(result, i)` for the i-th new coin."""
+    def merge_coins(self, destination: Any, sources: List[Any]) -> Dict[str, Any]: ...
+    def transfer_objects(self, objects: List[Any], address: Any) -> Dict[str, Any]: ...
+    def move_call(
+        self,
+        package: str,
+        module: str,
+        function: str,
+        args: Optional[List[Any]] = ...,
+        type_args: Optional[List[str]] = ...,
+    ) -> Dict[str, Any]: ...
+    def make_move_vec(
+        self,
+        elements: List[Any],
+        type_tag: Optional[str] = ...,
+    ) -> Dict[str, Any]: ...
+    def build(self) -> Dict[str, Any]:
+        """The PTB so far as `{"inputs": [...], "commands": [...]}`."""
+    def execute(self, target: Any, *, sender: Optional[str] = ...) -> Dict[str, Any]:
+        """Execute the PTB against a ForkSession (mutating it) or a prepared FlowSession.
+
+        A FlowSession runs the PTB once on a fresh fork of its packages plus the
+        objects given to `object()` with `bcs_bytes`; nothing persists afterwards.
+
+        Returns: the same dict as `ForkSession.execute_ptb()`
+        """
+
+
+class PackageModuleIterator:
+    """Iterator over a package's modules, fetched from GraphQL one page at a time.
+
+    Yields `(name, bytecode)` tuples; returned by [`stream_package_modules`].
+    """
+    def __iter__(self) -> PackageModuleIterator: ...
+    def __next__(self) -> Optional[Tuple[str, bytes]]: ...
+
+
+class CheckpointSubscription:
+    """Live checkpoint iterator returned by [`subscribe_checkpoints`].
+
+    Yields one dict per checkpoint, in order and without gaps across reconnects.
+    """
+    def __iter__(self) -> CheckpointSubscription: ...
+    def __next__(self) -> Optional[Dict[str, Any]]: ...
+    def cursor(self) -> Optional[int]:
+        """Sequence number of the next checkpoint to be yielded (None before the first
+        one when subscribed from the live tip). Pass it as `from_seq` to resume.
+        """
+    def reconnects(self) -> int:
+        """Number of reconnects so far."""
+
+
 def extract_interface(
     *,
     package_id: Optional[str] = ...,
     bytecode_dir: Optional[str] = ...,
     rpc_url: str = ...,
-) -> Dict[str, Any]: ...
+    include_entrypoints: bool = ...,
+) -> Dict[str, Any]:
+    """Extract the full interface JSON for a Sui Move package.
+
+    Returns the complete interface with all modules, structs, functions,
+    type parameters, abilities, fields, etc.
+
+    Provide either `package_id` (fetched via GraphQL) or `bytecode_dir`
+    (local directory with `bytecode_modules/*.mv`), but not both.
 
+    With `include_entrypoints=True` the result also carries an `entrypoints`
+    list: every public/entry function with parameter type tags, whether each
+    parameter is an object, pure value, or injected `TxContext`, and return types.
 
-def get_latest_checkpoint() -> int: ...
+    Standalone — no CLI binary needed.
+    """
 
 
-def get_checkpoint(checkpoint: int) -> Dict[str, Any]: ...
+def extract_call_targets(
+    package_id: Optional[str] = ...,
+    *,
+    bytecode_dir: Optional[str] = ...,
+    rpc_url: str = ...,
+) -> Dict[str, Any]:
+    """Extract the functions each of a package's functions statically calls.
+
+    Walks every function body for `Call`/`CallGeneric` and resolves the target
+    through the module's function, module and address handle tables, giving a
+    static call graph at function granularity (`extract_dependency_addrs` only
+    gives package-level dependencies).
+
+    Provide either `package_id` (fetched via GraphQL) or `bytecode_dir`
+    (local directory with `bytecode_modules/*.mv`), but not both.
+
+    Returns: Dict with `package_id`, `functions` (per function: `module`, `function`
+    and `calls`, each `{package, module, function, external}`), the distinct
+    `external_targets` and the `external_packages` they live in
+    """
+
+
+def package_types(
+    package_id: Optional[str] = ...,
+    *,
+    bytecode_dir: Optional[str] = ...,
+    rpc_url: str = ...,
+) -> Dict[str, Any]:
+    """List the structs and enums a package defines.
+
+    Each entry carries the declared abilities (`copy`, `drop`, `store`, `key`),
+    the type parameters with their ability constraints and phantom flag, whether
+    the type is an object (`key`), and whether it has the one-time-witness shape
+    (named after its module in upper case, `drop` only, no type parameters or fields).
+
+    Provide either `package_id` (fetched via GraphQL) or `bytecode_dir`
+    (local directory with `bytecode_modules/*.mv`), but not both.
+
+    Returns: Dict with `package_id`, `types` (per type: `module`, `name`, `type_tag`,
+    `kind`, `abilities`, `type_params`, `is_object`, `is_one_time_witness`) and `counts`
+    """
+
+
+def get_latest_checkpoint() -> int:
+    """Get the latest archived checkpoint number from Walrus.
+
+    No API keys or authentication required. Standalone — no CLI binary needed.
+    """
+
+
+def get_checkpoint(checkpoint: int) -> Dict[str, Any]:
+    """Fetch a checkpoint from Walrus and return a summary dict.
+
+    Returns: checkpoint, epoch, timestamp_ms, transaction_count,
+    transactions (list of {digest, sender, commands, input_objects, output_objects}),
+    and object_versions_count.
+
+    No API keys or authentication required. Standalone — no CLI binary needed.
+    """
 
 
 def doctor(
@@ -73,26 +346,27 @@ def doctor(
     state_file: Optional[str] = ...,
     timeout_secs: int = ...,
     include_toolchain_checks: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Run environment and endpoint preflight checks (CLI parity for `doctor`).
+
+    This function never raises on check failures; it always returns a structured report.
+    """
 
 
 def session_status(
     *,
     state_file: Optional[str] = ...,
     rpc_url: str = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Return sandbox session status for a state file (CLI parity for `status`)."""
 
 
-def session_reset(
-    *,
-    state_file: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+def session_reset(*, state_file: Optional[str] = ...) -> Dict[str, Any]:
+    """Reset sandbox session state to a clean baseline (CLI parity for `reset`)."""
 
 
-def session_clean(
-    *,
-    state_file: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+def session_clean(*, state_file: Optional[str] = ...) -> Dict[str, Any]:
+    """Remove sandbox session state file (CLI parity for `clean`)."""
 
 
 def snapshot_save(
@@ -100,20 +374,20 @@ def snapshot_save(
     *,
     description: Optional[str] = ...,
     state_file: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Save a named snapshot of the current session state (CLI parity for `snapshot save`)."""
 
 
-def snapshot_load(
-    name: str,
-    *,
-    state_file: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+def snapshot_load(name: str, *, state_file: Optional[str] = ...) -> Dict[str, Any]:
+    """Load a named snapshot into the session state file (CLI parity for `snapshot load`)."""
 
 
-def snapshot_list() -> List[Dict[str, Any]]: ...
+def snapshot_list() -> List[Dict[str, Any]]:
+    """List available snapshots (CLI parity for `snapshot list`)."""
 
 
-def snapshot_delete(name: str) -> Dict[str, Any]: ...
+def snapshot_delete(name: str) -> Dict[str, Any]:
+    """Delete a snapshot by name (CLI parity for `snapshot delete`)."""
 
 
 def ptb_universe(
@@ -125,7 +399,13 @@ def ptb_universe(
     out_dir: Optional[str] = ...,
     grpc_endpoint: Optional[str] = ...,
     stream_timeout_secs: int = ...,
-) -> Dict[str, Any]: ...
+    seed: Optional[int] = ...,
+) -> Dict[str, Any]:
+    """Build and execute a checkpoint-source PTB universe run via core engine.
+
+    This is the same reusable engine used by the Rust example wrapper
+    (`examples/walrus_ptb_universe.rs`), exposed as a first-class Python API.
+    """
 
 
 def discover_checkpoint_targets(
@@ -135,37 +415,52 @@ def discover_checkpoint_targets(
     package_id: Optional[str] = ...,
     include_framework: bool = ...,
     limit: int = ...,
+    network: Optional[str] = ...,
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Discover replay candidates from checkpoint Move calls.
 
+    Returns digests + package/module/function call summaries for programmable
+    transactions across one or more checkpoints.
 
-def protocol_discover(
+    By default this uses Walrus mainnet. Set `network="testnet"` (or the older
+    `walrus_network="testnet"`) or pass both `walrus_caching_url` and
+    `walrus_aggregator_url` for custom archive endpoints.
+    """
+
+
+def context_discover(
     *,
-    protocol: str = ...,
-    package_id: Optional[str] = ...,
     checkpoint: Optional[str] = ...,
     latest: Optional[int] = ...,
+    package_id: Optional[str] = ...,
     include_framework: bool = ...,
     limit: int = ...,
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Canonical alias for `discover_checkpoint_targets`."""
 
 
-def context_discover(
+def protocol_discover(
     *,
+    protocol: str = ...,
+    package_id: Optional[str] = ...,
     checkpoint: Optional[str] = ...,
     latest: Optional[int] = ...,
-    package_id: Optional[str] = ...,
     include_framework: bool = ...,
     limit: int = ...,
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Protocol-first replay-target discovery from checkpoints.
+
+    Non-generic protocols require `package_id` so package selection stays explicit.
+    """
 
 
 def adapter_discover(
@@ -179,16 +474,15 @@ def adapter_discover(
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
-) -> Dict[str, Any]: ...
-
-
-def workflow_validate(spec_path: str) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Canonical alias for `protocol_discover`."""
 
 
-def pipeline_validate(spec_path: str) -> Dict[str, Any]: ...
+def pipeline_validate(spec_path: str) -> Dict[str, Any]:
+    """Canonical alias for `workflow_validate`."""
 
 
-def workflow_init(
+def pipeline_init(
     *,
     template: str = ...,
     output_path: Optional[str] = ...,
@@ -201,44 +495,106 @@ def workflow_init(
     package_id: Optional[str] = ...,
     view_objects: List[str] = ...,
     force: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Canonical alias for `workflow_init`."""
 
 
-def pipeline_init(
+def pipeline_auto(
+    package_id: str,
     *,
-    template: str = ...,
+    template: Optional[str] = ...,
     output_path: Optional[str] = ...,
     format: Optional[str] = ...,
     digest: Optional[str] = ...,
+    discover_latest: Optional[int] = ...,
     checkpoint: Optional[int] = ...,
-    include_analyze_step: bool = ...,
-    strict_replay: bool = ...,
     name: Optional[str] = ...,
-    package_id: Optional[str] = ...,
-    view_objects: List[str] = ...,
+    best_effort: bool = ...,
     force: bool = ...,
-) -> Dict[str, Any]: ...
+    walrus_network: str = ...,
+    walrus_caching_url: Optional[str] = ...,
+    walrus_aggregator_url: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Canonical alias for `workflow_auto`."""
 
 
-def workflow_auto(
+def pipeline_run(
+    spec_path: str,
+    *,
+    dry_run: bool = ...,
+    explain: bool = ...,
+    continue_on_error: bool = ...,
+    max_parallel: int = ...,
+    report_path: Optional[str] = ...,
+    rpc_url: str = ...,
+    walrus_network: str = ...,
+    walrus_caching_url: Optional[str] = ...,
+    walrus_aggregator_url: Optional[str] = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Canonical alias for `workflow_run`."""
+
+
+def pipeline_run_inline(
+    spec: Any,
+    *,
+    dry_run: bool = ...,
+    explain: bool = ...,
+    continue_on_error: bool = ...,
+    max_parallel: int = ...,
+    report_path: Optional[str] = ...,
+    rpc_url: str = ...,
+    walrus_network: str = ...,
+    walrus_caching_url: Optional[str] = ...,
+    walrus_aggregator_url: Optional[str] = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Canonical alias for `workflow_run_inline`."""
+
+
+def pipeline_watch(
+    spec_path: str,
     package_id: str,
     *,
-    template: Optional[str] = ...,
+    cursor_path: Optional[str] = ...,
+    interval_secs: int = ...,
+    max_checkpoints_per_poll: int = ...,
+    from_checkpoint: Optional[int] = ...,
+    max_polls: Optional[int] = ...,
+    continue_on_error: bool = ...,
+    max_parallel: int = ...,
+    report_dir: Optional[str] = ...,
+    rpc_url: str = ...,
+    walrus_network: str = ...,
+    walrus_caching_url: Optional[str] = ...,
+    walrus_aggregator_url: Optional[str] = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Canonical alias for `workflow_watch`."""
+
+
+def workflow_validate(spec_path: str) -> Dict[str, Any]:
+    """Validate a typed workflow spec (JSON or YAML) and return step counts."""
+
+
+def workflow_init(
+    *,
+    template: str = ...,
     output_path: Optional[str] = ...,
     format: Optional[str] = ...,
     digest: Optional[str] = ...,
-    discover_latest: Optional[int] = ...,
     checkpoint: Optional[int] = ...,
+    include_analyze_step: bool = ...,
+    strict_replay: bool = ...,
     name: Optional[str] = ...,
-    best_effort: bool = ...,
+    package_id: Optional[str] = ...,
+    view_objects: List[str] = ...,
     force: bool = ...,
-    walrus_network: str = ...,
-    walrus_caching_url: Optional[str] = ...,
-    walrus_aggregator_url: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Generate a typed workflow spec from a built-in template."""
 
 
-def pipeline_auto(
+def workflow_auto(
     package_id: str,
     *,
     template: Optional[str] = ...,
@@ -253,63 +609,94 @@ def pipeline_auto(
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Auto-generate a draft adapter workflow from a package id.
+
+    This mirrors CLI `workflow auto` behavior:
+    - dependency closure validation (fail closed unless `best_effort=True`)
+    - template inference from module names (or explicit override)
+    - scaffold-only output when replay seed is unavailable
+    - replay-capable output with `digest` or `discover_latest`
+    """
 
 
 def workflow_run(
     spec_path: str,
     *,
     dry_run: bool = ...,
+    explain: bool = ...,
     continue_on_error: bool = ...,
+    max_parallel: int = ...,
     report_path: Optional[str] = ...,
     rpc_url: str = ...,
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Run a typed workflow spec natively via Python bindings.
 
+    Supports replay, analyze_replay, and command steps without shelling out to
+    `sui-sandbox pipeline run` (compatibility alias: `workflow run`).
 
-def pipeline_run(
-    spec_path: str,
-    *,
-    dry_run: bool = ...,
-    continue_on_error: bool = ...,
-    report_path: Optional[str] = ...,
-    rpc_url: str = ...,
-    walrus_network: str = ...,
-    walrus_caching_url: Optional[str] = ...,
-    walrus_aggregator_url: Optional[str] = ...,
-    verbose: bool = ...,
-) -> Dict[str, Any]: ...
+    `explain=True` returns the resolved plan instead of running: per step, the
+    effective source/profile (with the env vars the profile applies), each
+    setting's origin (`step`/`defaults`/`builtin`), the target and any Walrus
+    discovery the run would perform (not performed), the interpolated command,
+    and `problems` that would fail the step.
+
+    Steps run as a dependency graph (`depends_on`); `max_parallel` bounds how
+    many independent steps run at once. Reports keep spec order.
+    """
 
 
 def workflow_run_inline(
     spec: Any,
     *,
     dry_run: bool = ...,
+    explain: bool = ...,
     continue_on_error: bool = ...,
+    max_parallel: int = ...,
     report_path: Optional[str] = ...,
     rpc_url: str = ...,
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Run a typed workflow spec directly from an in-memory Python object (dict/list).
 
+    This avoids writing temporary spec files for ad-hoc or notebook workflows.
+    `explain=True` returns the resolved plan without executing, and `max_parallel`
+    runs independent steps concurrently (see `workflow_run`).
+    """
 
-def pipeline_run_inline(
-    spec: Any,
+
+def workflow_watch(
+    spec_path: str,
+    package_id: str,
     *,
-    dry_run: bool = ...,
+    cursor_path: Optional[str] = ...,
+    interval_secs: int = ...,
+    max_checkpoints_per_poll: int = ...,
+    from_checkpoint: Optional[int] = ...,
+    max_polls: Optional[int] = ...,
     continue_on_error: bool = ...,
-    report_path: Optional[str] = ...,
+    max_parallel: int = ...,
+    report_dir: Optional[str] = ...,
     rpc_url: str = ...,
     walrus_network: str = ...,
     walrus_caching_url: Optional[str] = ...,
     walrus_aggregator_url: Optional[str] = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Re-run a workflow spec for each new Walrus transaction touching `package_id`.
+
+    Each poll scans checkpoints after the cursor (`<spec>.watch.json` by default),
+    skips digests already run, and retargets the spec's replay/analyze_replay steps
+    at every new match. Returns a summary of the runs. With `max_polls=None` this
+    blocks until the process is stopped.
+    """
 
 
 def fetch_object_bcs(
@@ -318,7 +705,68 @@ def fetch_object_bcs(
     version: Optional[int] = ...,
     endpoint: Optional[str] = ...,
     api_key: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Fetch object BCS via gRPC, optionally pinned to a historical version.
+
+    Useful for constructing deterministic `call_view_function` object inputs.
+    """
+
+
+async def fetch_object_bcs_async(
+    object_id: str,
+    *,
+    version: Optional[int] = ...,
+    endpoint: Optional[str] = ...,
+    api_key: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Async variant of `fetch_object_bcs`; takes the same arguments.
+
+    Returns: Awaitable resolving to the `fetch_object_bcs` result dict.
+    """
+
+
+def list_dynamic_fields(
+    parent_id: str,
+    *,
+    checkpoint: Optional[int] = ...,
+    limit: Optional[int] = ...,
+    decode: bool = ...,
+    recursive: bool = ...,
+    max_depth: int = ...,
+    endpoint: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """List every dynamic field of a parent object with key and value BCS.
+
+    Pages through the parent's fields via GraphQL, optionally at `checkpoint`.
+    With `limit`, stops after that many entries and sets `truncated` when more
+    fields remain. With `decode`, keys and values are decoded field by field
+    using struct layouts from the packages that define their types. With
+    `recursive`, tables and bags stored as values are listed under `nested`,
+    up to `max_depth` levels deep.
+    """
+
+
+def fetch_historical_package_bytecodes(
+    package_ids: List[str],
+    *,
+    type_refs: List[str] = ...,
+    checkpoint: Optional[int] = ...,
+    endpoint: Optional[str] = ...,
+    api_key: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Fetch historical package bytecodes with transitive dependency resolution.
+
+    Standalone — no CLI binary needed.
+
+    Args:
+        package_ids: Root package IDs to fetch
+        type_refs: Optional type strings to infer additional package roots
+        checkpoint: Optional checkpoint to pin historical package versions
+        endpoint: Optional gRPC endpoint override
+        api_key: Optional gRPC API key override
+
+    Returns: Dict with packages (pkg_id -> [base64 module bytes]), count, endpoint_used
+    """
 
 
 def import_state(
@@ -328,20 +776,170 @@ def import_state(
     objects: Optional[str] = ...,
     packages: Optional[str] = ...,
     cache_dir: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+    format: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Import replay data files into a local replay cache directory.
+
+    Args:
+        state: Replay state file (JSON, or a binary snapshot from `export_state`)
+        transactions: Transactions input file (JSON/JSONL/CSV)
+        objects: Objects input file (JSON/JSONL/CSV)
+        packages: Packages input file (JSON/JSONL/CSV)
+        cache_dir: Cache directory (default: `~/.sui-sandbox/cache/local`)
+        format: Format of `state` — "auto" (default), "json" or "binary"
+    """
+
+
+def export_state(
+    state: str,
+    output: str,
+    *,
+    digest: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Convert a replay state file into a binary snapshot.
+
+    Snapshots hold the same states as JSON replay-state files as zstd-compressed BCS
+    behind a versioned header, and load much faster. Every API that takes a replay-state
+    file (`replay(state_file=...)`, `import_state(state=...)`) accepts them.
+
+    Args:
+        state: Replay state file (JSON or snapshot)
+        output: Snapshot file to write
+        digest: Export only this transaction from a multi-state file
+
+    Returns: dict with `output`, `states`, `digests` and `bytes`
+    """
+
+
+def cache_stats(cache_dir: Optional[str] = ...) -> Dict[str, Any]:
+    """Report counts and sizes of cached states/objects/packages in a local replay cache.
+
+    Args:
+        cache_dir: Cache directory (default: `~/.sui-sandbox/cache/local`)
 
+    Returns: Dict with `states`, `objects`, `packages`, byte totals and `unreadable` files
+    """
 
-def deserialize_transaction(raw_bcs: bytes) -> Dict[str, Any]: ...
 
+def cache_clear(
+    cache_dir: Optional[str] = ...,
+    *,
+    older_than_days: Optional[float] = ...,
+    digest: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Prune cached replay states from a local replay cache.
+
+    With no filters every cached state is removed; filters combine with AND.
+
+    Args:
+        cache_dir: Cache directory (default: `~/.sui-sandbox/cache/local`)
+        older_than_days: Only remove states last written at least this many days ago
+        digest: Only remove the state for this transaction digest
+
+    Returns: Dict with `removed` entries (digest/path/bytes), `bytes_removed`, `states_remaining`
+    """
+
+
+def deserialize_transaction(raw_bcs: bytes) -> Dict[str, Any]:
+    """Deserialize transaction BCS bytes into structured replay transaction JSON."""
+
+
+def deserialize_package(bcs: bytes) -> Dict[str, Any]:
+    """Deserialize package BCS bytes into structured package JSON."""
 
-def deserialize_package(bcs: bytes) -> Dict[str, Any]: ...
+
+def compute_package_id(
+    modules: Any,
+    *,
+    tx_digest: Optional[str] = ...,
+    tx_bytes: Optional[bytes] = ...,
+    ids_created: int = ...,
+) -> Dict[str, Any]:
+    """Compute the package id Sui assigns to newly published bytecode.
+
+    `modules` is `List[bytes]` or `List[str]` (base64); every module must deserialize
+    and be compiled at the `0x0` self-address placeholder. The id is
+    `derive_id(tx_digest, ids_created)`, so it depends on the whole publishing
+    transaction (sender, gas, inputs), not on the sender alone: pass either the
+    `tx_digest` or the unsigned `tx_bytes` (TransactionData BCS) to derive it from.
+    `ids_created` counts object ids the transaction created before the Publish
+    command (0 when Publish comes first).
+
+    Upgrades derive the new storage id the same way, but the package keeps its
+    original (runtime) id, which is what type tags continue to reference.
+    """
 
 
 def fetch_package_bytecodes(
     package_id: str,
     *,
     resolve_deps: bool = ...,
-) -> Dict[str, Any]: ...
+    include_framework: bool = ...,
+    checkpoint: Optional[int] = ...,
+) -> Dict[str, Any]:
+    """Fetch package bytecodes via GraphQL, optionally resolving transitive dependencies.
+
+    Standalone — no CLI binary needed.
+
+    Args:
+        package_id: The package to fetch
+        resolve_deps: If True, recursively fetch all dependency packages
+        include_framework: If True, also include framework package bytecode
+            (0x1/0x2/0x3 and any framework deps) so the output is self-contained
+        checkpoint: Optional checkpoint to pin every fetched package (framework
+            included) to the version live at that checkpoint
+
+    Returns: Dict with packages (pkg_id -> [base64 module bytes]) and count,
+             plus graphql_retries (retries, rate_limited, exhausted) from the
+             client's transient-failure retry policy
+    """
+
+
+def stream_package_modules(
+    package_id: str,
+    *,
+    endpoint: Optional[str] = ...,
+) -> PackageModuleIterator:
+    """Stream a package's modules as `(name, bytecode)` tuples.
+
+    Pages are fetched from GraphQL lazily as the iterator advances, so decoding or
+    analysing one module overlaps with fetching the rest and memory stays bounded
+    to one page (50 modules). `fetch_package_bytecodes` remains the all-at-once form.
+    """
+
+
+def subscribe_checkpoints(
+    from_seq: Optional[int] = ...,
+    *,
+    endpoint: Optional[str] = ...,
+    api_key: Optional[str] = ...,
+    buffer: int = ...,
+    max_reconnects: Optional[int] = ...,
+    ptb_only: bool = ...,
+) -> CheckpointSubscription:
+    """Tail checkpoints from a live gRPC fullnode.
+
+    A background task keeps the subscription alive: it reconnects with exponential
+    backoff, skips checkpoints already yielded, and backfills any gap (after a
+    reconnect, or when `from_seq` is behind the tip) so checkpoints arrive in order.
+    At most `buffer` checkpoints are held ahead of the consumer; a slow consumer
+    pauses the server stream instead of growing memory.
+
+    Args:
+        from_seq: First checkpoint to yield (default: the live tip). Use a previous
+            subscription's `cursor()` to resume
+        endpoint: gRPC endpoint (default: mainnet fullnode)
+        api_key: Optional API key for the endpoint
+        buffer: Checkpoints buffered ahead of the consumer (default: 64)
+        max_reconnects: Consecutive failed reconnects before giving up; None retries
+            forever (default: 10)
+        ptb_only: Only list programmable transactions in each checkpoint
+
+    Returns: Iterator of dicts with checkpoint, digest, epoch, timestamp_ms,
+             transaction_count, object_count and transactions (digest, sender,
+             is_ptb, commands, inputs, status, gas_budget, gas_price). A checkpoint
+             that cannot be backfilled, or giving up on reconnects, raises.
+    """
 
 
 def prepare_package_context(
@@ -349,49 +947,167 @@ def prepare_package_context(
     *,
     resolve_deps: bool = ...,
     output_path: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+    include_framework: bool = ...,
+    checkpoint: Optional[int] = ...,
+) -> Dict[str, Any]:
+    """Prepare a generic package context by fetching package bytecodes (+deps by default).
+
+    This is step 1 of a simple two-step developer flow:
+    1) `prepare_package_context(...)`
+    2) `replay_transaction(...)`
+
+    Args:
+        package_id: Root package id (0x...)
+        resolve_deps: If True, fetch transitive dependency closure (default: True)
+        output_path: Optional JSON path to persist the context payload
+        include_framework: If True, also store framework package bytecode so the
+            context can be replayed without a network connection
+        checkpoint: Optional checkpoint to pin every package (framework included) to
+
+    Returns: Dict with `package_id`, `packages`, and `count`
+    """
+
+
+def context_prepare(
+    package_id: str,
+    *,
+    resolve_deps: bool = ...,
+    output_path: Optional[str] = ...,
+    include_framework: bool = ...,
+    checkpoint: Optional[int] = ...,
+) -> Dict[str, Any]:
+    """Canonical alias for `prepare_package_context`."""
+
+
+def protocol_prepare(
+    *,
+    protocol: str = ...,
+    package_id: Optional[str] = ...,
+    resolve_deps: bool = ...,
+    output_path: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Protocol-first package-context preparation.
+
+    `package_id` is required for all non-generic protocols.
+    """
+
+
+def adapter_prepare(
+    *,
+    protocol: str = ...,
+    package_id: Optional[str] = ...,
+    resolve_deps: bool = ...,
+    output_path: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Canonical alias for `protocol_prepare`."""
+
+
+def parse_type(type_str: str) -> str:
+    """Parse a Move type string and return its canonical form.
+
+    Addresses are normalized to short `0x`-prefixed hex at every nesting level, so
+    `0x0000...0002::coin::Coin<0x2::sui::SUI>` and `0x2::coin::Coin<0x02::sui::SUI>`
+    both become `0x2::coin::Coin<0x2::sui::SUI>`.
+
+    Args:
+        type_str: Move type string (primitive, vector, or struct with type arguments)
+
+    Returns: Canonical type string. Raises on malformed input.
+    """
+
+
+def canonicalize_type(type_str: str, *, long_addresses: bool = ...) -> str:
+    """Canonicalize a Move type string.
+
+    Same round-trip as `parse_type`; with `long_addresses=True` every address is
+    rendered as full 32-byte hex, the form used in on-chain object types.
+
+    Args:
+        type_str: Move type string
+        long_addresses: Render addresses as 64 hex digits instead of the short form
+
+    Returns: Canonical type string. Raises on malformed input.
+    """
+
+
+def json_to_bcs(
+    type_str: str,
+    object_json: str,
+    package_bytecodes: List[bytes],
+) -> bytes:
+    """Convert Sui object JSON to BCS bytes using struct layouts from bytecode.
+
+    Standalone — no CLI binary needed.
+
+    Args:
+        type_str: Full Sui type string (e.g., "0x2::coin::Coin<0x2::sui::SUI>")
+        object_json: JSON string of the decoded object data
+        package_bytecodes: List of raw bytecode bytes for all needed package modules
+
+    Returns: BCS-encoded bytes
+    """
 
 
-def context_prepare(
-    package_id: str,
-    *,
-    resolve_deps: bool = ...,
-    output_path: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+def transaction_json_to_bcs(transaction_json: str) -> bytes:
+    """Convert Snowflake TRANSACTION_JSON (Sui TransactionData JSON) into raw transaction BCS bytes.
 
+    Accepts canonical Sui `TransactionData` JSON and Snowflake-style variants
+    (for example StructTag `type_args` and non-`0x` hex addresses).
+    """
 
-def protocol_prepare(
-    *,
-    protocol: str = ...,
-    package_id: Optional[str] = ...,
-    resolve_deps: bool = ...,
-    output_path: Optional[str] = ...,
-) -> Dict[str, Any]: ...
 
+def transaction_json_to_bcs_verbose(transaction_json: str) -> Dict[str, Any]:
+    """Convert TRANSACTION_JSON to BCS and report how the input was normalized.
 
-def adapter_prepare(
-    *,
-    protocol: str = ...,
-    package_id: Optional[str] = ...,
-    resolve_deps: bool = ...,
-    output_path: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+    Use this to debug exports that produce wrong BCS: every rewrite applied on the
+    way to canonical `TransactionData` JSON is listed, along with the fields that
+    looked like they needed one but did not match a known shape.
 
+    Returns: Dict with `bcs` (bytes), `normalizations` (each `path` as a JSON pointer,
+    `kind`, `before`, `after`) and `uncertain` (each `path`, `value`, `reason`)
+    """
 
-def fetch_historical_package_bytecodes(
-    package_ids: List[str],
-    *,
-    type_refs: List[str] = ...,
-    checkpoint: Optional[int] = ...,
-    endpoint: Optional[str] = ...,
-    api_key: Optional[str] = ...,
-) -> Dict[str, Any]: ...
 
+def transaction_json_to_bcs_batch(transaction_jsons: List[str]) -> List[Dict[str, Any]]:
+    """Convert many TRANSACTION_JSON payloads to raw transaction BCS in one native call.
 
-def json_to_bcs(type_str: str, object_json: str, package_bytecodes: List[bytes]) -> bytes: ...
+    Per-item failures do not fail the batch: each result is a dict with `index`, `ok`,
+    `bcs` (bytes, or None on error) and `error` (None on success), in input order.
+    """
 
 
-def transaction_json_to_bcs(transaction_json: str) -> bytes: ...
+def simulate_transaction(
+    tx: Any,
+    *,
+    checkpoint: Optional[int] = ...,
+    rpc_url: str = ...,
+    network: Optional[str] = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Dry-run unsigned `TransactionData` locally, without a fullnode.
+
+    The other half of `transaction_json_to_bcs`: fetches the transaction's input
+    objects as of `checkpoint` (latest if omitted) plus the packages it calls and
+    their dependencies via GraphQL, then executes it in the local VM under the
+    protocol version and reference gas price of that checkpoint's epoch. Gas
+    payment coins are not fetched; the sandbox synthesizes a gas coin. Framework
+    packages are the bundled ones.
+
+    Args:
+        tx: TransactionData as raw BCS bytes, base64 BCS string, JSON string, or dict
+            (the JSON shapes accepted by `transaction_json_to_bcs`)
+        checkpoint: Hydrate input objects and packages as of this checkpoint
+        rpc_url: Sui RPC endpoint; the GraphQL endpoint is derived from it
+        network: "mainnet", "testnet" or "devnet". Selects the network's full node (unless
+            `rpc_url` is set to another endpoint) and its GraphQL endpoint
+        verbose: Print hydration progress to stderr
+
+    Returns: Dict with sender, checkpoint, epoch, protocol_version (None when it could
+             not be resolved), gas_budget, gas_price, objects_loaded, packages_loaded,
+             effects (success, error, created/mutated/deleted ids, events, gas_used, ...),
+             gas_estimate (computation/storage costs, rebate, net_gas_cost,
+             within_budget; None without accurate gas) and warnings
+    """
 
 
 def call_view_function(
@@ -405,11 +1121,209 @@ def call_view_function(
     child_objects: Optional[Dict[str, List[Dict[str, Any]]]] = ...,
     historical_versions: Optional[Dict[str, int]] = ...,
     fetch_child_objects: bool = ...,
+    child_fetcher: Optional[Callable[[str, str], Optional[Tuple[str, bytes]]]] = ...,
     grpc_endpoint: Optional[str] = ...,
     grpc_api_key: Optional[str] = ...,
     package_bytecodes: Optional[Dict[str, Any]] = ...,
     fetch_deps: bool = ...,
-) -> Dict[str, Any]: ...
+    fetch_modules_only: bool = ...,
+    decode_returns: bool = ...,
+) -> Dict[str, Any]:
+    """Execute a view function via local Move VM.
+
+    Standalone — no CLI binary needed.
+
+    Args:
+        package_id: Package containing the view function
+        module: Module name
+        function: Function name
+        type_args: List of type argument strings (e.g., ["0x2::sui::SUI"])
+        object_inputs: List of dicts with keys: object_id, bcs_bytes, type_tag
+            optional: is_shared/mutable, by_value, or legacy owner
+            ("immutable"|"shared"|"address_owned"|"owned"), and arg_index
+            (argument position; default: objects first, then pure inputs).
+            Synthesized coin: {coin_balance, coin_type="0x2::sui::SUI", object_id=None}
+            builds a by-value Coin<coin_type> without real bytes.
+        pure_inputs: List of BCS-encoded pure argument bytes
+        child_objects: Dict mapping parent_id -> list of {child_id, bcs_bytes, type_tag}
+        historical_versions: Optional object_id -> version map for on-demand child fetches
+        fetch_child_objects: If True, fetch child objects on-demand via gRPC
+        child_fetcher: Optional callable (parent_id, child_id) -> (type_tag, bcs_bytes) | None,
+            consulted after child_objects and before the gRPC fetch
+        grpc_endpoint: Optional gRPC endpoint override for child fetches
+        grpc_api_key: Optional gRPC API key override for child fetches
+        package_bytecodes: Either:
+            - Dict[package_id -> list[module_bytes or module_base64]]
+            - Full payload returned by fetch_historical_package_bytecodes(...)
+        fetch_deps: If True, automatically resolve transitive deps via GraphQL
+        fetch_modules_only: With fetch_deps, fetch only the target module and the modules it
+            (transitively) references, one module at a time, instead of whole packages.
+            Cuts bandwidth for wide packages; each module comes with its package's
+            linkage table, so upgraded dependencies resolve to their storage ids
+        decode_returns: If True, add `decoded_returns`: per return value `{index, type_tag,
+            value, raw_base64, raw_hex}` decoded with the loaded struct layouts, so structs
+            become field dicts and `vector<T>` a list of decoded `T`
+
+    Returns: Dict with success, error, return_values, return_type_tags, gas_used,
+             child_fetches ({fetched, memo_hits} for on-demand gRPC child loads),
+             and `decoded_returns` (None if execution failed) when requested
+    """
+
+
+def call_view_functions(
+    package_id: str,
+    calls: List[Dict[str, Any]],
+    *,
+    shared_object_inputs: List[Dict[str, Any]] = ...,
+    child_objects: Optional[Dict[str, List[Dict[str, Any]]]] = ...,
+    historical_versions: Optional[Dict[str, int]] = ...,
+    fetch_child_objects: bool = ...,
+    child_fetcher: Optional[Callable[[str, str], Optional[Tuple[str, bytes]]]] = ...,
+    grpc_endpoint: Optional[str] = ...,
+    grpc_api_key: Optional[str] = ...,
+    package_bytecodes: Optional[Dict[str, Any]] = ...,
+    fetch_deps: bool = ...,
+    fetch_modules_only: bool = ...,
+    decode_returns: bool = ...,
+) -> List[Dict[str, Any]]:
+    """Execute several view functions against one shared object set.
+
+    The resolver, VM and child fetcher are built once and each call runs in order
+    with its own PTB: the shared object inputs first, then the call's pure inputs.
+    A call that fails (bad arguments, missing module, VM error) is reported in its
+    own result and does not stop the others.
+
+    Args:
+        package_id: Default package for calls that do not set their own
+        calls: List of dicts with keys: module, function, optional type_args
+            (list of type strings), pure_inputs (list of BCS bytes) and package_id
+        shared_object_inputs: Object inputs passed to every call, same shape as
+            call_view_function's object_inputs
+        child_objects, historical_versions, fetch_child_objects, child_fetcher,
+        grpc_endpoint, grpc_api_key, package_bytecodes, fetch_deps,
+        fetch_modules_only, decode_returns: As in call_view_function, shared by all calls
+
+    Returns: List of result dicts in call order, each shaped like call_view_function's
+             result plus `index`. A call that could not be executed has success=False,
+             its error message, and empty return_values.
+    """
+
+
+async def call_view_function_async(
+    package_id: str,
+    module: str,
+    function: str,
+    *,
+    type_args: List[str] = ...,
+    object_inputs: List[Dict[str, Any]] = ...,
+    pure_inputs: List[bytes] = ...,
+    child_objects: Optional[Dict[str, List[Dict[str, Any]]]] = ...,
+    historical_versions: Optional[Dict[str, int]] = ...,
+    fetch_child_objects: bool = ...,
+    child_fetcher: Optional[Callable[[str, str], Optional[Tuple[str, bytes]]]] = ...,
+    grpc_endpoint: Optional[str] = ...,
+    grpc_api_key: Optional[str] = ...,
+    package_bytecodes: Optional[Dict[str, Any]] = ...,
+    fetch_deps: bool = ...,
+    fetch_modules_only: bool = ...,
+    decode_returns: bool = ...,
+) -> Dict[str, Any]:
+    """Async variant of `call_view_function`; takes the same arguments.
+
+    Returns: Awaitable resolving to the `call_view_function` result dict.
+    """
+
+
+async def call_view_functions_async(
+    package_id: str,
+    calls: List[Dict[str, Any]],
+    *,
+    shared_object_inputs: List[Dict[str, Any]] = ...,
+    child_objects: Optional[Dict[str, List[Dict[str, Any]]]] = ...,
+    historical_versions: Optional[Dict[str, int]] = ...,
+    fetch_child_objects: bool = ...,
+    child_fetcher: Optional[Callable[[str, str], Optional[Tuple[str, bytes]]]] = ...,
+    grpc_endpoint: Optional[str] = ...,
+    grpc_api_key: Optional[str] = ...,
+    package_bytecodes: Optional[Dict[str, Any]] = ...,
+    fetch_deps: bool = ...,
+    fetch_modules_only: bool = ...,
+    decode_returns: bool = ...,
+) -> List[Dict[str, Any]]:
+    """Async variant of `call_view_functions`; takes the same arguments.
+
+    Returns: Awaitable resolving to the `call_view_functions` result list.
+    """
+
+
+def plan_child_fetches(
+    package_id: str,
+    module: str,
+    function: str,
+    *,
+    type_args: List[str] = ...,
+    package_bytecodes: Optional[Dict[str, Any]] = ...,
+    fetch_deps: bool = ...,
+) -> Dict[str, Any]:
+    """Predict which dynamic-field children a view function will touch, without executing it.
+
+    Statically walks the function's bytecode (and everything it calls) for
+    `dynamic_field::*` / `dynamic_object_field::*` calls and substitutes the
+    concrete `type_args`, so callers can preload children before
+    `call_view_function` instead of discovering them one abort at a time.
+
+    Args:
+        package_id: Package containing the view function
+        module: Module name
+        function: Function name
+        type_args: List of type argument strings (e.g., ["0x2::sui::SUI"])
+        package_bytecodes: Same shapes accepted by call_view_function
+        fetch_deps: If True and package_bytecodes is omitted, resolve the package
+            and its transitive deps via GraphQL
+
+    Returns: Dict with runtime_id, accesses (key_type, value_type, kind, mutating,
+             requires_existing_child, confidence, source_function, transitive),
+             predicted_child_fetches, unresolved, warnings
+    """
+
+
+def build_call_inputs(
+    package_id: str,
+    module: str,
+    function: str,
+    args_json: Any,
+    type_args: List[str] = ...,
+    *,
+    checkpoint: Optional[int] = ...,
+    rpc_url: str = ...,
+    network: Optional[str] = ...,
+) -> Dict[str, Any]:
+    """Build ready-to-execute `call_view_function` inputs from JSON arguments.
+
+    Looks up the function ABI (package + transitive deps via GraphQL), checks the
+    type and value argument counts, converts each pure argument to BCS via the
+    bytecode layouts, and fetches each object argument by ID (at `checkpoint` if
+    given), checking its type against the parameter. Errors name the offending
+    argument index and expected type. A trailing `TxContext` parameter is omitted.
+
+    Args:
+        package_id: Package containing the function
+        module: Module name
+        function: Function name
+        args_json: List of arguments, or a JSON string of one, in parameter order.
+            Objects are given as object ID strings; pure values use the same JSON
+            shapes as `json_to_bcs` (numbers or strings for integers, hex/base64 or
+            lists for `vector<u8>`, field dicts for structs)
+        type_args: List of type argument strings (e.g., ["0x2::sui::SUI"])
+        checkpoint: Fetch packages and object inputs as of this checkpoint instead of latest
+        rpc_url: Sui RPC endpoint; the GraphQL endpoint is derived from it
+        network: "mainnet", "testnet" or "devnet". Selects the network's full node (unless
+            `rpc_url` is set to another endpoint) and its GraphQL endpoint
+
+    Returns: Dict with object_inputs (each with its arg_index) and pure_inputs
+             (pass straight to call_view_function), arguments (index, type_tag,
+             kind, plus object_id/version for objects), type_args, takes_tx_context
+    """
 
 
 def historical_view_from_versions(
@@ -425,7 +1339,49 @@ def historical_view_from_versions(
     fetch_child_objects: bool = ...,
     grpc_endpoint: Optional[str] = ...,
     grpc_api_key: Optional[str] = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Execute a generic historical Move view function from a versions snapshot.
+
+    Protocol-specific logic (object selection, type args, decoding) should be
+    authored by callers on top of this generic primitive.
+    """
+
+
+def historical_series_from_points(
+    *,
+    points: List[Dict[str, Any]],
+    package_id: str,
+    module: str,
+    function: str,
+    required_objects: List[str],
+    type_args: List[str] = ...,
+    package_roots: List[str] = ...,
+    type_refs: List[str] = ...,
+    fetch_child_objects: bool = ...,
+    schema: Optional[List[Dict[str, Any]]] = ...,
+    command_index: int = ...,
+    grpc_endpoint: Optional[str] = ...,
+    grpc_api_key: Optional[str] = ...,
+    max_concurrency: int = ...,
+) -> Dict[str, Any]:
+    """Execute a historical view request across labeled checkpoint/version points.
+
+    `points` is a JSON-serializable list of:
+    `{ "checkpoint": int, "versions": {"0x...": version}, "label": str|None, "metadata": any|None }`.
+    """
+
+
+def historical_series_from_files(
+    *,
+    request_file: str,
+    series_file: str,
+    schema_file: Optional[str] = ...,
+    command_index: int = ...,
+    grpc_endpoint: Optional[str] = ...,
+    grpc_api_key: Optional[str] = ...,
+    max_concurrency: int = ...,
+) -> Dict[str, Any]:
+    """Execute historical series directly from request/series/schema file inputs."""
 
 
 def historical_decode_return_u64(
@@ -433,21 +1389,33 @@ def historical_decode_return_u64(
     *,
     command_index: int = ...,
     value_index: int,
-) -> Optional[int]: ...
+) -> Optional[int]:
+    """Decode one u64 return value from `historical_view_from_versions` output.
+
+    Returns `None` when the execution failed or the return index is missing.
+    """
 
 
 def historical_decode_return_u64s(
     result: Any,
     *,
     command_index: int = ...,
-) -> Optional[List[Optional[int]]]: ...
+) -> Optional[List[Optional[int]]]:
+    """Decode all return values from a command into `u64` values where possible.
+
+    Returns `None` when the execution failed or command return values are missing.
+    """
 
 
 def historical_decode_returns_typed(
     result: Any,
     *,
     command_index: int = ...,
-) -> Optional[List[Dict[str, Any]]]: ...
+) -> Optional[List[Dict[str, Any]]]:
+    """Decode all return values from a historical-view command into typed JSON values.
+
+    Returns `None` when execution failed or command return values are missing.
+    """
 
 
 def historical_decode_with_schema(
@@ -455,7 +1423,46 @@ def historical_decode_with_schema(
     schema: List[Dict[str, Any]],
     *,
     command_index: int = ...,
-) -> Optional[Dict[str, Any]]: ...
+) -> Optional[Dict[str, Any]]:
+    """Decode historical-view return values into a named object with field schema.
+
+    `schema` must be a JSON-serializable list of:
+    `{ "index": int, "name": str, "type_hint": str|None, "scale": float|None }`.
+    """
+
+
+def protocol_decode(
+    protocol: str,
+    result: Any,
+    *,
+    decoder: Optional[str] = ...,
+    command_index: int = ...,
+) -> Optional[Dict[str, Any]]:
+    """Decode historical-view return values with a built-in protocol decoder.
+
+    `protocol` is matched case-insensitively (e.g. `"deepbook"`, `"cetus"`).
+    `decoder` selects a `module::function` (or bare function name) and may be
+    omitted when the protocol has a single decoder.
+
+    Returns `{protocol, decoder, decoded}`, or `None` when execution failed or no
+    command return values are available.
+    """
+
+
+def protocol_decoders() -> Dict[str, Any]:
+    """List built-in protocol decoders.
+
+    Returns: Dict of protocol name -> list of `{module, function, description, fields}`.
+    """
+
+
+def protocol_adapters() -> List[Dict[str, Any]]:
+    """List protocol adapters: the built-ins plus definitions loaded from the adapter
+    config dir (`SUI_SANDBOX_PROTOCOLS_DIR`, default `~/.sui-sandbox/protocols`).
+
+    Returns: List of `{name, description, package_ids, required_objects, template,
+    keywords, source}`.
+    """
 
 
 def fuzz_function(
@@ -472,17 +1479,58 @@ def fuzz_function(
     max_vector_len: int = ...,
     dry_run: bool = ...,
     fetch_deps: bool = ...,
-) -> Dict[str, Any]: ...
+    coverage: bool = ...,
+    corpus_path: Optional[str] = ...,
+    invariants: List[str] = ...,
+) -> Dict[str, Any]:
+    """Fuzz a Move function with randomly generated inputs.
+
+    Standalone — no CLI binary needed.
+
+    Generates random valid inputs for a Move function's pure parameter types
+    and executes it repeatedly against the local VM. Reports aborts, errors,
+    gas exhaustion, and gas usage profiles.
+
+    Args:
+        package_id: Package address (e.g., "0x2")
+        module: Module name
+        function: Function name
+        iterations: Number of fuzz iterations (default: 100)
+        seed: Random seed for reproducibility (default: random)
+        sender: Sender address (default: "0x0")
+        gas_budget: Gas budget per execution (default: 50_000_000_000)
+        type_args: Type argument strings (e.g., ["0x2::sui::SUI"])
+        fail_fast: Stop on first abort/error (default: False)
+        max_vector_len: Max length for generated vectors (default: 32)
+        dry_run: Only analyze signature, don't execute (default: False)
+        fetch_deps: Auto-resolve transitive deps via GraphQL (default: True)
+        coverage: Coverage-guided mode; mutate inputs that reach new basic
+            blocks (default: False)
+        corpus_path: Corpus JSON file loaded before and saved after the run so
+            campaigns can resume; implies coverage (default: None)
+        invariants: Move functions ("0xPKG::module::function") that must hold
+            after every successful iteration. Each is called with the target's
+            inputs followed by its return values and is violated if it aborts
+            or returns False (default: [])
+
+    Returns: Dict with target, total_iterations, seed, outcomes, gas_profile,
+             interesting_cases, etc. (plus coverage for coverage-guided runs).
+             Violations are listed in outcomes.invariant_violations.
+             If dry_run=True, returns classification only.
+    """
 
 
 def replay(
     digest: Optional[str] = ...,
     *,
     rpc_url: str = ...,
+    network: Optional[str] = ...,
     source: str = ...,
     checkpoint: Optional[int] = ...,
     state_file: Optional[str] = ...,
+    state_format: Optional[str] = ...,
     context_path: Optional[str] = ...,
+    context_overrides: bool = ...,
     cache_dir: Optional[str] = ...,
     profile: Optional[str] = ...,
     fetch_strategy: Optional[str] = ...,
@@ -497,8 +1545,237 @@ def replay(
     synthesize_missing: bool = ...,
     self_heal_dynamic_fields: bool = ...,
     analyze_mm2: bool = ...,
+    fetch_modules_only: bool = ...,
+    object_type_overrides: Optional[Dict[str, Optional[bytes]]] = ...,
+    epoch_override: Optional[int] = ...,
+    protocol_version: Optional[int] = ...,
+    package_overrides: Optional[Dict[str, List[bytes]]] = ...,
+    ownership_hints: Optional[Dict[str, str]] = ...,
+    expect_effects_hash: Optional[str] = ...,
+    strict_object_digests: bool = ...,
+    require_comparison: bool = ...,
+    expected_effects: Optional[Any] = ...,
+    stub_unknown_natives: bool = ...,
+    gas_table: Optional[Any] = ...,
+    use_result_cache: bool = ...,
+    result_cache_dir: Optional[str] = ...,
+    offline: bool = ...,
+    apply_checkpoint_prologue: bool = ...,
+    instruction_trace: Optional[str] = ...,
+    instruction_trace_stack: bool = ...,
+    decode_events: bool = ...,
+    diff_objects: bool = ...,
+    gas_profile: bool = ...,
+    trace: bool = ...,
+    trace_depth: Optional[int] = ...,
+    reconcile: bool = ...,
+    record_requests: Optional[str] = ...,
+    summary_only: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Replay a historical Sui transaction locally with the Move VM.
+
+    Standalone — no CLI binary needed. All data is fetched directly.
+
+    By default, executes the transaction in the local Move VM and returns
+    execution results. Use `analyze_only=True` to inspect state hydration
+    without executing.
+
+    When `checkpoint` is provided, uses Walrus as data source (no API key needed).
+    Otherwise uses gRPC/hybrid (requires `SUI_GRPC_API_KEY` env var). With
+    `source="jsonrpc"`, state is hydrated from `rpc_url` via plain JSON-RPC.
+
+    Args:
+        digest: Transaction digest to replay
+        rpc_url: Sui RPC endpoint
+        network: "mainnet", "testnet" or "devnet". Selects the network's full node (unless
+            `rpc_url` is set to another endpoint), GraphQL, gRPC and Walrus endpoints; devnet
+            has no Walrus archive, so `checkpoint`/`source="walrus"` fail there
+        source: Data source — "hybrid", "grpc", "walrus", or "jsonrpc"
+        checkpoint: Walrus checkpoint number (auto-uses walrus, no API key needed)
+        state_format: Format of `state_file` — "auto" (default: binary snapshot if the file
+            starts with the snapshot header, JSON otherwise), "json" or "binary"
+        allow_fallback: Allow fallback to secondary data sources
+        context_path: Optional prepared package context JSON from prepare_package_context(...)
+        context_overrides: Let context_path packages replace fetched packages with the same id
+            (default: context only fills packages the fetch did not return). Replays with a
+            context report `package_sources` (`context`, `context_replaced`, `fetched` ids)
+        profile: Runtime defaults profile ("safe"|"balanced"|"fast")
+        fetch_strategy: Dynamic-field fetch strategy ("eager"|"full")
+        vm_only: Disable fallback paths and force VM-only behavior
+        prefetch_depth: Dynamic field prefetch depth
+        prefetch_limit: Dynamic field prefetch limit per parent
+        auto_system_objects: Auto-inject Clock/Random when missing
+        no_prefetch: Disable dynamic field prefetch
+        compare: Compare local execution with on-chain effects
+        analyze_only: Skip VM execution, just inspect state hydration
+        synthesize_missing: Retry with synthetic object bytes when inputs are missing
+        self_heal_dynamic_fields: Enable dynamic field child fetchers during VM execution
+        analyze_mm2: Build MM2 type-model diagnostics (analyze-only mode)
+        fetch_modules_only: Fetch only the dependency modules reachable from the
+            transaction's Move calls, one module at a time, instead of whole packages
+            (`dependency_fetch_mode="graphql_module_closure"`)
+        object_type_overrides: Map of type tag -> None (drop) or BCS bytes (substitute)
+            applied to matching objects before execution, for fault-injection replays
+        epoch_override: Execute as if in this epoch (protocol version is kept; a mismatch
+            with the epoch's on-chain protocol version is reported as an `[epoch]` warning)
+        protocol_version: Execute with this protocol version's framework, feature flags and
+            gas schedule. By default the transaction epoch's protocol version is used
+            (looked up over GraphQL for Walrus and JSON-RPC sources). The effective
+            version is reported under `execution_path.protocol_version`.
+        package_overrides: Map of package id -> list of module bytecode substituted for the
+            package before execution (id, version, and linkage are kept). Modules must be
+            compiled at the package's original id. Reported under `package_overrides`.
+        ownership_hints: Map of object id -> "shared" / "owned" / "immutable" that overrides
+            the inferred ownership of the object's transaction input before execution. Making
+            an input shared needs "shared:<initial_shared_version>:mutable|readonly".
+            Reported under `ownership_hints`; hints that contradict the hydrated object
+            metadata are flagged `contradicted` and raise an `[ownership]` warning
+        expect_effects_hash: Expected `local_effects_fingerprint` from a recorded baseline run.
+            Sets `verified` and a `verification` block (with a field `diff` against on-chain
+            effects on mismatch); combine with `state_file` for offline regression checks
+        strict_object_digests: Fail when a supplied object's digest differs from the
+            transaction's object reference (default: report a `[digest]` warning)
+        require_comparison: With `compare=True`, fail when no on-chain effects were available
+            to compare against instead of reporting `comparison_unavailable`
+        expected_effects: With `compare=True`, on-chain effects (dict or JSON string, in the
+            state-file `transaction.effects` schema) to compare against instead of the effects
+            the fetched or loaded transaction carries, e.g. from your own indexer. Execution
+            still uses the transaction's own effects. Reported under `expected_effects`
+        stub_unknown_natives: Degraded analysis mode: natives that cannot run locally return
+            zero values instead of aborting. Each is listed under `stubbed_natives`; results
+            after the first stubbed call are not faithful
+        gas_table: Custom gas cost table (dict or JSON string) with optional
+            `instruction_tiers` / `stack_height_tiers` / `stack_size_tiers` (threshold -> cost)
+            and `natives` (native cost name -> cost). Unspecified entries keep the protocol
+            default; coverage is reported under `execution_path.gas_table`, with entries the
+            replay cannot charge under `unapplied`
+        use_result_cache: Memoize the executed output keyed by the replay state's content
+            hash plus every execution-affecting parameter; an identical replay returns the
+            stored output without running the VM. Reported under `result_cache` (`hit`, `key`)
+        result_cache_dir: Result cache directory (default: `~/.sui-sandbox/cache/results`)
+        offline: Strict no-network mode. Requires `state_file` or `cache_dir`; every package
+            and input object must come from the state or `context_path`. Anything that would
+            be fetched fails with an `offline mode: cannot fetch ...` error listing it
+        apply_checkpoint_prologue: Before executing, apply the output objects of the system
+            transactions preceding the target in its checkpoint (consensus commit prologue,
+            authenticator state, randomness), reconstructed from the Walrus checkpoint; the
+            Clock takes the consensus commit timestamp. Reported under `checkpoint_prologue`
+        instruction_trace: Record an instruction-level VM trace and write it to this path as
+            JSON lines (`enter` / `instruction` / `exit` / `error` entries naming module,
+            function and bytecode offset). Large and slow; off by default. Counts are
+            reported under `instruction_trace`
+        instruction_trace_stack: With `instruction_trace`, also record the operand-stack
+            depth and the values each instruction pushes
+        decode_events: Decode each emitted event's BCS payload with its struct layout from
+            the loaded modules and add it as `fields` to the `effects.events` entry (which
+            already carries `type` and `sender`); undecodable events get `decode_error`
+        diff_objects: Decode each mutated object before and after execution with its struct
+            layout and report a field-level diff under `object_diffs`
+        gas_profile: Attribute gas to each PTB command and Move function and report it
+            under `gas_profile` (computation/storage totals, per-command costs and the
+            10 functions with the most self gas)
+        trace: Record the Move call tree of every PTB command and return it under `trace`:
+            one entry per command with `calls`, each call carrying `module`, `function`,
+            `type_args`, rendered `args` and `returns`, `gas_used`, `error` and `children`
+        trace_depth: With `trace`, keep calls up to this depth (the command's entry
+            function is depth 1); deeper calls are only counted in `omitted_calls`
+        reconcile: Compare local and on-chain effects object by object (IDs, versions,
+            owners) and report each mismatch under `reconciliation` with a `kind` such as
+            `missing-create`, `spurious-mutation` or `version-skew`, plus per-kind `counts`
+        record_requests: Record every gRPC/GraphQL/Walrus request and its response, with the
+            replay options, to this cassette file (API keys redacted), written even if the
+            replay fails. Re-run it without network via `replay_from_cassette(path)`.
+            Reported under `cassette`
+        summary_only: Return only `digest`, `local_success`, `gas_used`,
+            `failed_command_index`, a short `error`, `category` and `fidelity` (for bulk runs)
+        verbose: Enable verbose logging to stderr
+
+    Returns: dict replay envelope. In `analyze_only=True` mode, `analysis` contains
+    the hydration summary (with compatibility mirror fields also exposed at top level).
+    """
+
+
+async def replay_async(
+    digest: Optional[str] = ...,
+    *,
+    rpc_url: str = ...,
+    network: Optional[str] = ...,
+    source: str = ...,
+    checkpoint: Optional[int] = ...,
+    state_file: Optional[str] = ...,
+    state_format: Optional[str] = ...,
+    context_path: Optional[str] = ...,
+    context_overrides: bool = ...,
+    cache_dir: Optional[str] = ...,
+    profile: Optional[str] = ...,
+    fetch_strategy: Optional[str] = ...,
+    vm_only: bool = ...,
+    allow_fallback: bool = ...,
+    prefetch_depth: int = ...,
+    prefetch_limit: int = ...,
+    auto_system_objects: bool = ...,
+    no_prefetch: bool = ...,
+    compare: bool = ...,
+    analyze_only: bool = ...,
+    synthesize_missing: bool = ...,
+    self_heal_dynamic_fields: bool = ...,
+    analyze_mm2: bool = ...,
+    fetch_modules_only: bool = ...,
+    object_type_overrides: Optional[Dict[str, Optional[bytes]]] = ...,
+    epoch_override: Optional[int] = ...,
+    protocol_version: Optional[int] = ...,
+    package_overrides: Optional[Dict[str, List[bytes]]] = ...,
+    ownership_hints: Optional[Dict[str, str]] = ...,
+    expect_effects_hash: Optional[str] = ...,
+    strict_object_digests: bool = ...,
+    require_comparison: bool = ...,
+    expected_effects: Optional[Any] = ...,
+    stub_unknown_natives: bool = ...,
+    gas_table: Optional[Any] = ...,
+    use_result_cache: bool = ...,
+    result_cache_dir: Optional[str] = ...,
+    offline: bool = ...,
+    apply_checkpoint_prologue: bool = ...,
+    instruction_trace: Optional[str] = ...,
+    instruction_trace_stack: bool = ...,
+    decode_events: bool = ...,
+    diff_objects: bool = ...,
+    gas_profile: bool = ...,
+    trace: bool = ...,
+    trace_depth: Optional[int] = ...,
+    reconcile: bool = ...,
+    record_requests: Optional[str] = ...,
+    summary_only: bool = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Async variant of `replay`; takes the same arguments.
+
+    Returns: Awaitable resolving to the `replay` result dict.
+    """
+
+
+def replay_from_cassette(
+    path: str,
+    *,
+    summary_only: bool = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Re-run a replay recorded with `replay(..., record_requests=path)` without network.
+
+    Every gRPC/GraphQL/Walrus fetch is served from the cassette; a request the cassette
+    has no recording for fails instead of reaching the network. The digest, source,
+    fetch/execution flags and replay options (overrides, hints, expected effects, gas
+    table, protocol version, ...) come from the recording. Result caching and
+    `instruction_trace` are per call and not recorded.
+
+    Args:
+        path: Cassette file written by `record_requests`
+        summary_only: Return the compact replay summary (see `replay`)
+        verbose: Enable verbose logging to stderr
+
+    Returns: dict replay envelope (as `replay`), with `cassette` (`path`, `mode`, `interactions`)
+    """
 
 
 def replay_transaction(
@@ -529,7 +1806,91 @@ def replay_transaction(
     self_heal_dynamic_fields: bool = ...,
     analyze_mm2: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Replay a transaction with opinionated defaults for a compact native API.
+
+    Args:
+        digest: Transaction digest (optional when state_file contains a single transaction)
+        checkpoint: Optional checkpoint (if provided and source is omitted, source defaults to walrus)
+        discover_latest: Auto-discover digest from latest N checkpoints (requires discover_package_id)
+        discover_package_id: Package filter used for discovery when discover_latest is set
+        source: "hybrid", "grpc", "walrus", or "jsonrpc" (default: inferred)
+        state_file: Optional replay-state JSON for deterministic local input data
+        context_path: Optional prepared package context JSON to pre-seed package bytecode
+        cache_dir: Optional local replay cache when source="local"
+        walrus_network: Walrus network for discovery ("mainnet" or "testnet")
+        walrus_caching_url: Optional custom Walrus caching endpoint (requires walrus_aggregator_url)
+        walrus_aggregator_url: Optional custom Walrus aggregator endpoint (requires walrus_caching_url)
+        rpc_url: Sui RPC endpoint
+        allow_fallback: Allow fallback hydration paths
+        profile: Runtime defaults profile ("safe"|"balanced"|"fast")
+        fetch_strategy: Dynamic-field fetch strategy ("eager"|"full")
+        vm_only: Disable fallback paths and force VM-only behavior
+        prefetch_depth: Dynamic field prefetch depth
+        prefetch_limit: Dynamic field prefetch limit
+        auto_system_objects: Auto inject Clock/Random if missing
+        no_prefetch: Disable prefetch
+        compare: Compare local execution with on-chain effects
+        analyze_only: Hydration-only mode
+        synthesize_missing: Retry with synthetic object bytes when inputs are missing
+        self_heal_dynamic_fields: Enable dynamic field self-healing during VM execution
+        analyze_mm2: Build MM2 diagnostics (analyze-only mode)
+        verbose: Verbose replay logging
+
+    Returns: Replay result dict
+    """
+
+
+def discover_and_replay(
+    package_id: str,
+    latest: int = ...,
+    *,
+    max_transactions: Optional[int] = ...,
+    include_framework: bool = ...,
+    resolve_deps: bool = ...,
+    walrus_network: str = ...,
+    walrus_caching_url: Optional[str] = ...,
+    walrus_aggregator_url: Optional[str] = ...,
+    rpc_url: str = ...,
+    vm_only: bool = ...,
+    allow_fallback: bool = ...,
+    compare: bool = ...,
+    synthesize_missing: bool = ...,
+    self_heal_dynamic_fields: bool = ...,
+    on_result: Optional[Callable[[Dict[str, Any]], Any]] = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Discover recent transactions that call a package and replay each one.
+
+    Checkpoints are scanned one at a time and every matching PTB is replayed from the
+    already-fetched checkpoint against a package context prepared once up front. Dependency
+    packages fetched for one transaction are cached and reused by the rest, so the package
+    closure is not re-fetched per transaction. A failed replay is recorded on its result
+    entry and does not stop the run.
+
+    Args:
+        package_id: Package whose Move calls select the transactions
+        latest: Scan the latest N checkpoints, oldest first (max 500)
+        max_transactions: Stop after replaying this many transactions
+        include_framework: Also match framework Move calls
+        resolve_deps: Include the package's dependency closure in the shared context
+        walrus_network: Walrus network ("mainnet" or "testnet")
+        walrus_caching_url: Optional custom Walrus caching endpoint (requires walrus_aggregator_url)
+        walrus_aggregator_url: Optional custom Walrus aggregator endpoint (requires walrus_caching_url)
+        rpc_url: Sui RPC endpoint (GraphQL dependency fetches)
+        vm_only: Disable fallback paths and force VM-only behavior
+        allow_fallback: Allow fallback hydration paths
+        compare: Compare local execution with on-chain effects
+        synthesize_missing: Retry with synthetic object bytes when inputs are missing
+        self_heal_dynamic_fields: Enable dynamic field self-healing during VM execution
+        on_result: Optional callable invoked with each result entry as soon as it completes;
+            an exception raised by the callable aborts the run
+        verbose: Verbose replay logging
+
+    Returns: dict with `results` (one entry per replayed transaction: `checkpoint`, `digest`,
+        `move_calls`, and either `replay` or `error`) plus scan and outcome counters;
+        `cached_packages` counts the dependency packages shared across the batch.
+    """
 
 
 def analyze_replay(
@@ -556,7 +1917,11 @@ def analyze_replay(
     no_prefetch: bool = ...,
     analyze_mm2: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Analyze replay hydration/readiness only (CLI parity for `analyze replay`).
+
+    This is equivalent to `replay_transaction(..., analyze_only=True)`.
+    """
 
 
 def replay_analyze(
@@ -583,7 +1948,8 @@ def replay_analyze(
     no_prefetch: bool = ...,
     analyze_mm2: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Compatibility alias for `analyze_replay`."""
 
 
 def replay_effects(
@@ -612,10 +1978,206 @@ def replay_effects(
     synthesize_missing: bool = ...,
     self_heal_dynamic_fields: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Execute replay and return execution/effects-focused fields."""
+
+
+def replay_ab(
+    digest: Optional[str] = ...,
+    config_a: Optional[Dict[str, Any]] = ...,
+    config_b: Optional[Dict[str, Any]] = ...,
+    *,
+    source: str = ...,
+    checkpoint: Optional[int] = ...,
+    state_file: Optional[str] = ...,
+    cache_dir: Optional[str] = ...,
+    rpc_url: str = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Replay one transaction under two configurations and diff the results (A/B harness).
+
+    Args:
+        digest: Transaction digest (optional when state_file contains a single transaction)
+        config_a: Dict for run A (keys: label, context_path, vm_only, allow_fallback,
+            auto_system_objects, synthesize_missing, self_heal_dynamic_fields)
+        config_b: Dict for run B (same keys as config_a)
+        source: Replay source for network fetches ("hybrid", "grpc", "walrus", "jsonrpc", "local")
+        checkpoint: Optional checkpoint for Walrus-backed fetches
+        state_file: Optional replay-state JSON shared by both runs
+        cache_dir: Optional local replay cache shared by both runs
+        rpc_url: Sui RPC endpoint
+        verbose: Verbose replay logging
+
+    Returns: Dict with `a`/`b` (label + replay output) and `diff` (effects comparison,
+    return-value diffs, gas delta, `identical`)
+    """
+
+
+def bench_replay(
+    digests: List[str],
+    *,
+    profile: Optional[str] = ...,
+    concurrency: int = ...,
+    repeats: int = ...,
+    warmup: int = ...,
+    source: str = ...,
+    checkpoint: Optional[int] = ...,
+    state_file: Optional[str] = ...,
+    cache_dir: Optional[str] = ...,
+    use_result_cache: bool = ...,
+    result_cache_dir: Optional[str] = ...,
+    rpc_url: str = ...,
+) -> Dict[str, Any]:
+    """Benchmark replay throughput over a fixed digest set.
+
+    Args:
+        digests: Transaction digests replayed once per pass
+        profile: Runtime defaults profile ("safe"|"balanced"|"fast")
+        concurrency: Worker threads replaying in parallel
+        repeats: Measured passes over `digests`
+        warmup: Unmeasured passes run first (connections, caches, JIT-like warm paths)
+        source: Replay source for network fetches ("hybrid", "grpc", "walrus", "jsonrpc", "local")
+        checkpoint: Optional checkpoint for Walrus-backed fetches
+        state_file: Optional replay-state JSON holding every digest (parsed once up front)
+        cache_dir: Optional local replay cache to replay from
+        use_result_cache: Replay through the result cache (reports hit rate)
+        result_cache_dir: Result cache directory (default: `~/.sui-sandbox/cache/results`)
+        rpc_url: Sui RPC endpoint
+
+    Returns: Dict with `throughput_per_sec`, `wall_ms`, overall and `per_digest` `latency`
+    (mean/p50/p95/p99/max ms), `result_cache` hits, `fetches` (total, per replay, by kind)
+    and `errors`, for regression tracking across runs
+    """
+
+
+def replay_many(
+    digests: List[str],
+    *,
+    max_concurrency: int = ...,
+    profile: Optional[str] = ...,
+    source: str = ...,
+    cache_dir: Optional[str] = ...,
+    allow_fallback: bool = ...,
+    prefetch_depth: int = ...,
+    prefetch_limit: int = ...,
+    auto_system_objects: bool = ...,
+    compare: bool = ...,
+    include_outputs: bool = ...,
+    rpc_url: str = ...,
+) -> Dict[str, Any]:
+    """Replay many transactions with bounded parallelism and one shared fetch cache.
+
+    Args:
+        digests: Transaction digests to replay
+        max_concurrency: Worker threads replaying in parallel
+        profile: Runtime defaults profile ("safe"|"balanced"|"fast")
+        source: Replay source ("hybrid", "grpc", "walrus", "jsonrpc", "local")
+        cache_dir: Optional local replay cache to replay from
+        allow_fallback: Allow fallback hydration paths
+        prefetch_depth: Dynamic field prefetch depth
+        prefetch_limit: Dynamic field prefetch limit
+        auto_system_objects: Auto inject Clock/Random if missing
+        compare: Compare local execution with on-chain effects
+        include_outputs: Keep each replay's full result dict under `runs[i].output`
+        rpc_url: Sui RPC endpoint
+
+    Returns: Dict with `runs` (per digest, in input order: `digest`, `success`,
+    `elapsed_ms`, `error`), `summary` (`total`, `success`, `failed`, `errored`,
+    `success_rate`, `wall_ms`, `average_elapsed_ms`, `max_elapsed_ms`) and
+    `shared_cache` (packages/objects held by the cache the replays shared, plus the
+    `framework_versions` their shared resolver loaded)
+    """
+
+
+async def replay_many_async(
+    digests: List[str],
+    *,
+    max_concurrency: int = ...,
+    profile: Optional[str] = ...,
+    source: str = ...,
+    cache_dir: Optional[str] = ...,
+    allow_fallback: bool = ...,
+    prefetch_depth: int = ...,
+    prefetch_limit: int = ...,
+    auto_system_objects: bool = ...,
+    compare: bool = ...,
+    include_outputs: bool = ...,
+    rpc_url: str = ...,
+) -> Dict[str, Any]:
+    """Async variant of `replay_many`; takes the same arguments.
+
+    Returns: Awaitable resolving to the `replay_many` report dict.
+    """
+
+
+def replay_checkpoint(
+    checkpoint: int,
+    *,
+    compare: bool = ...,
+    protocol_version: Optional[int] = ...,
+    walrus_network: str = ...,
+    walrus_caching_url: Optional[str] = ...,
+    walrus_aggregator_url: Optional[str] = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Replay every transaction of a Walrus checkpoint in order and reconcile all effects.
+
+    Transactions share one evolving object store: an object written by an earlier
+    transaction in the checkpoint is read from its local output rather than from the
+    checkpoint, so divergences carry forward. System transactions are not executed.
 
+    Args:
+        checkpoint: Checkpoint sequence number
+        compare: Reconcile each transaction's local effects with its on-chain effects
+        protocol_version: Execute with this protocol version's framework, feature flags and
+            gas schedule instead of the checkpoint epoch's version (looked up over GraphQL;
+            the checkpoint loader's default when the lookup fails)
+        walrus_network: Walrus network ("mainnet" or "testnet")
+        walrus_caching_url: Optional custom Walrus caching endpoint (requires walrus_aggregator_url)
+        walrus_aggregator_url: Optional custom Walrus aggregator endpoint (requires walrus_caching_url)
+        verbose: Log each transaction's outcome
 
-def classify_replay_result(result: Any) -> Dict[str, Any]: ...
+    Returns: Dict with `transactions` (per transaction, in checkpoint order: `index`, `digest`,
+    `kind`, `executed`, `local_success`, `on_chain_success`, `chained_inputs`,
+    `diverged_inputs`, `error`, `reconciliation`), `summary` (`executed`, `skipped`,
+    `errored`, `local_success`, `status_mismatches`, `effects_matched`, `mismatch_counts`, ...),
+    `store_objects`, `protocol_version` (null when the loader's default was used) and `warnings`
+    """
+
+
+def classify_replay_result(result: Any) -> Dict[str, Any]:
+    """Classify replay output into structured failure categories and retry hints."""
+
+
+def replay_feasibility(
+    digest: Optional[str] = ...,
+    *,
+    checkpoint: Optional[int] = ...,
+    state_file: Optional[str] = ...,
+    verbose: bool = ...,
+) -> Dict[str, Any]:
+    """Check whether a transaction is replayable before running it.
+
+    Inspects the transaction's command and input kinds against the replay
+    capability registry (see `replay_capabilities()`); nothing is executed.
+
+    Args:
+        digest: Transaction digest (optional when state_file contains a single transaction)
+        checkpoint: Walrus checkpoint containing the transaction (required with digest)
+        state_file: Replay-state JSON to read the transaction from instead of Walrus
+        verbose: Enable verbose logging to stderr
+
+    Returns: dict with `replayable`, `support` ("supported"|"partial"|"unsupported"),
+    per-entry `commands`/`inputs` verdicts, and `reasons` for anything not fully supported.
+    """
+
+
+def replay_capabilities() -> Dict[str, Any]:
+    """PTB command and input kinds with their replay support level.
+
+    Returns: dict with `commands` and `inputs`, each a list of
+    `{kind, support, note}` entries.
+    """
 
 
 def dynamic_field_diagnostics(
@@ -641,7 +2203,11 @@ def dynamic_field_diagnostics(
     auto_system_objects: bool = ...,
     analyze_mm2: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Compare no-prefetch vs prefetch hydration to diagnose dynamic-field data gaps.
+
+    This API does not run VM execution; it uses hydration-only replay analysis.
+    """
 
 
 def context_replay(
@@ -672,14 +2238,14 @@ def context_replay(
     self_heal_dynamic_fields: bool = ...,
     analyze_mm2: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Canonical alias for replaying against a prepared context."""
 
 
-def protocol_run(
+def context_run(
+    package_id: str,
     digest: Optional[str] = ...,
     *,
-    protocol: str = ...,
-    package_id: Optional[str] = ...,
     resolve_deps: bool = ...,
     context_path: Optional[str] = ...,
     checkpoint: Optional[int] = ...,
@@ -705,10 +2271,11 @@ def protocol_run(
     self_heal_dynamic_fields: bool = ...,
     analyze_mm2: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Canonical context run wrapper: prepare context + replay in one call."""
 
 
-def adapter_run(
+def protocol_run(
     digest: Optional[str] = ...,
     *,
     protocol: str = ...,
@@ -741,10 +2308,11 @@ def adapter_run(
 ) -> Dict[str, Any]: ...
 
 
-def context_run(
-    package_id: str,
+def adapter_run(
     digest: Optional[str] = ...,
     *,
+    protocol: str = ...,
+    package_id: Optional[str] = ...,
     resolve_deps: bool = ...,
     context_path: Optional[str] = ...,
     checkpoint: Optional[int] = ...,
@@ -770,4 +2338,5 @@ def context_run(
     self_heal_dynamic_fields: bool = ...,
     analyze_mm2: bool = ...,
     verbose: bool = ...,
-) -> Dict[str, Any]: ...
+) -> Dict[str, Any]:
+    """Canonical alias for `protocol_run`."""
@@ -0,0 +1,203 @@
+//! Decode Move abort codes into named errors using the aborting module's bytecode.
+//!
+//! A `MoveAbort` only carries a `u64`. How much can be recovered depends on how the
+//! abort was compiled:
+//!
+//! - **Clever errors** (Move 2024 `#[error]` constants and `assert!` without a code)
+//!   pack the source line and constant-pool indices into the code itself:
+//!
+//!   | Bits  | Field |
+//!   |-------|-------|
+//!   | 63    | tag (always 1) |
+//!   | 48-62 | reserved |
+//!   | 32-47 | source line |
+//!   | 16-31 | identifier constant index (`0xffff` = none) |
+//!   | 0-15  | error value constant index (`0xffff` = none) |
+//!
+//!   The identifier constant holds the error constant's name and the value constant
+//!   holds its message (or number), so these decode to `0x2::coin::EFoo (line 42): msg`.
+//! - **Plain codes** (`const EFoo: u64 = 3; abort EFoo`) do not keep constant names
+//!   in bytecode; the decoder only reports the module and code, and callers fall back
+//!   to the heuristics in [`crate::error_context::get_abort_code_context`].
+
+use move_binary_format::file_format::{CompiledModule, Constant, SignatureToken};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::ModuleId;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const CLEVER_TAG_BIT: u64 = 1 << 63;
+const NO_CONSTANT_INDEX: u16 = u16::MAX;
+
+/// Fields packed into a Move 2024 clever abort code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CleverAbortCode {
+    /// Source line of the `abort`/`assert!`.
+    pub line_number: u16,
+    /// Constant-pool index of the error constant's name.
+    pub identifier_index: Option<u16>,
+    /// Constant-pool index of the error constant's value.
+    pub constant_index: Option<u16>,
+}
+
+impl CleverAbortCode {
+    /// Unpack a clever abort code. Returns `None` for plain abort codes.
+    pub fn parse(abort_code: u64) -> Option<Self> {
+        if abort_code & CLEVER_TAG_BIT == 0 {
+            return None;
+        }
+        let index = |raw: u64| Some(raw as u16).filter(|idx| *idx != NO_CONSTANT_INDEX);
+        Some(Self {
+            line_number: (abort_code >> 32) as u16,
+            identifier_index: index((abort_code >> 16) & 0xffff),
+            constant_index: index(abort_code & 0xffff),
+        })
+    }
+}
+
+/// An abort code resolved against the bytecode of the module that raised it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecodedAbort {
+    /// Aborting module, e.g. `0x2::coin`.
+    pub module: String,
+    /// Raw abort code as reported by the VM.
+    pub abort_code: u64,
+    /// Error constant name (clever errors only), e.g. `EInsufficientBalance`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constant_name: Option<String>,
+    /// Rendered error constant value (clever errors only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Source line of the abort (clever errors only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_line: Option<u16>,
+}
+
+impl DecodedAbort {
+    /// Whether decoding recovered anything beyond the raw code.
+    pub fn is_resolved(&self) -> bool {
+        self.constant_name.is_some() || self.source_line.is_some()
+    }
+}
+
+impl fmt::Display for DecodedAbort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.constant_name {
+            Some(name) => write!(f, "{}::{}", self.module, name)?,
+            None => write!(f, "{} abort code {}", self.module, self.abort_code)?,
+        }
+        if let Some(line) = self.source_line {
+            write!(f, " (line {})", line)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode `abort_code` raised by `module`.
+pub fn decode_abort(module: &CompiledModule, abort_code: u64) -> DecodedAbort {
+    let mut decoded = DecodedAbort {
+        module: short_module_name(&module.self_id()),
+        abort_code,
+        constant_name: None,
+        message: None,
+        source_line: None,
+    };
+    let Some(clever) = CleverAbortCode::parse(abort_code) else {
+        return decoded;
+    };
+    decoded.source_line = Some(clever.line_number);
+    decoded.constant_name = clever
+        .identifier_index
+        .and_then(|idx| module.constant_pool.get(idx as usize))
+        .and_then(render_constant);
+    decoded.message = clever
+        .constant_index
+        .and_then(|idx| module.constant_pool.get(idx as usize))
+        .and_then(render_constant);
+    decoded
+}
+
+fn short_module_name(id: &ModuleId) -> String {
+    format!("{}::{}", id.address().to_hex_literal(), id.name())
+}
+
+/// Render a constant-pool entry; `vector<u8>` is shown as (lossy) UTF-8.
+fn render_constant(constant: &Constant) -> Option<String> {
+    let data = constant.data.as_slice();
+    match &constant.type_ {
+        SignatureToken::Vector(inner) if **inner == SignatureToken::U8 => {
+            bcs::from_bytes::<Vec<u8>>(data)
+                .ok()
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        }
+        SignatureToken::Bool => bcs::from_bytes::<bool>(data).ok().map(|v| v.to_string()),
+        SignatureToken::U8 => bcs::from_bytes::<u8>(data).ok().map(|v| v.to_string()),
+        SignatureToken::U16 => bcs::from_bytes::<u16>(data).ok().map(|v| v.to_string()),
+        SignatureToken::U32 => bcs::from_bytes::<u32>(data).ok().map(|v| v.to_string()),
+        SignatureToken::U64 => bcs::from_bytes::<u64>(data).ok().map(|v| v.to_string()),
+        SignatureToken::U128 => bcs::from_bytes::<u128>(data).ok().map(|v| v.to_string()),
+        SignatureToken::Address => bcs::from_bytes::<AccountAddress>(data)
+            .ok()
+            .map(|v| v.to_hex_literal()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::empty_module;
+
+    fn clever_code(line: u16, identifier: u16, constant: u16) -> u64 {
+        CLEVER_TAG_BIT | ((line as u64) << 32) | ((identifier as u64) << 16) | constant as u64
+    }
+
+    fn module_with_error_constant(name: &str, message: &str) -> CompiledModule {
+        let mut module = empty_module();
+        let vector_u8 = SignatureToken::Vector(Box::new(SignatureToken::U8));
+        module.constant_pool.push(Constant {
+            type_: vector_u8.clone(),
+            data: bcs::to_bytes(name.as_bytes()).unwrap(),
+        });
+        module.constant_pool.push(Constant {
+            type_: vector_u8,
+            data: bcs::to_bytes(message.as_bytes()).unwrap(),
+        });
+        module
+    }
+
+    #[test]
+    fn test_decode_clever_error() {
+        let module = module_with_error_constant("ENotEnough", "balance too low");
+        let decoded = decode_abort(&module, clever_code(42, 0, 1));
+
+        assert_eq!(decoded.constant_name.as_deref(), Some("ENotEnough"));
+        assert_eq!(decoded.message.as_deref(), Some("balance too low"));
+        assert_eq!(decoded.source_line, Some(42));
+        assert_eq!(
+            decoded.to_string(),
+            format!("{}::ENotEnough (line 42): balance too low", decoded.module)
+        );
+    }
+
+    #[test]
+    fn test_decode_line_only_and_plain_codes() {
+        let module = empty_module();
+
+        let line_only = decode_abort(
+            &module,
+            clever_code(7, NO_CONSTANT_INDEX, NO_CONSTANT_INDEX),
+        );
+        assert!(line_only.is_resolved());
+        assert_eq!(line_only.constant_name, None);
+        assert_eq!(line_only.source_line, Some(7));
+
+        let plain = decode_abort(&module, 3);
+        assert!(!plain.is_resolved());
+        assert_eq!(plain.to_string(), format!("{} abort code 3", plain.module));
+        assert_eq!(CleverAbortCode::parse(3), None);
+    }
+}
@@ -27,7 +27,7 @@ impl ProtocolAdapter {
     ];
 
     pub fn parse(input: &str) -> Result<Self> {
-        match input.trim().to_ascii_lowercase().as_str() {
+        match normalize_protocol_name(input).as_str() {
             "generic" => Ok(Self::Generic),
             "deepbook" => Ok(Self::Deepbook),
             "cetus" => Ok(Self::Cetus),
@@ -56,6 +56,11 @@ impl ProtocolAdapter {
     }
 }
 
+/// Canonical form of a protocol name (trimmed, lowercase).
+pub fn normalize_protocol_name(input: &str) -> String {
+    input.trim().to_ascii_lowercase()
+}
+
 fn requires_package_id_error(protocol: ProtocolAdapter) -> anyhow::Error {
     anyhow!(
         "protocol `{}` requires --package-id (no built-in protocol package defaults)",
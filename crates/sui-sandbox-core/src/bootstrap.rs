@@ -14,7 +14,7 @@ use tokio::runtime::Runtime;
 
 use sui_state_fetcher::{HistoricalStateProvider, PackageData};
 use sui_transport::graphql::GraphQLClient;
-use sui_transport::grpc::{GrpcClient, GrpcOwner, GrpcTransaction};
+use sui_transport::grpc::{global_pool, GrpcClient, GrpcOwner, GrpcTransaction};
 use sui_types::digests::TransactionDigest as SuiTransactionDigest;
 
 use crate::fetcher::GrpcFetcher;
@@ -51,9 +51,8 @@ pub fn create_child_fetcher(
             let child_id_str = child_id.to_hex_literal();
             let version = historical_arc.get(&child_id_str).copied();
 
-            let rt = tokio::runtime::Runtime::new().ok()?;
-            let result =
-                rt.block_on(async { grpc_arc.get_object_at_version(&child_id_str, version).await });
+            let result = global_pool()
+                .block_on(async { grpc_arc.get_object_at_version(&child_id_str, version).await });
 
             if let Ok(Some(obj)) = result {
                 if let (Some(type_str), Some(bcs)) = (&obj.type_string, &obj.bcs) {
@@ -202,7 +201,7 @@ pub async fn create_mainnet_provider(historical_mode: bool) -> Result<Historical
     }
 
     let api_key = std::env::var("SUI_GRPC_API_KEY").ok();
-    let grpc = GrpcClient::with_api_key(&endpoint, api_key).await?;
+    let grpc = global_pool().client(&endpoint, api_key).await?;
     let graphql = GraphQLClient::mainnet();
 
     Ok(HistoricalStateProvider::with_clients(grpc, graphql))
@@ -289,7 +288,7 @@ pub async fn configure_environment_mainnet_fetchers(
     });
 
     let api_key = std::env::var("SUI_GRPC_API_KEY").ok();
-    let child_grpc = GrpcClient::with_api_key(endpoint, api_key).await?;
+    let child_grpc = global_pool().client(endpoint, api_key).await?;
     env.set_child_fetcher(create_child_fetcher(
         child_grpc,
         historical_versions,
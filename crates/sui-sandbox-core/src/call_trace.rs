@@ -0,0 +1,204 @@
+//! Move call trees per PTB command.
+//!
+//! With [`SimulationConfig::call_trace`](crate::vm::SimulationConfig) set, every Move
+//! call the harness runs is executed with a `MoveTraceBuilder` (shared with
+//! [`execution_trace`](crate::execution_trace)) and its frame events are folded into a
+//! tree of [`CallFrame`]s: function, type arguments, rendered arguments and return
+//! values, gas charged and any execution error. The PTB executor groups the trees by
+//! command into `TransactionEffects::call_trace`:
+//!
+//! ```text
+//! {"index":0,"command_type":"MoveCall","calls":[
+//!   {"module":"0x2::coin","function":"split","args":[...],"returns":[...],"gas_used":1200,
+//!    "children":[{"module":"0x2::balance","function":"split",...}]}]}
+//! ```
+//!
+//! Unlike the instruction trace, no per-instruction entries are kept, so call traces
+//! are cheap enough to leave on for a whole replay. [`CallTraceOptions::max_depth`]
+//! bounds the tree for deeply nested DeFi calls.
+
+use move_trace_format::format::{Effect, MoveTrace, TraceEvent};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::execution_trace::render_value;
+
+/// What a call trace records.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallTraceOptions {
+    /// Keep frames up to this call depth (the command's entry function is depth 1);
+    /// deeper calls are counted in `omitted_calls` of their nearest kept ancestor.
+    /// `None` keeps the whole tree.
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+}
+
+/// One Move function invocation and the calls it made.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CallFrame {
+    /// `0xaddr::module`
+    pub module: String,
+    pub function: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub type_args: Vec<String>,
+    pub args: Vec<String>,
+    pub returns: Vec<String>,
+    /// Gas charged while the frame was open, callees included, in the VM's internal
+    /// gas units (0 when unmetered).
+    pub gas_used: u64,
+    pub native: bool,
+    /// Execution error raised in this frame; the frame never returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<CallFrame>,
+    /// Calls below `max_depth` that were not kept.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub omitted_calls: usize,
+}
+
+/// The call trees recorded while one PTB command ran.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandCallTrace {
+    pub index: usize,
+    pub command_type: String,
+    pub description: String,
+    /// One tree per VM call; empty for commands that don't run Move code.
+    pub calls: Vec<CallFrame>,
+}
+
+fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
+
+struct OpenCall {
+    frame_id: usize,
+    gas_left: u64,
+    frame: CallFrame,
+}
+
+/// Call trees accumulated across the VM calls of one harness.
+#[derive(Debug, Default)]
+pub struct CallTraceLog {
+    options: CallTraceOptions,
+    calls: Mutex<Vec<CallFrame>>,
+}
+
+impl CallTraceLog {
+    pub fn new(options: CallTraceOptions) -> Self {
+        Self {
+            options,
+            calls: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Fold the frame events of one VM call into call trees.
+    pub fn record(&self, trace: &MoveTrace) {
+        let mut open: Vec<OpenCall> = Vec::new();
+        let mut roots = Vec::new();
+        let mut gas_left = 0u64;
+        for event in &trace.events {
+            match event {
+                TraceEvent::OpenFrame {
+                    frame,
+                    gas_left: left,
+                } => {
+                    gas_left = *left;
+                    open.push(OpenCall {
+                        frame_id: frame.frame_id,
+                        gas_left: *left,
+                        frame: CallFrame {
+                            module: format!(
+                                "{}::{}",
+                                frame.module.address().to_hex_literal(),
+                                frame.module.name()
+                            ),
+                            function: frame.function_name.clone(),
+                            type_args: frame
+                                .type_instantiation
+                                .iter()
+                                .map(|tag| tag.to_canonical_string(true))
+                                .collect(),
+                            args: frame.parameters.iter().map(render_value).collect(),
+                            returns: Vec::new(),
+                            gas_used: 0,
+                            native: frame.is_native,
+                            error: None,
+                            children: Vec::new(),
+                            omitted_calls: 0,
+                        },
+                    });
+                }
+                TraceEvent::CloseFrame {
+                    frame_id,
+                    return_,
+                    gas_left: left,
+                } => {
+                    gas_left = *left;
+                    let Some(pos) = open.iter().rposition(|call| call.frame_id == *frame_id) else {
+                        continue;
+                    };
+                    while open.len() > pos + 1 {
+                        self.close(&mut open, &mut roots, gas_left);
+                    }
+                    if let Some(call) = open.last_mut() {
+                        call.frame.returns = return_.iter().map(render_value).collect();
+                    }
+                    self.close(&mut open, &mut roots, gas_left);
+                }
+                TraceEvent::Instruction { gas_left: left, .. } => gas_left = *left,
+                TraceEvent::Effect(effect) => {
+                    if let Effect::ExecutionError(message) = effect.as_ref() {
+                        if let Some(call) = open.last_mut() {
+                            call.frame.error = Some(message.clone());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Frames still open aborted (or ran out of gas).
+        while !open.is_empty() {
+            self.close(&mut open, &mut roots, gas_left);
+        }
+        self.calls.lock().extend(roots);
+    }
+
+    /// Pop the innermost open frame and attach it to its caller (or the roots).
+    fn close(&self, open: &mut Vec<OpenCall>, roots: &mut Vec<CallFrame>, gas_left: u64) {
+        let depth = open.len();
+        let Some(call) = open.pop() else {
+            return;
+        };
+        let mut frame = call.frame;
+        frame.gas_used = call.gas_left.saturating_sub(gas_left);
+        let kept = !matches!(self.options.max_depth, Some(max) if depth > max);
+        match open.last_mut() {
+            Some(caller) if kept => caller.frame.children.push(frame),
+            Some(caller) => caller.frame.omitted_calls += 1 + frame.omitted_calls,
+            None => roots.push(frame),
+        }
+    }
+
+    /// Number of VM call trees recorded so far.
+    pub fn len(&self) -> usize {
+        self.calls.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.calls.lock().is_empty()
+    }
+
+    /// Call trees recorded after the first `start`.
+    pub fn calls_since(&self, start: usize) -> Vec<CallFrame> {
+        self.calls
+            .lock()
+            .get(start..)
+            .map(<[CallFrame]>::to_vec)
+            .unwrap_or_default()
+    }
+
+    pub fn clear(&self) {
+        self.calls.lock().clear();
+    }
+}
@@ -12,7 +12,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use sui_resolver::is_framework_address;
 use sui_transport::walrus::WalrusClient;
-use sui_types::transaction::{Command as SuiCommand, TransactionDataAPI, TransactionKind};
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::transaction::{
+    Command as SuiCommand, ProgrammableTransaction, TransactionDataAPI, TransactionKind,
+};
 
 /// Walrus archive network selector.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -194,6 +197,18 @@ pub fn resolve_discovery_checkpoints(
     Ok((start..=tip).collect())
 }
 
+/// Split `checkpoints` into `(first, last)` runs of consecutive ascending numbers.
+fn contiguous_checkpoint_runs(checkpoints: &[u64]) -> Vec<(u64, u64)> {
+    let mut runs: Vec<(u64, u64)> = Vec::new();
+    for &checkpoint in checkpoints {
+        match runs.last_mut() {
+            Some((_, last)) if last.checked_add(1) == Some(checkpoint) => *last = checkpoint,
+            _ => runs.push((checkpoint, checkpoint)),
+        }
+    }
+    runs
+}
+
 /// Discover PTB replay targets across checkpoint(s), optionally package-filtered.
 pub fn discover_checkpoint_targets(
     walrus: &WalrusClient,
@@ -212,73 +227,39 @@ pub fn discover_checkpoint_targets(
         Some(pkg) => Some(normalize_package_id(pkg)?),
         None => None,
     };
-    let filter_is_framework = package_filter
-        .as_deref()
-        .map(is_framework_package_id)
-        .unwrap_or(false);
-
     let mut checkpoints_scanned = 0usize;
     let mut transactions_scanned = 0usize;
     let mut ptbs_scanned = 0usize;
     let mut targets = Vec::new();
     let mut truncated = false;
 
-    'checkpoint_scan: for checkpoint in &checkpoints {
-        checkpoints_scanned += 1;
-        let checkpoint_data = walrus
-            .get_checkpoint(*checkpoint)
-            .with_context(|| format!("failed to fetch checkpoint {}", checkpoint))?;
-        for tx in &checkpoint_data.transactions {
-            transactions_scanned += 1;
-            let tx_data = tx.transaction.data().transaction_data();
-            let ptb = match tx_data.kind() {
-                TransactionKind::ProgrammableTransaction(ptb) => ptb,
-                _ => continue,
-            };
-            ptbs_scanned += 1;
-
-            let mut move_calls = Vec::new();
-            let mut package_ids: BTreeSet<String> = BTreeSet::new();
-            for (command_index, command) in ptb.commands.iter().enumerate() {
-                let SuiCommand::MoveCall(call) = command else {
+    // Contiguous runs are streamed so downloads overlap with scanning.
+    'checkpoint_scan: for (first, last) in contiguous_checkpoint_runs(&checkpoints) {
+        for item in walrus.get_checkpoint_range(first, last) {
+            let (checkpoint, checkpoint_data) = item?;
+            checkpoints_scanned += 1;
+            for tx in &checkpoint_data.transactions {
+                transactions_scanned += 1;
+                let TransactionKind::ProgrammableTransaction(ptb) =
+                    tx.transaction.data().transaction_data().kind()
+                else {
                     continue;
                 };
-                let package = normalize_package_id(&call.package.to_hex_uncompressed())
-                    .unwrap_or_else(|_| call.package.to_hex_uncompressed());
-                let matches_filter = package_filter
-                    .as_ref()
-                    .map(|filter| filter == &package)
-                    .unwrap_or(true);
-                if !matches_filter {
-                    continue;
-                }
-                if !include_framework && !filter_is_framework && is_framework_package_id(&package) {
+                ptbs_scanned += 1;
+                let Some(target) = ptb_target(
+                    checkpoint,
+                    tx,
+                    ptb,
+                    package_filter.as_deref(),
+                    include_framework,
+                ) else {
                     continue;
+                };
+                targets.push(target);
+                if targets.len() >= limit {
+                    truncated = true;
+                    break 'checkpoint_scan;
                 }
-                package_ids.insert(package.clone());
-                move_calls.push(DiscoverMoveCall {
-                    command_index,
-                    package,
-                    module: call.module.to_string(),
-                    function: call.function.to_string(),
-                });
-            }
-            if move_calls.is_empty() {
-                continue;
-            }
-            targets.push(DiscoverTarget {
-                checkpoint: *checkpoint,
-                digest: tx.transaction.digest().to_string(),
-                sender: tx_data.sender().to_string(),
-                commands: ptb.commands.len(),
-                input_objects: tx.input_objects.len(),
-                output_objects: tx.output_objects.len(),
-                package_ids: package_ids.into_iter().collect(),
-                move_calls,
-            });
-            if targets.len() >= limit {
-                truncated = true;
-                break 'checkpoint_scan;
             }
         }
     }
@@ -298,6 +279,79 @@ pub fn discover_checkpoint_targets(
     })
 }
 
+/// Replay targets in one already-fetched checkpoint, in transaction order.
+///
+/// Lets callers stream discovery checkpoint by checkpoint and reuse the fetched
+/// `CheckpointData` for replay. `package_filter` must already be normalized.
+pub fn checkpoint_ptb_targets(
+    checkpoint: u64,
+    checkpoint_data: &CheckpointData,
+    package_filter: Option<&str>,
+    include_framework: bool,
+) -> Vec<DiscoverTarget> {
+    checkpoint_data
+        .transactions
+        .iter()
+        .filter_map(|tx| {
+            let TransactionKind::ProgrammableTransaction(ptb) =
+                tx.transaction.data().transaction_data().kind()
+            else {
+                return None;
+            };
+            ptb_target(checkpoint, tx, ptb, package_filter, include_framework)
+        })
+        .collect()
+}
+
+fn ptb_target(
+    checkpoint: u64,
+    tx: &CheckpointTransaction,
+    ptb: &ProgrammableTransaction,
+    package_filter: Option<&str>,
+    include_framework: bool,
+) -> Option<DiscoverTarget> {
+    let filter_is_framework = package_filter.map(is_framework_package_id).unwrap_or(false);
+    let mut move_calls = Vec::new();
+    let mut package_ids: BTreeSet<String> = BTreeSet::new();
+    for (command_index, command) in ptb.commands.iter().enumerate() {
+        let SuiCommand::MoveCall(call) = command else {
+            continue;
+        };
+        let package = normalize_package_id(&call.package.to_hex_uncompressed())
+            .unwrap_or_else(|_| call.package.to_hex_uncompressed());
+        let matches_filter = package_filter
+            .map(|filter| filter == package)
+            .unwrap_or(true);
+        if !matches_filter {
+            continue;
+        }
+        if !include_framework && !filter_is_framework && is_framework_package_id(&package) {
+            continue;
+        }
+        package_ids.insert(package.clone());
+        move_calls.push(DiscoverMoveCall {
+            command_index,
+            package,
+            module: call.module.to_string(),
+            function: call.function.to_string(),
+        });
+    }
+    if move_calls.is_empty() {
+        return None;
+    }
+    let tx_data = tx.transaction.data().transaction_data();
+    Some(DiscoverTarget {
+        checkpoint,
+        digest: tx.transaction.digest().to_string(),
+        sender: tx_data.sender().to_string(),
+        commands: ptb.commands.len(),
+        input_objects: tx.input_objects.len(),
+        output_objects: tx.output_objects.len(),
+        package_ids: package_ids.into_iter().collect(),
+        move_calls,
+    })
+}
+
 /// Resolve digest/checkpoint for replay when digest was omitted and discovery is requested.
 pub fn resolve_replay_target_from_discovery(
     digest: Option<&str>,
@@ -345,6 +399,15 @@ fn is_framework_package_id(package: &str) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn groups_consecutive_checkpoints_into_runs() {
+        assert_eq!(
+            contiguous_checkpoint_runs(&[5, 6, 7, 9, 12, 13, 11]),
+            vec![(5, 7), (9, 9), (12, 13), (11, 11)]
+        );
+        assert!(contiguous_checkpoint_runs(&[]).is_empty());
+    }
+
     #[test]
     fn parses_checkpoint_range_spec() {
         let checkpoints = parse_checkpoint_spec("239615920..239615923").expect("range parse");
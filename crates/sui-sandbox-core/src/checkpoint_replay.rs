@@ -0,0 +1,510 @@
+//! Replay every transaction of a checkpoint in order and reconcile all effects.
+//!
+//! Transactions run one after another against a shared, evolving object store.
+//! Each transaction's inputs come from the checkpoint, except that an object an
+//! earlier transaction wrote locally is read from the store instead. A divergence
+//! in one transaction therefore carries into every later transaction that reads
+//! its objects, the same way it would on chain.
+//!
+//! System transactions (consensus commit prologue, authenticator state and
+//! randomness updates) are not executed; their on-chain outputs are applied to
+//! later transactions via [`apply_checkpoint_prologue`].
+//!
+//! Local outputs enter the store only when the local replay succeeded and the
+//! transaction did not fail on chain. Otherwise, the objects the transaction read
+//! or wrote are dropped from the store, so later transactions fall back to their
+//! on-chain versions. Gas coins are never stored: the replay does not charge gas.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::Instant;
+
+use anyhow::Result;
+use move_core_types::account_address::AccountAddress;
+use serde::Serialize;
+use sui_sandbox_types::TransactionStatus;
+use sui_state_fetcher::{
+    apply_checkpoint_prologue, checkpoint_to_replay_state, ReplayState, VersionedObject,
+};
+use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
+use sui_types::transaction::{TransactionDataAPI, TransactionKind};
+
+use crate::effects_reconciliation::{reconcile_effects, EffectsReconciliation, MismatchKind};
+use crate::ptb::{ObjectChange, Owner, TransactionEffects};
+use crate::replay_support::execute_replay_state;
+use crate::tx_replay::{EffectsReconcilePolicy, ReplayExecution};
+
+/// Options for [`replay_checkpoint`].
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointReplayOptions {
+    /// Reconcile each transaction's local effects with its on-chain effects.
+    pub compare: bool,
+    /// Execute with this protocol version instead of the one
+    /// [`checkpoint_to_replay_state`] assigns.
+    pub protocol_version: Option<u64>,
+    pub verbose: bool,
+}
+
+/// Outcome of one transaction of the checkpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckpointTransactionReplay {
+    /// Position of the transaction in the checkpoint.
+    pub index: usize,
+    pub digest: String,
+    /// Transaction kind, e.g. `ProgrammableTransaction` or `ConsensusCommitPrologueV3`.
+    pub kind: &'static str,
+    /// Whether the transaction was executed locally (system transactions are not).
+    pub executed: bool,
+    pub local_success: bool,
+    /// On-chain status, when the checkpoint carried effects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_chain_success: Option<bool>,
+    /// Input objects read from earlier local replays instead of the checkpoint.
+    pub chained_inputs: usize,
+    /// Chained inputs whose local contents differ from the checkpoint's.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diverged_inputs: Vec<String>,
+    pub elapsed_ms: f64,
+    /// Hydration error, or the local execution error of a failed replay.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Object-by-object comparison with the on-chain effects (`compare` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconciliation: Option<EffectsReconciliation>,
+}
+
+impl CheckpointTransactionReplay {
+    /// Executed, but the local status differs from the on-chain status.
+    pub fn status_mismatch(&self) -> bool {
+        self.executed
+            && self
+                .on_chain_success
+                .is_some_and(|on_chain| on_chain != self.local_success)
+    }
+}
+
+/// Aggregate counts over a checkpoint replay.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckpointReplaySummary {
+    pub transactions: usize,
+    pub executed: usize,
+    /// System transactions that were not executed.
+    pub skipped: usize,
+    /// Transactions that errored before execution (e.g. hydration failures).
+    pub errored: usize,
+    pub local_success: usize,
+    /// Executed transactions whose local status differs from the on-chain status.
+    pub status_mismatches: usize,
+    /// Transactions that read at least one object from an earlier local replay.
+    pub chained: usize,
+    /// Transactions that read at least one diverged object.
+    pub diverged: usize,
+    /// Executed transactions reconciled against on-chain effects.
+    pub reconciled: usize,
+    /// Reconciled transactions whose effects match object by object.
+    pub effects_matched: usize,
+    /// Number of reconciliation mismatches per kind, over all transactions.
+    pub mismatch_counts: BTreeMap<MismatchKind, usize>,
+    pub wall_ms: f64,
+}
+
+/// Result of [`replay_checkpoint`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckpointReplayReport {
+    pub checkpoint: u64,
+    pub epoch: u64,
+    pub timestamp_ms: u64,
+    /// Per-transaction outcomes, in checkpoint order.
+    pub transactions: Vec<CheckpointTransactionReplay>,
+    pub summary: CheckpointReplaySummary,
+    /// Objects held in the local object store after the last transaction.
+    pub store_objects: usize,
+}
+
+/// Replay all transactions of `checkpoint_data` in order against one evolving
+/// object store. Per-transaction failures are recorded, not fatal.
+pub fn replay_checkpoint(
+    checkpoint_data: &CheckpointData,
+    options: &CheckpointReplayOptions,
+) -> CheckpointReplayReport {
+    let start = Instant::now();
+    let mut store = ObjectStore::default();
+    let mut transactions = Vec::with_capacity(checkpoint_data.transactions.len());
+
+    for (index, checkpoint_tx) in checkpoint_data.transactions.iter().enumerate() {
+        let tx_start = Instant::now();
+        let kind = checkpoint_tx.transaction.data().transaction_data().kind();
+        let mut entry = CheckpointTransactionReplay {
+            index,
+            digest: checkpoint_tx.transaction.digest().to_string(),
+            kind: kind.name(),
+            executed: false,
+            local_success: false,
+            on_chain_success: None,
+            chained_inputs: 0,
+            diverged_inputs: Vec::new(),
+            elapsed_ms: 0.0,
+            error: None,
+            reconciliation: None,
+        };
+
+        if matches!(kind, TransactionKind::ProgrammableTransaction(_)) {
+            match replay_transaction(checkpoint_data, &store, &mut entry, options) {
+                Ok((state, execution)) => {
+                    entry.executed = true;
+                    entry.local_success = execution.result.local_success;
+                    entry.error = execution.result.local_error.clone();
+                    let gas_objects: Vec<String> = state
+                        .transaction
+                        .gas_payment
+                        .iter()
+                        .map(|gas| gas.object_id.clone())
+                        .collect();
+                    if options.compare {
+                        entry.reconciliation = state.transaction.effects.as_ref().map(|on_chain| {
+                            reconcile_effects(on_chain, &execution.effects, &gas_objects)
+                        });
+                    }
+                    if entry.local_success && entry.on_chain_success != Some(false) {
+                        store.apply(&execution.effects, &parse_ids(&gas_objects));
+                    } else {
+                        store.invalidate(checkpoint_tx);
+                    }
+                }
+                Err(err) => {
+                    entry.error = Some(format!("{:#}", err));
+                    store.invalidate(checkpoint_tx);
+                }
+            }
+            if options.verbose {
+                eprintln!(
+                    "[checkpoint_replay] {}/{} {} local_success={} chained_inputs={}{}",
+                    index + 1,
+                    checkpoint_data.transactions.len(),
+                    entry.digest,
+                    entry.local_success,
+                    entry.chained_inputs,
+                    entry
+                        .error
+                        .as_deref()
+                        .map(|error| format!(" error={}", error))
+                        .unwrap_or_default()
+                );
+            }
+        }
+
+        entry.elapsed_ms = tx_start.elapsed().as_secs_f64() * 1000.0;
+        transactions.push(entry);
+    }
+
+    let mut summary = summarize(&transactions);
+    summary.wall_ms = start.elapsed().as_secs_f64() * 1000.0;
+    CheckpointReplayReport {
+        checkpoint: checkpoint_data.checkpoint_summary.sequence_number,
+        epoch: checkpoint_data.checkpoint_summary.epoch,
+        timestamp_ms: checkpoint_data.checkpoint_summary.timestamp_ms,
+        transactions,
+        summary,
+        store_objects: store.objects.len(),
+    }
+}
+
+/// Hydrate one programmable transaction from the checkpoint, overlay the store,
+/// and execute it.
+fn replay_transaction(
+    checkpoint_data: &CheckpointData,
+    store: &ObjectStore,
+    entry: &mut CheckpointTransactionReplay,
+    options: &CheckpointReplayOptions,
+) -> Result<(ReplayState, ReplayExecution)> {
+    let mut state = checkpoint_to_replay_state(checkpoint_data, &entry.digest)?;
+    apply_checkpoint_prologue(checkpoint_data, &entry.digest, &mut state)?;
+    if let Some(version) = options.protocol_version {
+        state.protocol_version = version;
+    }
+    entry.on_chain_success = state
+        .transaction
+        .effects
+        .as_ref()
+        .map(|effects| matches!(effects.status, TransactionStatus::Success));
+
+    let overlay = store.overlay(&mut state.objects);
+    entry.chained_inputs = overlay.chained;
+    entry.diverged_inputs = overlay
+        .diverged
+        .iter()
+        .map(AccountAddress::to_hex_literal)
+        .collect();
+
+    let execution = execute_replay_state(&state, EffectsReconcilePolicy::Strict, options.verbose)?;
+    Ok((state, execution))
+}
+
+fn summarize(transactions: &[CheckpointTransactionReplay]) -> CheckpointReplaySummary {
+    let mut summary = CheckpointReplaySummary {
+        transactions: transactions.len(),
+        ..Default::default()
+    };
+    for tx in transactions {
+        if tx.executed {
+            summary.executed += 1;
+        } else if tx.error.is_some() {
+            summary.errored += 1;
+        } else {
+            summary.skipped += 1;
+        }
+        summary.local_success += usize::from(tx.local_success);
+        summary.status_mismatches += usize::from(tx.status_mismatch());
+        summary.chained += usize::from(tx.chained_inputs > 0);
+        summary.diverged += usize::from(!tx.diverged_inputs.is_empty());
+        if let Some(reconciliation) = &tx.reconciliation {
+            summary.reconciled += 1;
+            summary.effects_matched += usize::from(reconciliation.is_match());
+            for (kind, count) in &reconciliation.counts {
+                *summary.mismatch_counts.entry(*kind).or_default() += count;
+            }
+        }
+    }
+    summary
+}
+
+fn parse_ids(ids: &[String]) -> BTreeSet<AccountAddress> {
+    ids.iter()
+        .filter_map(|id| AccountAddress::from_hex_literal(id).ok())
+        .collect()
+}
+
+/// Which of a transaction's inputs [`ObjectStore::overlay`] replaced.
+#[derive(Debug, Default)]
+struct Overlay {
+    chained: usize,
+    diverged: Vec<AccountAddress>,
+}
+
+/// Latest local contents of every object written so far in the checkpoint.
+#[derive(Debug, Default)]
+struct ObjectStore {
+    objects: HashMap<AccountAddress, VersionedObject>,
+}
+
+impl ObjectStore {
+    /// Replace the contents of inputs the store holds with their local contents.
+    ///
+    /// The checkpoint's version and type are kept so the transaction's object
+    /// references still resolve; the digest is cleared because it no longer
+    /// describes the contents.
+    fn overlay(&self, inputs: &mut HashMap<AccountAddress, VersionedObject>) -> Overlay {
+        let mut overlay = Overlay::default();
+        for (id, input) in inputs.iter_mut() {
+            let Some(local) = self.objects.get(id) else {
+                continue;
+            };
+            overlay.chained += 1;
+            if local.bcs_bytes != input.bcs_bytes {
+                overlay.diverged.push(*id);
+            }
+            input.bcs_bytes = local.bcs_bytes.clone();
+            if input.type_tag.is_none() {
+                input.type_tag = local.type_tag.clone();
+            }
+            input.digest = None;
+        }
+        overlay.diverged.sort();
+        overlay
+    }
+
+    /// Record the objects a successful local replay created, mutated, or removed.
+    fn apply(&mut self, effects: &TransactionEffects, gas_objects: &BTreeSet<AccountAddress>) {
+        // id -> (type, (is_shared, is_immutable)) as reported by the object changes.
+        let mut changes: HashMap<AccountAddress, (Option<String>, Option<(bool, bool)>)> =
+            HashMap::new();
+        for change in &effects.object_changes {
+            let (id, object_type, flags) = match change {
+                ObjectChange::Created {
+                    id,
+                    owner,
+                    object_type,
+                }
+                | ObjectChange::Mutated {
+                    id,
+                    owner,
+                    object_type,
+                }
+                | ObjectChange::Unwrapped {
+                    id,
+                    owner,
+                    object_type,
+                } => (
+                    id,
+                    object_type,
+                    Some((
+                        matches!(owner, Owner::Shared),
+                        matches!(owner, Owner::Immutable),
+                    )),
+                ),
+                ObjectChange::Transferred {
+                    id, object_type, ..
+                } => (id, object_type, Some((false, false))),
+                ObjectChange::Deleted { id, object_type }
+                | ObjectChange::Wrapped { id, object_type } => (id, object_type, None),
+            };
+            let entry = changes.entry(*id).or_default();
+            if let Some(object_type) = object_type {
+                entry.0 = Some(object_type.to_canonical_string(true));
+            }
+            if flags.is_some() {
+                entry.1 = flags;
+            }
+        }
+
+        for id in effects.deleted.iter().chain(&effects.wrapped) {
+            self.objects.remove(id);
+        }
+        let written = effects
+            .created_object_bytes
+            .iter()
+            .chain(&effects.mutated_object_bytes);
+        for (id, bytes) in written {
+            if gas_objects.contains(id) {
+                continue;
+            }
+            let (type_tag, flags) = changes.remove(id).unwrap_or_default();
+            let previous = self.objects.remove(id);
+            let version = effects
+                .object_versions
+                .as_ref()
+                .and_then(|versions| versions.get(id))
+                .map(|info| info.output_version)
+                .or(effects.lamport_timestamp)
+                .or(previous.as_ref().map(|object| object.version))
+                .unwrap_or_default();
+            let (is_shared, is_immutable) = flags
+                .or_else(|| {
+                    previous
+                        .as_ref()
+                        .map(|object| (object.is_shared, object.is_immutable))
+                })
+                .unwrap_or_default();
+            self.objects.insert(
+                *id,
+                VersionedObject {
+                    id: *id,
+                    version,
+                    digest: None,
+                    type_tag: type_tag.or_else(|| previous.and_then(|object| object.type_tag)),
+                    bcs_bytes: bytes.clone(),
+                    is_shared,
+                    is_immutable,
+                },
+            );
+        }
+        for id in gas_objects {
+            self.objects.remove(id);
+        }
+    }
+
+    /// Forget every object `checkpoint_tx` read or wrote on chain, so later
+    /// transactions use the checkpoint's versions of them.
+    fn invalidate(&mut self, checkpoint_tx: &CheckpointTransaction) {
+        for object in checkpoint_tx
+            .input_objects
+            .iter()
+            .chain(&checkpoint_tx.output_objects)
+        {
+            self.objects.remove(&AccountAddress::from(object.id()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(id: AccountAddress, version: u64, bytes: &[u8]) -> VersionedObject {
+        VersionedObject {
+            id,
+            version,
+            digest: Some("digest".to_string()),
+            type_tag: Some("0x2::m::T".to_string()),
+            bcs_bytes: bytes.to_vec(),
+            is_shared: false,
+            is_immutable: false,
+        }
+    }
+
+    #[test]
+    fn test_store_chains_local_outputs_into_later_inputs() {
+        let shared = AccountAddress::from_hex_literal("0xa").unwrap();
+        let created = AccountAddress::from_hex_literal("0xb").unwrap();
+        let deleted = AccountAddress::from_hex_literal("0xc").unwrap();
+        let gas = AccountAddress::from_hex_literal("0xd").unwrap();
+
+        let mut store = ObjectStore::default();
+        store.objects.insert(deleted, object(deleted, 3, &[9]));
+        let effects = TransactionEffects {
+            object_changes: vec![ObjectChange::Mutated {
+                id: shared,
+                owner: Owner::Shared,
+                object_type: None,
+            }],
+            deleted: vec![deleted],
+            mutated_object_bytes: HashMap::from([(shared, vec![2]), (gas, vec![7])]),
+            created_object_bytes: HashMap::from([(created, vec![5])]),
+            lamport_timestamp: Some(11),
+            ..Default::default()
+        };
+        store.apply(&effects, &BTreeSet::from([gas]));
+
+        assert_eq!(store.objects.len(), 2);
+        assert!(store.objects[&shared].is_shared);
+        assert_eq!(store.objects[&created].version, 11);
+        assert!(!store.objects.contains_key(&gas));
+        assert!(!store.objects.contains_key(&deleted));
+
+        // The next transaction's checkpoint inputs: `shared` matches the local
+        // output, `created` diverged, and `gas` is not in the store.
+        let mut inputs = HashMap::from([
+            (shared, object(shared, 10, &[2])),
+            (created, object(created, 11, &[6])),
+            (gas, object(gas, 10, &[8])),
+        ]);
+        let overlay = store.overlay(&mut inputs);
+        assert_eq!(overlay.chained, 2);
+        assert_eq!(overlay.diverged, vec![created]);
+        assert_eq!(inputs[&created].bcs_bytes, vec![5]);
+        assert_eq!(inputs[&created].version, 11);
+        assert_eq!(inputs[&created].digest, None);
+        assert_eq!(inputs[&gas].bcs_bytes, vec![8]);
+    }
+
+    #[test]
+    fn test_summarize_counts_outcomes() {
+        let tx = |executed: bool, local: bool, on_chain: Option<bool>, error: Option<&str>| {
+            CheckpointTransactionReplay {
+                index: 0,
+                digest: String::new(),
+                kind: "ProgrammableTransaction",
+                executed,
+                local_success: local,
+                on_chain_success: on_chain,
+                chained_inputs: usize::from(executed),
+                diverged_inputs: Vec::new(),
+                elapsed_ms: 0.0,
+                error: error.map(str::to_string),
+                reconciliation: None,
+            }
+        };
+        let summary = summarize(&[
+            tx(false, false, None, None),
+            tx(true, true, Some(true), None),
+            tx(true, false, Some(true), Some("abort")),
+            tx(false, false, None, Some("hydration failed")),
+        ]);
+        assert_eq!(summary.transactions, 4);
+        assert_eq!(summary.executed, 2);
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(summary.errored, 1);
+        assert_eq!(summary.local_success, 1);
+        assert_eq!(summary.status_mismatches, 1);
+        assert_eq!(summary.chained, 2);
+    }
+}
@@ -0,0 +1,520 @@
+//! Object-by-object reconciliation of local and on-chain effects.
+//!
+//! [`EffectsComparison`](sui_sandbox_types::EffectsComparison) only says whether the
+//! created/mutated/deleted *counts* agree. This module lines the two sides up by object
+//! ID instead and lists every divergence with a classification:
+//!
+//! ```text
+//! {"object_id":"0x5f..","kind":"missing-create","detail":"created on-chain, not created locally"}
+//! {"object_id":"0x9a..","kind":"version-skew","detail":"input version on-chain 41, local 40"}
+//! {"object_id":"0x3c..","kind":"spurious-mutation","detail":"mutated locally, unchanged on-chain"}
+//! ```
+//!
+//! Versions and owners are only compared when the on-chain effects carry them
+//! ([`TransactionEffectsSummary::object_changes`], populated for gRPC and Walrus
+//! sources) and the replay tracked versions. Digests are reported for context but never
+//! compared: the local executor's object digests are not Sui object digests.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use sui_resolver::normalize_address_short;
+use sui_sandbox_types::{ObjectChangeDetail, ObjectChangeKind, TransactionEffectsSummary};
+
+use crate::ptb::{ObjectChange, Owner, TransactionEffects};
+
+/// How one object's effects differ between the chain and the local replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MismatchKind {
+    /// Created on-chain, not created locally.
+    MissingCreate,
+    /// Created locally, not on-chain.
+    SpuriousCreate,
+    MissingMutation,
+    SpuriousMutation,
+    MissingDelete,
+    SpuriousDelete,
+    MissingWrap,
+    SpuriousWrap,
+    MissingUnwrap,
+    SpuriousUnwrap,
+    /// Changed on both sides, but differently (e.g. deleted on-chain, wrapped locally).
+    ChangeKindMismatch,
+    /// Same change, different input or output version.
+    VersionSkew,
+    /// Same change, different owner afterwards.
+    OwnerMismatch,
+}
+
+impl MismatchKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MissingCreate => "missing-create",
+            Self::SpuriousCreate => "spurious-create",
+            Self::MissingMutation => "missing-mutation",
+            Self::SpuriousMutation => "spurious-mutation",
+            Self::MissingDelete => "missing-delete",
+            Self::SpuriousDelete => "spurious-delete",
+            Self::MissingWrap => "missing-wrap",
+            Self::SpuriousWrap => "spurious-wrap",
+            Self::MissingUnwrap => "missing-unwrap",
+            Self::SpuriousUnwrap => "spurious-unwrap",
+            Self::ChangeKindMismatch => "change-kind-mismatch",
+            Self::VersionSkew => "version-skew",
+            Self::OwnerMismatch => "owner-mismatch",
+        }
+    }
+
+    fn missing(change: ObjectChangeKind) -> Self {
+        match change {
+            ObjectChangeKind::Created => Self::MissingCreate,
+            ObjectChangeKind::Mutated => Self::MissingMutation,
+            ObjectChangeKind::Deleted => Self::MissingDelete,
+            ObjectChangeKind::Wrapped => Self::MissingWrap,
+            ObjectChangeKind::Unwrapped => Self::MissingUnwrap,
+        }
+    }
+
+    fn spurious(change: ObjectChangeKind) -> Self {
+        match change {
+            ObjectChangeKind::Created => Self::SpuriousCreate,
+            ObjectChangeKind::Mutated => Self::SpuriousMutation,
+            ObjectChangeKind::Deleted => Self::SpuriousDelete,
+            ObjectChangeKind::Wrapped => Self::SpuriousWrap,
+            ObjectChangeKind::Unwrapped => Self::SpuriousUnwrap,
+        }
+    }
+}
+
+/// One object whose effects differ.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ObjectMismatch {
+    pub object_id: String,
+    pub kind: MismatchKind,
+    /// The object's on-chain change, if it changed on-chain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_chain: Option<ObjectChangeDetail>,
+    /// The object's local change, if it changed locally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<ObjectChangeDetail>,
+    pub detail: String,
+}
+
+/// Result of [`reconcile_effects`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EffectsReconciliation {
+    /// Objects changed on either side (gas coins excluded).
+    pub objects_compared: usize,
+    /// Objects with no mismatch.
+    pub objects_matched: usize,
+    /// Whether on-chain versions and owners were available; when false only the
+    /// change kind of each object is compared.
+    pub detailed: bool,
+    pub mismatches: Vec<ObjectMismatch>,
+    /// Number of mismatches per kind.
+    pub counts: BTreeMap<MismatchKind, usize>,
+    /// Gas coins left out of the comparison (the replay does not charge them).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ignored_gas_objects: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+}
+
+impl EffectsReconciliation {
+    pub fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Compare on-chain effects with the effects of a local replay, object by object.
+///
+/// `gas_objects` are the transaction's gas payment coins; they are excluded because
+/// the replay does not smash or charge them the way validators do.
+pub fn reconcile_effects(
+    on_chain: &TransactionEffectsSummary,
+    local: &TransactionEffects,
+    gas_objects: &[String],
+) -> EffectsReconciliation {
+    let detailed = !on_chain.object_changes.is_empty();
+    let gas: BTreeSet<String> = gas_objects
+        .iter()
+        .map(|id| normalize_address_short(id))
+        .collect();
+
+    let on_chain_changes = index_changes(if detailed {
+        on_chain.object_changes.clone()
+    } else {
+        summary_object_changes(on_chain)
+    });
+    let local_changes = index_changes(local_object_changes(local));
+
+    let ids: BTreeSet<&String> = on_chain_changes
+        .keys()
+        .chain(local_changes.keys())
+        .collect();
+    let mut ignored_gas_objects = Vec::new();
+    let mut mismatches = Vec::new();
+    let mut objects_compared = 0;
+    let mut objects_matched = 0;
+    for id in ids {
+        if gas.contains(id) {
+            ignored_gas_objects.push(id.clone());
+            continue;
+        }
+        objects_compared += 1;
+        let before = mismatches.len();
+        reconcile_object(
+            id,
+            on_chain_changes.get(id),
+            local_changes.get(id),
+            &mut mismatches,
+        );
+        if mismatches.len() == before {
+            objects_matched += 1;
+        }
+    }
+
+    let mut counts = BTreeMap::new();
+    for mismatch in &mismatches {
+        *counts.entry(mismatch.kind).or_insert(0) += 1;
+    }
+
+    let mut notes = Vec::new();
+    if !detailed {
+        notes.push(
+            "on-chain effects list object IDs only; versions and owners were not compared"
+                .to_string(),
+        );
+    } else if local.object_versions.is_none() {
+        notes.push("replay did not track versions; versions were not compared".to_string());
+    }
+
+    EffectsReconciliation {
+        objects_compared,
+        objects_matched,
+        detailed,
+        mismatches,
+        counts,
+        ignored_gas_objects,
+        notes,
+    }
+}
+
+fn reconcile_object(
+    id: &str,
+    on_chain: Option<&ObjectChangeDetail>,
+    local: Option<&ObjectChangeDetail>,
+    mismatches: &mut Vec<ObjectMismatch>,
+) {
+    let mut push = |kind, detail: String| {
+        mismatches.push(ObjectMismatch {
+            object_id: id.to_string(),
+            kind,
+            on_chain: on_chain.cloned(),
+            local: local.cloned(),
+            detail,
+        })
+    };
+
+    let (on_chain, local) = match (on_chain, local) {
+        (Some(on_chain), Some(local)) => (on_chain, local),
+        (Some(on_chain), None) => {
+            let change = change_label(on_chain.change);
+            push(
+                MismatchKind::missing(on_chain.change),
+                format!("{} on-chain, not {} locally", change, change),
+            );
+            return;
+        }
+        (None, Some(local)) => {
+            push(
+                MismatchKind::spurious(local.change),
+                format!("{} locally, unchanged on-chain", change_label(local.change)),
+            );
+            return;
+        }
+        (None, None) => return,
+    };
+
+    if on_chain.change != local.change {
+        push(
+            MismatchKind::ChangeKindMismatch,
+            format!(
+                "{} on-chain, {} locally",
+                change_label(on_chain.change),
+                change_label(local.change)
+            ),
+        );
+        return;
+    }
+
+    let mut skew = Vec::new();
+    for (label, on_chain_version, local_version) in [
+        ("input", on_chain.input_version, local.input_version),
+        ("output", on_chain.output_version, local.output_version),
+    ] {
+        if let (Some(on_chain_version), Some(local_version)) = (on_chain_version, local_version) {
+            if on_chain_version != local_version {
+                skew.push(format!(
+                    "{} version on-chain {}, local {}",
+                    label, on_chain_version, local_version
+                ));
+            }
+        }
+    }
+    if !skew.is_empty() {
+        push(MismatchKind::VersionSkew, skew.join("; "));
+    }
+
+    if let (Some(on_chain_owner), Some(local_owner)) = (&on_chain.owner, &local.owner) {
+        if owner_key(on_chain_owner) != owner_key(local_owner) {
+            push(
+                MismatchKind::OwnerMismatch,
+                format!("owner on-chain {}, local {}", on_chain_owner, local_owner),
+            );
+        }
+    }
+}
+
+fn change_label(change: ObjectChangeKind) -> &'static str {
+    match change {
+        ObjectChangeKind::Created => "created",
+        ObjectChangeKind::Mutated => "mutated",
+        ObjectChangeKind::Deleted => "deleted",
+        ObjectChangeKind::Wrapped => "wrapped",
+        ObjectChangeKind::Unwrapped => "unwrapped",
+    }
+}
+
+/// The local executor records object-owned children as address-owned, so owners are
+/// compared by the owning address alone.
+fn owner_key(owner: &str) -> String {
+    match owner
+        .strip_prefix("address:")
+        .or_else(|| owner.strip_prefix("object:"))
+    {
+        Some(address) => normalize_address_short(address),
+        None => owner.to_string(),
+    }
+}
+
+/// Key changes by normalized object ID; the first entry for an ID wins.
+fn index_changes(changes: Vec<ObjectChangeDetail>) -> BTreeMap<String, ObjectChangeDetail> {
+    let mut indexed = BTreeMap::new();
+    for mut change in changes {
+        change.object_id = normalize_address_short(&change.object_id);
+        indexed.entry(change.object_id.clone()).or_insert(change);
+    }
+    indexed
+}
+
+/// Per-object changes from ID-only effects.
+fn summary_object_changes(summary: &TransactionEffectsSummary) -> Vec<ObjectChangeDetail> {
+    [
+        (ObjectChangeKind::Created, &summary.created),
+        (ObjectChangeKind::Mutated, &summary.mutated),
+        (ObjectChangeKind::Deleted, &summary.deleted),
+        (ObjectChangeKind::Wrapped, &summary.wrapped),
+        (ObjectChangeKind::Unwrapped, &summary.unwrapped),
+    ]
+    .into_iter()
+    .flat_map(|(change, ids)| {
+        ids.iter().map(move |id| ObjectChangeDetail {
+            object_id: id.clone(),
+            change,
+            input_version: None,
+            output_version: None,
+            input_digest: None,
+            output_digest: None,
+            owner: None,
+        })
+    })
+    .collect()
+}
+
+/// Per-object changes of a local execution.
+///
+/// Versions come from `object_versions` (version tracking), owners from
+/// `object_changes`. Objects that were only transferred count as mutated, as they do
+/// on-chain. Digests are left out: they are not Sui object digests.
+pub fn local_object_changes(effects: &TransactionEffects) -> Vec<ObjectChangeDetail> {
+    let mut owners = BTreeMap::new();
+    for change in &effects.object_changes {
+        match change {
+            ObjectChange::Created { id, owner, .. }
+            | ObjectChange::Mutated { id, owner, .. }
+            | ObjectChange::Unwrapped { id, owner, .. } => {
+                owners.entry(*id).or_insert_with(|| owner_label(owner));
+            }
+            ObjectChange::Transferred { id, recipient, .. } => {
+                owners.insert(*id, format!("address:{}", recipient.to_hex_literal()));
+            }
+            ObjectChange::Deleted { .. } | ObjectChange::Wrapped { .. } => {}
+        }
+    }
+
+    let mut seen = BTreeSet::new();
+    let mut changes = Vec::new();
+    for (change, ids) in [
+        (ObjectChangeKind::Created, &effects.created),
+        (ObjectChangeKind::Mutated, &effects.mutated),
+        (ObjectChangeKind::Deleted, &effects.deleted),
+        (ObjectChangeKind::Wrapped, &effects.wrapped),
+        (ObjectChangeKind::Unwrapped, &effects.unwrapped),
+        (ObjectChangeKind::Mutated, &effects.transferred),
+    ] {
+        for id in ids {
+            if !seen.insert(*id) {
+                continue;
+            }
+            let versions = effects
+                .object_versions
+                .as_ref()
+                .and_then(|versions| versions.get(id));
+            let exists_after = matches!(
+                change,
+                ObjectChangeKind::Created | ObjectChangeKind::Mutated | ObjectChangeKind::Unwrapped
+            );
+            changes.push(ObjectChangeDetail {
+                object_id: id.to_hex_literal(),
+                change,
+                input_version: versions.and_then(|info| info.input_version),
+                output_version: versions.map(|info| info.output_version),
+                input_digest: None,
+                output_digest: None,
+                owner: owners.get(id).cloned().filter(|_| exists_after),
+            });
+        }
+    }
+    changes
+}
+
+fn owner_label(owner: &Owner) -> String {
+    match owner {
+        Owner::Address(address) => format!("address:{}", address.to_hex_literal()),
+        Owner::Shared => "shared".to_string(),
+        Owner::Immutable => "immutable".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ptb::{ObjectID, ObjectVersionInfo, VersionChangeType};
+    use move_core_types::account_address::AccountAddress;
+    use std::collections::HashMap;
+    use sui_sandbox_types::{GasSummary, TransactionStatus};
+
+    fn detail(
+        id: &str,
+        change: ObjectChangeKind,
+        input_version: Option<u64>,
+        owner: &str,
+    ) -> ObjectChangeDetail {
+        ObjectChangeDetail {
+            object_id: id.to_string(),
+            change,
+            input_version,
+            output_version: Some(50),
+            input_digest: None,
+            output_digest: Some("digest".to_string()),
+            owner: Some(owner.to_string()),
+        }
+    }
+
+    fn version(input_version: Option<u64>, change_type: VersionChangeType) -> ObjectVersionInfo {
+        ObjectVersionInfo {
+            input_version,
+            output_version: 50,
+            input_digest: None,
+            output_digest: [0; 32],
+            change_type,
+        }
+    }
+
+    #[test]
+    fn reconcile_classifies_each_mismatch() {
+        let id = |n: u8| ObjectID::from_hex_literal(&format!("0x{:x}", n)).unwrap();
+        let on_chain = TransactionEffectsSummary {
+            status: TransactionStatus::Success,
+            created: vec![],
+            mutated: vec![],
+            deleted: vec![],
+            wrapped: vec![],
+            unwrapped: vec![],
+            gas_used: GasSummary::default(),
+            events_count: 0,
+            shared_object_versions: HashMap::new(),
+            object_changes: vec![
+                detail("0xa", ObjectChangeKind::Created, None, "address:0xb0b"),
+                detail("0xb", ObjectChangeKind::Mutated, Some(41), "shared"),
+                detail("0xc", ObjectChangeKind::Deleted, Some(7), "shared"),
+                detail("0xd", ObjectChangeKind::Mutated, Some(3), "object:0xff"),
+                detail(
+                    "0x00000000000000000000000000000000000000000000000000000000000000ee",
+                    ObjectChangeKind::Mutated,
+                    Some(1),
+                    "address:0xb0b",
+                ),
+            ],
+        };
+        let local = TransactionEffects {
+            mutated: vec![id(0xb), id(0xd), id(0xee)],
+            wrapped: vec![id(0xc)],
+            object_changes: vec![
+                ObjectChange::Mutated {
+                    id: id(0xb),
+                    owner: Owner::Shared,
+                    object_type: None,
+                },
+                ObjectChange::Mutated {
+                    id: id(0xd),
+                    owner: Owner::Address(AccountAddress::from_hex_literal("0xff").unwrap()),
+                    object_type: None,
+                },
+            ],
+            object_versions: Some(HashMap::from([
+                (id(0xb), version(Some(40), VersionChangeType::Mutated)),
+                (id(0xd), version(Some(3), VersionChangeType::Mutated)),
+            ])),
+            ..Default::default()
+        };
+
+        let report = reconcile_effects(&on_chain, &local, &["0xee".to_string()]);
+        let kinds: Vec<(&str, MismatchKind)> = report
+            .mismatches
+            .iter()
+            .map(|mismatch| (mismatch.object_id.as_str(), mismatch.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("0xa", MismatchKind::MissingCreate),
+                ("0xb", MismatchKind::VersionSkew),
+                ("0xc", MismatchKind::ChangeKindMismatch),
+            ]
+        );
+        assert_eq!(
+            report.mismatches[1].detail,
+            "input version on-chain 41, local 40"
+        );
+        assert!(report.detailed);
+        assert_eq!(report.objects_compared, 4);
+        assert_eq!(report.objects_matched, 1);
+        assert_eq!(report.ignored_gas_objects, vec!["0xee".to_string()]);
+        assert_eq!(report.counts[&MismatchKind::MissingCreate], 1);
+
+        // ID-only on-chain effects still classify presence.
+        let ids_only = TransactionEffectsSummary {
+            mutated: vec!["0xb".to_string()],
+            object_changes: vec![],
+            ..on_chain
+        };
+        let report = reconcile_effects(&ids_only, &local, &[]);
+        assert!(!report.detailed);
+        assert_eq!(report.counts.get(&MismatchKind::SpuriousMutation), Some(&2));
+        assert_eq!(report.counts.get(&MismatchKind::SpuriousWrap), Some(&1));
+        assert_eq!(report.counts.len(), 2);
+    }
+}
@@ -385,13 +385,17 @@ pub struct TransactionAbortInfo {
     pub abort_code: u64,
 
     /// The constant name used for this abort code (e.g., "E_INSUFFICIENT_BALANCE").
-    /// Only available when the transaction was fetched via gRPC and the abort
-    /// used a named constant (CleverError).
+    /// Only available when the abort used a named constant (CleverError), either
+    /// reported via gRPC or decoded from the aborting module's bytecode.
     pub constant_name: Option<String>,
 
     /// Human-readable interpretation of the abort code (if known)
     pub abort_meaning: Option<String>,
 
+    /// Source line of the abort, decoded from a CleverError abort code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_line: Option<u16>,
+
     /// Objects that were arguments to the aborting function
     pub involved_objects: Vec<String>,
 }
@@ -423,6 +427,7 @@ impl TransactionAbortInfo {
             abort_code: abort.abort_code,
             constant_name: abort.constant_name.clone(),
             abort_meaning,
+            source_line: None,
             involved_objects: Vec::new(),
         }
     }
@@ -627,6 +632,9 @@ impl fmt::Display for CommandErrorContext {
             } else {
                 writeln!(f, "  Abort code: {}", abort.abort_code)?;
             }
+            if let Some(line) = abort.source_line {
+                writeln!(f, "  Source line: {}", line)?;
+            }
             if let Some(ref meaning) = abort.abort_meaning {
                 writeln!(f, "  Meaning: {}", meaning)?;
             }
@@ -774,6 +782,7 @@ mod tests {
             abort_code: 1,
             constant_name: Some("E_INSUFFICIENT_BALANCE".into()),
             abort_meaning: Some("Insufficient balance".into()),
+            source_line: None,
             involved_objects: vec!["0x123".into()],
         };
 
@@ -0,0 +1,301 @@
+//! Instruction-level VM execution traces.
+//!
+//! With [`SimulationConfig::execution_trace`](crate::vm::SimulationConfig) set, every
+//! Move call the harness runs is executed with a `MoveTraceBuilder` and the resulting
+//! trace is appended to the harness's [`ExecutionTraceLog`]. Entries name modules and
+//! functions rather than handle indices so the trace reads on its own:
+//!
+//! ```text
+//! {"event":"enter","call":0,"depth":1,"module":"0x2::coin","function":"split",...}
+//! {"event":"instruction","call":0,"depth":1,"module":"0x2::coin","function":"split","pc":0,"instruction":"MOVE_LOC",...}
+//! {"event":"error","call":0,"depth":1,"message":"..."}
+//! ```
+//!
+//! Traces are large (one entry per executed instruction), so tracing is off by
+//! default and [`ExecutionTraceOptions::max_instructions`] caps the log.
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use move_trace_format::format::{Effect, MoveTrace, TraceEvent};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+
+/// Default cap on recorded instructions per harness.
+pub const DEFAULT_MAX_TRACED_INSTRUCTIONS: usize = 1_000_000;
+
+/// Longest rendering kept for a pushed operand-stack value.
+const MAX_VALUE_CHARS: usize = 160;
+
+/// What an execution trace records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionTraceOptions {
+    /// Record operand-stack depth and the values each instruction pushes.
+    #[serde(default)]
+    pub stack: bool,
+    /// Stop recording instructions after this many (frames and errors are still
+    /// recorded); `None` records everything.
+    #[serde(default)]
+    pub max_instructions: Option<usize>,
+}
+
+impl Default for ExecutionTraceOptions {
+    fn default() -> Self {
+        Self {
+            stack: false,
+            max_instructions: Some(DEFAULT_MAX_TRACED_INSTRUCTIONS),
+        }
+    }
+}
+
+/// One trace entry. `call` numbers the VM calls made by the harness (one per Move
+/// call command); `depth` is the call-stack depth within that call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TraceEntry {
+    Enter {
+        call: usize,
+        depth: usize,
+        module: String,
+        function: String,
+        type_args: Vec<String>,
+        native: bool,
+    },
+    Instruction {
+        call: usize,
+        depth: usize,
+        module: String,
+        function: String,
+        pc: u16,
+        instruction: String,
+        gas_left: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stack_depth: Option<usize>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        pushed: Vec<String>,
+    },
+    Exit {
+        call: usize,
+        depth: usize,
+        module: String,
+        function: String,
+    },
+    Error {
+        call: usize,
+        depth: usize,
+        message: String,
+    },
+}
+
+/// Counts for a recorded trace.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ExecutionTraceSummary {
+    pub calls: usize,
+    pub entries: usize,
+    pub instructions: usize,
+    /// Instructions dropped after `max_instructions` was reached.
+    pub dropped_instructions: usize,
+    pub errors: usize,
+}
+
+#[derive(Debug, Default)]
+struct TraceLogState {
+    entries: Vec<TraceEntry>,
+    summary: ExecutionTraceSummary,
+}
+
+/// Trace entries accumulated across the VM calls of one harness.
+#[derive(Debug, Default)]
+pub struct ExecutionTraceLog {
+    options: ExecutionTraceOptions,
+    state: Mutex<TraceLogState>,
+}
+
+impl ExecutionTraceLog {
+    pub fn new(options: ExecutionTraceOptions) -> Self {
+        Self {
+            options,
+            state: Mutex::new(TraceLogState::default()),
+        }
+    }
+
+    /// Append the events of one VM call.
+    pub fn record(&self, trace: MoveTrace) {
+        let mut state = self.state.lock();
+        let call = state.summary.calls;
+        state.summary.calls += 1;
+
+        // (frame id, module, function) for each open frame.
+        let mut frames: Vec<(usize, String, String)> = Vec::new();
+        let mut stack_depth = 0usize;
+        let mut recording = false;
+        for event in trace.events {
+            match event {
+                TraceEvent::OpenFrame { frame, .. } => {
+                    let module = format!(
+                        "{}::{}",
+                        frame.module.address().to_hex_literal(),
+                        frame.module.name()
+                    );
+                    frames.push((frame.frame_id, module.clone(), frame.function_name.clone()));
+                    state.push(TraceEntry::Enter {
+                        call,
+                        depth: frames.len(),
+                        module,
+                        function: frame.function_name,
+                        type_args: frame
+                            .type_instantiation
+                            .iter()
+                            .map(|tag| tag.to_canonical_string(true))
+                            .collect(),
+                        native: frame.is_native,
+                    });
+                    recording = false;
+                }
+                TraceEvent::CloseFrame { frame_id, .. } => {
+                    if let Some(pos) = frames.iter().rposition(|(id, _, _)| *id == frame_id) {
+                        let depth = pos + 1;
+                        let (_, module, function) = frames.remove(pos);
+                        frames.truncate(pos);
+                        state.push(TraceEntry::Exit {
+                            call,
+                            depth,
+                            module,
+                            function,
+                        });
+                    }
+                    recording = false;
+                }
+                TraceEvent::Instruction {
+                    pc,
+                    gas_left,
+                    instruction,
+                    ..
+                } => {
+                    let limit_reached = self
+                        .options
+                        .max_instructions
+                        .is_some_and(|max| state.summary.instructions >= max);
+                    if limit_reached {
+                        state.summary.dropped_instructions += 1;
+                        recording = false;
+                        continue;
+                    }
+                    let (module, function) = frames
+                        .last()
+                        .map(|(_, module, function)| (module.clone(), function.clone()))
+                        .unwrap_or_default();
+                    state.summary.instructions += 1;
+                    state.push(TraceEntry::Instruction {
+                        call,
+                        depth: frames.len(),
+                        module,
+                        function,
+                        pc,
+                        instruction: *instruction,
+                        gas_left,
+                        stack_depth: self.options.stack.then_some(stack_depth),
+                        pushed: Vec::new(),
+                    });
+                    recording = true;
+                }
+                TraceEvent::Effect(effect) => match *effect {
+                    Effect::Push(value) => {
+                        stack_depth += 1;
+                        if recording && self.options.stack {
+                            state.note_push(render_value(&value), stack_depth);
+                        }
+                    }
+                    Effect::Pop(_) => {
+                        stack_depth = stack_depth.saturating_sub(1);
+                        if recording && self.options.stack {
+                            state.note_stack_depth(stack_depth);
+                        }
+                    }
+                    Effect::ExecutionError(message) => {
+                        state.summary.errors += 1;
+                        state.push(TraceEntry::Error {
+                            call,
+                            depth: frames.len(),
+                            message,
+                        });
+                        recording = false;
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+
+    pub fn summary(&self) -> ExecutionTraceSummary {
+        self.state.lock().summary.clone()
+    }
+
+    pub fn entries(&self) -> Vec<TraceEntry> {
+        self.state.lock().entries.clone()
+    }
+
+    pub fn clear(&self) {
+        *self.state.lock() = TraceLogState::default();
+    }
+
+    /// Write the trace as JSON lines (one entry per line) and return the entry count.
+    pub fn write_jsonl(&self, path: &Path) -> Result<usize> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create trace directory {}", parent.display()))?;
+        }
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("create trace file {}", path.display()))?;
+        let mut writer = std::io::BufWriter::new(file);
+        let state = self.state.lock();
+        for entry in &state.entries {
+            serde_json::to_writer(&mut writer, entry).context("serialize trace entry")?;
+            writer.write_all(b"\n").context("write trace entry")?;
+        }
+        writer.flush().context("flush trace file")?;
+        Ok(state.entries.len())
+    }
+}
+
+impl TraceLogState {
+    fn push(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+        self.summary.entries += 1;
+    }
+
+    /// Attach a pushed value to the instruction that produced it.
+    fn note_push(&mut self, value: String, depth: usize) {
+        if let Some(TraceEntry::Instruction {
+            pushed,
+            stack_depth,
+            ..
+        }) = self.entries.last_mut()
+        {
+            pushed.push(value);
+            *stack_depth = Some(depth);
+        }
+    }
+
+    fn note_stack_depth(&mut self, depth: usize) {
+        if let Some(TraceEntry::Instruction { stack_depth, .. }) = self.entries.last_mut() {
+            *stack_depth = Some(depth);
+        }
+    }
+}
+
+/// Render a trace value as JSON, cut to `MAX_VALUE_CHARS`.
+pub(crate) fn render_value<T: Serialize>(value: &T) -> String {
+    let mut rendered = serde_json::to_string(value).unwrap_or_else(|_| "?".to_string());
+    if rendered.len() > MAX_VALUE_CHARS {
+        let mut end = MAX_VALUE_CHARS;
+        while !rendered.is_char_boundary(end) {
+            end -= 1;
+        }
+        rendered.truncate(end);
+        rendered.push_str("...");
+    }
+    rendered
+}
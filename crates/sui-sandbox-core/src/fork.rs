@@ -0,0 +1,419 @@
+//! Local forks of historical state.
+//!
+//! A [`ForkSession`] hydrates a [`SimulationEnvironment`] from a [`ReplayState`] (the
+//! packages and objects a historical transaction touched, at its checkpoint) and then
+//! executes synthetic PTBs on top of it, Anvil-style. Unlike a replay, the store is not
+//! reset between calls: every PTB sees the objects as the previous ones left them.
+//!
+//! ```ignore
+//! let state = sui_state_fetcher::load_replay_state(Path::new("pool.snap"))?;
+//! let mut fork = ForkSession::from_replay_state(&state)?;
+//! let first = fork.execute_ptb(&inputs, &commands)?;
+//! // The pool object now carries the first swap's mutation.
+//! let second = fork.execute_ptb(&inputs, &commands)?;
+//! ```
+//!
+//! Forks are deterministic: the transaction hash of the `n`th PTB is derived from the
+//! origin digest and `n`, the clock stays at the origin timestamp, and object versions
+//! advance Lamport-style from the versions of the inputs. Gas coins are synthesized per
+//! PTB, so `GasCoin` arguments never deplete.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{anyhow, Result};
+use base64::Engine;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+use sui_sandbox_types::{
+    FetchedTransaction, GasSummary, PtbCommand, TransactionDigest, TransactionInput,
+};
+use sui_state_fetcher::{build_address_aliases, ReplayState};
+
+use crate::ptb::{InputValue, ObjectInput};
+use crate::replay_support::{build_simulation_config, hydrate_resolver_from_replay_state};
+use crate::simulation::{ExecutionResult, SimulatedObject, SimulationEnvironment};
+use crate::tx_replay::to_ptb_commands_with_objects_and_aliases;
+
+/// The historical point a fork was taken from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ForkOrigin {
+    /// Digest of the transaction whose replay state seeded the fork.
+    pub digest: String,
+    pub checkpoint: Option<u64>,
+    pub epoch: u64,
+    pub protocol_version: u64,
+    pub timestamp_ms: Option<u64>,
+    pub objects: usize,
+    pub packages: usize,
+}
+
+/// Outcome of one PTB executed on a fork.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ForkExecution {
+    /// 1-based position of the PTB in the fork.
+    pub index: u64,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_command_index: Option<usize>,
+    pub commands_succeeded: usize,
+    pub gas_used: u64,
+    /// Computation/storage cost breakdown (None without accurate gas metering).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_summary: Option<GasSummary>,
+    pub created: Vec<String>,
+    pub mutated: Vec<String>,
+    pub deleted: Vec<String>,
+    pub wrapped: Vec<String>,
+    pub unwrapped: Vec<String>,
+    /// Type of each emitted event, in emission order.
+    pub events: Vec<String>,
+    /// Version of every created or mutated object after the PTB.
+    pub versions: BTreeMap<String, u64>,
+}
+
+/// A mutable fork of historical state that persists across PTB executions.
+pub struct ForkSession {
+    env: SimulationEnvironment,
+    origin: ForkOrigin,
+    aliases: HashMap<AccountAddress, AccountAddress>,
+    gas_budget: u64,
+    gas_price: u64,
+    executed: u64,
+}
+
+impl ForkSession {
+    /// Hydrate a fork from the packages and objects of a replay state.
+    ///
+    /// Objects that are neither shared nor immutable are owned by the origin
+    /// transaction's sender, which is also the default sender of forked PTBs.
+    pub fn from_replay_state(state: &ReplayState) -> Result<Self> {
+        let mut linkage_upgrades = HashMap::new();
+        for package in state.packages.values() {
+            for (original, upgraded) in &package.linkage {
+                if original != upgraded {
+                    linkage_upgrades.insert(*original, *upgraded);
+                }
+            }
+        }
+        let aliases = build_address_aliases(state);
+        let resolver = hydrate_resolver_from_replay_state(state, &linkage_upgrades, &aliases)?;
+
+        let mut env = SimulationEnvironment::with_resolver(resolver)?;
+        env.set_config(build_simulation_config(state));
+        env.set_track_versions(true);
+        env.set_sender(state.transaction.sender);
+        if let Some(timestamp_ms) = state.transaction.timestamp_ms {
+            env.set_timestamp_ms(timestamp_ms);
+        }
+        env.set_address_aliases_with_versions(
+            aliases.clone(),
+            state
+                .packages
+                .iter()
+                .map(|(id, package)| (*id, package.version))
+                .collect(),
+        );
+
+        for object in state.objects.values() {
+            let type_tag = object
+                .type_tag
+                .as_deref()
+                .and_then(SimulationEnvironment::parse_type_string)
+                .unwrap_or(TypeTag::Address);
+            env.add_object_with_version_and_status(
+                object.id,
+                object.bcs_bytes.clone(),
+                type_tag,
+                object.version,
+                object.is_shared,
+                object.is_immutable,
+            );
+        }
+
+        Ok(Self {
+            env,
+            origin: ForkOrigin {
+                digest: state.transaction.digest.0.clone(),
+                checkpoint: state.checkpoint.or(state.transaction.checkpoint),
+                epoch: state.epoch,
+                protocol_version: state.protocol_version,
+                timestamp_ms: state.transaction.timestamp_ms,
+                objects: state.objects.len(),
+                packages: state.packages.len(),
+            },
+            aliases,
+            gas_budget: state.transaction.gas_budget,
+            gas_price: state.transaction.gas_price,
+            executed: 0,
+        })
+    }
+
+    pub fn origin(&self) -> &ForkOrigin {
+        &self.origin
+    }
+
+    /// Number of PTBs executed on the fork so far (failed ones included).
+    pub fn executed(&self) -> u64 {
+        self.executed
+    }
+
+    pub fn sender(&self) -> AccountAddress {
+        self.env.sender()
+    }
+
+    /// Sender of subsequent PTBs.
+    pub fn set_sender(&mut self, sender: AccountAddress) {
+        self.env.set_sender(sender);
+    }
+
+    pub fn env(&self) -> &SimulationEnvironment {
+        &self.env
+    }
+
+    pub fn env_mut(&mut self) -> &mut SimulationEnvironment {
+        &mut self.env
+    }
+
+    /// Current state of an object in the fork.
+    pub fn get_object(&self, id: &AccountAddress) -> Option<&SimulatedObject> {
+        self.env.get_object(id)
+    }
+
+    /// Execute a PTB given in the replay-state schema (`transaction.inputs` /
+    /// `transaction.commands`) against the fork's current objects.
+    ///
+    /// Object inputs are resolved by ID from the fork; their `version`/`digest`
+    /// fields are ignored, so a PTB can be resubmitted unchanged after earlier PTBs
+    /// mutated its inputs.
+    pub fn execute_ptb(
+        &mut self,
+        inputs: &[TransactionInput],
+        commands: &[PtbCommand],
+    ) -> Result<ForkExecution> {
+        let tx = FetchedTransaction {
+            digest: TransactionDigest(format!("fork-{}", self.executed + 1)),
+            sender: self.env.sender(),
+            gas_budget: self.gas_budget,
+            gas_price: self.gas_price,
+            commands: commands.to_vec(),
+            inputs: inputs.to_vec(),
+            effects: None,
+            timestamp_ms: self.origin.timestamp_ms,
+            checkpoint: self.origin.checkpoint,
+            gas_payment: vec![],
+        };
+
+        let mut cached_objects = HashMap::new();
+        for input in inputs {
+            let object_id = match input {
+                TransactionInput::Pure { .. } => continue,
+                TransactionInput::Object { object_id, .. }
+                | TransactionInput::SharedObject { object_id, .. }
+                | TransactionInput::ImmutableObject { object_id, .. }
+                | TransactionInput::Receiving { object_id, .. } => object_id,
+            };
+            let id = AccountAddress::from_hex_literal(object_id)
+                .map_err(|e| anyhow!("invalid object ID '{}': {}", object_id, e))?;
+            let object = self
+                .env
+                .get_object(&id)
+                .ok_or_else(|| anyhow!("object {} is not in the fork", object_id))?;
+            cached_objects.insert(
+                object_id.clone(),
+                base64::engine::general_purpose::STANDARD.encode(&object.bcs_bytes),
+            );
+        }
+
+        let (mut ptb_inputs, ptb_commands) =
+            to_ptb_commands_with_objects_and_aliases(&tx, &cached_objects, &self.aliases)?;
+        let mut max_version = 0;
+        for input in &mut ptb_inputs {
+            if let InputValue::Object(object_input) = input {
+                if let Some(object) = self.env.get_object(object_input.id()) {
+                    sync_object_input(object_input, object);
+                    max_version = max_version.max(object.version);
+                }
+            }
+        }
+        // Sui assigns outputs the Lamport version max(input versions) + 1.
+        self.env.set_lamport_clock(max_version);
+
+        let result = self.env.execute_ptb(ptb_inputs, ptb_commands);
+        self.executed += 1;
+        Ok(self.summarize(result))
+    }
+
+    fn summarize(&self, result: ExecutionResult) -> ForkExecution {
+        let hex = |ids: &[AccountAddress]| -> Vec<String> {
+            ids.iter().map(AccountAddress::to_hex_literal).collect()
+        };
+        let effects = result.effects.unwrap_or_default();
+        let versions = effects
+            .created
+            .iter()
+            .chain(&effects.mutated)
+            .filter_map(|id| {
+                self.env
+                    .get_object(id)
+                    .map(|object| (id.to_hex_literal(), object.version))
+            })
+            .collect();
+        ForkExecution {
+            index: self.executed,
+            success: result.success,
+            error: result.raw_error,
+            failed_command_index: result.failed_command_index,
+            commands_succeeded: result.commands_succeeded,
+            gas_used: effects.gas_used,
+            gas_summary: effects.gas_summary.clone(),
+            created: hex(&effects.created),
+            mutated: hex(&effects.mutated),
+            deleted: hex(&effects.deleted),
+            wrapped: hex(&effects.wrapped),
+            unwrapped: hex(&effects.unwrapped),
+            events: effects
+                .events
+                .iter()
+                .map(|event| event.type_tag.clone())
+                .collect(),
+            versions,
+        }
+    }
+}
+
+/// Give a converted object input the fork's current type and version.
+fn sync_object_input(input: &mut ObjectInput, object: &SimulatedObject) {
+    let (type_tag, version) = match input {
+        ObjectInput::ImmRef {
+            type_tag, version, ..
+        }
+        | ObjectInput::MutRef {
+            type_tag, version, ..
+        }
+        | ObjectInput::Owned {
+            type_tag, version, ..
+        }
+        | ObjectInput::Shared {
+            type_tag, version, ..
+        }
+        | ObjectInput::Receiving {
+            type_tag, version, ..
+        } => (type_tag, version),
+    };
+    if type_tag.is_none() {
+        *type_tag = Some(object.type_tag.clone());
+    }
+    *version = Some(object.version);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sandbox_types::PtbArgument;
+
+    const SENDER: &str = "0xa11ce";
+    const COIN: &str = "0xc0ffee";
+
+    fn coin_bytes(id: AccountAddress, balance: u64) -> Vec<u8> {
+        let mut bytes = id.to_vec();
+        bytes.extend_from_slice(&balance.to_le_bytes());
+        bytes
+    }
+
+    fn coin_state() -> ReplayState {
+        let coin = AccountAddress::from_hex_literal(COIN).unwrap();
+        ReplayState {
+            transaction: FetchedTransaction {
+                digest: TransactionDigest("origin".to_string()),
+                sender: AccountAddress::from_hex_literal(SENDER).unwrap(),
+                gas_budget: 50_000_000,
+                gas_price: 1_000,
+                commands: vec![],
+                inputs: vec![],
+                effects: None,
+                timestamp_ms: Some(1_700_000_000_000),
+                checkpoint: Some(100),
+                gas_payment: vec![],
+            },
+            objects: HashMap::from([(
+                coin,
+                sui_state_fetcher::VersionedObject {
+                    id: coin,
+                    version: 10,
+                    digest: None,
+                    type_tag: Some("0x2::coin::Coin<0x2::sui::SUI>".to_string()),
+                    bcs_bytes: coin_bytes(coin, 1_000),
+                    is_shared: false,
+                    is_immutable: false,
+                },
+            )]),
+            packages: HashMap::new(),
+            protocol_version: 0,
+            epoch: 5,
+            reference_gas_price: Some(1_000),
+            checkpoint: Some(100),
+        }
+    }
+
+    fn split_to_sender(amount: u64) -> (Vec<TransactionInput>, Vec<PtbCommand>) {
+        let sender = AccountAddress::from_hex_literal(SENDER).unwrap();
+        (
+            vec![
+                TransactionInput::Object {
+                    object_id: COIN.to_string(),
+                    version: 10,
+                    digest: String::new(),
+                },
+                TransactionInput::Pure {
+                    bytes: amount.to_le_bytes().to_vec(),
+                },
+                TransactionInput::Pure {
+                    bytes: sender.to_vec(),
+                },
+            ],
+            vec![
+                PtbCommand::SplitCoins {
+                    coin: PtbArgument::Input { index: 0 },
+                    amounts: vec![PtbArgument::Input { index: 1 }],
+                },
+                PtbCommand::TransferObjects {
+                    objects: vec![PtbArgument::Result { index: 0 }],
+                    address: PtbArgument::Input { index: 2 },
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn fork_persists_mutations_across_ptbs() {
+        let coin = AccountAddress::from_hex_literal(COIN).unwrap();
+        let mut fork = ForkSession::from_replay_state(&coin_state()).unwrap();
+        assert_eq!(fork.origin().checkpoint, Some(100));
+
+        let (inputs, commands) = split_to_sender(300);
+        let first = fork.execute_ptb(&inputs, &commands).unwrap();
+        assert!(first.success, "{:?}", first.error);
+        assert_eq!(first.index, 1);
+        assert_eq!(first.created.len(), 1);
+        assert_eq!(first.versions[&coin.to_hex_literal()], 11);
+
+        // The same PTB (with its stale input version) runs against the split coin.
+        let second = fork.execute_ptb(&inputs, &commands).unwrap();
+        assert!(second.success, "{:?}", second.error);
+        assert_eq!(fork.executed(), 2);
+        assert_ne!(first.created, second.created);
+
+        let object = fork.get_object(&coin).unwrap();
+        assert_eq!(object.bcs_bytes, coin_bytes(coin, 400));
+        assert_eq!(object.version, 12);
+
+        let overdraw = fork
+            .execute_ptb(&split_to_sender(500).0, &commands)
+            .unwrap();
+        assert!(!overdraw.success);
+        assert_eq!(fork.executed(), 3);
+    }
+}
@@ -0,0 +1,174 @@
+//! Static numeric-bound pre-pass for Move function fuzzing.
+//!
+//! Scans a function's bytecode for comparisons between a parameter and an
+//! integer constant (e.g. `assert!(amount > 0 && amount < MAX)`) so the value
+//! generator can bias those parameters toward boundary and in-range values.
+//!
+//! This is a cheap abstract-stack walk, not symbolic execution: any
+//! instruction it does not model resets the tracked stack, so it only finds
+//! the direct `param <op> const` shapes the Move compiler emits for guards.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use move_binary_format::file_format::{Bytecode, ConstantPoolIndex, SignatureToken};
+use move_binary_format::CompiledModule;
+use serde::{Deserialize, Serialize};
+
+/// A parameter whose generation was biased by constants found in comparisons.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoundedParam {
+    /// Index of the parameter in the function signature.
+    pub param_index: usize,
+    /// Constants the parameter is compared against (decimal strings, u128-safe).
+    pub constants: Vec<String>,
+}
+
+/// Abstract stack slot tracked during the scan.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Param(usize),
+    Const(u128),
+    Unknown,
+}
+
+/// Infer comparison constants per parameter for `function_name` in `module`.
+///
+/// Returns an empty map if the function is missing or native.
+pub fn infer_param_bounds(
+    module: &CompiledModule,
+    function_name: &str,
+) -> BTreeMap<usize, BTreeSet<u128>> {
+    for def in &module.function_defs {
+        let handle = &module.function_handles[def.function.0 as usize];
+        if module.identifier_at(handle.name).as_str() != function_name {
+            continue;
+        }
+        let Some(code) = &def.code else {
+            return BTreeMap::new();
+        };
+        let param_count = module.signatures[handle.parameters.0 as usize].0.len();
+        return scan_comparisons(&code.code, param_count, |idx| integer_constant(module, idx));
+    }
+    BTreeMap::new()
+}
+
+/// Walk `code` and collect constants compared directly against parameter locals.
+fn scan_comparisons(
+    code: &[Bytecode],
+    param_count: usize,
+    resolve_const: impl Fn(ConstantPoolIndex) -> Option<u128>,
+) -> BTreeMap<usize, BTreeSet<u128>> {
+    let mut bounds: BTreeMap<usize, BTreeSet<u128>> = BTreeMap::new();
+    let mut stack: Vec<Slot> = Vec::new();
+
+    for instr in code {
+        match instr {
+            Bytecode::CopyLoc(idx) | Bytecode::MoveLoc(idx) => {
+                let idx = *idx as usize;
+                stack.push(if idx < param_count {
+                    Slot::Param(idx)
+                } else {
+                    Slot::Unknown
+                });
+            }
+            Bytecode::LdU8(v) => stack.push(Slot::Const(*v as u128)),
+            Bytecode::LdU16(v) => stack.push(Slot::Const(*v as u128)),
+            Bytecode::LdU32(v) => stack.push(Slot::Const(*v as u128)),
+            Bytecode::LdU64(v) => stack.push(Slot::Const(*v as u128)),
+            Bytecode::LdU128(v) => stack.push(Slot::Const(**v)),
+            Bytecode::LdConst(idx) => stack.push(match resolve_const(*idx) {
+                Some(v) => Slot::Const(v),
+                None => Slot::Unknown,
+            }),
+            Bytecode::Lt
+            | Bytecode::Gt
+            | Bytecode::Le
+            | Bytecode::Ge
+            | Bytecode::Eq
+            | Bytecode::Neq => {
+                let rhs = stack.pop();
+                let lhs = stack.pop();
+                match (lhs, rhs) {
+                    (Some(Slot::Param(p)), Some(Slot::Const(c)))
+                    | (Some(Slot::Const(c)), Some(Slot::Param(p))) => {
+                        bounds.entry(p).or_default().insert(c);
+                    }
+                    _ => {}
+                }
+                stack.push(Slot::Unknown);
+            }
+            // Anything else: stop tracking rather than model its stack effect.
+            _ => stack.clear(),
+        }
+    }
+    bounds
+}
+
+/// Decode an integer constant from the module's constant pool.
+fn integer_constant(module: &CompiledModule, idx: ConstantPoolIndex) -> Option<u128> {
+    let constant = module.constant_pool().get(idx.0 as usize)?;
+    let data = &constant.data;
+    match constant.type_ {
+        SignatureToken::U8 => data.first().map(|v| *v as u128),
+        SignatureToken::U16 => Some(u16::from_le_bytes(data.get(..2)?.try_into().ok()?) as u128),
+        SignatureToken::U32 => Some(u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as u128),
+        SignatureToken::U64 => Some(u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as u128),
+        SignatureToken::U128 => Some(u128::from_le_bytes(data.get(..16)?.try_into().ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_consts(_: ConstantPoolIndex) -> Option<u128> {
+        None
+    }
+
+    #[test]
+    fn test_detects_param_vs_literal() {
+        // amount > 0 && amount < 1000
+        let code = vec![
+            Bytecode::CopyLoc(0),
+            Bytecode::LdU64(0),
+            Bytecode::Gt,
+            Bytecode::BrFalse(10),
+            Bytecode::CopyLoc(0),
+            Bytecode::LdU64(1000),
+            Bytecode::Lt,
+        ];
+        let bounds = scan_comparisons(&code, 1, no_consts);
+        let consts: Vec<u128> = bounds[&0].iter().copied().collect();
+        assert_eq!(consts, vec![0, 1000]);
+    }
+
+    #[test]
+    fn test_detects_reversed_operands_and_const_pool() {
+        let code = vec![
+            Bytecode::LdConst(ConstantPoolIndex(0)),
+            Bytecode::MoveLoc(1),
+            Bytecode::Le,
+        ];
+        let bounds = scan_comparisons(&code, 2, |idx| (idx.0 == 0).then_some(42));
+        assert!(bounds.get(&0).is_none());
+        assert!(bounds[&1].contains(&42));
+    }
+
+    #[test]
+    fn test_ignores_non_param_locals() {
+        let code = vec![Bytecode::CopyLoc(3), Bytecode::LdU8(7), Bytecode::Eq];
+        assert!(scan_comparisons(&code, 1, no_consts).is_empty());
+    }
+
+    #[test]
+    fn test_unmodeled_instruction_resets_stack() {
+        let code = vec![
+            Bytecode::CopyLoc(0),
+            Bytecode::Pop,
+            Bytecode::LdU64(5),
+            Bytecode::Lt,
+        ];
+        assert!(scan_comparisons(&code, 1, no_consts).is_empty());
+    }
+}
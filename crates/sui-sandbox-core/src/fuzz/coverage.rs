@@ -0,0 +1,302 @@
+//! Basic-block coverage feedback and corpus persistence for coverage-guided fuzzing.
+//!
+//! Coverage is derived from the VM's instruction trace
+//! ([`crate::execution_trace`]): within each frame, an instruction starts a new
+//! basic block when it is the first one executed in the frame, when it does not
+//! directly follow the previous instruction (a taken jump), or when the previous
+//! instruction was a branch (a fall-through). Blocks are keyed by module,
+//! function, and leading pc, so coverage reached in dependencies counts too.
+//!
+//! Inputs that reach new blocks are kept in a [`FuzzCorpus`], which can be saved
+//! to and loaded from JSON so a campaign can resume where it stopped.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::execution_trace::TraceEntry;
+
+/// A basic block identified by its first instruction.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct CoverageBlock {
+    /// Module path (e.g., "0x2::coin").
+    pub module: String,
+    pub function: String,
+    /// Offset of the block's first instruction.
+    pub pc: u16,
+}
+
+/// Basic blocks entered by one or more executions.
+#[derive(Debug, Clone, Default)]
+pub struct CoverageMap {
+    blocks: BTreeSet<CoverageBlock>,
+}
+
+impl CoverageMap {
+    /// Add `blocks`, returning how many were not covered before.
+    pub fn merge(&mut self, blocks: BTreeSet<CoverageBlock>) -> usize {
+        let before = self.blocks.len();
+        self.blocks.extend(blocks);
+        self.blocks.len() - before
+    }
+
+    /// Whether any of `blocks` is not covered yet.
+    pub fn has_new(&self, blocks: &BTreeSet<CoverageBlock>) -> bool {
+        !blocks.is_subset(&self.blocks)
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Number of distinct functions with at least one covered block.
+    pub fn function_count(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(|block| (&block.module, &block.function))
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+}
+
+/// Collect the basic blocks entered in an instruction trace.
+pub fn blocks_from_trace(entries: &[TraceEntry]) -> BTreeSet<CoverageBlock> {
+    let mut blocks = BTreeSet::new();
+    // Per call-stack depth: (pc, was a branch) of the last instruction in that frame.
+    let mut last: Vec<Option<(u16, bool)>> = Vec::new();
+    for entry in entries {
+        match entry {
+            TraceEntry::Enter { depth, .. } => {
+                last.truncate(depth.saturating_sub(1));
+                last.resize(*depth, None);
+            }
+            TraceEntry::Exit { depth, .. } => {
+                last.truncate(depth.saturating_sub(1));
+            }
+            TraceEntry::Instruction {
+                depth,
+                module,
+                function,
+                pc,
+                instruction,
+                ..
+            } => {
+                let Some(slot) = depth.checked_sub(1) else {
+                    continue;
+                };
+                if last.len() <= slot {
+                    last.resize(slot + 1, None);
+                }
+                let starts_block = match last[slot] {
+                    Some((prev, was_branch)) => was_branch || prev.checked_add(1) != Some(*pc),
+                    None => true,
+                };
+                if starts_block {
+                    blocks.insert(CoverageBlock {
+                        module: module.clone(),
+                        function: function.clone(),
+                        pc: *pc,
+                    });
+                }
+                last[slot] = Some((*pc, is_branch(instruction)));
+            }
+            TraceEntry::Error { .. } => {}
+        }
+    }
+    blocks
+}
+
+/// Whether a traced instruction ends its block with a conditional or
+/// unconditional jump. Accepts both `BR_TRUE` and `BrTrue(3)` renderings.
+fn is_branch(instruction: &str) -> bool {
+    let opcode: String = instruction
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic() || *c == '_')
+        .filter(|c| *c != '_')
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    matches!(
+        opcode.as_str(),
+        "BRTRUE" | "BRFALSE" | "BRANCH" | "VARIANTSWITCH"
+    )
+}
+
+/// An input kept because it reached new coverage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    /// BCS-encoded pure inputs (hex), in parameter order.
+    pub inputs_bcs_hex: Vec<String>,
+    /// Human-readable inputs.
+    #[serde(default)]
+    pub inputs_human: Vec<String>,
+    /// Blocks this input reached that the corpus had not covered.
+    #[serde(default)]
+    pub new_blocks: usize,
+}
+
+impl CorpusEntry {
+    /// Decode the hex inputs back to BCS bytes.
+    pub fn decode_inputs(&self) -> Result<Vec<Vec<u8>>> {
+        self.inputs_bcs_hex
+            .iter()
+            .map(|input| {
+                hex::decode(input).with_context(|| format!("invalid corpus input hex '{input}'"))
+            })
+            .collect()
+    }
+}
+
+/// Inputs that found new coverage for one target function.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzCorpus {
+    /// Target function the corpus was built for (empty until first run).
+    #[serde(default)]
+    pub target: String,
+    #[serde(default)]
+    pub entries: Vec<CorpusEntry>,
+}
+
+impl FuzzCorpus {
+    /// Load a corpus, or start an empty one if `path` does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read fuzz corpus {}", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid fuzz corpus {}", path.display()))
+    }
+
+    /// Write the corpus as pretty JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).with_context(|| {
+                format!(
+                    "Failed to create fuzz corpus directory {}",
+                    parent.display()
+                )
+            })?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write fuzz corpus {}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enter(depth: usize, function: &str) -> TraceEntry {
+        TraceEntry::Enter {
+            call: 0,
+            depth,
+            module: "0x2::m".into(),
+            function: function.into(),
+            type_args: vec![],
+            native: false,
+        }
+    }
+
+    fn instr(depth: usize, function: &str, pc: u16, instruction: &str) -> TraceEntry {
+        TraceEntry::Instruction {
+            call: 0,
+            depth,
+            module: "0x2::m".into(),
+            function: function.into(),
+            pc,
+            instruction: instruction.into(),
+            gas_left: 0,
+            stack_depth: None,
+            pushed: vec![],
+        }
+    }
+
+    fn exit(depth: usize, function: &str) -> TraceEntry {
+        TraceEntry::Exit {
+            call: 0,
+            depth,
+            module: "0x2::m".into(),
+            function: function.into(),
+        }
+    }
+
+    fn pcs(blocks: &BTreeSet<CoverageBlock>, function: &str) -> Vec<u16> {
+        blocks
+            .iter()
+            .filter(|block| block.function == function)
+            .map(|block| block.pc)
+            .collect()
+    }
+
+    #[test]
+    fn test_blocks_from_trace_splits_on_jumps_and_branches() {
+        let trace = vec![
+            enter(1, "f"),
+            instr(1, "f", 0, "COPY_LOC"),
+            instr(1, "f", 1, "BR_FALSE"),
+            // fall-through after a branch starts a block
+            instr(1, "f", 2, "CALL"),
+            enter(2, "g"),
+            instr(2, "g", 0, "RET"),
+            exit(2, "g"),
+            // returning from a call continues the caller's block
+            instr(1, "f", 3, "BRANCH"),
+            // taken jump
+            instr(1, "f", 7, "RET"),
+            exit(1, "f"),
+        ];
+        let blocks = blocks_from_trace(&trace);
+        assert_eq!(pcs(&blocks, "f"), vec![0, 2, 7]);
+        assert_eq!(pcs(&blocks, "g"), vec![0]);
+
+        let mut map = CoverageMap::default();
+        assert_eq!(map.merge(blocks.clone()), 4);
+        assert!(!map.has_new(&blocks));
+        assert_eq!(map.merge(blocks), 0);
+        assert_eq!(map.function_count(), 2);
+    }
+
+    #[test]
+    fn test_is_branch_renderings() {
+        assert!(is_branch("BR_TRUE"));
+        assert!(is_branch("BrFalse(4)"));
+        assert!(is_branch("Branch(0)"));
+        assert!(!is_branch("BRANCH_X"));
+        assert!(!is_branch("RET"));
+    }
+
+    #[test]
+    fn test_corpus_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("corpus").join("target.json");
+        assert!(FuzzCorpus::load(&path).unwrap().is_empty());
+
+        let corpus = FuzzCorpus {
+            target: "0x2::m::f".into(),
+            entries: vec![CorpusEntry {
+                inputs_bcs_hex: vec!["2a00000000000000".into()],
+                inputs_human: vec!["42".into()],
+                new_blocks: 3,
+            }],
+        };
+        corpus.save(&path).unwrap();
+        let loaded = FuzzCorpus::load(&path).unwrap();
+        assert_eq!(loaded, corpus);
+        assert_eq!(
+            loaded.entries[0].decode_inputs().unwrap(),
+            vec![42u64.to_le_bytes().to_vec()]
+        );
+    }
+}
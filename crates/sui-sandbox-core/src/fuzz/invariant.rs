@@ -0,0 +1,225 @@
+//! Property checks that must hold after every successful fuzz iteration.
+//!
+//! Two kinds of invariant can be registered on a [`FuzzRunner`](super::FuzzRunner):
+//!
+//! - [`MoveInvariant`]: a Move function called in the same PTB right after the
+//!   target, so it sees the target's post-state. It is violated if it aborts or
+//!   returns `false`.
+//! - A Rust closure over the target's inputs and [`TransactionEffects`]
+//!   (see [`Invariant::check`]).
+//!
+//! Iterations where the target itself aborts or errors are not checked.
+
+use anyhow::{anyhow, Context, Result};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::TypeTag;
+
+use crate::ptb::{Argument, Command, TransactionEffects};
+
+/// Argument passed to a [`MoveInvariant`] call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvariantArg {
+    /// Every pure input of the target, in parameter order.
+    Inputs,
+    /// Every return value of the target, in order.
+    Results,
+    /// The target's pure input at this index.
+    Input(usize),
+    /// The target's return value at this index.
+    Result(usize),
+    /// A fixed BCS-encoded pure value.
+    Pure(Vec<u8>),
+}
+
+/// A Move function that must not abort (and, if it returns `bool`, must return
+/// `true`) after the target runs.
+#[derive(Debug, Clone)]
+pub struct MoveInvariant {
+    pub package: AccountAddress,
+    pub module: Identifier,
+    pub function: Identifier,
+    pub type_args: Vec<TypeTag>,
+    pub args: Vec<InvariantArg>,
+}
+
+impl MoveInvariant {
+    /// Parse `0xPKG::module::function`. The call receives the target's pure
+    /// inputs followed by its return values.
+    pub fn parse(target: &str) -> Result<Self> {
+        let parts: Vec<&str> = target.split("::").collect();
+        let [package, module, function] = parts.as_slice() else {
+            return Err(anyhow!(
+                "Invalid invariant '{}'. Expected '0xPKG::module::function'",
+                target
+            ));
+        };
+        Ok(Self {
+            package: AccountAddress::from_hex_literal(package)
+                .with_context(|| format!("Invalid invariant package address '{}'", package))?,
+            module: Identifier::new(*module)
+                .map_err(|e| anyhow!("Invalid invariant module '{}': {}", module, e))?,
+            function: Identifier::new(*function)
+                .map_err(|e| anyhow!("Invalid invariant function '{}': {}", function, e))?,
+            type_args: Vec::new(),
+            args: vec![InvariantArg::Inputs, InvariantArg::Results],
+        })
+    }
+
+    pub fn with_type_args(mut self, type_args: Vec<TypeTag>) -> Self {
+        self.type_args = type_args;
+        self
+    }
+
+    pub fn with_args(mut self, args: Vec<InvariantArg>) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// `0xPKG::module::function`.
+    pub fn name(&self) -> String {
+        format!(
+            "{}::{}::{}",
+            self.package.to_hex_literal(),
+            self.module,
+            self.function
+        )
+    }
+
+    /// Build the MoveCall run after the target (command 0).
+    ///
+    /// `input_count` is the target's pure input count and `return_count` its
+    /// return value count. `Pure` arguments are appended to `extra_inputs` and
+    /// referenced as inputs starting at `input_count + extra_inputs.len()`.
+    pub(crate) fn command(
+        &self,
+        input_count: usize,
+        return_count: usize,
+        extra_inputs: &mut Vec<Vec<u8>>,
+    ) -> Command {
+        let mut args = Vec::new();
+        for arg in &self.args {
+            match arg {
+                InvariantArg::Inputs => {
+                    args.extend((0..input_count).map(|i| Argument::Input(i as u16)))
+                }
+                InvariantArg::Results => {
+                    args.extend((0..return_count).map(|i| Argument::NestedResult(0, i as u16)))
+                }
+                InvariantArg::Input(i) => args.push(Argument::Input(*i as u16)),
+                InvariantArg::Result(i) => args.push(Argument::NestedResult(0, *i as u16)),
+                InvariantArg::Pure(bytes) => {
+                    args.push(Argument::Input((input_count + extra_inputs.len()) as u16));
+                    extra_inputs.push(bytes.clone());
+                }
+            }
+        }
+        Command::MoveCall {
+            package: self.package,
+            module: self.module.clone(),
+            function: self.function.clone(),
+            type_args: self.type_args.clone(),
+            args,
+        }
+    }
+}
+
+/// What a Rust invariant can inspect after a successful target call.
+pub struct InvariantContext<'a> {
+    /// BCS-encoded pure inputs of the target, in parameter order.
+    pub inputs: &'a [Vec<u8>],
+    /// Effects of the whole PTB (the target plus any Move invariants).
+    pub effects: &'a TransactionEffects,
+}
+
+impl InvariantContext<'_> {
+    /// BCS-encoded return values of the target.
+    pub fn return_values(&self) -> &[Vec<u8>] {
+        self.effects
+            .return_values
+            .first()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Rust invariant: `Err(message)` marks a violation.
+pub type InvariantFn =
+    Box<dyn Fn(&InvariantContext<'_>) -> std::result::Result<(), String> + Send + Sync>;
+
+/// A property checked after every successful fuzz iteration.
+pub enum Invariant {
+    Move(MoveInvariant),
+    Check { name: String, check: InvariantFn },
+}
+
+impl Invariant {
+    /// A Rust closure invariant.
+    pub fn check<F>(name: impl Into<String>, check: F) -> Self
+    where
+        F: Fn(&InvariantContext<'_>) -> std::result::Result<(), String> + Send + Sync + 'static,
+    {
+        Self::Check {
+            name: name.into(),
+            check: Box::new(check),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Move(invariant) => invariant.name(),
+            Self::Check { name, .. } => name.clone(),
+        }
+    }
+}
+
+impl From<MoveInvariant> for Invariant {
+    fn from(invariant: MoveInvariant) -> Self {
+        Self::Move(invariant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_move_invariant() {
+        let invariant = MoveInvariant::parse("0x2::pool::check_invariant").unwrap();
+        assert_eq!(invariant.name(), "0x2::pool::check_invariant");
+        assert_eq!(
+            invariant.args,
+            vec![InvariantArg::Inputs, InvariantArg::Results]
+        );
+        assert!(MoveInvariant::parse("0x2::pool").is_err());
+        assert!(MoveInvariant::parse("pool::a::b").is_err());
+    }
+
+    #[test]
+    fn test_move_invariant_command_args() {
+        let invariant = MoveInvariant::parse("0x2::pool::check")
+            .unwrap()
+            .with_args(vec![
+                InvariantArg::Inputs,
+                InvariantArg::Pure(vec![7]),
+                InvariantArg::Results,
+                InvariantArg::Result(1),
+            ]);
+        let mut extra = Vec::new();
+        let Command::MoveCall { args, .. } = invariant.command(2, 2, &mut extra) else {
+            panic!("expected MoveCall");
+        };
+        assert_eq!(
+            args,
+            vec![
+                Argument::Input(0),
+                Argument::Input(1),
+                Argument::Input(2),
+                Argument::NestedResult(0, 0),
+                Argument::NestedResult(0, 1),
+                Argument::NestedResult(0, 1),
+            ]
+        );
+        assert_eq!(extra, vec![vec![7]]);
+    }
+}
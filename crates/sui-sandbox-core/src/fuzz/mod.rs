@@ -9,22 +9,43 @@
 //! - [`classifier`]: Classifies function parameters as pure (fuzzable),
 //!   system-injected, object-based, or unfuzzable
 //! - [`value_gen`]: Boundary-heavy random BCS value generation
+//! - [`bounds`]: Static pre-pass that finds constant comparisons against
+//!   parameters to bias generation toward guarded ranges
 //! - [`runner`]: Fuzzing execution loop with gas profiling
+//! - [`coverage`]: Basic-block coverage from VM instruction traces and a
+//!   persistent corpus of coverage-increasing inputs
+//! - [`invariant`]: Move view calls and Rust closures that must hold after
+//!   every successful iteration
 //! - [`report`]: Result types for fuzz outcomes
 //!
-//! # Phase 2 Seam
+//! # Coverage-Guided Mode
 //!
-//! The [`runner::FuzzRunner`] is designed to support future coverage-guided
-//! fuzzing via an optional `CoverageTracker` parameter (not yet implemented).
+//! With [`runner::FuzzConfig::coverage_guided`], the runner traces every
+//! execution, keeps inputs that reach new basic blocks in a
+//! [`coverage::FuzzCorpus`], and spends most iterations mutating corpus
+//! entries. [`runner::FuzzRunner::run_with_corpus`] resumes from a saved corpus.
+//!
+//! # Invariants
+//!
+//! [`runner::FuzzRunner::with_invariant`] registers properties checked after
+//! each successful iteration; violating inputs are reported per invariant in
+//! [`report::FuzzOutcomeSummary::invariant_violations`].
 
+pub mod bounds;
 pub mod classifier;
+pub mod coverage;
+pub mod invariant;
 pub mod report;
 pub mod runner;
 pub mod value_gen;
 
+pub use bounds::{infer_param_bounds, BoundedParam};
 pub use classifier::{classify_params, ClassifiedFunction, ParamClass, PureType, SystemType};
+pub use coverage::{CorpusEntry, CoverageBlock, CoverageMap, FuzzCorpus};
+pub use invariant::{Invariant, InvariantArg, InvariantContext, InvariantFn, MoveInvariant};
 pub use report::{
-    AbortInfo, ErrorInfo, FuzzOutcomeSummary, FuzzReport, GasProfile, InterestingCase, Outcome,
+    AbortInfo, CoverageSummary, ErrorInfo, FuzzOutcomeSummary, FuzzReport, GasProfile,
+    InterestingCase, InvariantViolationInfo, Outcome,
 };
 pub use runner::{FuzzConfig, FuzzRunner};
 pub use value_gen::ValueGenerator;
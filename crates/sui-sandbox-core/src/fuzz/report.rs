@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::bounds::BoundedParam;
 use super::classifier::ClassifiedFunction;
 
 /// Complete report from a fuzz run.
@@ -19,12 +20,33 @@ pub struct FuzzReport {
     pub elapsed_ms: u64,
     /// Parameter classification.
     pub classification: ClassifiedFunction,
+    /// Parameters whose generation was biased by the static bounds pre-pass.
+    #[serde(default)]
+    pub bounded_params: Vec<BoundedParam>,
     /// Outcome summary.
     pub outcomes: FuzzOutcomeSummary,
     /// Gas usage profile.
     pub gas_profile: GasProfile,
     /// Interesting cases (first occurrence of each distinct abort/error).
     pub interesting_cases: Vec<InterestingCase>,
+    /// Coverage statistics (coverage-guided runs only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageSummary>,
+}
+
+/// Basic-block coverage reached by a coverage-guided run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageSummary {
+    /// Distinct basic blocks entered, including blocks in called dependencies.
+    pub blocks: usize,
+    /// Distinct functions with at least one entered block.
+    pub functions: usize,
+    /// Corpus entries loaded from a previous campaign.
+    pub seeded_entries: usize,
+    /// Inputs added to the corpus during this run.
+    pub new_entries: usize,
+    /// Corpus size at the end of the run.
+    pub corpus_size: usize,
 }
 
 /// Summary of fuzz outcomes.
@@ -38,6 +60,9 @@ pub struct FuzzOutcomeSummary {
     pub aborts: Vec<AbortInfo>,
     /// Error info grouped by error message.
     pub errors: Vec<ErrorInfo>,
+    /// Invariant violations grouped by invariant.
+    #[serde(default)]
+    pub invariant_violations: Vec<InvariantViolationInfo>,
 }
 
 /// Information about a specific abort code.
@@ -55,6 +80,21 @@ pub struct AbortInfo {
     pub sample_inputs_bcs: Vec<String>,
 }
 
+/// Information about a violated invariant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvariantViolationInfo {
+    /// Invariant name (`0xPKG::module::function` for Move invariants).
+    pub invariant: String,
+    /// Violation message of the first occurrence (may be truncated).
+    pub message: String,
+    /// Number of iterations that violated this invariant.
+    pub count: u64,
+    /// Human-readable representation of the first violating input.
+    pub sample_inputs: Vec<String>,
+    /// BCS-encoded inputs (hex) for reproducibility.
+    pub sample_inputs_bcs: Vec<String>,
+}
+
 /// Information about a specific error type.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorInfo {
@@ -125,9 +165,19 @@ pub struct InterestingCase {
 #[serde(tag = "type")]
 pub enum Outcome {
     Success,
-    Abort { code: u64, location: Option<String> },
-    Error { message: String },
+    Abort {
+        code: u64,
+        location: Option<String>,
+    },
+    Error {
+        message: String,
+    },
     GasExhaustion,
+    /// The target succeeded but a registered invariant did not hold.
+    InvariantViolation {
+        invariant: String,
+        message: String,
+    },
 }
 
 #[cfg(test)]
@@ -2,23 +2,40 @@
 //!
 //! Runs N iterations of random input generation + VM execution,
 //! collecting outcomes and gas statistics.
+//!
+//! With [`FuzzConfig::coverage_guided`], each execution is traced, inputs that
+//! reach new basic blocks are added to a [`FuzzCorpus`], and most iterations
+//! mutate a corpus entry instead of generating inputs from scratch.
+//!
+//! Registered [`Invariant`]s are checked after every successful iteration;
+//! violations are reported as [`Outcome::InvariantViolation`].
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::time::Instant;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::TypeTag;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
-use crate::ptb::{Argument, Command, InputValue, PTBExecutor};
+use crate::execution_trace::ExecutionTraceOptions;
+use crate::ptb::{Argument, Command, InputValue, PTBExecutor, TransactionEffects};
 use crate::resolver::LocalModuleResolver;
 use crate::vm::{SimulationConfig, VMHarness};
 
+use super::bounds::{infer_param_bounds, BoundedParam};
 use super::classifier::{ClassifiedFunction, ParamClass, PureType};
+use super::coverage::{blocks_from_trace, CorpusEntry, CoverageBlock, CoverageMap, FuzzCorpus};
+use super::invariant::{Invariant, InvariantContext, MoveInvariant};
 use super::report::*;
 use super::value_gen::ValueGenerator;
 
+/// Share of coverage-guided iterations that mutate a corpus entry (once the
+/// corpus is non-empty) rather than generate fresh inputs.
+const CORPUS_MUTATION_RATE: f64 = 0.8;
+
 /// Configuration for a fuzz run.
 pub struct FuzzConfig {
     /// Number of iterations to run.
@@ -35,21 +52,64 @@ pub struct FuzzConfig {
     pub fail_fast: bool,
     /// Maximum vector length for generated inputs.
     pub max_vector_len: usize,
+    /// Trace executions and mutate inputs that reach new basic blocks.
+    pub coverage_guided: bool,
+}
+
+/// The function being fuzzed.
+struct TargetCall {
+    package: AccountAddress,
+    module: Identifier,
+    function: Identifier,
+    /// Number of values the function returns (for `InvariantArg::Results`).
+    return_count: usize,
+}
+
+/// Result of executing one set of inputs.
+struct Execution {
+    outcome: Outcome,
+    gas_used: u64,
+    /// Blocks entered (empty unless coverage-guided).
+    blocks: BTreeSet<CoverageBlock>,
 }
 
 /// Runs fuzz iterations against the local Move VM.
 pub struct FuzzRunner<'a> {
     resolver: &'a LocalModuleResolver,
+    invariants: Vec<Invariant>,
 }
 
 impl<'a> FuzzRunner<'a> {
     pub fn new(resolver: &'a LocalModuleResolver) -> Self {
-        Self { resolver }
+        Self {
+            resolver,
+            invariants: Vec::new(),
+        }
+    }
+
+    /// Register a property that must hold after every successful iteration.
+    ///
+    /// Move invariants run in the same PTB as the target, so reported gas and
+    /// coverage include them.
+    pub fn with_invariant(mut self, invariant: impl Into<Invariant>) -> Self {
+        self.invariants.push(invariant.into());
+        self
+    }
+
+    fn move_invariants(&self) -> impl Iterator<Item = &MoveInvariant> {
+        self.invariants
+            .iter()
+            .filter_map(|invariant| match invariant {
+                Invariant::Move(invariant) => Some(invariant),
+                Invariant::Check { .. } => None,
+            })
     }
 
     /// Run the fuzzer against a target function.
     ///
     /// Returns a complete FuzzReport with outcomes, gas profile, and interesting cases.
+    /// Coverage-guided runs start from an empty corpus; use
+    /// [`FuzzRunner::run_with_corpus`] to resume a campaign.
     pub fn run(
         &self,
         package: AccountAddress,
@@ -57,6 +117,32 @@ impl<'a> FuzzRunner<'a> {
         function_name: &str,
         classification: &ClassifiedFunction,
         config: &FuzzConfig,
+    ) -> Result<FuzzReport> {
+        let mut corpus = FuzzCorpus::default();
+        self.run_with_corpus(
+            package,
+            module_name,
+            function_name,
+            classification,
+            config,
+            &mut corpus,
+        )
+    }
+
+    /// Run the fuzzer, seeding coverage from `corpus` and adding inputs that
+    /// reach new basic blocks to it.
+    ///
+    /// Existing entries are re-executed first (not counted as iterations) so
+    /// the run only keeps inputs that are new relative to the loaded corpus.
+    /// `corpus` is left untouched unless `config.coverage_guided` is set.
+    pub fn run_with_corpus(
+        &self,
+        package: AccountAddress,
+        module_name: &str,
+        function_name: &str,
+        classification: &ClassifiedFunction,
+        config: &FuzzConfig,
+        corpus: &mut FuzzCorpus,
     ) -> Result<FuzzReport> {
         let target = format!(
             "{}::{}::{}",
@@ -64,6 +150,16 @@ impl<'a> FuzzRunner<'a> {
             module_name,
             function_name
         );
+        if config.coverage_guided {
+            if !corpus.target.is_empty() && corpus.target != target {
+                bail!(
+                    "Fuzz corpus was built for {}, not {}",
+                    corpus.target,
+                    target
+                );
+            }
+            corpus.target = target.clone();
+        }
 
         // Collect the pure parameter types (in order), skipping system-injected ones
         let pure_params: Vec<(usize, &PureType)> = classification
@@ -79,6 +175,32 @@ impl<'a> FuzzRunner<'a> {
             })
             .collect();
 
+        // Static pre-pass: find constants each parameter is compared against.
+        let param_bounds = self
+            .resolver
+            .get_module_by_addr_name(&package, module_name)
+            .map(|module| infer_param_bounds(module, function_name))
+            .unwrap_or_default();
+        let bounded_params: Vec<BoundedParam> = pure_params
+            .iter()
+            .filter_map(|(param_idx, _)| {
+                let constants = param_bounds.get(param_idx)?;
+                Some(BoundedParam {
+                    param_index: *param_idx,
+                    constants: constants.iter().map(|c| c.to_string()).collect(),
+                })
+            })
+            .collect();
+        let pure_bounds: Vec<Vec<u128>> = pure_params
+            .iter()
+            .map(|(param_idx, _)| {
+                param_bounds
+                    .get(param_idx)
+                    .map(|c| c.iter().copied().collect())
+                    .unwrap_or_default()
+            })
+            .collect();
+
         let mut gen = ValueGenerator::new(config.seed, config.max_vector_len);
         let mut successes = 0u64;
         let mut gas_exhaustions = 0u64;
@@ -87,82 +209,95 @@ impl<'a> FuzzRunner<'a> {
         let mut gas_values: Vec<u64> = Vec::with_capacity(config.iterations as usize);
         let mut max_gas_input: Vec<String> = Vec::new();
         let mut max_gas_value = 0u64;
+        let mut violation_map: HashMap<String, InvariantViolationInfo> = HashMap::new();
         let mut interesting_cases: Vec<InterestingCase> = Vec::new();
         let mut completed = 0u64;
 
-        let module_ident = Identifier::new(module_name)
-            .map_err(|e| anyhow!("Invalid module name '{}': {}", module_name, e))?;
-        let function_ident = Identifier::new(function_name)
-            .map_err(|e| anyhow!("Invalid function name '{}': {}", function_name, e))?;
+        let call = TargetCall {
+            package,
+            module: Identifier::new(module_name)
+                .map_err(|e| anyhow!("Invalid module name '{}': {}", module_name, e))?,
+            function: Identifier::new(function_name)
+                .map_err(|e| anyhow!("Invalid function name '{}': {}", function_name, e))?,
+            return_count: self
+                .resolver
+                .get_function_signature(&package, module_name, function_name)
+                .map(|sig| sig.return_types.len())
+                .unwrap_or_default(),
+        };
+        for invariant in self.move_invariants() {
+            self.resolver
+                .check_function_callable(
+                    &invariant.package,
+                    invariant.module.as_str(),
+                    invariant.function.as_str(),
+                )
+                .with_context(|| format!("Invariant {} is not callable", invariant.name()))?;
+        }
+
+        // Coverage seeding: replay corpus entries that still fit the signature.
+        let mut coverage = CoverageMap::default();
+        let mut schedule = StdRng::seed_from_u64(config.seed.rotate_left(32));
+        let mut seeded_entries = 0usize;
+        if config.coverage_guided {
+            corpus
+                .entries
+                .retain(|entry| entry.inputs_bcs_hex.len() == pure_params.len());
+            for entry in &corpus.entries {
+                let execution = self.execute(&call, config, &entry.decode_inputs()?)?;
+                coverage.merge(execution.blocks);
+            }
+            seeded_entries = corpus.len();
+        }
 
         let start = Instant::now();
 
         for iteration in 0..config.iterations {
-            // Generate random pure inputs
-            let mut input_values: Vec<InputValue> = Vec::new();
+            // Mutate a corpus entry, or generate random pure inputs
+            let parent = (config.coverage_guided
+                && !corpus.is_empty()
+                && schedule.gen_bool(CORPUS_MUTATION_RATE))
+            .then(|| corpus.entries[schedule.gen_range(0..corpus.len())].decode_inputs())
+            .transpose()?;
+            let mutate_idx = parent
+                .as_ref()
+                .filter(|parent| !parent.is_empty())
+                .map(|parent| schedule.gen_range(0..parent.len()));
+
+            let mut inputs: Vec<Vec<u8>> = Vec::with_capacity(pure_params.len());
             let mut input_human: Vec<String> = Vec::new();
             let mut input_bcs_hex: Vec<String> = Vec::new();
 
-            for (_param_idx, pure_type) in &pure_params {
-                let bcs_bytes = gen.generate(pure_type);
+            for (i, ((_param_idx, pure_type), bounds)) in
+                pure_params.iter().zip(&pure_bounds).enumerate()
+            {
+                let bcs_bytes = match &parent {
+                    // Mutate one parameter and keep the rest of the parent.
+                    Some(parent) if mutate_idx == Some(i) => gen.mutate(pure_type, &parent[i]),
+                    Some(parent) => parent[i].clone(),
+                    None if bounds.is_empty() => gen.generate(pure_type),
+                    None => gen.generate_bounded(pure_type, bounds),
+                };
                 input_human.push(ValueGenerator::format_value(pure_type, &bcs_bytes));
                 input_bcs_hex.push(hex::encode(&bcs_bytes));
-                input_values.push(InputValue::Pure(bcs_bytes));
+                inputs.push(bcs_bytes);
             }
 
-            // Create fresh VM harness per iteration
-            let sim_config = SimulationConfig {
-                sender_address: config.sender.into(),
-                gas_budget: Some(config.gas_budget),
-                deterministic_random: true,
-                mock_crypto_pass: true,
-                ..Default::default()
-            };
-            let mut harness = match VMHarness::with_config(self.resolver, false, sim_config) {
-                Ok(h) => h,
-                Err(e) => {
-                    return Err(anyhow!("Failed to create VM harness: {}", e));
-                }
-            };
-
-            // Create executor and add inputs
-            let mut executor = PTBExecutor::new(&mut harness);
-            for input in &input_values {
-                executor.add_input(input.clone());
+            let Execution {
+                outcome,
+                gas_used,
+                blocks,
+            } = self.execute(&call, config, &inputs)?;
+
+            if coverage.has_new(&blocks) {
+                let new_blocks = coverage.merge(blocks);
+                corpus.entries.push(CorpusEntry {
+                    inputs_bcs_hex: input_bcs_hex.clone(),
+                    inputs_human: input_human.clone(),
+                    new_blocks,
+                });
             }
 
-            // Build MoveCall command
-            let args: Vec<Argument> = (0..input_values.len())
-                .map(|i| Argument::Input(i as u16))
-                .collect();
-            let command = Command::MoveCall {
-                package,
-                module: module_ident.clone(),
-                function: function_ident.clone(),
-                type_args: config.type_args.clone(),
-                args,
-            };
-
-            // Execute
-            let effects = executor.execute_commands(&[command]);
-
-            // Classify outcome
-            let (outcome, gas_used) = match effects {
-                Ok(effects) => {
-                    let gas = effects.gas_used;
-                    if effects.success {
-                        (Outcome::Success, gas)
-                    } else {
-                        let err_msg = effects.error.unwrap_or_default();
-                        classify_error(&err_msg, gas)
-                    }
-                }
-                Err(e) => {
-                    let err_msg = e.to_string();
-                    classify_error(&err_msg, 0)
-                }
-            };
-
             // Track gas
             gas_values.push(gas_used);
             if gas_used > max_gas_value {
@@ -224,6 +359,29 @@ impl<'a> FuzzRunner<'a> {
                         });
                     }
                 }
+                Outcome::InvariantViolation { invariant, message } => {
+                    let entry = violation_map.entry(invariant.clone()).or_insert_with(|| {
+                        InvariantViolationInfo {
+                            invariant: invariant.clone(),
+                            message: truncate_error(message),
+                            count: 0,
+                            sample_inputs: input_human.clone(),
+                            sample_inputs_bcs: input_bcs_hex.clone(),
+                        }
+                    });
+                    entry.count += 1;
+
+                    // Record first violation of each invariant
+                    if entry.count == 1 {
+                        interesting_cases.push(InterestingCase {
+                            iteration,
+                            outcome: outcome.clone(),
+                            inputs_human: input_human.clone(),
+                            inputs_bcs_hex: input_bcs_hex.clone(),
+                            gas_used,
+                        });
+                    }
+                }
             }
 
             completed = iteration + 1;
@@ -247,8 +405,23 @@ impl<'a> FuzzRunner<'a> {
             .collect();
         errors.sort_by(|a, b| b.count.cmp(&a.count));
 
+        // Build invariant violation list in registration order
+        let invariant_violations: Vec<InvariantViolationInfo> = self
+            .invariants
+            .iter()
+            .filter_map(|invariant| violation_map.remove(&invariant.name()))
+            .collect();
+
         let gas_profile = GasProfile::from_values(&mut gas_values, max_gas_input);
 
+        let coverage = config.coverage_guided.then(|| CoverageSummary {
+            blocks: coverage.block_count(),
+            functions: coverage.function_count(),
+            seeded_entries,
+            new_entries: corpus.len() - seeded_entries,
+            corpus_size: corpus.len(),
+        });
+
         Ok(FuzzReport {
             target,
             total_iterations: config.iterations,
@@ -256,16 +429,152 @@ impl<'a> FuzzRunner<'a> {
             seed: config.seed,
             elapsed_ms,
             classification: classification.clone(),
+            bounded_params,
             outcomes: FuzzOutcomeSummary {
                 successes,
                 gas_exhaustions,
                 aborts,
                 errors,
+                invariant_violations,
             },
             gas_profile,
             interesting_cases,
+            coverage,
         })
     }
+
+    /// Execute the target once in a fresh VM harness, followed by any Move
+    /// invariants in the same PTB.
+    fn execute(
+        &self,
+        call: &TargetCall,
+        config: &FuzzConfig,
+        inputs: &[Vec<u8>],
+    ) -> Result<Execution> {
+        // Create fresh VM harness per iteration
+        let sim_config = SimulationConfig {
+            sender_address: config.sender.into(),
+            gas_budget: Some(config.gas_budget),
+            deterministic_random: true,
+            mock_crypto_pass: true,
+            execution_trace: config.coverage_guided.then(ExecutionTraceOptions::default),
+            ..Default::default()
+        };
+        let mut harness = match VMHarness::with_config(self.resolver, false, sim_config) {
+            Ok(h) => h,
+            Err(e) => {
+                return Err(anyhow!("Failed to create VM harness: {}", e));
+            }
+        };
+
+        // Build MoveCall command, then one command per Move invariant
+        let args: Vec<Argument> = (0..inputs.len())
+            .map(|i| Argument::Input(i as u16))
+            .collect();
+        let mut commands = vec![Command::MoveCall {
+            package: call.package,
+            module: call.module.clone(),
+            function: call.function.clone(),
+            type_args: config.type_args.clone(),
+            args,
+        }];
+        let mut extra_inputs = Vec::new();
+        let move_invariants: Vec<&MoveInvariant> = self.move_invariants().collect();
+        for invariant in &move_invariants {
+            commands.push(invariant.command(inputs.len(), call.return_count, &mut extra_inputs));
+        }
+
+        // Create executor and add inputs
+        let mut executor = PTBExecutor::new(&mut harness);
+        for input in inputs.iter().chain(&extra_inputs) {
+            executor.add_input(InputValue::Pure(input.clone()));
+        }
+
+        // Execute
+        let effects = executor.execute_commands(&commands);
+
+        // Classify outcome
+        let (outcome, gas_used) = match effects {
+            Ok(effects) => {
+                let gas = effects.gas_used;
+                if effects.success {
+                    (
+                        self.check_invariants(&move_invariants, inputs, &effects),
+                        gas,
+                    )
+                } else {
+                    let err_msg = effects.error.unwrap_or_default();
+                    match effects.failed_command_index {
+                        // The target succeeded and a Move invariant after it failed.
+                        Some(idx) if idx > 0 => (
+                            Outcome::InvariantViolation {
+                                invariant: move_invariants[idx - 1].name(),
+                                message: err_msg,
+                            },
+                            gas,
+                        ),
+                        _ => classify_error(&err_msg, gas),
+                    }
+                }
+            }
+            Err(e) => {
+                let err_msg = e.to_string();
+                classify_error(&err_msg, 0)
+            }
+        };
+
+        let blocks = harness
+            .execution_trace()
+            .map(|log| blocks_from_trace(&log.entries()))
+            .unwrap_or_default();
+
+        Ok(Execution {
+            outcome,
+            gas_used,
+            blocks,
+        })
+    }
+
+    /// Check Move invariant return values and Rust invariants after a
+    /// successful PTB. Returns the first violation, or `Outcome::Success`.
+    fn check_invariants(
+        &self,
+        move_invariants: &[&MoveInvariant],
+        inputs: &[Vec<u8>],
+        effects: &TransactionEffects,
+    ) -> Outcome {
+        for (idx, invariant) in move_invariants.iter().enumerate() {
+            // Command 0 is the target.
+            let command_idx = idx + 1;
+            let returns_bool = matches!(
+                effects.return_type_tags.get(command_idx).map(Vec::as_slice),
+                Some([Some(TypeTag::Bool)])
+            );
+            let returned_false = matches!(
+                effects.return_values.get(command_idx).map(Vec::as_slice),
+                Some([value]) if value.as_slice() == [0u8]
+            );
+            if returns_bool && returned_false {
+                return Outcome::InvariantViolation {
+                    invariant: invariant.name(),
+                    message: "returned false".to_string(),
+                };
+            }
+        }
+
+        let context = InvariantContext { inputs, effects };
+        for invariant in &self.invariants {
+            if let Invariant::Check { name, check } = invariant {
+                if let Err(message) = check(&context) {
+                    return Outcome::InvariantViolation {
+                        invariant: name.clone(),
+                        message,
+                    };
+                }
+            }
+        }
+        Outcome::Success
+    }
 }
 
 /// Parse an error message to extract abort code and location.
@@ -393,6 +702,156 @@ mod tests {
         assert_eq!(truncate_error("short error"), "short error");
     }
 
+    #[test]
+    fn test_coverage_guided_run_grows_and_resumes_corpus() {
+        use crate::fuzz::classify_params;
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let module = resolver
+            .get_module_by_addr_name(&AccountAddress::ONE, "u64")
+            .unwrap();
+        let sig = resolver
+            .get_function_signature(&AccountAddress::ONE, "u64", "max")
+            .unwrap();
+        let classification = classify_params(module, &sig.parameter_types);
+        let config = FuzzConfig {
+            iterations: 20,
+            seed: 42,
+            sender: AccountAddress::ZERO,
+            gas_budget: 50_000_000_000,
+            type_args: vec![],
+            fail_fast: false,
+            max_vector_len: 8,
+            coverage_guided: true,
+        };
+
+        let runner = FuzzRunner::new(&resolver);
+        let mut corpus = FuzzCorpus::default();
+        let report = runner
+            .run_with_corpus(
+                AccountAddress::ONE,
+                "u64",
+                "max",
+                &classification,
+                &config,
+                &mut corpus,
+            )
+            .unwrap();
+        let coverage = report.coverage.unwrap();
+        assert!(coverage.blocks > 0);
+        assert!(!corpus.is_empty());
+        assert_eq!(coverage.corpus_size, corpus.len());
+        assert!(corpus.target.ends_with("::u64::max"));
+
+        let resumed = runner
+            .run_with_corpus(
+                AccountAddress::ONE,
+                "u64",
+                "max",
+                &classification,
+                &config,
+                &mut corpus,
+            )
+            .unwrap()
+            .coverage
+            .unwrap();
+        assert_eq!(resumed.seeded_entries, coverage.corpus_size);
+        assert!(resumed.blocks >= coverage.blocks);
+
+        let err = runner
+            .run_with_corpus(
+                AccountAddress::ONE,
+                "u64",
+                "min",
+                &classification,
+                &config,
+                &mut corpus,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("was built for"));
+    }
+
+    #[test]
+    fn test_invariants_flag_violating_inputs() {
+        use crate::fuzz::classify_params;
+        use crate::fuzz::invariant::InvariantArg;
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let module = resolver
+            .get_module_by_addr_name(&AccountAddress::ONE, "u64")
+            .unwrap();
+        let sig = resolver
+            .get_function_signature(&AccountAddress::ONE, "u64", "max")
+            .unwrap();
+        let classification = classify_params(module, &sig.parameter_types);
+        let config = FuzzConfig {
+            iterations: 50,
+            seed: 42,
+            sender: AccountAddress::ZERO,
+            gas_budget: 50_000_000_000,
+            type_args: vec![],
+            fail_fast: false,
+            max_vector_len: 8,
+            coverage_guided: false,
+        };
+        let decode = |bytes: &[u8]| bcs::from_bytes::<u64>(bytes).map_err(|e| e.to_string());
+
+        let runner = FuzzRunner::new(&resolver)
+            // Runs after the target; must not abort.
+            .with_invariant(
+                MoveInvariant::parse("0x1::u64::min")
+                    .unwrap()
+                    .with_args(vec![InvariantArg::Inputs]),
+            )
+            .with_invariant(Invariant::check("max_is_upper_bound", move |ctx| {
+                let result = decode(&ctx.return_values()[0])?;
+                for input in ctx.inputs {
+                    if result < decode(input)? {
+                        return Err(format!("max returned {result}, below an input"));
+                    }
+                }
+                Ok(())
+            }))
+            .with_invariant(Invariant::check("returns_first_input", |ctx| {
+                if ctx.return_values()[0] == ctx.inputs[0] {
+                    Ok(())
+                } else {
+                    Err("max returned the second input".to_string())
+                }
+            }));
+        let report = runner
+            .run(AccountAddress::ONE, "u64", "max", &classification, &config)
+            .unwrap();
+
+        let violations = &report.outcomes.invariant_violations;
+        assert_eq!(violations.len(), 1, "{violations:?}");
+        assert_eq!(violations[0].invariant, "returns_first_input");
+        assert_eq!(violations[0].sample_inputs_bcs.len(), 2);
+        assert_eq!(
+            report.outcomes.successes + violations[0].count,
+            report.completed_iterations
+        );
+        assert!(report.interesting_cases.iter().any(|case| matches!(
+            &case.outcome,
+            Outcome::InvariantViolation { invariant, .. } if invariant == "returns_first_input"
+        )));
+
+        let missing = FuzzRunner::new(&resolver)
+            .with_invariant(MoveInvariant::parse("0x1::u64::no_such_check").unwrap())
+            .run(
+                AccountAddress::ONE,
+                "u64",
+                "max",
+                &classification,
+                &FuzzConfig {
+                    iterations: 1,
+                    ..config
+                },
+            )
+            .unwrap_err();
+        assert!(missing.to_string().contains("is not callable"));
+    }
+
     #[test]
     fn test_truncate_error_long() {
         let long = "x".repeat(300);
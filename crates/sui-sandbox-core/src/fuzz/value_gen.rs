@@ -47,6 +47,87 @@ impl ValueGenerator {
         }
     }
 
+    /// Generate a value biased toward `constants` the parameter is compared against.
+    ///
+    /// Integer types pick from `c - 1`, `c`, `c + 1` (~50%) or a uniform value
+    /// between the smallest and largest constant (~30%); the rest, and all
+    /// non-integer types, fall back to [`ValueGenerator::generate`].
+    pub fn generate_bounded(&mut self, ty: &PureType, constants: &[u128]) -> Vec<u8> {
+        let max = match ty {
+            PureType::U8 => u8::MAX as u128,
+            PureType::U16 => u16::MAX as u128,
+            PureType::U32 => u32::MAX as u128,
+            PureType::U64 => u64::MAX as u128,
+            PureType::U128 => u128::MAX,
+            _ => return self.generate(ty),
+        };
+        let in_type: Vec<u128> = constants.iter().copied().filter(|c| *c <= max).collect();
+        if in_type.is_empty() {
+            return self.generate(ty);
+        }
+
+        let tier: f64 = self.rng.gen();
+        let val = if tier < 0.5 {
+            let base = in_type[self.rng.gen_range(0..in_type.len())];
+            match self.rng.gen_range(0..3) {
+                0 => base.saturating_sub(1),
+                1 => base,
+                _ => base.saturating_add(1).min(max),
+            }
+        } else if tier < 0.8 {
+            let lo = *in_type.iter().min().expect("non-empty");
+            let hi = *in_type.iter().max().expect("non-empty");
+            self.rng.gen_range(lo..=hi)
+        } else {
+            return self.generate(ty);
+        };
+
+        match ty {
+            PureType::U8 => bcs::to_bytes(&(val as u8)).unwrap(),
+            PureType::U16 => bcs::to_bytes(&(val as u16)).unwrap(),
+            PureType::U32 => bcs::to_bytes(&(val as u32)).unwrap(),
+            PureType::U64 => bcs::to_bytes(&(val as u64)).unwrap(),
+            _ => bcs::to_bytes(&val).unwrap(),
+        }
+    }
+
+    /// Mutate an existing BCS value of type `ty` (coverage-guided fuzzing).
+    ///
+    /// Integers get a small delta or a flipped bit, bools flip, addresses and
+    /// u256 get a flipped bit, and byte vectors and strings get one byte
+    /// changed, appended, or removed. Other types, values that do not decode,
+    /// and a share of all mutations fall back to [`ValueGenerator::generate`].
+    pub fn mutate(&mut self, ty: &PureType, bcs_bytes: &[u8]) -> Vec<u8> {
+        if self.rng.gen_bool(0.1) {
+            return self.generate(ty);
+        }
+        match ty {
+            PureType::Bool => match bcs::from_bytes::<bool>(bcs_bytes) {
+                Ok(val) => bcs::to_bytes(&!val).unwrap(),
+                Err(_) => self.generate(ty),
+            },
+            PureType::U8 => self.mutate_integer(ty, bcs_bytes, 1),
+            PureType::U16 => self.mutate_integer(ty, bcs_bytes, 2),
+            PureType::U32 => self.mutate_integer(ty, bcs_bytes, 4),
+            PureType::U64 => self.mutate_integer(ty, bcs_bytes, 8),
+            PureType::U128 => self.mutate_integer(ty, bcs_bytes, 16),
+            PureType::U256 | PureType::Address if bcs_bytes.len() == 32 => {
+                let mut bytes = bcs_bytes.to_vec();
+                let bit = self.rng.gen_range(0..256);
+                bytes[bit / 8] ^= 1 << (bit % 8);
+                bytes
+            }
+            PureType::VectorU8 | PureType::String | PureType::AsciiString => {
+                let Ok(mut bytes) = bcs::from_bytes::<Vec<u8>>(bcs_bytes) else {
+                    return self.generate(ty);
+                };
+                self.mutate_bytes(&mut bytes, !matches!(ty, PureType::VectorU8));
+                bcs::to_bytes(&bytes).unwrap()
+            }
+            _ => self.generate(ty),
+        }
+    }
+
     /// Format a BCS-encoded value as a human-readable string for reporting.
     pub fn format_value(ty: &PureType, bcs_bytes: &[u8]) -> String {
         match ty {
@@ -227,6 +308,55 @@ impl ValueGenerator {
         }
     }
 
+    // ---- Mutation helpers ----
+
+    /// Mutate a little-endian integer of `width` bytes.
+    fn mutate_integer(&mut self, ty: &PureType, bcs_bytes: &[u8], width: usize) -> Vec<u8> {
+        if bcs_bytes.len() != width {
+            return self.generate(ty);
+        }
+        let mut le = [0u8; 16];
+        le[..width].copy_from_slice(bcs_bytes);
+        let val = u128::from_le_bytes(le);
+        let max = u128::MAX >> (128 - width * 8);
+        let mutated = if self.rng.gen_bool(0.5) {
+            let delta = self.rng.gen_range(1..=16_u128);
+            if self.rng.gen_bool(0.5) {
+                val.saturating_add(delta).min(max)
+            } else {
+                val.saturating_sub(delta)
+            }
+        } else {
+            val ^ (1u128 << self.rng.gen_range(0..width * 8))
+        };
+        mutated.to_le_bytes()[..width].to_vec()
+    }
+
+    /// Change, append, or remove one byte. `printable` keeps bytes in ASCII
+    /// `0x20..=0x7E` so strings stay valid.
+    fn mutate_bytes(&mut self, bytes: &mut Vec<u8>, printable: bool) {
+        let new_byte = if printable {
+            self.rng.gen_range(0x20..=0x7E)
+        } else {
+            self.rng.gen()
+        };
+        match self.rng.gen_range(0..3) {
+            0 if !bytes.is_empty() => {
+                let idx = self.rng.gen_range(0..bytes.len());
+                bytes[idx] = new_byte;
+            }
+            1 if !bytes.is_empty() => {
+                bytes.pop();
+            }
+            _ if bytes.len() < self.max_vector_len => bytes.push(new_byte),
+            _ if !bytes.is_empty() => {
+                let idx = self.rng.gen_range(0..bytes.len());
+                bytes[idx] = new_byte;
+            }
+            _ => {}
+        }
+    }
+
     // ---- Integer generation with tiered distribution ----
 
     /// Generate a u64 value with boundary-heavy distribution.
@@ -440,6 +570,59 @@ mod tests {
         assert!(seen.contains(&1), "Should see boundary 1");
     }
 
+    #[test]
+    fn test_generate_bounded_hits_guard_window() {
+        let mut gen = ValueGenerator::new(606, 32);
+        let mut in_range = 0;
+        for _ in 0..200 {
+            let bytes = gen.generate_bounded(&PureType::U64, &[0, 1000]);
+            let val: u64 = bcs::from_bytes(&bytes).expect("u64 BCS roundtrip");
+            if val > 0 && val < 1000 {
+                in_range += 1;
+            }
+        }
+        // Blind u64 generation essentially never lands in (0, 1000) outside boundaries.
+        assert!(
+            in_range > 50,
+            "expected bias toward (0, 1000), got {in_range}"
+        );
+    }
+
+    #[test]
+    fn test_generate_bounded_drops_out_of_type_constants() {
+        let mut gen = ValueGenerator::new(707, 32);
+        for _ in 0..50 {
+            let bytes = gen.generate_bounded(&PureType::U8, &[100_000]);
+            let _val: u8 = bcs::from_bytes(&bytes).expect("u8 BCS roundtrip");
+        }
+    }
+
+    #[test]
+    fn test_mutate_keeps_values_decodable() {
+        let mut gen = ValueGenerator::new(7, 4);
+        let mut u16_val = bcs::to_bytes(&1000u16).unwrap();
+        let mut bytes = bcs::to_bytes(&vec![1u8, 2, 3, 4]).unwrap();
+        let mut text = bcs::to_bytes(&b"abc".to_vec()).unwrap();
+        let mut changed = false;
+        for _ in 0..200 {
+            let next = gen.mutate(&PureType::U16, &u16_val);
+            changed |= next != u16_val;
+            bcs::from_bytes::<u16>(&next).expect("u16 mutation stays 2 bytes");
+            u16_val = next;
+
+            bytes = gen.mutate(&PureType::VectorU8, &bytes);
+            let decoded: Vec<u8> = bcs::from_bytes(&bytes).expect("vector<u8> mutation decodes");
+            assert!(decoded.len() <= 4, "mutation respects max_vector_len");
+
+            text = gen.mutate(&PureType::String, &text);
+            let decoded: Vec<u8> = bcs::from_bytes(&text).expect("string mutation decodes");
+            assert!(std::string::String::from_utf8(decoded).is_ok());
+        }
+        assert!(changed);
+        let flipped = gen.mutate(&PureType::Address, &[0u8; 32]);
+        assert_eq!(flipped.len(), 32);
+    }
+
     #[test]
     fn test_format_value() {
         assert_eq!(
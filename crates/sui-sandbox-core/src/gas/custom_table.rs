@@ -0,0 +1,207 @@
+//! User-supplied gas cost tables for modeling gas schedule changes.
+//!
+//! A [`CustomGasTable`] replaces parts of the protocol's cost schedule: the tiered
+//! instruction/stack cost tables and individual native function costs. Anything it
+//! leaves out keeps the protocol-default value for the configured protocol version,
+//! so a table only needs to list the entries a reprice proposal changes.
+//!
+//! # JSON Shape
+//!
+//! ```json
+//! {
+//!   "instruction_tiers": {"0": 2, "20000": 4, "50000": 8},
+//!   "natives": {"event_emit_per_byte": 20, "dynamic_field_add_child_base": 200}
+//! }
+//! ```
+//!
+//! Tier maps go from a threshold (instructions executed, stack frames, stack bytes) to
+//! the per-unit cost from that threshold on, exactly like Sui's `CostTable`. Native
+//! names are the [`NativeFunctionCosts`] field names.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sui_types::gas_model::units_types::CostTable;
+
+use super::{cost_table_for_version, NativeFunctionCosts};
+
+/// Custom gas cost table; unspecified entries fall back to the protocol defaults.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomGasTable {
+    /// Instructions-executed threshold -> cost per instruction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instruction_tiers: Option<BTreeMap<u64, u64>>,
+    /// Stack-height threshold -> cost per pushed frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_height_tiers: Option<BTreeMap<u64, u64>>,
+    /// Stack-size threshold (bytes) -> cost per byte.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack_size_tiers: Option<BTreeMap<u64, u64>>,
+    /// Native function costs keyed by [`NativeFunctionCosts`] field name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub natives: BTreeMap<String, u64>,
+}
+
+/// Which parts of a [`CustomGasTable`] are custom and which use protocol defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GasTableCoverage {
+    pub custom_tiers: Vec<&'static str>,
+    pub default_tiers: Vec<&'static str>,
+    pub custom_natives: Vec<String>,
+    /// Number of native costs left at their protocol default.
+    pub default_natives: usize,
+    /// Custom entries the simulation does not charge, with the reason
+    /// (see [`crate::vm::SimulationConfig::gas_table_coverage`]).
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub unapplied: BTreeMap<String, &'static str>,
+}
+
+impl CustomGasTable {
+    /// Parse and validate a table from JSON.
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        let table: Self = serde_json::from_str(json).context("invalid gas table JSON")?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Check that every tier map starts at threshold 0 (so every count has a cost)
+    /// and every native name is a known native cost.
+    pub fn validate(&self) -> Result<()> {
+        for (name, tiers) in self.tier_sections() {
+            if let Some(tiers) = tiers {
+                if !tiers.contains_key(&0) {
+                    return Err(anyhow!(
+                        "gas table {} must include a tier starting at 0",
+                        name
+                    ));
+                }
+            }
+        }
+        let known = native_cost_names();
+        let unknown: Vec<&str> = self
+            .natives
+            .keys()
+            .filter(|name| !known.contains(name))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(anyhow!(
+                "gas table has unknown native cost(s): {}",
+                unknown.join(", ")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Instruction cost table: the protocol's table for `gas_model_version` with the
+    /// custom tier maps swapped in.
+    pub fn cost_table(&self, gas_model_version: u64) -> CostTable {
+        let mut table = cost_table_for_version(gas_model_version);
+        if let Some(tiers) = &self.instruction_tiers {
+            table.instruction_tiers = tiers.clone();
+        }
+        if let Some(tiers) = &self.stack_height_tiers {
+            table.stack_height_tiers = tiers.clone();
+        }
+        if let Some(tiers) = &self.stack_size_tiers {
+            table.stack_size_tiers = tiers.clone();
+        }
+        table
+    }
+
+    /// Apply the native overrides on top of `base`.
+    pub fn native_costs(&self, base: NativeFunctionCosts) -> Result<NativeFunctionCosts> {
+        if self.natives.is_empty() {
+            return Ok(base);
+        }
+        let mut value = serde_json::to_value(&base).context("serialize native costs")?;
+        let fields = value
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("native costs are not a JSON object"))?;
+        for (name, cost) in &self.natives {
+            match fields.get_mut(name) {
+                Some(slot) => *slot = serde_json::json!(cost),
+                None => return Err(anyhow!("unknown native cost {}", name)),
+            }
+        }
+        serde_json::from_value(value).context("rebuild native costs")
+    }
+
+    /// Report which entries are custom and which fall back to the protocol defaults.
+    pub fn coverage(&self) -> GasTableCoverage {
+        let (custom_tiers, default_tiers) = self
+            .tier_sections()
+            .into_iter()
+            .partition::<Vec<_>, _>(|(_, tiers)| tiers.is_some());
+        GasTableCoverage {
+            custom_tiers: custom_tiers.into_iter().map(|(name, _)| name).collect(),
+            default_tiers: default_tiers.into_iter().map(|(name, _)| name).collect(),
+            custom_natives: self.natives.keys().cloned().collect(),
+            default_natives: native_cost_names().len().saturating_sub(self.natives.len()),
+            unapplied: BTreeMap::new(),
+        }
+    }
+
+    fn tier_sections(&self) -> [(&'static str, Option<&BTreeMap<u64, u64>>); 3] {
+        [
+            ("instruction_tiers", self.instruction_tiers.as_ref()),
+            ("stack_height_tiers", self.stack_height_tiers.as_ref()),
+            ("stack_size_tiers", self.stack_size_tiers.as_ref()),
+        ]
+    }
+}
+
+/// Field names of [`NativeFunctionCosts`].
+fn native_cost_names() -> Vec<String> {
+    let costs = NativeFunctionCosts::from_protocol_config(&super::load_default_protocol_config());
+    match serde_json::to_value(costs) {
+        Ok(serde_json::Value::Object(fields)) => fields.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_gas_table_overrides_and_defaults() {
+        let table = CustomGasTable::from_json_str(
+            r#"{"instruction_tiers": {"0": 7, "1000": 9}, "natives": {"event_emit_per_byte": 99}}"#,
+        )
+        .unwrap();
+
+        let cost_table = table.cost_table(8);
+        let default_table = cost_table_for_version(8);
+        assert_eq!(cost_table.instruction_tier(10).0, 7);
+        assert_eq!(cost_table.instruction_tier(5000).0, 9);
+        assert_eq!(
+            cost_table.stack_size_tiers, default_table.stack_size_tiers,
+            "unspecified tiers keep the protocol default"
+        );
+
+        let base =
+            NativeFunctionCosts::from_protocol_config(&crate::gas::load_default_protocol_config());
+        let costs = table.native_costs(base.clone()).unwrap();
+        assert_eq!(costs.event_emit_per_byte, 99);
+        assert_eq!(costs.event_emit_base, base.event_emit_base);
+
+        let coverage = table.coverage();
+        assert_eq!(coverage.custom_tiers, vec!["instruction_tiers"]);
+        assert_eq!(coverage.default_tiers.len(), 2);
+        assert_eq!(coverage.custom_natives, vec!["event_emit_per_byte"]);
+        assert!(coverage.default_natives > 0);
+    }
+
+    #[test]
+    fn test_custom_gas_table_validation() {
+        let err =
+            CustomGasTable::from_json_str(r#"{"instruction_tiers": {"100": 1}}"#).unwrap_err();
+        assert!(err.to_string().contains("tier starting at 0"));
+        let err = CustomGasTable::from_json_str(r#"{"natives": {"not_a_native": 1}}"#).unwrap_err();
+        assert!(err.to_string().contains("not_a_native"));
+        assert!(CustomGasTable::from_json_str(r#"{"opcodes": {}}"#).is_err());
+    }
+}
@@ -50,6 +50,7 @@ use move_vm_types::gas::{GasMeter, SimpleInstruction};
 use move_vm_types::views::{TypeView, ValueView};
 
 use sui_types::gas_model::tables::GasStatus;
+use sui_types::gas_model::units_types::CostTable;
 
 use super::{cost_table_for_version, GasParameters};
 
@@ -79,7 +80,22 @@ impl AccurateGasMeter {
     /// * `gas_price` - Gas price for this transaction
     /// * `params` - Gas parameters from protocol config
     pub fn new(budget: u64, gas_price: u64, params: &GasParameters) -> Self {
-        let cost_table = cost_table_for_version(params.gas_model_version);
+        Self::with_cost_table(
+            budget,
+            gas_price,
+            params,
+            cost_table_for_version(params.gas_model_version),
+        )
+    }
+
+    /// Create a meter that charges instructions from `cost_table` instead of the
+    /// protocol's table (see [`super::CustomGasTable`]).
+    pub fn with_cost_table(
+        budget: u64,
+        gas_price: u64,
+        params: &GasParameters,
+        cost_table: CostTable,
+    ) -> Self {
         let mut gas_status = GasStatus::new(
             cost_table,
             budget,
@@ -51,6 +51,7 @@
 //!
 //! 1. **Protocol Configuration** - Loads gas parameters from `ProtocolConfig`
 //! 2. **Cost Tables** - Tiered instruction costs based on gas model version
+//!    (optionally replaced by a user-supplied [`CustomGasTable`])
 //! 3. **Storage Tracking** - Tracks object read/write/delete costs
 //! 4. **Gas Meter** - Implements Move VM's `GasMeter` trait
 //! 5. **Gas Charger** - Orchestrates all gas operations
@@ -82,6 +83,7 @@
 
 mod charger;
 mod cost_table;
+mod custom_table;
 mod meter;
 mod native_costs;
 mod protocol;
@@ -90,6 +92,7 @@ mod summary;
 
 pub use charger::*;
 pub use cost_table::*;
+pub use custom_table::*;
 pub use meter::*;
 pub use native_costs::*;
 pub use protocol::{
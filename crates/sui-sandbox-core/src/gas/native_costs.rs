@@ -23,6 +23,7 @@
 //! let sender_cost = costs.tx_context_sender_base;
 //! ```
 
+use serde::{Deserialize, Serialize};
 use sui_protocol_config::ProtocolConfig;
 
 /// Native function costs extracted from ProtocolConfig.
@@ -30,7 +31,7 @@ use sui_protocol_config::ProtocolConfig;
 /// These costs match the on-chain costs for native function execution.
 /// When accurate gas metering is enabled, these costs should be used
 /// instead of the default zero costs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NativeFunctionCosts {
     // ========== TxContext Natives ==========
     /// tx_context::sender base cost
@@ -0,0 +1,442 @@
+//! Gas attribution per PTB command and per Move function.
+//!
+//! With [`SimulationConfig::gas_profile`](crate::vm::SimulationConfig) set, every Move
+//! call the harness runs goes through a [`ProfilingGasMeter`], which wraps the
+//! configured [`GasMeterImpl`] and follows call frames from the meter's own hooks
+//! (`charge_call` opens a frame, `charge_drop_frame` / `charge_native_function`
+//! closes it). Gas charged while a frame is on top is that function's self gas;
+//! gas charged until the frame closes is its inclusive gas.
+//!
+//! The PTB executor adds one [`CommandGas`] per command and attaches the combined
+//! [`GasProfile`] to `TransactionEffects::gas_profile`. All amounts are gas units
+//! before the gas price is applied, like `ExecutionOutput::computation_gas`.
+
+use std::collections::HashMap;
+
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::{InternalGas, NumArgs, NumBytes};
+use move_core_types::language_storage::ModuleId;
+use move_vm_types::gas::{GasMeter, SimpleInstruction};
+use move_vm_types::views::{TypeView, ValueView};
+use parking_lot::Mutex;
+use serde::Serialize;
+
+use crate::vm::GasMeterImpl;
+
+/// Number of functions kept in [`GasProfile::top_functions`].
+pub const TOP_FUNCTIONS: usize = 10;
+
+/// Gas charged to one Move function across all of its calls.
+///
+/// `inclusive_gas` counts callees; for recursive functions the nested calls are
+/// counted again, so only `self_gas` sums to the transaction total.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FunctionGas {
+    /// `0xaddr::module::function`
+    pub function: String,
+    pub calls: u64,
+    pub inclusive_gas: u64,
+    pub self_gas: u64,
+}
+
+/// Gas charged while one PTB command ran.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandGas {
+    pub index: usize,
+    pub command_type: String,
+    pub description: String,
+    pub gas_used: u64,
+    pub computation_gas: u64,
+    /// Storage charges folded into `gas_used` by accurate metering.
+    pub storage_gas: u64,
+}
+
+/// Gas breakdown of one PTB execution.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GasProfile {
+    pub gas_used: u64,
+    pub computation_gas: u64,
+    pub storage_gas: u64,
+    /// On-chain style cost summary, when accurate gas metering is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<sui_sandbox_types::GasSummary>,
+    pub commands: Vec<CommandGas>,
+    /// The most expensive functions by self gas, at most [`TOP_FUNCTIONS`].
+    pub top_functions: Vec<FunctionGas>,
+}
+
+/// `0xaddr::module::function`, the name used for profile entries.
+pub fn qualified_function_name(module: &ModuleId, function: &str) -> String {
+    format!(
+        "{}::{}::{}",
+        module.address().to_hex_literal(),
+        module.name(),
+        function
+    )
+}
+
+struct OpenFrame {
+    function: String,
+    entered_at: u64,
+    callee_gas: u64,
+    native: bool,
+}
+
+/// Frame stack for a single VM call.
+pub struct FrameGasProfiler {
+    frames: Vec<OpenFrame>,
+    functions: HashMap<String, FunctionGas>,
+}
+
+impl FrameGasProfiler {
+    /// Start profiling a call to `root` with `consumed` gas already charged.
+    pub fn new(root: String, consumed: u64) -> Self {
+        let mut profiler = Self {
+            frames: Vec::new(),
+            functions: HashMap::new(),
+        };
+        profiler.enter(root, consumed);
+        profiler
+    }
+
+    fn enter(&mut self, function: String, consumed: u64) {
+        self.frames.push(OpenFrame {
+            function,
+            entered_at: consumed,
+            callee_gas: 0,
+            native: false,
+        });
+    }
+
+    fn mark_native(&mut self) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.native = true;
+        }
+    }
+
+    fn exit(&mut self, consumed: u64) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        let inclusive = consumed.saturating_sub(frame.entered_at);
+        if let Some(caller) = self.frames.last_mut() {
+            caller.callee_gas += inclusive;
+        }
+        let stats = self
+            .functions
+            .entry(frame.function.clone())
+            .or_insert_with(|| FunctionGas {
+                function: frame.function,
+                ..FunctionGas::default()
+            });
+        stats.calls += 1;
+        stats.inclusive_gas += inclusive;
+        stats.self_gas += inclusive.saturating_sub(frame.callee_gas);
+    }
+
+    fn exit_native(&mut self, consumed: u64) {
+        if self.frames.last().is_some_and(|frame| frame.native) {
+            self.exit(consumed);
+        }
+    }
+
+    /// Close the frames still open (the call aborted or ran out of gas) and return
+    /// the per-function totals.
+    pub fn finish(mut self, consumed: u64) -> HashMap<String, FunctionGas> {
+        while !self.frames.is_empty() {
+            self.exit(consumed);
+        }
+        self.functions
+    }
+}
+
+/// Per-function totals accumulated across the VM calls of one harness.
+#[derive(Debug, Default)]
+pub struct GasProfileLog {
+    functions: Mutex<HashMap<String, FunctionGas>>,
+}
+
+impl GasProfileLog {
+    pub fn record(&self, functions: HashMap<String, FunctionGas>) {
+        let mut totals = self.functions.lock();
+        for (name, gas) in functions {
+            let entry = totals.entry(name).or_insert_with(|| FunctionGas {
+                function: gas.function.clone(),
+                ..FunctionGas::default()
+            });
+            entry.calls += gas.calls;
+            entry.inclusive_gas += gas.inclusive_gas;
+            entry.self_gas += gas.self_gas;
+        }
+    }
+
+    pub fn clear(&self) {
+        self.functions.lock().clear();
+    }
+
+    /// Functions ordered by self gas, then inclusive gas, then name.
+    pub fn top_functions(&self, limit: usize) -> Vec<FunctionGas> {
+        let mut functions: Vec<FunctionGas> = self.functions.lock().values().cloned().collect();
+        functions.sort_by(|a, b| {
+            b.self_gas
+                .cmp(&a.self_gas)
+                .then(b.inclusive_gas.cmp(&a.inclusive_gas))
+                .then_with(|| a.function.cmp(&b.function))
+        });
+        functions.truncate(limit);
+        functions
+    }
+}
+
+/// A [`GasMeter`] that charges `inner` and, with a profiler, attributes each charge
+/// to the function frame on top of the call stack.
+pub struct ProfilingGasMeter<'m> {
+    inner: &'m mut GasMeterImpl,
+    profiler: Option<&'m mut FrameGasProfiler>,
+}
+
+impl<'m> ProfilingGasMeter<'m> {
+    pub fn new(inner: &'m mut GasMeterImpl, profiler: Option<&'m mut FrameGasProfiler>) -> Self {
+        Self { inner, profiler }
+    }
+}
+
+impl GasMeter for ProfilingGasMeter<'_> {
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        self.inner.charge_simple_instr(instr)
+    }
+
+    fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_pop(popped_val)
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.inner
+            .charge_call(module_id, func_name, args, num_locals)?;
+        if let Some(profiler) = self.profiler.as_deref_mut() {
+            profiler.enter(
+                qualified_function_name(module_id, func_name),
+                self.inner.gas_consumed(),
+            );
+        }
+        Ok(())
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.inner
+            .charge_call_generic(module_id, func_name, ty_args, args, num_locals)?;
+        if let Some(profiler) = self.profiler.as_deref_mut() {
+            profiler.enter(
+                qualified_function_name(module_id, func_name),
+                self.inner.gas_consumed(),
+            );
+        }
+        Ok(())
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        self.inner.charge_ld_const(size)
+    }
+
+    fn charge_ld_const_after_deserialization(
+        &mut self,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_ld_const_after_deserialization(val)
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_copy_loc(val)
+    }
+
+    fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_move_loc(val)
+    }
+
+    fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_store_loc(val)
+    }
+
+    fn charge_pack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_pack(is_generic, args)
+    }
+
+    fn charge_unpack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_unpack(is_generic, args)
+    }
+
+    fn charge_variant_switch(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_variant_switch(val)
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_read_ref(val)
+    }
+
+    fn charge_write_ref(
+        &mut self,
+        new_val: impl ValueView,
+        old_val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_write_ref(new_val, old_val)
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_eq(lhs, rhs)
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.inner.charge_neq(lhs, rhs)
+    }
+
+    fn charge_vec_pack<'a>(
+        &mut self,
+        ty: impl TypeView + 'a,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_vec_pack(ty, args)
+    }
+
+    fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.inner.charge_vec_len(ty)
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        is_mut: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_vec_borrow(is_mut, ty, is_success)
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_vec_push_back(ty, val)
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_vec_pop_back(ty, val)
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        ty: impl TypeView,
+        expect_num_elements: NumArgs,
+        elems: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.inner.charge_vec_unpack(ty, expect_num_elements, elems)
+    }
+
+    fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.inner.charge_vec_swap(ty)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+    ) -> PartialVMResult<()> {
+        let charged = self.inner.charge_native_function(amount, ret_vals);
+        if let Some(profiler) = self.profiler.as_deref_mut() {
+            profiler.exit_native(self.inner.gas_consumed());
+        }
+        charged
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        if let Some(profiler) = self.profiler.as_deref_mut() {
+            profiler.mark_native();
+        }
+        self.inner
+            .charge_native_function_before_execution(ty_args, args)
+    }
+
+    fn charge_drop_frame(
+        &mut self,
+        locals: impl Iterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let charged = self.inner.charge_drop_frame(locals);
+        if let Some(profiler) = self.profiler.as_deref_mut() {
+            profiler.exit(self.inner.gas_consumed());
+        }
+        charged
+    }
+
+    fn remaining_gas(&self) -> InternalGas {
+        self.inner.remaining_gas()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_split_self_and_inclusive_gas() {
+        // entry -> helper -> native hash, then entry -> helper again.
+        let mut profiler = FrameGasProfiler::new("0x1::m::entry".to_string(), 100);
+        profiler.enter("0x1::m::helper".to_string(), 105);
+        profiler.enter("0x2::hash::blake2b256".to_string(), 110);
+        profiler.mark_native();
+        profiler.exit_native(115);
+        profiler.exit(130);
+        profiler.enter("0x1::m::helper".to_string(), 132);
+        // Aborts inside the second call: both frames are closed by `finish`.
+        let functions = profiler.finish(140);
+
+        let log = GasProfileLog::default();
+        log.record(functions);
+        let top = log.top_functions(TOP_FUNCTIONS);
+        let names: Vec<&str> = top.iter().map(|f| f.function.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["0x1::m::helper", "0x1::m::entry", "0x2::hash::blake2b256"]
+        );
+        assert_eq!(top[0].calls, 2);
+        assert_eq!(top[0].self_gas, 20 + 8);
+        assert_eq!(top[0].inclusive_gas, 25 + 8);
+        assert_eq!(top[1].inclusive_gas, 40);
+        assert_eq!(top[1].self_gas, 7);
+        let total_self: u64 = top.iter().map(|f| f.self_gas).sum();
+        assert_eq!(total_self, 40);
+        assert_eq!(log.top_functions(1).len(), 1);
+
+        // A native charge with no native frame open must not close the caller.
+        let mut profiler = FrameGasProfiler::new("0x1::m::entry".to_string(), 0);
+        profiler.exit_native(3);
+        assert_eq!(profiler.finish(4)["0x1::m::entry"].inclusive_gas, 4);
+    }
+}
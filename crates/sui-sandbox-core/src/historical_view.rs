@@ -20,7 +20,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sui_resolver::address::normalize_address;
 use sui_state_fetcher::{HistoricalStateProvider, PackageData};
 use sui_transport::graphql::GraphQLClient;
-use sui_transport::grpc::{resolve_historical_endpoint_and_api_key, GrpcClient, GrpcOwner};
+use sui_transport::grpc::{global_pool, resolve_historical_endpoint_and_api_key, GrpcOwner};
 use sui_transport::network::resolve_graphql_endpoint;
 
 use crate::bootstrap::archive_runtime_gap_hint;
@@ -73,17 +73,13 @@ where
     T: Send + 'static,
 {
     if tokio::runtime::Handle::try_current().is_ok() {
-        return std::thread::spawn(move || {
-            let rt = tokio::runtime::Runtime::new().ok()?;
-            rt.block_on(future)
-        })
-        .join()
-        .ok()
-        .flatten();
+        return std::thread::spawn(move || global_pool().block_on(future))
+            .join()
+            .ok()
+            .flatten();
     }
 
-    let rt = tokio::runtime::Runtime::new().ok()?;
-    rt.block_on(future)
+    global_pool().block_on(future)
 }
 
 fn block_on_result<F, T>(future: F) -> Result<T>
@@ -94,8 +90,7 @@ where
         return tokio::task::block_in_place(|| handle.block_on(future));
     }
 
-    let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
-    rt.block_on(future)
+    global_pool().block_on(future)
 }
 
 impl HistoricalViewRequest {
@@ -472,7 +467,8 @@ async fn fetch_required_object_inputs(
     historical_versions: &HashMap<String, u64>,
     required_objects: &[String],
 ) -> Result<Vec<ViewObjectInput>> {
-    let grpc = GrpcClient::with_api_key(grpc_endpoint, grpc_api_key)
+    let grpc = global_pool()
+        .client(grpc_endpoint, grpc_api_key)
         .await
         .context("Failed to create gRPC client")?;
 
@@ -528,7 +524,8 @@ async fn fetch_dynamic_field_object_inputs(
     let parent_scan_limit = env_usize("SUI_HISTORICAL_DYNAMIC_FIELD_PARENT_SCAN_LIMIT", 512).max(1);
 
     let graphql = GraphQLClient::new(&resolve_graphql_endpoint(grpc_endpoint));
-    let grpc = GrpcClient::with_api_key(grpc_endpoint, grpc_api_key)
+    let grpc = global_pool()
+        .client(grpc_endpoint, grpc_api_key)
         .await
         .context("Failed to create gRPC client for dynamic-field hydration")?;
 
@@ -656,7 +653,8 @@ async fn fetch_historical_packages(
             .into_iter()
             .collect();
 
-    let grpc = GrpcClient::with_api_key(grpc_endpoint, grpc_api_key)
+    let grpc = global_pool()
+        .client(grpc_endpoint, grpc_api_key)
         .await
         .context("Failed to create gRPC client")?;
     let graphql = GraphQLClient::new(&resolve_graphql_endpoint(
@@ -713,10 +711,10 @@ fn execute_view_call(
             let fetch_child_id = child_id.clone();
             let fetch_version_hint = version_hint;
             let mut fetched = block_on_optional(async move {
-                let client =
-                    GrpcClient::with_api_key(&fetch_grpc_config.0, fetch_grpc_config.1.clone())
-                        .await
-                        .ok()?;
+                let client = global_pool()
+                    .client(&fetch_grpc_config.0, fetch_grpc_config.1.clone())
+                    .await
+                    .ok()?;
                 let mut fetched = client
                     .get_object_at_version(&fetch_child_id, fetch_version_hint)
                     .await
@@ -784,10 +782,10 @@ fn execute_view_call(
                 let retry_child_id = child_id.clone();
                 let retry_version_hint = version_hint;
                 fetched = block_on_optional(async move {
-                    let client =
-                        GrpcClient::with_api_key(&retry_grpc_config.0, retry_grpc_config.1.clone())
-                            .await
-                            .ok()?;
+                    let client = global_pool()
+                        .client(&retry_grpc_config.0, retry_grpc_config.1.clone())
+                        .await
+                        .ok()?;
                     let mut fetched = client
                         .get_object_at_version(&retry_child_id, retry_version_hint)
                         .await
@@ -44,20 +44,28 @@
 #![allow(clippy::too_many_arguments)]
 
 // Core simulation modules
+pub mod abort_decoder;
 pub mod adapter;
 pub mod bootstrap;
+pub mod call_trace;
 pub mod checkpoint_discovery;
+pub mod checkpoint_replay;
 pub mod constructor_map;
 pub mod context_contract;
 pub mod environment_bootstrap;
 pub mod error_context;
 pub mod errors;
+pub mod execution_trace;
 pub mod fetcher;
+pub mod fork;
 pub mod gas;
+pub mod gas_profile;
 pub mod mm2;
 pub mod natives;
 pub mod orchestrator;
 pub mod phases;
+pub mod protocol_decoders;
+pub mod protocol_registry;
 pub mod sandbox_runtime;
 
 pub mod predictive_prefetch;
@@ -80,6 +88,8 @@ pub mod workflow_adapter;
 pub mod workflow_command_builder;
 pub mod workflow_planner;
 pub mod workflow_runner;
+pub mod workflow_template;
+pub mod workflow_watch;
 
 // Package building and analysis (for creating mock contracts)
 // Note: bytecode_analyzer functionality is in mm2/bytecode_analyzer.rs
@@ -92,10 +102,13 @@ pub mod storage_log;
 pub mod fuzz;
 
 // Replay support (shared between CLI and Python bindings)
+pub mod effects_reconciliation;
 pub mod health;
 pub mod historical_view;
+pub mod object_diff;
 pub mod replay_reporting;
 pub mod replay_support;
+pub mod sandbox_error;
 
 // Utilities for working around infrastructure limitations
 pub mod utilities;
@@ -110,6 +123,7 @@ pub use predictive_prefetch::{
     PredictivePrefetcher,
 };
 pub use resolver::LocalModuleResolver;
+pub use sandbox_error::{SandboxError, SandboxErrorCategory, SandboxErrorKind};
 pub use sandbox_runtime::{
     ChildFetcherFn, ComputedChildInfo, KeyBasedChildFetcherFn, ObjectRuntime, SharedObjectRuntime,
     VersionedChildFetcherFn,
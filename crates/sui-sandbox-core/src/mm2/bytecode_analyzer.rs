@@ -72,6 +72,23 @@ impl DynamicFieldAccessKind {
     pub fn is_mutating(&self) -> bool {
         matches!(self, Self::BorrowMut | Self::Add | Self::Remove)
     }
+
+    /// Returns true if the access reads an existing child (everything but `Add`).
+    pub fn reads_existing_child(&self) -> bool {
+        !matches!(self, Self::Add)
+    }
+
+    /// Stable snake_case name for reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Borrow => "borrow",
+            Self::BorrowMut => "borrow_mut",
+            Self::Add => "add",
+            Self::Remove => "remove",
+            Self::Exists => "exists",
+            Self::FieldInfo => "field_info",
+        }
+    }
 }
 
 /// Result of analyzing a function's bytecode for dynamic field accesses.
@@ -440,6 +457,15 @@ mod tests {
         assert!(!has_unresolved_params("TOKEN"));
     }
 
+    #[test]
+    fn test_access_kind_reads_existing_child() {
+        assert!(!DynamicFieldAccessKind::Add.reads_existing_child());
+        assert!(DynamicFieldAccessKind::Borrow.reads_existing_child());
+        assert!(DynamicFieldAccessKind::Remove.reads_existing_child());
+        assert_eq!(DynamicFieldAccessKind::BorrowMut.as_str(), "borrow_mut");
+        assert_eq!(DynamicFieldAccessKind::FieldInfo.as_str(), "field_info");
+    }
+
     #[test]
     fn test_access_kind_mutating() {
         assert!(!DynamicFieldAccessKind::Borrow.is_mutating());
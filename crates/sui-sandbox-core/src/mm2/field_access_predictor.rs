@@ -63,6 +63,17 @@ pub enum Confidence {
     High = 2,
 }
 
+impl Confidence {
+    /// Stable lowercase name for reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
 /// Predicts dynamic field accesses for function calls.
 pub struct FieldAccessPredictor {
     /// Bytecode analyzer for extracting patterns
@@ -29,6 +29,11 @@
 //! - config::* - System configuration requires on-chain state
 //! - nitro_attestation::* - AWS Nitro attestation requires enclave access
 //!
+//! With `SimulationConfig::stub_unknown_natives`, [`stub_unknown_natives`] makes
+//! category D natives (and natives with no implementation at all) return zero
+//! values instead, recording each one in a [`StubbedNativeLog`]. This is a
+//! degraded analysis mode: results after the first stubbed call are not faithful.
+//!
 //! ## Cryptographic Fidelity
 //!
 //! All cryptographic operations use fastcrypto (Mysten Labs' crypto library),
@@ -44,6 +49,8 @@ macro_rules! debug_native {
 }
 
 use move_binary_format::errors::PartialVMResult;
+use move_binary_format::file_format::SignatureToken;
+use move_binary_format::CompiledModule;
 use move_core_types::{
     account_address::AccountAddress,
     gas_algebra::InternalGas,
@@ -58,7 +65,7 @@ use move_vm_types::{
 };
 use parking_lot::Mutex;
 use smallvec::smallvec;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
@@ -340,6 +347,14 @@ pub struct EmittedEvent {
     pub data: Vec<u8>,
     /// Sequence number within the transaction (0-indexed)
     pub sequence: u64,
+    /// Transaction sender at emission time
+    pub sender: AccountAddress,
+    /// Index of the PTB command that emitted the event (set by the PTB executor)
+    pub command_index: Option<usize>,
+    /// Package of the Move call that emitted the event (Sui's `Event::package_id`)
+    pub package_id: Option<AccountAddress>,
+    /// Module of the Move call that emitted the event (Sui's `Event::transaction_module`)
+    pub transaction_module: Option<String>,
 }
 
 /// Thread-safe store for events emitted during Move execution.
@@ -361,12 +376,18 @@ impl EventStore {
     }
 
     /// Record an emitted event.
-    pub fn emit(&self, type_tag: String, data: Vec<u8>) {
+    ///
+    /// The emitting command and Move call are unknown here; the PTB executor fills them in.
+    pub fn emit(&self, type_tag: String, data: Vec<u8>, sender: AccountAddress) {
         let sequence = self.counter.fetch_add(1, Ordering::SeqCst);
         let event = EmittedEvent {
             type_tag,
             data,
             sequence,
+            sender,
+            command_index: None,
+            package_id: None,
+            transaction_module: None,
         };
         self.events.lock().push(event);
     }
@@ -659,6 +680,11 @@ impl MockNativeState {
         self.events.get_events_by_type(type_prefix)
     }
 
+    /// Number of events emitted so far (the next event's sequence number).
+    pub fn event_count(&self) -> u64 {
+        self.events.count()
+    }
+
     /// Clear all events (call between transactions).
     pub fn clear_events(&self) {
         self.events.clear()
@@ -1292,7 +1318,9 @@ fn build_sui_natives(
                     event_size = event_bytes.len();
 
                     // Record the event
-                    state_clone.events.emit(type_tag_str, event_bytes);
+                    state_clone
+                        .events
+                        .emit(type_tag_str, event_bytes, state_clone.sender);
                 }
             }
             let total_cost = base_cost + (per_byte_cost * event_size as u64);
@@ -1325,7 +1353,9 @@ fn build_sui_natives(
                         )
                         .unwrap_or_default();
                     event_size = event_bytes.len();
-                    state_clone.events.emit(type_tag_str, event_bytes);
+                    state_clone
+                        .events
+                        .emit(type_tag_str, event_bytes, state_clone.sender);
                 }
             }
             let total_cost = base_cost + (per_byte_cost * event_size as u64);
@@ -4012,3 +4042,164 @@ where
 {
     Arc::new(f)
 }
+
+/// A native that returned stub defaults instead of executing.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StubbedNative {
+    /// Fully qualified function, e.g. `0x2::poseidon::poseidon_bn254_internal`.
+    pub function: String,
+    /// `missing` (no implementation registered) or `unsupported` (aborted with
+    /// [`E_NOT_SUPPORTED`]).
+    pub reason: String,
+    /// Times the stub ran.
+    pub calls: u64,
+}
+
+/// Stubbed natives in order of their first call, shared with the stubs.
+#[derive(Debug, Default)]
+pub struct StubbedNativeLog {
+    entries: Mutex<Vec<StubbedNative>>,
+}
+
+impl StubbedNativeLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, function: &str, reason: &str) {
+        let mut entries = self.entries.lock();
+        match entries.iter_mut().find(|entry| entry.function == function) {
+            Some(entry) => entry.calls += 1,
+            None => entries.push(StubbedNative {
+                function: function.to_string(),
+                reason: reason.to_string(),
+                calls: 1,
+            }),
+        }
+    }
+
+    /// Natives stubbed so far, in order of first call.
+    pub fn entries(&self) -> Vec<StubbedNative> {
+        self.entries.lock().clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().clear()
+    }
+}
+
+/// Zero value for a native return type: `false`, `0`, `@0x0`, or an empty vector of a
+/// primitive. `None` for structs, references, type parameters and nested vectors.
+fn stub_default_value(token: &SignatureToken) -> Option<Value> {
+    Some(match token {
+        SignatureToken::Bool => Value::bool(false),
+        SignatureToken::U8 => Value::u8(0),
+        SignatureToken::U16 => Value::u16(0),
+        SignatureToken::U32 => Value::u32(0),
+        SignatureToken::U64 => Value::u64(0),
+        SignatureToken::U128 => Value::u128(0),
+        SignatureToken::U256 => Value::u256(move_core_types::u256::U256::zero()),
+        SignatureToken::Address => Value::address(AccountAddress::ZERO),
+        SignatureToken::Vector(inner) => match inner.as_ref() {
+            SignatureToken::Bool => Value::vector_bool(vec![]),
+            SignatureToken::U8 => Value::vector_u8(vec![]),
+            SignatureToken::U64 => Value::vector_u64(vec![]),
+            SignatureToken::U128 => Value::vector_u128(vec![]),
+            SignatureToken::Address => Value::vector_address(vec![]),
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Stub result for `returns`, or `E_NOT_SUPPORTED` when a return type has no zero value.
+fn stub_result(
+    log: &StubbedNativeLog,
+    function: &str,
+    reason: &str,
+    returns: &[SignatureToken],
+) -> NativeResult {
+    let values: Option<Vec<Value>> = returns.iter().map(stub_default_value).collect();
+    match values {
+        Some(values) => {
+            debug_native!("[natives] stubbed {} ({})", function, reason);
+            log.record(function, reason);
+            NativeResult::ok(InternalGas::new(0), values.into_iter().collect())
+        }
+        None => NativeResult::err(InternalGas::new(0), E_NOT_SUPPORTED),
+    }
+}
+
+/// Best-effort analysis mode: stub every native declared by `modules` that the table
+/// cannot execute.
+///
+/// Natives missing from `table` are added; natives that abort with [`E_NOT_SUPPORTED`]
+/// are wrapped. Either way the stub returns zero values for the declared return types
+/// (see [`stub_default_value`]) and records the call in `log`. Natives returning
+/// structs or references still abort, since no default can be built for them.
+///
+/// The VM binds natives when it is created, so `modules` must be every module it can
+/// load. `VMHarness` passes its resolver's modules; the harness borrows the resolver
+/// for its whole lifetime, so modules added later are covered by the next harness.
+pub fn stub_unknown_natives<'m>(
+    table: &mut NativeFunctionTable,
+    modules: impl IntoIterator<Item = &'m CompiledModule>,
+    log: Arc<StubbedNativeLog>,
+) {
+    let mut seen: HashSet<(AccountAddress, String, String)> = HashSet::new();
+
+    for module in modules {
+        let module_id = module.self_id();
+        for def in module.function_defs() {
+            if !def.is_native() {
+                continue;
+            }
+            let handle = module.function_handle_at(def.function);
+            let name = module.identifier_at(handle.name);
+            let key = (
+                *module_id.address(),
+                module_id.name().to_string(),
+                name.to_string(),
+            );
+            if !seen.insert(key.clone()) {
+                continue;
+            }
+            let function = format!(
+                "{}::{}::{}",
+                module_id.address().to_hex_literal(),
+                module_id.name(),
+                name
+            );
+            let returns = module.signature_at(handle.return_).0.clone();
+            let log = log.clone();
+
+            let existing = table
+                .iter_mut()
+                .find(|(addr, m, f, _)| (*addr, m.to_string(), f.to_string()) == key);
+            match existing {
+                Some(entry) => {
+                    let inner = entry.3.clone();
+                    entry.3 = make_native(move |ctx, ty_args, args| {
+                        let result = inner(ctx, ty_args, args)?;
+                        if matches!(result.result, Err(code) if code == E_NOT_SUPPORTED) {
+                            return Ok(stub_result(&log, &function, "unsupported", &returns));
+                        }
+                        Ok(result)
+                    });
+                }
+                None => table.push((
+                    key.0,
+                    module_id.name().to_owned(),
+                    name.to_owned(),
+                    make_native(move |_ctx, _ty_args, _args| {
+                        Ok(stub_result(&log, &function, "missing", &returns))
+                    }),
+                )),
+            }
+        }
+    }
+}
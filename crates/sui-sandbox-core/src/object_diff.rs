@@ -0,0 +1,228 @@
+//! Field-level diffs of the objects a replay mutated.
+//!
+//! Each mutated object's contents are decoded twice with the struct layout of its
+//! type: the input version from the [`ReplayState`] and the bytes the VM wrote back
+//! ([`TransactionEffects::mutated_object_bytes`]). The two JSON values are then
+//! walked together and every leaf that differs is reported with its path:
+//!
+//! ```text
+//! {"path": "balance", "before": 1000, "after": 750}
+//! {"path": "positions[2].liquidity", "before": "0", "after": "81250"}
+//! ```
+//!
+//! Values use the same JSON conventions as
+//! [`ReplayOrchestrator::decode_bcs_with_layout`].
+
+use serde::Serialize;
+use sui_state_fetcher::ReplayState;
+
+use crate::orchestrator::ReplayOrchestrator;
+use crate::ptb::{ObjectChange, ObjectID, TransactionEffects};
+use crate::resolver::LocalModuleResolver;
+
+/// One leaf that differs between the decoded before and after states. A side is
+/// `null` when the path only exists on the other one (a vector grew or shrank).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObjectFieldChange {
+    pub path: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+/// Before/after state of one mutated object.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObjectDiff {
+    pub object_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_tag: Option<String>,
+    /// Version of the input object the replay started from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
+    pub changes: Vec<ObjectFieldChange>,
+    /// Why the object could not be decoded; `changes` is empty when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Decode and diff every object in `effects.mutated`, ordered by object id.
+///
+/// Objects that cannot be decoded (no input state, unknown type, layout failure) are
+/// still listed, with `error` set.
+pub fn build_object_diffs(
+    replay_state: &ReplayState,
+    effects: &TransactionEffects,
+    resolver: &LocalModuleResolver,
+) -> Vec<ObjectDiff> {
+    let mut mutated: Vec<&ObjectID> = effects.mutated.iter().collect();
+    mutated.sort();
+    mutated.dedup();
+    mutated
+        .into_iter()
+        .map(|id| diff_object(id, replay_state, effects, resolver))
+        .collect()
+}
+
+fn diff_object(
+    id: &ObjectID,
+    replay_state: &ReplayState,
+    effects: &TransactionEffects,
+    resolver: &LocalModuleResolver,
+) -> ObjectDiff {
+    let input = replay_state.objects.get(id);
+    let type_tag = input
+        .and_then(|object| object.type_tag.clone())
+        .or_else(|| mutated_object_type(effects, id));
+    let mut diff = ObjectDiff {
+        object_id: id.to_hex_literal(),
+        type_tag: type_tag.clone(),
+        input_version: input.map(|object| object.version),
+        before: None,
+        after: None,
+        changes: Vec::new(),
+        error: None,
+    };
+
+    let Some(type_tag) = type_tag else {
+        diff.error = Some("object type unknown".to_string());
+        return diff;
+    };
+    let Some(input) = input else {
+        diff.error = Some("object is not in the replay state's inputs".to_string());
+        return diff;
+    };
+    let Some(after_bytes) = effects.mutated_object_bytes.get(id) else {
+        diff.error = Some("execution did not report the object's new contents".to_string());
+        return diff;
+    };
+
+    let decoded = ReplayOrchestrator::decode_bcs_with_layout(&input.bcs_bytes, &type_tag, resolver)
+        .and_then(|before| {
+            ReplayOrchestrator::decode_bcs_with_layout(after_bytes, &type_tag, resolver)
+                .map(|after| (before, after))
+        });
+    match decoded {
+        Ok((before, after)) => {
+            diff.changes = diff_json_fields(&before, &after);
+            diff.before = Some(before);
+            diff.after = Some(after);
+        }
+        Err(err) => diff.error = Some(format!("{:#}", err)),
+    }
+    diff
+}
+
+fn mutated_object_type(effects: &TransactionEffects, id: &ObjectID) -> Option<String> {
+    effects
+        .object_changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Mutated {
+                id: changed,
+                object_type: Some(object_type),
+                ..
+            } if changed == id => Some(object_type.to_canonical_string(true)),
+            _ => None,
+        })
+}
+
+/// Leaf-level differences between two decoded values.
+///
+/// Objects are compared key by key and arrays index by index; any other mismatch
+/// (including a type change) is reported at the path where it occurs.
+pub fn diff_json_fields(
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+) -> Vec<ObjectFieldChange> {
+    let mut changes = Vec::new();
+    diff_values("", before, after, &mut changes);
+    changes
+}
+
+fn diff_values(
+    path: &str,
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+    changes: &mut Vec<ObjectFieldChange>,
+) {
+    use serde_json::Value;
+
+    match (before, after) {
+        (Value::Object(before_fields), Value::Object(after_fields)) => {
+            for (key, before_value) in before_fields {
+                let field_path = join_field(path, key);
+                let after_value = after_fields.get(key).unwrap_or(&Value::Null);
+                diff_values(&field_path, before_value, after_value, changes);
+            }
+            for (key, after_value) in after_fields {
+                if !before_fields.contains_key(key) {
+                    diff_values(&join_field(path, key), &Value::Null, after_value, changes);
+                }
+            }
+        }
+        (Value::Array(before_items), Value::Array(after_items)) => {
+            for index in 0..before_items.len().max(after_items.len()) {
+                diff_values(
+                    &format!("{}[{}]", path, index),
+                    before_items.get(index).unwrap_or(&Value::Null),
+                    after_items.get(index).unwrap_or(&Value::Null),
+                    changes,
+                );
+            }
+        }
+        _ if before != after => changes.push(ObjectFieldChange {
+            path: path.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn join_field(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_changed_leaves_with_paths() {
+        let before = serde_json::json!({
+            "id": "0x5",
+            "balance": 1000,
+            "config": {"fee_bps": 30, "paused": false},
+            "positions": [{"liquidity": "10"}, {"liquidity": "20"}],
+        });
+        let after = serde_json::json!({
+            "id": "0x5",
+            "balance": 750,
+            "config": {"fee_bps": 30, "paused": true},
+            "positions": [{"liquidity": "10"}, {"liquidity": "25"}, {"liquidity": "5"}],
+        });
+
+        let changes = diff_json_fields(&before, &after);
+        let paths: Vec<&str> = changes.iter().map(|change| change.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "balance",
+                "config.paused",
+                "positions[1].liquidity",
+                "positions[2]"
+            ]
+        );
+        assert_eq!(changes[0].before, serde_json::json!(1000));
+        assert_eq!(changes[0].after, serde_json::json!(750));
+        assert_eq!(changes[3].before, serde_json::Value::Null);
+        assert!(diff_json_fields(&before, &before).is_empty());
+    }
+}
@@ -9,6 +9,7 @@ use std::path::Path;
 use anyhow::{anyhow, Context, Result};
 use base64::Engine as _;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::annotated_value::MoveValue;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::TypeTag;
 use rayon::prelude::*;
@@ -20,7 +21,9 @@ use crate::historical_view::{
     HistoricalViewRequest,
 };
 use crate::ptb::{Argument, Command, InputValue, ObjectChange, ObjectInput, PTBBuilder};
+use crate::resolver::LocalModuleResolver;
 use crate::simulation::{ExecutionResult, SimulationEnvironment};
+use crate::validator::Validator;
 use crate::workflow::{WorkflowAnalyzeReplayStep, WorkflowDefaults, WorkflowReplayStep};
 
 /// Replay-first orchestrator surface shared by CLI/Python adapters.
@@ -179,6 +182,54 @@ pub struct HistoricalSeriesReport {
     pub summary: HistoricalSeriesSummary,
 }
 
+/// Execution options for batch replay runs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct ReplayBatchOptions {
+    /// Optional upper bound on worker threads replaying in parallel.
+    ///
+    /// `None` or `Some(1)` replays sequentially.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
+}
+
+/// Outcome of one digest in a batch replay run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplayBatchRun {
+    pub digest: String,
+    /// The replay ran and its local execution succeeded.
+    pub success: bool,
+    pub elapsed_ms: f64,
+    /// Hydration/setup error, or the local execution error of a failed replay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Replay output; absent when the replay errored before producing one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<serde_json::Value>,
+}
+
+/// Aggregate summary over batch replay runs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplayBatchSummary {
+    pub total: usize,
+    pub success: usize,
+    /// Replays that ran but failed local execution.
+    pub failed: usize,
+    /// Replays that errored before producing an output (e.g. hydration failures).
+    pub errored: usize,
+    pub success_rate: f64,
+    pub max_concurrency: usize,
+    pub wall_ms: f64,
+    pub average_elapsed_ms: f64,
+    pub max_elapsed_ms: f64,
+}
+
+/// Full output payload for one batch replay run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayBatchReport {
+    pub runs: Vec<ReplayBatchRun>,
+    pub summary: ReplayBatchSummary,
+}
+
 /// Compact execution-effects summary for one PTB/function call.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub struct ExecutionEffectsSummary {
@@ -364,6 +415,43 @@ fn run_historical_series_point(
     })
 }
 
+fn replay_batch_run(
+    digest: &str,
+    elapsed: std::time::Duration,
+    outcome: Result<serde_json::Value>,
+) -> ReplayBatchRun {
+    let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+    match outcome {
+        Ok(output) => {
+            let success = output
+                .get("local_success")
+                .and_then(serde_json::Value::as_bool)
+                == Some(true);
+            let error = (!success).then(|| {
+                output
+                    .get("local_error")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("local execution failed")
+                    .to_string()
+            });
+            ReplayBatchRun {
+                digest: digest.to_string(),
+                success,
+                elapsed_ms,
+                error,
+                output: Some(output),
+            }
+        }
+        Err(err) => ReplayBatchRun {
+            digest: digest.to_string(),
+            success: false,
+            elapsed_ms,
+            error: Some(format!("{:#}", err)),
+            output: None,
+        },
+    }
+}
+
 fn parse_historical_series_point_entry(value: &serde_json::Value) -> Result<HistoricalSeriesPoint> {
     let obj = value
         .as_object()
@@ -786,6 +874,75 @@ impl ReplayOrchestrator {
         }
     }
 
+    /// Replay `digests` with up to `options.max_concurrency` workers.
+    ///
+    /// `replay_one` produces one replay output (with a `local_success` flag) per digest;
+    /// callers share caches across digests by capturing them in the closure, typically a
+    /// [`SharedReplayResolver`](crate::replay_support::SharedReplayResolver) for package
+    /// resolution and its `VersionedCache` for state. Errors are
+    /// recorded per digest rather than aborting the batch, and runs keep input order.
+    pub fn replay_many<F>(
+        digests: &[String],
+        options: &ReplayBatchOptions,
+        replay_one: F,
+    ) -> Result<ReplayBatchReport>
+    where
+        F: Fn(&str) -> Result<serde_json::Value> + Sync,
+    {
+        let workers = options.max_concurrency.unwrap_or(1).max(1);
+        let run_digest = |digest: &String| {
+            let start = std::time::Instant::now();
+            let outcome = replay_one(digest);
+            replay_batch_run(digest, start.elapsed(), outcome)
+        };
+
+        let start = std::time::Instant::now();
+        let runs = if workers <= 1 || digests.len() <= 1 {
+            digests.iter().map(run_digest).collect::<Vec<_>>()
+        } else {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(workers)
+                .build()
+                .context("failed to build batch replay worker pool")?;
+            pool.install(|| digests.par_iter().map(run_digest).collect::<Vec<_>>())
+        };
+        let summary = Self::summarize_replay_batch_runs(&runs, workers, start.elapsed());
+        Ok(ReplayBatchReport { runs, summary })
+    }
+
+    /// Summarize batch replay outcomes.
+    pub fn summarize_replay_batch_runs(
+        runs: &[ReplayBatchRun],
+        max_concurrency: usize,
+        wall: std::time::Duration,
+    ) -> ReplayBatchSummary {
+        let total = runs.len();
+        let success = runs.iter().filter(|run| run.success).count();
+        let errored = runs.iter().filter(|run| run.output.is_none()).count();
+        let total_elapsed_ms = runs.iter().map(|run| run.elapsed_ms).sum::<f64>();
+        let max_elapsed_ms = runs.iter().map(|run| run.elapsed_ms).fold(0.0, f64::max);
+        let (average_elapsed_ms, success_rate) = if total > 0 {
+            (
+                total_elapsed_ms / total as f64,
+                success as f64 / total as f64,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        ReplayBatchSummary {
+            total,
+            success,
+            failed: total - success - errored,
+            errored,
+            success_rate,
+            max_concurrency,
+            wall_ms: wall.as_secs_f64() * 1000.0,
+            average_elapsed_ms,
+            max_elapsed_ms,
+        }
+    }
+
     /// Convenience constructor for batch snapshots.
     pub fn snapshot_from_checkpoint_versions(
         checkpoint: u64,
@@ -925,6 +1082,61 @@ impl ReplayOrchestrator {
         Ok(Some(typed))
     }
 
+    /// Decode command return values using struct layouts from `resolver`.
+    ///
+    /// Like [`Self::decode_command_return_values_typed`], but struct returns (including
+    /// `vector<T>` and `Option<T>` of structs) decode field by field: a `vector<T>`
+    /// becomes a JSON array of `T` objects. Values whose layout cannot be resolved
+    /// fall back to the type-tag-only decode.
+    pub fn decode_command_return_values_with_layouts(
+        raw: &serde_json::Value,
+        command_index: usize,
+        resolver: &LocalModuleResolver,
+    ) -> Result<Option<Vec<DecodedReturnValue>>> {
+        let Some(values) = Self::decode_command_return_values(raw, command_index)? else {
+            return Ok(None);
+        };
+        let tags = Self::decode_command_return_type_tags(raw, command_index)?.unwrap_or_default();
+        let validator = Validator::new(resolver);
+        let decoded = values
+            .iter()
+            .enumerate()
+            .map(|(idx, bytes)| {
+                let type_tag = tags.get(idx).cloned().unwrap_or(None);
+                let value = match type_tag.as_deref() {
+                    Some(tag) => decode_bytes_with_layout(&validator, bytes, tag)
+                        .or_else(|layout_err| {
+                            decode_bytes_with_optional_type_tag(bytes, Some(tag), true)
+                                .map_err(|_| layout_err)
+                        })
+                        .unwrap_or_else(|err| raw_decode_value(bytes, Some(format!("{:#}", err)))),
+                    None => raw_decode_value(bytes, None),
+                };
+                DecodedReturnValue {
+                    index: idx,
+                    type_tag,
+                    value,
+                    raw_base64: base64::engine::general_purpose::STANDARD.encode(bytes),
+                    raw_hex: format!("0x{}", hex::encode(bytes)),
+                }
+            })
+            .collect();
+        Ok(Some(decoded))
+    }
+
+    /// Decode BCS `bytes` of `type_tag` field by field, with the struct layout
+    /// resolved from `resolver`'s loaded modules (used for emitted event payloads).
+    ///
+    /// Uses the same JSON conventions as
+    /// [`Self::decode_command_return_values_with_layouts`].
+    pub fn decode_bcs_with_layout(
+        bytes: &[u8],
+        type_tag: &str,
+        resolver: &LocalModuleResolver,
+    ) -> Result<serde_json::Value> {
+        decode_bytes_with_layout(&Validator::new(resolver), bytes, type_tag)
+    }
+
     /// Decode command return values into a named object according to a schema.
     ///
     /// Schema fields can override type decoding with `type_hint` and optionally apply
@@ -1595,6 +1807,92 @@ fn decode_bytes_with_type_tag(bytes: &[u8], type_tag: &TypeTag) -> Result<serde_
     }
 }
 
+/// Decode `bytes` as `type_tag` with its full (struct-aware) layout.
+fn decode_bytes_with_layout(
+    validator: &Validator<'_>,
+    bytes: &[u8],
+    type_tag: &str,
+) -> Result<serde_json::Value> {
+    let tag = crate::types::parse_type_tag(type_tag)
+        .with_context(|| format!("invalid type tag '{}'", type_tag))?;
+    let layout = validator
+        .resolve_type_layout(&tag)
+        .with_context(|| format!("resolve layout for {}", type_tag))?;
+    let value = MoveValue::simple_deserialize(bytes, &layout)
+        .with_context(|| format!("decode {} with layout", type_tag))?;
+    Ok(move_value_to_json(&value))
+}
+
+/// JSON form of a decoded Move value, matching the scalar conventions of the
+/// type-tag decoder (u128/u256 as strings, addresses as hex literals).
+///
+/// `String`/`ascii::String` become JSON strings, `UID`/`ID` their hex id, and
+/// `Option<T>` `null` or the inner value.
+fn move_value_to_json(value: &MoveValue) -> serde_json::Value {
+    match value {
+        MoveValue::Bool(v) => serde_json::json!(v),
+        MoveValue::U8(v) => serde_json::json!(v),
+        MoveValue::U16(v) => serde_json::json!(v),
+        MoveValue::U32(v) => serde_json::json!(v),
+        MoveValue::U64(v) => serde_json::json!(v),
+        MoveValue::U128(v) => serde_json::Value::String(v.to_string()),
+        MoveValue::U256(v) => serde_json::Value::String(v.to_string()),
+        MoveValue::Address(v) | MoveValue::Signer(v) => {
+            serde_json::Value::String(v.to_hex_literal())
+        }
+        MoveValue::Vector(items) => {
+            serde_json::Value::Array(items.iter().map(move_value_to_json).collect())
+        }
+        MoveValue::Struct(s) => {
+            let tag = &s.type_;
+            let well_known = (tag.address, tag.module.as_str(), tag.name.as_str());
+            match (well_known, &s.fields[..]) {
+                (
+                    (AccountAddress::ONE, "string" | "ascii", "String"),
+                    [(_, MoveValue::Vector(bytes))],
+                ) => {
+                    let raw: Vec<u8> = bytes
+                        .iter()
+                        .filter_map(|b| match b {
+                            MoveValue::U8(b) => Some(*b),
+                            _ => None,
+                        })
+                        .collect();
+                    serde_json::Value::String(String::from_utf8_lossy(&raw).into_owned())
+                }
+                ((AccountAddress::ONE, "option", "Option"), [(_, MoveValue::Vector(items))]) => {
+                    items
+                        .first()
+                        .map(move_value_to_json)
+                        .unwrap_or(serde_json::Value::Null)
+                }
+                ((AccountAddress::TWO, "object", "UID" | "ID"), [(_, inner)]) => {
+                    match move_value_to_json(inner) {
+                        serde_json::Value::Object(map) if map.len() == 1 => {
+                            map.into_iter().next().map(|(_, v)| v).unwrap_or_default()
+                        }
+                        other => other,
+                    }
+                }
+                (_, fields) => fields_to_json(fields),
+            }
+        }
+        MoveValue::Variant(v) => serde_json::json!({
+            "variant": v.variant_name.to_string(),
+            "fields": fields_to_json(&v.fields),
+        }),
+    }
+}
+
+fn fields_to_json(fields: &[(Identifier, MoveValue)]) -> serde_json::Value {
+    serde_json::Value::Object(
+        fields
+            .iter()
+            .map(|(field, value)| (field.to_string(), move_value_to_json(value)))
+            .collect(),
+    )
+}
+
 fn decode_vector_with_type_tag(bytes: &[u8], inner: &TypeTag) -> Result<serde_json::Value> {
     match inner {
         TypeTag::Bool => {
@@ -1742,6 +2040,61 @@ mod tests {
         assert_eq!(decoded[1].value, serde_json::json!(true));
     }
 
+    #[test]
+    fn decodes_vector_of_struct_returns_with_layouts() {
+        // A view returning `vector<VecMap Entry<u64, bool>>` (a two-field struct).
+        let entries: Vec<(u64, bool)> = vec![(7, true), (42, false)];
+        let encoded =
+            base64::engine::general_purpose::STANDARD.encode(bcs::to_bytes(&entries).unwrap());
+        let raw = serde_json::json!({
+            "success": true,
+            "return_values": [[encoded]],
+            "return_type_tags": [["vector<0x2::vec_map::Entry<u64, bool>>"]],
+        });
+        let resolver = LocalModuleResolver::with_sui_framework().expect("framework");
+
+        let decoded =
+            ReplayOrchestrator::decode_command_return_values_with_layouts(&raw, 0, &resolver)
+                .expect("layout decode should succeed")
+                .expect("values should exist");
+        assert_eq!(
+            decoded[0].value,
+            serde_json::json!([
+                {"key": 7, "value": true},
+                {"key": 42, "value": false},
+            ])
+        );
+
+        // Without layouts the same value stays raw.
+        let typed = ReplayOrchestrator::decode_command_return_values_typed(&raw, 0)
+            .unwrap()
+            .unwrap();
+        assert!(typed[0].value.get("raw_base64").is_some());
+    }
+
+    #[test]
+    fn decodes_event_payload_with_struct_layout() {
+        let id = AccountAddress::from_hex_literal("0xabc").unwrap();
+        let bytes = bcs::to_bytes(&id).unwrap();
+        let resolver = LocalModuleResolver::with_sui_framework().expect("framework");
+
+        let decoded = ReplayOrchestrator::decode_bcs_with_layout(
+            &bytes,
+            "0x2::display::DisplayCreated<0x2::coin::Coin<0x2::sui::SUI>>",
+            &resolver,
+        )
+        .expect("event should decode");
+        assert_eq!(decoded, serde_json::json!({"id": id.to_hex_literal()}));
+
+        let err = ReplayOrchestrator::decode_bcs_with_layout(
+            &bytes,
+            "0x2::display::NoSuchEvent",
+            &resolver,
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("resolve layout"));
+    }
+
     #[test]
     fn decodes_command_return_schema_with_scaling() {
         let encoded_u64 =
@@ -2142,6 +2495,45 @@ mod tests {
         assert_eq!(summary.success_rate, 0.5);
     }
 
+    #[test]
+    fn replay_many_keeps_order_and_counts_outcomes() {
+        let digests: Vec<String> = ["ok", "failed", "errored", "ok2"]
+            .iter()
+            .map(|digest| digest.to_string())
+            .collect();
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let report = ReplayOrchestrator::replay_many(
+            &digests,
+            &ReplayBatchOptions {
+                max_concurrency: Some(3),
+            },
+            |digest| {
+                calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                match digest {
+                    "failed" => Ok(serde_json::json!({
+                        "local_success": false,
+                        "local_error": "MoveAbort(7)",
+                    })),
+                    "errored" => Err(anyhow!("Failed to fetch replay state")),
+                    _ => Ok(serde_json::json!({"local_success": true})),
+                }
+            },
+        )
+        .expect("batch");
+
+        assert_eq!(calls.into_inner(), 4);
+        let order: Vec<&str> = report.runs.iter().map(|run| run.digest.as_str()).collect();
+        assert_eq!(order, vec!["ok", "failed", "errored", "ok2"]);
+        assert_eq!(report.runs[1].error.as_deref(), Some("MoveAbort(7)"));
+        assert!(report.runs[2].output.is_none());
+        assert_eq!(report.summary.total, 4);
+        assert_eq!(report.summary.success, 2);
+        assert_eq!(report.summary.failed, 1);
+        assert_eq!(report.summary.errored, 1);
+        assert_eq!(report.summary.success_rate, 0.5);
+        assert_eq!(report.summary.max_concurrency, 3);
+    }
+
     #[test]
     fn move_call_builder_builds_command() {
         let package = AccountAddress::from_hex_literal("0x2").expect("package");
@@ -0,0 +1,284 @@
+//! Protocol-aware decoders for well-known view-function returns.
+//!
+//! A decoder maps one `module::function` return tuple to named, human-unit JSON
+//! fields (via [`ReturnDecodeField`] schemas). Decoders are grouped by protocol
+//! name (normalized with [`normalize_protocol_name`]), so callers can write
+//! `protocol_decode("deepbook", &result)` instead of hand-maintaining return
+//! indices and scales. [`ProtocolDecoderRegistry::register`] adds decoders for
+//! further protocols or functions.
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::adapter::normalize_protocol_name;
+use crate::orchestrator::{ReplayOrchestrator, ReturnDecodeField};
+
+/// Decoder for one view function's return tuple.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ProtocolDecoder {
+    pub module: String,
+    pub function: String,
+    pub description: String,
+    pub fields: Vec<ReturnDecodeField>,
+}
+
+impl ProtocolDecoder {
+    pub fn new(
+        module: impl Into<String>,
+        function: impl Into<String>,
+        fields: Vec<ReturnDecodeField>,
+    ) -> Self {
+        Self {
+            module: module.into(),
+            function: function.into(),
+            description: String::new(),
+            fields,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Decoder name (`module::function`).
+    pub fn name(&self) -> String {
+        format!("{}::{}", self.module, self.function)
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        let name = name.trim();
+        name == self.function || name == self.name()
+    }
+}
+
+/// Decoders keyed by normalized protocol name.
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolDecoderRegistry {
+    decoders: BTreeMap<String, Vec<ProtocolDecoder>>,
+}
+
+impl ProtocolDecoderRegistry {
+    /// Empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with the built-in DeepBook and Cetus decoders.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+
+        // Amounts stay in base units: asset decimals depend on the pool's coin pair.
+        // Risk ratio is a 1e9 fixed-point float, reported here as a percentage.
+        registry.register(
+            "deepbook",
+            ProtocolDecoder::new(
+                "margin_manager",
+                "manager_state",
+                vec![
+                    ReturnDecodeField::scaled_u64(2, "risk_ratio_pct", 10_000_000.0),
+                    ReturnDecodeField::new(3, "base_asset").with_type_hint("u64"),
+                    ReturnDecodeField::new(4, "quote_asset").with_type_hint("u64"),
+                    ReturnDecodeField::new(5, "base_debt").with_type_hint("u64"),
+                    ReturnDecodeField::new(6, "quote_debt").with_type_hint("u64"),
+                    ReturnDecodeField::new(11, "current_price").with_type_hint("u64"),
+                ],
+            )
+            .with_description("DeepBook margin manager risk, assets, and debts"),
+        );
+
+        registry.register(
+            "cetus",
+            ProtocolDecoder::new(
+                "pool",
+                "fees_growth_global",
+                vec![
+                    ReturnDecodeField::new(0, "fee_growth_global_a").with_type_hint("u128"),
+                    ReturnDecodeField::new(1, "fee_growth_global_b").with_type_hint("u128"),
+                ],
+            )
+            .with_description("Cetus CLMM global fee growth (Q64.64) per coin"),
+        );
+        registry.register(
+            "cetus",
+            ProtocolDecoder::new(
+                "pool",
+                "protocol_fee",
+                vec![
+                    ReturnDecodeField::new(0, "protocol_fee_a").with_type_hint("u64"),
+                    ReturnDecodeField::new(1, "protocol_fee_b").with_type_hint("u64"),
+                ],
+            )
+            .with_description("Cetus CLMM protocol fees owed per coin, in base units"),
+        );
+
+        registry
+    }
+
+    /// Add a decoder for `protocol`, replacing one with the same name.
+    pub fn register(&mut self, protocol: &str, decoder: ProtocolDecoder) {
+        let entries = self
+            .decoders
+            .entry(normalize_protocol_name(protocol))
+            .or_default();
+        entries.retain(|existing| existing.name() != decoder.name());
+        entries.push(decoder);
+    }
+
+    /// Protocols with at least one decoder, sorted.
+    pub fn protocols(&self) -> Vec<&str> {
+        self.decoders.keys().map(String::as_str).collect()
+    }
+
+    /// Decoders registered for `protocol`.
+    pub fn decoders(&self, protocol: &str) -> &[ProtocolDecoder] {
+        self.decoders
+            .get(&normalize_protocol_name(protocol))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Pick a decoder by `module::function` or bare function name; `None` selects
+    /// the protocol's only decoder.
+    pub fn resolve(&self, protocol: &str, decoder: Option<&str>) -> Result<&ProtocolDecoder> {
+        let candidates = self.decoders(protocol);
+        if candidates.is_empty() {
+            return Err(anyhow!(
+                "no decoders registered for protocol '{}': expected one of {}",
+                protocol.trim(),
+                self.protocols().join(", ")
+            ));
+        }
+        let names = || {
+            candidates
+                .iter()
+                .map(ProtocolDecoder::name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match decoder {
+            Some(name) => candidates
+                .iter()
+                .find(|candidate| candidate.matches(name))
+                .ok_or_else(|| {
+                    anyhow!(
+                        "protocol '{}' has no decoder '{}': expected one of {}",
+                        protocol.trim(),
+                        name,
+                        names()
+                    )
+                }),
+            None if candidates.len() == 1 => Ok(&candidates[0]),
+            None => Err(anyhow!(
+                "protocol '{}' has several decoders; pass decoder= one of {}",
+                protocol.trim(),
+                names()
+            )),
+        }
+    }
+
+    /// Decode one command's return values with the selected decoder.
+    ///
+    /// Returns `None` when `raw` carries no return values for `command_index`
+    /// (for example, a failed execution).
+    pub fn decode(
+        &self,
+        protocol: &str,
+        raw: &serde_json::Value,
+        decoder: Option<&str>,
+        command_index: usize,
+    ) -> Result<Option<serde_json::Value>> {
+        let selected = self.resolve(protocol, decoder)?;
+        let Some(fields) =
+            ReplayOrchestrator::decode_command_return_schema(raw, command_index, &selected.fields)?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(serde_json::json!({
+            "protocol": normalize_protocol_name(protocol),
+            "decoder": selected.name(),
+            "decoded": fields,
+        })))
+    }
+}
+
+/// Decode `raw` with the built-in decoder for `protocol`.
+pub fn protocol_decode(
+    protocol: &str,
+    raw: &serde_json::Value,
+    decoder: Option<&str>,
+    command_index: usize,
+) -> Result<Option<serde_json::Value>> {
+    ProtocolDecoderRegistry::builtin().decode(protocol, raw, decoder, command_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+
+    fn encoded_u64s(values: &[u64]) -> Vec<String> {
+        values
+            .iter()
+            .map(|v| base64::engine::general_purpose::STANDARD.encode(v.to_le_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn deepbook_manager_state_decodes_by_protocol_name() {
+        let mut values = vec![0u64; 12];
+        values[2] = 1_500_000_000; // 1.5 risk ratio
+        values[6] = 1_180_196;
+        let raw = serde_json::json!({
+            "success": true,
+            "return_values": [encoded_u64s(&values)],
+        });
+
+        let out = protocol_decode(" DeepBook ", &raw, None, 0)
+            .expect("decode")
+            .expect("values");
+        assert_eq!(out["decoder"], "margin_manager::manager_state");
+        assert_eq!(out["decoded"]["risk_ratio_pct"], serde_json::json!(150.0));
+        assert_eq!(
+            out["decoded"]["quote_debt"],
+            serde_json::json!(1_180_196u64)
+        );
+    }
+
+    #[test]
+    fn ambiguous_and_unknown_decoders_are_rejected() {
+        let registry = ProtocolDecoderRegistry::builtin();
+        let err = registry.resolve("cetus", None).unwrap_err().to_string();
+        assert!(err.contains("pool::protocol_fee"), "{err}");
+        assert_eq!(
+            registry
+                .resolve("cetus", Some("protocol_fee"))
+                .unwrap()
+                .name(),
+            "pool::protocol_fee"
+        );
+        assert!(registry.resolve("unknown", None).is_err());
+    }
+
+    #[test]
+    fn registered_decoders_extend_and_replace() {
+        let mut registry = ProtocolDecoderRegistry::new();
+        registry.register(
+            "Suilend",
+            ProtocolDecoder::new("obligation", "deposited_value_usd", vec![]),
+        );
+        registry.register(
+            "suilend",
+            ProtocolDecoder::new(
+                "obligation",
+                "deposited_value_usd",
+                vec![ReturnDecodeField::new(0, "value")],
+            ),
+        );
+        assert_eq!(registry.protocols(), vec!["suilend"]);
+        assert_eq!(registry.decoders("SUILEND").len(), 1);
+        assert_eq!(registry.decoders("suilend")[0].fields.len(), 1);
+    }
+}
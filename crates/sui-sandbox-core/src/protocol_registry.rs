@@ -0,0 +1,404 @@
+//! Protocol adapter definitions loaded from config files.
+//!
+//! An adapter describes how the protocol-first flows (`adapter_discover`,
+//! `adapter_prepare`, workflow template inference) treat one protocol: its default
+//! package IDs, objects its transactions usually need, the built-in workflow template
+//! it maps to, and module-name keywords used to recognize it.
+//!
+//! [`ProtocolRegistry::load_default`] starts from the built-in adapters and then
+//! loads every `*.toml` / `*.json` file in [`protocol_config_dir`], so third parties
+//! can ship an adapter as a file:
+//!
+//! ```toml
+//! name = "turbos"
+//! description = "Turbos CLMM"
+//! package_ids = ["0x91bfbc386a41afcfd9b2533058d7e915a1d3829089cc268ff4333d54d6339ca1"]
+//! required_objects = ["0x6"]
+//! template = "cetus"
+//! keywords = ["turbos", "swap_router"]
+//! ```
+//!
+//! A file may also hold several adapters (`[[adapters]]` in TOML, an array or
+//! `{"adapters": [...]}` in JSON). Later definitions replace earlier ones with the
+//! same (normalized) name.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::adapter::normalize_protocol_name;
+use crate::checkpoint_discovery::normalize_package_id;
+use crate::workflow_adapter::BuiltinWorkflowTemplate;
+use crate::workflow_planner::parse_builtin_workflow_template;
+
+/// Overrides the adapter config directory.
+pub const PROTOCOL_CONFIG_DIR_ENV: &str = "SUI_SANDBOX_PROTOCOLS_DIR";
+
+/// One protocol adapter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtocolAdapterDefinition {
+    pub name: String,
+    pub description: String,
+    /// Default package IDs; the first is used when the caller passes none.
+    pub package_ids: Vec<String>,
+    /// Objects transactions against this protocol typically read (e.g. shared config).
+    pub required_objects: Vec<String>,
+    /// Built-in workflow template (`generic`, `cetus`, `suilend`, `scallop`).
+    pub template: Option<String>,
+    /// Module-name substrings that identify the protocol's packages.
+    pub keywords: Vec<String>,
+    /// Where the definition came from: `builtin` or a file path.
+    #[serde(skip_deserializing)]
+    pub source: String,
+}
+
+impl ProtocolAdapterDefinition {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            source: "builtin".to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    pub fn with_keywords(mut self, keywords: &[&str]) -> Self {
+        self.keywords = keywords.iter().map(|kw| kw.to_string()).collect();
+        self
+    }
+
+    /// Parsed [`Self::template`].
+    pub fn workflow_template(&self) -> Result<Option<BuiltinWorkflowTemplate>> {
+        self.template
+            .as_deref()
+            .map(parse_builtin_workflow_template)
+            .transpose()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if normalize_protocol_name(&self.name).is_empty() {
+            return Err(anyhow!("protocol adapter is missing a name"));
+        }
+        self.workflow_template()
+            .with_context(|| format!("protocol adapter `{}`", self.name))?;
+        for package_id in &self.package_ids {
+            normalize_package_id(package_id)
+                .with_context(|| format!("protocol adapter `{}`", self.name))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AdapterFile {
+    Many {
+        adapters: Vec<ProtocolAdapterDefinition>,
+    },
+    List(Vec<ProtocolAdapterDefinition>),
+    One(ProtocolAdapterDefinition),
+}
+
+/// Adapters keyed by normalized protocol name, in registration order.
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolRegistry {
+    adapters: Vec<ProtocolAdapterDefinition>,
+}
+
+impl ProtocolRegistry {
+    /// Empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Built-in adapters. None ship package defaults, so non-generic protocols still
+    /// need an explicit package ID unless a config file provides one.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(
+            ProtocolAdapterDefinition::new("generic")
+                .with_description("Any package; no protocol-specific defaults")
+                .with_template("generic"),
+        );
+        registry.register(
+            ProtocolAdapterDefinition::new("deepbook")
+                .with_description("DeepBook order book and margin")
+                .with_keywords(&["deepbook", "margin_manager", "pool_proxy"]),
+        );
+        registry.register(
+            ProtocolAdapterDefinition::new("cetus")
+                .with_description("Cetus CLMM/DLMM pools")
+                .with_template("cetus")
+                .with_keywords(&["cetus", "clmm", "dlmm", "pool_script", "position_manager"]),
+        );
+        registry.register(
+            ProtocolAdapterDefinition::new("suilend")
+                .with_description("Suilend lending markets")
+                .with_template("suilend")
+                .with_keywords(&["suilend", "lending", "reserve", "obligation", "liquidation"]),
+        );
+        registry.register(
+            ProtocolAdapterDefinition::new("scallop")
+                .with_description("Scallop lending markets")
+                .with_template("scallop")
+                .with_keywords(&["scallop", "scoin", "spool", "collateral", "market"]),
+        );
+        registry
+    }
+
+    /// Built-in adapters plus every file in [`protocol_config_dir`] (if it exists).
+    pub fn load_default() -> Result<Self> {
+        let mut registry = Self::builtin();
+        if let Some(dir) = protocol_config_dir() {
+            if dir.is_dir() {
+                registry.load_dir(&dir)?;
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Add an adapter, replacing one with the same normalized name.
+    pub fn register(&mut self, adapter: ProtocolAdapterDefinition) {
+        let name = normalize_protocol_name(&adapter.name);
+        match self
+            .adapters
+            .iter_mut()
+            .find(|existing| normalize_protocol_name(&existing.name) == name)
+        {
+            Some(existing) => *existing = adapter,
+            None => self.adapters.push(adapter),
+        }
+    }
+
+    /// Load the adapters in one TOML or JSON file. Returns how many were registered.
+    pub fn load_file(&mut self, path: &Path) -> Result<usize> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("failed to read protocol adapter {}", path.display()))?;
+        let parsed: AdapterFile = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&raw)
+                .with_context(|| format!("invalid protocol adapter TOML {}", path.display()))?,
+            Some("json") => serde_json::from_str(&raw)
+                .with_context(|| format!("invalid protocol adapter JSON {}", path.display()))?,
+            _ => {
+                return Err(anyhow!(
+                    "unsupported protocol adapter file {} (expected .toml or .json)",
+                    path.display()
+                ))
+            }
+        };
+        let adapters = match parsed {
+            AdapterFile::Many { adapters } | AdapterFile::List(adapters) => adapters,
+            AdapterFile::One(adapter) => vec![adapter],
+        };
+        let count = adapters.len();
+        for mut adapter in adapters {
+            adapter
+                .validate()
+                .with_context(|| format!("in {}", path.display()))?;
+            adapter.source = path.display().to_string();
+            self.register(adapter);
+        }
+        Ok(count)
+    }
+
+    /// Load every `*.toml` / `*.json` file in `dir`, in file-name order.
+    pub fn load_dir(&mut self, dir: &Path) -> Result<usize> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+            .with_context(|| format!("failed to read protocol adapter dir {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                matches!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("toml") | Some("json")
+                )
+            })
+            .collect();
+        paths.sort();
+        let mut count = 0;
+        for path in paths {
+            count += self.load_file(&path)?;
+        }
+        Ok(count)
+    }
+
+    /// All adapters, in registration order.
+    pub fn adapters(&self) -> &[ProtocolAdapterDefinition] {
+        &self.adapters
+    }
+
+    /// Adapter for `protocol` (case-insensitive).
+    pub fn get(&self, protocol: &str) -> Option<&ProtocolAdapterDefinition> {
+        let name = normalize_protocol_name(protocol);
+        self.adapters
+            .iter()
+            .find(|adapter| normalize_protocol_name(&adapter.name) == name)
+    }
+
+    /// Adapter for `protocol`, or an error listing the known ones.
+    pub fn resolve(&self, protocol: &str) -> Result<&ProtocolAdapterDefinition> {
+        self.get(protocol).ok_or_else(|| {
+            anyhow!(
+                "invalid protocol '{}': expected one of {}",
+                normalize_protocol_name(protocol),
+                self.adapters
+                    .iter()
+                    .map(|adapter| normalize_protocol_name(&adapter.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+    }
+
+    /// Package ID for prepare/run flows: the explicit one, else the adapter's first
+    /// default package.
+    pub fn resolve_package_id(&self, protocol: &str, package_id: Option<&str>) -> Result<String> {
+        let adapter = self.resolve(protocol)?;
+        match package_id.or_else(|| adapter.package_ids.first().map(String::as_str)) {
+            Some(raw) => normalize_package_id(raw),
+            None => Err(requires_package_id_error(adapter)),
+        }
+    }
+
+    /// Package filter for discovery flows. `generic` allows none; other protocols fall
+    /// back to their default package.
+    pub fn resolve_discovery_package_filter(
+        &self,
+        protocol: &str,
+        package_id: Option<&str>,
+    ) -> Result<Option<String>> {
+        let adapter = self.resolve(protocol)?;
+        if package_id.is_none() && normalize_protocol_name(&adapter.name) == "generic" {
+            return Ok(None);
+        }
+        self.resolve_package_id(protocol, package_id).map(Some)
+    }
+
+    /// Module-name keywords per workflow template, merged across adapters.
+    pub fn template_keywords(&self) -> Vec<(BuiltinWorkflowTemplate, Vec<String>)> {
+        let mut grouped: Vec<(BuiltinWorkflowTemplate, Vec<String>)> = Vec::new();
+        for adapter in &self.adapters {
+            let Ok(Some(template)) = adapter.workflow_template() else {
+                continue;
+            };
+            if adapter.keywords.is_empty() {
+                continue;
+            }
+            let keywords = adapter.keywords.iter().map(|kw| kw.to_ascii_lowercase());
+            match grouped
+                .iter_mut()
+                .find(|(existing, _)| *existing == template)
+            {
+                Some((_, existing)) => existing.extend(keywords),
+                None => grouped.push((template, keywords.collect())),
+            }
+        }
+        grouped
+    }
+}
+
+fn requires_package_id_error(adapter: &ProtocolAdapterDefinition) -> anyhow::Error {
+    anyhow!(
+        "protocol `{}` requires --package-id (no default package configured)",
+        normalize_protocol_name(&adapter.name)
+    )
+}
+
+/// Directory scanned by [`ProtocolRegistry::load_default`].
+///
+/// Defaults to `~/.sui-sandbox/protocols` unless `SUI_SANDBOX_PROTOCOLS_DIR` is set.
+pub fn protocol_config_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(PROTOCOL_CONFIG_DIR_ENV) {
+        if !dir.trim().is_empty() {
+            return Some(PathBuf::from(dir));
+        }
+    }
+    dirs::home_dir().map(|home| home.join(".sui-sandbox").join("protocols"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TURBOS: &str = "0x91bfbc386a41afcfd9b2533058d7e915a1d3829089cc268ff4333d54d6339ca1";
+
+    #[test]
+    fn loads_adapters_from_toml_and_json() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        fs::write(
+            dir.path().join("turbos.toml"),
+            format!(
+                "name = \"Turbos\"\npackage_ids = [\"{TURBOS}\"]\ntemplate = \"cetus\"\nkeywords = [\"turbos\"]\n"
+            ),
+        )
+        .expect("write toml");
+        fs::write(
+            dir.path().join("more.json"),
+            r#"{"adapters": [{"name": "deepbook", "package_ids": ["0xdee9"]}, {"name": "navi"}]}"#,
+        )
+        .expect("write json");
+        fs::write(dir.path().join("README.md"), "ignored").expect("write readme");
+
+        let mut registry = ProtocolRegistry::builtin();
+        assert_eq!(registry.load_dir(dir.path()).expect("load"), 3);
+
+        let turbos = registry.get("turbos").expect("turbos adapter");
+        assert!(turbos.source.ends_with("turbos.toml"));
+        assert_eq!(
+            registry
+                .resolve_package_id("TURBOS", None)
+                .expect("default"),
+            TURBOS
+        );
+        // Files override built-ins of the same name.
+        assert!(registry
+            .resolve_package_id("deepbook", None)
+            .expect("deepbook default")
+            .ends_with("dee9"));
+        assert!(registry
+            .template_keywords()
+            .iter()
+            .any(
+                |(template, keywords)| *template == BuiltinWorkflowTemplate::Cetus
+                    && keywords.contains(&"turbos".to_string())
+            ));
+
+        let err = registry.resolve_package_id("navi", None).unwrap_err();
+        assert!(err.to_string().contains("requires --package-id"));
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("bad.toml");
+        fs::write(&path, "name = \"bad\"\ntemplate = \"uniswap\"\n").expect("write");
+        let err = ProtocolRegistry::new().load_file(&path).unwrap_err();
+        assert!(format!("{err:#}").contains("invalid template"));
+    }
+
+    #[test]
+    fn builtin_discovery_filters() {
+        let registry = ProtocolRegistry::builtin();
+        assert_eq!(
+            registry
+                .resolve_discovery_package_filter("generic", None)
+                .expect("generic"),
+            None
+        );
+        assert!(registry
+            .resolve_discovery_package_filter("cetus", None)
+            .is_err());
+        assert!(registry.resolve("uniswap").is_err());
+    }
+}
@@ -48,6 +48,8 @@ use std::collections::{HashMap, HashSet};
 // Re-export ObjectID from sui_sandbox_types for backward compatibility
 pub use sui_sandbox_types::ObjectID;
 
+use crate::call_trace::CommandCallTrace;
+use crate::gas_profile::{CommandGas, GasProfile, TOP_FUNCTIONS};
 use crate::natives::EmittedEvent;
 use crate::vm::{gas_costs, VMHarness};
 use crate::well_known;
@@ -1701,6 +1703,22 @@ pub struct TransactionEffects {
     /// Gas used (always 0 in our unmetered execution)
     pub gas_used: u64,
 
+    /// Computation/storage/rebate breakdown of `gas_used`, in the shape of on-chain
+    /// gas cost summaries. `None` unless accurate gas metering is enabled.
+    pub gas_summary: Option<sui_sandbox_types::GasSummary>,
+
+    /// Per-owner, per-coin-type balance deltas, in the shape of on-chain balance
+    /// changes. Filled by transaction replay; empty for direct PTB execution.
+    pub balance_changes: Vec<sui_sandbox_types::BalanceChange>,
+
+    /// Per-command and per-function gas breakdown. `None` unless
+    /// `SimulationConfig::gas_profile` is enabled.
+    pub gas_profile: Option<GasProfile>,
+
+    /// Move call tree of each command. `None` unless `SimulationConfig::call_trace`
+    /// is enabled.
+    pub call_trace: Option<Vec<CommandCallTrace>>,
+
     /// Whether execution succeeded
     pub success: bool,
 
@@ -1910,6 +1928,16 @@ pub struct PTBExecutor<'a, 'b> {
     /// Accumulated gas used across all commands
     gas_used: u64,
 
+    /// Storage charges included in MoveCall `gas_used` by the metered VM.
+    /// Subtracted from `gas_used` to recover computation for `gas_summary`.
+    metered_storage_gas: u64,
+
+    /// Gas charged per command, when the harness profiles gas.
+    command_gas: Vec<CommandGas>,
+
+    /// Call trees per command, when the harness records call traces.
+    command_traces: Vec<CommandCallTrace>,
+
     /// Optional gas budget limit. If set, execution fails when gas_used exceeds this.
     /// If None, no limit is enforced (unlimited gas).
     gas_budget: Option<u64>,
@@ -1976,6 +2004,10 @@ pub struct PTBExecutor<'a, 'b> {
     /// The gas coin can only be used with TransferObjects command.
     /// Any other usage will fail with InvalidGasCoinUsage error.
     gas_coin_id: Option<ObjectID>,
+
+    /// Event sequence number at the start of each executed command, with the
+    /// (package, module) of its Move call. Used to attribute emitted events.
+    event_origins: Vec<(u64, Option<(AccountAddress, String)>)>,
 }
 
 impl<'a, 'b> PTBExecutor<'a, 'b> {
@@ -2002,6 +2034,9 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
             pending_receives: HashMap::new(),
             sender,
             gas_used: 0,
+            metered_storage_gas: 0,
+            command_gas: Vec::new(),
+            command_traces: Vec::new(),
             consumed_objects: HashSet::new(),
             transferable_objects: HashSet::new(),
             gas_budget: None,
@@ -2020,6 +2055,7 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
             input_object_digests: HashMap::new(),
             lamport_timestamp: 1,
             gas_coin_id: None,
+            event_origins: Vec::new(),
         }
     }
 
@@ -2066,6 +2102,9 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
             pending_receives: HashMap::new(),
             sender,
             gas_used: 0,
+            metered_storage_gas: 0,
+            command_gas: Vec::new(),
+            command_traces: Vec::new(),
             consumed_objects: HashSet::new(),
             transferable_objects: HashSet::new(),
             gas_budget: None,
@@ -2084,6 +2123,7 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
             input_object_digests: HashMap::new(),
             lamport_timestamp: 1,
             gas_coin_id: None,
+            event_origins: Vec::new(),
         }
     }
 
@@ -3037,6 +3077,7 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
 
                 // Accumulate gas
                 self.gas_used += output.gas_used;
+                self.metered_storage_gas += output.gas_used.saturating_sub(output.computation_gas);
 
                 // Sync objects created by native transfer/share/freeze calls
                 self.sync_created_objects_from_vm();
@@ -3093,6 +3134,8 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
 
                             // Accumulate gas
                             self.gas_used += output.gas_used;
+                            self.metered_storage_gas +=
+                                output.gas_used.saturating_sub(output.computation_gas);
 
                             // Sync objects created by native transfer/share/freeze calls
                             self.sync_created_objects_from_vm();
@@ -4038,7 +4081,9 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
 
     /// Execute a MakeMoveVec command.
     ///
-    /// Creates a vector from the given elements.
+    /// Creates a vector from the given elements. As on Sui, the element type comes from the
+    /// explicit annotation when present (required for an empty vector) and otherwise from
+    /// the first element.
     fn execute_make_move_vec(
         &mut self,
         type_tag: Option<TypeTag>,
@@ -4106,7 +4151,14 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
         self.gas_used += gas_costs::NATIVE_CALL + (vec_bytes.len() as u64) * gas_costs::OUTPUT_BYTE;
 
         // Create a vector TypeTag if we have the element type
-        let vec_type = type_tag.map(|t| TypeTag::Vector(Box::new(t)));
+        let element_type = type_tag.or_else(|| {
+            let first = elements.first()?;
+            self.get_type_from_arg(first).or_else(|| {
+                self.get_object_id_and_type_from_arg(first)
+                    .and_then(|(_, t)| t)
+            })
+        });
+        let vec_type = element_type.map(|t| TypeTag::Vector(Box::new(t)));
 
         Ok(CommandResult::Values(vec![TypedValue::new(
             vec_bytes, vec_type,
@@ -4514,19 +4566,35 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
             .map(|id| id.name().to_string())
             .unwrap_or_else(|| module.to_string());
 
-        let abort_meaning =
-            crate::error_context::get_abort_code_context(abort_info.abort_code, &resolved_module);
+        // Prefer the clever-error constant decoded from bytecode, fall back to heuristic
+        let decoded = abort_info.decoded.as_ref();
+        let abort_meaning = decoded.and_then(|d| d.message.clone()).or_else(|| {
+            crate::error_context::get_abort_code_context(abort_info.abort_code, &resolved_module)
+        });
 
         Some(TransactionAbortInfo {
             module: resolved_module,
             function: resolved_function,
             abort_code: abort_info.abort_code,
-            constant_name: None, // Could be resolved from bytecode in the future
+            constant_name: decoded.and_then(|d| d.constant_name.clone()),
             abort_meaning,
+            source_line: decoded.and_then(|d| d.source_line),
             involved_objects: Vec::new(),
         })
     }
 
+    /// Decoded abort of the last failed MoveCall, if bytecode lookup recovered
+    /// a constant name or source line.
+    fn decoded_abort(&self) -> Option<&crate::abort_decoder::DecodedAbort> {
+        self.last_structured_error
+            .as_ref()?
+            .abort_info
+            .as_ref()?
+            .decoded
+            .as_ref()
+            .filter(|decoded| decoded.is_resolved())
+    }
+
     /// Parse abort information from an error message (fallback method).
     ///
     /// This is the legacy method that uses string parsing. It's kept as a fallback
@@ -4592,8 +4660,9 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
                 module: module.to_string(),
                 function: function.to_string(),
                 abort_code: code,
-                constant_name: None, // Not available from local execution - only from gRPC CleverError
+                constant_name: None, // Not in the error string - only from bytecode or gRPC CleverError
                 abort_meaning,
+                source_line: None,
                 involved_objects: Vec::new(),
             }
         })
@@ -4858,6 +4927,18 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
 
     /// Execute all commands in the PTB.
     pub fn execute(&mut self, commands: Vec<Command>) -> Result<TransactionEffects> {
+        self.command_gas.clear();
+        self.command_traces.clear();
+        let mut effects = self.run_commands(commands)?;
+        effects.gas_profile = self.build_gas_profile(&effects);
+        effects.call_trace = self
+            .vm
+            .call_trace()
+            .map(|_| std::mem::take(&mut self.command_traces));
+        Ok(effects)
+    }
+
+    fn run_commands(&mut self, commands: Vec<Command>) -> Result<TransactionEffects> {
         let start_time = std::time::Instant::now();
         let progress = match std::env::var("SUI_PTB_PROGRESS") {
             Ok(raw) => {
@@ -4905,10 +4986,19 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
         // Clear the VM's execution trace and events before starting
         self.vm.clear_trace();
         self.vm.clear_events();
+        self.vm.clear_gas_profile();
+        self.event_origins.clear();
 
         for (index, cmd) in commands.iter().enumerate() {
             let cmd_description = Self::describe_command(cmd);
             let cmd_type = Self::command_type_name(cmd);
+            let move_call = match cmd {
+                Command::MoveCall {
+                    package, module, ..
+                } => Some((*package, module.to_string())),
+                _ => None,
+            };
+            self.event_origins.push((self.vm.event_count(), move_call));
             if progress {
                 eprintln!(
                     "[ptb] start cmd {}/{}: {}",
@@ -4939,8 +5029,15 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
 
             // Capture timing for this command
             let cmd_start = std::time::Instant::now();
+            let gas_before = (self.gas_used, self.metered_storage_gas);
+            let traced_calls = self.vm.call_trace().map(|log| log.len());
 
-            match self.execute_command(cmd.clone()) {
+            let outcome = self.execute_command(cmd.clone());
+            self.record_command_gas(index, &cmd_type, &cmd_description, gas_before);
+            if let Some(start) = traced_calls {
+                self.record_command_trace(index, &cmd_type, &cmd_description, start);
+            }
+            match outcome {
                 Ok(result) => {
                     let cmd_duration_us = cmd_start.elapsed().as_micros() as u64;
                     let return_count = result.len();
@@ -5015,8 +5112,14 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
                     }
                     self.execution_trace
                         .complete(false, Some(start_time.elapsed().as_millis() as u64));
+                    let error = match self.decoded_abort() {
+                        Some(decoded) if matches!(cmd, Command::MoveCall { .. }) => {
+                            format!("{} [abort: {}]", e, decoded)
+                        }
+                        _ => e.to_string(),
+                    };
                     return Ok(TransactionEffects::failure_at_with_context(
-                        e.to_string(),
+                        error,
                         index,
                         cmd_description,
                         self.results.len(),
@@ -5098,6 +5201,60 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
         Ok(self.compute_effects())
     }
 
+    /// Record the gas charged by one command (only when profiling gas).
+    fn record_command_gas(
+        &mut self,
+        index: usize,
+        cmd_type: &str,
+        description: &str,
+        (gas_before, storage_before): (u64, u64),
+    ) {
+        if self.vm.gas_profile().is_none() {
+            return;
+        }
+        let gas_used = self.gas_used.saturating_sub(gas_before);
+        let storage_gas = self.metered_storage_gas.saturating_sub(storage_before);
+        self.command_gas.push(CommandGas {
+            index,
+            command_type: cmd_type.to_string(),
+            description: description.to_string(),
+            gas_used,
+            computation_gas: gas_used.saturating_sub(storage_gas),
+            storage_gas,
+        });
+    }
+
+    /// Record the call trees of the VM calls made since `start`.
+    fn record_command_trace(
+        &mut self,
+        index: usize,
+        cmd_type: &str,
+        description: &str,
+        start: usize,
+    ) {
+        let Some(log) = self.vm.call_trace() else {
+            return;
+        };
+        self.command_traces.push(CommandCallTrace {
+            index,
+            command_type: cmd_type.to_string(),
+            description: description.to_string(),
+            calls: log.calls_since(start),
+        });
+    }
+
+    fn build_gas_profile(&self, effects: &TransactionEffects) -> Option<GasProfile> {
+        let log = self.vm.gas_profile()?;
+        Some(GasProfile {
+            gas_used: self.gas_used,
+            computation_gas: self.gas_used.saturating_sub(self.metered_storage_gas),
+            storage_gas: self.metered_storage_gas,
+            summary: effects.gas_summary.clone(),
+            commands: self.command_gas.clone(),
+            top_functions: log.top_functions(TOP_FUNCTIONS),
+        })
+    }
+
     /// Get the command type name for tracing.
     fn command_type_name(cmd: &Command) -> String {
         match cmd {
@@ -5197,6 +5354,23 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
         }
     }
 
+    /// Fill in the command and Move call that emitted each event.
+    fn attribute_events(&self, events: &mut [EmittedEvent]) {
+        for event in events {
+            let origin = self
+                .event_origins
+                .partition_point(|(first_sequence, _)| *first_sequence <= event.sequence);
+            let Some(command_index) = origin.checked_sub(1) else {
+                continue;
+            };
+            event.command_index = Some(command_index);
+            if let Some((package, module)) = &self.event_origins[command_index].1 {
+                event.package_id = Some(*package);
+                event.transaction_module = Some(module.clone());
+            }
+        }
+    }
+
     /// Compute the transaction effects after execution.
     fn compute_effects(&self) -> TransactionEffects {
         let mut effects = TransactionEffects::success();
@@ -5308,6 +5482,7 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
 
         // Collect events emitted during execution
         effects.events = self.vm.get_events();
+        self.attribute_events(&mut effects.events);
 
         // Capture return values and type tags from each command
         let mut return_values = Vec::new();
@@ -5358,6 +5533,15 @@ impl<'a, 'b> PTBExecutor<'a, 'b> {
 
         // Set accumulated gas usage
         effects.gas_used = self.gas_used;
+        effects.gas_summary = self
+            .vm
+            .gas_summary_for_computation(self.gas_used.saturating_sub(self.metered_storage_gas))
+            .map(|summary| sui_sandbox_types::GasSummary {
+                computation_cost: summary.computation_cost,
+                storage_cost: summary.storage_cost,
+                storage_rebate: summary.storage_rebate,
+                non_refundable_storage_fee: summary.non_refundable_storage_fee,
+            });
 
         // Compute version information if version tracking is enabled
         if self.track_versions {
@@ -6624,6 +6808,49 @@ mod tests {
         assert_ne!(VersionChangeType::Created, VersionChangeType::Mutated);
     }
 
+    #[test]
+    fn test_make_move_vec_types_from_first_element() {
+        use crate::resolver::LocalModuleResolver;
+        use crate::vm::VMHarness;
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let mut harness = VMHarness::new(&resolver, false).unwrap();
+        let mut executor = PTBExecutor::new(&mut harness);
+
+        let coin_id = AccountAddress::from_hex_literal("0xc0").unwrap();
+        let mut coin_bytes = coin_id.to_vec();
+        coin_bytes.extend_from_slice(&100u64.to_le_bytes());
+        executor.add_input(InputValue::Object(ObjectInput::Owned {
+            id: coin_id,
+            bytes: coin_bytes,
+            type_tag: Some(well_known::types::sui_coin()),
+            version: None,
+        }));
+        executor.add_input(InputValue::Pure(10u64.to_le_bytes().to_vec()));
+        executor.add_input(InputValue::Pure(20u64.to_le_bytes().to_vec()));
+
+        let effects = executor
+            .execute(vec![
+                Command::SplitCoins {
+                    coin: Argument::Input(0),
+                    amounts: vec![Argument::Input(1), Argument::Input(2)],
+                },
+                Command::MakeMoveVec {
+                    type_tag: None,
+                    elements: vec![Argument::NestedResult(0, 0), Argument::NestedResult(0, 1)],
+                },
+            ])
+            .unwrap();
+
+        assert!(effects.success, "{:?}", effects.error);
+        assert_eq!(
+            effects.return_type_tags[1],
+            vec![Some(TypeTag::Vector(Box::new(
+                well_known::types::sui_coin()
+            )))]
+        );
+    }
+
     #[test]
     fn test_version_tracking_input_registration() {
         // Test that add_input registers versions when tracking is enabled
@@ -47,6 +47,9 @@ pub struct Args {
     pub out_dir: PathBuf,
     pub grpc_endpoint: Option<String>,
     pub stream_timeout_secs: u64,
+    /// Seed for ranking tie-breaks and fallback candidate ordering.
+    /// `None` keeps plain lexicographic tie-breaks.
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -116,6 +119,7 @@ struct UniverseSummary {
     top_tags: Vec<CountRow>,
     top_packages: Vec<CountRow>,
     top_functions: Vec<FunctionCountRow>,
+    seed: Option<u64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -260,6 +264,9 @@ fn run(args: Args) -> Result<()> {
     println!("latest checkpoints: {}", args.latest);
     println!("top packages: {}", args.top_packages);
     println!("max PTBs to execute: {}", args.max_ptbs);
+    if let Some(seed) = args.seed {
+        println!("seed: {}", seed);
+    }
     if args.source == CheckpointSource::GrpcStream {
         println!("stream timeout (secs): {}", args.stream_timeout_secs);
         if let Some(endpoint) = args.grpc_endpoint.as_deref() {
@@ -291,10 +298,10 @@ fn run(args: Args) -> Result<()> {
             )
         }
     };
-    let summary = universe_summary(&universe, checkpoints_loaded);
+    let summary = universe_summary(&universe, checkpoints_loaded, args.seed);
     write_json(args.out_dir.join("universe_summary.json"), &summary)?;
 
-    let top_packages = top_package_addrs(&universe.package_counts, args.top_packages);
+    let top_packages = top_package_addrs(&universe.package_counts, args.top_packages, args.seed);
 
     println!(
         "downloading {} top package(s) + dependency closure...",
@@ -336,7 +343,8 @@ fn run(args: Args) -> Result<()> {
         .map(canonical_address)
         .collect::<BTreeSet<_>>();
 
-    let observed = select_observed_functions(&universe.function_counts, &top_package_hex);
+    let observed =
+        select_observed_functions(&universe.function_counts, &top_package_hex, args.seed);
     let planning_limit = args.max_ptbs.saturating_mul(5).max(args.max_ptbs);
 
     println!(
@@ -376,6 +384,7 @@ fn run(args: Args) -> Result<()> {
             &top_packages,
             &seen_functions,
             fallback_limit,
+            args.seed,
         );
 
         for plan in fallback_plans {
@@ -561,6 +570,7 @@ fn parse_args() -> Result<Args> {
     let mut out_dir = PathBuf::from("examples/out/walrus_ptb_universe");
     let mut grpc_endpoint = None;
     let mut stream_timeout_secs = DEFAULT_STREAM_TIMEOUT_SECS;
+    let mut seed = None;
 
     let mut iter = std::env::args().skip(1);
     while let Some(arg) = iter.next() {
@@ -615,6 +625,15 @@ fn parse_args() -> Result<Args> {
                     .parse::<u64>()
                     .with_context(|| format!("invalid --stream-timeout-secs value: {v}"))?;
             }
+            "--seed" => {
+                let v = iter
+                    .next()
+                    .ok_or_else(|| anyhow!("--seed requires a value"))?;
+                seed = Some(
+                    v.parse::<u64>()
+                        .with_context(|| format!("invalid --seed value: {v}"))?,
+                );
+            }
             "--help" | "-h" => {
                 print_usage();
                 std::process::exit(0);
@@ -646,6 +665,7 @@ fn parse_args() -> Result<Args> {
         out_dir,
         grpc_endpoint,
         stream_timeout_secs,
+        seed,
     })
 }
 
@@ -653,7 +673,7 @@ fn print_usage() {
     println!(
         "Checkpoint-source PTB universe example\n\n\
 Usage:\n  cargo run --example walrus_ptb_universe -- [OPTIONS]\n\n\
-Options:\n  --source SRC         Checkpoint source: walrus | grpc-stream (default: walrus)\n  --latest N           Number of checkpoints to analyze/collect (default: {DEFAULT_LATEST})\n  --top-packages N     Number of top packages to fetch (default: {DEFAULT_TOP_PACKAGES})\n  --max-ptbs N         Max generated PTBs to execute (default: {DEFAULT_MAX_PTBS})\n  --out-dir PATH       Output directory (default: examples/out/walrus_ptb_universe)\n  --grpc-endpoint URL  gRPC endpoint for --source grpc-stream (default: env/default resolver)\n  --stream-timeout-secs N  Max seconds to wait for streaming checkpoints (default: {DEFAULT_STREAM_TIMEOUT_SECS})\n  --help               Show this help\n"
+Options:\n  --source SRC         Checkpoint source: walrus | grpc-stream (default: walrus)\n  --latest N           Number of checkpoints to analyze/collect (default: {DEFAULT_LATEST})\n  --top-packages N     Number of top packages to fetch (default: {DEFAULT_TOP_PACKAGES})\n  --max-ptbs N         Max generated PTBs to execute (default: {DEFAULT_MAX_PTBS})\n  --out-dir PATH       Output directory (default: examples/out/walrus_ptb_universe)\n  --grpc-endpoint URL  gRPC endpoint for --source grpc-stream (default: env/default resolver)\n  --stream-timeout-secs N  Max seconds to wait for streaming checkpoints (default: {DEFAULT_STREAM_TIMEOUT_SECS})\n  --seed N             Seed for deterministic ranking tie-breaks (default: lexicographic)\n  --help               Show this help\n"
     );
 }
 
@@ -1021,7 +1041,11 @@ fn classify_walrus_checkpoint_tx(
     })
 }
 
-fn universe_summary(stats: &UniverseStats, checkpoints_loaded: usize) -> UniverseSummary {
+fn universe_summary(
+    stats: &UniverseStats,
+    checkpoints_loaded: usize,
+    seed: Option<u64>,
+) -> UniverseSummary {
     let top_tags = top_count_rows(&stats.tag_counts, 20);
     let top_packages = top_count_rows(&stats.package_counts, 25);
 
@@ -1053,6 +1077,7 @@ fn universe_summary(stats: &UniverseStats, checkpoints_loaded: usize) -> Univers
         top_tags,
         top_packages,
         top_functions,
+        seed,
     }
 }
 
@@ -1069,9 +1094,41 @@ fn top_count_rows(map: &BTreeMap<String, usize>, limit: usize) -> Vec<CountRow>
     rows
 }
 
-fn top_package_addrs(counts: &BTreeMap<String, usize>, limit: usize) -> Vec<AccountAddress> {
+/// Stable seeded sort key for breaking count ties.
+///
+/// FNV-1a over `key` mixed with the seed through splitmix64, so orderings are
+/// reproducible across runs and platforms. Without a seed every key maps to 0
+/// and callers fall through to lexicographic order.
+fn seeded_tiebreak(seed: Option<u64>, key: &str) -> u64 {
+    let Some(seed) = seed else {
+        return 0;
+    };
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in key.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    let mut z = hash ^ seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn function_key_label(key: &FunctionKey) -> String {
+    format!("{}::{}::{}", key.package, key.module, key.function)
+}
+
+fn top_package_addrs(
+    counts: &BTreeMap<String, usize>,
+    limit: usize,
+    seed: Option<u64>,
+) -> Vec<AccountAddress> {
     let mut rows: Vec<(String, usize)> = counts.iter().map(|(k, v)| (k.clone(), *v)).collect();
-    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    rows.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| seeded_tiebreak(seed, &a.0).cmp(&seeded_tiebreak(seed, &b.0)))
+            .then_with(|| a.0.cmp(&b.0))
+    });
 
     rows.into_iter()
         .filter_map(|(pkg, _)| AccountAddress::from_hex_literal(&pkg).ok())
@@ -1215,6 +1272,7 @@ fn fetch_dependency_closure(
 fn select_observed_functions(
     function_counts: &BTreeMap<FunctionKey, usize>,
     allowed_packages: &BTreeSet<String>,
+    seed: Option<u64>,
 ) -> Vec<ObservedFunction> {
     let mut rows: Vec<ObservedFunction> = function_counts
         .iter()
@@ -1228,6 +1286,10 @@ fn select_observed_functions(
     rows.sort_by(|a, b| {
         b.observed_calls
             .cmp(&a.observed_calls)
+            .then_with(|| {
+                seeded_tiebreak(seed, &function_key_label(&a.key))
+                    .cmp(&seeded_tiebreak(seed, &function_key_label(&b.key)))
+            })
             .then_with(|| a.key.cmp(&b.key))
     });
 
@@ -1239,19 +1301,23 @@ fn discover_callable_fallback_plans(
     packages: &[AccountAddress],
     seen: &BTreeSet<FunctionKey>,
     limit: usize,
+    seed: Option<u64>,
 ) -> Vec<FunctionPlan> {
     let mut out = Vec::new();
     let mut seen_local = seen.clone();
 
     for package in packages {
         let package_hex = canonical_address(package);
-        let module_names = {
+        let mut module_names = {
             let resolver = env.resolver_mut();
             resolver.get_package_modules(package)
         };
+        if seed.is_some() {
+            module_names.sort_by_key(|name| (seeded_tiebreak(seed, name), name.clone()));
+        }
 
         for module_name in module_names {
-            let function_names = {
+            let mut function_names = {
                 let resolver = env.resolver_mut();
                 let Some(module) = resolver.get_module_by_addr_name(package, &module_name) else {
                     continue;
@@ -1266,6 +1332,12 @@ fn discover_callable_fallback_plans(
                     })
                     .collect::<Vec<String>>()
             };
+            if seed.is_some() {
+                function_names.sort_by_key(|name| {
+                    let label = format!("{}::{}::{}", package_hex, module_name, name);
+                    (seeded_tiebreak(seed, &label), name.clone())
+                });
+            }
 
             for function_name in function_names {
                 let key = FunctionKey {
@@ -1942,3 +2014,42 @@ Runtime: `{source_line}`\n\n\
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tied_counts() -> BTreeMap<String, usize> {
+        (1..=6u8)
+            .map(|i| (format!("0x{:064x}", i + 0x10), 5usize))
+            .collect()
+    }
+
+    #[test]
+    fn test_unseeded_ranking_is_lexicographic() {
+        let ranked = top_package_addrs(&tied_counts(), 6, None);
+        let mut sorted = ranked.clone();
+        sorted.sort_by_key(canonical_address);
+        assert_eq!(ranked, sorted);
+    }
+
+    #[test]
+    fn test_seeded_ranking_is_reproducible_and_seed_dependent() {
+        let counts = tied_counts();
+        let a = top_package_addrs(&counts, 6, Some(7));
+        assert_eq!(a, top_package_addrs(&counts, 6, Some(7)));
+        let differs = (0..16u64).any(|seed| top_package_addrs(&counts, 6, Some(seed)) != a);
+        assert!(differs, "some seed should permute tied packages");
+    }
+
+    #[test]
+    fn test_seed_does_not_override_counts() {
+        let mut counts = tied_counts();
+        let winner = format!("0x{:064x}", 0x99);
+        counts.insert(winner.clone(), 100);
+        for seed in 0..8u64 {
+            let ranked = top_package_addrs(&counts, 1, Some(seed));
+            assert_eq!(canonical_address(&ranked[0]), winner);
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use move_core_types::account_address::AccountAddress;
 use serde::{Deserialize, Serialize};
-use sui_sandbox_types::{PtbCommand, TransactionInput};
+use sui_sandbox_types::{
+    EffectsComparison, FetchedTransaction, GasSummary, PtbArgument, PtbCommand,
+    TransactionEffectsSummary, TransactionInput, TransactionStatus,
+};
 use sui_state_fetcher::ReplayState;
+use sui_transport::TransportError;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
 pub struct ReplayDiagnostics {
@@ -175,6 +179,10 @@ pub fn build_replay_analysis_summary(
             "receiving": receiving,
         },
         "command_summaries": command_summaries,
+        "object_type_histogram": object_type_histogram(replay_state)
+            .into_iter()
+            .map(|(type_tag, count)| serde_json::json!({"type_tag": type_tag, "count": count}))
+            .collect::<Vec<_>>(),
         "hydration": {
             "source": source,
             "allow_fallback": allow_fallback,
@@ -199,6 +207,218 @@ pub fn build_replay_analysis_summary(
     result
 }
 
+/// Count of hydrated objects per type tag, most frequent first (ties by type tag).
+/// Objects without a type tag are counted under `"unknown"`.
+pub fn object_type_histogram(replay_state: &ReplayState) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for object in replay_state.objects.values() {
+        *counts
+            .entry(object.type_tag.as_deref().unwrap_or("unknown"))
+            .or_default() += 1;
+    }
+    let mut histogram: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(type_tag, count)| (type_tag.to_string(), count))
+        .collect();
+    histogram.sort_by(|a, b| b.1.cmp(&a.1));
+    histogram
+}
+
+/// Per-input detail rows (kind, object id/version, hydration status) for analysis views.
+pub fn describe_replay_inputs(replay_state: &ReplayState) -> Vec<serde_json::Value> {
+    replay_state
+        .transaction
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let (kind, object_id, version, mutable) = match input {
+                TransactionInput::Pure { bytes } => {
+                    return serde_json::json!({
+                        "index": index,
+                        "kind": "Pure",
+                        "bytes_len": bytes.len(),
+                    });
+                }
+                TransactionInput::Object {
+                    object_id, version, ..
+                } => ("Owned", object_id, Some(*version), None),
+                TransactionInput::SharedObject {
+                    object_id,
+                    initial_shared_version,
+                    mutable,
+                } => (
+                    "Shared",
+                    object_id,
+                    Some(*initial_shared_version),
+                    Some(*mutable),
+                ),
+                TransactionInput::ImmutableObject {
+                    object_id, version, ..
+                } => ("Immutable", object_id, Some(*version), None),
+                TransactionInput::Receiving {
+                    object_id, version, ..
+                } => ("Receiving", object_id, Some(*version), None),
+            };
+            let hydrated = AccountAddress::from_hex_literal(object_id)
+                .ok()
+                .and_then(|address| replay_state.objects.get(&address));
+            serde_json::json!({
+                "index": index,
+                "kind": kind,
+                "object_id": object_id,
+                "version": version,
+                "mutable": mutable,
+                "hydrated": hydrated.is_some(),
+                "hydrated_version": hydrated.map(|obj| obj.version),
+                "type_tag": hydrated.and_then(|obj| obj.type_tag.clone()),
+            })
+        })
+        .collect()
+}
+
+fn argument_label(arg: &PtbArgument) -> String {
+    match arg {
+        PtbArgument::GasCoin => "gas".to_string(),
+        PtbArgument::Input { index } => format!("input:{}", index),
+        PtbArgument::Result { index } => format!("result:{}", index),
+        PtbArgument::NestedResult {
+            index,
+            result_index,
+        } => format!("result:{}.{}", index, result_index),
+    }
+}
+
+fn command_arguments(cmd: &PtbCommand) -> Vec<&PtbArgument> {
+    match cmd {
+        PtbCommand::MoveCall { arguments, .. } => arguments.iter().collect(),
+        PtbCommand::SplitCoins { coin, amounts } => std::iter::once(coin).chain(amounts).collect(),
+        PtbCommand::MergeCoins {
+            destination,
+            sources,
+        } => std::iter::once(destination).chain(sources).collect(),
+        PtbCommand::TransferObjects { objects, address } => {
+            objects.iter().chain(std::iter::once(address)).collect()
+        }
+        PtbCommand::MakeMoveVec { elements, .. } => elements.iter().collect(),
+        PtbCommand::Publish { .. } => Vec::new(),
+        PtbCommand::Upgrade { ticket, .. } => vec![ticket],
+    }
+}
+
+/// Per-command detail rows (kind, target, type args, argument references).
+pub fn describe_replay_commands(replay_state: &ReplayState) -> Vec<serde_json::Value> {
+    replay_state
+        .transaction
+        .commands
+        .iter()
+        .enumerate()
+        .map(|(index, cmd)| {
+            let (kind, target, type_args) = match cmd {
+                PtbCommand::MoveCall {
+                    package,
+                    module,
+                    function,
+                    type_arguments,
+                    ..
+                } => (
+                    "MoveCall",
+                    Some(format!("{}::{}::{}", package, module, function)),
+                    type_arguments.clone(),
+                ),
+                PtbCommand::SplitCoins { .. } => ("SplitCoins", None, Vec::new()),
+                PtbCommand::MergeCoins { .. } => ("MergeCoins", None, Vec::new()),
+                PtbCommand::TransferObjects { .. } => ("TransferObjects", None, Vec::new()),
+                PtbCommand::MakeMoveVec { type_arg, .. } => {
+                    ("MakeMoveVec", None, type_arg.iter().cloned().collect())
+                }
+                PtbCommand::Publish { .. } => ("Publish", None, Vec::new()),
+                PtbCommand::Upgrade { package, .. } => ("Upgrade", Some(package.clone()), Vec::new()),
+            };
+            serde_json::json!({
+                "index": index,
+                "kind": kind,
+                "target": target,
+                "type_args": type_args,
+                "arguments": command_arguments(cmd).into_iter().map(argument_label).collect::<Vec<_>>(),
+            })
+        })
+        .collect()
+}
+
+/// Add `return_types` to each MoveCall entry of a summary's `command_summaries`.
+///
+/// `resolve(package, module, function)` returns the declared return types; a miss
+/// (e.g. package not available) is reported as `"unknown"` instead of failing.
+pub fn attach_move_call_return_types<F>(
+    summary: &mut serde_json::Value,
+    commands: &[PtbCommand],
+    mut resolve: F,
+) where
+    F: FnMut(&str, &str, &str) -> Option<Vec<String>>,
+{
+    let Some(entries) = summary
+        .get_mut("command_summaries")
+        .and_then(serde_json::Value::as_array_mut)
+    else {
+        return;
+    };
+    for (entry, cmd) in entries.iter_mut().zip(commands) {
+        if let PtbCommand::MoveCall {
+            package,
+            module,
+            function,
+            ..
+        } = cmd
+        {
+            entry["return_types"] = match resolve(package, module, function) {
+                Some(types) => serde_json::json!(types),
+                None => serde_json::json!("unknown"),
+            };
+        }
+    }
+}
+
+/// Build the PTB dataflow graph: which inputs/results feed which commands.
+///
+/// Returns `{"edges": [...], "unused_inputs": [...], "unused_results": [...]}` where
+/// each edge is `{"from": "input:0" | "result:1" | "result:1.0" | "gas", "to": <command>, "arg_index": n}`.
+pub fn build_replay_dataflow(replay_state: &ReplayState) -> serde_json::Value {
+    let commands = &replay_state.transaction.commands;
+    let mut edges = Vec::new();
+    let mut used_inputs = BTreeSet::new();
+    let mut used_results = BTreeSet::new();
+    for (cmd_index, cmd) in commands.iter().enumerate() {
+        for (arg_index, arg) in command_arguments(cmd).into_iter().enumerate() {
+            match arg {
+                PtbArgument::Input { index } => {
+                    used_inputs.insert(*index as usize);
+                }
+                PtbArgument::Result { index } | PtbArgument::NestedResult { index, .. } => {
+                    used_results.insert(*index as usize);
+                }
+                PtbArgument::GasCoin => {}
+            }
+            edges.push(serde_json::json!({
+                "from": argument_label(arg),
+                "to": cmd_index,
+                "arg_index": arg_index,
+            }));
+        }
+    }
+    let unused_inputs = (0..replay_state.transaction.inputs.len())
+        .filter(|i| !used_inputs.contains(i))
+        .collect::<Vec<_>>();
+    let unused_results = (0..commands.len())
+        .filter(|i| !used_results.contains(i))
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "edges": edges,
+        "unused_inputs": unused_inputs,
+        "unused_results": unused_results,
+    })
+}
+
 pub fn missing_input_objects_from_state(replay_state: &ReplayState) -> Vec<String> {
     let mut missing_inputs = Vec::new();
     for input in &replay_state.transaction.inputs {
@@ -222,6 +442,163 @@ pub fn missing_input_objects_from_state(replay_state: &ReplayState) -> Vec<Strin
     missing_inputs
 }
 
+/// Missing input objects split by an on-chain existence probe at the version the
+/// transaction referenced (initial shared version for shared objects).
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct MissingInputExistence {
+    /// Not found at the referenced version: the version or checkpoint is wrong.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub nonexistent: Vec<String>,
+    /// Present on-chain but not hydrated into the replay state.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub unhydrated: Vec<String>,
+    /// Existed at the referenced version, but the probed endpoint has not archived it
+    /// (`TransportError::ArchiveVersionGap` as JSON, with `remediation`).
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub archive_gaps: Vec<serde_json::Value>,
+}
+
+impl MissingInputExistence {
+    pub fn is_empty(&self) -> bool {
+        self.nonexistent.is_empty() && self.unhydrated.is_empty() && self.archive_gaps.is_empty()
+    }
+
+    /// Move `nonexistent` inputs whose referenced version is known to have existed
+    /// (see [`input_version_known_to_exist`]) to `archive_gaps`, using the error
+    /// `classify(object_id, version)` reports for them, so a pruned archive is not
+    /// mistaken for a wrong version or checkpoint. Other inputs stay `nonexistent`.
+    pub fn separate_archive_gaps<F>(&mut self, replay_state: &ReplayState, mut classify: F)
+    where
+        F: FnMut(&str, u64) -> Option<TransportError>,
+    {
+        let mut nonexistent = Vec::with_capacity(self.nonexistent.len());
+        for object_id in std::mem::take(&mut self.nonexistent) {
+            let gap = input_object_version(replay_state, &object_id)
+                .filter(|version| input_version_known_to_exist(replay_state, &object_id, *version))
+                .and_then(|version| classify(&object_id, version))
+                .filter(|err| matches!(err, TransportError::ArchiveVersionGap { .. }));
+            match gap {
+                Some(gap) => self.archive_gaps.push(gap.to_json()),
+                None => nonexistent.push(object_id),
+            }
+        }
+        self.nonexistent = nonexistent;
+    }
+
+    pub fn suggestions(&self) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        if !self.nonexistent.is_empty() {
+            suggestions.push(format!(
+                "Input objects do not exist at the referenced version ({}); check the transaction \
+                 digest/checkpoint rather than the hydration source.",
+                self.nonexistent.join(", ")
+            ));
+        }
+        if !self.unhydrated.is_empty() {
+            suggestions.push(format!(
+                "Input objects exist on-chain but were not hydrated ({}); use a source with \
+                 archive access or provide them via state_file.",
+                self.unhydrated.join(", ")
+            ));
+        }
+        if !self.archive_gaps.is_empty() {
+            let ids: Vec<&str> = self
+                .archive_gaps
+                .iter()
+                .filter_map(|gap| gap["object_id"].as_str())
+                .collect();
+            let alternate = self.archive_gaps[0]["remediation"]
+                .as_array()
+                .and_then(|steps| {
+                    steps
+                        .iter()
+                        .find_map(|step| step.get("endpoint").and_then(serde_json::Value::as_str))
+                })
+                .unwrap_or("another archive endpoint");
+            suggestions.push(format!(
+                "Input object versions are not archived at the gRPC endpoint ({}); retry with \
+                 SUI_GRPC_HISTORICAL_ENDPOINT={} or source='walrus' with the transaction's checkpoint.",
+                ids.join(", "),
+                alternate
+            ));
+        }
+        suggestions
+    }
+}
+
+/// Probe each missing input with `exists_at(object_id, version)`.
+///
+/// Probes returning `None` (transport errors) leave the object unclassified.
+pub fn probe_missing_input_existence<F>(
+    replay_state: &ReplayState,
+    missing_input_objects: &[String],
+    mut exists_at: F,
+) -> MissingInputExistence
+where
+    F: FnMut(&str, Option<u64>) -> Option<bool>,
+{
+    let mut existence = MissingInputExistence::default();
+    for object_id in missing_input_objects {
+        let version = input_object_version(replay_state, object_id);
+        match exists_at(object_id, version) {
+            Some(true) => existence.unhydrated.push(object_id.clone()),
+            Some(false) => existence.nonexistent.push(object_id.clone()),
+            None => {}
+        }
+    }
+    existence
+}
+
+/// Whether `object_id` at `version` is known to have existed on-chain: the
+/// transaction's changed refs list it as an input version, or the transaction
+/// carries on-chain effects (so it executed against its input refs) and an input
+/// ref names that version. Object versions have gaps, so nothing else proves it.
+pub fn input_version_known_to_exist(
+    replay_state: &ReplayState,
+    object_id: &str,
+    version: u64,
+) -> bool {
+    let Some(effects) = replay_state.transaction.effects.as_ref() else {
+        return false;
+    };
+    effects.object_changes.iter().any(|change| {
+        same_object_id(&change.object_id, object_id) && change.input_version == Some(version)
+    }) || input_object_version(replay_state, object_id) == Some(version)
+}
+
+fn same_object_id(a: &str, b: &str) -> bool {
+    match (
+        AccountAddress::from_hex_literal(a).ok(),
+        AccountAddress::from_hex_literal(b).ok(),
+    ) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn input_object_version(replay_state: &ReplayState, missing_id: &str) -> Option<u64> {
+    replay_state.transaction.inputs.iter().find_map(|input| {
+        let (id, version) = match input {
+            TransactionInput::Object {
+                object_id, version, ..
+            }
+            | TransactionInput::ImmutableObject {
+                object_id, version, ..
+            }
+            | TransactionInput::Receiving {
+                object_id, version, ..
+            } => (object_id, *version),
+            TransactionInput::SharedObject {
+                object_id,
+                initial_shared_version,
+                ..
+            } => (object_id, *initial_shared_version),
+            TransactionInput::Pure { .. } => return None,
+        };
+        same_object_id(id, missing_id).then_some(version)
+    })
+}
+
 pub fn collect_required_packages(replay_state: &ReplayState) -> BTreeSet<AccountAddress> {
     let mut required_packages: BTreeSet<AccountAddress> = BTreeSet::new();
     for cmd in &replay_state.transaction.commands {
@@ -425,6 +802,18 @@ pub fn classify_replay_output(result: &serde_json::Value) -> ReplayClassificatio
         "execution_error"
     };
 
+    if category == "move_abort" {
+        if let Some(abort) = describe_decoded_abort(result) {
+            suggestions.insert(
+                0,
+                format!(
+                    "Move abort decoded as {}; check the assertion at that location.",
+                    abort
+                ),
+            );
+        }
+    }
+
     if suggestions.is_empty() {
         let hint = match category {
             "missing_input_objects" => {
@@ -474,9 +863,918 @@ pub fn classify_replay_output(result: &serde_json::Value) -> ReplayClassificatio
     }
 }
 
+/// Render `effects.abort` as `module::CONSTANT (line N)` when the abort code was
+/// decoded from bytecode (clever errors); `None` for plain abort codes.
+fn describe_decoded_abort(result: &serde_json::Value) -> Option<String> {
+    let abort = result.get("effects")?.get("abort")?;
+    let module = abort.get("module").and_then(serde_json::Value::as_str)?;
+    let constant = abort
+        .get("constant_name")
+        .and_then(serde_json::Value::as_str);
+    let line = abort.get("source_line").and_then(serde_json::Value::as_u64);
+    let mut rendered = match (constant, line) {
+        (Some(constant), _) => format!("`{}::{}`", module, constant),
+        (None, Some(_)) => format!("`{}` assertion", module),
+        (None, None) => return None,
+    };
+    if let Some(line) = line {
+        rendered.push_str(&format!(" (line {})", line));
+    }
+    Some(rendered)
+}
+
+/// Maximum characters of the error kept by [`summarize_replay_output`].
+pub const REPLAY_SUMMARY_ERROR_CHARS: usize = 240;
+
+/// Compact envelope for bulk replay runs: `digest`, `local_success`, `gas_used`,
+/// `failed_command_index`, a short `error` (first line, at most
+/// [`REPLAY_SUMMARY_ERROR_CHARS`] chars), the classification `category`, and the
+/// `fidelity` level when present.
+pub fn summarize_replay_output(output: &serde_json::Value) -> serde_json::Value {
+    let classification = classify_replay_output(output);
+    let error = classification.local_error.as_deref().map(|err| {
+        let first_line = err.lines().next().unwrap_or_default();
+        if first_line.chars().count() > REPLAY_SUMMARY_ERROR_CHARS {
+            let truncated: String = first_line
+                .chars()
+                .take(REPLAY_SUMMARY_ERROR_CHARS)
+                .collect();
+            format!("{}...", truncated)
+        } else {
+            first_line.to_string()
+        }
+    });
+    serde_json::json!({
+        "digest": output.get("digest").cloned().unwrap_or(serde_json::Value::Null),
+        "local_success": output
+            .get("local_success")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false),
+        "gas_used": output
+            .get("effects")
+            .and_then(|effects| effects.get("gas_used"))
+            .and_then(serde_json::Value::as_u64),
+        "failed_command_index": classification.failed_command_index,
+        "error": error,
+        "category": classification.category,
+        "fidelity": output
+            .get("fidelity")
+            .and_then(|fidelity| fidelity.get("level"))
+            .cloned()
+            .unwrap_or(serde_json::Value::Null),
+    })
+}
+
+/// Real-layout vs stub counts for synthesized input objects.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct SynthesisCounts {
+    /// Synthesized from the type's real struct layout.
+    pub real_layout: usize,
+    /// Best-effort fallback (generics ignored or a UID-only stub).
+    pub stubs: usize,
+}
+
+impl SynthesisCounts {
+    fn record(&mut self, is_stub: bool) {
+        if is_stub {
+            self.stubs += 1;
+        } else {
+            self.real_layout += 1;
+        }
+    }
+}
+
+/// Aggregate synthesis outcomes of a replay, overall and per object type.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct SynthesisSummary {
+    #[serde(flatten)]
+    pub total: SynthesisCounts,
+    pub by_type: BTreeMap<String, SynthesisCounts>,
+}
+
+impl SynthesisSummary {
+    /// Record one synthesized object of `type_str`.
+    pub fn record(&mut self, type_str: &str, is_stub: bool) {
+        self.total.record(is_stub);
+        self.by_type
+            .entry(type_str.to_string())
+            .or_default()
+            .record(is_stub);
+    }
+
+    /// Number of objects synthesized.
+    pub fn synthesized(&self) -> usize {
+        self.total.real_layout + self.total.stubs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_type.is_empty()
+    }
+}
+
+/// How much a replay result can be trusted as a faithful re-execution.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ReplayFidelity {
+    /// `"faithful"` when only real historical data was used, else `"degraded"`.
+    pub level: String,
+    /// Human-readable list of what was approximated.
+    pub approximations: Vec<String>,
+}
+
+/// Fidelity-relevant events recorded by the replay's warning log rather than the output envelope.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplayFidelitySignals {
+    /// Dynamic field values synthesized by self-heal.
+    pub self_healed_values: usize,
+    /// The Clock fell back to the default base timestamp.
+    pub clock_defaulted: bool,
+}
+
+/// Aggregate the synthesis/self-heal/fallback signals of a replay output envelope.
+///
+/// Reads `execution_path.synthetic_inputs` (+ `synthesis.stubs`), `execution_path.fallback_used`
+/// (+ `fallback_reasons`), `execution_path.gas_table`, `object_overrides`, `package_overrides`,
+/// `ownership_hints`, and `package_sources.context_replaced`, plus the logged `signals`.
+pub fn assess_replay_fidelity(
+    output: &serde_json::Value,
+    signals: &ReplayFidelitySignals,
+) -> ReplayFidelity {
+    let mut approximations = Vec::new();
+    let execution_path = output.get("execution_path");
+
+    let synthetic_inputs = execution_path
+        .and_then(|path| path.get("synthetic_inputs"))
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0);
+    if synthetic_inputs > 0 {
+        let stubs = output
+            .get("synthesis")
+            .and_then(|synthesis| synthesis.get("stubs"))
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        if stubs > 0 {
+            approximations.push(format!(
+                "synthesized {} missing input object(s), {} as best-effort stubs",
+                synthetic_inputs, stubs
+            ));
+        } else {
+            approximations.push(format!(
+                "synthesized {} missing input object(s)",
+                synthetic_inputs
+            ));
+        }
+    }
+
+    let fallback_used = execution_path
+        .and_then(|path| path.get("fallback_used"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+    if fallback_used {
+        let reasons =
+            parse_json_string_list(execution_path.and_then(|path| path.get("fallback_reasons")));
+        if reasons.is_empty() {
+            approximations.push("fallback execution path used".to_string());
+        } else {
+            approximations.push(format!(
+                "fallback execution path used ({})",
+                reasons.join(", ")
+            ));
+        }
+    }
+
+    if let Some(epoch) = execution_path
+        .and_then(|path| path.get("epoch_override"))
+        .and_then(serde_json::Value::as_u64)
+    {
+        approximations.push(format!("epoch overridden to {}", epoch));
+    }
+
+    if let Some(table) = execution_path
+        .and_then(|path| path.get("gas_table"))
+        .filter(|table| !table.is_null())
+    {
+        let custom = parse_json_string_list(table.get("custom_tiers")).len()
+            + parse_json_string_list(table.get("custom_natives")).len();
+        let unapplied = table
+            .get("unapplied")
+            .and_then(|unapplied| unapplied.as_object())
+            .map_or(0, |unapplied| unapplied.len());
+        if unapplied == 0 {
+            approximations.push("custom gas cost table applied".to_string());
+        } else if unapplied < custom {
+            approximations.push(format!(
+                "custom gas cost table partially applied ({} entr(ies) not charged)",
+                unapplied
+            ));
+        }
+    }
+
+    if let Some(overrides) = output.get("object_overrides") {
+        let dropped = parse_json_string_list(overrides.get("dropped")).len();
+        let replaced = parse_json_string_list(overrides.get("replaced")).len();
+        if dropped + replaced > 0 {
+            approximations.push(format!(
+                "object overrides applied ({} dropped, {} replaced)",
+                dropped, replaced
+            ));
+        }
+    }
+
+    if let Some(packages) = output
+        .get("package_overrides")
+        .and_then(serde_json::Value::as_array)
+        .filter(|packages| !packages.is_empty())
+    {
+        approximations.push(format!(
+            "package bytecode overridden for {} package(s)",
+            packages.len()
+        ));
+    }
+
+    let ownership_changes = output
+        .get("ownership_hints")
+        .and_then(serde_json::Value::as_array)
+        .map(|hints| {
+            hints
+                .iter()
+                .filter(|hint| {
+                    let contradicted = hint.get("contradicted") == Some(&serde_json::json!(true));
+                    let input = hint.get("input").filter(|input| !input.is_null());
+                    contradicted || input.is_some_and(|input| Some(input) != hint.get("hint"))
+                })
+                .count()
+        })
+        .unwrap_or(0);
+    if ownership_changes > 0 {
+        approximations.push(format!(
+            "object ownership overridden for {} object(s)",
+            ownership_changes
+        ));
+    }
+
+    if let Some(packages) = output
+        .get("package_sources")
+        .and_then(|sources| sources.get("context_replaced"))
+        .and_then(serde_json::Value::as_array)
+        .filter(|packages| !packages.is_empty())
+    {
+        approximations.push(format!(
+            "fetched bytecode replaced from context for {} package(s)",
+            packages.len()
+        ));
+    }
+
+    if let Some(stubbed) = output
+        .get("stubbed_natives")
+        .and_then(serde_json::Value::as_array)
+        .filter(|stubbed| !stubbed.is_empty())
+    {
+        approximations.push(format!(
+            "{} native function(s) stubbed; results after the first stubbed call are not faithful",
+            stubbed.len()
+        ));
+    }
+
+    if signals.self_healed_values > 0 {
+        approximations.push(format!(
+            "self-heal synthesized {} dynamic field value(s)",
+            signals.self_healed_values
+        ));
+    }
+    if signals.clock_defaulted {
+        approximations
+            .push("Clock timestamp defaulted (transaction timestamp unavailable)".to_string());
+    }
+
+    ReplayFidelity {
+        level: if approximations.is_empty() {
+            "faithful"
+        } else {
+            "degraded"
+        }
+        .to_string(),
+        approximations,
+    }
+}
+
+/// How completely the replay VM path handles a PTB command or input kind.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplaySupportLevel {
+    Supported,
+    /// Executes, but some on-chain semantics are approximated.
+    Partial,
+    /// Not executed by replay; results will diverge from on-chain effects.
+    Unsupported,
+}
+
+/// One entry of the replay capability registry.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct ReplayCapability {
+    pub kind: &'static str,
+    pub support: ReplaySupportLevel,
+    pub note: &'static str,
+}
+
+/// PTB command kinds and how the replay VM path handles them.
+///
+/// Must match the command conversion in `tx_replay`; [`command_kind`] is an
+/// exhaustive match, so a new `PtbCommand` variant fails to compile until it is listed.
+pub const REPLAY_COMMAND_CAPABILITIES: &[ReplayCapability] = &[
+    ReplayCapability {
+        kind: "MoveCall",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "SplitCoins",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "MergeCoins",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "TransferObjects",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "MakeMoveVec",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "Publish",
+        support: ReplaySupportLevel::Unsupported,
+        note: "skipped during replay; no package is published and later uses of its result fail",
+    },
+    ReplayCapability {
+        kind: "Upgrade",
+        support: ReplaySupportLevel::Unsupported,
+        note: "skipped during replay; the upgrade ticket is never consumed",
+    },
+];
+
+/// Transaction input kinds and how the replay VM path handles them.
+pub const REPLAY_INPUT_CAPABILITIES: &[ReplayCapability] = &[
+    ReplayCapability {
+        kind: "Pure",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "Owned",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "Shared",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "Immutable",
+        support: ReplaySupportLevel::Supported,
+        note: "",
+    },
+    ReplayCapability {
+        kind: "Receiving",
+        support: ReplaySupportLevel::Partial,
+        note: "the parent object is not known during replay, so receive ownership is not checked",
+    },
+];
+
+/// Registry name of a PTB command's kind.
+pub fn command_kind(cmd: &PtbCommand) -> &'static str {
+    match cmd {
+        PtbCommand::MoveCall { .. } => "MoveCall",
+        PtbCommand::SplitCoins { .. } => "SplitCoins",
+        PtbCommand::MergeCoins { .. } => "MergeCoins",
+        PtbCommand::TransferObjects { .. } => "TransferObjects",
+        PtbCommand::MakeMoveVec { .. } => "MakeMoveVec",
+        PtbCommand::Publish { .. } => "Publish",
+        PtbCommand::Upgrade { .. } => "Upgrade",
+    }
+}
+
+/// Registry name of a transaction input's kind (same names as [`describe_replay_inputs`]).
+pub fn input_kind(input: &TransactionInput) -> &'static str {
+    match input {
+        TransactionInput::Pure { .. } => "Pure",
+        TransactionInput::Object { .. } => "Owned",
+        TransactionInput::SharedObject { .. } => "Shared",
+        TransactionInput::ImmutableObject { .. } => "Immutable",
+        TransactionInput::Receiving { .. } => "Receiving",
+    }
+}
+
+fn lookup_capability(registry: &[ReplayCapability], kind: &str) -> ReplayCapability {
+    registry
+        .iter()
+        .find(|capability| capability.kind == kind)
+        .copied()
+        .unwrap_or(ReplayCapability {
+            kind: "unknown",
+            support: ReplaySupportLevel::Unsupported,
+            note: "missing from the replay capability registry",
+        })
+}
+
+/// Support verdict for one command or input of a transaction.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReplayFeasibilityEntry {
+    pub index: usize,
+    pub kind: &'static str,
+    pub support: ReplaySupportLevel,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub note: &'static str,
+}
+
+/// Whether a transaction can be replayed by the current VM path, checked from
+/// its commands and input kinds alone (nothing is fetched or executed).
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReplayFeasibility {
+    pub digest: String,
+    /// `false` when any command or input kind is unsupported.
+    pub replayable: bool,
+    /// `"supported"`, `"partial"`, or `"unsupported"`: the worst level seen.
+    pub support: ReplaySupportLevel,
+    pub commands: Vec<ReplayFeasibilityEntry>,
+    pub inputs: Vec<ReplayFeasibilityEntry>,
+    /// One line per partial/unsupported entry, e.g. `"command 2 (Publish): ..."`.
+    pub reasons: Vec<String>,
+}
+
+/// Check a transaction's commands and inputs against the capability registry.
+pub fn assess_replay_feasibility(tx: &FetchedTransaction) -> ReplayFeasibility {
+    let entry = |registry, index, kind| {
+        let capability = lookup_capability(registry, kind);
+        ReplayFeasibilityEntry {
+            index,
+            kind,
+            support: capability.support,
+            note: capability.note,
+        }
+    };
+    let commands: Vec<_> = tx
+        .commands
+        .iter()
+        .enumerate()
+        .map(|(index, cmd)| entry(REPLAY_COMMAND_CAPABILITIES, index, command_kind(cmd)))
+        .collect();
+    let inputs: Vec<_> = tx
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| entry(REPLAY_INPUT_CAPABILITIES, index, input_kind(input)))
+        .collect();
+
+    let mut support = ReplaySupportLevel::Supported;
+    let mut reasons = Vec::new();
+    for (label, entries) in [("command", &commands), ("input", &inputs)] {
+        for e in entries
+            .iter()
+            .filter(|e| e.support != ReplaySupportLevel::Supported)
+        {
+            if e.support == ReplaySupportLevel::Unsupported
+                || support == ReplaySupportLevel::Supported
+            {
+                support = e.support;
+            }
+            reasons.push(format!("{} {} ({}): {}", label, e.index, e.kind, e.note));
+        }
+    }
+
+    ReplayFeasibility {
+        digest: tx.digest.0.clone(),
+        replayable: support != ReplaySupportLevel::Unsupported,
+        support,
+        commands,
+        inputs,
+        reasons,
+    }
+}
+
+/// Return values of one command that differ between the A and B runs.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ReturnValueDiff {
+    pub command_index: usize,
+    /// Base64 BCS return values from run A (`None` if the command did not run).
+    pub a: Option<Vec<String>>,
+    /// Base64 BCS return values from run B (`None` if the command did not run).
+    pub b: Option<Vec<String>>,
+}
+
+/// Gas reported by the A and B runs.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct GasDiff {
+    pub a: u64,
+    pub b: u64,
+    /// `b - a`.
+    pub delta: i128,
+}
+
+/// Structured diff of two local replays of the same transaction.
+///
+/// Effects are compared with [`EffectsComparison`], treating run A as the
+/// baseline and run B as the "local" side, so notes read "A=..., B=...".
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayAbDiff {
+    /// True when status, effects, return values and gas all match.
+    pub identical: bool,
+    pub success_a: bool,
+    pub success_b: bool,
+    pub error_a: Option<String>,
+    pub error_b: Option<String>,
+    pub effects: EffectsComparison,
+    pub return_values_match: bool,
+    pub return_value_diffs: Vec<ReturnValueDiff>,
+    pub gas: GasDiff,
+}
+
+fn replay_output_effects_summary(output: &serde_json::Value) -> TransactionEffectsSummary {
+    let effects = output.get("effects");
+    let ids = |key: &str| -> Vec<String> {
+        effects
+            .and_then(|e| e.get(key))
+            .and_then(serde_json::Value::as_array)
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(ToOwned::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let success = output
+        .get("local_success")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false);
+    let status = if success {
+        TransactionStatus::Success
+    } else {
+        TransactionStatus::Failure {
+            error: replay_output_error(output).unwrap_or_default(),
+        }
+    };
+    TransactionEffectsSummary {
+        status,
+        created: ids("created"),
+        mutated: ids("mutated"),
+        deleted: ids("deleted"),
+        wrapped: ids("wrapped"),
+        unwrapped: ids("unwrapped"),
+        gas_used: GasSummary {
+            computation_cost: replay_output_gas(output),
+            ..GasSummary::default()
+        },
+        events_count: effects
+            .and_then(|e| e.get("events_count"))
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0) as usize,
+        shared_object_versions: Default::default(),
+        object_changes: Vec::new(),
+    }
+}
+
+fn replay_output_error(output: &serde_json::Value) -> Option<String> {
+    output
+        .get("local_error")
+        .or_else(|| output.get("effects").and_then(|e| e.get("error")))
+        .and_then(serde_json::Value::as_str)
+        .map(ToOwned::to_owned)
+}
+
+fn replay_output_gas(output: &serde_json::Value) -> u64 {
+    output
+        .get("effects")
+        .and_then(|e| e.get("gas_used"))
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or(0)
+}
+
+fn replay_output_return_values(output: &serde_json::Value) -> Vec<Vec<String>> {
+    output
+        .get("effects")
+        .and_then(|e| e.get("return_value_bytes"))
+        .and_then(serde_json::Value::as_array)
+        .map(|commands| {
+            commands
+                .iter()
+                .map(|values| {
+                    values
+                        .as_array()
+                        .map(|arr| {
+                            arr.iter()
+                                .filter_map(serde_json::Value::as_str)
+                                .map(ToOwned::to_owned)
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Diff two replay outputs (`replay(...)` envelopes) of the same transaction.
+///
+/// Return values are read from `effects.return_value_bytes` (base64 per value),
+/// which the A/B harness requests for both runs.
+pub fn diff_replay_outputs(a: &serde_json::Value, b: &serde_json::Value) -> ReplayAbDiff {
+    let summary_a = replay_output_effects_summary(a);
+    let summary_b = replay_output_effects_summary(b);
+    let success_a = summary_a.status == TransactionStatus::Success;
+    let success_b = summary_b.status == TransactionStatus::Success;
+
+    let mut effects = EffectsComparison::compare(
+        &summary_a,
+        success_b,
+        summary_b.created.len(),
+        summary_b.mutated.len(),
+        summary_b.deleted.len(),
+    );
+    effects.apply_object_id_comparison(&summary_a, &summary_b);
+    for note in &mut effects.notes {
+        *note = note.replace("on-chain=", "A=").replace("local=", "B=");
+    }
+
+    let returns_a = replay_output_return_values(a);
+    let returns_b = replay_output_return_values(b);
+    let return_value_diffs: Vec<ReturnValueDiff> = (0..returns_a.len().max(returns_b.len()))
+        .filter_map(|command_index| {
+            let ra = returns_a.get(command_index);
+            let rb = returns_b.get(command_index);
+            (ra != rb).then(|| ReturnValueDiff {
+                command_index,
+                a: ra.cloned(),
+                b: rb.cloned(),
+            })
+        })
+        .collect();
+
+    let gas_a = replay_output_gas(a);
+    let gas_b = replay_output_gas(b);
+    let gas = GasDiff {
+        a: gas_a,
+        b: gas_b,
+        delta: gas_b as i128 - gas_a as i128,
+    };
+
+    // `compare` tolerates extra mutations for the on-chain gas coin; an A/B diff
+    // between two local runs should not, so require exact ID sets here.
+    let effects_match = effects.status_match
+        && effects.created_ids_match
+        && effects.deleted_ids_match
+        && effects.mutated_ids_missing.is_empty()
+        && effects.mutated_ids_extra.is_empty();
+    let return_values_match = return_value_diffs.is_empty();
+
+    ReplayAbDiff {
+        identical: effects_match && return_values_match && gas.delta == 0,
+        success_a,
+        success_b,
+        error_a: replay_output_error(a),
+        error_b: replay_output_error(b),
+        effects,
+        return_values_match,
+        return_value_diffs,
+        gas,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use sui_sandbox_types::TransactionDigest;
+
+    fn dataflow_state() -> ReplayState {
+        ReplayState {
+            transaction: FetchedTransaction {
+                digest: TransactionDigest::new("dataflow"),
+                sender: AccountAddress::ZERO,
+                gas_budget: 0,
+                gas_price: 0,
+                commands: vec![
+                    PtbCommand::SplitCoins {
+                        coin: PtbArgument::GasCoin,
+                        amounts: vec![PtbArgument::Input { index: 0 }],
+                    },
+                    PtbCommand::TransferObjects {
+                        objects: vec![PtbArgument::NestedResult {
+                            index: 0,
+                            result_index: 0,
+                        }],
+                        address: PtbArgument::Input { index: 1 },
+                    },
+                ],
+                inputs: vec![
+                    TransactionInput::Pure { bytes: vec![1] },
+                    TransactionInput::Pure { bytes: vec![2] },
+                    TransactionInput::Object {
+                        object_id: "0x5".to_string(),
+                        version: 1,
+                        digest: "d".to_string(),
+                    },
+                ],
+                effects: None,
+                timestamp_ms: None,
+                checkpoint: None,
+                gas_payment: vec![],
+            },
+            objects: HashMap::new(),
+            packages: HashMap::new(),
+            protocol_version: 0,
+            epoch: 0,
+            reference_gas_price: None,
+            checkpoint: None,
+        }
+    }
+
+    #[test]
+    fn dataflow_tracks_edges_and_unused_slots() {
+        let flow = build_replay_dataflow(&dataflow_state());
+        let edges = flow["edges"].as_array().unwrap();
+        assert_eq!(edges.len(), 4);
+        assert_eq!(edges[0]["from"], "gas");
+        assert_eq!(edges[2]["from"], "result:0.0");
+        assert_eq!(edges[2]["to"], 1);
+        assert_eq!(flow["unused_inputs"], serde_json::json!([2]));
+        assert_eq!(flow["unused_results"], serde_json::json!([1]));
+    }
+
+    #[test]
+    fn missing_inputs_split_by_existence_probe() {
+        let state = dataflow_state();
+        let missing = missing_input_objects_from_state(&state);
+        assert_eq!(missing, vec!["0x5".to_string()]);
+
+        let mut probed = Vec::new();
+        let existence = probe_missing_input_existence(&state, &missing, |id, version| {
+            probed.push((id.to_string(), version));
+            Some(false)
+        });
+        assert_eq!(probed, vec![("0x5".to_string(), Some(1))]);
+        assert_eq!(existence.nonexistent, vec!["0x5".to_string()]);
+        assert!(existence.unhydrated.is_empty());
+        assert_eq!(existence.suggestions().len(), 1);
+
+        let unknown = probe_missing_input_existence(&state, &missing, |_, _| None);
+        assert!(unknown.is_empty());
+
+        let gap = |id: &str, version: u64| {
+            Some(TransportError::ArchiveVersionGap {
+                object_id: id.to_string(),
+                requested_version: version,
+                endpoint: "https://archive.mainnet.sui.io:443".to_string(),
+            })
+        };
+
+        // Without on-chain effects nothing proves version 1 ever existed.
+        assert!(!input_version_known_to_exist(&state, "0x5", 1));
+        let mut unproven = existence.clone();
+        unproven.separate_archive_gaps(&state, |_, _| panic!("classified an unproven version"));
+        assert_eq!(unproven, existence);
+
+        let mut executed = state.clone();
+        executed.transaction.effects = Some(TransactionEffectsSummary {
+            status: TransactionStatus::Success,
+            created: vec![],
+            mutated: vec![],
+            deleted: vec![],
+            wrapped: vec![],
+            unwrapped: vec![],
+            gas_used: GasSummary::default(),
+            events_count: 0,
+            shared_object_versions: HashMap::new(),
+            object_changes: Vec::new(),
+        });
+        assert!(input_version_known_to_exist(&executed, "0x5", 1));
+        assert!(!input_version_known_to_exist(&executed, "0x5", 2));
+        let mut gapped = existence.clone();
+        gapped.separate_archive_gaps(&executed, gap);
+        assert!(gapped.nonexistent.is_empty());
+        assert_eq!(gapped.archive_gaps[0]["object_id"], "0x5");
+        assert_eq!(gapped.archive_gaps[0]["requested_version"], 1);
+        assert!(gapped.suggestions()[0].contains("not archived"));
+
+        let mut not_found = existence.clone();
+        not_found.separate_archive_gaps(&executed, |id, version| {
+            Some(TransportError::ObjectNotFound {
+                object_id: id.to_string(),
+                version: Some(version),
+            })
+        });
+        assert_eq!(not_found, existence);
+    }
+
+    #[test]
+    fn describe_inputs_reports_hydration() {
+        let rows = describe_replay_inputs(&dataflow_state());
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0]["kind"], "Pure");
+        assert_eq!(rows[2]["kind"], "Owned");
+        assert_eq!(rows[2]["hydrated"], false);
+    }
+
+    #[test]
+    fn object_type_histogram_sorts_by_frequency() {
+        let mut state = dataflow_state();
+        for (id, type_tag) in [
+            (1u8, Some("0x2::dynamic_field::Field<u64, u64>")),
+            (2, Some("0x2::coin::Coin<0x2::sui::SUI>")),
+            (3, Some("0x2::dynamic_field::Field<u64, u64>")),
+            (4, None),
+        ] {
+            let id = AccountAddress::new([id; 32]);
+            state.objects.insert(
+                id,
+                sui_state_fetcher::VersionedObject {
+                    id,
+                    version: 1,
+                    digest: None,
+                    type_tag: type_tag.map(str::to_string),
+                    bcs_bytes: vec![],
+                    is_shared: false,
+                    is_immutable: false,
+                },
+            );
+        }
+        let histogram = object_type_histogram(&state);
+        assert_eq!(
+            histogram,
+            vec![
+                ("0x2::dynamic_field::Field<u64, u64>".to_string(), 2),
+                ("0x2::coin::Coin<0x2::sui::SUI>".to_string(), 1),
+                ("unknown".to_string(), 1),
+            ]
+        );
+        let summary =
+            build_replay_analysis_summary(&state, "test", false, false, false, 0, 0, false);
+        assert_eq!(summary["object_type_histogram"][0]["count"], 2);
+    }
+
+    #[test]
+    fn feasibility_flags_unsupported_commands_and_partial_inputs() {
+        let mut state = dataflow_state();
+        let feasibility = assess_replay_feasibility(&state.transaction);
+        assert!(feasibility.replayable);
+        assert_eq!(feasibility.support, ReplaySupportLevel::Supported);
+        assert!(feasibility.reasons.is_empty());
+
+        state.transaction.inputs.push(TransactionInput::Receiving {
+            object_id: "0x6".to_string(),
+            version: 3,
+            digest: "d".to_string(),
+        });
+        let feasibility = assess_replay_feasibility(&state.transaction);
+        assert!(feasibility.replayable);
+        assert_eq!(feasibility.support, ReplaySupportLevel::Partial);
+
+        state.transaction.commands.push(PtbCommand::Publish {
+            modules: vec![],
+            dependencies: vec![],
+        });
+        let feasibility = assess_replay_feasibility(&state.transaction);
+        assert!(!feasibility.replayable);
+        assert_eq!(feasibility.support, ReplaySupportLevel::Unsupported);
+        assert_eq!(feasibility.commands[2].kind, "Publish");
+        assert_eq!(feasibility.reasons.len(), 2);
+        assert!(feasibility.reasons[0].starts_with("command 2 (Publish)"));
+    }
+
+    #[test]
+    fn move_call_return_types_mark_unresolved_as_unknown() {
+        let mut state = dataflow_state();
+        state.transaction.commands = vec![
+            PtbCommand::MoveCall {
+                package: "0x2".to_string(),
+                module: "coin".to_string(),
+                function: "value".to_string(),
+                type_arguments: vec![],
+                arguments: vec![],
+            },
+            PtbCommand::MoveCall {
+                package: "0xabc".to_string(),
+                module: "pool".to_string(),
+                function: "swap".to_string(),
+                type_arguments: vec![],
+                arguments: vec![],
+            },
+        ];
+        let mut summary =
+            build_replay_analysis_summary(&state, "test", false, false, false, 0, 0, false);
+        attach_move_call_return_types(&mut summary, &state.transaction.commands, |pkg, _, _| {
+            (pkg == "0x2").then(|| vec!["u64".to_string()])
+        });
+        let commands = summary["command_summaries"].as_array().unwrap();
+        assert_eq!(commands[0]["return_types"], serde_json::json!(["u64"]));
+        assert_eq!(commands[1]["return_types"], "unknown");
+    }
 
     #[test]
     fn classify_success_output() {
@@ -561,4 +1859,188 @@ mod tests {
             Some("MoveCall 3")
         );
     }
+
+    #[test]
+    fn classify_move_abort_reports_decoded_constant() {
+        let result = serde_json::json!({
+            "local_success": false,
+            "local_error": "MoveAbort [abort: 0x2::coin::ENotEnough (line 42)]",
+            "effects": {
+                "abort": {
+                    "module": "coin",
+                    "function": "split",
+                    "abort_code": 9223372217243467775u64,
+                    "constant_name": "ENotEnough",
+                    "source_line": 42
+                }
+            }
+        });
+        let classified = classify_replay_output(&result);
+        assert_eq!(classified.category, "move_abort");
+        assert_eq!(
+            classified.suggestions.first().map(String::as_str),
+            Some("Move abort decoded as `coin::ENotEnough` (line 42); check the assertion at that location.")
+        );
+    }
+
+    #[test]
+    fn diff_replay_outputs_reports_effect_return_and_gas_changes() {
+        let a = serde_json::json!({
+            "local_success": true,
+            "effects": {
+                "created": ["0x10"],
+                "mutated": ["0x20"],
+                "deleted": [],
+                "gas_used": 100,
+                "return_value_bytes": [["AQ=="], []],
+            },
+        });
+        let b = serde_json::json!({
+            "local_success": true,
+            "effects": {
+                "created": ["0x10", "0x11"],
+                "mutated": ["0x20"],
+                "deleted": [],
+                "gas_used": 120,
+                "return_value_bytes": [["Ag=="], []],
+            },
+        });
+
+        let same = diff_replay_outputs(&a, &a);
+        assert!(same.identical);
+        assert!(same.return_value_diffs.is_empty());
+
+        let diff = diff_replay_outputs(&a, &b);
+        assert!(!diff.identical);
+        assert!(diff.effects.status_match);
+        assert!(!diff.effects.created_count_match);
+        assert_eq!(diff.effects.created_ids_extra, vec!["0x11".to_string()]);
+        assert_eq!(diff.return_value_diffs.len(), 1);
+        assert_eq!(diff.return_value_diffs[0].command_index, 0);
+        assert_eq!(diff.gas.delta, 20);
+        assert!(diff.effects.notes.iter().any(|n| n.contains("A=1, B=2")));
+    }
+
+    #[test]
+    fn summarize_replay_output_keeps_triage_fields() {
+        let long_error = format!("MoveAbort in command 1: {}\nstack...", "x".repeat(400));
+        let output = serde_json::json!({
+            "digest": "AbC",
+            "local_success": false,
+            "local_error": long_error,
+            "effects": {"gas_used": 1234, "failed_command_index": 1, "created": ["0x1"]},
+            "execution_path": {"synthetic_inputs": 0},
+            "fidelity": {"level": "faithful", "approximations": []},
+        });
+        let summary = summarize_replay_output(&output);
+        assert_eq!(summary["digest"], "AbC");
+        assert_eq!(summary["local_success"], false);
+        assert_eq!(summary["gas_used"], 1234);
+        assert_eq!(summary["failed_command_index"], 1);
+        assert_eq!(summary["fidelity"], "faithful");
+        let error = summary["error"].as_str().unwrap();
+        assert!(error.starts_with("MoveAbort in command 1"));
+        assert_eq!(error.chars().count(), REPLAY_SUMMARY_ERROR_CHARS + 3);
+        assert!(summary.get("effects").is_none());
+    }
+
+    #[test]
+    fn synthesis_summary_counts_per_type() {
+        let mut summary = SynthesisSummary::default();
+        assert!(summary.is_empty());
+        summary.record("0x2::coin::Coin<0x2::sui::SUI>", false);
+        summary.record("0x2::coin::Coin<0x2::sui::SUI>", false);
+        summary.record("0xabc::pool::Pool", true);
+        assert_eq!(summary.total.real_layout, 2);
+        assert_eq!(summary.total.stubs, 1);
+        assert_eq!(summary.synthesized(), 3);
+        assert_eq!(summary.by_type["0xabc::pool::Pool"].stubs, 1);
+
+        let value = serde_json::to_value(&summary).unwrap();
+        assert_eq!(value["real_layout"], 2);
+        assert_eq!(value["stubs"], 1);
+        assert_eq!(
+            value["by_type"]["0x2::coin::Coin<0x2::sui::SUI>"]["real_layout"],
+            2
+        );
+
+        let output = serde_json::json!({
+            "execution_path": {"synthetic_inputs": 3},
+            "synthesis": value,
+        });
+        let fidelity = assess_replay_fidelity(&output, &ReplayFidelitySignals::default());
+        assert!(fidelity.approximations[0].contains("1 as best-effort stubs"));
+    }
+
+    #[test]
+    fn assess_replay_fidelity_flags_approximations() {
+        let clean = serde_json::json!({
+            "local_success": true,
+            "execution_path": {"synthetic_inputs": 0, "fallback_used": false},
+            "warnings": ["[deps] failed to fetch package 0x9: timeout"],
+        });
+        let fidelity = assess_replay_fidelity(&clean, &ReplayFidelitySignals::default());
+        assert_eq!(fidelity.level, "faithful");
+        assert!(fidelity.approximations.is_empty());
+
+        let degraded = serde_json::json!({
+            "local_success": true,
+            "execution_path": {"synthetic_inputs": 2, "fallback_used": false},
+        });
+        let signals = ReplayFidelitySignals {
+            self_healed_values: 1,
+            clock_defaulted: true,
+        };
+        let fidelity = assess_replay_fidelity(&degraded, &signals);
+        assert_eq!(fidelity.level, "degraded");
+        assert_eq!(fidelity.approximations.len(), 3);
+        assert!(fidelity.approximations[0].contains("2 missing input"));
+
+        let context_filled = serde_json::json!({
+            "local_success": true,
+            "package_sources": {"context": ["0xa"], "context_replaced": [], "fetched": ["0x2"]},
+        });
+        assert_eq!(
+            assess_replay_fidelity(&context_filled, &ReplayFidelitySignals::default()).level,
+            "faithful"
+        );
+        let context_replaced = serde_json::json!({
+            "local_success": true,
+            "package_sources": {"context": ["0xa"], "context_replaced": ["0xa"], "fetched": []},
+        });
+        let fidelity = assess_replay_fidelity(&context_replaced, &ReplayFidelitySignals::default());
+        assert_eq!(fidelity.level, "degraded");
+        assert!(fidelity.approximations[0].contains("replaced from context"));
+
+        let stubbed = serde_json::json!({
+            "local_success": true,
+            "stubbed_natives": [
+                {"function": "0x2::poseidon::poseidon_bn254_internal", "reason": "unsupported", "calls": 1},
+            ],
+        });
+        let fidelity = assess_replay_fidelity(&stubbed, &ReplayFidelitySignals::default());
+        assert_eq!(fidelity.level, "degraded");
+        assert!(fidelity.approximations[0].contains("1 native function(s) stubbed"));
+
+        let repriced = serde_json::json!({
+            "local_success": true,
+            "execution_path": {"gas_table": {"custom_tiers": ["instruction_tiers"]}},
+        });
+        let fidelity = assess_replay_fidelity(&repriced, &ReplayFidelitySignals::default());
+        assert_eq!(
+            fidelity.approximations,
+            vec!["custom gas cost table applied"]
+        );
+
+        // A table the simulation could not charge leaves gas at the protocol default.
+        let unapplied = serde_json::json!({
+            "local_success": true,
+            "execution_path": {"gas_table": {
+                "custom_tiers": ["instruction_tiers"],
+                "unapplied": {"instruction_tiers": "requires accurate_gas and a gas budget"},
+            }},
+        });
+        let fidelity = assess_replay_fidelity(&unapplied, &ReplayFidelitySignals::default());
+        assert!(fidelity.approximations.is_empty());
+    }
 }
@@ -9,21 +9,32 @@
 //! - Object version patching for historical replay
 //! - Simulation config construction from replay state
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::{anyhow, Context, Result};
 use base64::Engine;
+use move_binary_format::CompiledModule;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use serde::{Deserialize, Serialize};
 
+use sui_sandbox_types::{
+    synthesize_clock_bytes, PtbCommand, TransactionEffectsSummary, TransactionInput,
+    CLOCK_OBJECT_ID, DEFAULT_CLOCK_BASE_MS,
+};
 use sui_state_fetcher::{
-    build_address_aliases, parse_replay_states_file, PackageData, ReplayState,
+    build_address_aliases, graphql_package_to_data, parse_replay_states_file, PackageData,
+    ReplayState, VersionedCache, VersionedObject,
 };
 use sui_transport::decode_graphql_modules;
 use sui_transport::graphql::GraphQLClient;
 
 use crate::resolver::LocalModuleResolver;
+use crate::sandbox_error::SandboxError;
 use crate::tx_replay::{
     replay_with_version_tracking_with_policy_with_effects, EffectsReconcilePolicy, ReplayExecution,
 };
@@ -39,14 +50,28 @@ use crate::VMHarness;
 /// Sui framework and all packages from the replay state.
 ///
 /// Unlike the CLI version which clones from `SandboxState`, this starts fresh
-/// with `LocalModuleResolver::with_sui_framework()`.
+/// with `LocalModuleResolver::with_sui_framework_version()` so the framework
+/// matches `replay_state.protocol_version` when a snapshot is available.
 pub fn hydrate_resolver_from_replay_state(
     replay_state: &ReplayState,
     linkage_upgrades: &HashMap<AccountAddress, AccountAddress>,
     aliases: &HashMap<AccountAddress, AccountAddress>,
 ) -> Result<LocalModuleResolver> {
-    let mut resolver = LocalModuleResolver::with_sui_framework()?;
+    // Historical transactions must see the framework as of their protocol version.
+    let mut resolver =
+        LocalModuleResolver::with_sui_framework_version(replay_state.protocol_version)?;
+    load_replay_packages(&mut resolver, replay_state, linkage_upgrades, aliases);
+    Ok(resolver)
+}
 
+/// Load the packages, linkage upgrades and address aliases of `replay_state` into
+/// `resolver`.
+fn load_replay_packages(
+    resolver: &mut LocalModuleResolver,
+    replay_state: &ReplayState,
+    linkage_upgrades: &HashMap<AccountAddress, AccountAddress>,
+    aliases: &HashMap<AccountAddress, AccountAddress>,
+) {
     // Sort packages by (runtime_id, version) for deterministic loading
     let mut packages: Vec<&PackageData> = replay_state.packages.values().collect();
     packages.sort_by(|a, b| {
@@ -69,7 +94,6 @@ pub fn hydrate_resolver_from_replay_state(
     for (storage, runtime) in aliases {
         resolver.add_address_alias(*storage, *runtime);
     }
-    Ok(resolver)
 }
 
 // ---------------------------------------------------------------------------
@@ -85,6 +109,22 @@ pub fn fetch_dependency_closure(
     graphql: &GraphQLClient,
     checkpoint: Option<u64>,
     verbose: bool,
+) -> Result<usize> {
+    fetch_dependency_closure_cached(resolver, graphql, checkpoint, None, verbose)
+}
+
+/// [`fetch_dependency_closure`] that serves packages from `cache` before going to
+/// GraphQL and stores every package it fetches there.
+///
+/// Packages are immutable per storage id, so one cache can back every replay of a
+/// batch regardless of checkpoint. Returns the number of packages fetched over the
+/// network; cache hits are not counted.
+pub fn fetch_dependency_closure_cached(
+    resolver: &mut LocalModuleResolver,
+    graphql: &GraphQLClient,
+    checkpoint: Option<u64>,
+    cache: Option<&VersionedCache>,
+    verbose: bool,
 ) -> Result<usize> {
     const MAX_ROUNDS: usize = 8;
     let mut fetched = 0usize;
@@ -123,6 +163,17 @@ pub fn fetch_dependency_closure(
                 }
                 seen.insert(candidate);
                 let addr_hex = candidate.to_hex_literal();
+                if let Some(cached) = cache
+                    .and_then(|cache| cache.get_package_latest(&candidate))
+                    .filter(|pkg| !pkg.modules.is_empty())
+                {
+                    if verbose {
+                        eprintln!("[deps] reusing cached {}", addr_hex);
+                    }
+                    let _ = resolver.add_package_modules_at(cached.modules, Some(candidate));
+                    fetched_this = true;
+                    break;
+                }
                 if verbose {
                     eprintln!("[deps] fetching {}", addr_hex);
                 }
@@ -142,14 +193,17 @@ pub fn fetch_dependency_closure(
                     },
                     None => graphql.fetch_package(&addr_hex)?,
                 };
-                let modules = decode_graphql_modules(&addr_hex, &pkg.modules)?;
-                if modules.is_empty() {
+                let data = graphql_package_to_data(candidate, pkg)?;
+                if data.modules.is_empty() {
                     if verbose {
                         eprintln!("[deps] no modules for {}", addr_hex);
                     }
                     continue;
                 }
-                let _ = resolver.add_package_modules_at(modules, Some(candidate));
+                if let Some(cache) = cache {
+                    cache.put_package(data.clone());
+                }
+                let _ = resolver.add_package_modules_at(data.modules, Some(candidate));
                 fetched += 1;
                 fetched_this = true;
                 break;
@@ -166,6 +220,307 @@ pub fn fetch_dependency_closure(
     Ok(fetched)
 }
 
+/// Fetch only the modules transitively referenced from `roots`, one module per
+/// GraphQL request, instead of whole packages.
+///
+/// Useful for very wide packages where a call touches a handful of modules. Each
+/// single-module fetch also returns its package's linkage table, so dependencies of
+/// a fetched module are requested at the storage ids that package links against.
+///
+/// Returns the number of modules fetched.
+pub fn fetch_module_closure(
+    resolver: &mut LocalModuleResolver,
+    graphql: &GraphQLClient,
+    roots: &[ModuleId],
+    checkpoint: Option<u64>,
+    verbose: bool,
+) -> Result<usize> {
+    fetch_module_closure_with(resolver, roots, |storage, name| {
+        fetch_module_package(graphql, storage, name, checkpoint, verbose)
+    })
+}
+
+/// Fetch the single module `storage::name` over GraphQL, with its package's linkage.
+///
+/// Returns `None` when the module has no bytecode.
+fn fetch_module_package(
+    graphql: &GraphQLClient,
+    storage: AccountAddress,
+    name: &str,
+    checkpoint: Option<u64>,
+    verbose: bool,
+) -> Result<Option<PackageData>> {
+    let addr_hex = storage.to_hex_literal();
+    if verbose {
+        eprintln!("[deps] fetching module {}::{}", addr_hex, name);
+    }
+    let pkg = match checkpoint {
+        Some(cp) => match graphql.fetch_package_module_at_checkpoint(&addr_hex, name, cp) {
+            Ok(p) => p,
+            Err(err) => {
+                if verbose {
+                    eprintln!(
+                        "[deps] failed to fetch {}::{} at checkpoint {}: {}",
+                        addr_hex, name, cp, err
+                    );
+                    eprintln!("[deps] falling back to latest module for {}", addr_hex);
+                }
+                graphql.fetch_package_module(&addr_hex, name)?
+            }
+        },
+        None => graphql.fetch_package_module(&addr_hex, name)?,
+    };
+    let data = graphql_package_to_data(storage, pkg)?;
+    if data.modules.is_empty() {
+        if verbose {
+            eprintln!("[deps] no bytecode for {}::{}", addr_hex, name);
+        }
+        return Ok(None);
+    }
+    Ok(Some(data))
+}
+
+/// Walk the module closure of `roots`, loading each module the resolver lacks through
+/// `fetch(storage_id, module_name)`.
+///
+/// `fetch` returns a package holding the requested module and its package's linkage
+/// table, or `None` when it has no bytecode. A module's dependencies are requested at
+/// the storage id the referencing package's linkage names for them, falling back to
+/// known upgrades and then to the runtime id.
+///
+/// Returns the number of modules fetched.
+pub fn fetch_module_closure_with(
+    resolver: &mut LocalModuleResolver,
+    roots: &[ModuleId],
+    mut fetch: impl FnMut(AccountAddress, &str) -> Result<Option<PackageData>>,
+) -> Result<usize> {
+    let mut fetched = 0usize;
+    let mut seen: BTreeSet<ModuleId> = BTreeSet::new();
+    // Each module is queued with the storage id of the package that referenced it.
+    let mut queue: VecDeque<(ModuleId, Option<AccountAddress>)> =
+        roots.iter().map(|id| (id.clone(), None)).collect();
+
+    while let Some((id, link_context)) = queue.pop_front() {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        let addr = *id.address();
+        let name = id.name().as_str();
+        let storage = link_context
+            .and_then(|context| resolver.get_per_package_linkage(&context, &addr))
+            .or_else(|| resolver.get_linkage_upgrade(&addr))
+            .unwrap_or(addr);
+        if resolver.get_module_by_addr_name(&addr, name).is_none() {
+            let Some(pkg) = fetch(storage, name)? else {
+                continue;
+            };
+            let (_, runtime_id) = resolver
+                .add_package_modules_at(pkg.modules, Some(storage))
+                .with_context(|| SandboxError::PackageLoadFailed {
+                    package_id: storage.to_hex_literal(),
+                    module: Some(name.to_string()),
+                })?;
+            resolver.add_package_linkage(storage, runtime_id.unwrap_or(storage), &pkg.linkage);
+            fetched += 1;
+        }
+
+        let Some(module) = resolver.get_module_by_addr_name(&addr, name) else {
+            continue;
+        };
+        let deps: Vec<(ModuleId, Option<AccountAddress>)> = module
+            .module_handles()
+            .iter()
+            .map(|handle| module.module_id_for_handle(handle))
+            .filter(|dep| !seen.contains(dep))
+            .map(|dep| (dep, Some(storage)))
+            .collect();
+        queue.extend(deps);
+    }
+
+    Ok(fetched)
+}
+
+/// Modules a transaction's Move calls start from: each call's target module and the
+/// modules defining its type arguments. These are the roots of
+/// [`fetch_module_closure`] for a replay.
+pub fn transaction_module_roots(commands: &[PtbCommand]) -> Vec<ModuleId> {
+    fn collect_type_modules(tag: &TypeTag, out: &mut Vec<ModuleId>) {
+        match tag {
+            TypeTag::Struct(st) => {
+                out.push(st.module_id());
+                for param in &st.type_params {
+                    collect_type_modules(param, out);
+                }
+            }
+            TypeTag::Vector(inner) => collect_type_modules(inner, out),
+            _ => {}
+        }
+    }
+
+    let mut roots = Vec::new();
+    for command in commands {
+        let PtbCommand::MoveCall {
+            package,
+            module,
+            type_arguments,
+            ..
+        } = command
+        else {
+            continue;
+        };
+        if let (Ok(address), Ok(module)) = (
+            AccountAddress::from_hex_literal(package),
+            Identifier::new(module.as_str()),
+        ) {
+            roots.push(ModuleId::new(address, module));
+        }
+        for type_arg in type_arguments {
+            if let Ok(tag) = crate::types::parse_type_tag(type_arg) {
+                collect_type_modules(&tag, &mut roots);
+            }
+        }
+    }
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+// ---------------------------------------------------------------------------
+// Shared batch resolver
+// ---------------------------------------------------------------------------
+
+/// Package resolution shared by every replay of a batch.
+///
+/// Keeps one framework-loaded resolver per protocol version, which each replay forks
+/// instead of loading the framework again, plus the packages and single modules that
+/// earlier dependency closures fetched, so later replays resolve them without going
+/// back to GraphQL. Packages and modules are immutable per storage id, so the shared
+/// state holds across checkpoints.
+pub struct SharedReplayResolver {
+    packages: Arc<VersionedCache>,
+    frameworks: parking_lot::Mutex<HashMap<u64, LocalModuleResolver>>,
+    modules: parking_lot::RwLock<HashMap<(AccountAddress, String), PackageData>>,
+}
+
+/// What a [`SharedReplayResolver`] holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedResolverStats {
+    /// Protocol versions with a loaded framework resolver.
+    pub framework_versions: usize,
+    /// Whole packages fetched by dependency closures (and anything else in the cache).
+    pub packages: usize,
+    /// Single modules fetched by module closures.
+    pub modules: usize,
+}
+
+impl SharedReplayResolver {
+    /// Share resolution state through `packages`; pass the cache that the batch's
+    /// state hydration uses so packages it fetched count too.
+    pub fn new(packages: Arc<VersionedCache>) -> Self {
+        Self {
+            packages,
+            frameworks: parking_lot::Mutex::new(HashMap::new()),
+            modules: parking_lot::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The package cache backing this resolver.
+    pub fn package_cache(&self) -> &Arc<VersionedCache> {
+        &self.packages
+    }
+
+    /// [`hydrate_resolver_from_replay_state`] starting from a fork of the shared
+    /// framework resolver for `replay_state.protocol_version`.
+    pub fn hydrate(
+        &self,
+        replay_state: &ReplayState,
+        linkage_upgrades: &HashMap<AccountAddress, AccountAddress>,
+        aliases: &HashMap<AccountAddress, AccountAddress>,
+    ) -> Result<LocalModuleResolver> {
+        let mut resolver = self.framework(replay_state.protocol_version)?;
+        load_replay_packages(&mut resolver, replay_state, linkage_upgrades, aliases);
+        Ok(resolver)
+    }
+
+    /// Fork of the framework resolver for `protocol_version`, loading it on first use.
+    fn framework(&self, protocol_version: u64) -> Result<LocalModuleResolver> {
+        let mut frameworks = self.frameworks.lock();
+        if let Some(base) = frameworks.get(&protocol_version) {
+            return Ok(base.fork());
+        }
+        let base = LocalModuleResolver::with_sui_framework_version(protocol_version)?;
+        let resolver = base.fork();
+        frameworks.insert(protocol_version, base);
+        Ok(resolver)
+    }
+
+    /// [`fetch_dependency_closure`] through the shared package cache.
+    pub fn fetch_dependency_closure(
+        &self,
+        resolver: &mut LocalModuleResolver,
+        graphql: &GraphQLClient,
+        checkpoint: Option<u64>,
+        verbose: bool,
+    ) -> Result<usize> {
+        fetch_dependency_closure_cached(
+            resolver,
+            graphql,
+            checkpoint,
+            Some(&self.packages),
+            verbose,
+        )
+    }
+
+    /// [`fetch_module_closure`] that serves modules earlier replays fetched before going
+    /// to GraphQL and keeps every module it fetches.
+    ///
+    /// Returns the number of modules loaded, including those served from the shared state.
+    pub fn fetch_module_closure(
+        &self,
+        resolver: &mut LocalModuleResolver,
+        graphql: &GraphQLClient,
+        roots: &[ModuleId],
+        checkpoint: Option<u64>,
+        verbose: bool,
+    ) -> Result<usize> {
+        fetch_module_closure_with(resolver, roots, |storage, name| {
+            let key = (storage, name.to_string());
+            if let Some(pkg) = self.modules.read().get(&key) {
+                if verbose {
+                    eprintln!(
+                        "[deps] reusing cached module {}::{}",
+                        storage.to_hex_literal(),
+                        name
+                    );
+                }
+                return Ok(Some(pkg.clone()));
+            }
+            let fetched = fetch_module_package(graphql, storage, name, checkpoint, verbose)?;
+            if let Some(pkg) = &fetched {
+                self.modules.write().insert(key, pkg.clone());
+            }
+            Ok(fetched)
+        })
+    }
+
+    /// Counts of the shared state.
+    pub fn stats(&self) -> SharedResolverStats {
+        SharedResolverStats {
+            framework_versions: self.frameworks.lock().len(),
+            packages: self.packages.package_count(),
+            modules: self.modules.read().len(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SharedReplayResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedReplayResolver")
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Object maps
 // ---------------------------------------------------------------------------
@@ -182,9 +537,125 @@ pub struct ReplayObjectMaps {
     pub object_bytes: HashMap<String, Vec<u8>>,
     /// Object type tags, keyed by hex ID
     pub object_types: HashMap<String, String>,
+    /// Supplied objects whose digest disagrees with the transaction's object reference
+    pub digest_mismatches: Vec<ObjectDigestMismatch>,
+    /// Referenced objects (`id vN`) supplied without a digest, so unverified
+    pub unverified_digests: Vec<String>,
+}
+
+/// A hydrated object whose digest differs from the one the transaction references.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObjectDigestMismatch {
+    pub object_id: String,
+    pub version: u64,
+    /// Digest in the transaction's object reference.
+    pub expected: String,
+    /// Digest recorded (or computed) for the supplied object.
+    pub actual: String,
+}
+
+impl std::fmt::Display for ObjectDigestMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "object {} v{}: transaction references digest {} but supplied object has {}",
+            self.object_id, self.version, self.expected, self.actual
+        )
+    }
+}
+
+impl ReplayObjectMaps {
+    /// Strict mode: fail when any supplied object's digest disagrees with its reference.
+    pub fn ensure_object_digests_match(&self) -> Result<()> {
+        if self.digest_mismatches.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = self
+            .digest_mismatches
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        Err(SandboxError::ObjectDigestMismatch {
+            mismatches: details,
+        }
+        .into())
+    }
+}
+
+/// Objects hydrated at the version the transaction references, with the referenced digest.
+///
+/// Shared inputs carry no digest and are skipped.
+fn referenced_input_objects(
+    replay_state: &ReplayState,
+) -> Vec<(AccountAddress, &VersionedObject, &str)> {
+    let mut referenced = Vec::new();
+    for input in &replay_state.transaction.inputs {
+        let (object_id, version, expected) = match input {
+            TransactionInput::Object {
+                object_id,
+                version,
+                digest,
+            }
+            | TransactionInput::ImmutableObject {
+                object_id,
+                version,
+                digest,
+            }
+            | TransactionInput::Receiving {
+                object_id,
+                version,
+                digest,
+            } => (object_id, *version, digest),
+            _ => continue,
+        };
+        let Ok(id) = AccountAddress::from_hex_literal(object_id) else {
+            continue;
+        };
+        let Some(obj) = replay_state.objects.get(&id) else {
+            continue;
+        };
+        if obj.version == version && !expected.is_empty() {
+            referenced.push((id, obj, expected.as_str()));
+        }
+    }
+    referenced
+}
+
+/// Compare hydrated objects against the digests in the transaction's object references.
+///
+/// An object's digest is the one its provider recorded; the gRPC and Walrus providers
+/// compute it from the full object BCS. Objects without one are reported by
+/// [`find_unverified_object_digests`] instead.
+pub fn find_object_digest_mismatches(replay_state: &ReplayState) -> Vec<ObjectDigestMismatch> {
+    referenced_input_objects(replay_state)
+        .into_iter()
+        .filter_map(|(id, obj, expected)| {
+            let actual = obj.digest.as_deref()?;
+            (actual != expected).then(|| ObjectDigestMismatch {
+                object_id: id.to_hex_literal(),
+                version: obj.version,
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Referenced input objects (`id vN`) whose bytes carry no digest to check.
+pub fn find_unverified_object_digests(replay_state: &ReplayState) -> Vec<String> {
+    let mut unverified: Vec<String> = referenced_input_objects(replay_state)
+        .into_iter()
+        .filter(|(_, obj, _)| obj.digest.is_none())
+        .map(|(id, obj, _)| format!("{} v{}", id.to_hex_literal(), obj.version))
+        .collect();
+    unverified.sort();
+    unverified
 }
 
 /// Convert `ReplayState` objects into the maps needed by the VM harness.
+///
+/// Digest disagreements with the transaction's object references are collected in
+/// `digest_mismatches`; see [`ReplayObjectMaps::ensure_object_digests_match`].
 pub fn build_replay_object_maps(
     replay_state: &ReplayState,
     versions: &HashMap<AccountAddress, u64>,
@@ -215,6 +686,8 @@ pub fn build_replay_object_maps(
         version_map,
         object_bytes,
         object_types,
+        digest_mismatches: find_object_digest_mismatches(replay_state),
+        unverified_digests: find_unverified_object_digests(replay_state),
     }
 }
 
@@ -274,6 +747,734 @@ pub fn maybe_patch_replay_objects(
     }
 }
 
+/// Per-type object overrides applied on top of the patched replay object maps.
+///
+/// Used for fault-injection replays (e.g. "what if the oracle object is absent or
+/// stale"). Keys are type tags; a key matches objects of exactly that type or any
+/// instantiation of it (`0x2::coin::Coin` matches `0x2::coin::Coin<0x2::sui::SUI>`).
+/// A `None` value drops matching objects; `Some(bytes)` substitutes their BCS contents.
+///
+/// When several rules match an object, a rule naming its exact instantiation wins over
+/// one naming the base type; among equally specific rules, the first key in sorted
+/// order wins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayObjectTypeOverrides {
+    #[serde(with = "base64_payloads::object_rules")]
+    pub rules: BTreeMap<String, Option<Vec<u8>>>,
+}
+
+/// Object IDs affected by [`ReplayObjectTypeOverrides`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ReplayObjectOverrideReport {
+    pub dropped: Vec<String>,
+    pub replaced: Vec<String>,
+}
+
+impl ReplayObjectTypeOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    fn rule_for(
+        &self,
+        object_type: &str,
+        aliases: &HashMap<AccountAddress, AccountAddress>,
+    ) -> Option<&Option<Vec<u8>>> {
+        let object_type = crate::types::normalize_type_string_with_aliases(object_type, aliases);
+        let base = object_type.split('<').next().unwrap_or(&object_type);
+        let mut base_match = None;
+        for (pattern, action) in &self.rules {
+            let pattern = crate::types::normalize_type_string_with_aliases(pattern.trim(), aliases);
+            if pattern == object_type {
+                return Some(action);
+            }
+            if pattern == base && base_match.is_none() {
+                base_match = Some(action);
+            }
+        }
+        base_match
+    }
+
+    /// Drop or substitute objects in `maps` whose type matches a rule.
+    pub fn apply(
+        &self,
+        maps: &mut ReplayObjectMaps,
+        aliases: &HashMap<AccountAddress, AccountAddress>,
+    ) -> ReplayObjectOverrideReport {
+        let mut report = ReplayObjectOverrideReport::default();
+        if self.is_empty() {
+            return report;
+        }
+        let matched: Vec<(String, Option<Vec<u8>>)> = maps
+            .object_types
+            .iter()
+            .filter_map(|(id, object_type)| {
+                self.rule_for(object_type, aliases)
+                    .map(|action| (id.clone(), action.clone()))
+            })
+            .collect();
+        for (id, action) in matched {
+            match action {
+                None => {
+                    maps.cached_objects.remove(&id);
+                    maps.version_map.remove(&id);
+                    maps.object_bytes.remove(&id);
+                    maps.object_types.remove(&id);
+                    report.dropped.push(id);
+                }
+                Some(bytes) => {
+                    maps.cached_objects.insert(
+                        id.clone(),
+                        base64::engine::general_purpose::STANDARD.encode(&bytes),
+                    );
+                    maps.object_bytes.insert(id.clone(), bytes);
+                    report.replaced.push(id);
+                }
+            }
+        }
+        report.dropped.sort();
+        report.replaced.sort();
+        report
+    }
+}
+
+/// [`maybe_patch_replay_objects`] followed by per-type drop/substitute overrides.
+///
+/// Overrides run after version patching so substituted bytes are used verbatim.
+pub fn maybe_patch_replay_objects_with_overrides(
+    resolver: &LocalModuleResolver,
+    replay_state: &ReplayState,
+    versions: &HashMap<AccountAddress, u64>,
+    aliases: &HashMap<AccountAddress, AccountAddress>,
+    maps: &mut ReplayObjectMaps,
+    overrides: &ReplayObjectTypeOverrides,
+    verbose: bool,
+) -> ReplayObjectOverrideReport {
+    maybe_patch_replay_objects(resolver, replay_state, versions, aliases, maps, verbose);
+    let report = overrides.apply(maps, aliases);
+    if verbose && (!report.dropped.is_empty() || !report.replaced.is_empty()) {
+        eprintln!(
+            "[override] dropped_objects={} replaced_objects={}",
+            report.dropped.len(),
+            report.replaced.len()
+        );
+    }
+    report
+}
+
+// ---------------------------------------------------------------------------
+// Package overrides
+// ---------------------------------------------------------------------------
+
+/// Locally-built bytecode substituted for packages before replay, keyed by package id.
+///
+/// The package keeps its historical id, version, and linkage; only its modules
+/// change. Override modules must be compiled at the package's runtime (original)
+/// address so existing type tags and callers still resolve to them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayPackageOverrides {
+    #[serde(with = "base64_payloads::package_modules")]
+    pub packages: HashMap<AccountAddress, Vec<Vec<u8>>>,
+}
+
+/// Serde for override payloads, stored as base64 strings rather than byte arrays.
+mod base64_payloads {
+    use base64::Engine;
+    use serde::de::Error;
+
+    fn encode(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn decode<E: Error>(text: &str) -> Result<Vec<u8>, E> {
+        base64::engine::general_purpose::STANDARD
+            .decode(text)
+            .map_err(E::custom)
+    }
+
+    pub(super) mod object_rules {
+        use std::collections::BTreeMap;
+
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            rules: &BTreeMap<String, Option<Vec<u8>>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            rules
+                .iter()
+                .map(|(type_tag, bytes)| (type_tag, bytes.as_deref().map(super::encode)))
+                .collect::<BTreeMap<_, _>>()
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<BTreeMap<String, Option<Vec<u8>>>, D::Error> {
+            BTreeMap::<String, Option<String>>::deserialize(deserializer)?
+                .into_iter()
+                .map(|(type_tag, bytes)| {
+                    let bytes = bytes.as_deref().map(super::decode::<D::Error>);
+                    Ok((type_tag, bytes.transpose()?))
+                })
+                .collect()
+        }
+    }
+
+    pub(super) mod package_modules {
+        use std::collections::HashMap;
+
+        use move_core_types::account_address::AccountAddress;
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            packages: &HashMap<AccountAddress, Vec<Vec<u8>>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            packages
+                .iter()
+                .map(|(id, modules)| {
+                    let modules: Vec<String> =
+                        modules.iter().map(|bytes| super::encode(bytes)).collect();
+                    (id, modules)
+                })
+                .collect::<HashMap<_, _>>()
+                .serialize(serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<HashMap<AccountAddress, Vec<Vec<u8>>>, D::Error> {
+            HashMap::<AccountAddress, Vec<String>>::deserialize(deserializer)?
+                .into_iter()
+                .map(|(id, modules)| {
+                    let modules = modules
+                        .iter()
+                        .map(|text| super::decode::<D::Error>(text))
+                        .collect::<Result<_, _>>()?;
+                    Ok((id, modules))
+                })
+                .collect()
+        }
+    }
+}
+
+/// One package whose bytecode was substituted by [`ReplayPackageOverrides`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReplayPackageOverride {
+    pub package_id: String,
+    pub runtime_id: String,
+    pub modules: Vec<String>,
+    /// Historical modules missing from the override; they are no longer loadable.
+    pub removed_modules: Vec<String>,
+    /// `false` when the package was not part of the historical state and was added.
+    pub replaced: bool,
+}
+
+impl ReplayPackageOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    /// Swap override bytecode into `replay_state.packages`.
+    pub fn apply(&self, replay_state: &mut ReplayState) -> Result<Vec<ReplayPackageOverride>> {
+        let mut package_ids: Vec<&AccountAddress> = self.packages.keys().collect();
+        package_ids.sort();
+
+        let mut report = Vec::with_capacity(package_ids.len());
+        for package_id in package_ids {
+            let id_hex = package_id.to_hex_literal();
+            let mut modules = Vec::new();
+            let mut self_address = None;
+            for bytes in &self.packages[package_id] {
+                let module =
+                    CompiledModule::deserialize_with_defaults(bytes).with_context(|| {
+                        format!("package override {}: invalid module bytecode", id_hex)
+                    })?;
+                let address = *module.self_id().address();
+                if self_address.is_some_and(|existing| existing != address) {
+                    return Err(anyhow!(
+                        "package override {}: modules are compiled at different addresses",
+                        id_hex
+                    ));
+                }
+                self_address = Some(address);
+                modules.push((module.self_id().name().to_string(), bytes.clone()));
+            }
+            let runtime_id = self_address
+                .ok_or_else(|| anyhow!("package override {}: no modules given", id_hex))?;
+
+            let mut names: Vec<String> = modules.iter().map(|(name, _)| name.clone()).collect();
+            names.sort();
+            let entry = match replay_state.packages.get_mut(package_id) {
+                Some(pkg) => {
+                    if pkg.runtime_id() != runtime_id {
+                        return Err(anyhow!(
+                            "package override {}: modules are compiled at {} but the package's runtime id is {}",
+                            id_hex,
+                            runtime_id.to_hex_literal(),
+                            pkg.runtime_id().to_hex_literal()
+                        ));
+                    }
+                    let mut removed_modules: Vec<String> = pkg
+                        .modules
+                        .iter()
+                        .map(|(name, _)| name.clone())
+                        .filter(|name| !names.contains(name))
+                        .collect();
+                    removed_modules.sort();
+                    pkg.modules = modules;
+                    ReplayPackageOverride {
+                        package_id: id_hex,
+                        runtime_id: runtime_id.to_hex_literal(),
+                        modules: names,
+                        removed_modules,
+                        replaced: true,
+                    }
+                }
+                None => {
+                    replay_state.packages.insert(
+                        *package_id,
+                        PackageData {
+                            address: *package_id,
+                            version: 1,
+                            modules,
+                            linkage: HashMap::new(),
+                            original_id: (runtime_id != *package_id).then_some(runtime_id),
+                        },
+                    );
+                    ReplayPackageOverride {
+                        package_id: id_hex,
+                        runtime_id: runtime_id.to_hex_literal(),
+                        modules: names,
+                        removed_modules: Vec::new(),
+                        replaced: false,
+                    }
+                }
+            };
+            report.push(entry);
+        }
+        Ok(report)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Ownership hints
+// ---------------------------------------------------------------------------
+
+/// Ownership a replay should assume for an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectOwnershipHint {
+    Shared,
+    Owned,
+    Immutable,
+}
+
+impl FromStr for ObjectOwnershipHint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "shared" => Ok(Self::Shared),
+            "owned" => Ok(Self::Owned),
+            "immutable" => Ok(Self::Immutable),
+            other => Err(anyhow!(
+                "unknown ownership '{}' (expected shared, owned or immutable)",
+                other
+            )),
+        }
+    }
+}
+
+impl ObjectOwnershipHint {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Shared => "shared",
+            Self::Owned => "owned",
+            Self::Immutable => "immutable",
+        }
+    }
+
+    fn of_object(object: &VersionedObject) -> Self {
+        if object.is_shared {
+            Self::Shared
+        } else if object.is_immutable {
+            Self::Immutable
+        } else {
+            Self::Owned
+        }
+    }
+
+    fn of_input(input: &TransactionInput) -> Option<Self> {
+        match input {
+            TransactionInput::Object { .. } => Some(Self::Owned),
+            TransactionInput::SharedObject { .. } => Some(Self::Shared),
+            TransactionInput::ImmutableObject { .. } => Some(Self::Immutable),
+            TransactionInput::Receiving { .. } | TransactionInput::Pure { .. } => None,
+        }
+    }
+}
+
+/// The shared-input fields a `Shared` hint must supply: hydrated objects carry neither
+/// the version the object was shared at nor how the transaction took it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedInputHint {
+    pub initial_shared_version: u64,
+    pub mutable: bool,
+}
+
+/// Per-object ownership overrides applied to the replay state before execution.
+///
+/// The VM takes ownership from the transaction's object inputs, so a hint rewrites the
+/// input that references the object (owned, shared or immutable) and the hydrated
+/// object's `is_shared` / `is_immutable` flags. Receiving inputs are left as they are.
+/// Making an input shared needs its [`SharedInputHint`] in `shared_inputs`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayOwnershipHints {
+    pub hints: HashMap<AccountAddress, ObjectOwnershipHint>,
+    pub shared_inputs: HashMap<AccountAddress, SharedInputHint>,
+}
+
+/// One object whose ownership was set by [`ReplayOwnershipHints`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReplayOwnershipHintReport {
+    pub object_id: String,
+    pub hint: ObjectOwnershipHint,
+    /// Shared-input fields given with a `Shared` hint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_input: Option<SharedInputHint>,
+    /// Ownership recorded in the hydrated object metadata (`None` when not hydrated).
+    pub hydrated: Option<ObjectOwnershipHint>,
+    /// Ownership of the transaction input that referenced the object, before the hint.
+    pub input: Option<ObjectOwnershipHint>,
+    /// The hint disagrees with the hydrated metadata.
+    pub contradicted: bool,
+}
+
+impl ReplayOwnershipHints {
+    pub fn is_empty(&self) -> bool {
+        self.hints.is_empty()
+    }
+
+    /// Add a hint given as `"owned"`, `"immutable"`, `"shared"`, or
+    /// `"shared:<initial_shared_version>:mutable|readonly"`.
+    pub fn insert_spec(&mut self, object_id: AccountAddress, spec: &str) -> Result<()> {
+        let mut parts = spec.split(':');
+        let hint: ObjectOwnershipHint = parts.next().unwrap_or_default().parse()?;
+        let rest: Vec<&str> = parts.collect();
+        match (hint, rest.as_slice()) {
+            (_, []) => {
+                self.shared_inputs.remove(&object_id);
+            }
+            (ObjectOwnershipHint::Shared, [version, access]) => {
+                let initial_shared_version = version.trim().parse().with_context(|| {
+                    format!("invalid initial_shared_version '{}'", version.trim())
+                })?;
+                let mutable = match access.trim().to_ascii_lowercase().as_str() {
+                    "mutable" => true,
+                    "readonly" => false,
+                    other => {
+                        return Err(anyhow!(
+                            "unknown shared access '{}' (expected mutable or readonly)",
+                            other
+                        ))
+                    }
+                };
+                self.shared_inputs.insert(
+                    object_id,
+                    SharedInputHint {
+                        initial_shared_version,
+                        mutable,
+                    },
+                );
+            }
+            _ => {
+                return Err(anyhow!(
+                    "invalid ownership hint '{}' (expected owned, immutable, shared or \
+                     shared:<initial_shared_version>:mutable|readonly)",
+                    spec
+                ))
+            }
+        }
+        self.hints.insert(object_id, hint);
+        Ok(())
+    }
+
+    /// Rewrite inputs and object flags in `replay_state` to match the hints.
+    ///
+    /// Fails when a `Shared` hint would make an owned or immutable input shared without
+    /// a [`SharedInputHint`]; a shared input's fields are only replaced when one is given.
+    pub fn apply(&self, replay_state: &mut ReplayState) -> Result<Vec<ReplayOwnershipHintReport>> {
+        let mut ids: Vec<&AccountAddress> = self.hints.keys().collect();
+        ids.sort();
+
+        let mut report = Vec::with_capacity(ids.len());
+        for id in ids {
+            let hint = self.hints[id];
+            let shared_input = self.shared_inputs.get(id).copied();
+            if hint == ObjectOwnershipHint::Shared && shared_input.is_none() {
+                let unshared = replay_state.transaction.inputs.iter().any(|input| {
+                    input_object_id(input) == Some(*id)
+                        && matches!(
+                            ObjectOwnershipHint::of_input(input),
+                            Some(ObjectOwnershipHint::Owned | ObjectOwnershipHint::Immutable)
+                        )
+                });
+                if unshared {
+                    return Err(anyhow!(
+                        "ownership hint makes input {} shared; give its initial shared version \
+                         and access as 'shared:<initial_shared_version>:mutable|readonly'",
+                        id.to_hex_literal()
+                    ));
+                }
+            }
+            let object = replay_state.objects.get_mut(id);
+            let hydrated = object.as_deref().map(ObjectOwnershipHint::of_object);
+            let (version, digest) = object
+                .as_deref()
+                .map(|object| (object.version, object.digest.clone()))
+                .unzip();
+            if let Some(object) = object {
+                object.is_shared = hint == ObjectOwnershipHint::Shared;
+                object.is_immutable = hint == ObjectOwnershipHint::Immutable;
+            }
+
+            let mut input_ownership = None;
+            for input in replay_state.transaction.inputs.iter_mut() {
+                let Some(current) = ObjectOwnershipHint::of_input(input) else {
+                    continue;
+                };
+                if !input_object_id(input).is_some_and(|input_id| input_id == *id) {
+                    continue;
+                }
+                input_ownership = Some(current);
+                let reshare = current == ObjectOwnershipHint::Shared && shared_input.is_some();
+                if current != hint || reshare {
+                    *input =
+                        hinted_input(input, hint, version, digest.clone().flatten(), shared_input);
+                }
+            }
+
+            report.push(ReplayOwnershipHintReport {
+                object_id: id.to_hex_literal(),
+                hint,
+                shared_input,
+                hydrated,
+                input: input_ownership,
+                contradicted: hydrated.is_some_and(|hydrated| hydrated != hint),
+            });
+        }
+        Ok(report)
+    }
+}
+
+fn input_object_id(input: &TransactionInput) -> Option<AccountAddress> {
+    let object_id = match input {
+        TransactionInput::Object { object_id, .. }
+        | TransactionInput::SharedObject { object_id, .. }
+        | TransactionInput::ImmutableObject { object_id, .. }
+        | TransactionInput::Receiving { object_id, .. } => object_id,
+        TransactionInput::Pure { .. } => return None,
+    };
+    AccountAddress::from_hex_literal(object_id).ok()
+}
+
+/// `input` re-expressed with `hint` ownership. Version and digest come from the
+/// hydrated object when there is one, else from the input itself. A shared input takes
+/// `shared_input` when given and otherwise keeps its own fields; [`ReplayOwnershipHints::apply`]
+/// rejects making a non-shared input shared without one.
+fn hinted_input(
+    input: &TransactionInput,
+    hint: ObjectOwnershipHint,
+    object_version: Option<u64>,
+    object_digest: Option<String>,
+    shared_input: Option<SharedInputHint>,
+) -> TransactionInput {
+    let (object_id, input_version, input_digest, input_shared) = match input {
+        TransactionInput::Object {
+            object_id,
+            version,
+            digest,
+        }
+        | TransactionInput::ImmutableObject {
+            object_id,
+            version,
+            digest,
+        } => (object_id.clone(), *version, Some(digest.clone()), None),
+        TransactionInput::SharedObject {
+            object_id,
+            initial_shared_version,
+            mutable,
+        } => (
+            object_id.clone(),
+            *initial_shared_version,
+            None,
+            Some(SharedInputHint {
+                initial_shared_version: *initial_shared_version,
+                mutable: *mutable,
+            }),
+        ),
+        _ => return input.clone(),
+    };
+    let version = object_version.unwrap_or(input_version);
+    let digest = object_digest.or(input_digest).unwrap_or_default();
+    match hint {
+        ObjectOwnershipHint::Shared => {
+            let Some(shared) = shared_input.or(input_shared) else {
+                return input.clone();
+            };
+            TransactionInput::SharedObject {
+                object_id,
+                initial_shared_version: shared.initial_shared_version,
+                mutable: shared.mutable,
+            }
+        }
+        ObjectOwnershipHint::Owned => TransactionInput::Object {
+            object_id,
+            version,
+            digest,
+        },
+        ObjectOwnershipHint::Immutable => TransactionInput::ImmutableObject {
+            object_id,
+            version,
+            digest,
+        },
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Expected effects
+// ---------------------------------------------------------------------------
+
+/// On-chain effects supplied by the caller (e.g. from their own indexer) for `compare`.
+///
+/// The comparison diffs against them instead of the effects carried by the fetched or
+/// loaded transaction; execution still reads the transaction's own effects, so
+/// supplied values (shared-object versions included) only change what is compared.
+/// The schema is the one replay-state files use for `transaction.effects`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayExpectedEffects {
+    pub effects: TransactionEffectsSummary,
+}
+
+impl ReplayExpectedEffects {
+    /// Parse and validate supplied effects; object ids must be addresses.
+    ///
+    /// Failures are raised as [`SandboxError::InvalidExpectedEffects`].
+    pub fn from_json(value: &serde_json::Value) -> Result<Self> {
+        Self::parse(value).map_err(|err| {
+            SandboxError::InvalidExpectedEffects {
+                message: format!("{:#}", err),
+            }
+            .into()
+        })
+    }
+
+    fn parse(value: &serde_json::Value) -> Result<Self> {
+        if !value.is_object() {
+            return Err(anyhow!("expected_effects must be an object"));
+        }
+        let effects: TransactionEffectsSummary = serde_json::from_value(value.clone())
+            .context("expected_effects does not match the transaction effects schema")?;
+        let lists = [
+            ("created", &effects.created),
+            ("mutated", &effects.mutated),
+            ("deleted", &effects.deleted),
+            ("wrapped", &effects.wrapped),
+            ("unwrapped", &effects.unwrapped),
+        ];
+        for (field, ids) in lists {
+            for id in ids {
+                AccountAddress::from_hex_literal(id).with_context(|| {
+                    format!("expected_effects.{}: invalid object id {}", field, id)
+                })?;
+            }
+        }
+        for id in effects.shared_object_versions.keys() {
+            AccountAddress::from_hex_literal(id).with_context(|| {
+                format!(
+                    "expected_effects.shared_object_versions: invalid object id {}",
+                    id
+                )
+            })?;
+        }
+        Ok(Self { effects })
+    }
+
+    /// Install the effects on `replay_state` for reporting once the replay has executed,
+    /// returning whether they replaced effects the transaction already carried.
+    pub fn apply(&self, replay_state: &mut ReplayState) -> bool {
+        replay_state
+            .transaction
+            .effects
+            .replace(self.effects.clone())
+            .is_some()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replay clock
+// ---------------------------------------------------------------------------
+
+/// Default the replay clock to the checkpoint timestamp when the source omitted it.
+///
+/// Without `transaction.timestamp_ms` the VM's Clock and `TxContext.epoch_timestamp_ms`
+/// fall back to `DEFAULT_CLOCK_BASE_MS`. A Clock object previously injected with that
+/// default is re-stamped as well. Without `graphql` (offline replays) only a timestamp
+/// the state already carries is used. Returns the timestamp now in effect, if any.
+pub fn backfill_replay_clock_timestamp(
+    replay_state: &mut ReplayState,
+    graphql: Option<&GraphQLClient>,
+) -> Option<u64> {
+    if replay_state.transaction.timestamp_ms.is_none() {
+        if let (Some(checkpoint), Some(graphql)) = (replay_state.checkpoint, graphql) {
+            replay_state.transaction.timestamp_ms = graphql
+                .fetch_checkpoint_timestamp_ms(checkpoint)
+                .ok()
+                .flatten();
+        }
+    }
+    let timestamp_ms = replay_state.transaction.timestamp_ms?;
+    restamp_default_clock(&mut replay_state.objects, timestamp_ms);
+    Some(timestamp_ms)
+}
+
+/// Set the replay's protocol version to the one that was active in its epoch.
+///
+/// Walrus checkpoints do not carry the protocol version (their states assume a recent
+/// one) and JSON-RPC only reports the current one, so the epoch's version is looked up
+/// via GraphQL. Returns the epoch's version, or `None` when it could not be fetched
+/// (the state's version is then kept).
+pub fn backfill_replay_protocol_version(
+    replay_state: &mut ReplayState,
+    graphql: &GraphQLClient,
+) -> Option<u64> {
+    let version = graphql
+        .fetch_epoch_protocol_version(replay_state.epoch)
+        .ok()
+        .flatten()?;
+    replay_state.protocol_version = version;
+    Some(version)
+}
+
+/// Replace a Clock synthesized at `DEFAULT_CLOCK_BASE_MS` with one at `timestamp_ms`.
+///
+/// Hydrated (real) Clock objects are left untouched.
+fn restamp_default_clock(
+    objects: &mut HashMap<AccountAddress, VersionedObject>,
+    timestamp_ms: u64,
+) -> bool {
+    let Some(clock) = objects.get_mut(&CLOCK_OBJECT_ID) else {
+        return false;
+    };
+    if clock.bcs_bytes != synthesize_clock_bytes(&CLOCK_OBJECT_ID, DEFAULT_CLOCK_BASE_MS) {
+        return false;
+    }
+    clock.bcs_bytes = synthesize_clock_bytes(&CLOCK_OBJECT_ID, timestamp_ms);
+    true
+}
+
 // ---------------------------------------------------------------------------
 // Simulation config
 // ---------------------------------------------------------------------------
@@ -292,7 +1493,9 @@ pub fn build_simulation_config(replay_state: &ReplayState) -> SimulationConfig {
         config = config.with_reference_gas_price(rgp);
     }
     if replay_state.protocol_version > 0 {
-        config = config.with_protocol_version(replay_state.protocol_version);
+        config = config
+            .with_protocol_version(replay_state.protocol_version)
+            .with_pinned_protocol_config(true);
     }
     if let Some(ts) = replay_state.transaction.timestamp_ms {
         config = config.with_tx_timestamp(ts);
@@ -315,6 +1518,13 @@ pub struct OfflineReplayExecution {
     pub execution: ReplayExecution,
 }
 
+fn invalid_replay_state(message: impl Into<String>) -> anyhow::Error {
+    SandboxError::InvalidReplayState {
+        message: message.into(),
+    }
+    .into()
+}
+
 /// Select a replay state from parsed state JSON payloads.
 ///
 /// Rules:
@@ -323,20 +1533,21 @@ pub struct OfflineReplayExecution {
 /// - many states + no digest => error
 pub fn select_replay_state(states: Vec<ReplayState>, digest: Option<&str>) -> Result<ReplayState> {
     if states.is_empty() {
-        return Err(anyhow!("replay state file did not contain any states"));
+        return Err(invalid_replay_state(
+            "replay state file did not contain any states",
+        ));
     }
     if states.len() == 1 {
         let state = states
             .into_iter()
             .next()
-            .ok_or_else(|| anyhow!("replay state file did not contain any states"))?;
+            .ok_or_else(|| invalid_replay_state("replay state file did not contain any states"))?;
         if let Some(requested) = digest {
             if !requested.is_empty() && state.transaction.digest.0 != requested {
-                return Err(anyhow!(
+                return Err(invalid_replay_state(format!(
                     "digest '{}' does not match replay state digest '{}'",
-                    requested,
-                    state.transaction.digest.0
-                ));
+                    requested, state.transaction.digest.0
+                )));
             }
         }
         return Ok(state);
@@ -346,32 +1557,31 @@ pub fn select_replay_state(states: Vec<ReplayState>, digest: Option<&str>) -> Re
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .ok_or_else(|| {
-            anyhow!("replay state file contains multiple states; provide a digest selector")
+            invalid_replay_state(
+                "replay state file contains multiple states; provide a digest selector",
+            )
         })?;
 
     states
         .into_iter()
         .find(|state| state.transaction.digest.0 == requested)
-        .ok_or_else(|| anyhow!("replay state file does not contain digest '{}'", requested))
+        .ok_or_else(|| {
+            invalid_replay_state(format!(
+                "replay state file does not contain digest '{}'",
+                requested
+            ))
+        })
 }
 
-/// Replay a transaction fully offline from a replay-state JSON file.
+/// Execute a hydrated `ReplayState` fully offline.
 ///
-/// This helper encapsulates the full orchestration path:
-/// parse state JSON -> hydrate resolver -> patch historical objects -> execute replay.
-pub fn replay_state_json_offline(
-    state_json: &Path,
-    digest: Option<&str>,
+/// Hydrates the resolver from the state's packages, patches historical objects,
+/// and replays the transaction with version tracking.
+pub fn execute_replay_state(
+    replay_state: &ReplayState,
+    policy: EffectsReconcilePolicy,
     verbose: bool,
-) -> Result<OfflineReplayExecution> {
-    let states = parse_replay_states_file(state_json).with_context(|| {
-        format!(
-            "failed to parse replay states from {}",
-            state_json.display()
-        )
-    })?;
-    let replay_state = select_replay_state(states, digest)?;
-
+) -> Result<ReplayExecution> {
     let mut linkage_upgrades: HashMap<AccountAddress, AccountAddress> = HashMap::new();
     for package in replay_state.packages.values() {
         for (original, upgraded) in &package.linkage {
@@ -381,8 +1591,8 @@ pub fn replay_state_json_offline(
         }
     }
 
-    let aliases = build_address_aliases(&replay_state);
-    let resolver = hydrate_resolver_from_replay_state(&replay_state, &linkage_upgrades, &aliases)?;
+    let aliases = build_address_aliases(replay_state);
+    let resolver = hydrate_resolver_from_replay_state(replay_state, &linkage_upgrades, &aliases)?;
 
     let package_versions: HashMap<AccountAddress, u64> = replay_state
         .packages
@@ -390,27 +1600,50 @@ pub fn replay_state_json_offline(
         .map(|(id, package)| (*id, package.version))
         .collect();
 
-    let mut object_maps = build_replay_object_maps(&replay_state, &package_versions);
+    let mut object_maps = build_replay_object_maps(replay_state, &package_versions);
     maybe_patch_replay_objects(
         &resolver,
-        &replay_state,
+        replay_state,
         &package_versions,
         &aliases,
         &mut object_maps,
         verbose,
     );
 
-    let config = build_simulation_config(&replay_state);
-    let mut harness = VMHarness::with_config(&resolver, false, config)
-        .context("failed to create VM harness for replay")?;
+    let config = build_simulation_config(replay_state);
+    let mut harness =
+        VMHarness::with_config(&resolver, false, config).context(SandboxError::VmSetupFailed)?;
 
-    let execution = replay_with_version_tracking_with_policy_with_effects(
+    replay_with_version_tracking_with_policy_with_effects(
         &replay_state.transaction,
         &mut harness,
         &object_maps.cached_objects,
         &aliases,
         Some(&object_maps.version_map),
+        policy,
+    )
+}
+
+/// Replay a transaction fully offline from a replay-state JSON file.
+///
+/// This helper encapsulates the full orchestration path:
+/// parse state JSON -> hydrate resolver -> patch historical objects -> execute replay.
+pub fn replay_state_json_offline(
+    state_json: &Path,
+    digest: Option<&str>,
+    verbose: bool,
+) -> Result<OfflineReplayExecution> {
+    let states = parse_replay_states_file(state_json).with_context(|| {
+        format!(
+            "failed to parse replay states from {}",
+            state_json.display()
+        )
+    })?;
+    let replay_state = select_replay_state(states, digest)?;
+    let execution = execute_replay_state(
+        &replay_state,
         EffectsReconcilePolicy::DynamicFields,
+        verbose,
     )?;
 
     Ok(OfflineReplayExecution {
@@ -418,3 +1651,630 @@ pub fn replay_state_json_offline(
         execution,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock_object(timestamp_ms: u64) -> VersionedObject {
+        VersionedObject {
+            id: CLOCK_OBJECT_ID,
+            version: 1,
+            digest: None,
+            type_tag: Some("0x2::clock::Clock".to_string()),
+            bcs_bytes: synthesize_clock_bytes(&CLOCK_OBJECT_ID, timestamp_ms),
+            is_shared: true,
+            is_immutable: false,
+        }
+    }
+
+    #[test]
+    fn restamp_default_clock_only_touches_synthesized_default() {
+        let mut objects = HashMap::new();
+        objects.insert(CLOCK_OBJECT_ID, clock_object(DEFAULT_CLOCK_BASE_MS));
+        assert!(restamp_default_clock(&mut objects, 1_760_000_000_123));
+        assert_eq!(
+            objects[&CLOCK_OBJECT_ID].bcs_bytes,
+            synthesize_clock_bytes(&CLOCK_OBJECT_ID, 1_760_000_000_123)
+        );
+
+        let mut hydrated = HashMap::new();
+        hydrated.insert(CLOCK_OBJECT_ID, clock_object(1_700_000_000_000));
+        assert!(!restamp_default_clock(&mut hydrated, 1_760_000_000_123));
+        assert_eq!(
+            hydrated[&CLOCK_OBJECT_ID].bcs_bytes,
+            synthesize_clock_bytes(&CLOCK_OBJECT_ID, 1_700_000_000_000)
+        );
+    }
+
+    #[test]
+    fn expected_effects_validate_shape_and_ids() {
+        let effects = serde_json::json!({
+            "status": "Success",
+            "created": ["0x5"],
+            "mutated": ["0x6"],
+            "deleted": [],
+            "wrapped": [],
+            "unwrapped": [],
+            "gas_used": {
+                "computation_cost": 1000,
+                "storage_cost": 2000,
+                "storage_rebate": 500,
+                "non_refundable_storage_fee": 5
+            },
+            "events_count": 1
+        });
+        let expected = ReplayExpectedEffects::from_json(&effects).unwrap();
+        assert_eq!(expected.effects.created, vec!["0x5".to_string()]);
+        assert_eq!(expected.effects.gas_used.storage_cost, 2000);
+
+        assert!(ReplayExpectedEffects::from_json(&serde_json::json!([])).is_err());
+        let mut missing_status = effects.clone();
+        missing_status.as_object_mut().unwrap().remove("status");
+        assert!(ReplayExpectedEffects::from_json(&missing_status).is_err());
+        let mut bad_id = effects;
+        bad_id["mutated"] = serde_json::json!(["not-an-id"]);
+        let err = ReplayExpectedEffects::from_json(&bad_id).unwrap_err();
+        assert!(format!("{:#}", err).contains("expected_effects.mutated"));
+        assert_eq!(
+            SandboxError::find(&err).map(SandboxError::code),
+            Some("invalid_expected_effects")
+        );
+    }
+
+    fn maps_with(objects: &[(&str, &str)]) -> ReplayObjectMaps {
+        let mut maps = ReplayObjectMaps {
+            versions_str: HashMap::new(),
+            cached_objects: HashMap::new(),
+            version_map: HashMap::new(),
+            object_bytes: HashMap::new(),
+            object_types: HashMap::new(),
+            digest_mismatches: vec![],
+            unverified_digests: vec![],
+        };
+        for (id, object_type) in objects {
+            maps.cached_objects
+                .insert(id.to_string(), "AQ==".to_string());
+            maps.version_map.insert(id.to_string(), 1);
+            maps.object_bytes.insert(id.to_string(), vec![1]);
+            maps.object_types
+                .insert(id.to_string(), object_type.to_string());
+        }
+        maps
+    }
+
+    #[test]
+    fn test_type_overrides_drop_and_replace() {
+        let mut maps = maps_with(&[
+            ("0xa", "0xabc::oracle::Price"),
+            ("0xb", "0x2::coin::Coin<0x2::sui::SUI>"),
+            ("0xc", "0x2::clock::Clock"),
+        ]);
+        let overrides = ReplayObjectTypeOverrides {
+            rules: BTreeMap::from([
+                ("0x0abc::oracle::Price".to_string(), None),
+                ("0x2::coin::Coin".to_string(), Some(vec![9, 9])),
+            ]),
+        };
+        let report = overrides.apply(&mut maps, &HashMap::new());
+        assert_eq!(report.dropped, vec!["0xa".to_string()]);
+        assert_eq!(report.replaced, vec!["0xb".to_string()]);
+        assert!(!maps.cached_objects.contains_key("0xa"));
+        assert!(!maps.version_map.contains_key("0xa"));
+        assert_eq!(maps.object_bytes["0xb"], vec![9, 9]);
+        assert_eq!(maps.cached_objects["0xc"], "AQ==");
+    }
+
+    #[test]
+    fn test_type_overrides_prefer_exact_instantiation() {
+        let overrides = ReplayObjectTypeOverrides {
+            rules: BTreeMap::from([
+                ("0x2::coin::Coin".to_string(), None),
+                ("0x2::coin::Coin<0x2::sui::SUI>".to_string(), Some(vec![1])),
+                ("0x02::coin::Coin".to_string(), Some(vec![2])),
+            ]),
+        };
+        let aliases = HashMap::new();
+        assert_eq!(
+            overrides.rule_for("0x2::coin::Coin<0x2::sui::SUI>", &aliases),
+            Some(&Some(vec![1]))
+        );
+        // Both base rules normalize alike; the first key in sorted order wins.
+        assert_eq!(
+            overrides.rule_for("0x2::coin::Coin<0xabc::usdc::USDC>", &aliases),
+            Some(&Some(vec![2]))
+        );
+    }
+
+    fn framework_package(
+        address: &str,
+        names: &[&str],
+    ) -> (AccountAddress, Vec<(String, Vec<u8>)>) {
+        let address = AccountAddress::from_hex_literal(address).unwrap();
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let modules = resolver
+            .iter_modules()
+            .filter(|m| *m.self_id().address() == address)
+            .filter(|m| names.contains(&m.self_id().name().as_str()))
+            .map(|m| {
+                let mut bytes = Vec::new();
+                m.serialize_with_version(m.version, &mut bytes).unwrap();
+                (m.self_id().name().to_string(), bytes)
+            })
+            .collect();
+        (address, modules)
+    }
+
+    fn module_with_deps(
+        address: AccountAddress,
+        name: &str,
+        deps: &[(AccountAddress, &str)],
+    ) -> (String, Vec<u8>) {
+        use move_binary_format::file_format::{
+            empty_module, AddressIdentifierIndex, IdentifierIndex, ModuleHandle,
+        };
+        use move_core_types::identifier::Identifier;
+        let mut module = empty_module();
+        module.identifiers[0] = Identifier::new(name).unwrap();
+        module.address_identifiers[0] = address;
+        for (dep_address, dep_name) in deps {
+            module.address_identifiers.push(*dep_address);
+            module.identifiers.push(Identifier::new(*dep_name).unwrap());
+            module.module_handles.push(ModuleHandle {
+                address: AddressIdentifierIndex((module.address_identifiers.len() - 1) as u16),
+                name: IdentifierIndex((module.identifiers.len() - 1) as u16),
+            });
+        }
+        let mut bytes = Vec::new();
+        module
+            .serialize_with_version(module.version, &mut bytes)
+            .unwrap();
+        (name.to_string(), bytes)
+    }
+
+    #[test]
+    fn test_dependency_closure_serves_cached_packages_without_fetching() {
+        let (app, lib) = (
+            AccountAddress::from_hex_literal("0xaa").unwrap(),
+            AccountAddress::from_hex_literal("0xbb").unwrap(),
+        );
+        let cache = VersionedCache::new();
+        cache.put_package(PackageData {
+            address: lib,
+            version: 1,
+            modules: vec![module_with_deps(lib, "lib", &[])],
+            linkage: HashMap::new(),
+            original_id: None,
+        });
+        // Nothing listens here, so the dependency can only come from the cache.
+        let graphql = GraphQLClient::new("http://127.0.0.1:9/graphql");
+
+        let mut resolver = LocalModuleResolver::new();
+        resolver
+            .add_package_modules_at(
+                vec![module_with_deps(app, "app", &[(lib, "lib")])],
+                Some(app),
+            )
+            .unwrap();
+
+        let fetched =
+            fetch_dependency_closure_cached(&mut resolver, &graphql, None, Some(&cache), false)
+                .unwrap();
+        assert_eq!(fetched, 0);
+        assert!(resolver.has_package(&lib));
+        assert!(resolver.get_missing_dependencies().is_empty());
+    }
+
+    #[test]
+    fn test_shared_resolver_reuses_framework_packages_and_modules_across_replays() {
+        let (app, lib) = (
+            AccountAddress::from_hex_literal("0xaa").unwrap(),
+            AccountAddress::from_hex_literal("0xbb").unwrap(),
+        );
+        let lib_package = PackageData {
+            address: lib,
+            version: 1,
+            modules: vec![module_with_deps(lib, "lib", &[])],
+            linkage: HashMap::new(),
+            original_id: None,
+        };
+        let shared = SharedReplayResolver::new(Arc::new(VersionedCache::new()));
+        shared.package_cache().put_package(lib_package.clone());
+        shared
+            .modules
+            .write()
+            .insert((lib, "lib".to_string()), lib_package);
+        // Nothing listens here, so dependencies can only come from the shared state.
+        let graphql = GraphQLClient::new("http://127.0.0.1:9/graphql");
+        let state = replay_state_with(vec![PackageData {
+            address: app,
+            version: 1,
+            modules: vec![module_with_deps(app, "app", &[(lib, "lib")])],
+            linkage: HashMap::new(),
+            original_id: None,
+        }]);
+        let no_aliases = HashMap::new();
+
+        let mut first = shared.hydrate(&state, &no_aliases, &no_aliases).unwrap();
+        let fetched = shared
+            .fetch_dependency_closure(&mut first, &graphql, None, false)
+            .unwrap();
+        assert_eq!(fetched, 0);
+        assert!(first.has_package(&lib));
+
+        let mut second = shared.hydrate(&state, &no_aliases, &no_aliases).unwrap();
+        assert!(
+            !second.has_package(&lib),
+            "replays start from the framework"
+        );
+        let roots = [ModuleId::new(app, Identifier::new("app").unwrap())];
+        shared
+            .fetch_module_closure(&mut second, &graphql, &roots, None, false)
+            .unwrap();
+        assert!(second.get_module_by_addr_name(&lib, "lib").is_some());
+
+        assert_eq!(
+            shared.stats(),
+            SharedResolverStats {
+                framework_versions: 1,
+                packages: 1,
+                modules: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_module_closure_fetches_only_referenced_modules_at_linked_storage_ids() {
+        let app = AccountAddress::from_hex_literal("0xa1").unwrap();
+        let lib = AccountAddress::from_hex_literal("0xb1").unwrap();
+        let lib_v2 = AccountAddress::from_hex_literal("0xb2").unwrap();
+        let package = |address, module, linkage: &[(AccountAddress, AccountAddress)]| PackageData {
+            address,
+            version: 1,
+            modules: vec![module],
+            linkage: linkage.iter().copied().collect(),
+            original_id: None,
+        };
+
+        // app::entry -> app::math -> lib::pool; app links the upgraded lib at 0xb2.
+        // app::unused and lib::other are never referenced and must not be fetched.
+        let mut requested = Vec::new();
+        let mut resolver = LocalModuleResolver::new();
+        let roots = [ModuleId::new(app, Identifier::new("entry").unwrap())];
+        let fetched = fetch_module_closure_with(&mut resolver, &roots, |storage, name| {
+            requested.push((storage, name.to_string()));
+            Ok(match (storage, name) {
+                (s, "entry") if s == app => Some(package(
+                    app,
+                    module_with_deps(app, "entry", &[(app, "math")]),
+                    &[(lib, lib_v2)],
+                )),
+                (s, "math") if s == app => Some(package(
+                    app,
+                    module_with_deps(app, "math", &[(lib, "pool")]),
+                    &[(lib, lib_v2)],
+                )),
+                (s, "pool") if s == lib_v2 => {
+                    Some(package(lib_v2, module_with_deps(lib, "pool", &[]), &[]))
+                }
+                _ => None,
+            })
+        })
+        .unwrap();
+
+        assert_eq!(fetched, 3);
+        assert_eq!(
+            requested,
+            vec![
+                (app, "entry".to_string()),
+                (app, "math".to_string()),
+                (lib_v2, "pool".to_string()),
+            ]
+        );
+        assert!(resolver.get_module_by_addr_name(&lib, "pool").is_some());
+        assert!(resolver.get_module_by_addr_name(&app, "unused").is_none());
+        assert_eq!(resolver.get_per_package_linkage(&app, &lib), Some(lib_v2));
+        assert_eq!(resolver.get_alias(&lib_v2), Some(lib));
+    }
+
+    #[test]
+    fn test_module_closure_skips_loaded_and_unserved_modules() {
+        let app = AccountAddress::from_hex_literal("0xa1").unwrap();
+        let lib = AccountAddress::from_hex_literal("0xb1").unwrap();
+        let mut resolver = LocalModuleResolver::new();
+        resolver
+            .add_package_modules(vec![module_with_deps(app, "entry", &[(lib, "pool")])])
+            .unwrap();
+
+        // The root is already loaded, so only its missing dependency is requested,
+        // once, and a module the transport cannot serve is left missing.
+        let mut requested = Vec::new();
+        let entry = ModuleId::new(app, Identifier::new("entry").unwrap());
+        let roots = [entry.clone(), entry];
+        let fetched = fetch_module_closure_with(&mut resolver, &roots, |storage, name| {
+            requested.push((storage, name.to_string()));
+            Ok(None)
+        })
+        .unwrap();
+        assert_eq!(fetched, 0);
+        assert_eq!(requested, vec![(lib, "pool".to_string())]);
+
+        let err =
+            fetch_module_closure_with(&mut resolver, &roots, |_, _| Err(anyhow!("transport down")))
+                .unwrap_err();
+        assert!(err.to_string().contains("transport down"));
+    }
+
+    #[test]
+    fn test_transaction_module_roots_cover_call_targets_and_type_arguments() {
+        let call = |package: &str, module: &str, type_arguments: &[&str]| PtbCommand::MoveCall {
+            package: package.to_string(),
+            module: module.to_string(),
+            function: "f".to_string(),
+            type_arguments: type_arguments.iter().map(|t| t.to_string()).collect(),
+            arguments: vec![],
+        };
+        let roots = transaction_module_roots(&[
+            call(
+                "0xa1",
+                "pool",
+                &["0xb1::coin_a::A", "vector<0x2::sui::SUI>"],
+            ),
+            call("0xa1", "pool", &[]),
+            PtbCommand::MergeCoins {
+                destination: sui_sandbox_types::PtbArgument::GasCoin,
+                sources: vec![],
+            },
+        ]);
+        let roots: Vec<String> = roots
+            .iter()
+            .map(|id| format!("{}::{}", id.address().short_str_lossless(), id.name()))
+            .collect();
+        assert_eq!(roots, vec!["2::sui", "a1::pool", "b1::coin_a"]);
+    }
+
+    fn replay_state_with(packages: Vec<PackageData>) -> ReplayState {
+        ReplayState {
+            transaction: sui_sandbox_types::FetchedTransaction {
+                digest: sui_sandbox_types::TransactionDigest::new("override"),
+                sender: AccountAddress::ZERO,
+                gas_budget: 0,
+                gas_price: 0,
+                commands: vec![],
+                inputs: vec![],
+                effects: None,
+                timestamp_ms: None,
+                checkpoint: None,
+                gas_payment: vec![],
+            },
+            objects: HashMap::new(),
+            packages: packages.into_iter().map(|p| (p.address, p)).collect(),
+            protocol_version: 0,
+            epoch: 0,
+            reference_gas_price: None,
+            checkpoint: None,
+        }
+    }
+
+    #[test]
+    fn test_simulation_config_pins_known_protocol_version() {
+        let mut state = replay_state_with(vec![]);
+        let config = build_simulation_config(&state);
+        assert!(!config.pin_protocol_config);
+        assert_eq!(
+            config.protocol_version,
+            crate::gas::DEFAULT_PROTOCOL_VERSION
+        );
+
+        state.protocol_version = 62;
+        let config = build_simulation_config(&state);
+        assert!(config.pin_protocol_config);
+        assert_eq!(config.protocol_version, 62);
+    }
+
+    #[test]
+    fn test_object_maps_flag_digest_mismatches() {
+        let owned = AccountAddress::from_hex_literal("0xa").unwrap();
+        let stale = AccountAddress::from_hex_literal("0xb").unwrap();
+        let mut state = replay_state_with(vec![]);
+        state.transaction.inputs = vec![
+            TransactionInput::Object {
+                object_id: "0xa".to_string(),
+                version: 3,
+                digest: "good".to_string(),
+            },
+            TransactionInput::Receiving {
+                object_id: "0xb".to_string(),
+                version: 5,
+                digest: "expected".to_string(),
+            },
+        ];
+        for (id, version, digest) in [(owned, 3, "good"), (stale, 5, "tampered")] {
+            state.objects.insert(
+                id,
+                VersionedObject {
+                    id,
+                    version,
+                    digest: Some(digest.to_string()),
+                    type_tag: None,
+                    bcs_bytes: vec![],
+                    is_shared: false,
+                    is_immutable: false,
+                },
+            );
+        }
+
+        let maps = build_replay_object_maps(&state, &HashMap::new());
+        assert_eq!(maps.digest_mismatches.len(), 1);
+        assert_eq!(maps.digest_mismatches[0].object_id, stale.to_hex_literal());
+        assert_eq!(maps.digest_mismatches[0].actual, "tampered");
+        let err = maps.ensure_object_digests_match().unwrap_err().to_string();
+        assert!(err.contains("references digest expected"), "{err}");
+
+        state.objects.get_mut(&stale).unwrap().digest = None;
+        let maps = build_replay_object_maps(&state, &HashMap::new());
+        assert!(maps.ensure_object_digests_match().is_ok());
+        assert_eq!(
+            maps.unverified_digests,
+            vec![format!("{} v5", stale.to_hex_literal())]
+        );
+    }
+
+    #[test]
+    fn test_package_overrides_swap_bytecode_and_keep_linkage() {
+        let (sui, historical) = framework_package("0x2", &["balance", "coin"]);
+        let (_, patched) = framework_package("0x2", &["coin"]);
+        let linkage = HashMap::from([(AccountAddress::ONE, AccountAddress::ONE)]);
+        let mut state = replay_state_with(vec![PackageData {
+            address: sui,
+            version: 7,
+            modules: historical,
+            linkage: linkage.clone(),
+            original_id: None,
+        }]);
+
+        let overrides = ReplayPackageOverrides {
+            packages: HashMap::from([(
+                sui,
+                patched.iter().map(|(_, bytes)| bytes.clone()).collect(),
+            )]),
+        };
+        let report = overrides.apply(&mut state).unwrap();
+        assert_eq!(report.len(), 1);
+        assert!(report[0].replaced);
+        assert_eq!(report[0].modules, vec!["coin".to_string()]);
+        assert_eq!(report[0].removed_modules, vec!["balance".to_string()]);
+        let pkg = &state.packages[&sui];
+        assert_eq!((pkg.version, &pkg.linkage), (7, &linkage));
+        assert_eq!(pkg.modules, patched);
+    }
+
+    #[test]
+    fn test_package_overrides_reject_wrong_runtime_address() {
+        let (sui, historical) = framework_package("0x2", &["coin"]);
+        let (_, stdlib) = framework_package("0x1", &["option"]);
+        let mut state = replay_state_with(vec![PackageData {
+            address: sui,
+            version: 1,
+            modules: historical,
+            linkage: HashMap::new(),
+            original_id: None,
+        }]);
+        let overrides = ReplayPackageOverrides {
+            packages: HashMap::from([(sui, vec![stdlib[0].1.clone()])]),
+        };
+        let err = overrides.apply(&mut state).unwrap_err().to_string();
+        assert!(err.contains("runtime id"), "{err}");
+    }
+
+    #[test]
+    fn test_ownership_hints_rewrite_inputs_and_report_contradictions() {
+        let pool = AccountAddress::from_hex_literal("0xa").unwrap();
+        let coin = AccountAddress::from_hex_literal("0xb").unwrap();
+        let mut state = replay_state_with(vec![]);
+        state.transaction.inputs = vec![
+            TransactionInput::SharedObject {
+                object_id: "0xa".to_string(),
+                initial_shared_version: 4,
+                mutable: true,
+            },
+            TransactionInput::Object {
+                object_id: "0x0b".to_string(),
+                version: 9,
+                digest: "coin".to_string(),
+            },
+        ];
+        for (id, version, is_shared) in [(pool, 12, true), (coin, 9, false)] {
+            state.objects.insert(
+                id,
+                VersionedObject {
+                    id,
+                    version,
+                    digest: Some(format!("d{version}")),
+                    type_tag: None,
+                    bcs_bytes: vec![],
+                    is_shared,
+                    is_immutable: false,
+                },
+            );
+        }
+
+        let hints = ReplayOwnershipHints {
+            hints: HashMap::from([
+                (pool, ObjectOwnershipHint::Owned),
+                (coin, ObjectOwnershipHint::Owned),
+            ]),
+            ..Default::default()
+        };
+        let report = hints.apply(&mut state).unwrap();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].object_id, pool.to_hex_literal());
+        assert_eq!(report[0].hydrated, Some(ObjectOwnershipHint::Shared));
+        assert_eq!(report[0].input, Some(ObjectOwnershipHint::Shared));
+        assert!(report[0].contradicted);
+        assert!(!report[1].contradicted);
+
+        match &state.transaction.inputs[0] {
+            TransactionInput::Object {
+                version, digest, ..
+            } => assert_eq!((*version, digest.as_str()), (12, "d12")),
+            other => panic!("expected an owned input, got {other:?}"),
+        }
+        assert!(!state.objects[&pool].is_shared);
+        assert!(matches!(
+            state.transaction.inputs[1],
+            TransactionInput::Object { version: 9, .. }
+        ));
+        assert!("frozen".parse::<ObjectOwnershipHint>().is_err());
+        assert_eq!(
+            " Shared ".parse::<ObjectOwnershipHint>().unwrap(),
+            ObjectOwnershipHint::Shared
+        );
+    }
+
+    #[test]
+    fn test_shared_ownership_hint_requires_shared_input_fields() {
+        let coin = AccountAddress::from_hex_literal("0xb").unwrap();
+        let mut state = replay_state_with(vec![]);
+        state.transaction.inputs = vec![TransactionInput::Object {
+            object_id: "0xb".to_string(),
+            version: 9,
+            digest: "coin".to_string(),
+        }];
+
+        let mut hints = ReplayOwnershipHints::default();
+        hints.insert_spec(coin, "shared").unwrap();
+        let err = hints.apply(&mut state.clone()).unwrap_err();
+        assert!(err.to_string().contains("initial shared version"));
+
+        hints.insert_spec(coin, "shared:3:readonly").unwrap();
+        let report = hints.apply(&mut state).unwrap();
+        assert_eq!(
+            report[0].shared_input,
+            Some(SharedInputHint {
+                initial_shared_version: 3,
+                mutable: false,
+            })
+        );
+        assert!(matches!(
+            state.transaction.inputs[0],
+            TransactionInput::SharedObject {
+                initial_shared_version: 3,
+                mutable: false,
+                ..
+            }
+        ));
+
+        assert!(hints.insert_spec(coin, "shared:3").is_err());
+        assert!(hints.insert_spec(coin, "owned:3:mutable").is_err());
+        assert!(hints.insert_spec(coin, "shared:x:mutable").is_err());
+    }
+
+    #[test]
+    fn test_empty_overrides_leave_maps_untouched() {
+        let mut maps = maps_with(&[("0xa", "0x2::clock::Clock")]);
+        let report = ReplayObjectTypeOverrides::default().apply(&mut maps, &HashMap::new());
+        assert!(report.dropped.is_empty() && report.replaced.is_empty());
+        assert_eq!(maps.cached_objects.len(), 1);
+    }
+}
@@ -65,7 +65,7 @@ use move_core_types::language_storage::ModuleId;
 use move_core_types::resolver::ModuleResolver;
 use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use sui_transport::decode_graphql_modules;
 use tracing::{debug, info, warn};
 
@@ -164,6 +164,68 @@ pub struct LocalModuleResolver {
     package_runtime_ids: std::collections::HashMap<AccountAddress, AccountAddress>,
 }
 
+/// Environment variable overriding the historical framework snapshot directory.
+pub const FRAMEWORK_SNAPSHOT_DIR_ENV: &str = "SUI_FRAMEWORK_SNAPSHOT_DIR";
+
+/// Per-package files inside a snapshot directory (same BCS `Vec<Vec<u8>>`
+/// format as the bundled `framework_bytecode/` files).
+const FRAMEWORK_SNAPSHOT_FILES: [&str; 3] = ["move-stdlib", "sui-framework", "sui-system"];
+
+/// Root directory of historical framework snapshots.
+///
+/// Layout: `<root>/<protocol_version>/{move-stdlib,sui-framework,sui-system}`.
+/// Defaults to `~/.sui-sandbox/framework` unless `SUI_FRAMEWORK_SNAPSHOT_DIR` is set.
+pub fn framework_snapshot_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(FRAMEWORK_SNAPSHOT_DIR_ENV) {
+        if !dir.trim().is_empty() {
+            return Some(PathBuf::from(dir));
+        }
+    }
+    dirs::home_dir().map(|home| home.join(".sui-sandbox").join("framework"))
+}
+
+/// Protocol versions with a complete snapshot under `root`, sorted ascending.
+pub fn list_framework_snapshots(root: &Path) -> Vec<u64> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<u64> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let version = entry.file_name().to_str()?.parse::<u64>().ok()?;
+            let path = entry.path();
+            FRAMEWORK_SNAPSHOT_FILES
+                .iter()
+                .all(|name| path.join(name).is_file())
+                .then_some(version)
+        })
+        .collect();
+    versions.sort_unstable();
+    versions
+}
+
+/// Protocol version the bundled `framework_bytecode/` corresponds to.
+pub const BUNDLED_FRAMEWORK_PROTOCOL_VERSION: u64 = crate::gas::DEFAULT_PROTOCOL_VERSION;
+
+/// Pick the newest snapshot at or below `protocol_version`, unless the bundled
+/// framework (at `bundled`) is at least as close; `None` means use the bundled one.
+///
+/// The framework only changes at protocol upgrades, so the closest earlier
+/// framework carries the bytecode that was live at `protocol_version`. A snapshot
+/// older than the bundled framework is only picked for protocol versions before it.
+fn select_framework_snapshot(
+    available: &[u64],
+    protocol_version: u64,
+    bundled: u64,
+) -> Option<u64> {
+    let snapshot = available
+        .iter()
+        .copied()
+        .filter(|v| *v <= protocol_version)
+        .max()?;
+    (snapshot > bundled || protocol_version < bundled).then_some(snapshot)
+}
+
 impl Default for LocalModuleResolver {
     fn default() -> Self {
         Self::new()
@@ -193,6 +255,18 @@ impl LocalModuleResolver {
         Ok(resolver)
     }
 
+    /// Create a new resolver with the framework matching `protocol_version`.
+    ///
+    /// Uses the closest historical snapshot at or below `protocol_version` from
+    /// [`framework_snapshot_dir`], falling back to the bundled framework when no
+    /// snapshot applies, the bundled framework is at least as close (see
+    /// [`BUNDLED_FRAMEWORK_PROTOCOL_VERSION`]), or `protocol_version` is 0 (unknown).
+    pub fn with_sui_framework_version(protocol_version: u64) -> Result<Self> {
+        let mut resolver = Self::new();
+        resolver.load_sui_framework_version(protocol_version)?;
+        Ok(resolver)
+    }
+
     /// Load Sui framework modules from bundled bytecode files.
     ///
     /// The framework bytecode is bundled at compile time from `framework_bytecode/` directory.
@@ -211,14 +285,59 @@ impl LocalModuleResolver {
         static SUI_FRAMEWORK: &[u8] = include_bytes!("../../../framework_bytecode/sui-framework");
         static SUI_SYSTEM: &[u8] = include_bytes!("../../../framework_bytecode/sui-system");
 
-        let mut count = 0;
-
-        // Load each package's modules
-        for (pkg_addr, package_bytes) in [
+        self.load_framework_package_blobs([
             ("0x1 (Move stdlib)", MOVE_STDLIB),
             ("0x2 (Sui framework)", SUI_FRAMEWORK),
             ("0x3 (Sui system)", SUI_SYSTEM),
-        ] {
+        ])
+    }
+
+    /// Load the framework snapshot matching `protocol_version` (see
+    /// [`Self::with_sui_framework_version`]).
+    pub fn load_sui_framework_version(&mut self, protocol_version: u64) -> Result<usize> {
+        if protocol_version > 0 {
+            if let Some(dir) = framework_snapshot_dir() {
+                let available = list_framework_snapshots(&dir);
+                if let Some(snapshot) = select_framework_snapshot(
+                    &available,
+                    protocol_version,
+                    BUNDLED_FRAMEWORK_PROTOCOL_VERSION,
+                ) {
+                    let snapshot_dir = dir.join(snapshot.to_string());
+                    let blobs = FRAMEWORK_SNAPSHOT_FILES
+                        .iter()
+                        .map(|name| {
+                            let path = snapshot_dir.join(name);
+                            fs::read(&path).with_context(|| {
+                                format!("Failed to read framework snapshot {}", path.display())
+                            })
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let count = self.load_framework_package_blobs([
+                        ("0x1 (Move stdlib)", blobs[0].as_slice()),
+                        ("0x2 (Sui framework)", blobs[1].as_slice()),
+                        ("0x3 (Sui system)", blobs[2].as_slice()),
+                    ])?;
+                    debug!(
+                        protocol_version,
+                        snapshot, count, "loaded historical framework snapshot"
+                    );
+                    return Ok(count);
+                }
+            }
+            debug!(
+                protocol_version,
+                "no framework snapshot for protocol version, using bundled framework"
+            );
+        }
+        self.load_sui_framework()
+    }
+
+    fn load_framework_package_blobs(&mut self, packages: [(&str, &[u8]); 3]) -> Result<usize> {
+        let mut count = 0;
+
+        // Load each package's modules
+        for (pkg_addr, package_bytes) in packages {
             let module_bytes_list: Vec<Vec<u8>> = bcs::from_bytes(package_bytes).map_err(|e| {
                 anyhow!("Failed to deserialize embedded package {}: {}", pkg_addr, e)
             })?;
@@ -439,6 +558,19 @@ impl LocalModuleResolver {
         (total, callable)
     }
 
+    /// Clone this resolver with its own, empty function cache.
+    ///
+    /// A plain `clone` shares the function cache, which is only sound while both
+    /// copies load the same packages.
+    pub fn fork(&self) -> Self {
+        Self {
+            function_cache: std::sync::Arc::new(parking_lot::RwLock::new(
+                std::collections::HashMap::new(),
+            )),
+            ..self.clone()
+        }
+    }
+
     /// Dynamically add multiple modules (e.g., from a package).
     /// Returns the number of modules successfully loaded.
     /// Add package modules and return (module_count, package_address).
@@ -1387,6 +1519,28 @@ impl LocalModuleResolver {
         }
     }
 
+    /// Get a function's declared return types as type strings.
+    ///
+    /// Type parameters render as `T0`, `T1`, ... (uninstantiated). Returns `None` if the
+    /// module or function is not loaded.
+    pub fn get_function_return_types(
+        &self,
+        package_addr: &AccountAddress,
+        module_name: &str,
+        function_name: &str,
+    ) -> Option<Vec<String>> {
+        let id = ModuleId::new(*package_addr, Identifier::new(module_name).ok()?);
+        let module = self.get_module_with_alias(&id)?;
+        let signature = self.get_function_signature(package_addr, module_name, function_name)?;
+        Some(
+            signature
+                .return_types
+                .iter()
+                .map(|token| format_signature_token(module, token))
+                .collect(),
+        )
+    }
+
     /// Check if a function is callable from a PTB (i.e., is public or entry).
     ///
     /// Returns `Ok(())` if the function is callable, or an error describing why not.
@@ -2288,3 +2442,50 @@ impl ModuleResolver for LocalModuleResolver {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_framework_snapshot_picks_closest_not_after() {
+        let available = [40, 60, 80];
+        assert_eq!(select_framework_snapshot(&available, 65, 73), Some(60));
+        assert_eq!(select_framework_snapshot(&available, 80, 73), Some(80));
+        assert_eq!(select_framework_snapshot(&available, 200, 73), Some(80));
+        assert_eq!(select_framework_snapshot(&available, 10, 73), None);
+    }
+
+    #[test]
+    fn test_select_framework_snapshot_never_downgrades_bundled() {
+        let available = [40, 60, 80];
+        // The bundled framework (73) is newer than snapshot 60 and not after 75.
+        assert_eq!(select_framework_snapshot(&available, 75, 73), None);
+        assert_eq!(select_framework_snapshot(&available, 73, 73), None);
+        assert_eq!(select_framework_snapshot(&[40, 60], 200, 73), None);
+        assert_eq!(select_framework_snapshot(&[73], 75, 73), None);
+    }
+
+    #[test]
+    fn test_list_framework_snapshots_requires_all_packages() {
+        let root = tempfile::tempdir().unwrap();
+        for (version, files) in [("50", 3), ("70", 2), ("latest", 3)] {
+            let dir = root.path().join(version);
+            fs::create_dir_all(&dir).unwrap();
+            for name in FRAMEWORK_SNAPSHOT_FILES.iter().take(files) {
+                fs::write(dir.join(name), b"").unwrap();
+            }
+        }
+        assert_eq!(list_framework_snapshots(root.path()), vec![50]);
+    }
+
+    #[test]
+    fn test_unknown_protocol_version_uses_bundled_framework() {
+        let bundled = LocalModuleResolver::with_sui_framework().unwrap();
+        let versioned = LocalModuleResolver::with_sui_framework_version(0).unwrap();
+        assert_eq!(
+            bundled.iter_modules().count(),
+            versioned.iter_modules().count()
+        );
+    }
+}
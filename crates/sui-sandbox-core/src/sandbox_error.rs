@@ -0,0 +1,390 @@
+//! Structured errors for replay paths.
+//!
+//! Replay code returns `anyhow::Result`. Failures callers need to tell apart are
+//! raised as a [`SandboxError`], which survives `.context(..)` and can be recovered
+//! with [`SandboxError::find`]. [`SandboxError::classify`] maps *any* replay error —
+//! including transport failures and VM error strings — onto the taxonomy, so CLI and
+//! Python output can report a stable `category` and `code` next to the message.
+//!
+//! | Category | Codes |
+//! |----------|-------|
+//! | hydration | `missing_object`, `object_digest_mismatch`, `invalid_replay_state` |
+//! | resolution | `invalid_identifier`, `package_load_failed`, `linker_error` |
+//! | execution | `vm_setup_failed`, `move_abort`, `out_of_gas`, `execution_failed` |
+//! | reconciliation | `invalid_expected_effects`, `comparison_unavailable` |
+//! | transport | `network`, plus [`TransportError`] codes |
+//!
+//! ```ignore
+//! match replay_state_json_offline(path, None, false) {
+//!     Err(err) => {
+//!         let error = SandboxError::classify(&err);
+//!         if error.category() == SandboxErrorCategory::Transport {
+//!             // retry against another endpoint
+//!         }
+//!     }
+//!     Ok(replay) => { /* ... */ }
+//! }
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+use sui_transport::graphql::GraphQLClient;
+use sui_transport::TransportError;
+
+/// Coarse stage of a replay at which an error occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxErrorCategory {
+    /// Building the replay state: objects, state files.
+    Hydration,
+    /// Parsing identifiers and loading packages/modules.
+    Resolution,
+    /// Running the transaction in the VM.
+    Execution,
+    /// Comparing local effects against expected/on-chain effects.
+    Reconciliation,
+    /// Reaching a data source.
+    Transport,
+}
+
+impl SandboxErrorCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SandboxErrorCategory::Hydration => "hydration",
+            SandboxErrorCategory::Resolution => "resolution",
+            SandboxErrorCategory::Execution => "execution",
+            SandboxErrorCategory::Reconciliation => "reconciliation",
+            SandboxErrorCategory::Transport => "transport",
+        }
+    }
+}
+
+impl fmt::Display for SandboxErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The typed part of a [`SandboxError`], reported next to an error message
+/// (`local_error_kind` in replay output).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SandboxErrorKind {
+    pub category: SandboxErrorCategory,
+    pub code: &'static str,
+    pub retryable: bool,
+}
+
+/// A replay failure with a machine-readable cause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SandboxError {
+    /// An input object is not in the replay state.
+    MissingObject { object_id: String },
+    /// Supplied objects disagree with the digests the transaction references.
+    ObjectDigestMismatch { mismatches: Vec<String> },
+    /// The replay state source is unusable (empty, ambiguous, wrong digest).
+    InvalidReplayState { message: String },
+    /// An address or identifier in the transaction does not parse.
+    InvalidIdentifier {
+        kind: &'static str,
+        value: String,
+        reason: String,
+    },
+    /// Package bytecode could not be loaded into the resolver.
+    PackageLoadFailed {
+        package_id: String,
+        module: Option<String>,
+    },
+    /// The VM could not link a module or function (usually a missing dependency).
+    LinkerError { message: String },
+    /// The VM harness could not be created.
+    VmSetupFailed,
+    /// A Move function aborted.
+    MoveAbort { message: String },
+    /// Execution ran out of gas.
+    OutOfGas { message: String },
+    /// Any other execution failure.
+    ExecutionFailed { message: String },
+    /// Expected effects supplied for comparison are malformed.
+    InvalidExpectedEffects { message: String },
+    /// A comparison was required but none could be made; `reason` is a stable
+    /// identifier such as `no_effects_in_state` or `source_without_effects`.
+    ComparisonUnavailable { reason: String, detail: String },
+    /// A data source failed with a diagnosable cause.
+    Transport(TransportError),
+    /// A data source could not be reached.
+    Network { message: String },
+}
+
+impl SandboxError {
+    pub fn category(&self) -> SandboxErrorCategory {
+        match self {
+            SandboxError::MissingObject { .. }
+            | SandboxError::ObjectDigestMismatch { .. }
+            | SandboxError::InvalidReplayState { .. } => SandboxErrorCategory::Hydration,
+            SandboxError::InvalidIdentifier { .. }
+            | SandboxError::PackageLoadFailed { .. }
+            | SandboxError::LinkerError { .. } => SandboxErrorCategory::Resolution,
+            SandboxError::VmSetupFailed
+            | SandboxError::MoveAbort { .. }
+            | SandboxError::OutOfGas { .. }
+            | SandboxError::ExecutionFailed { .. } => SandboxErrorCategory::Execution,
+            SandboxError::InvalidExpectedEffects { .. }
+            | SandboxError::ComparisonUnavailable { .. } => SandboxErrorCategory::Reconciliation,
+            SandboxError::Transport(_) | SandboxError::Network { .. } => {
+                SandboxErrorCategory::Transport
+            }
+        }
+    }
+
+    /// Stable identifier for the error kind.
+    pub fn code(&self) -> &'static str {
+        match self {
+            SandboxError::MissingObject { .. } => "missing_object",
+            SandboxError::ObjectDigestMismatch { .. } => "object_digest_mismatch",
+            SandboxError::InvalidReplayState { .. } => "invalid_replay_state",
+            SandboxError::InvalidIdentifier { .. } => "invalid_identifier",
+            SandboxError::PackageLoadFailed { .. } => "package_load_failed",
+            SandboxError::LinkerError { .. } => "linker_error",
+            SandboxError::VmSetupFailed => "vm_setup_failed",
+            SandboxError::MoveAbort { .. } => "move_abort",
+            SandboxError::OutOfGas { .. } => "out_of_gas",
+            SandboxError::ExecutionFailed { .. } => "execution_failed",
+            SandboxError::InvalidExpectedEffects { .. } => "invalid_expected_effects",
+            SandboxError::ComparisonUnavailable { .. } => "comparison_unavailable",
+            SandboxError::Transport(err) => err.code(),
+            SandboxError::Network { .. } => "network",
+        }
+    }
+
+    /// Whether retrying with a different data source or more hydration may help.
+    pub fn retryable(&self) -> bool {
+        matches!(
+            self.category(),
+            SandboxErrorCategory::Hydration | SandboxErrorCategory::Transport
+        ) || matches!(
+            self,
+            SandboxError::PackageLoadFailed { .. } | SandboxError::LinkerError { .. }
+        )
+    }
+
+    pub fn kind(&self) -> SandboxErrorKind {
+        SandboxErrorKind {
+            category: self.category(),
+            code: self.code(),
+            retryable: self.retryable(),
+        }
+    }
+
+    /// JSON form for reports: `category`, `code`, `message`, `retryable` and the
+    /// error's fields.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut value = match self {
+            SandboxError::MissingObject { object_id } => {
+                serde_json::json!({ "object_id": object_id })
+            }
+            SandboxError::ObjectDigestMismatch { mismatches } => {
+                serde_json::json!({ "mismatches": mismatches })
+            }
+            SandboxError::InvalidIdentifier { kind, value, .. } => {
+                serde_json::json!({ "kind": kind, "value": value })
+            }
+            SandboxError::PackageLoadFailed { package_id, module } => {
+                serde_json::json!({ "package_id": package_id, "module": module })
+            }
+            SandboxError::ComparisonUnavailable { reason, detail } => {
+                serde_json::json!({ "reason": reason, "detail": detail })
+            }
+            SandboxError::Transport(err) => err.to_json(),
+            _ => serde_json::json!({}),
+        };
+        value["category"] = serde_json::json!(self.category());
+        value["code"] = serde_json::json!(self.code());
+        value["message"] = serde_json::json!(self.to_string());
+        value["retryable"] = serde_json::json!(self.retryable());
+        value
+    }
+
+    /// The `SandboxError` raised anywhere in `err`'s chain, as an error or as context.
+    pub fn find(err: &anyhow::Error) -> Option<&SandboxError> {
+        err.downcast_ref::<SandboxError>()
+    }
+
+    /// Map any replay error onto the taxonomy.
+    ///
+    /// A raised [`SandboxError`] wins, then an unreachable GraphQL endpoint, then any
+    /// other [`TransportError`]; anything else is classified from its message.
+    pub fn classify(err: &anyhow::Error) -> SandboxError {
+        if let Some(error) = Self::find(err) {
+            return error.clone();
+        }
+        let message = format!("{:#}", err);
+        if GraphQLClient::is_unreachable_error(err) {
+            return SandboxError::Network { message };
+        }
+        if let Some(error) = TransportError::find(err) {
+            return SandboxError::Transport(error.clone());
+        }
+        Self::from_message(&message)
+    }
+
+    /// Classify a VM/executor error string (e.g. `effects.error`).
+    pub fn from_message(message: &str) -> SandboxError {
+        let message = message.to_string();
+        if message.contains("MoveAbort")
+            || message.contains("ABORTED")
+            || message.to_ascii_lowercase().contains("move abort")
+        {
+            SandboxError::MoveAbort { message }
+        } else if message.contains("OutOfGas")
+            || message.contains("OUT_OF_GAS")
+            || message.contains("InsufficientGas")
+        {
+            SandboxError::OutOfGas { message }
+        } else if message.contains("LINKER_ERROR")
+            || message.contains("MISSING_DEPENDENCY")
+            || message.contains("FUNCTION_RESOLUTION_FAILURE")
+            || message.contains("LOOKUP_FAILED")
+        {
+            SandboxError::LinkerError { message }
+        } else {
+            SandboxError::ExecutionFailed { message }
+        }
+    }
+}
+
+impl fmt::Display for SandboxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SandboxError::MissingObject { object_id } => write!(
+                f,
+                "object '{}' not found in cache - ensure all input objects are fetched before replay",
+                object_id
+            ),
+            SandboxError::ObjectDigestMismatch { mismatches } => write!(
+                f,
+                "{} input object digest mismatch(es): {}",
+                mismatches.len(),
+                mismatches.join("; ")
+            ),
+            SandboxError::InvalidIdentifier {
+                kind,
+                value,
+                reason,
+            } => write!(f, "invalid {} '{}': {}", kind, value, reason),
+            SandboxError::PackageLoadFailed {
+                package_id,
+                module: Some(module),
+            } => write!(f, "load module {}::{}", package_id, module),
+            SandboxError::PackageLoadFailed {
+                package_id,
+                module: None,
+            } => write!(f, "load package {}", package_id),
+            SandboxError::VmSetupFailed => f.write_str("failed to create VM harness for replay"),
+            SandboxError::ComparisonUnavailable { reason, detail } => {
+                write!(f, "comparison unavailable ({}): {}", reason, detail)
+            }
+            SandboxError::InvalidReplayState { message }
+            | SandboxError::LinkerError { message }
+            | SandboxError::MoveAbort { message }
+            | SandboxError::OutOfGas { message }
+            | SandboxError::ExecutionFailed { message }
+            | SandboxError::InvalidExpectedEffects { message }
+            | SandboxError::Network { message } => f.write_str(message),
+            SandboxError::Transport(err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl std::error::Error for SandboxError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raised_errors_survive_context_and_classify_first() {
+        let missing = SandboxError::MissingObject {
+            object_id: "0x6".to_string(),
+        };
+        let err = anyhow::Error::new(missing.clone()).context("convert transaction to PTB");
+        assert_eq!(SandboxError::find(&err), Some(&missing));
+        assert_eq!(SandboxError::classify(&err), missing);
+
+        let setup = anyhow::anyhow!("bad resolver").context(SandboxError::VmSetupFailed);
+        assert_eq!(SandboxError::classify(&setup), SandboxError::VmSetupFailed);
+        assert_eq!(setup.to_string(), "failed to create VM harness for replay");
+
+        let json = missing.to_json();
+        assert_eq!(json["category"], "hydration");
+        assert_eq!(json["code"], "missing_object");
+        assert_eq!(json["object_id"], "0x6");
+        assert_eq!(json["retryable"], true);
+    }
+
+    #[test]
+    fn classify_transport_and_network_errors() {
+        let gap = TransportError::ArchiveVersionGap {
+            object_id: "0xabc".to_string(),
+            requested_version: 7,
+            endpoint: "https://archive.mainnet.sui.io:443".to_string(),
+        };
+        let classified = SandboxError::classify(&anyhow::Error::new(gap).context("fetch"));
+        assert_eq!(classified.category(), SandboxErrorCategory::Transport);
+        assert_eq!(classified.code(), "archive_version_gap");
+        assert_eq!(classified.to_json()["requested_version"], 7);
+
+        let unreachable = anyhow::Error::new(TransportError::EndpointUnreachable {
+            endpoint: "https://graphql.mainnet.sui.io/graphql".to_string(),
+            detail: "GraphQL request failed: connection refused".to_string(),
+        });
+        assert_eq!(SandboxError::classify(&unreachable).code(), "network");
+    }
+
+    #[test]
+    fn comparison_unavailable_is_a_reconciliation_error() {
+        let unavailable = SandboxError::ComparisonUnavailable {
+            reason: "replay_failed".to_string(),
+            detail: "replay produced no local effects".to_string(),
+        };
+        let err = anyhow::Error::new(unavailable.clone());
+        assert_eq!(SandboxError::classify(&err), unavailable);
+        let json = unavailable.to_json();
+        assert_eq!(json["category"], "reconciliation");
+        assert_eq!(json["code"], "comparison_unavailable");
+        assert_eq!(json["reason"], "replay_failed");
+        assert_eq!(json["retryable"], false);
+    }
+
+    #[test]
+    fn classify_vm_error_messages() {
+        let cases = [
+            (
+                "MoveAbort(MoveLocation { module: 0x2::coin, function: 0, instruction: 5 }, 1)",
+                "move_abort",
+            ),
+            (
+                "VMError { major_status: ABORTED, sub_status: Some(202) }",
+                "move_abort",
+            ),
+            ("OUT_OF_GAS", "out_of_gas"),
+            (
+                "VMError { major_status: LINKER_ERROR, message: Some(\"0xabc::pool\") }",
+                "linker_error",
+            ),
+            ("type mismatch in command 2", "execution_failed"),
+        ];
+        for (message, code) in cases {
+            assert_eq!(
+                SandboxError::from_message(message).code(),
+                code,
+                "{}",
+                message
+            );
+        }
+        assert_eq!(
+            SandboxError::from_message("LINKER_ERROR").category(),
+            SandboxErrorCategory::Resolution
+        );
+    }
+}
@@ -474,6 +474,9 @@ pub struct SuiRuntimeConfig {
     /// Controls whether version bounds are respected when fetching child objects.
     /// Default: Sandbox (ignores version bounds for backward compatibility).
     pub child_resolution_mode: ChildResolutionMode,
+    /// Protocol version whose `ProtocolConfig` the natives run with.
+    /// Default: `None` (the latest supported version).
+    pub protocol_version: Option<u64>,
 }
 
 impl Default for SuiRuntimeConfig {
@@ -487,10 +490,21 @@ impl Default for SuiRuntimeConfig {
             sponsor: None,
             is_metered: false,
             child_resolution_mode: ChildResolutionMode::Sandbox,
+            protocol_version: None,
         }
     }
 }
 
+/// Leak the `ProtocolConfig` for `config.protocol_version` (or the latest one) to get
+/// a `'static` reference.
+fn leak_protocol_config(config: &SuiRuntimeConfig) -> &'static ProtocolConfig {
+    let protocol_config = match config.protocol_version {
+        Some(version) => crate::gas::load_protocol_config(version),
+        None => ProtocolConfig::get_for_max_version_UNSAFE(),
+    };
+    Box::leak(Box::new(protocol_config))
+}
+
 /// A holder for Sui native extensions that can be added to NativeContextExtensions.
 ///
 /// This struct owns the various components needed for Sui natives and can create
@@ -524,8 +538,7 @@ impl SuiNativeExtensions {
     /// long-running service.
     pub fn new(fetcher: ChildFetchFn, config: SuiRuntimeConfig) -> Self {
         // Leak the protocol config to get 'static lifetime
-        let protocol_config: &'static ProtocolConfig =
-            Box::leak(Box::new(ProtocolConfig::get_for_max_version_UNSAFE()));
+        let protocol_config = leak_protocol_config(&config);
 
         // Create resolver based on configured mode
         let resolver: &'static dyn ChildObjectResolver = match config.child_resolution_mode {
@@ -558,8 +571,7 @@ impl SuiNativeExtensions {
     ///
     /// SAFETY: This leaks memory, which is acceptable for simulation/testing contexts.
     pub fn new_replay(replay_fetcher: VersionBoundChildFetchFn, config: SuiRuntimeConfig) -> Self {
-        let protocol_config: &'static ProtocolConfig =
-            Box::leak(Box::new(ProtocolConfig::get_for_max_version_UNSAFE()));
+        let protocol_config = leak_protocol_config(&config);
 
         let resolver_box = Box::new(ChildObjectResolverWrapper::new_replay(
             replay_fetcher,
@@ -578,8 +590,7 @@ impl SuiNativeExtensions {
         replay_fetcher: VersionBoundChildFetchFn,
         config: SuiRuntimeConfig,
     ) -> Self {
-        let protocol_config: &'static ProtocolConfig =
-            Box::leak(Box::new(ProtocolConfig::get_for_max_version_UNSAFE()));
+        let protocol_config = leak_protocol_config(&config);
 
         let resolver_box = Box::new(ChildObjectResolverWrapper::new_dual(
             sandbox_fetcher,
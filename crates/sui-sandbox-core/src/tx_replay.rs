@@ -106,9 +106,32 @@ use sui_sandbox_types::encoding::{base64_decode, try_base64_decode};
 use sui_types::base_types::ObjectID as SuiObjectID;
 use sui_types::digests::TransactionDigest as SuiTransactionDigest;
 
+use crate::natives::EmittedEvent;
 use crate::ptb::{Argument, Command, InputValue, ObjectInput};
+use crate::sandbox_error::SandboxError;
 use crate::vm::VMHarness;
 
+/// A [`SandboxError::InvalidIdentifier`] for `value`.
+fn invalid_identifier(
+    kind: &'static str,
+    value: &str,
+    reason: impl std::fmt::Display,
+) -> anyhow::Error {
+    SandboxError::InvalidIdentifier {
+        kind,
+        value: value.to_string(),
+        reason: reason.to_string(),
+    }
+    .into()
+}
+
+fn missing_object(object_id: &str) -> anyhow::Error {
+    SandboxError::MissingObject {
+        object_id: object_id.to_string(),
+    }
+    .into()
+}
+
 fn linkage_debug_enabled() -> bool {
     matches!(
         std::env::var("SUI_DEBUG_LINKAGE")
@@ -247,6 +270,284 @@ fn normalize_object_id(id: &str) -> String {
     sui_resolver::normalize_id(id)
 }
 
+/// Report event provenance the way the chain does: the Move call's on-chain package id
+/// (before alias rewriting to the bytecode self-address) and the transaction sender.
+fn attribute_events_to_transaction(events: &mut [EmittedEvent], tx: &FetchedTransaction) {
+    for event in events {
+        event.sender = tx.sender;
+        let call = event.command_index.and_then(|index| tx.commands.get(index));
+        if let Some(PtbCommand::MoveCall {
+            package, module, ..
+        }) = call
+        {
+            if let Ok(package_id) = AccountAddress::from_hex_literal(package) {
+                event.package_id = Some(package_id);
+            }
+            event.transaction_module = Some(module.clone());
+        }
+    }
+}
+
+/// Size of a BCS-encoded `Coin<T>`: UID (32 bytes) + balance (u64).
+const COIN_BCS_LEN: usize = 40;
+
+/// Build the gas coin the PTB sees as `GasCoin`.
+///
+/// When the transaction's gas payment is known and every payment coin is in
+/// `cached_objects`, the coins are smashed the way the chain does it: the
+/// primary coin receives the combined balance, minus the gas budget that stays
+/// reserved while commands run. Otherwise a placeholder `Coin<SUI>` holding
+/// `gas_balance` is used.
+fn gas_coin_input(
+    tx: &FetchedTransaction,
+    gas_balance: u64,
+    cached_objects: &std::collections::HashMap<String, String>,
+) -> ObjectInput {
+    let primary = tx.gas_payment.first().and_then(|coin| {
+        Some((
+            AccountAddress::from_hex_literal(&coin.object_id).ok()?,
+            coin,
+        ))
+    });
+    let smashed_balance = tx.gas_payment.iter().try_fold(0u64, |total, coin| {
+        let bytes = cached_coin_bytes(cached_objects, &coin.object_id)?;
+        let balance = u64::from_le_bytes(bytes[32..COIN_BCS_LEN].try_into().ok()?);
+        Some(total.saturating_add(balance))
+    });
+
+    match (primary, smashed_balance) {
+        (Some((id, coin)), Some(total)) => {
+            let mut bytes = id.to_vec();
+            bytes.extend_from_slice(&total.saturating_sub(tx.gas_budget).to_le_bytes());
+            ObjectInput::Owned {
+                id,
+                bytes,
+                type_tag: Some(crate::well_known::types::sui_coin()),
+                version: Some(coin.version),
+            }
+        }
+        (primary, _) => {
+            // Coin<T> layout: id (UID = 32 bytes) + balance (u64 = 8 bytes) = 40 bytes
+            let id = primary.map(|(id, _)| id).unwrap_or(AccountAddress::ZERO);
+            let mut bytes = id.to_vec();
+            bytes.extend_from_slice(&gas_balance.to_le_bytes());
+            ObjectInput::Owned {
+                id,
+                bytes,
+                type_tag: None, // Gas coin type is known to be Coin<SUI>
+                version: primary.map(|(_, coin)| coin.version),
+            }
+        }
+    }
+}
+
+fn cached_coin_bytes(
+    cached_objects: &std::collections::HashMap<String, String>,
+    object_id: &str,
+) -> Option<Vec<u8>> {
+    let normalized = crate::utilities::normalize_address(object_id);
+    let b64 = cached_objects
+        .get(object_id)
+        .or_else(|| cached_objects.get(&normalized))?;
+    try_base64_decode(b64).filter(|bytes| bytes.len() == COIN_BCS_LEN)
+}
+
+/// Report gas smashing in local effects: the primary gas coin is mutated and
+/// every other payment coin is deleted, matching on-chain effects.
+fn apply_gas_smashing(tx: &FetchedTransaction, effects: &mut crate::ptb::TransactionEffects) {
+    let coins: Vec<AccountAddress> = tx
+        .gas_payment
+        .iter()
+        .filter_map(|coin| AccountAddress::from_hex_literal(&coin.object_id).ok())
+        .collect();
+    let Some((primary, smashed)) = coins.split_first() else {
+        return;
+    };
+    if !effects.mutated.contains(primary) && !effects.deleted.contains(primary) {
+        effects.mutated.push(*primary);
+    }
+    for coin in smashed {
+        effects.mutated.retain(|id| id != coin);
+        if !effects.deleted.contains(coin) {
+            effects.deleted.push(*coin);
+        }
+    }
+}
+
+/// Coin type `T` and balance of BCS `bytes` when `type_tag` is `0x2::coin::Coin<T>`.
+fn coin_balance(type_tag: Option<&TypeTag>, bytes: &[u8]) -> Option<(String, u64)> {
+    let TypeTag::Struct(tag) = type_tag? else {
+        return None;
+    };
+    if tag.address != AccountAddress::TWO
+        || tag.module.as_str() != "coin"
+        || tag.name.as_str() != "Coin"
+        || tag.type_params.len() != 1
+        || bytes.len() != COIN_BCS_LEN
+    {
+        return None;
+    }
+    let balance = u64::from_le_bytes(bytes[32..COIN_BCS_LEN].try_into().ok()?);
+    Some((format!("{}", tag.type_params[0]), balance))
+}
+
+/// Per-owner, per-coin-type balance deltas of a replay, mirroring on-chain
+/// `BalanceChanges`.
+///
+/// Compares every address-owned `Coin<T>` before (owned inputs and gas payment
+/// coins) and after execution (created, mutated, unwrapped and transferred
+/// objects). Deleted and wrapped coins count as spent. The gas coin is credited
+/// back the reserved budget and charged the net gas cost when
+/// `effects.gas_summary` is known; without accurate gas the SUI delta excludes fees.
+pub fn compute_balance_changes(
+    tx: &FetchedTransaction,
+    inputs: &[InputValue],
+    cached_objects: &HashMap<String, String>,
+    effects: &crate::ptb::TransactionEffects,
+) -> Vec<BalanceChange> {
+    use crate::ptb::{ObjectChange, Owner};
+
+    // id -> (owner, coin type, balance)
+    let mut before: HashMap<AccountAddress, (AccountAddress, String, u64)> = HashMap::new();
+    let mut input_types: HashMap<AccountAddress, TypeTag> = HashMap::new();
+    for input in inputs {
+        let InputValue::Object(obj) = input else {
+            continue;
+        };
+        if let Some(type_tag) = obj.type_tag() {
+            input_types.insert(*obj.id(), type_tag.clone());
+        }
+        if !matches!(obj, ObjectInput::Owned { .. } | ObjectInput::MutRef { .. }) {
+            continue;
+        }
+        if let Some((coin_type, balance)) = coin_balance(obj.type_tag(), obj.bytes()) {
+            before.insert(*obj.id(), (tx.sender, coin_type, balance));
+        }
+    }
+
+    // Gas payment coins, at their real (pre-smashing) balances.
+    let sui_coin_type = format!("{}", *crate::well_known::types::SUI_TYPE);
+    let mut gas_coins = Vec::new();
+    for coin in &tx.gas_payment {
+        let (Ok(id), Some(bytes)) = (
+            AccountAddress::from_hex_literal(&coin.object_id),
+            cached_coin_bytes(cached_objects, &coin.object_id),
+        ) else {
+            continue;
+        };
+        let balance = u64::from_le_bytes(bytes[32..COIN_BCS_LEN].try_into().unwrap_or_default());
+        before.insert(id, (tx.sender, sui_coin_type.clone(), balance));
+        gas_coins.push((id, balance));
+    }
+
+    let mut after = before.clone();
+    for id in effects.deleted.iter().chain(&effects.wrapped) {
+        after.remove(id);
+    }
+    let mut record =
+        |id: &AccountAddress, owner: AccountAddress, type_tag: Option<&TypeTag>, bytes: &[u8]| {
+            let type_tag = type_tag.or_else(|| input_types.get(id));
+            match coin_balance(type_tag, bytes) {
+                Some((coin_type, balance)) => {
+                    after.insert(*id, (owner, coin_type, balance));
+                }
+                None => {
+                    after.remove(id);
+                }
+            }
+        };
+    for change in &effects.object_changes {
+        let (id, owner, object_type, bytes) = match change {
+            ObjectChange::Created {
+                id,
+                owner,
+                object_type,
+            } => (id, owner, object_type, effects.created_object_bytes.get(id)),
+            ObjectChange::Mutated {
+                id,
+                owner,
+                object_type,
+            }
+            | ObjectChange::Unwrapped {
+                id,
+                owner,
+                object_type,
+            } => (
+                id,
+                owner,
+                object_type,
+                effects
+                    .mutated_object_bytes
+                    .get(id)
+                    .or_else(|| effects.created_object_bytes.get(id)),
+            ),
+            _ => continue,
+        };
+        let Some(bytes) = bytes else {
+            continue;
+        };
+        match owner {
+            // Owners the executor did not track default to 0x0; inputs stay with the sender.
+            Owner::Address(addr) if *addr == AccountAddress::ZERO && before.contains_key(id) => {
+                record(id, tx.sender, object_type.as_ref(), bytes)
+            }
+            Owner::Address(addr) => record(id, *addr, object_type.as_ref(), bytes),
+            Owner::Shared | Owner::Immutable => {
+                after.remove(id);
+            }
+        }
+    }
+    for change in &effects.object_changes {
+        if let ObjectChange::Transferred {
+            id,
+            recipient,
+            object_type,
+            object_bytes,
+        } = change
+        {
+            record(id, *recipient, object_type.as_ref(), object_bytes);
+        }
+    }
+
+    // Gas smashing: the primary coin holds every payment coin's balance, minus the
+    // budget reserved while commands ran, and pays the net gas cost.
+    if let Some(((primary, _), smashed)) = gas_coins.split_first() {
+        let total: u64 = gas_coins.iter().map(|(_, balance)| balance).sum();
+        let primary_after = match effects.mutated_object_bytes.get(primary) {
+            Some(bytes) => coin_balance(Some(&crate::well_known::types::sui_coin()), bytes)
+                .map(|(_, balance)| balance.saturating_add(tx.gas_budget))
+                .unwrap_or(total),
+            None => total,
+        };
+        let gas_cost = effects.gas_summary.as_ref().map_or(0, |gas| gas.total());
+        let primary_after = (primary_after as i128 - gas_cost as i128).max(0) as u64;
+        for (id, _) in smashed {
+            after.remove(id);
+        }
+        if let Some(entry) = after.get_mut(primary) {
+            entry.2 = primary_after;
+        }
+    }
+
+    let mut deltas: std::collections::BTreeMap<(AccountAddress, String), i128> =
+        std::collections::BTreeMap::new();
+    for (owner, coin_type, balance) in before.into_values() {
+        *deltas.entry((owner, coin_type)).or_default() -= balance as i128;
+    }
+    for (owner, coin_type, balance) in after.into_values() {
+        *deltas.entry((owner, coin_type)).or_default() += balance as i128;
+    }
+    deltas
+        .into_iter()
+        .filter(|(_, amount)| *amount != 0)
+        .map(|((owner, coin_type), amount)| BalanceChange {
+            owner: owner.to_hex_literal(),
+            coin_type,
+            amount,
+        })
+        .collect()
+}
+
 fn filter_mutated_to_inputs(mutated: Vec<String>, inputs: &[TransactionInput]) -> Vec<String> {
     if inputs.is_empty() {
         return mutated;
@@ -308,11 +609,11 @@ pub use crate::types::{
 // ============================================================================
 
 pub use sui_sandbox_types::{
-    transaction::base64_bytes, CachedDynamicField, CachedTransaction, DynamicFieldEntry,
-    EffectsComparison, FetchedObject, FetchedTransaction, GasSummary, LocalVersionInfo, ObjectID,
-    PtbArgument, PtbCommand, ReplayResult, TransactionCache, TransactionDigest,
-    TransactionEffectsSummary, TransactionInput, TransactionStatus, VersionMismatch,
-    VersionMismatchType, VersionSummary,
+    transaction::base64_bytes, BalanceChange, CachedDynamicField, CachedTransaction,
+    DynamicFieldEntry, EffectsComparison, FetchedObject, FetchedTransaction, GasPaymentObject,
+    GasSummary, LocalVersionInfo, ObjectID, PtbArgument, PtbCommand, ReplayResult,
+    TransactionCache, TransactionDigest, TransactionEffectsSummary, TransactionInput,
+    TransactionStatus, VersionMismatch, VersionMismatchType, VersionSummary,
 };
 
 // ============================================================================
@@ -718,7 +1019,7 @@ fn to_ptb_commands_internal(
     // Helper to parse object ID with proper error handling
     let parse_object_id = |object_id: &str| -> Result<AccountAddress> {
         AccountAddress::from_hex_literal(object_id)
-            .map_err(|e| anyhow::anyhow!("invalid object ID '{}': {}", object_id, e))
+            .map_err(|e| invalid_identifier("object ID", object_id, e))
     };
 
     // Helper to get object bytes from cache.
@@ -732,12 +1033,7 @@ fn to_ptb_commands_internal(
             .get(object_id)
             .or_else(|| cached_objects.get(&normalized))
             .or_else(|| cached_objects.get(&short))
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "object '{}' not found in cache - ensure all input objects are fetched before replay",
-                    object_id
-                )
-            })
+            .ok_or_else(|| missing_object(object_id))
             .and_then(|b64| base64_decode(b64, &format!("object '{}'", object_id)))
     };
 
@@ -763,18 +1059,13 @@ fn to_ptb_commands_internal(
     // Input index offset: if we prepend GasCoin, all other input indices shift by 1
     let input_offset: u16 = if uses_gas_coin { 1 } else { 0 };
 
-    // If uses GasCoin, prepend a synthetic gas coin object
+    // If uses GasCoin, prepend the (smashed) gas coin object
     if uses_gas_coin {
-        // Create a synthetic Coin<SUI> with the gas budget as balance
-        // Coin<T> layout: id (UID = 32 bytes) + balance (u64 = 8 bytes) = 40 bytes
-        let mut gas_coin_bytes = vec![0u8; 32]; // UID (placeholder)
-        gas_coin_bytes.extend_from_slice(&gas_balance.to_le_bytes()); // Balance
-        inputs.push(InputValue::Object(ObjectInput::Owned {
-            id: AccountAddress::ZERO, // Placeholder gas coin ID
-            bytes: gas_coin_bytes,
-            type_tag: None, // Gas coin type is known to be Coin<SUI>
-            version: None,
-        }));
+        inputs.push(InputValue::Object(gas_coin_input(
+            tx,
+            gas_balance,
+            cached_objects,
+        )));
     }
 
     // Convert inputs, using cached object data when available
@@ -867,11 +1158,11 @@ fn to_ptb_commands_internal(
                 arguments,
             } => {
                 let package_addr = AccountAddress::from_hex_literal(package)
-                    .map_err(|e| anyhow!("Invalid package address: {}", e))?;
+                    .map_err(|e| invalid_identifier("package address", package, e))?;
                 let module_id = Identifier::new(module.clone())
-                    .map_err(|e| anyhow!("Invalid module name: {}", e))?;
+                    .map_err(|e| invalid_identifier("module name", module, e))?;
                 let function_id = Identifier::new(function.clone())
-                    .map_err(|e| anyhow!("Invalid function name: {}", e))?;
+                    .map_err(|e| invalid_identifier("function name", function, e))?;
 
                 // Parse type arguments from RPC strings
                 let type_args: Vec<TypeTag> = type_arguments
@@ -922,7 +1213,7 @@ fn to_ptb_commands_internal(
             }
 
             PtbCommand::MakeMoveVec { type_arg, elements } => {
-                let type_tag = type_arg.as_ref().and_then(|s| parse_type_tag(s).ok());
+                let type_tag = parse_make_move_vec_type(type_arg.as_deref())?;
                 let elem_args: Vec<Argument> = elements.iter().map(&convert_arg).collect();
                 commands.push(Command::MakeMoveVec {
                     type_tag,
@@ -939,6 +1230,19 @@ fn to_ptb_commands_internal(
     Ok((inputs, commands))
 }
 
+/// Parse the element type annotation of a `MakeMoveVec` command.
+///
+/// Unlike Move call type arguments, a malformed annotation is an error: silently dropping it
+/// would leave an empty vector untyped.
+fn parse_make_move_vec_type(type_arg: Option<&str>) -> Result<Option<TypeTag>> {
+    type_arg
+        .map(|s| {
+            parse_type_tag(s)
+                .map_err(|e| anyhow!("invalid MakeMoveVec type argument '{}': {}", s, e))
+        })
+        .transpose()
+}
+
 /// Internal method that converts to PTB commands with explicit version information.
 ///
 /// The `object_versions` map allows overriding versions from TransactionInput with
@@ -955,7 +1259,7 @@ fn to_ptb_commands_internal_with_versions(
     // Helper to parse object ID with proper error handling
     let parse_object_id = |object_id: &str| -> Result<AccountAddress> {
         AccountAddress::from_hex_literal(object_id)
-            .map_err(|e| anyhow::anyhow!("invalid object ID '{}': {}", object_id, e))
+            .map_err(|e| invalid_identifier("object ID", object_id, e))
     };
 
     // Helper to get object bytes from cache
@@ -967,7 +1271,7 @@ fn to_ptb_commands_internal_with_versions(
             .get(object_id)
             .or_else(|| cached_objects.get(&normalized))
             .or_else(|| cached_objects.get(&short))
-            .ok_or_else(|| anyhow::anyhow!("object '{}' not found in cache", object_id))
+            .ok_or_else(|| missing_object(object_id))
             .and_then(|b64| base64_decode(b64, &format!("object '{}'", object_id)))
     };
 
@@ -1001,14 +1305,11 @@ fn to_ptb_commands_internal_with_versions(
     let input_offset: u16 = if uses_gas_coin { 1 } else { 0 };
 
     if uses_gas_coin {
-        let mut gas_coin_bytes = vec![0u8; 32];
-        gas_coin_bytes.extend_from_slice(&gas_balance.to_le_bytes());
-        inputs.push(InputValue::Object(ObjectInput::Owned {
-            id: AccountAddress::ZERO,
-            bytes: gas_coin_bytes,
-            type_tag: None,
-            version: None, // Synthetic gas coin has no real version
-        }));
+        inputs.push(InputValue::Object(gas_coin_input(
+            tx,
+            gas_balance,
+            cached_objects,
+        )));
     }
 
     // Convert inputs with version information
@@ -1100,9 +1401,11 @@ fn to_ptb_commands_internal_with_versions(
                 arguments,
             } => {
                 let package_addr = AccountAddress::from_hex_literal(package)
-                    .map_err(|e| anyhow::anyhow!("invalid package address: {}", e))?;
-                let module_id = Identifier::new(module.clone())?;
-                let function_id = Identifier::new(function.clone())?;
+                    .map_err(|e| invalid_identifier("package address", package, e))?;
+                let module_id = Identifier::new(module.clone())
+                    .map_err(|e| invalid_identifier("module name", module, e))?;
+                let function_id = Identifier::new(function.clone())
+                    .map_err(|e| invalid_identifier("function name", function, e))?;
                 let type_args: Vec<TypeTag> = type_arguments
                     .iter()
                     .filter_map(|s| parse_type_tag(s).ok())
@@ -1140,7 +1443,7 @@ fn to_ptb_commands_internal_with_versions(
             }
             PtbCommand::MakeMoveVec { type_arg, elements } => {
                 commands.push(Command::MakeMoveVec {
-                    type_tag: type_arg.as_ref().and_then(|s| parse_type_tag(s).ok()),
+                    type_tag: parse_make_move_vec_type(type_arg.as_deref())?,
                     elements: elements.iter().map(&convert_arg).collect(),
                 });
             }
@@ -1166,7 +1469,7 @@ fn to_ptb_commands_internal_with_aliases(
     // Helper to parse object ID with proper error handling
     let parse_object_id = |object_id: &str| -> Result<AccountAddress> {
         AccountAddress::from_hex_literal(object_id)
-            .map_err(|e| anyhow::anyhow!("invalid object ID '{}': {}", object_id, e))
+            .map_err(|e| invalid_identifier("object ID", object_id, e))
     };
 
     // Helper to get object bytes from cache.
@@ -1180,12 +1483,7 @@ fn to_ptb_commands_internal_with_aliases(
             .get(object_id)
             .or_else(|| cached_objects.get(&normalized))
             .or_else(|| cached_objects.get(&short))
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "object '{}' not found in cache - ensure all input objects are fetched before replay",
-                    object_id
-                )
-            })
+            .ok_or_else(|| missing_object(object_id))
             .and_then(|b64| base64_decode(b64, &format!("object '{}'", object_id)))
     };
 
@@ -1237,14 +1535,11 @@ fn to_ptb_commands_internal_with_aliases(
     let input_offset: u16 = if uses_gas_coin { 1 } else { 0 };
 
     if uses_gas_coin {
-        let mut gas_coin_bytes = vec![0u8; 32];
-        gas_coin_bytes.extend_from_slice(&gas_balance.to_le_bytes());
-        inputs.push(InputValue::Object(ObjectInput::Owned {
-            id: AccountAddress::ZERO,
-            bytes: gas_coin_bytes,
-            type_tag: None,
-            version: None,
-        }));
+        inputs.push(InputValue::Object(gas_coin_input(
+            tx,
+            gas_balance,
+            cached_objects,
+        )));
     }
 
     // Convert inputs
@@ -1333,7 +1628,7 @@ fn to_ptb_commands_internal_with_aliases(
                 arguments,
             } => {
                 let package_addr = AccountAddress::from_hex_literal(package)
-                    .map_err(|e| anyhow!("Invalid package address: {}", e))?;
+                    .map_err(|e| invalid_identifier("package address", package, e))?;
                 // Rewrite package address to bytecode self-address
                 let rewritten_package = rewrite_addr(package_addr);
                 if std::env::var("SUI_DEBUG_ALIAS_REWRITE")
@@ -1350,9 +1645,9 @@ fn to_ptb_commands_internal_with_aliases(
                     );
                 }
                 let module_id = Identifier::new(module.clone())
-                    .map_err(|e| anyhow!("Invalid module name: {}", e))?;
+                    .map_err(|e| invalid_identifier("module name", module, e))?;
                 let function_id = Identifier::new(function.clone())
-                    .map_err(|e| anyhow!("Invalid function name: {}", e))?;
+                    .map_err(|e| invalid_identifier("function name", function, e))?;
 
                 // Parse and rewrite type arguments
                 let type_args: Vec<TypeTag> = type_arguments
@@ -1397,9 +1692,7 @@ fn to_ptb_commands_internal_with_aliases(
             }
 
             PtbCommand::MakeMoveVec { type_arg, elements } => {
-                let type_tag = type_arg
-                    .as_ref()
-                    .and_then(|s| parse_type_tag(s).ok())
+                let type_tag = parse_make_move_vec_type(type_arg.as_deref())?
                     .map(|t| rewrite_type_tag(t, address_aliases));
                 commands.push(Command::MakeMoveVec {
                     type_tag,
@@ -1498,6 +1791,192 @@ pub struct ReplayExecution {
     pub effects: crate::ptb::TransactionEffects,
 }
 
+// ============================================================================
+// Canonical Effects Digest
+// ============================================================================
+
+/// The effects fields a local replay can reconstruct, in canonical (sorted) form.
+///
+/// Sui's on-chain effects digest also commits to gas costs, per-object digests
+/// (which embed storage rebates and the previous transaction), the lamport
+/// version, and transaction dependencies. Unmetered local execution reproduces
+/// none of those, so they are left out; both local and on-chain effects go
+/// through this same canonicalization so their digests are directly comparable.
+/// The digest is a fingerprint in Sui's hashing scheme, not the on-chain
+/// effects digest itself.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectsFingerprint {
+    pub transaction_digest: String,
+    pub success: bool,
+    pub created: Vec<AccountAddress>,
+    pub mutated: Vec<AccountAddress>,
+    pub deleted: Vec<AccountAddress>,
+    pub wrapped: Vec<AccountAddress>,
+    pub unwrapped: Vec<AccountAddress>,
+    pub events_count: u64,
+}
+
+/// One fingerprint field that differs from a baseline.
+///
+/// Scalar fields carry both values; for object-id sets `expected` holds the ids
+/// only the baseline has and `actual` the ids only the replay has.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectsFieldDiff {
+    pub field: &'static str,
+    pub expected: serde_json::Value,
+    pub actual: serde_json::Value,
+}
+
+impl EffectsFingerprint {
+    /// Drop the transaction's gas payment coins from the mutated and deleted sets.
+    ///
+    /// Gas coins are mutated (primary) or deleted (smashed) by every on-chain
+    /// transaction, while local effects only report them when execution charged
+    /// the known gas payment, so they are excluded from both sides.
+    fn excluding_gas_payment(mut self, tx: &FetchedTransaction) -> Self {
+        let gas_coins: Vec<AccountAddress> = tx
+            .gas_payment
+            .iter()
+            .filter_map(|coin| AccountAddress::from_hex_literal(&coin.object_id).ok())
+            .collect();
+        self.mutated.retain(|id| !gas_coins.contains(id));
+        self.deleted.retain(|id| !gas_coins.contains(id));
+        self
+    }
+
+    fn canonicalized(mut self) -> Self {
+        for ids in [
+            &mut self.created,
+            &mut self.mutated,
+            &mut self.deleted,
+            &mut self.wrapped,
+            &mut self.unwrapped,
+        ] {
+            ids.sort();
+            ids.dedup();
+        }
+        self
+    }
+
+    /// Hash as Sui hashes effects: Blake2b256 over `"TransactionEffects::" || bcs(value)`,
+    /// rendered as a base58 effects digest.
+    pub fn digest(&self) -> String {
+        use fastcrypto::hash::{Blake2b256, HashFunction};
+
+        let canonical = self.clone().canonicalized();
+        let mut hasher = Blake2b256::default();
+        hasher.update(b"TransactionEffects::");
+        hasher.update(bcs::to_bytes(&canonical).expect("canonical effects are BCS-serializable"));
+        sui_types::digests::TransactionEffectsDigest::new(hasher.finalize().digest).to_string()
+    }
+
+    /// Fields where `self` differs from `baseline`, in declaration order.
+    pub fn diff(&self, baseline: &EffectsFingerprint) -> Vec<EffectsFieldDiff> {
+        let mut diffs = Vec::new();
+        if self.transaction_digest != baseline.transaction_digest {
+            diffs.push(EffectsFieldDiff {
+                field: "transaction_digest",
+                expected: serde_json::json!(baseline.transaction_digest),
+                actual: serde_json::json!(self.transaction_digest),
+            });
+        }
+        if self.success != baseline.success {
+            diffs.push(EffectsFieldDiff {
+                field: "success",
+                expected: serde_json::json!(baseline.success),
+                actual: serde_json::json!(self.success),
+            });
+        }
+        let id_sets = [
+            ("created", &self.created, &baseline.created),
+            ("mutated", &self.mutated, &baseline.mutated),
+            ("deleted", &self.deleted, &baseline.deleted),
+            ("wrapped", &self.wrapped, &baseline.wrapped),
+            ("unwrapped", &self.unwrapped, &baseline.unwrapped),
+        ];
+        for (field, actual, expected) in id_sets {
+            let only = |ids: &[AccountAddress], other: &[AccountAddress]| {
+                let mut out: Vec<String> = ids
+                    .iter()
+                    .filter(|id| !other.contains(*id))
+                    .map(|id| id.to_hex_literal())
+                    .collect();
+                out.sort();
+                out.dedup();
+                out
+            };
+            let missing = only(expected, actual);
+            let unexpected = only(actual, expected);
+            if !missing.is_empty() || !unexpected.is_empty() {
+                diffs.push(EffectsFieldDiff {
+                    field,
+                    expected: serde_json::json!(missing),
+                    actual: serde_json::json!(unexpected),
+                });
+            }
+        }
+        if self.events_count != baseline.events_count {
+            diffs.push(EffectsFieldDiff {
+                field: "events_count",
+                expected: serde_json::json!(baseline.events_count),
+                actual: serde_json::json!(self.events_count),
+            });
+        }
+        diffs
+    }
+}
+
+/// Canonical fingerprint of a local replay's effects; `digest()` it for the
+/// comparable `local_effects_fingerprint` value.
+pub fn local_effects_fingerprint(
+    tx: &FetchedTransaction,
+    effects: &crate::ptb::TransactionEffects,
+) -> EffectsFingerprint {
+    EffectsFingerprint {
+        transaction_digest: tx.digest.0.clone(),
+        success: effects.success,
+        created: effects.created.clone(),
+        mutated: effects.mutated.clone(),
+        deleted: effects.deleted.clone(),
+        wrapped: effects.wrapped.clone(),
+        unwrapped: effects.unwrapped.clone(),
+        events_count: effects.events.len() as u64,
+    }
+    .excluding_gas_payment(tx)
+    .canonicalized()
+}
+
+/// Canonical fingerprint of the transaction's on-chain effects summary.
+///
+/// Computed with the same canonicalization as [`local_effects_fingerprint`], so equal
+/// values mean the replay reproduced status, object-id sets, and event count.
+/// Without a recorded `gas_payment` the on-chain gas coin cannot be told apart from
+/// other mutated objects and stays in the fingerprint, so it will not match.
+///
+/// Returns `None` when on-chain effects are unavailable or contain unparseable ids.
+pub fn onchain_effects_fingerprint(tx: &FetchedTransaction) -> Option<EffectsFingerprint> {
+    let effects = tx.effects.as_ref()?;
+    let parse = |ids: &[String]| -> Option<Vec<AccountAddress>> {
+        ids.iter()
+            .map(|id| AccountAddress::from_hex_literal(id).ok())
+            .collect()
+    };
+    Some(
+        EffectsFingerprint {
+            transaction_digest: tx.digest.0.clone(),
+            success: effects.status == TransactionStatus::Success,
+            created: parse(&effects.created)?,
+            mutated: parse(&effects.mutated)?,
+            deleted: parse(&effects.deleted)?,
+            wrapped: parse(&effects.wrapped)?,
+            unwrapped: parse(&effects.unwrapped)?,
+            events_count: effects.events_count as u64,
+        }
+        .excluding_gas_payment(tx)
+        .canonicalized(),
+    )
+}
+
 pub fn replay_with_version_tracking_with_policy(
     tx: &FetchedTransaction,
     harness: &mut VMHarness,
@@ -1524,6 +2003,31 @@ pub fn replay_with_version_tracking_with_policy_with_effects(
     address_aliases: &std::collections::HashMap<AccountAddress, AccountAddress>,
     object_versions: Option<&std::collections::HashMap<String, u64>>,
     policy: EffectsReconcilePolicy,
+) -> Result<ReplayExecution> {
+    replay_with_version_tracking_against_effects(
+        tx,
+        tx.effects.as_ref(),
+        harness,
+        cached_objects,
+        address_aliases,
+        object_versions,
+        policy,
+    )
+}
+
+/// Replay `tx` and compare the local effects against `on_chain` rather than the
+/// effects `tx` carries.
+///
+/// Execution only reads `tx` (including the created-id seed inferred from its own
+/// effects), so effects supplied for comparison cannot change what the replay does.
+pub fn replay_with_version_tracking_against_effects(
+    tx: &FetchedTransaction,
+    on_chain: Option<&TransactionEffectsSummary>,
+    harness: &mut VMHarness,
+    cached_objects: &std::collections::HashMap<String, String>,
+    address_aliases: &std::collections::HashMap<AccountAddress, AccountAddress>,
+    object_versions: Option<&std::collections::HashMap<String, u64>>,
+    policy: EffectsReconcilePolicy,
 ) -> Result<ReplayExecution> {
     use crate::ptb::PTBExecutor;
 
@@ -1567,7 +2071,7 @@ pub fn replay_with_version_tracking_with_policy_with_effects(
     }
 
     // Execute commands
-    let effects = match executor.execute_commands(&commands) {
+    let mut effects = match executor.execute_commands(&commands) {
         Ok(effects) => effects,
         Err(e) => {
             if matches!(
@@ -1638,6 +2142,9 @@ pub fn replay_with_version_tracking_with_policy_with_effects(
             });
         }
     };
+    attribute_events_to_transaction(&mut effects.events, tx);
+    apply_gas_smashing(tx, &mut effects);
+    effects.balance_changes = compute_balance_changes(tx, &inputs, cached_objects, &effects);
 
     if !effects.success {
         let debug_ctx = matches!(
@@ -1711,7 +2218,7 @@ pub fn replay_with_version_tracking_with_policy_with_effects(
     let mut filtered_df_mutated_count = 0usize;
     let mut filtered_df_deleted = false;
     let mut filtered_df_deleted_count = 0usize;
-    if let (Some(on_chain), EffectsReconcilePolicy::DynamicFields) = (tx.effects.as_ref(), policy) {
+    if let (Some(on_chain), EffectsReconcilePolicy::DynamicFields) = (on_chain, policy) {
         let mut df_children: std::collections::HashSet<String> = effects
             .dynamic_field_entries
             .keys()
@@ -1789,16 +2296,17 @@ pub fn replay_with_version_tracking_with_policy_with_effects(
             .iter()
             .map(|id| id.to_hex_literal())
             .collect(),
-        gas_used: GasSummary {
+        gas_used: effects.gas_summary.clone().unwrap_or(GasSummary {
             computation_cost: effects.gas_used,
             ..GasSummary::default()
-        },
+        }),
         events_count: effects.events.len(),
         shared_object_versions: HashMap::new(),
+        object_changes: Vec::new(),
     };
 
     // Compare with on-chain effects using version-aware comparison if versions provided
-    let comparison = tx.effects.as_ref().map(|on_chain| {
+    let comparison = on_chain.map(|on_chain| {
         let mut on_chain_cmp = on_chain.clone();
         let mut local_summary_cmp = local_summary.clone();
         if !tx.inputs.is_empty() {
@@ -1877,7 +2385,7 @@ pub fn replay_with_version_tracking_with_policy_with_effects(
         if !local_transferred.is_empty() {
             eprintln!("[mutations] local transferred ids: {:?}", local_transferred);
         }
-        if let Some(on_chain) = tx.effects.as_ref() {
+        if let Some(on_chain) = on_chain {
             eprintln!(
                 "[mutations] on-chain mutated count={}",
                 on_chain.mutated.len()
@@ -1929,7 +2437,7 @@ pub fn replay_with_version_tracking_with_policy_with_effects(
                     "[mutations] comparison missing={:?} extra={:?}",
                     cmp.mutated_ids_missing, cmp.mutated_ids_extra
                 );
-                if let Some(on_chain) = tx.effects.as_ref() {
+                if let Some(on_chain) = on_chain {
                     let created_set: std::collections::HashSet<_> =
                         on_chain.created.iter().cloned().collect();
                     let extra_in_created: Vec<_> = cmp
@@ -2166,6 +2674,7 @@ pub fn graphql_to_fetched_transaction(
         effects,
         timestamp_ms: tx.timestamp_ms,
         checkpoint: tx.checkpoint,
+        gas_payment: Vec::new(),
     })
 }
 
@@ -2261,6 +2770,7 @@ fn convert_graphql_effects(
         gas_used: GasSummary::default(),
         events_count: 0,
         shared_object_versions: std::collections::HashMap::new(),
+        object_changes: Vec::new(),
     }
 }
 
@@ -2322,6 +2832,452 @@ mod tests {
         assert!(matches!(arg, Argument::NestedResult(2, 1)));
     }
 
+    #[test]
+    fn test_attribute_events_to_transaction_uses_on_chain_call() {
+        let sender = AccountAddress::from_hex_literal("0xa11ce").unwrap();
+        let tx = FetchedTransaction {
+            digest: TransactionDigest::new("events"),
+            sender,
+            gas_budget: 0,
+            gas_price: 0,
+            commands: vec![
+                PtbCommand::SplitCoins {
+                    coin: PtbArgument::GasCoin,
+                    amounts: vec![PtbArgument::Input { index: 0 }],
+                },
+                PtbCommand::MoveCall {
+                    package: "0xbeef".to_string(),
+                    module: "pool".to_string(),
+                    function: "swap".to_string(),
+                    type_arguments: vec![],
+                    arguments: vec![],
+                },
+            ],
+            inputs: vec![],
+            effects: None,
+            timestamp_ms: None,
+            checkpoint: None,
+            gas_payment: vec![],
+        };
+        let event = |command_index| EmittedEvent {
+            type_tag: "0xcafe::pool::Swapped".to_string(),
+            data: vec![],
+            sequence: 0,
+            sender: AccountAddress::ZERO,
+            command_index,
+            // Executor saw the bytecode self-address after alias rewriting.
+            package_id: Some(AccountAddress::from_hex_literal("0xcafe").unwrap()),
+            transaction_module: Some("pool".to_string()),
+        };
+        let mut events = vec![event(Some(1)), event(None)];
+
+        attribute_events_to_transaction(&mut events, &tx);
+
+        assert_eq!(
+            events[0].package_id,
+            Some(AccountAddress::from_hex_literal("0xbeef").unwrap())
+        );
+        assert_eq!(events[0].transaction_module.as_deref(), Some("pool"));
+        assert_eq!(events[0].sender, sender);
+        assert_eq!(
+            events[1].package_id,
+            Some(AccountAddress::from_hex_literal("0xcafe").unwrap())
+        );
+        assert_eq!(events[1].sender, sender);
+    }
+
+    #[test]
+    fn test_ptb_conversion_raises_typed_errors() {
+        let tx = |inputs, package: &str| FetchedTransaction {
+            digest: TransactionDigest::new("typed-errors"),
+            sender: AccountAddress::ZERO,
+            gas_budget: 0,
+            gas_price: 0,
+            commands: vec![PtbCommand::MoveCall {
+                package: package.to_string(),
+                module: "pool".to_string(),
+                function: "swap".to_string(),
+                type_arguments: vec![],
+                arguments: vec![],
+            }],
+            inputs,
+            effects: None,
+            timestamp_ms: None,
+            checkpoint: None,
+            gas_payment: vec![],
+        };
+        let no_objects = std::collections::HashMap::new();
+        let no_aliases = std::collections::HashMap::new();
+
+        let missing = tx(
+            vec![TransactionInput::Object {
+                object_id: "0x6".to_string(),
+                version: 1,
+                digest: "d".to_string(),
+            }],
+            "0x2",
+        );
+        let err = to_ptb_commands_with_objects_and_aliases(&missing, &no_objects, &no_aliases)
+            .unwrap_err();
+        assert_eq!(
+            SandboxError::find(&err),
+            Some(&SandboxError::MissingObject {
+                object_id: "0x6".to_string()
+            })
+        );
+
+        let bad_package = tx(vec![], "not-an-address");
+        let err = to_ptb_commands_with_objects_and_aliases(&bad_package, &no_objects, &no_aliases)
+            .unwrap_err();
+        let error = SandboxError::classify(&err);
+        assert_eq!(error.code(), "invalid_identifier");
+        assert_eq!(
+            error.category(),
+            crate::sandbox_error::SandboxErrorCategory::Resolution
+        );
+    }
+
+    #[test]
+    fn test_replay_empty_make_move_vec_uses_type_annotation() {
+        use crate::resolver::LocalModuleResolver;
+
+        let make_vec_tx = |type_arg: &str| FetchedTransaction {
+            digest: TransactionDigest::new("empty_vec"),
+            sender: AccountAddress::from_hex_literal("0xa11ce").unwrap(),
+            gas_budget: 0,
+            gas_price: 0,
+            commands: vec![PtbCommand::MakeMoveVec {
+                type_arg: Some(type_arg.to_string()),
+                elements: vec![],
+            }],
+            inputs: vec![],
+            effects: None,
+            timestamp_ms: None,
+            checkpoint: None,
+            gas_payment: vec![],
+        };
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let mut harness = VMHarness::new(&resolver, false).unwrap();
+        let no_objects = HashMap::new();
+        let no_aliases = HashMap::new();
+
+        let execution = replay_with_version_tracking_with_policy_with_effects(
+            &make_vec_tx(
+                "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<0x2::sui::SUI>",
+            ),
+            &mut harness,
+            &no_objects,
+            &no_aliases,
+            None,
+            EffectsReconcilePolicy::Strict,
+        )
+        .unwrap();
+
+        assert!(
+            execution.result.local_success,
+            "{:?}",
+            execution.result.local_error
+        );
+        assert_eq!(execution.effects.return_values[0], vec![vec![0u8]]);
+        assert_eq!(
+            execution.effects.return_type_tags[0],
+            vec![Some(TypeTag::Vector(Box::new(
+                crate::well_known::types::sui_coin()
+            )))]
+        );
+
+        let err = to_ptb_commands(&make_vec_tx("0x2::coin::Coin<")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid MakeMoveVec type argument"));
+    }
+
+    #[test]
+    fn test_replay_smashes_multiple_gas_coins() {
+        use crate::resolver::LocalModuleResolver;
+
+        let addr = |s: &str| AccountAddress::from_hex_literal(s).unwrap();
+        let sender = addr("0xa11ce");
+        let coin = |id: &str, balance: u64| {
+            let mut bytes = addr(id).to_vec();
+            bytes.extend_from_slice(&balance.to_le_bytes());
+            sui_sandbox_types::encoding::base64_encode(&bytes)
+        };
+        let cached_objects: HashMap<String, String> = [
+            ("0x91".to_string(), coin("0x91", 600)),
+            ("0x92".to_string(), coin("0x92", 700)),
+        ]
+        .into_iter()
+        .collect();
+        // Neither coin alone covers the split; the smashed coin does after the
+        // 100 MIST budget is reserved.
+        let tx = FetchedTransaction {
+            digest: TransactionDigest::new("smash"),
+            sender,
+            gas_budget: 100,
+            gas_price: 1,
+            commands: vec![
+                PtbCommand::SplitCoins {
+                    coin: PtbArgument::GasCoin,
+                    amounts: vec![PtbArgument::Input { index: 0 }],
+                },
+                PtbCommand::TransferObjects {
+                    objects: vec![PtbArgument::NestedResult {
+                        index: 0,
+                        result_index: 0,
+                    }],
+                    address: PtbArgument::Input { index: 1 },
+                },
+            ],
+            inputs: vec![
+                TransactionInput::Pure {
+                    bytes: 1_000u64.to_le_bytes().to_vec(),
+                },
+                TransactionInput::Pure {
+                    bytes: sender.to_vec(),
+                },
+            ],
+            effects: None,
+            timestamp_ms: None,
+            checkpoint: None,
+            gas_payment: vec![
+                GasPaymentObject {
+                    object_id: "0x91".to_string(),
+                    version: 3,
+                },
+                GasPaymentObject {
+                    object_id: "0x92".to_string(),
+                    version: 5,
+                },
+            ],
+        };
+
+        let (inputs, _) =
+            to_ptb_commands_with_objects_and_aliases(&tx, &cached_objects, &HashMap::new())
+                .unwrap();
+        match &inputs[0] {
+            InputValue::Object(ObjectInput::Owned {
+                id, bytes, version, ..
+            }) => {
+                assert_eq!(*id, addr("0x91"));
+                assert_eq!(bytes[32..], 1_200u64.to_le_bytes());
+                assert_eq!(*version, Some(3));
+            }
+            other => panic!("expected owned gas coin, got {:?}", other),
+        }
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let mut harness = VMHarness::new(&resolver, false).unwrap();
+        let execution = replay_with_version_tracking_with_policy_with_effects(
+            &tx,
+            &mut harness,
+            &cached_objects,
+            &HashMap::new(),
+            None,
+            EffectsReconcilePolicy::Strict,
+        )
+        .unwrap();
+
+        assert!(
+            execution.result.local_success,
+            "{:?}",
+            execution.result.local_error
+        );
+        assert!(execution.effects.mutated.contains(&addr("0x91")));
+        assert!(!execution.effects.mutated.contains(&addr("0x92")));
+        assert_eq!(execution.effects.deleted, vec![addr("0x92")]);
+        assert_eq!(execution.effects.created.len(), 1);
+    }
+
+    #[test]
+    fn test_replay_reports_balance_changes() {
+        use crate::resolver::LocalModuleResolver;
+
+        let addr = |s: &str| AccountAddress::from_hex_literal(s).unwrap();
+        let (sender, recipient) = (addr("0xa11ce"), addr("0xb0b"));
+        let mut coin = addr("0x91").to_vec();
+        coin.extend_from_slice(&5_000u64.to_le_bytes());
+        let cached_objects: HashMap<String, String> = [(
+            "0x91".to_string(),
+            sui_sandbox_types::encoding::base64_encode(&coin),
+        )]
+        .into_iter()
+        .collect();
+        let tx = FetchedTransaction {
+            digest: TransactionDigest::new("pay"),
+            sender,
+            gas_budget: 100,
+            gas_price: 1,
+            commands: vec![
+                PtbCommand::SplitCoins {
+                    coin: PtbArgument::GasCoin,
+                    amounts: vec![PtbArgument::Input { index: 0 }],
+                },
+                PtbCommand::TransferObjects {
+                    objects: vec![PtbArgument::NestedResult {
+                        index: 0,
+                        result_index: 0,
+                    }],
+                    address: PtbArgument::Input { index: 1 },
+                },
+            ],
+            inputs: vec![
+                TransactionInput::Pure {
+                    bytes: 1_000u64.to_le_bytes().to_vec(),
+                },
+                TransactionInput::Pure {
+                    bytes: recipient.to_vec(),
+                },
+            ],
+            effects: None,
+            timestamp_ms: None,
+            checkpoint: None,
+            gas_payment: vec![GasPaymentObject {
+                object_id: "0x91".to_string(),
+                version: 3,
+            }],
+        };
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let mut harness = VMHarness::new(&resolver, false).unwrap();
+        let execution = replay_with_version_tracking_with_policy_with_effects(
+            &tx,
+            &mut harness,
+            &cached_objects,
+            &HashMap::new(),
+            None,
+            EffectsReconcilePolicy::Strict,
+        )
+        .unwrap();
+        assert!(execution.result.local_success);
+
+        let gas_cost = execution
+            .effects
+            .gas_summary
+            .as_ref()
+            .map_or(0, |gas| gas.total() as i128);
+        let sui = "0x2::sui::SUI".to_string();
+        assert_eq!(
+            execution.effects.balance_changes,
+            vec![
+                BalanceChange {
+                    owner: recipient.to_hex_literal(),
+                    coin_type: sui.clone(),
+                    amount: 1_000,
+                },
+                BalanceChange {
+                    owner: sender.to_hex_literal(),
+                    coin_type: sui,
+                    amount: -1_000 - gas_cost,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_effects_fingerprint_matches_on_chain_modulo_gas_payment() {
+        let addr = |s: &str| AccountAddress::from_hex_literal(s).unwrap();
+        let mut tx = FetchedTransaction {
+            digest: TransactionDigest::new("effects"),
+            sender: addr("0xa11ce"),
+            gas_budget: 0,
+            gas_price: 0,
+            commands: vec![],
+            inputs: vec![TransactionInput::SharedObject {
+                object_id: "0xa".to_string(),
+                initial_shared_version: 1,
+                mutable: true,
+            }],
+            effects: Some(TransactionEffectsSummary {
+                status: TransactionStatus::Success,
+                created: vec!["0xc".to_string()],
+                // 0x9 is the gas coin: mutated on-chain, never touched locally.
+                mutated: vec!["0x9".to_string(), "0xa".to_string()],
+                deleted: vec![],
+                wrapped: vec![],
+                unwrapped: vec![],
+                gas_used: GasSummary::default(),
+                events_count: 1,
+                shared_object_versions: HashMap::new(),
+                object_changes: Vec::new(),
+            }),
+            timestamp_ms: None,
+            checkpoint: None,
+            gas_payment: vec![GasPaymentObject {
+                object_id: "0x9".to_string(),
+                version: 4,
+            }],
+        };
+        let mut local = crate::ptb::TransactionEffects {
+            success: true,
+            created: vec![addr("0xc")],
+            mutated: vec![addr("0xa")],
+            events: vec![EmittedEvent {
+                type_tag: "0xcafe::pool::Swapped".to_string(),
+                data: vec![],
+                sequence: 0,
+                sender: AccountAddress::ZERO,
+                command_index: Some(0),
+                package_id: None,
+                transaction_module: None,
+            }],
+            ..Default::default()
+        };
+
+        let local_fingerprint = local_effects_fingerprint(&tx, &local).digest();
+        let onchain = || onchain_effects_fingerprint(&tx).map(|f| f.digest());
+        assert_eq!(onchain(), Some(local_fingerprint.clone()));
+
+        // A local run that charged the gas coin reports it too; it is still excluded.
+        local.mutated.push(addr("0x9"));
+        assert_eq!(
+            local_effects_fingerprint(&tx, &local).digest(),
+            local_fingerprint
+        );
+        local.mutated.pop();
+
+        // Only the recorded gas payment is excluded, not every object the replay missed.
+        let mut with_missed = tx.clone();
+        with_missed
+            .effects
+            .as_mut()
+            .unwrap()
+            .mutated
+            .push("0xb".to_string());
+        let missed = onchain_effects_fingerprint(&with_missed).unwrap();
+        let diff = local_effects_fingerprint(&with_missed, &local).diff(&missed);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].field, "mutated");
+
+        local.created.clear();
+        assert_ne!(
+            local_effects_fingerprint(&tx, &local).digest(),
+            local_fingerprint
+        );
+        assert_ne!(
+            onchain(),
+            Some(local_effects_fingerprint(&tx, &local).digest())
+        );
+
+        let baseline = onchain_effects_fingerprint(&tx).expect("on-chain fingerprint");
+        let diff = local_effects_fingerprint(&tx, &local).diff(&baseline);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].field, "created");
+        assert_eq!(
+            diff[0].expected,
+            serde_json::json!([addr("0xc").to_hex_literal()])
+        );
+        assert_eq!(diff[0].actual, serde_json::json!([]));
+
+        // Without a recorded gas payment the gas coin cannot be told apart.
+        tx.gas_payment.clear();
+        local.created.push(addr("0xc"));
+        assert_ne!(
+            onchain_effects_fingerprint(&tx).map(|f| f.digest()),
+            Some(local_effects_fingerprint(&tx, &local).digest())
+        );
+    }
+
     #[test]
     fn test_transaction_status_serialization() {
         let success = TransactionStatus::Success;
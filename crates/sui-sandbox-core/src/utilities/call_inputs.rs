@@ -0,0 +1,441 @@
+//! Call input planning from a function ABI and JSON arguments.
+//!
+//! [`plan_call_inputs`] looks up a function in compiled bytecode, checks the
+//! supplied type arguments and JSON arguments against its signature, and turns
+//! each argument into either pure BCS bytes or an object reference to fetch. A
+//! trailing `&TxContext`/`&mut TxContext` parameter is supplied by the VM and is
+//! not expected in the arguments.
+//!
+//! A parameter is an object when its type (after stripping a reference) has the
+//! `key` ability, or is a type parameter constrained by `key`; object arguments
+//! are given as object ID strings. Everything else is pure and is converted with
+//! [`JsonToBcsConverter`], so structs such as `String` or `Option<T>` accept the
+//! same JSON shapes as object reconstruction.
+
+use anyhow::{anyhow, Context, Result};
+use move_binary_format::file_format::{
+    AbilitySet, DatatypeHandleIndex, SignatureToken, Visibility,
+};
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use super::generic_patcher::MoveType;
+use super::json_to_bcs::{parse_hex_address, JsonToBcsConverter};
+
+/// How a planned argument is passed to the function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallArgumentKind {
+    Pure,
+    ObjectImmRef,
+    ObjectMutRef,
+    ObjectByValue,
+}
+
+impl CallArgumentKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pure => "pure",
+            Self::ObjectImmRef => "object_imm_ref",
+            Self::ObjectMutRef => "object_mut_ref",
+            Self::ObjectByValue => "object_by_value",
+        }
+    }
+
+    pub fn is_object(&self) -> bool {
+        !matches!(self, Self::Pure)
+    }
+}
+
+/// One planned function argument.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallArgumentPlan {
+    pub index: usize,
+    /// Canonical parameter type with type arguments substituted (references stripped).
+    pub type_tag: String,
+    pub kind: CallArgumentKind,
+    /// Object to fetch, for object arguments.
+    pub object_id: Option<AccountAddress>,
+    /// BCS bytes, for pure arguments.
+    pub pure_bytes: Option<Vec<u8>>,
+}
+
+/// Planned inputs for a single function call.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallInputsPlan {
+    pub arguments: Vec<CallArgumentPlan>,
+    /// Whether the function takes a trailing `TxContext` supplied by the VM.
+    pub takes_tx_context: bool,
+}
+
+/// Plan the inputs for calling `module_id::function<type_args>(args)`.
+///
+/// `modules` must contain the target module and the modules defining any struct
+/// types used by pure arguments. Errors name the offending argument index and type.
+pub fn plan_call_inputs(
+    modules: &[CompiledModule],
+    module_id: &ModuleId,
+    function: &str,
+    type_args: &[TypeTag],
+    args: &[JsonValue],
+) -> Result<CallInputsPlan> {
+    let module = modules
+        .iter()
+        .find(|m| m.self_id() == *module_id)
+        .ok_or_else(|| anyhow!("module {} not found", module_id))?;
+    let func_def = module
+        .function_defs
+        .iter()
+        .find(|def| {
+            module
+                .identifier_at(module.function_handle_at(def.function).name)
+                .as_str()
+                == function
+        })
+        .ok_or_else(|| anyhow!("function {}::{} not found", module_id, function))?;
+    if func_def.visibility != Visibility::Public && !func_def.is_entry {
+        return Err(anyhow!(
+            "function {}::{} is neither public nor entry",
+            module_id,
+            function
+        ));
+    }
+    let handle = module.function_handle_at(func_def.function);
+    if handle.type_parameters.len() != type_args.len() {
+        return Err(anyhow!(
+            "function {}::{} expects {} type argument(s), got {}",
+            module_id,
+            function,
+            handle.type_parameters.len(),
+            type_args.len()
+        ));
+    }
+
+    let mut params: &[SignatureToken] = &module.signature_at(handle.parameters).0;
+    let takes_tx_context = params.last().is_some_and(|t| is_tx_context(module, t));
+    if takes_tx_context {
+        params = &params[..params.len() - 1];
+    }
+    if params.len() != args.len() {
+        let expected = params
+            .iter()
+            .map(|t| instantiate(module, t, type_args).map(|tag| tag.to_canonical_string(true)))
+            .collect::<Result<Vec<_>>>()?;
+        return Err(anyhow!(
+            "function {}::{} expects {} argument(s) ({}), got {}",
+            module_id,
+            function,
+            params.len(),
+            expected.join(", "),
+            args.len()
+        ));
+    }
+
+    let mut converter = JsonToBcsConverter::new();
+    converter.add_modules(modules);
+    let mut arguments = Vec::with_capacity(params.len());
+    for (index, (token, arg)) in params.iter().zip(args).enumerate() {
+        let (inner, kind) = match token {
+            SignatureToken::Reference(inner) => (inner.as_ref(), CallArgumentKind::ObjectImmRef),
+            SignatureToken::MutableReference(inner) => {
+                (inner.as_ref(), CallArgumentKind::ObjectMutRef)
+            }
+            other => (other, CallArgumentKind::ObjectByValue),
+        };
+        let tag =
+            instantiate(module, inner, type_args).with_context(|| format!("argument {}", index))?;
+        let type_tag = tag.to_canonical_string(true);
+        if has_key(module, handle.type_parameters.as_slice(), inner) {
+            let id = arg
+                .as_str()
+                .ok_or_else(|| {
+                    anyhow!(
+                        "argument {} ({}): expected an object ID string, got {}",
+                        index,
+                        type_tag,
+                        arg
+                    )
+                })
+                .and_then(|s| {
+                    parse_hex_address(s)
+                        .map(AccountAddress::new)
+                        .map_err(|e| anyhow!("argument {} ({}): {}", index, type_tag, e))
+                })?;
+            arguments.push(CallArgumentPlan {
+                index,
+                type_tag,
+                kind,
+                object_id: Some(id),
+                pure_bytes: None,
+            });
+            continue;
+        }
+        if kind != CallArgumentKind::ObjectByValue {
+            return Err(anyhow!(
+                "argument {} ({}): references to non-object types cannot be passed as inputs",
+                index,
+                type_tag
+            ));
+        }
+        let bytes = converter
+            .convert_value(
+                &type_tag_to_move_type(&tag),
+                arg,
+                &format!("argument {}", index),
+            )
+            .map_err(|e| anyhow!("argument {} ({}): {:#}", index, type_tag, e))?;
+        arguments.push(CallArgumentPlan {
+            index,
+            type_tag,
+            kind: CallArgumentKind::Pure,
+            object_id: None,
+            pure_bytes: Some(bytes),
+        });
+    }
+
+    Ok(CallInputsPlan {
+        arguments,
+        takes_tx_context,
+    })
+}
+
+fn is_tx_context(module: &CompiledModule, token: &SignatureToken) -> bool {
+    let inner = match token {
+        SignatureToken::Reference(inner) | SignatureToken::MutableReference(inner) => inner,
+        _ => return false,
+    };
+    let SignatureToken::Datatype(idx) = inner.as_ref() else {
+        return false;
+    };
+    let handle = module.datatype_handle_at(*idx);
+    let module_handle = module.module_handle_at(handle.module);
+    *module.address_identifier_at(module_handle.address) == AccountAddress::TWO
+        && module.identifier_at(module_handle.name).as_str() == "tx_context"
+        && module.identifier_at(handle.name).as_str() == "TxContext"
+}
+
+fn has_key(
+    module: &CompiledModule,
+    type_parameters: &[AbilitySet],
+    token: &SignatureToken,
+) -> bool {
+    match token {
+        SignatureToken::Datatype(idx) => module.datatype_handle_at(*idx).abilities.has_key(),
+        SignatureToken::DatatypeInstantiation(inst) => {
+            module.datatype_handle_at(inst.0).abilities.has_key()
+        }
+        SignatureToken::TypeParameter(idx) => type_parameters
+            .get(*idx as usize)
+            .is_some_and(|abilities| abilities.has_key()),
+        _ => false,
+    }
+}
+
+/// Resolve a signature token to a concrete type, substituting `type_args`.
+fn instantiate(
+    module: &CompiledModule,
+    token: &SignatureToken,
+    type_args: &[TypeTag],
+) -> Result<TypeTag> {
+    Ok(match token {
+        SignatureToken::Bool => TypeTag::Bool,
+        SignatureToken::U8 => TypeTag::U8,
+        SignatureToken::U16 => TypeTag::U16,
+        SignatureToken::U32 => TypeTag::U32,
+        SignatureToken::U64 => TypeTag::U64,
+        SignatureToken::U128 => TypeTag::U128,
+        SignatureToken::U256 => TypeTag::U256,
+        SignatureToken::Address => TypeTag::Address,
+        SignatureToken::Signer => TypeTag::Signer,
+        SignatureToken::Vector(inner) => {
+            TypeTag::Vector(Box::new(instantiate(module, inner, type_args)?))
+        }
+        SignatureToken::Datatype(idx) => struct_tag(module, *idx, vec![]),
+        SignatureToken::DatatypeInstantiation(inst) => {
+            let (idx, tokens) = inst.as_ref();
+            let args = tokens
+                .iter()
+                .map(|t| instantiate(module, t, type_args))
+                .collect::<Result<Vec<_>>>()?;
+            struct_tag(module, *idx, args)
+        }
+        SignatureToken::TypeParameter(idx) => type_args
+            .get(*idx as usize)
+            .cloned()
+            .ok_or_else(|| anyhow!("type parameter {} is out of range", idx))?,
+        SignatureToken::Reference(_) | SignatureToken::MutableReference(_) => {
+            return Err(anyhow!("nested reference types are not supported"))
+        }
+    })
+}
+
+fn struct_tag(
+    module: &CompiledModule,
+    idx: DatatypeHandleIndex,
+    type_params: Vec<TypeTag>,
+) -> TypeTag {
+    let handle = module.datatype_handle_at(idx);
+    let module_handle = module.module_handle_at(handle.module);
+    TypeTag::Struct(Box::new(StructTag {
+        address: *module.address_identifier_at(module_handle.address),
+        module: module.identifier_at(module_handle.name).to_owned(),
+        name: module.identifier_at(handle.name).to_owned(),
+        type_params,
+    }))
+}
+
+/// Encode one pure PTB argument of type `type_tag` from JSON.
+///
+/// Accepts the same JSON shapes as [`plan_call_inputs`]; `modules` supply the
+/// layouts of struct types other than the framework wrappers the converter
+/// already knows (`String`, `Option`, `ID`, ...).
+pub fn encode_pure_argument(
+    type_tag: &TypeTag,
+    value: &JsonValue,
+    modules: &[CompiledModule],
+) -> Result<Vec<u8>> {
+    let mut converter = JsonToBcsConverter::new();
+    converter.add_modules(modules);
+    converter
+        .convert_value(&type_tag_to_move_type(type_tag), value, "pure argument")
+        .with_context(|| format!("pure argument ({})", type_tag.to_canonical_string(true)))
+}
+
+fn type_tag_to_move_type(tag: &TypeTag) -> MoveType {
+    match tag {
+        TypeTag::Bool => MoveType::Bool,
+        TypeTag::U8 => MoveType::U8,
+        TypeTag::U16 => MoveType::U16,
+        TypeTag::U32 => MoveType::U32,
+        TypeTag::U64 => MoveType::U64,
+        TypeTag::U128 => MoveType::U128,
+        TypeTag::U256 => MoveType::U256,
+        TypeTag::Address => MoveType::Address,
+        TypeTag::Signer => MoveType::Signer,
+        TypeTag::Vector(inner) => MoveType::Vector(Box::new(type_tag_to_move_type(inner))),
+        TypeTag::Struct(tag) => MoveType::Struct {
+            address: tag.address,
+            module: tag.module.to_string(),
+            name: tag.name.to_string(),
+            type_args: tag.type_params.iter().map(type_tag_to_move_type).collect(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver::LocalModuleResolver;
+    use move_core_types::identifier::Identifier;
+    use serde_json::json;
+
+    fn framework_modules() -> Vec<CompiledModule> {
+        LocalModuleResolver::with_sui_framework()
+            .unwrap()
+            .iter_modules()
+            .cloned()
+            .collect()
+    }
+
+    fn sui_coin_type() -> TypeTag {
+        TypeTag::Struct(Box::new(StructTag {
+            address: AccountAddress::TWO,
+            module: Identifier::new("sui").unwrap(),
+            name: Identifier::new("SUI").unwrap(),
+            type_params: vec![],
+        }))
+    }
+
+    fn coin_module() -> ModuleId {
+        ModuleId::new(AccountAddress::TWO, Identifier::new("coin").unwrap())
+    }
+
+    #[test]
+    fn test_plan_call_inputs_splits_objects_and_pure_args() {
+        let modules = framework_modules();
+        let plan = plan_call_inputs(
+            &modules,
+            &coin_module(),
+            "split",
+            &[sui_coin_type()],
+            &[json!("0x5"), json!(100)],
+        )
+        .unwrap();
+
+        assert!(plan.takes_tx_context);
+        assert_eq!(plan.arguments.len(), 2);
+        assert_eq!(plan.arguments[0].kind, CallArgumentKind::ObjectMutRef);
+        assert_eq!(
+            plan.arguments[0].object_id,
+            Some(AccountAddress::from_hex_literal("0x5").unwrap())
+        );
+        let coin_type = &plan.arguments[0].type_tag;
+        assert!(coin_type.contains("::coin::Coin<") && coin_type.ends_with("::sui::SUI>"));
+        assert_eq!(plan.arguments[1].kind, CallArgumentKind::Pure);
+        assert_eq!(
+            plan.arguments[1].pure_bytes,
+            Some(100u64.to_le_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn test_encode_pure_argument() {
+        assert_eq!(
+            encode_pure_argument(&TypeTag::U64, &json!("42"), &[]).unwrap(),
+            42u64.to_le_bytes().to_vec()
+        );
+        assert_eq!(
+            encode_pure_argument(&TypeTag::Address, &json!("0x2"), &[]).unwrap(),
+            AccountAddress::TWO.to_vec()
+        );
+        let err = encode_pure_argument(&TypeTag::U8, &json!(256), &[]).unwrap_err();
+        assert!(format!("{:#}", err).contains("out of range for u8"));
+    }
+
+    #[test]
+    fn test_plan_call_inputs_reports_argument_errors() {
+        let modules = framework_modules();
+        let err = plan_call_inputs(
+            &modules,
+            &coin_module(),
+            "split",
+            &[sui_coin_type()],
+            &[json!("0x5")],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("expects 2 argument(s)"));
+
+        let err = plan_call_inputs(
+            &modules,
+            &coin_module(),
+            "split",
+            &[sui_coin_type()],
+            &[json!("0x5"), json!("not a number")],
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with("argument 1 (u64)"));
+
+        let err = plan_call_inputs(
+            &modules,
+            &coin_module(),
+            "split",
+            &[],
+            &[json!("0x5"), json!(1)],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("type argument"));
+
+        let err = plan_call_inputs(
+            &modules,
+            &coin_module(),
+            "split",
+            &[sui_coin_type()],
+            &[json!(5), json!(1)],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("expected an object ID string"));
+    }
+}
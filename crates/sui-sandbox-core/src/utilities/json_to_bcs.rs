@@ -95,6 +95,21 @@ impl JsonToBcsConverter {
         Ok(bcs_bytes)
     }
 
+    /// Convert a JSON value of any Move type (primitive, vector, or struct) to BCS bytes.
+    ///
+    /// `name` labels the value in error messages.
+    pub fn convert_value(
+        &mut self,
+        move_type: &MoveType,
+        json: &JsonValue,
+        name: &str,
+    ) -> Result<Vec<u8>> {
+        let value = self.convert_field(json, move_type, name)?;
+        BcsEncoder::new()
+            .encode(&value)
+            .with_context(|| format!("Failed to encode {} to BCS", name))
+    }
+
     /// Substitute type parameters in a MoveType using the provided type arguments.
     fn substitute_type_params(&self, move_type: &MoveType, type_args: &[MoveType]) -> MoveType {
         match move_type {
@@ -177,15 +192,21 @@ impl JsonToBcsConverter {
                 Ok(DynamicValue::Bool(v))
             }
             MoveType::U8 => {
-                let v = parse_json_number_u64(json, field_name)? as u8;
+                let v = parse_json_number_u64(json, field_name)?;
+                let v = u8::try_from(v)
+                    .map_err(|_| anyhow!("Value {} out of range for u8 in {}", v, field_name))?;
                 Ok(DynamicValue::U8(v))
             }
             MoveType::U16 => {
-                let v = parse_json_number_u64(json, field_name)? as u16;
+                let v = parse_json_number_u64(json, field_name)?;
+                let v = u16::try_from(v)
+                    .map_err(|_| anyhow!("Value {} out of range for u16 in {}", v, field_name))?;
                 Ok(DynamicValue::U16(v))
             }
             MoveType::U32 => {
-                let v = parse_json_number_u64(json, field_name)? as u32;
+                let v = parse_json_number_u64(json, field_name)?;
+                let v = u32::try_from(v)
+                    .map_err(|_| anyhow!("Value {} out of range for u32 in {}", v, field_name))?;
                 Ok(DynamicValue::U32(v))
             }
             MoveType::U64 => {
@@ -1062,6 +1083,23 @@ mod tests {
         assert_eq!(v, 787937890670812057358292u128);
     }
 
+    #[test]
+    fn test_convert_value_primitives_and_range_checks() {
+        let mut converter = JsonToBcsConverter::new();
+        let bytes = converter
+            .convert_value(
+                &MoveType::Vector(Box::new(MoveType::U16)),
+                &serde_json::json!([1, "2"]),
+                "arg",
+            )
+            .unwrap();
+        assert_eq!(bytes, vec![2, 1, 0, 2, 0]);
+        let err = converter
+            .convert_value(&MoveType::U8, &serde_json::json!(300), "arg")
+            .unwrap_err();
+        assert!(err.to_string().contains("out of range for u8"));
+    }
+
     #[test]
     fn test_parse_json_u256_from_decimal() {
         // Test the Suilend Decimal value that was failing
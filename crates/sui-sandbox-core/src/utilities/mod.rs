@@ -41,9 +41,12 @@
 //! - [`historical_state`]: High-level facade for historical state reconstruction
 //! - [`historical_package`]: Package resolution following linkage tables
 //! - [`bcs_scanner`]: Extract embedded addresses from BCS object data
+//! - [`call_inputs`]: Function call input planning from ABI and JSON arguments
+//! - [`package_id`]: Package ID derivation for newly published bytecode
 
 pub mod address;
 pub mod bcs_scanner;
+pub mod call_inputs;
 pub mod enhanced_patcher;
 pub mod generic_patcher;
 pub mod historical_bytecode;
@@ -52,6 +55,7 @@ pub mod historical_state;
 pub mod historical_version_finder;
 pub mod json_to_bcs;
 pub mod offset_calculator;
+pub mod package_id;
 pub mod package_roots;
 pub mod type_utils;
 pub mod version_field_detector;
@@ -59,12 +63,16 @@ pub mod version_utils;
 
 // Re-export commonly used items
 pub use address::{is_framework_package, normalize_address};
+pub use call_inputs::{
+    encode_pure_argument, plan_call_inputs, CallArgumentKind, CallArgumentPlan, CallInputsPlan,
+};
 pub use generic_patcher::{FieldPatchRule, GenericObjectPatcher, PatchAction, PatchCondition};
 pub use json_to_bcs::{
     validate_json_bcs_reconstruction, JsonBcsValidationEntry, JsonBcsValidationObject,
     JsonBcsValidationPlan, JsonBcsValidationReport, JsonBcsValidationStatus,
     JsonBcsValidationSummary, JsonToBcsConverter,
 };
+pub use package_id::{compute_package_id, validate_publish_modules, PackageIdDerivation};
 pub use package_roots::{
     collect_required_package_roots_from_type_strings,
     collect_required_package_roots_from_type_tags, unresolved_package_dependencies_for_modules,
@@ -0,0 +1,132 @@
+//! Package ID derivation for newly published bytecode.
+//!
+//! Sui assigns a fresh package the next object ID of the publishing transaction:
+//! `derive_id(tx_digest, ids_created)`, the same derivation `tx_context::fresh_id`
+//! uses. The module bytes do not enter the hash; the transaction digest does, and it
+//! commits to the sender, gas data and every command, so a sender alone is not enough
+//! to predict the ID. `ids_created` is the number of IDs the transaction created
+//! before the Publish command (0 when Publish is the first command to create one).
+//!
+//! Upgrades are different: the new package gets a fresh storage ID the same way, but
+//! types keep referring to the original (runtime) ID, which never changes.
+
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use serde::Serialize;
+use sui_types::base_types::ObjectID as SuiObjectID;
+use sui_types::digests::TransactionDigest as SuiTransactionDigest;
+
+/// Package ID a Publish would be assigned, with the inputs it was derived from.
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageIdDerivation {
+    pub package_id: AccountAddress,
+    pub tx_digest: String,
+    pub ids_created: u64,
+    /// Module names in the order given.
+    pub modules: Vec<String>,
+}
+
+/// Check that `modules` form a publishable package: at least one module, every module
+/// deserializes, names are unique, and each is compiled at the `0x0` self-address
+/// placeholder that Publish rewrites to the new package ID.
+pub fn validate_publish_modules(modules: &[Vec<u8>]) -> Result<Vec<String>> {
+    if modules.is_empty() {
+        return Err(anyhow!("publish requires at least one module"));
+    }
+    let mut names = Vec::with_capacity(modules.len());
+    let mut seen = BTreeSet::new();
+    for (idx, bytes) in modules.iter().enumerate() {
+        let module = CompiledModule::deserialize_with_defaults(bytes)
+            .with_context(|| format!("module {} does not deserialize", idx))?;
+        let module_id = module.self_id();
+        let name = module_id.name().to_string();
+        if *module_id.address() != AccountAddress::ZERO {
+            return Err(anyhow!(
+                "module {} ({}) is compiled at {}, expected the 0x0 placeholder",
+                idx,
+                name,
+                module_id.address().to_hex_literal()
+            ));
+        }
+        if !seen.insert(name.clone()) {
+            return Err(anyhow!("duplicate module name {}", name));
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+/// Compute the package ID Sui assigns when `modules` are published by the
+/// transaction `tx_digest` after it created `ids_created` objects.
+pub fn compute_package_id(
+    modules: &[Vec<u8>],
+    tx_digest: &str,
+    ids_created: u64,
+) -> Result<PackageIdDerivation> {
+    let names = validate_publish_modules(modules)?;
+    let digest = SuiTransactionDigest::from_str(tx_digest)
+        .map_err(|e| anyhow!("invalid transaction digest {}: {}", tx_digest, e))?;
+    let package_id = SuiObjectID::derive_id(digest, ids_created);
+    Ok(PackageIdDerivation {
+        package_id: AccountAddress::new(package_id.into_bytes()),
+        tx_digest: digest.to_string(),
+        ids_created,
+        modules: names,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolver::LocalModuleResolver;
+
+    fn framework_coin_module() -> CompiledModule {
+        LocalModuleResolver::with_sui_framework()
+            .unwrap()
+            .iter_modules()
+            .find(|m| *m.self_id().address() == AccountAddress::TWO && m.name().as_str() == "coin")
+            .unwrap()
+            .clone()
+    }
+
+    fn serialize(module: &CompiledModule) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        module
+            .serialize_with_version(module.version, &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_compute_package_id_follows_fresh_id_derivation() {
+        let digest = SuiTransactionDigest::new([7u8; 32]);
+        let mut module = framework_coin_module();
+        let self_handle = module.self_handle_idx().0 as usize;
+        let address_idx = module.module_handles[self_handle].address.0 as usize;
+        module.address_identifiers[address_idx] = AccountAddress::ZERO;
+        let bytes = serialize(&module);
+
+        let derived = compute_package_id(&[bytes.clone()], &digest.to_string(), 0).unwrap();
+        assert_eq!(
+            derived.package_id,
+            AccountAddress::new(SuiObjectID::derive_id(digest, 0).into_bytes())
+        );
+        assert_eq!(derived.modules, vec!["coin".to_string()]);
+        let next = compute_package_id(&[bytes.clone()], &digest.to_string(), 1).unwrap();
+        assert_ne!(next.package_id, derived.package_id);
+
+        assert!(compute_package_id(&[bytes.clone(), bytes], &digest.to_string(), 0).is_err());
+        assert!(compute_package_id(&[], &digest.to_string(), 0).is_err());
+    }
+
+    #[test]
+    fn test_validate_publish_modules_rejects_non_placeholder_address() {
+        let bytes = serialize(&framework_coin_module());
+        let err = validate_publish_modules(&[bytes]).unwrap_err();
+        assert!(err.to_string().contains("expected the 0x0 placeholder"));
+    }
+}
@@ -27,6 +27,7 @@ use move_core_types::gas_algebra::{InternalGas, NumArgs, NumBytes};
 use move_core_types::language_storage::{ModuleId, TypeTag};
 use move_core_types::resolver::{LinkageResolver, ModuleResolver};
 use move_core_types::vm_status::StatusCode;
+use move_trace_format::format::MoveTraceBuilder;
 use move_vm_runtime::move_vm::MoveVM;
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 use move_vm_types::gas::{GasMeter, SimpleInstruction, UnmeteredGasMeter};
@@ -35,11 +36,19 @@ use parking_lot::Mutex;
 use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
+use crate::call_trace::CallTraceLog;
+use crate::execution_trace::ExecutionTraceLog;
 use crate::gas::{
     bucketize_computation, AccurateGasMeter, GasParameters, GasSummary, GasSummaryBuilder,
     StorageTracker,
 };
-use crate::natives::{build_native_function_table, EmittedEvent, MockNativeState};
+use crate::gas_profile::{
+    qualified_function_name, FrameGasProfiler, GasProfileLog, ProfilingGasMeter,
+};
+use crate::natives::{
+    build_native_function_table, stub_unknown_natives, EmittedEvent, MockNativeState,
+    StubbedNative, StubbedNativeLog,
+};
 use crate::resolver::LocalModuleResolver;
 use crate::sandbox_runtime::{
     ChildFetcherFn, ChildIdAliasMap, KeyBasedChildFetcherFn, KeyTypeResolverFn, ObjectRuntimeState,
@@ -218,6 +227,14 @@ pub struct SimulationConfig {
     /// Features are generally enabled when `protocol_version >= 60`.
     pub protocol_version: u64,
 
+    /// Run Sui natives with the `ProtocolConfig` of `protocol_version` (default: false).
+    ///
+    /// By default the native function table and native extensions use the latest
+    /// `ProtocolConfig`, so newer feature flags are always on. Historical replays pin
+    /// the transaction's version so natives see the feature flags of its epoch.
+    #[serde(default)]
+    pub pin_protocol_config: bool,
+
     /// Storage price per unit in MIST (default: 76).
     ///
     /// Used to calculate storage rebates when objects are deleted.
@@ -267,6 +284,44 @@ pub struct SimulationConfig {
     /// Default: `Sandbox` for backward compatibility.
     #[serde(default)]
     pub child_resolution_mode: crate::sui_object_runtime::ChildResolutionMode,
+
+    /// Stub natives that cannot run locally instead of aborting (default: false).
+    ///
+    /// Degraded analysis mode: unsupported or unregistered natives return zero
+    /// values for their return types, and each stubbed native is recorded (see
+    /// [`VMHarness::stubbed_natives`]). Results after the first stubbed call are
+    /// not faithful.
+    #[serde(default)]
+    pub stub_unknown_natives: bool,
+
+    /// User-supplied gas cost table (optional, requires `accurate_gas`).
+    ///
+    /// Replaces the protocol's instruction tiers and native costs where specified;
+    /// everything else keeps the protocol default. For modeling gas reprices.
+    /// [`Self::gas_table_coverage`] reports the entries this configuration cannot charge.
+    #[serde(default)]
+    pub gas_table: Option<crate::gas::CustomGasTable>,
+
+    /// Record an instruction-level execution trace (default: off).
+    ///
+    /// Every Move call runs with a VM tracer and the events are kept in
+    /// [`VMHarness::execution_trace`]. Expensive: one entry per executed instruction.
+    #[serde(default)]
+    pub execution_trace: Option<crate::execution_trace::ExecutionTraceOptions>,
+
+    /// Record the Move call tree of every command (default: off).
+    ///
+    /// Frames are kept in [`VMHarness::call_trace`]; the PTB executor groups them
+    /// per command into `TransactionEffects::call_trace`.
+    #[serde(default)]
+    pub call_trace: Option<crate::call_trace::CallTraceOptions>,
+
+    /// Attribute gas to PTB commands and Move functions (default: off).
+    ///
+    /// Every Move call runs through a [`ProfilingGasMeter`] and the PTB executor
+    /// reports the result in `TransactionEffects::gas_profile`.
+    #[serde(default)]
+    pub gas_profile: bool,
 }
 
 // Re-use protocol and gas constants from the gas module (single source of truth)
@@ -297,11 +352,17 @@ impl Default for SimulationConfig {
             reference_gas_price: DEFAULT_REFERENCE_GAS_PRICE,
             gas_price: DEFAULT_REFERENCE_GAS_PRICE, // No tip by default
             protocol_version: DEFAULT_PROTOCOL_VERSION,
+            pin_protocol_config: false,
             storage_price: DEFAULT_STORAGE_PRICE,
             track_versions: false,   // Opt-in for backwards compatibility
             accurate_gas: true,      // Default to accurate gas for improved fidelity
             replay_checkpoint: None, // Not in replay mode by default
             child_resolution_mode: crate::sui_object_runtime::ChildResolutionMode::Sandbox,
+            stub_unknown_natives: false,
+            gas_table: None,
+            execution_trace: None,
+            call_trace: None,
+            gas_profile: false,
         }
     }
 }
@@ -341,11 +402,17 @@ impl SimulationConfig {
             reference_gas_price: DEFAULT_REFERENCE_GAS_PRICE,
             gas_price: DEFAULT_REFERENCE_GAS_PRICE,
             protocol_version: DEFAULT_PROTOCOL_VERSION,
+            pin_protocol_config: false,
             storage_price: DEFAULT_STORAGE_PRICE,
             track_versions: false,   // Opt-in feature
             accurate_gas: true,      // Strict mode uses accurate gas
             replay_checkpoint: None, // Not in replay mode by default
             child_resolution_mode: crate::sui_object_runtime::ChildResolutionMode::Sandbox,
+            stub_unknown_natives: false,
+            gas_table: None,
+            execution_trace: None,
+            call_trace: None,
+            gas_profile: false,
         }
     }
 
@@ -396,6 +463,71 @@ impl SimulationConfig {
         self
     }
 
+    /// Builder method: stub natives that cannot run locally (degraded analysis mode).
+    pub fn with_stub_unknown_natives(mut self, stub: bool) -> Self {
+        self.stub_unknown_natives = stub;
+        self
+    }
+
+    /// Builder method: charge gas from a custom cost table (see [`crate::gas::CustomGasTable`]).
+    pub fn with_gas_table(mut self, table: Option<crate::gas::CustomGasTable>) -> Self {
+        self.gas_table = table;
+        self
+    }
+
+    /// Coverage of `gas_table`, listing under `unapplied` the custom entries this
+    /// configuration does not charge.
+    ///
+    /// Tiers and native costs are only charged by the accurate gas meter (`accurate_gas`
+    /// with a budget). Native overrides reach the sandbox natives only: Sui's natives
+    /// (`use_sui_natives`) charge from the protocol's native cost table.
+    pub fn gas_table_coverage(&self) -> Option<crate::gas::GasTableCoverage> {
+        const NOT_METERED: &str = "requires accurate_gas and a gas budget";
+        const SUI_NATIVES: &str = "Sui natives charge the protocol native cost table";
+
+        let mut coverage = self.gas_table.as_ref()?.coverage();
+        let metered = self.accurate_gas && self.gas_budget.is_some();
+        if !metered {
+            for tier in &coverage.custom_tiers {
+                coverage.unapplied.insert(tier.to_string(), NOT_METERED);
+            }
+        }
+        let natives_reason = if !metered {
+            Some(NOT_METERED)
+        } else if self.use_sui_natives {
+            Some(SUI_NATIVES)
+        } else {
+            None
+        };
+        if let Some(reason) = natives_reason {
+            for native in &coverage.custom_natives {
+                coverage.unapplied.insert(native.clone(), reason);
+            }
+        }
+        Some(coverage)
+    }
+
+    /// Builder method: record an instruction-level execution trace.
+    pub fn with_execution_trace(
+        mut self,
+        options: Option<crate::execution_trace::ExecutionTraceOptions>,
+    ) -> Self {
+        self.execution_trace = options;
+        self
+    }
+
+    /// Builder method: record the Move call tree of every command.
+    pub fn with_call_trace(mut self, options: Option<crate::call_trace::CallTraceOptions>) -> Self {
+        self.call_trace = options;
+        self
+    }
+
+    /// Builder method: enable/disable per-command and per-function gas profiling.
+    pub fn with_gas_profile(mut self, enabled: bool) -> Self {
+        self.gas_profile = enabled;
+        self
+    }
+
     /// Builder method: enable/disable immutability enforcement.
     pub fn with_immutability_enforcement(mut self, enforce: bool) -> Self {
         self.enforce_immutability = enforce;
@@ -446,6 +578,23 @@ impl SimulationConfig {
         self
     }
 
+    /// Builder method: run natives with the `ProtocolConfig` of `protocol_version`
+    /// instead of the latest one.
+    pub fn with_pinned_protocol_config(mut self, pinned: bool) -> Self {
+        self.pin_protocol_config = pinned;
+        self
+    }
+
+    /// The `ProtocolConfig` natives run with: the one for `protocol_version` when
+    /// `pin_protocol_config` is set, the latest otherwise.
+    pub fn sui_protocol_config(&self) -> ProtocolConfig {
+        if self.pin_protocol_config {
+            crate::gas::load_protocol_config(self.protocol_version)
+        } else {
+            ProtocolConfig::get_for_max_version_UNSAFE()
+        }
+    }
+
     /// Builder method: set storage price per unit.
     ///
     /// This is used to calculate storage rebates when objects are deleted.
@@ -588,6 +737,9 @@ pub struct ExecutionOutput {
     /// - Cost per byte of arguments
     /// - Cost per byte of return values
     pub gas_used: u64,
+    /// Computation gas consumed by the Move VM (before bucketization), without the
+    /// storage charges folded into `gas_used`. 0 when unmetered.
+    pub computation_gas: u64,
 }
 
 // =============================================================================
@@ -618,6 +770,8 @@ pub struct StructuredAbortInfo {
     /// Full stack trace if available (from `VMError::exec_state()`).
     /// Each entry is (module_id, function_index, instruction_offset).
     pub stack_trace: Vec<(ModuleId, u16, u16)>,
+    /// Abort code decoded against the module's constants (resolved from bytecode).
+    pub decoded: Option<crate::abort_decoder::DecodedAbort>,
 }
 
 impl StructuredAbortInfo {
@@ -667,13 +821,15 @@ impl StructuredAbortInfo {
             instruction_offset,
             function_name: None, // Resolved later via bytecode lookup
             stack_trace,
+            decoded: None, // Resolved later via bytecode lookup
         })
     }
 
-    /// Resolve the function name from bytecode.
+    /// Resolve the function name and decode the abort code from bytecode.
     ///
-    /// Call this after creation to look up the function name from the compiled module.
-    pub fn resolve_function_name<R, E>(&mut self, resolver: &R)
+    /// Call this after creation to look up the function name and error constants
+    /// from the compiled module.
+    pub fn resolve_from_bytecode<R, E>(&mut self, resolver: &R)
     where
         R: ModuleResolver<Error = E>,
         E: std::fmt::Debug,
@@ -682,7 +838,7 @@ impl StructuredAbortInfo {
             return;
         };
 
-        // Try to load the module and look up the function name
+        // Try to load the module and look up the function name and error constants
         // Note: get_module returns Result<Option<Vec<u8>>, E>
         if let Ok(Some(module_bytes)) = resolver.get_module(module_id) {
             if let Ok(module) =
@@ -694,6 +850,7 @@ impl StructuredAbortInfo {
                     self.function_name =
                         Some(module.identifiers[func_handle.name.0 as usize].to_string());
                 }
+                self.decoded = Some(crate::abort_decoder::decode_abort(&module, self.abort_code));
             }
         }
     }
@@ -1332,7 +1489,16 @@ impl GasMeterImpl {
                 let params = GasParameters::from_protocol_config(
                     &crate::gas::load_protocol_config(config.protocol_version),
                 );
-                GasMeterImpl::Accurate(AccurateGasMeter::new(budget, config.gas_price, &params))
+                let meter = match &config.gas_table {
+                    Some(table) => AccurateGasMeter::with_cost_table(
+                        budget,
+                        config.gas_price,
+                        &params,
+                        table.cost_table(params.gas_model_version),
+                    ),
+                    None => AccurateGasMeter::new(budget, config.gas_price, &params),
+                };
+                GasMeterImpl::Accurate(meter)
             }
             (Some(budget), false) => {
                 // Use simple metered gas
@@ -1952,6 +2118,14 @@ pub struct VMHarness<'a> {
     /// Optional storage tracker for accurate gas metering.
     /// When enabled, tracks object read/write/delete costs.
     storage_tracker: Option<StorageTracker>,
+    /// Natives stubbed under `stub_unknown_natives`, in order of first call.
+    stubbed_natives: Arc<StubbedNativeLog>,
+    /// Instruction trace, when `execution_trace` is configured.
+    execution_trace: Option<Arc<ExecutionTraceLog>>,
+    /// Call trees, when `call_trace` is configured.
+    call_trace: Option<Arc<CallTraceLog>>,
+    /// Per-function gas totals, when `gas_profile` is configured.
+    gas_profile: Option<Arc<GasProfileLog>>,
 }
 
 impl<'a> VMHarness<'a> {
@@ -1989,8 +2163,11 @@ impl<'a> VMHarness<'a> {
         // If accurate gas is enabled, set native function costs
         if config.accurate_gas {
             let protocol_config = crate::gas::load_protocol_config(config.protocol_version);
-            let native_costs =
+            let mut native_costs =
                 crate::gas::NativeFunctionCosts::from_protocol_config(&protocol_config);
+            if let Some(table) = &config.gas_table {
+                native_costs = table.native_costs(native_costs)?;
+            }
             native_state.native_costs = Some(native_costs);
         }
 
@@ -1998,12 +2175,12 @@ impl<'a> VMHarness<'a> {
 
         // Build native function table based on configuration
         let protocol_config = if config.use_sui_natives {
-            Some(ProtocolConfig::get_for_max_version_UNSAFE())
+            Some(config.sui_protocol_config())
         } else {
             None
         };
 
-        let natives = if let Some(ref pc) = protocol_config {
+        let mut natives = if let Some(ref pc) = protocol_config {
             // Use Sui's actual native implementations for 1:1 parity
             // Note: This provides correct dynamic field behavior but requires
             // extensions to be set up per-session (ObjectRuntime, TransactionContext, etc.)
@@ -2013,8 +2190,28 @@ impl<'a> VMHarness<'a> {
             build_native_function_table(native_state.clone())
         };
 
+        let stubbed_natives = Arc::new(StubbedNativeLog::new());
+        if config.stub_unknown_natives {
+            stub_unknown_natives(
+                &mut natives,
+                resolver.iter_modules(),
+                stubbed_natives.clone(),
+            );
+        }
+
         let vm = MoveVM::new(natives).map_err(|e| anyhow!("failed to create VM: {:?}", e))?;
         let trace = Arc::new(Mutex::new(ModuleAccessTrace::new()));
+        let execution_trace = config
+            .execution_trace
+            .clone()
+            .map(|options| Arc::new(ExecutionTraceLog::new(options)));
+        let call_trace = config
+            .call_trace
+            .clone()
+            .map(|options| Arc::new(CallTraceLog::new(options)));
+        let gas_profile = config
+            .gas_profile
+            .then(|| Arc::new(GasProfileLog::default()));
         // Create storage tracker if accurate gas is enabled
         let storage_tracker = if config.accurate_gas {
             let params = GasParameters::from_protocol_config(&crate::gas::load_protocol_config(
@@ -2043,6 +2240,9 @@ impl<'a> VMHarness<'a> {
                 sponsor: None,
                 is_metered: config.accurate_gas,
                 child_resolution_mode: config.child_resolution_mode,
+                protocol_version: config
+                    .pin_protocol_config
+                    .then_some(config.protocol_version),
             };
             Some(sui_object_runtime::SuiNativeExtensions::new(
                 noop_fetcher,
@@ -2068,9 +2268,79 @@ impl<'a> VMHarness<'a> {
             package_versions: std::collections::HashMap::new(),
             sui_extensions,
             storage_tracker,
+            stubbed_natives,
+            execution_trace,
+            call_trace,
+            gas_profile,
         })
     }
 
+    /// Natives that returned stub defaults (only with `stub_unknown_natives`).
+    pub fn stubbed_natives(&self) -> Vec<StubbedNative> {
+        self.stubbed_natives.entries()
+    }
+
+    /// Instruction trace recorded so far (only with `execution_trace`).
+    pub fn execution_trace(&self) -> Option<&ExecutionTraceLog> {
+        self.execution_trace.as_deref()
+    }
+
+    /// Call trees recorded so far, one per VM call (only with `call_trace`).
+    pub fn call_trace(&self) -> Option<&CallTraceLog> {
+        self.call_trace.as_deref()
+    }
+
+    /// A VM tracer for the next call, when tracing is enabled.
+    fn trace_builder(&self) -> Option<MoveTraceBuilder> {
+        (self.execution_trace.is_some() || self.call_trace.is_some()).then(MoveTraceBuilder::new)
+    }
+
+    fn finish_trace(&self, tracer: Option<MoveTraceBuilder>) {
+        let Some(tracer) = tracer else {
+            return;
+        };
+        let trace = tracer.into_trace();
+        if let Some(log) = &self.call_trace {
+            log.record(&trace);
+        }
+        if let Some(log) = &self.execution_trace {
+            log.record(trace);
+        }
+    }
+
+    /// Per-function gas recorded so far (only with `gas_profile`).
+    pub fn gas_profile(&self) -> Option<&GasProfileLog> {
+        self.gas_profile.as_deref()
+    }
+
+    /// Drop recorded per-function gas (call before each new execution).
+    pub fn clear_gas_profile(&self) {
+        if let Some(log) = &self.gas_profile {
+            log.clear();
+        }
+    }
+
+    /// A frame profiler for the next call, when gas profiling is enabled.
+    fn gas_profiler(
+        &self,
+        module: &ModuleId,
+        function: &str,
+        gas_meter: &GasMeterImpl,
+    ) -> Option<FrameGasProfiler> {
+        self.gas_profile.as_ref().map(|_| {
+            FrameGasProfiler::new(
+                qualified_function_name(module, function),
+                gas_meter.gas_consumed(),
+            )
+        })
+    }
+
+    fn finish_gas_profile(&self, profiler: Option<FrameGasProfiler>, gas_meter: &GasMeterImpl) {
+        if let (Some(log), Some(profiler)) = (&self.gas_profile, profiler) {
+            log.record(profiler.finish(gas_meter.gas_consumed()));
+        }
+    }
+
     /// Set address aliases for package upgrades.
     /// Maps bytecode addresses to runtime/storage addresses, enabling correct
     /// type tag rewriting in dynamic field operations for upgraded packages.
@@ -2199,12 +2469,19 @@ impl<'a> VMHarness<'a> {
     /// }
     /// ```
     pub fn get_gas_summary(&self, gas_meter: &GasMeterImpl) -> Option<GasSummary> {
+        self.gas_summary_for_computation(gas_meter.gas_consumed())
+    }
+
+    /// Gas summary for `computation_gas` accumulated across several executions
+    /// (e.g. all commands of a PTB), combined with the storage tracked so far.
+    ///
+    /// Returns None if accurate gas metering is not enabled.
+    pub fn gas_summary_for_computation(&self, computation_gas: u64) -> Option<GasSummary> {
         // Only works with accurate gas metering
         if !self.config.accurate_gas {
             return None;
         }
 
-        let computation_gas = gas_meter.gas_consumed();
         let storage_summary = self.storage_tracker.as_ref()?.summary();
 
         // Apply bucketization based on protocol config
@@ -2321,6 +2598,10 @@ impl<'a> VMHarness<'a> {
                 // Enable gas metering for native functions when accurate_gas is enabled
                 is_metered: self.config.accurate_gas,
                 child_resolution_mode: self.config.child_resolution_mode,
+                protocol_version: self
+                    .config
+                    .pin_protocol_config
+                    .then_some(self.config.protocol_version),
             };
 
             self.sui_extensions = Some(sui_object_runtime::SuiNativeExtensions::new(
@@ -2372,6 +2653,10 @@ impl<'a> VMHarness<'a> {
                 // Enable gas metering for native functions when accurate_gas is enabled
                 is_metered: self.config.accurate_gas,
                 child_resolution_mode: self.config.child_resolution_mode,
+                protocol_version: self
+                    .config
+                    .pin_protocol_config
+                    .then_some(self.config.protocol_version),
             };
 
             self.sui_extensions = Some(sui_object_runtime::SuiNativeExtensions::new(
@@ -2512,6 +2797,11 @@ impl<'a> VMHarness<'a> {
         self.native_state.get_events_by_type(type_prefix)
     }
 
+    /// Number of events emitted so far (the next event's sequence number).
+    pub fn event_count(&self) -> u64 {
+        self.native_state.event_count()
+    }
+
     /// Clear all emitted events (call between transactions).
     pub fn clear_events(&self) {
         self.native_state.clear_events()
@@ -2733,16 +3023,19 @@ impl<'a> VMHarness<'a> {
 
         let mut gas_meter = GasMeterImpl::from_config(&self.config);
 
-        let serialized_return = session
-            .execute_function_bypass_visibility(
-                &relocated_module,
-                function_name.as_ident_str(),
-                loaded_ty_args,
-                args.clone(),
-                &mut gas_meter,
-                None,
-            )
-            .map_err(|e| anyhow!("execution failed: {:?}", e))?;
+        let mut profiler = self.gas_profiler(&relocated_module, function_name.as_str(), &gas_meter);
+        let mut tracer = self.trace_builder();
+        let outcome = session.execute_function_bypass_visibility(
+            &relocated_module,
+            function_name.as_ident_str(),
+            loaded_ty_args,
+            args.clone(),
+            &mut ProfilingGasMeter::new(&mut gas_meter, profiler.as_mut()),
+            tracer.as_mut(),
+        );
+        self.finish_trace(tracer);
+        self.finish_gas_profile(profiler, &gas_meter);
+        let serialized_return = outcome.map_err(|e| anyhow!("execution failed: {:?}", e))?;
 
         let (result, _store) = session.finish();
         let _changes = result.map_err(|e| anyhow!("session finish failed: {:?}", e))?;
@@ -2769,6 +3062,7 @@ impl<'a> VMHarness<'a> {
             return_values,
             mutable_ref_outputs,
             gas_used,
+            computation_gas: gas_meter.gas_consumed(),
         })
     }
 
@@ -2853,23 +3147,29 @@ impl<'a> VMHarness<'a> {
         let mut gas_meter = GasMeterImpl::from_config(&self.config);
 
         // Execute the function - this is where we capture VMError directly
-        let serialized_return = match session.execute_function_bypass_visibility(
+        let mut profiler =
+            self.gas_profiler(&relocated_module, function_name_ident.as_str(), &gas_meter);
+        let mut tracer = self.trace_builder();
+        let outcome = session.execute_function_bypass_visibility(
             &relocated_module,
             function_name_ident.as_ident_str(),
             loaded_ty_args,
             args.clone(),
-            &mut gas_meter,
-            None,
-        ) {
+            &mut ProfilingGasMeter::new(&mut gas_meter, profiler.as_mut()),
+            tracer.as_mut(),
+        );
+        self.finish_trace(tracer);
+        self.finish_gas_profile(profiler, &gas_meter);
+        let serialized_return = match outcome {
             Ok(result) => result,
             Err(vm_error) => {
                 self.storage.reset_link_context();
                 // Extract structured error BEFORE converting to string
                 let mut structured = StructuredVMError::from_vm_error(&vm_error);
 
-                // Try to resolve function name if we have abort info
+                // Try to resolve function name and abort constants if we have abort info
                 if let Some(ref mut abort_info) = structured.abort_info {
-                    abort_info.resolve_function_name(&self.storage);
+                    abort_info.resolve_from_bytecode(&self.storage);
                 }
 
                 let error_message = format!("execution failed: {:?}", vm_error);
@@ -2911,6 +3211,7 @@ impl<'a> VMHarness<'a> {
             return_values,
             mutable_ref_outputs,
             gas_used,
+            computation_gas: gas_meter.gas_consumed(),
         })
     }
 
@@ -3084,16 +3385,21 @@ impl<'a, 'b> PTBSession<'a, 'b> {
 
         let mut gas_meter = GasMeterImpl::from_config(self.harness.config());
 
-        let serialized_return = session
-            .execute_function_bypass_visibility(
-                &relocated_module,
-                function_name.as_ident_str(),
-                loaded_ty_args,
-                args,
-                &mut gas_meter,
-                None,
-            )
-            .map_err(|e| anyhow!("execution failed: {:?}", e))?;
+        let mut profiler =
+            self.harness
+                .gas_profiler(&relocated_module, function_name.as_str(), &gas_meter);
+        let mut tracer = self.harness.trace_builder();
+        let outcome = session.execute_function_bypass_visibility(
+            &relocated_module,
+            function_name.as_ident_str(),
+            loaded_ty_args,
+            args,
+            &mut ProfilingGasMeter::new(&mut gas_meter, profiler.as_mut()),
+            tracer.as_mut(),
+        );
+        self.harness.finish_trace(tracer);
+        self.harness.finish_gas_profile(profiler, &gas_meter);
+        let serialized_return = outcome.map_err(|e| anyhow!("execution failed: {:?}", e))?;
 
         // Finish the session
         let (result, _store) = session.finish();
@@ -3125,6 +3431,7 @@ impl<'a, 'b> PTBSession<'a, 'b> {
             return_values,
             mutable_ref_outputs,
             gas_used,
+            computation_gas: gas_meter.gas_consumed(),
         })
     }
 
@@ -3282,6 +3589,7 @@ mod structured_error_tests {
             return_values: vec![TypedReturnValue::new(vec![1, 2, 3], None)],
             mutable_ref_outputs: vec![],
             gas_used: 100,
+            computation_gas: 0,
         };
 
         let result = ExecutionResult::Success(output);
@@ -3316,6 +3624,7 @@ mod structured_error_tests {
             return_values: vec![],
             mutable_ref_outputs: vec![],
             gas_used: 50,
+            computation_gas: 0,
         };
 
         let result = ExecutionResult::Success(output);
@@ -3347,6 +3656,247 @@ mod structured_error_tests {
 mod simulation_config_tests {
     use super::*;
 
+    #[test]
+    fn test_stub_unknown_natives_covers_every_framework_native() {
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let mut table = build_native_function_table(Arc::new(MockNativeState::new()));
+        let log = Arc::new(StubbedNativeLog::new());
+        stub_unknown_natives(&mut table, resolver.iter_modules(), log.clone());
+
+        for module in resolver.iter_modules() {
+            let module_id = module.self_id();
+            for def in module.function_defs().iter().filter(|def| def.is_native()) {
+                let name = module.identifier_at(module.function_handle_at(def.function).name);
+                assert!(
+                    table
+                        .iter()
+                        .any(|(addr, m, f, _)| addr == module_id.address()
+                            && m.as_ident_str() == module_id.name()
+                            && f.as_ident_str() == name),
+                    "{}::{} has no native after stubbing",
+                    module_id,
+                    name
+                );
+            }
+        }
+        assert!(log.is_empty());
+    }
+
+    /// `oracle::call(): u64` returning `oracle::answer()`, a native with no implementation.
+    fn module_calling_unknown_native(address: AccountAddress) -> Vec<u8> {
+        use move_binary_format::file_format::{
+            empty_module, Bytecode, CodeUnit, FunctionDefinition, FunctionHandle,
+            FunctionHandleIndex, IdentifierIndex, ModuleHandleIndex, Signature, SignatureIndex,
+            SignatureToken, Visibility,
+        };
+        use move_binary_format::file_format_common::VERSION_6;
+        use move_core_types::identifier::Identifier;
+
+        fn signature(
+            module: &mut move_binary_format::CompiledModule,
+            tokens: Vec<SignatureToken>,
+        ) -> SignatureIndex {
+            let signature = Signature(tokens);
+            let index = match module.signatures.iter().position(|s| *s == signature) {
+                Some(index) => index,
+                None => {
+                    module.signatures.push(signature);
+                    module.signatures.len() - 1
+                }
+            };
+            SignatureIndex(index as u16)
+        }
+
+        let mut module = empty_module();
+        module.version = VERSION_6;
+        module.identifiers[0] = Identifier::new("oracle").unwrap();
+        module.address_identifiers[0] = address;
+        let empty = signature(&mut module, vec![]);
+        let returns_u64 = signature(&mut module, vec![SignatureToken::U64]);
+        for name in ["answer", "call"] {
+            module.identifiers.push(Identifier::new(name).unwrap());
+            module.function_handles.push(FunctionHandle {
+                module: ModuleHandleIndex(0),
+                name: IdentifierIndex((module.identifiers.len() - 1) as u16),
+                parameters: empty,
+                return_: returns_u64,
+                type_parameters: vec![],
+            });
+        }
+        module.function_defs.push(FunctionDefinition {
+            function: FunctionHandleIndex(0),
+            visibility: Visibility::Private,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: None,
+        });
+        module.function_defs.push(FunctionDefinition {
+            function: FunctionHandleIndex(1),
+            visibility: Visibility::Public,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: Some(CodeUnit {
+                locals: empty,
+                code: vec![Bytecode::Call(FunctionHandleIndex(0)), Bytecode::Ret],
+                jump_tables: vec![],
+            }),
+        });
+        let mut bytes = Vec::new();
+        module
+            .serialize_with_version(VERSION_6, &mut bytes)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_stub_unknown_natives_stubs_modules_added_after_a_harness() {
+        let address = AccountAddress::from_hex_literal("0xc0ffee").unwrap();
+        let config = SimulationConfig::default().with_stub_unknown_natives(true);
+        let mut resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        {
+            let harness = VMHarness::with_config(&resolver, false, config.clone()).unwrap();
+            assert!(harness.stubbed_natives().is_empty());
+        }
+
+        let module = resolver
+            .add_module_bytes(module_calling_unknown_native(address))
+            .unwrap();
+        let mut harness = VMHarness::with_config(&resolver, false, config).unwrap();
+        for _ in 0..2 {
+            let returns = harness
+                .execute_function_with_return(&module, "call", vec![], vec![])
+                .unwrap();
+            assert_eq!(returns, vec![bcs::to_bytes(&0u64).unwrap()]);
+        }
+        assert_eq!(
+            harness.stubbed_natives(),
+            vec![StubbedNative {
+                function: format!("{}::oracle::answer", address.to_hex_literal()),
+                reason: "missing".to_string(),
+                calls: 2,
+            }]
+        );
+
+        // Without the option the native is not bound, so the module cannot run.
+        let mut strict = VMHarness::new(&resolver, false).unwrap();
+        assert!(strict
+            .execute_function_with_return(&module, "call", vec![], vec![])
+            .is_err());
+    }
+
+    #[test]
+    fn test_execution_trace_names_functions() {
+        use crate::execution_trace::{ExecutionTraceOptions, TraceEntry};
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let config =
+            SimulationConfig::default().with_execution_trace(Some(ExecutionTraceOptions {
+                stack: true,
+                max_instructions: None,
+            }));
+        let mut harness = VMHarness::with_config(&resolver, false, config).unwrap();
+        let module = ModuleId::new(
+            AccountAddress::ONE,
+            move_core_types::identifier::Identifier::new("u64").unwrap(),
+        );
+        harness
+            .execute_function_full(
+                &module,
+                "max",
+                vec![],
+                vec![bcs::to_bytes(&3u64).unwrap(), bcs::to_bytes(&7u64).unwrap()],
+            )
+            .unwrap();
+
+        let log = harness.execution_trace().unwrap();
+        let summary = log.summary();
+        assert_eq!(summary.calls, 1);
+        assert!(summary.instructions > 0);
+        let entries = log.entries();
+        assert!(matches!(
+            &entries[0],
+            TraceEntry::Enter { module, function, .. }
+                if module.ends_with("::u64") && function == "max"
+        ));
+        assert!(entries.iter().any(|entry| matches!(
+            entry,
+            TraceEntry::Instruction { function, stack_depth: Some(_), .. } if function == "max"
+        )));
+
+        let untraced = VMHarness::new(&resolver, false).unwrap();
+        assert!(untraced.execution_trace().is_none());
+    }
+
+    #[test]
+    fn test_call_trace_builds_call_tree() {
+        use crate::call_trace::CallTraceOptions;
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let module = ModuleId::new(
+            AccountAddress::ONE,
+            move_core_types::identifier::Identifier::new("string").unwrap(),
+        );
+        let args = vec![bcs::to_bytes(&b"hi".to_vec()).unwrap()];
+
+        let config = SimulationConfig::default().with_call_trace(Some(CallTraceOptions::default()));
+        let mut harness = VMHarness::with_config(&resolver, false, config).unwrap();
+        harness
+            .execute_function_full(&module, "utf8", vec![], args.clone())
+            .unwrap();
+        assert!(harness.execution_trace().is_none());
+        let calls = harness.call_trace().unwrap().calls_since(0);
+        assert_eq!(calls.len(), 1);
+        let root = &calls[0];
+        assert!(root.module.ends_with("::string") && root.function == "utf8");
+        assert_eq!(root.args.len(), 1);
+        assert_eq!(root.returns.len(), 1);
+        assert!(root
+            .children
+            .iter()
+            .any(|child| child.native && child.function == "internal_check_utf8"));
+
+        let config = SimulationConfig::default()
+            .with_call_trace(Some(CallTraceOptions { max_depth: Some(1) }));
+        let mut harness = VMHarness::with_config(&resolver, false, config).unwrap();
+        harness
+            .execute_function_full(&module, "utf8", vec![], args)
+            .unwrap();
+        let calls = harness.call_trace().unwrap().calls_since(0);
+        assert!(calls[0].children.is_empty());
+        assert!(calls[0].omitted_calls >= 1);
+    }
+
+    #[test]
+    fn test_pinned_protocol_config_gates_native_feature_flags() {
+        // Poseidon was not enabled on mainnet at protocol version 25.
+        const PRE_POSEIDON_VERSION: u64 = 25;
+        assert!(!crate::gas::load_protocol_config(PRE_POSEIDON_VERSION).enable_poseidon());
+        assert!(ProtocolConfig::get_for_max_version_UNSAFE().enable_poseidon());
+
+        let resolver = LocalModuleResolver::with_sui_framework().unwrap();
+        let module = ModuleId::new(
+            AccountAddress::TWO,
+            move_core_types::identifier::Identifier::new("poseidon").unwrap(),
+        );
+        let args = vec![bcs::to_bytes(&vec![move_core_types::u256::U256::one()]).unwrap()];
+        let config = SimulationConfig::default()
+            .with_use_sui_natives(true)
+            .with_protocol_version(PRE_POSEIDON_VERSION);
+
+        let mut latest = VMHarness::with_config(&resolver, false, config.clone()).unwrap();
+        latest
+            .execute_function_full(&module, "poseidon_bn254", vec![], args.clone())
+            .unwrap();
+
+        let mut pinned =
+            VMHarness::with_config(&resolver, false, config.with_pinned_protocol_config(true))
+                .unwrap();
+        let err = pinned
+            .execute_function_full(&module, "poseidon_bn254", vec![], args)
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("ABORTED"), "{:#}", err);
+    }
+
     #[test]
     fn test_default_config() {
         let config = SimulationConfig::default();
@@ -3357,6 +3907,7 @@ mod simulation_config_tests {
         assert!(config.deterministic_random);
         assert!(config.permissive_ownership);
         assert!(!config.use_sui_natives);
+        assert!(!config.stub_unknown_natives);
 
         // Gas metering enabled by default
         assert!(config.gas_budget.is_some());
@@ -3428,6 +3979,39 @@ mod simulation_config_tests {
         assert_eq!(deserialized.gas_budget, Some(1_000_000));
     }
 
+    #[test]
+    fn test_gas_table_coverage_reports_unapplied_entries() {
+        let table = crate::gas::CustomGasTable::from_json_str(
+            r#"{"instruction_tiers": {"0": 7}, "natives": {"event_emit_per_byte": 99}}"#,
+        )
+        .unwrap();
+        let config = SimulationConfig::default();
+        assert!(config.gas_table_coverage().is_none());
+
+        let config = config.with_gas_table(Some(table));
+        assert!(config.gas_table_coverage().unwrap().unapplied.is_empty());
+
+        let coverage = SimulationConfig {
+            use_sui_natives: true,
+            ..config.clone()
+        }
+        .gas_table_coverage()
+        .unwrap();
+        assert_eq!(
+            coverage.unapplied.keys().collect::<Vec<_>>(),
+            vec!["event_emit_per_byte"]
+        );
+
+        let coverage = config
+            .with_accurate_gas(false)
+            .gas_table_coverage()
+            .unwrap();
+        assert_eq!(
+            coverage.unapplied.keys().collect::<Vec<_>>(),
+            vec!["event_emit_per_byte", "instruction_tiers"]
+        );
+    }
+
     #[test]
     fn test_for_replay_config() {
         let config = SimulationConfig::default().for_replay(12345);
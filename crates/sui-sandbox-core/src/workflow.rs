@@ -1,9 +1,11 @@
 use anyhow::{anyhow, bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::workflow_template::{step_template_refs, WorkflowTemplateContext, WorkflowTemplateRef};
+
 pub const SUPPORTED_WORKFLOW_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +52,9 @@ pub struct WorkflowDefaults {
     pub self_heal_dynamic_fields: Option<bool>,
     #[serde(default)]
     pub mm2: Option<bool>,
+    /// Extra keys, available to steps as `${defaults.<key>}`.
+    #[serde(flatten)]
+    pub vars: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +65,10 @@ pub struct WorkflowStep {
     pub name: Option<String>,
     #[serde(default)]
     pub continue_on_error: bool,
+    /// Ids of steps that must succeed before this one starts. Steps without
+    /// dependencies may run concurrently when the runner allows parallelism.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
     #[serde(flatten)]
     pub action: WorkflowStepAction,
 }
@@ -228,6 +237,78 @@ impl WorkflowSpec {
         bail!("Workflow spec validation failed:\n{formatted}");
     }
 
+    /// Zero-based indices of each step's dependencies: its `depends_on` entries plus
+    /// any step referenced as `${steps.<id>...}`.
+    ///
+    /// Fails on unknown or self-referencing ids and on dependency cycles.
+    pub fn dependency_graph(&self) -> Result<Vec<Vec<usize>>> {
+        let ids: HashMap<&str, usize> = self
+            .steps
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, step)| step.id.as_deref().map(|id| (id.trim(), idx)))
+            .collect();
+        let mut graph = Vec::with_capacity(self.steps.len());
+        for (idx, step) in self.steps.iter().enumerate() {
+            let step_label = format_step_label(step, idx + 1);
+            // `${steps.<id>...}` references are implicit dependencies.
+            let template_deps = step_template_refs(step)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|reference| match reference {
+                    WorkflowTemplateRef::Step { id, .. } => Some((id, "`${steps...}`")),
+                    _ => None,
+                });
+            let named_deps = step
+                .depends_on
+                .iter()
+                .map(|dep| (dep.trim().to_string(), "`depends_on`"))
+                .chain(template_deps);
+            let mut deps = Vec::with_capacity(step.depends_on.len());
+            for (dep, origin) in named_deps {
+                match ids.get(dep.as_str()) {
+                    Some(&dep_idx) if dep_idx == idx => {
+                        bail!("{step_label}: {origin} cannot reference itself")
+                    }
+                    Some(&dep_idx) => {
+                        if !deps.contains(&dep_idx) {
+                            deps.push(dep_idx);
+                        }
+                    }
+                    None => bail!("{step_label}: {origin} references unknown step id `{dep}`"),
+                }
+            }
+            graph.push(deps);
+        }
+
+        // Kahn's algorithm: every step must become ready eventually.
+        let mut remaining: Vec<usize> = graph.iter().map(Vec::len).collect();
+        let mut ready: Vec<usize> = (0..graph.len())
+            .filter(|idx| remaining[*idx] == 0)
+            .collect();
+        let mut visited = 0usize;
+        while let Some(done) = ready.pop() {
+            visited += 1;
+            for (idx, deps) in graph.iter().enumerate() {
+                if deps.contains(&done) {
+                    remaining[idx] -= 1;
+                    if remaining[idx] == 0 {
+                        ready.push(idx);
+                    }
+                }
+            }
+        }
+        if visited != graph.len() {
+            let cyclic = (0..graph.len())
+                .filter(|idx| remaining[*idx] > 0)
+                .map(|idx| format_step_label(&self.steps[idx], idx + 1))
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!("step dependencies form a cycle among {cyclic}");
+        }
+        Ok(graph)
+    }
+
     fn collect_validation_issues(&self, issues: &mut Vec<String>) {
         if self.version != SUPPORTED_WORKFLOW_VERSION {
             issues.push(format!(
@@ -324,6 +405,27 @@ impl WorkflowSpec {
                 }
             }
         }
+        let templates = WorkflowTemplateContext::new(&self.defaults);
+        for (idx, step) in self.steps.iter().enumerate() {
+            let step_label = format_step_label(step, idx + 1);
+            match step_template_refs(step) {
+                Ok(refs) => {
+                    for reference in refs {
+                        if let WorkflowTemplateRef::Defaults(key) = &reference {
+                            if !templates.has_default(key) {
+                                issues.push(format!(
+                                    "{step_label}: {reference} references unset `defaults.{key}`"
+                                ));
+                            }
+                        }
+                    }
+                }
+                Err(err) => issues.push(format!("{step_label}: {err}")),
+            }
+        }
+        if let Err(err) = self.dependency_graph() {
+            issues.push(err.to_string());
+        }
     }
 }
 
@@ -381,6 +483,7 @@ mod tests {
                 id: Some("replay-1".to_string()),
                 name: Some("Replay tx".to_string()),
                 continue_on_error: false,
+                depends_on: Vec::new(),
                 action: WorkflowStepAction::Replay(WorkflowReplayStep {
                     digest: Some("9V3xKMn".to_string()),
                     checkpoint: None,
@@ -418,6 +521,7 @@ mod tests {
                     id: Some("dup".to_string()),
                     name: None,
                     continue_on_error: false,
+                    depends_on: Vec::new(),
                     action: WorkflowStepAction::Command(WorkflowCommandStep {
                         args: vec!["status".to_string()],
                     }),
@@ -426,6 +530,7 @@ mod tests {
                     id: Some("dup".to_string()),
                     name: None,
                     continue_on_error: false,
+                    depends_on: Vec::new(),
                     action: WorkflowStepAction::Command(WorkflowCommandStep {
                         args: vec!["status".to_string()],
                     }),
@@ -437,6 +542,69 @@ mod tests {
         assert!(err.to_string().contains("duplicate step id"));
     }
 
+    #[test]
+    fn validates_step_dependencies() {
+        let step = |id: &str, depends_on: &[&str]| WorkflowStep {
+            id: Some(id.to_string()),
+            name: None,
+            continue_on_error: false,
+            depends_on: depends_on.iter().map(|dep| dep.to_string()).collect(),
+            action: WorkflowStepAction::Command(WorkflowCommandStep {
+                args: vec!["status".to_string()],
+            }),
+        };
+        let mut spec = WorkflowSpec {
+            version: SUPPORTED_WORKFLOW_VERSION,
+            name: None,
+            description: None,
+            defaults: WorkflowDefaults::default(),
+            steps: vec![step("a", &[]), step("b", &["a"]), step("c", &["a", "b"])],
+        };
+        assert_eq!(
+            spec.dependency_graph().expect("acyclic"),
+            vec![vec![], vec![0], vec![0, 1]]
+        );
+
+        spec.steps[0].depends_on = vec!["c".to_string()];
+        let err = spec.validate().expect_err("expected cycle error");
+        assert!(err.to_string().contains("form a cycle"));
+
+        spec.steps[0].depends_on = vec!["missing".to_string()];
+        let err = spec.validate().expect_err("expected unknown id error");
+        assert!(err.to_string().contains("unknown step id `missing`"));
+    }
+
+    #[test]
+    fn validates_template_references() {
+        let mut spec: WorkflowSpec = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "defaults": {"checkpoint": "239615926"},
+            "steps": [
+                {"id": "discover", "kind": "command", "args": ["status", "--json"]},
+                {
+                    "id": "replay",
+                    "kind": "replay",
+                    "digest": "${steps.discover.digest}",
+                    "checkpoint": "${defaults.checkpoint}"
+                }
+            ]
+        }))
+        .expect("valid spec");
+        spec.validate().expect("template references resolve");
+        assert_eq!(
+            spec.dependency_graph().expect("acyclic"),
+            vec![vec![], vec![0]]
+        );
+
+        spec.defaults.vars.clear();
+        let err = spec.validate().expect_err("expected unset default error");
+        assert!(err.to_string().contains("unset `defaults.checkpoint`"));
+
+        spec.steps[0].id = Some("other".to_string());
+        let err = spec.validate().expect_err("expected unknown id error");
+        assert!(err.to_string().contains("unknown step id `discover`"));
+    }
+
     #[test]
     fn allows_explicit_false_boolean_flags() {
         let spec = WorkflowSpec {
@@ -457,6 +625,7 @@ mod tests {
                 id: None,
                 name: None,
                 continue_on_error: false,
+                depends_on: Vec::new(),
                 action: WorkflowStepAction::Replay(WorkflowReplayStep {
                     digest: Some("tx".to_string()),
                     checkpoint: None,
@@ -120,6 +120,7 @@ pub fn build_builtin_workflow(
             id: Some(format!("{protocol}_package")),
             name: Some(format!("{protocol} package interface summary")),
             continue_on_error: false,
+            depends_on: Vec::new(),
             action: WorkflowStepAction::Command(WorkflowCommandStep {
                 args: vec![
                     "analyze".to_string(),
@@ -137,6 +138,7 @@ pub fn build_builtin_workflow(
             id: Some(format!("{protocol}_view_object_{}", idx + 1)),
             name: Some(format!("{protocol} inspect object {}", idx + 1)),
             continue_on_error: true,
+            depends_on: Vec::new(),
             action: WorkflowStepAction::Command(WorkflowCommandStep {
                 args: vec![
                     "view".to_string(),
@@ -157,6 +159,7 @@ pub fn build_builtin_workflow(
             id: Some(format!("{protocol}_analyze")),
             name: Some(format!("{protocol} analyze replay hydration")),
             continue_on_error: false,
+            depends_on: Vec::new(),
             action: WorkflowStepAction::AnalyzeReplay(WorkflowAnalyzeReplayStep {
                 digest,
                 checkpoint: Some(checkpoint),
@@ -181,6 +184,7 @@ pub fn build_builtin_workflow(
             id: Some(format!("{protocol}_replay")),
             name: Some(format!("{protocol} replay execution")),
             continue_on_error: false,
+            depends_on: Vec::new(),
             action: WorkflowStepAction::Replay(WorkflowReplayStep {
                 digest: Some(digest),
                 checkpoint: Some(checkpoint.to_string()),
@@ -207,6 +211,7 @@ pub fn build_builtin_workflow(
         id: Some(format!("{protocol}_status")),
         name: Some("session status".to_string()),
         continue_on_error: false,
+        depends_on: Vec::new(),
         action: WorkflowStepAction::Command(WorkflowCommandStep {
             args: vec!["status".to_string()],
         }),
@@ -234,6 +239,7 @@ pub fn build_builtin_workflow(
             synthesize_missing: None,
             self_heal_dynamic_fields: None,
             mm2: None,
+            vars: Default::default(),
         },
         steps,
     })
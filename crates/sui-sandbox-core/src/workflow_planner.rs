@@ -1,8 +1,9 @@
 use anyhow::{anyhow, Result};
 
+use crate::protocol_registry::ProtocolRegistry;
 use crate::workflow::{
     normalize_command_args, WorkflowAnalyzeReplayStep, WorkflowDefaults, WorkflowFetchStrategy,
-    WorkflowReplayProfile, WorkflowReplayStep, WorkflowStep, WorkflowStepAction,
+    WorkflowReplayProfile, WorkflowReplayStep, WorkflowSource, WorkflowStep, WorkflowStepAction,
 };
 use crate::workflow_adapter::BuiltinWorkflowTemplate;
 use crate::workflow_command_builder;
@@ -28,7 +29,17 @@ pub fn parse_builtin_workflow_template(template: &str) -> Result<BuiltinWorkflow
     }
 }
 
+/// Infer a workflow template from module names using the built-in adapter keywords.
 pub fn infer_workflow_template_from_modules(module_names: &[String]) -> WorkflowTemplateInference {
+    infer_workflow_template_with_registry(module_names, &ProtocolRegistry::builtin())
+}
+
+/// Infer a workflow template from module names using the keywords of every adapter in
+/// `registry` that maps to a template.
+pub fn infer_workflow_template_with_registry(
+    module_names: &[String],
+    registry: &ProtocolRegistry,
+) -> WorkflowTemplateInference {
     if module_names.is_empty() {
         return WorkflowTemplateInference {
             template: BuiltinWorkflowTemplate::Generic,
@@ -38,34 +49,32 @@ pub fn infer_workflow_template_from_modules(module_names: &[String]) -> Workflow
         };
     }
 
-    let cetus_keywords = ["cetus", "clmm", "dlmm", "pool_script", "position_manager"];
-    let suilend_keywords = ["suilend", "lending", "reserve", "obligation", "liquidation"];
-    let scallop_keywords = ["scallop", "scoin", "spool", "collateral", "market"];
-
-    let mut cetus_score = 0usize;
-    let mut suilend_score = 0usize;
-    let mut scallop_score = 0usize;
-    for name in module_names.iter().map(|value| value.to_ascii_lowercase()) {
-        if cetus_keywords.iter().any(|kw| name.contains(kw)) {
-            cetus_score += 1;
-        }
-        if suilend_keywords.iter().any(|kw| name.contains(kw)) {
-            suilend_score += 1;
-        }
-        if scallop_keywords.iter().any(|kw| name.contains(kw)) {
-            scallop_score += 1;
-        }
-    }
+    let lowered: Vec<String> = module_names
+        .iter()
+        .map(|value| value.to_ascii_lowercase())
+        .collect();
+    let scores: Vec<(BuiltinWorkflowTemplate, usize)> = registry
+        .template_keywords()
+        .into_iter()
+        .map(|(template, keywords)| {
+            let score = lowered
+                .iter()
+                .filter(|name| keywords.iter().any(|kw| name.contains(kw.as_str())))
+                .count();
+            (template, score)
+        })
+        .collect();
+    let score_summary = scores
+        .iter()
+        .map(|(template, score)| format!("{}={}", template.key(), score))
+        .collect::<Vec<_>>()
+        .join(", ");
 
-    let mut ranked = [
-        (BuiltinWorkflowTemplate::Cetus, cetus_score),
-        (BuiltinWorkflowTemplate::Suilend, suilend_score),
-        (BuiltinWorkflowTemplate::Scallop, scallop_score),
-        (BuiltinWorkflowTemplate::Generic, 0usize),
-    ];
+    let mut ranked = scores;
+    ranked.push((BuiltinWorkflowTemplate::Generic, 0usize));
     ranked.sort_by(|a, b| b.1.cmp(&a.1));
     let (top_template, top_score) = ranked[0];
-    let second_score = ranked[1].1;
+    let second_score = ranked.get(1).map_or(0, |(_, score)| *score);
 
     if top_score == 0 {
         return WorkflowTemplateInference {
@@ -80,10 +89,7 @@ pub fn infer_workflow_template_from_modules(module_names: &[String]) -> Workflow
             template: BuiltinWorkflowTemplate::Generic,
             confidence: "low",
             source: "module_probe",
-            reason: Some(format!(
-                "ambiguous module matches (cetus={}, suilend={}, scallop={})",
-                cetus_score, suilend_score, scallop_score
-            )),
+            reason: Some(format!("ambiguous module matches ({})", score_summary)),
         };
     }
 
@@ -98,10 +104,7 @@ pub fn infer_workflow_template_from_modules(module_names: &[String]) -> Workflow
         template: top_template,
         confidence,
         source: "module_probe",
-        reason: Some(format!(
-            "module keyword matches: cetus={}, suilend={}, scallop={}",
-            cetus_score, suilend_score, scallop_score
-        )),
+        reason: Some(format!("module keyword matches: {}", score_summary)),
     }
 }
 
@@ -261,3 +264,350 @@ pub fn apply_workflow_profile_env(profile: WorkflowReplayProfile) -> WorkflowEnv
     }
     WorkflowEnvGuard { previous }
 }
+
+/// Effective value of a step setting and where it came from (`step`, `defaults`, `builtin`).
+fn resolve_setting<T: Copy>(
+    origins: &mut serde_json::Map<String, serde_json::Value>,
+    field: &str,
+    step: Option<T>,
+    defaults: Option<T>,
+    builtin: T,
+) -> T {
+    let (value, origin) = match (step, defaults) {
+        (Some(value), _) => (value, "step"),
+        (None, Some(value)) => (value, "defaults"),
+        (None, None) => (builtin, "builtin"),
+    };
+    origins.insert(field.to_string(), serde_json::json!(origin));
+    value
+}
+
+fn profile_env_json(profile: WorkflowReplayProfile) -> serde_json::Value {
+    profile_env_defaults(profile)
+        .iter()
+        .map(|(key, value)| ((*key).to_string(), serde_json::json!(value)))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+/// Resolved plan for one step, computed without touching the network.
+///
+/// Mirrors how the native runner resolves step settings against `defaults`:
+/// effective source/profile (with the env vars the profile would set), each
+/// setting's origin, the target and any Walrus discovery the run would perform,
+/// the interpolated command, and `problems` that would fail the step.
+pub fn workflow_explain_step(
+    defaults: &WorkflowDefaults,
+    step: &WorkflowStep,
+    index: usize,
+) -> serde_json::Value {
+    let mut origins = serde_json::Map::new();
+    let mut problems: Vec<String> = Vec::new();
+    let mut plan = match &step.action {
+        WorkflowStepAction::Replay(replay) => {
+            let profile = resolve_setting(
+                &mut origins,
+                "profile",
+                replay.profile,
+                defaults.profile,
+                WorkflowReplayProfile::Balanced,
+            );
+            let source = resolve_setting(
+                &mut origins,
+                "source",
+                replay.source,
+                defaults.source,
+                WorkflowSource::Hybrid,
+            );
+            let fetch_strategy = resolve_setting(
+                &mut origins,
+                "fetch_strategy",
+                replay.fetch_strategy,
+                defaults.fetch_strategy,
+                WorkflowFetchStrategy::Full,
+            );
+            let vm_only = resolve_setting(
+                &mut origins,
+                "vm_only",
+                replay.vm_only,
+                defaults.vm_only,
+                false,
+            );
+            let allow_fallback = resolve_setting(
+                &mut origins,
+                "allow_fallback",
+                replay.allow_fallback,
+                defaults.allow_fallback,
+                true,
+            ) && !vm_only;
+            let no_prefetch = resolve_setting(
+                &mut origins,
+                "no_prefetch",
+                replay.no_prefetch,
+                defaults.no_prefetch,
+                false,
+            ) || fetch_strategy == WorkflowFetchStrategy::Eager;
+            let settings = serde_json::json!({
+                "vm_only": vm_only,
+                "allow_fallback": allow_fallback,
+                "auto_system_objects": resolve_setting(
+                    &mut origins,
+                    "auto_system_objects",
+                    replay.auto_system_objects,
+                    defaults.auto_system_objects,
+                    true,
+                ),
+                "dynamic_field_prefetch": !no_prefetch,
+                "prefetch_depth": resolve_setting(
+                    &mut origins,
+                    "prefetch_depth",
+                    replay.prefetch_depth,
+                    defaults.prefetch_depth,
+                    3,
+                ),
+                "prefetch_limit": resolve_setting(
+                    &mut origins,
+                    "prefetch_limit",
+                    replay.prefetch_limit,
+                    defaults.prefetch_limit,
+                    200,
+                ),
+                "compare": resolve_setting(
+                    &mut origins,
+                    "compare",
+                    replay.compare,
+                    defaults.compare,
+                    false,
+                ),
+                "strict": resolve_setting(
+                    &mut origins,
+                    "strict",
+                    replay.strict,
+                    defaults.strict,
+                    false,
+                ),
+                "synthesize_missing": resolve_setting(
+                    &mut origins,
+                    "synthesize_missing",
+                    replay.synthesize_missing,
+                    defaults.synthesize_missing,
+                    false,
+                ),
+                "self_heal_dynamic_fields": resolve_setting(
+                    &mut origins,
+                    "self_heal_dynamic_fields",
+                    replay.self_heal_dynamic_fields,
+                    defaults.self_heal_dynamic_fields,
+                    false,
+                ),
+            });
+
+            let digest = replay
+                .digest
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty());
+            let checkpoint = replay
+                .checkpoint
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty());
+            let mut discovery = serde_json::Value::Null;
+            if digest.is_some() && replay.latest.is_some() {
+                problems.push("cannot combine `digest` and `latest`".to_string());
+            }
+            if let Some(raw) = checkpoint.filter(|raw| raw.parse::<u64>().is_err()) {
+                if digest.is_some() {
+                    problems.push(format!(
+                        "checkpoint `{}` must be numeric when digest is provided",
+                        raw
+                    ));
+                } else {
+                    discovery = serde_json::json!({
+                        "mode": "checkpoint",
+                        "selector": raw,
+                        "performed": false,
+                    });
+                }
+            }
+            if let Some(latest) = replay.latest {
+                if latest == 0 {
+                    problems.push("latest must be >= 1".to_string());
+                }
+                discovery = serde_json::json!({
+                    "mode": "latest",
+                    "count": latest,
+                    "performed": false,
+                });
+            }
+            if digest.is_none() && discovery.is_null() && replay.state_json.is_none() {
+                problems.push(
+                    "requires digest, checkpoint/latest discovery, or state_json".to_string(),
+                );
+            }
+            let path = if replay.state_json.is_some() {
+                "state_json"
+            } else if source == WorkflowSource::Local {
+                "local_cache"
+            } else {
+                "network"
+            };
+            serde_json::json!({
+                "action": "replay",
+                "path": path,
+                "target": {
+                    "digest": digest,
+                    "checkpoint": checkpoint,
+                    "latest": replay.latest,
+                    "state_json": replay.state_json.as_ref().map(|p| p.display().to_string()),
+                },
+                "discovery": discovery,
+                "source": source.as_cli_value(),
+                "profile": profile.as_cli_value(),
+                "profile_env": profile_env_json(profile),
+                "fetch_strategy": fetch_strategy.as_cli_value(),
+                "settings": settings,
+            })
+        }
+        WorkflowStepAction::AnalyzeReplay(analyze) => {
+            // analyze_replay steps have no per-step profile.
+            let profile = resolve_setting(
+                &mut origins,
+                "profile",
+                None,
+                defaults.profile,
+                WorkflowReplayProfile::Balanced,
+            );
+            let source = resolve_setting(
+                &mut origins,
+                "source",
+                analyze.source,
+                defaults.source,
+                WorkflowSource::Hybrid,
+            );
+            let no_prefetch = resolve_setting(
+                &mut origins,
+                "no_prefetch",
+                analyze.no_prefetch,
+                defaults.no_prefetch,
+                false,
+            );
+            let settings = serde_json::json!({
+                "mm2": resolve_setting(&mut origins, "mm2", analyze.mm2, defaults.mm2, false),
+                "allow_fallback": resolve_setting(
+                    &mut origins,
+                    "allow_fallback",
+                    analyze.allow_fallback,
+                    defaults.allow_fallback,
+                    true,
+                ),
+                "auto_system_objects": resolve_setting(
+                    &mut origins,
+                    "auto_system_objects",
+                    analyze.auto_system_objects,
+                    defaults.auto_system_objects,
+                    true,
+                ),
+                "dynamic_field_prefetch": !no_prefetch,
+                "prefetch_depth": resolve_setting(
+                    &mut origins,
+                    "prefetch_depth",
+                    analyze.prefetch_depth,
+                    defaults.prefetch_depth,
+                    3,
+                ),
+                "prefetch_limit": resolve_setting(
+                    &mut origins,
+                    "prefetch_limit",
+                    analyze.prefetch_limit,
+                    defaults.prefetch_limit,
+                    200,
+                ),
+            });
+            let digest = analyze.digest.trim();
+            if digest.is_empty() {
+                problems.push("digest cannot be empty".to_string());
+            }
+            serde_json::json!({
+                "action": "analyze_replay",
+                "path": if source == WorkflowSource::Local { "local_cache" } else { "network" },
+                "target": {
+                    "digest": digest,
+                    "checkpoint": analyze.checkpoint,
+                },
+                "discovery": serde_json::Value::Null,
+                "source": source.as_cli_value(),
+                "profile": profile.as_cli_value(),
+                "profile_env": profile_env_json(profile),
+                "settings": settings,
+            })
+        }
+        WorkflowStepAction::Command(_) => serde_json::json!({
+            "action": "command",
+            "path": "cli",
+            "discovery": serde_json::Value::Null,
+        }),
+    };
+
+    let command = workflow_build_step_command(defaults, step);
+    if let Some(object) = plan.as_object_mut() {
+        object.insert("index".to_string(), serde_json::json!(index));
+        object.insert(
+            "label".to_string(),
+            serde_json::json!(workflow_step_label(step, index)),
+        );
+        object.insert("id".to_string(), serde_json::json!(step.id));
+        object.insert("name".to_string(), serde_json::json!(step.name));
+        object.insert(
+            "continue_on_error".to_string(),
+            serde_json::json!(step.continue_on_error),
+        );
+        match command {
+            Ok(argv) => {
+                object.insert("command".to_string(), serde_json::json!(argv.join(" ")));
+                object.insert("argv".to_string(), serde_json::json!(argv));
+            }
+            Err(err) => problems.push(format!("failed to build step command: {}", err)),
+        }
+        object.insert("origins".to_string(), origins.into());
+        object.insert("problems".to_string(), serde_json::json!(problems));
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::WorkflowSpec;
+
+    #[test]
+    fn explain_step_resolves_settings_and_defers_discovery() {
+        let spec: WorkflowSpec = serde_json::from_value(serde_json::json!({
+            "defaults": { "profile": "fast", "vm_only": true },
+            "steps": [
+                { "id": "latest", "kind": "replay", "latest": 2, "source": "walrus" },
+                { "kind": "replay", "digest": "D1", "checkpoint": "tip" },
+            ],
+        }))
+        .expect("spec");
+
+        let plan = workflow_explain_step(&spec.defaults, &spec.steps[0], 1);
+        assert_eq!(plan["label"], "1:latest");
+        assert_eq!(plan["profile"], "fast");
+        assert_eq!(plan["origins"]["profile"], "defaults");
+        assert_eq!(plan["origins"]["source"], "step");
+        assert_eq!(plan["origins"]["compare"], "builtin");
+        assert_eq!(plan["settings"]["allow_fallback"], false);
+        assert_eq!(plan["profile_env"]["SUI_OBJECT_FETCH_CONCURRENCY"], "32");
+        assert_eq!(plan["discovery"]["mode"], "latest");
+        assert_eq!(plan["discovery"]["performed"], false);
+        assert!(plan["command"].as_str().unwrap().contains("--latest"));
+        assert_eq!(plan["problems"], serde_json::json!([]));
+
+        let plan = workflow_explain_step(&spec.defaults, &spec.steps[1], 2);
+        assert!(plan["problems"][0]
+            .as_str()
+            .unwrap()
+            .contains("must be numeric"));
+    }
+}
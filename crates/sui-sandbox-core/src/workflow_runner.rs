@@ -5,9 +5,12 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Condvar, Mutex};
 use std::time::Instant;
 
 use crate::workflow::{WorkflowSpec, WorkflowStep};
+use crate::workflow_template::{step_template_refs, WorkflowTemplateContext};
 
 /// Prepared workflow step metadata plus command build result.
 #[derive(Debug, Clone)]
@@ -75,6 +78,9 @@ pub struct WorkflowRunReport {
 }
 
 /// Run prepared workflow steps with shared stop/continue semantics.
+///
+/// `${...}` references in a step are resolved against earlier step outputs right
+/// before it executes (see [`crate::workflow_template`]).
 pub fn run_prepared_workflow_steps<StartFn, ExecFn>(
     spec_label: String,
     spec: &WorkflowSpec,
@@ -91,121 +97,407 @@ where
     let started = Instant::now();
     let mut reports = Vec::with_capacity(prepared_steps.len());
     let mut stopped_early = false;
+    let mut templates = WorkflowTemplateContext::new(&spec.defaults);
 
     for prepared in prepared_steps {
         let step_idx = prepared.index.saturating_sub(1);
         let Some(step) = spec.steps.get(step_idx) else {
-            reports.push(WorkflowStepReport {
-                index: prepared.index,
-                id: prepared.id,
-                name: prepared.name,
-                kind: prepared.kind,
+            reports.push(invalid_step_report(&prepared));
+            stopped_early = true;
+            break;
+        };
+        let should_continue = continue_on_error || prepared.continue_on_error;
+        let resolved = resolve_step_templates(&templates, step, &prepared, dry_run);
+        let report = start_and_run_step(
+            step,
+            &prepared,
+            resolved,
+            dry_run,
+            &mut on_step_start,
+            &mut execute_step,
+        );
+        record_step_output(&mut templates, &prepared, &report);
+        let failed = !report.success;
+        reports.push(report);
+        if failed && !should_continue {
+            stopped_early = true;
+            break;
+        }
+    }
+
+    build_run_report(spec_label, spec, dry_run, stopped_early, started, reports)
+}
+
+/// Run prepared workflow steps as a dependency graph.
+///
+/// A step starts once every step in its `depends_on` has succeeded; up to
+/// `max_parallel` ready steps run at a time, lowest index first. A failed step
+/// stops scheduling unless it (or the run) continues on error, in which case only
+/// its dependents are skipped. Reports keep the prepared-step order regardless of
+/// completion order. With `max_parallel <= 1` and no dependencies this matches
+/// [`run_prepared_workflow_steps`].
+#[allow(clippy::too_many_arguments)]
+pub fn run_prepared_workflow_steps_parallel<StartFn, ExecFn>(
+    spec_label: String,
+    spec: &WorkflowSpec,
+    prepared_steps: Vec<WorkflowPreparedStep>,
+    dry_run: bool,
+    continue_on_error: bool,
+    max_parallel: usize,
+    on_step_start: StartFn,
+    execute_step: ExecFn,
+) -> WorkflowRunReport
+where
+    StartFn: Fn(&WorkflowStep, &WorkflowPreparedStep) + Sync,
+    ExecFn: Fn(&WorkflowStep, &WorkflowPreparedStep) -> Result<WorkflowStepExecution> + Sync,
+{
+    let started = Instant::now();
+    let graph = match spec.dependency_graph() {
+        Ok(graph) => graph,
+        Err(err) => {
+            let report = WorkflowStepReport {
+                index: 0,
+                id: None,
+                name: None,
+                kind: "workflow".to_string(),
                 command: Vec::new(),
                 success: false,
                 exit_code: -1,
                 elapsed_ms: 0,
-                error: Some(format!("invalid prepared step index {}", prepared.index)),
+                error: Some(err.to_string()),
                 output: None,
-            });
-            stopped_early = true;
-            break;
-        };
-        let step_started = Instant::now();
-        on_step_start(step, &prepared);
-        let should_continue = continue_on_error || prepared.continue_on_error;
+            };
+            return build_run_report(spec_label, spec, dry_run, true, started, vec![report]);
+        }
+    };
 
-        let command = match &prepared.command {
-            Ok(command) => command.clone(),
-            Err(err) => {
-                reports.push(WorkflowStepReport {
-                    index: prepared.index,
-                    id: prepared.id.clone(),
-                    name: prepared.name.clone(),
-                    kind: prepared.kind.clone(),
-                    command: Vec::new(),
-                    success: false,
-                    exit_code: -1,
-                    elapsed_ms: step_started.elapsed().as_millis(),
-                    error: Some(format!("failed to build step command: {}", err)),
-                    output: None,
-                });
-                if !should_continue {
-                    stopped_early = true;
-                    break;
+    // Map spec step indices onto prepared positions; dependencies on steps that were
+    // not prepared count as satisfied.
+    let position: HashMap<usize, usize> = prepared_steps
+        .iter()
+        .enumerate()
+        .map(|(pos, prepared)| (prepared.index.saturating_sub(1), pos))
+        .collect();
+    let mut remaining = vec![0usize; prepared_steps.len()];
+    let mut dependents = vec![Vec::new(); prepared_steps.len()];
+    for (pos, prepared) in prepared_steps.iter().enumerate() {
+        let deps = graph
+            .get(prepared.index.saturating_sub(1))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for dep_pos in deps.iter().filter_map(|dep| position.get(dep)) {
+            remaining[pos] += 1;
+            dependents[*dep_pos].push(pos);
+        }
+    }
+    let ready = (0..prepared_steps.len())
+        .filter(|pos| remaining[*pos] == 0)
+        .collect();
+    let state = Mutex::new(DagState {
+        remaining,
+        ready,
+        reports: vec![None; prepared_steps.len()],
+        running: 0,
+        stopped: false,
+        templates: WorkflowTemplateContext::new(&spec.defaults),
+    });
+    let wake = Condvar::new();
+
+    let worker = || loop {
+        let (pos, resolved) = {
+            let mut guard = state.lock().unwrap_or_else(|e| e.into_inner());
+            loop {
+                if guard.stopped {
+                    return;
+                }
+                if let Some(pos) = guard.ready.pop_first() {
+                    guard.running += 1;
+                    // Dependencies (including referenced steps) have finished, so
+                    // their outputs are already recorded.
+                    let resolved = spec
+                        .steps
+                        .get(prepared_steps[pos].index.saturating_sub(1))
+                        .map(|step| {
+                            resolve_step_templates(
+                                &guard.templates,
+                                step,
+                                &prepared_steps[pos],
+                                dry_run,
+                            )
+                        });
+                    break (pos, resolved);
+                }
+                if guard.running == 0 {
+                    return;
                 }
-                continue;
+                guard = wake.wait(guard).unwrap_or_else(|e| e.into_inner());
             }
         };
 
-        if dry_run {
-            reports.push(WorkflowStepReport {
-                index: prepared.index,
-                id: prepared.id.clone(),
-                name: prepared.name.clone(),
-                kind: prepared.kind.clone(),
-                command,
-                success: true,
-                exit_code: 0,
-                elapsed_ms: step_started.elapsed().as_millis(),
-                error: None,
-                output: None,
-            });
-            continue;
-        }
+        let prepared = &prepared_steps[pos];
+        let should_continue = continue_on_error || prepared.continue_on_error;
+        let (report, valid) = match (spec.steps.get(prepared.index.saturating_sub(1)), resolved) {
+            (Some(step), Some(resolved)) => (
+                start_and_run_step(
+                    step,
+                    prepared,
+                    resolved,
+                    dry_run,
+                    &on_step_start,
+                    &execute_step,
+                ),
+                true,
+            ),
+            _ => (invalid_step_report(prepared), false),
+        };
 
-        match execute_step(step, &prepared) {
-            Ok(executed) => {
-                let success = executed.exit_code == 0;
-                let error = if success {
-                    None
-                } else {
-                    executed.error.or_else(|| {
-                        Some(format!(
-                            "step {} failed with exit code {}",
-                            prepared.index, executed.exit_code
-                        ))
-                    })
-                };
-
-                reports.push(WorkflowStepReport {
-                    index: prepared.index,
-                    id: prepared.id.clone(),
-                    name: prepared.name.clone(),
-                    kind: prepared.kind.clone(),
-                    command,
-                    success,
-                    exit_code: executed.exit_code,
-                    elapsed_ms: step_started.elapsed().as_millis(),
-                    error,
-                    output: executed.output,
-                });
-
-                if !(success || should_continue) {
-                    stopped_early = true;
-                    break;
+        let mut guard = state.lock().unwrap_or_else(|e| e.into_inner());
+        guard.running -= 1;
+        record_step_output(&mut guard.templates, prepared, &report);
+        if report.success {
+            for &next in &dependents[pos] {
+                guard.remaining[next] -= 1;
+                if guard.remaining[next] == 0 && guard.reports[next].is_none() {
+                    guard.ready.insert(next);
                 }
             }
-            Err(err) => {
-                reports.push(WorkflowStepReport {
-                    index: prepared.index,
-                    id: prepared.id.clone(),
-                    name: prepared.name.clone(),
-                    kind: prepared.kind.clone(),
-                    command,
-                    success: false,
-                    exit_code: -1,
-                    elapsed_ms: step_started.elapsed().as_millis(),
-                    error: Some(err.to_string()),
-                    output: None,
-                });
-                if !should_continue {
-                    stopped_early = true;
-                    break;
+        } else if valid && should_continue {
+            let label = step_display_label(prepared);
+            let mut pending = dependents[pos].clone();
+            while let Some(next) = pending.pop() {
+                if guard.reports[next].is_some() {
+                    continue;
                 }
+                guard.reports[next] = Some(skipped_step_report(&prepared_steps[next], &label));
+                pending.extend(dependents[next].iter().copied());
+            }
+        } else {
+            guard.stopped = true;
+        }
+        guard.reports[pos] = Some(report);
+        wake.notify_all();
+    };
+
+    let workers = max_parallel.clamp(1, prepared_steps.len().max(1));
+    std::thread::scope(|scope| {
+        for _ in 1..workers {
+            scope.spawn(worker);
+        }
+        worker();
+    });
+
+    let state = state.into_inner().unwrap_or_else(|e| e.into_inner());
+    let reports = state.reports.into_iter().flatten().collect();
+    build_run_report(spec_label, spec, dry_run, state.stopped, started, reports)
+}
+
+struct DagState {
+    /// Unfinished dependencies per prepared position.
+    remaining: Vec<usize>,
+    /// Positions whose dependencies have all succeeded, lowest first.
+    ready: BTreeSet<usize>,
+    reports: Vec<Option<WorkflowStepReport>>,
+    running: usize,
+    stopped: bool,
+    /// Outputs of finished steps for `${steps...}` references.
+    templates: WorkflowTemplateContext,
+}
+
+/// A step and its prepared command with `${...}` references resolved, `None` when
+/// nothing needs resolving. Dry runs are left as-is since no step has produced
+/// output.
+type ResolvedStep = Result<Option<(WorkflowStep, WorkflowPreparedStep)>>;
+
+fn resolve_step_templates(
+    templates: &WorkflowTemplateContext,
+    step: &WorkflowStep,
+    prepared: &WorkflowPreparedStep,
+    dry_run: bool,
+) -> ResolvedStep {
+    let has_templates = !matches!(step_template_refs(step), Ok(refs) if refs.is_empty());
+    if dry_run || !has_templates {
+        return Ok(None);
+    }
+    let step = templates.interpolate_step(step)?;
+    let command = match &prepared.command {
+        Ok(argv) => Ok(templates.interpolate_args(argv)?),
+        Err(err) => Err(err.clone()),
+    };
+    Ok(Some((
+        step,
+        WorkflowPreparedStep {
+            command,
+            ..prepared.clone()
+        },
+    )))
+}
+
+fn start_and_run_step<StartFn, ExecFn>(
+    step: &WorkflowStep,
+    prepared: &WorkflowPreparedStep,
+    resolved: ResolvedStep,
+    dry_run: bool,
+    on_step_start: StartFn,
+    execute_step: ExecFn,
+) -> WorkflowStepReport
+where
+    StartFn: FnOnce(&WorkflowStep, &WorkflowPreparedStep),
+    ExecFn: FnOnce(&WorkflowStep, &WorkflowPreparedStep) -> Result<WorkflowStepExecution>,
+{
+    match resolved {
+        Ok(Some((step, prepared))) => {
+            on_step_start(&step, &prepared);
+            run_single_step(&step, &prepared, dry_run, execute_step)
+        }
+        Ok(None) => {
+            on_step_start(step, prepared);
+            run_single_step(step, prepared, dry_run, execute_step)
+        }
+        Err(err) => {
+            on_step_start(step, prepared);
+            WorkflowStepReport {
+                command: prepared.command.clone().unwrap_or_default(),
+                error: Some(format!("failed to interpolate step: {err:#}")),
+                ..invalid_step_report(prepared)
+            }
+        }
+    }
+}
+
+/// Make a successful step's output available to later `${steps.<id>...}` references.
+fn record_step_output(
+    templates: &mut WorkflowTemplateContext,
+    prepared: &WorkflowPreparedStep,
+    report: &WorkflowStepReport,
+) {
+    if let (true, Some(id), Some(output)) = (
+        report.success,
+        prepared.id.as_deref(),
+        report.output.as_ref(),
+    ) {
+        templates.record_step_output(id, output.clone());
+    }
+}
+
+fn run_single_step<ExecFn>(
+    step: &WorkflowStep,
+    prepared: &WorkflowPreparedStep,
+    dry_run: bool,
+    execute_step: ExecFn,
+) -> WorkflowStepReport
+where
+    ExecFn: FnOnce(&WorkflowStep, &WorkflowPreparedStep) -> Result<WorkflowStepExecution>,
+{
+    let step_started = Instant::now();
+    let report = |command: Vec<String>, success: bool, exit_code: i32| WorkflowStepReport {
+        index: prepared.index,
+        id: prepared.id.clone(),
+        name: prepared.name.clone(),
+        kind: prepared.kind.clone(),
+        command,
+        success,
+        exit_code,
+        elapsed_ms: 0,
+        error: None,
+        output: None,
+    };
+
+    let command = match &prepared.command {
+        Ok(command) => command.clone(),
+        Err(err) => {
+            return WorkflowStepReport {
+                elapsed_ms: step_started.elapsed().as_millis(),
+                error: Some(format!("failed to build step command: {}", err)),
+                ..report(Vec::new(), false, -1)
+            };
+        }
+    };
+
+    if dry_run {
+        return WorkflowStepReport {
+            elapsed_ms: step_started.elapsed().as_millis(),
+            ..report(command, true, 0)
+        };
+    }
+
+    match execute_step(step, prepared) {
+        Ok(executed) => {
+            let success = executed.exit_code == 0;
+            let error = if success {
+                None
+            } else {
+                executed.error.or_else(|| {
+                    Some(format!(
+                        "step {} failed with exit code {}",
+                        prepared.index, executed.exit_code
+                    ))
+                })
+            };
+            WorkflowStepReport {
+                elapsed_ms: step_started.elapsed().as_millis(),
+                error,
+                output: executed.output,
+                ..report(command, success, executed.exit_code)
             }
         }
+        Err(err) => WorkflowStepReport {
+            elapsed_ms: step_started.elapsed().as_millis(),
+            error: Some(err.to_string()),
+            ..report(command, false, -1)
+        },
+    }
+}
+
+fn invalid_step_report(prepared: &WorkflowPreparedStep) -> WorkflowStepReport {
+    WorkflowStepReport {
+        index: prepared.index,
+        id: prepared.id.clone(),
+        name: prepared.name.clone(),
+        kind: prepared.kind.clone(),
+        command: Vec::new(),
+        success: false,
+        exit_code: -1,
+        elapsed_ms: 0,
+        error: Some(format!("invalid prepared step index {}", prepared.index)),
+        output: None,
+    }
+}
+
+fn skipped_step_report(
+    prepared: &WorkflowPreparedStep,
+    failed_dependency: &str,
+) -> WorkflowStepReport {
+    WorkflowStepReport {
+        index: prepared.index,
+        id: prepared.id.clone(),
+        name: prepared.name.clone(),
+        kind: prepared.kind.clone(),
+        command: Vec::new(),
+        success: false,
+        exit_code: -1,
+        elapsed_ms: 0,
+        error: Some(format!(
+            "skipped: dependency {failed_dependency} did not succeed"
+        )),
+        output: None,
+    }
+}
+
+fn step_display_label(prepared: &WorkflowPreparedStep) -> String {
+    match prepared.id.as_deref() {
+        Some(id) => format!("`{id}`"),
+        None => format!("step {}", prepared.index),
     }
+}
 
+fn build_run_report(
+    spec_label: String,
+    spec: &WorkflowSpec,
+    dry_run: bool,
+    stopped_early: bool,
+    started: Instant,
+    reports: Vec<WorkflowStepReport>,
+) -> WorkflowRunReport {
     let succeeded_steps = reports.iter().filter(|entry| entry.success).count();
     let failed_steps = reports.len().saturating_sub(succeeded_steps);
     WorkflowRunReport {
@@ -240,6 +532,7 @@ mod tests {
                     id: Some("s1".to_string()),
                     name: Some("step1".to_string()),
                     continue_on_error: false,
+                    depends_on: Vec::new(),
                     action: WorkflowStepAction::Command(WorkflowCommandStep {
                         args: vec!["status".to_string()],
                     }),
@@ -248,6 +541,7 @@ mod tests {
                     id: Some("s2".to_string()),
                     name: Some("step2".to_string()),
                     continue_on_error: false,
+                    depends_on: Vec::new(),
                     action: WorkflowStepAction::Command(WorkflowCommandStep {
                         args: vec!["status".to_string()],
                     }),
@@ -358,4 +652,146 @@ mod tests {
         assert!(report.stopped_early);
         assert_eq!(report.steps[0].error.as_deref(), Some("boom"));
     }
+
+    fn dag_spec() -> (WorkflowSpec, Vec<WorkflowPreparedStep>) {
+        let steps: Vec<WorkflowStep> = [("a", vec![]), ("b", vec![]), ("c", vec!["a", "b"])]
+            .into_iter()
+            .map(|(id, deps)| WorkflowStep {
+                id: Some(id.to_string()),
+                name: None,
+                continue_on_error: false,
+                depends_on: deps.into_iter().map(str::to_string).collect(),
+                action: WorkflowStepAction::Command(WorkflowCommandStep {
+                    args: vec!["status".to_string()],
+                }),
+            })
+            .collect();
+        let prepared = steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| WorkflowPreparedStep {
+                index: idx + 1,
+                id: step.id.clone(),
+                name: None,
+                kind: "command".to_string(),
+                continue_on_error: false,
+                command: Ok(vec!["status".to_string()]),
+            })
+            .collect();
+        let spec = WorkflowSpec {
+            version: 1,
+            name: None,
+            description: None,
+            defaults: WorkflowDefaults::default(),
+            steps,
+        };
+        (spec, prepared)
+    }
+
+    #[test]
+    fn parallel_runner_respects_dependencies_and_order() {
+        let (spec, prepared) = dag_spec();
+        let finished = Mutex::new(Vec::new());
+        let report = run_prepared_workflow_steps_parallel(
+            "<inline>".to_string(),
+            &spec,
+            prepared,
+            false,
+            false,
+            4,
+            |_step, _prepared| {},
+            |step, _prepared| {
+                let id = step.id.clone().unwrap_or_default();
+                if id == "c" {
+                    let done = finished.lock().unwrap();
+                    assert!(done.contains(&"a".to_string()) && done.contains(&"b".to_string()));
+                }
+                finished.lock().unwrap().push(id);
+                Ok(WorkflowStepExecution::default())
+            },
+        );
+
+        assert_eq!(finished.lock().unwrap().len(), 3);
+        assert_eq!(report.succeeded_steps, 3);
+        assert!(!report.stopped_early);
+        let order: Vec<usize> = report.steps.iter().map(|step| step.index).collect();
+        assert_eq!(order, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn parallel_runner_skips_dependents_of_failed_steps() {
+        let (spec, prepared) = dag_spec();
+        let report = run_prepared_workflow_steps_parallel(
+            "<inline>".to_string(),
+            &spec,
+            prepared,
+            false,
+            true,
+            2,
+            |_step, _prepared| {},
+            |step, _prepared| {
+                let exit_code = i32::from(step.id.as_deref() == Some("a"));
+                Ok(WorkflowStepExecution {
+                    exit_code,
+                    ..WorkflowStepExecution::default()
+                })
+            },
+        );
+
+        assert_eq!(report.total_steps, 3);
+        assert_eq!(report.succeeded_steps, 1);
+        assert!(!report.stopped_early);
+        assert_eq!(
+            report.steps[2].error.as_deref(),
+            Some("skipped: dependency `a` did not succeed")
+        );
+    }
+
+    #[test]
+    fn pipes_step_output_into_later_step() {
+        let spec: WorkflowSpec = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "steps": [
+                {"id": "discover", "kind": "command", "args": ["status"]},
+                {"id": "replay", "kind": "replay", "digest": "${steps.discover.digest}"}
+            ]
+        }))
+        .expect("valid spec");
+        let prepared = spec
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| WorkflowPreparedStep {
+                index: idx + 1,
+                id: step.id.clone(),
+                name: None,
+                kind: "command".to_string(),
+                continue_on_error: false,
+                command: crate::workflow_planner::workflow_build_step_command(&spec.defaults, step)
+                    .map_err(|err| err.to_string()),
+            })
+            .collect();
+
+        let report = run_prepared_workflow_steps(
+            "<inline>".to_string(),
+            &spec,
+            prepared,
+            false,
+            false,
+            |_step, _prepared| {},
+            |step, prepared| {
+                if let WorkflowStepAction::Replay(replay) = &step.action {
+                    assert_eq!(replay.digest.as_deref(), Some("Digest111"));
+                    assert_eq!(prepared.command.as_ref().unwrap()[1], "Digest111");
+                }
+                Ok(WorkflowStepExecution {
+                    output: Some(serde_json::json!({"digest": "Digest111"})),
+                    ..WorkflowStepExecution::default()
+                })
+            },
+        );
+
+        assert_eq!(report.succeeded_steps, 2);
+        assert_eq!(report.steps[1].command[1], "Digest111");
+    }
 }
@@ -0,0 +1,325 @@
+//! `${...}` interpolation for workflow step fields.
+//!
+//! String fields of a step (replay `digest`/`checkpoint`/`state_json`,
+//! analyze_replay `digest`, command `args`) may reference:
+//!
+//! - `${steps.<id>.<path>}`: a value in an earlier step's JSON output
+//!   (`<path>` is dot-separated; numeric segments index arrays)
+//! - `${env.<NAME>}`: a process environment variable
+//! - `${defaults.<key>}`: a spec default, either a built-in setting or an extra
+//!   key under `defaults`
+//!
+//! References are checked by [`WorkflowSpec::validate`](crate::workflow::WorkflowSpec::validate)
+//! and resolved by the workflow runner right before each step executes. A step
+//! referencing another step's output implicitly depends on it.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::workflow::{WorkflowDefaults, WorkflowStep};
+
+const TEMPLATE_OPEN: &str = "${";
+const TEMPLATE_CLOSE: char = '}';
+
+/// A parsed `${...}` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkflowTemplateRef {
+    /// `${steps.<id>.<path>}`
+    Step { id: String, path: Vec<String> },
+    /// `${env.<NAME>}`
+    Env(String),
+    /// `${defaults.<key>}`
+    Defaults(String),
+}
+
+impl WorkflowTemplateRef {
+    /// Parse the expression between `${` and `}`.
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        let (scope, rest) = expr
+            .split_once('.')
+            .ok_or_else(|| anyhow!("`${{{expr}}}` must be `<scope>.<name>`"))?;
+        let segments = rest.split('.').map(str::trim).collect::<Vec<_>>();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            bail!("`${{{expr}}}` has an empty path segment");
+        }
+        match scope.trim() {
+            "steps" => {
+                let (id, path) = segments
+                    .split_first()
+                    .expect("split always yields a segment");
+                if path.is_empty() {
+                    bail!("`${{{expr}}}` must name a field of the step output");
+                }
+                Ok(Self::Step {
+                    id: (*id).to_string(),
+                    path: path.iter().map(|segment| (*segment).to_string()).collect(),
+                })
+            }
+            "env" | "defaults" if segments.len() != 1 => {
+                bail!("`${{{expr}}}` must name a single `{scope}` key")
+            }
+            "env" => Ok(Self::Env(segments[0].to_string())),
+            "defaults" => Ok(Self::Defaults(segments[0].to_string())),
+            other => bail!(
+                "`${{{expr}}}` has unknown scope `{other}` (expected one of: steps, env, defaults)"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for WorkflowTemplateRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Step { id, path } => write!(f, "${{steps.{id}.{}}}", path.join(".")),
+            Self::Env(name) => write!(f, "${{env.{name}}}"),
+            Self::Defaults(key) => write!(f, "${{defaults.{key}}}"),
+        }
+    }
+}
+
+/// All references in `raw`, in order of appearance.
+pub fn template_refs(raw: &str) -> Result<Vec<WorkflowTemplateRef>> {
+    let mut refs = Vec::new();
+    split_templates(raw, |segment| {
+        if let Segment::Template(expr) = segment {
+            refs.push(WorkflowTemplateRef::parse(expr)?);
+        }
+        Ok(())
+    })?;
+    Ok(refs)
+}
+
+/// All references in a step's string fields.
+pub fn step_template_refs(step: &WorkflowStep) -> Result<Vec<WorkflowTemplateRef>> {
+    let mut action = serde_json::to_value(&step.action).context("serialize workflow step")?;
+    let mut refs = Vec::new();
+    map_strings(&mut action, &mut |raw| {
+        refs.extend(template_refs(raw)?);
+        Ok(None)
+    })?;
+    Ok(refs)
+}
+
+/// Values available to `${...}` references while a workflow runs.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowTemplateContext {
+    defaults: serde_json::Value,
+    step_outputs: HashMap<String, serde_json::Value>,
+}
+
+impl WorkflowTemplateContext {
+    pub fn new(defaults: &WorkflowDefaults) -> Self {
+        Self {
+            defaults: serde_json::to_value(defaults).unwrap_or_default(),
+            step_outputs: HashMap::new(),
+        }
+    }
+
+    /// Whether `${defaults.<key>}` would resolve.
+    pub fn has_default(&self, key: &str) -> bool {
+        self.defaults.get(key).is_some_and(|value| !value.is_null())
+    }
+
+    /// Make a finished step's output available as `${steps.<id>...}`.
+    pub fn record_step_output(&mut self, id: &str, output: serde_json::Value) {
+        self.step_outputs.insert(id.trim().to_string(), output);
+    }
+
+    /// Resolve a single reference to its string form. Strings are inserted as-is,
+    /// numbers and booleans in their JSON form, objects and arrays as compact JSON.
+    pub fn resolve(&self, reference: &WorkflowTemplateRef) -> Result<String> {
+        let value = match reference {
+            WorkflowTemplateRef::Step { id, path } => {
+                let output = self
+                    .step_outputs
+                    .get(id)
+                    .ok_or_else(|| anyhow!("{reference}: step `{id}` has no output yet"))?;
+                lookup_path(output, path).ok_or_else(|| {
+                    anyhow!(
+                        "{reference}: step `{id}` output has no `{}`",
+                        path.join(".")
+                    )
+                })?
+            }
+            WorkflowTemplateRef::Env(name) => {
+                return std::env::var(name)
+                    .with_context(|| format!("{reference}: env var `{name}` is not set"));
+            }
+            WorkflowTemplateRef::Defaults(key) => self
+                .defaults
+                .get(key)
+                .filter(|value| !value.is_null())
+                .ok_or_else(|| anyhow!("{reference}: `defaults.{key}` is not set"))?,
+        };
+        match value {
+            serde_json::Value::Null => bail!("{reference} resolved to null"),
+            serde_json::Value::String(value) => Ok(value.clone()),
+            other => Ok(other.to_string()),
+        }
+    }
+
+    /// Replace every `${...}` in `raw`.
+    pub fn interpolate_str(&self, raw: &str) -> Result<String> {
+        let mut out = String::with_capacity(raw.len());
+        split_templates(raw, |segment| {
+            match segment {
+                Segment::Text(text) => out.push_str(text),
+                Segment::Template(expr) => {
+                    out.push_str(&self.resolve(&WorkflowTemplateRef::parse(expr)?)?)
+                }
+            }
+            Ok(())
+        })?;
+        Ok(out)
+    }
+
+    /// Interpolate every string field of a step.
+    pub fn interpolate_step(&self, step: &WorkflowStep) -> Result<WorkflowStep> {
+        let mut action = serde_json::to_value(&step.action).context("serialize workflow step")?;
+        map_strings(&mut action, &mut |raw| {
+            if raw.contains(TEMPLATE_OPEN) {
+                self.interpolate_str(raw).map(Some)
+            } else {
+                Ok(None)
+            }
+        })?;
+        Ok(WorkflowStep {
+            action: serde_json::from_value(action)
+                .context("interpolated workflow step is no longer valid")?,
+            ..step.clone()
+        })
+    }
+
+    /// Interpolate a prepared argv.
+    pub fn interpolate_args(&self, args: &[String]) -> Result<Vec<String>> {
+        args.iter().map(|arg| self.interpolate_str(arg)).collect()
+    }
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Template(&'a str),
+}
+
+fn split_templates<'a>(
+    raw: &'a str,
+    mut visit: impl FnMut(Segment<'a>) -> Result<()>,
+) -> Result<()> {
+    let mut rest = raw;
+    while let Some(start) = rest.find(TEMPLATE_OPEN) {
+        if start > 0 {
+            visit(Segment::Text(&rest[..start]))?;
+        }
+        let after = &rest[start + TEMPLATE_OPEN.len()..];
+        let end = after
+            .find(TEMPLATE_CLOSE)
+            .ok_or_else(|| anyhow!("unterminated `${{` in `{raw}`"))?;
+        visit(Segment::Template(&after[..end]))?;
+        rest = &after[end + 1..];
+    }
+    if !rest.is_empty() {
+        visit(Segment::Text(rest))?;
+    }
+    Ok(())
+}
+
+/// Apply `f` to every string in `value`; `Some` replaces the string.
+fn map_strings(
+    value: &mut serde_json::Value,
+    f: &mut impl FnMut(&str) -> Result<Option<String>>,
+) -> Result<()> {
+    match value {
+        serde_json::Value::String(raw) => {
+            if let Some(replaced) = f(raw)? {
+                *raw = replaced;
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                map_strings(item, f)?;
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for field in fields.values_mut() {
+                map_strings(field, f)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn lookup_path<'a>(value: &'a serde_json::Value, path: &[String]) -> Option<&'a serde_json::Value> {
+    path.iter()
+        .try_fold(value, |current, segment| match current {
+            serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            other => other.get(segment),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_template_refs() {
+        assert_eq!(
+            template_refs("--digest ${steps.discover.digests.0} at ${defaults.checkpoint}")
+                .unwrap(),
+            vec![
+                WorkflowTemplateRef::Step {
+                    id: "discover".to_string(),
+                    path: vec!["digests".to_string(), "0".to_string()],
+                },
+                WorkflowTemplateRef::Defaults("checkpoint".to_string()),
+            ]
+        );
+        assert!(template_refs("plain").unwrap().is_empty());
+        assert!(template_refs("${steps.discover}").is_err());
+        assert!(template_refs("${vars.x}").is_err());
+        assert!(template_refs("${env.A").is_err());
+    }
+
+    #[test]
+    fn interpolates_step_fields_from_outputs_and_defaults() {
+        let defaults: WorkflowDefaults = serde_json::from_value(json!({
+            "source": "walrus",
+            "checkpoint": 239615926
+        }))
+        .unwrap();
+        let mut context = WorkflowTemplateContext::new(&defaults);
+        context.record_step_output("discover", json!({"digests": ["AbC", "DeF"]}));
+
+        let step: WorkflowStep = serde_json::from_value(json!({
+            "kind": "replay",
+            "digest": "${steps.discover.digests.1}",
+            "checkpoint": "${defaults.checkpoint}"
+        }))
+        .unwrap();
+        let resolved = context.interpolate_step(&step).unwrap();
+        assert_eq!(
+            serde_json::to_value(&resolved.action).unwrap()["digest"],
+            "DeF"
+        );
+        assert_eq!(
+            context
+                .interpolate_args(&["--checkpoint".into(), "${defaults.checkpoint}".into()])
+                .unwrap(),
+            vec!["--checkpoint", "239615926"]
+        );
+        assert_eq!(
+            context.interpolate_str("${defaults.source}").unwrap(),
+            "walrus"
+        );
+
+        let err = context
+            .interpolate_str("${steps.other.digest}")
+            .unwrap_err();
+        assert!(err.to_string().contains("has no output yet"));
+        let err = context.interpolate_str("${defaults.profile}").unwrap_err();
+        assert!(err.to_string().contains("is not set"));
+    }
+}
@@ -0,0 +1,541 @@
+//! Watch mode: re-run a workflow for each new transaction touching a package.
+//!
+//! Each poll scans the Walrus checkpoints after a persisted cursor, skips digests
+//! that were already run, and retargets the spec's `replay`/`analyze_replay` steps
+//! at every new matching transaction. Other steps run unchanged. A failed poll
+//! (Walrus, discovery or a run) is logged and retried with backoff; the cursor
+//! only advances past checkpoints that were fully processed.
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use sui_transport::walrus::WalrusClient;
+
+use crate::checkpoint_discovery::{
+    discover_checkpoint_targets, normalize_package_id, DiscoverOutput, DiscoverTarget,
+};
+use crate::workflow::{WorkflowSpec, WorkflowStepAction};
+use crate::workflow_runner::WorkflowRunReport;
+
+/// Number of recently run digests kept in the cursor for dedupe.
+pub const WATCH_DEDUPE_WINDOW: usize = 1_000;
+/// Largest checkpoint window scanned per poll (the discovery range cap).
+pub const MAX_WATCH_CHECKPOINTS_PER_POLL: u64 = 10_000;
+/// Longest wait before retrying after consecutive failed polls.
+pub const MAX_WATCH_RETRY_BACKOFF: Duration = Duration::from_secs(600);
+
+/// Checkpoint feed polled by [`watch_workflow`]; implemented for [`WalrusClient`].
+pub trait WatchCheckpointSource {
+    fn latest_checkpoint(&self) -> Result<u64>;
+
+    /// Transactions in checkpoints `first..=last` touching `package_id`.
+    fn discover(
+        &self,
+        first: u64,
+        last: u64,
+        package_id: &str,
+        include_framework: bool,
+    ) -> Result<DiscoverOutput>;
+}
+
+impl WatchCheckpointSource for WalrusClient {
+    fn latest_checkpoint(&self) -> Result<u64> {
+        self.get_latest_checkpoint()
+            .context("failed to fetch latest checkpoint from Walrus")
+    }
+
+    fn discover(
+        &self,
+        first: u64,
+        last: u64,
+        package_id: &str,
+        include_framework: bool,
+    ) -> Result<DiscoverOutput> {
+        discover_checkpoint_targets(
+            self,
+            Some(&format!("{first}..{last}")),
+            None,
+            Some(package_id),
+            include_framework,
+            usize::MAX,
+        )
+    }
+}
+
+/// Persistent watch position.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkflowWatchCursor {
+    /// Normalized package the cursor was created for.
+    #[serde(default)]
+    pub package_id: Option<String>,
+    /// Last checkpoint fully processed.
+    #[serde(default)]
+    pub last_checkpoint: Option<u64>,
+    /// Most recent digests already run, oldest first.
+    #[serde(default)]
+    pub recent_digests: VecDeque<String>,
+}
+
+impl WorkflowWatchCursor {
+    /// Load a cursor, or start a fresh one if `path` does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read watch cursor {}", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid watch cursor {}", path.display()))
+    }
+
+    /// Write the cursor atomically (temp file + rename).
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent).with_context(|| {
+                    format!(
+                        "Failed to create watch cursor directory {}",
+                        parent.display()
+                    )
+                })?;
+            }
+        }
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write watch cursor {}", tmp.display()))?;
+        fs::rename(&tmp, path)
+            .with_context(|| format!("Failed to write watch cursor {}", path.display()))
+    }
+
+    pub fn has_seen(&self, digest: &str) -> bool {
+        self.recent_digests.iter().any(|seen| seen == digest)
+    }
+
+    pub fn mark_seen(&mut self, digest: &str) {
+        self.recent_digests.push_back(digest.to_string());
+        while self.recent_digests.len() > WATCH_DEDUPE_WINDOW {
+            self.recent_digests.pop_front();
+        }
+    }
+}
+
+/// Watch loop settings.
+#[derive(Debug, Clone)]
+pub struct WorkflowWatchConfig {
+    pub package_id: String,
+    /// Sleep between polls once caught up with the Walrus tip.
+    pub poll_interval: Duration,
+    pub max_checkpoints_per_poll: u64,
+    /// First checkpoint to scan when the cursor is fresh (default: the current tip).
+    pub start_checkpoint: Option<u64>,
+    /// Stop after this many polls (default: run until interrupted).
+    pub max_polls: Option<usize>,
+    pub include_framework: bool,
+}
+
+impl WorkflowWatchConfig {
+    pub fn new(package_id: impl Into<String>) -> Self {
+        Self {
+            package_id: package_id.into(),
+            poll_interval: Duration::from_secs(30),
+            max_checkpoints_per_poll: 20,
+            start_checkpoint: None,
+            max_polls: None,
+            include_framework: false,
+        }
+    }
+}
+
+/// One workflow run triggered by a discovered transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowWatchRun {
+    pub checkpoint: u64,
+    pub digest: String,
+    pub success: bool,
+    pub succeeded_steps: usize,
+    pub failed_steps: usize,
+}
+
+/// Watch session totals.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkflowWatchSummary {
+    pub package_id: String,
+    pub polls: usize,
+    pub checkpoints_scanned: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_checkpoint: Option<u64>,
+    pub skipped_duplicates: usize,
+    /// Polls that failed and were retried.
+    #[serde(default)]
+    pub failed_polls: usize,
+    pub runs: Vec<WorkflowWatchRun>,
+}
+
+/// Default cursor location for a spec file: `<spec>.watch.json` beside it.
+pub fn default_watch_cursor_path(spec_path: &Path) -> PathBuf {
+    spec_path.with_extension("watch.json")
+}
+
+/// Inclusive checkpoint window for the next poll, or `None` when caught up.
+pub fn next_watch_window(
+    cursor: &WorkflowWatchCursor,
+    start_checkpoint: Option<u64>,
+    tip: u64,
+    max_checkpoints_per_poll: u64,
+) -> Option<(u64, u64)> {
+    let first = match cursor.last_checkpoint {
+        Some(last) => last.checked_add(1)?,
+        None => start_checkpoint.unwrap_or(tip),
+    };
+    if first > tip {
+        return None;
+    }
+    let last = first
+        .saturating_add(max_checkpoints_per_poll.max(1) - 1)
+        .min(tip);
+    Some((first, last))
+}
+
+/// Wait before retrying after `failures` consecutive failed polls: the poll
+/// interval, doubled per further failure, capped at [`MAX_WATCH_RETRY_BACKOFF`].
+pub fn watch_retry_backoff(poll_interval: Duration, failures: u32) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1).min(16));
+    poll_interval
+        .saturating_mul(factor)
+        .min(MAX_WATCH_RETRY_BACKOFF)
+}
+
+/// Copy of `spec` with its replay/analyze_replay steps pointed at `target`.
+pub fn retarget_workflow_spec(spec: &WorkflowSpec, target: &DiscoverTarget) -> WorkflowSpec {
+    let mut spec = spec.clone();
+    for step in &mut spec.steps {
+        match &mut step.action {
+            WorkflowStepAction::Replay(replay) => {
+                replay.digest = Some(target.digest.clone());
+                replay.checkpoint = Some(target.checkpoint.to_string());
+                replay.latest = None;
+                replay.state_json = None;
+            }
+            WorkflowStepAction::AnalyzeReplay(analyze) => {
+                analyze.digest = target.digest.clone();
+                analyze.checkpoint = Some(target.checkpoint);
+            }
+            WorkflowStepAction::Command(_) => {}
+        }
+    }
+    spec
+}
+
+/// Poll `source` (Walrus) and run `run_target` for every new transaction touching
+/// the watched package. The cursor is saved to `cursor_path` after each run, so a
+/// restarted watch resumes without re-running digests.
+///
+/// A poll that fails is logged and retried after [`watch_retry_backoff`]; the
+/// checkpoint window is not advanced until every target in it has run.
+pub fn watch_workflow<S, F>(
+    source: &S,
+    spec: &WorkflowSpec,
+    config: &WorkflowWatchConfig,
+    cursor_path: Option<&Path>,
+    mut run_target: F,
+) -> Result<WorkflowWatchSummary>
+where
+    S: WatchCheckpointSource + ?Sized,
+    F: FnMut(&DiscoverTarget, &WorkflowSpec) -> Result<WorkflowRunReport>,
+{
+    if config.max_checkpoints_per_poll == 0
+        || config.max_checkpoints_per_poll > MAX_WATCH_CHECKPOINTS_PER_POLL
+    {
+        bail!(
+            "max_checkpoints_per_poll must be between 1 and {}",
+            MAX_WATCH_CHECKPOINTS_PER_POLL
+        );
+    }
+    if !spec.steps.iter().any(|step| {
+        matches!(
+            step.action,
+            WorkflowStepAction::Replay(_) | WorkflowStepAction::AnalyzeReplay(_)
+        )
+    }) {
+        bail!("workflow has no replay or analyze_replay steps to run per transaction");
+    }
+
+    let package_id = normalize_package_id(&config.package_id)?;
+    let mut cursor = match cursor_path {
+        Some(path) => WorkflowWatchCursor::load(path)?,
+        None => WorkflowWatchCursor::default(),
+    };
+    if let Some(existing) = cursor.package_id.as_deref() {
+        if existing != package_id {
+            return Err(anyhow!(
+                "watch cursor tracks package {existing}, not {package_id}"
+            ));
+        }
+    }
+    cursor.package_id = Some(package_id.clone());
+    let save = |cursor: &WorkflowWatchCursor| match cursor_path {
+        Some(path) => cursor.save(path),
+        None => Ok(()),
+    };
+
+    let mut summary = WorkflowWatchSummary {
+        package_id: package_id.clone(),
+        last_checkpoint: cursor.last_checkpoint,
+        ..WorkflowWatchSummary::default()
+    };
+    let polls_left = |polls: usize| !matches!(config.max_polls, Some(max) if polls >= max);
+    // One poll: scan the next window and run its new targets. Returns whether the
+    // watch has caught up with the tip.
+    let mut poll =
+        |cursor: &mut WorkflowWatchCursor, summary: &mut WorkflowWatchSummary| -> Result<bool> {
+            let tip = source.latest_checkpoint()?;
+            let Some((first, last)) = next_watch_window(
+                cursor,
+                config.start_checkpoint,
+                tip,
+                config.max_checkpoints_per_poll,
+            ) else {
+                return Ok(true);
+            };
+            let discovered = source.discover(first, last, &package_id, config.include_framework)?;
+            for target in &discovered.targets {
+                if cursor.has_seen(&target.digest) {
+                    summary.skipped_duplicates += 1;
+                    continue;
+                }
+                let report = run_target(target, &retarget_workflow_spec(spec, target))?;
+                summary.runs.push(WorkflowWatchRun {
+                    checkpoint: target.checkpoint,
+                    digest: target.digest.clone(),
+                    success: report.failed_steps == 0,
+                    succeeded_steps: report.succeeded_steps,
+                    failed_steps: report.failed_steps,
+                });
+                cursor.mark_seen(&target.digest);
+                save(cursor)?;
+            }
+            summary.checkpoints_scanned += discovered.checkpoints_scanned;
+            cursor.last_checkpoint = Some(last);
+            save(cursor)?;
+            Ok(last >= tip)
+        };
+
+    let mut failures = 0u32;
+    while polls_left(summary.polls) {
+        summary.polls += 1;
+        let wait = match poll(&mut cursor, &mut summary) {
+            Ok(caught_up) => {
+                failures = 0;
+                caught_up.then_some(config.poll_interval)
+            }
+            Err(err) => {
+                failures = failures.saturating_add(1);
+                summary.failed_polls += 1;
+                let backoff = watch_retry_backoff(config.poll_interval, failures);
+                eprintln!(
+                    "[workflow-watch] poll {} failed: {:#}; retrying in {:?}",
+                    summary.polls, err, backoff
+                );
+                Some(backoff)
+            }
+        };
+        summary.last_checkpoint = cursor.last_checkpoint;
+
+        if let Some(wait) = wait.filter(|_| polls_left(summary.polls)) {
+            std::thread::sleep(wait);
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(digest: &str, checkpoint: u64) -> DiscoverTarget {
+        DiscoverTarget {
+            checkpoint,
+            digest: digest.to_string(),
+            sender: "0x1".to_string(),
+            commands: 1,
+            input_objects: 0,
+            output_objects: 0,
+            package_ids: Vec::new(),
+            move_calls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn next_watch_window_resumes_after_cursor() {
+        assert_eq!(
+            default_watch_cursor_path(Path::new("specs/cetus.yaml")),
+            PathBuf::from("specs/cetus.watch.json")
+        );
+
+        let mut cursor = WorkflowWatchCursor::default();
+        assert_eq!(next_watch_window(&cursor, None, 500, 20), Some((500, 500)));
+        assert_eq!(
+            next_watch_window(&cursor, Some(400), 500, 20),
+            Some((400, 419))
+        );
+
+        cursor.last_checkpoint = Some(490);
+        assert_eq!(
+            next_watch_window(&cursor, Some(400), 500, 20),
+            Some((491, 500))
+        );
+        cursor.last_checkpoint = Some(500);
+        assert_eq!(next_watch_window(&cursor, None, 500, 20), None);
+    }
+
+    #[test]
+    fn cursor_dedupes_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("watch").join("cursor.json");
+        assert_eq!(
+            WorkflowWatchCursor::load(&path).unwrap(),
+            WorkflowWatchCursor::default()
+        );
+
+        let mut cursor = WorkflowWatchCursor {
+            package_id: Some("0x2".to_string()),
+            last_checkpoint: Some(7),
+            ..WorkflowWatchCursor::default()
+        };
+        for idx in 0..=WATCH_DEDUPE_WINDOW {
+            cursor.mark_seen(&format!("digest{idx}"));
+        }
+        assert!(!cursor.has_seen("digest0"));
+        assert!(cursor.has_seen(&format!("digest{WATCH_DEDUPE_WINDOW}")));
+
+        cursor.save(&path).unwrap();
+        assert_eq!(WorkflowWatchCursor::load(&path).unwrap(), cursor);
+    }
+
+    /// Source whose first `latest_checkpoint` call fails.
+    struct FlakySource {
+        tip_calls: std::cell::Cell<usize>,
+    }
+
+    impl WatchCheckpointSource for FlakySource {
+        fn latest_checkpoint(&self) -> Result<u64> {
+            let calls = self.tip_calls.get();
+            self.tip_calls.set(calls + 1);
+            if calls == 0 {
+                bail!("walrus aggregator unreachable");
+            }
+            Ok(10)
+        }
+
+        fn discover(
+            &self,
+            first: u64,
+            last: u64,
+            _package_id: &str,
+            include_framework: bool,
+        ) -> Result<DiscoverOutput> {
+            assert_eq!((first, last), (10, 10));
+            Ok(DiscoverOutput {
+                success: true,
+                checkpoints_scanned: 1,
+                transactions_scanned: 1,
+                ptbs_scanned: 1,
+                matches: 1,
+                package_filter: None,
+                include_framework,
+                limit: usize::MAX,
+                truncated: false,
+                checkpoints: vec![10],
+                targets: vec![target("Digest10", 10)],
+            })
+        }
+    }
+
+    #[test]
+    fn failed_poll_is_retried_without_advancing_cursor() {
+        let spec: WorkflowSpec = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "steps": [{"kind": "analyze_replay", "digest": "old"}]
+        }))
+        .unwrap();
+        let config = WorkflowWatchConfig {
+            poll_interval: Duration::ZERO,
+            start_checkpoint: Some(10),
+            max_polls: Some(2),
+            ..WorkflowWatchConfig::new("0x2")
+        };
+        let source = FlakySource {
+            tip_calls: std::cell::Cell::new(0),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let cursor_path = dir.path().join("cursor.json");
+
+        let summary = watch_workflow(&source, &spec, &config, Some(&cursor_path), |_, _| {
+            Ok(WorkflowRunReport {
+                spec_file: "spec.json".to_string(),
+                name: None,
+                description: None,
+                dry_run: false,
+                total_steps: 1,
+                succeeded_steps: 1,
+                failed_steps: 0,
+                stopped_early: false,
+                elapsed_ms: 0,
+                steps: Vec::new(),
+            })
+        })
+        .expect("a failed poll does not end the watch");
+
+        assert_eq!(summary.polls, 2);
+        assert_eq!(summary.failed_polls, 1);
+        assert_eq!(summary.runs.len(), 1);
+        assert_eq!(summary.runs[0].digest, "Digest10");
+        assert_eq!(summary.last_checkpoint, Some(10));
+        let cursor = WorkflowWatchCursor::load(&cursor_path).unwrap();
+        assert_eq!(cursor.last_checkpoint, Some(10));
+        assert!(cursor.has_seen("Digest10"));
+
+        assert_eq!(
+            watch_retry_backoff(Duration::from_secs(30), 1).as_secs(),
+            30
+        );
+        assert_eq!(
+            watch_retry_backoff(Duration::from_secs(30), 3).as_secs(),
+            120
+        );
+        assert_eq!(
+            watch_retry_backoff(Duration::from_secs(30), 40),
+            MAX_WATCH_RETRY_BACKOFF
+        );
+    }
+
+    #[test]
+    fn retargets_replay_and_analyze_steps() {
+        let spec: WorkflowSpec = serde_json::from_value(serde_json::json!({
+            "version": 1,
+            "steps": [
+                {"kind": "replay", "latest": 5, "source": "walrus"},
+                {"kind": "analyze_replay", "digest": "old"},
+                {"kind": "command", "args": ["status"]}
+            ]
+        }))
+        .unwrap();
+
+        let spec = retarget_workflow_spec(&spec, &target("NewDigest", 42));
+        spec.validate().expect("retargeted spec validates");
+        let WorkflowStepAction::Replay(replay) = &spec.steps[0].action else {
+            panic!("expected replay step");
+        };
+        assert_eq!(replay.digest.as_deref(), Some("NewDigest"));
+        assert_eq!(replay.checkpoint.as_deref(), Some("42"));
+        assert_eq!(replay.latest, None);
+        let WorkflowStepAction::AnalyzeReplay(analyze) = &spec.steps[1].action else {
+            panic!("expected analyze_replay step");
+        };
+        assert_eq!(analyze.digest, "NewDigest");
+        assert_eq!(analyze.checkpoint, Some(42));
+    }
+}
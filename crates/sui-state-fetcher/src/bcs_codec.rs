@@ -5,13 +5,17 @@
 //! - file import/cache pipelines
 //! - Python bindings
 
+use std::str::FromStr;
+
 use anyhow::{Context, Result};
 use base64::Engine;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
 use serde_json::Value;
 use sui_sandbox_types::{
-    FetchedTransaction, PtbArgument, PtbCommand, TransactionDigest, TransactionEffectsSummary,
-    TransactionInput,
+    FetchedTransaction, GasPaymentObject, PtbArgument, PtbCommand, TransactionDigest,
+    TransactionEffectsSummary, TransactionInput,
 };
 use sui_types::move_package::MovePackage;
 use sui_types::object::{Data as SuiData, Object as SuiObject};
@@ -42,11 +46,69 @@ pub fn deserialize_transaction_data_base64(encoded: &str) -> Result<TransactionD
     deserialize_transaction_data(&raw)
 }
 
+/// Everything [`normalize_transaction_json_with_report`] rewrote, and the fields it
+/// could not confidently interpret (left as-is).
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TransactionJsonNormalizationReport {
+    pub normalizations: Vec<TransactionJsonNormalization>,
+    pub uncertain: Vec<TransactionJsonUncertainField>,
+}
+
+/// One rewrite applied to the input JSON.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransactionJsonNormalization {
+    /// JSON pointer to the field in the input.
+    pub path: String,
+    /// `type_args_renamed`, `whitespace_trimmed`, `hex_prefix_added`,
+    /// `hex_prefix_lowercased`, `hex_lowercased`, `mutability_normalized` or
+    /// `type_tag_parsed`.
+    pub kind: &'static str,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// A field that looked like it needed normalizing but did not match a known shape.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransactionJsonUncertainField {
+    /// JSON pointer to the field in the input.
+    pub path: String,
+    pub value: Value,
+    pub reason: String,
+}
+
+impl TransactionJsonNormalizationReport {
+    fn applied(&mut self, path: &str, kind: &'static str, before: Value, after: Value) {
+        self.normalizations.push(TransactionJsonNormalization {
+            path: path.to_string(),
+            kind,
+            before,
+            after,
+        });
+    }
+
+    fn uncertain(&mut self, path: &str, value: Value, reason: impl Into<String>) {
+        self.uncertain.push(TransactionJsonUncertainField {
+            path: path.to_string(),
+            value,
+            reason: reason.into(),
+        });
+    }
+}
+
 /// Normalize Snowflake-style transaction JSON into a serde shape accepted by Sui `TransactionData`.
 pub fn normalize_transaction_json(value: &Value) -> Value {
+    normalize_transaction_json_with_report(value).0
+}
+
+/// Like [`normalize_transaction_json`], also reporting every rewrite it applied and
+/// every field it could not confidently interpret.
+pub fn normalize_transaction_json_with_report(
+    value: &Value,
+) -> (Value, TransactionJsonNormalizationReport) {
     let mut normalized = value.clone();
-    normalize_transaction_json_value(&mut normalized);
-    normalized
+    let mut report = TransactionJsonNormalizationReport::default();
+    normalize_transaction_json_value(&mut normalized, "", &mut report);
+    (normalized, report)
 }
 
 /// Deserialize transaction JSON (Snowflake or canonical serde shape) into `TransactionData`.
@@ -69,6 +131,35 @@ pub fn transaction_json_to_bcs(json_str: &str) -> Result<Vec<u8>> {
     bcs::to_bytes(&tx_data).context("Failed to serialize transaction JSON to BCS")
 }
 
+/// Convert transaction JSON into raw BCS bytes, with the normalization report.
+///
+/// When the normalized JSON does not parse, the error lists the uncertain fields,
+/// which are the usual culprits in a bad export.
+pub fn transaction_json_to_bcs_with_report(
+    json_str: &str,
+) -> Result<(Vec<u8>, TransactionJsonNormalizationReport)> {
+    let value: Value =
+        serde_json::from_str(json_str).context("Failed to parse transaction JSON text")?;
+    let (normalized, report) = normalize_transaction_json_with_report(&value);
+    let tx_data: TransactionData = serde_json::from_value(normalized).with_context(|| {
+        let uncertain: Vec<String> = report
+            .uncertain
+            .iter()
+            .map(|field| format!("{} ({})", field.path, field.reason))
+            .collect();
+        if uncertain.is_empty() {
+            "Failed to parse transaction JSON as Sui TransactionData".to_string()
+        } else {
+            format!(
+                "Failed to parse transaction JSON as Sui TransactionData; uncertain fields: {}",
+                uncertain.join(", ")
+            )
+        }
+    })?;
+    let bcs = bcs::to_bytes(&tx_data).context("Failed to serialize transaction JSON to BCS")?;
+    Ok((bcs, report))
+}
+
 /// Convert transaction JSON into base64-encoded BCS bytes.
 pub fn transaction_json_to_bcs_base64(json_str: &str) -> Result<String> {
     let raw = transaction_json_to_bcs(json_str)?;
@@ -101,9 +192,22 @@ pub fn transaction_data_to_fetched_transaction(
         effects,
         timestamp_ms,
         checkpoint,
+        gas_payment: gas_payment_objects(tx_data),
     }
 }
 
+/// Gas payment coins referenced by `tx_data`, primary coin first.
+pub fn gas_payment_objects(tx_data: &TransactionData) -> Vec<GasPaymentObject> {
+    tx_data
+        .gas()
+        .iter()
+        .map(|(id, version, _)| GasPaymentObject {
+            object_id: AccountAddress::from(*id).to_hex_literal(),
+            version: version.value(),
+        })
+        .collect()
+}
+
 /// Deserialize a transaction from raw BCS bytes into sandbox format.
 pub fn deserialize_transaction(
     raw_bcs: &[u8],
@@ -148,6 +252,16 @@ pub fn deserialize_package(raw_bcs: &[u8]) -> Result<PackageData> {
     Ok(package_data_from_move_package(&pkg))
 }
 
+/// Digest of the full BCS-encoded `Object` in `full_bcs`, computed from its bytes.
+///
+/// `None` unless `full_bcs` decodes to a Move object whose contents are exactly
+/// `contents`, so the digest vouches for the bytes the replay will execute.
+pub fn object_digest_for_contents(full_bcs: &[u8], contents: &[u8]) -> Option<String> {
+    let obj = bcs::from_bytes::<SuiObject>(full_bcs).ok()?;
+    let move_obj = obj.data.try_as_move()?;
+    (move_obj.contents() == contents).then(|| obj.digest().to_string())
+}
+
 /// Deserialize package data from base64 BCS.
 pub fn deserialize_package_base64(encoded: &str) -> Result<PackageData> {
     let raw = decode_base64_bytes(encoded).context("Failed to decode package BCS base64")?;
@@ -242,75 +356,205 @@ fn convert_call_arg(arg: &CallArg) -> TransactionInput {
     }
 }
 
-fn normalize_transaction_json_value(value: &mut Value) {
+/// Keys whose string values are addresses/object IDs.
+const ADDRESS_KEYS: [&str; 8] = [
+    "address",
+    "sender",
+    "owner",
+    "package",
+    "id",
+    "object_id",
+    "package_id",
+    "original_id",
+];
+
+/// Keys whose arrays hold type tags.
+const TYPE_TAG_LIST_KEYS: [&str; 2] = ["type_arguments", "type_params"];
+
+fn normalize_transaction_json_value(
+    value: &mut Value,
+    path: &str,
+    report: &mut TransactionJsonNormalizationReport,
+) {
     match value {
         Value::Object(map) => {
             // Snowflake may emit StructTag as { ..., "type_args": [...] }.
             if let Some(type_args) = map.remove("type_args") {
-                map.entry("type_params".to_string()).or_insert(type_args);
+                if map.contains_key("type_params") {
+                    report.uncertain(
+                        &json_pointer(path, "type_args"),
+                        type_args,
+                        "both type_args and type_params present; kept type_params",
+                    );
+                } else {
+                    report.applied(
+                        &json_pointer(path, "type_args"),
+                        "type_args_renamed",
+                        Value::String("type_args".to_string()),
+                        Value::String("type_params".to_string()),
+                    );
+                    map.insert("type_params".to_string(), type_args);
+                }
             }
 
-            for key in [
-                "address",
-                "sender",
-                "owner",
-                "package",
-                "id",
-                "object_id",
-                "package_id",
-                "original_id",
-            ] {
+            for key in ADDRESS_KEYS {
                 if let Some(Value::String(s)) = map.get_mut(key) {
-                    normalize_hex_address_in_place(s);
+                    normalize_hex_address_in_place(s, &json_pointer(path, key), report);
+                }
+            }
+
+            for key in TYPE_TAG_LIST_KEYS {
+                if let Some(Value::Array(tags)) = map.get_mut(key) {
+                    let list_path = json_pointer(path, key);
+                    for (idx, tag) in tags.iter_mut().enumerate() {
+                        parse_type_tag_string_in_place(
+                            tag,
+                            &json_pointer(&list_path, &idx.to_string()),
+                            report,
+                        );
+                    }
                 }
             }
 
             // Accept flexible casing/format for shared-object mutability.
             if let Some(Value::String(s)) = map.get_mut("mutability") {
-                *s = normalize_mutability_string(s);
+                let child_path = json_pointer(path, "mutability");
+                match normalize_mutability_string(s) {
+                    Some(normalized) if normalized != *s => {
+                        report.applied(
+                            &child_path,
+                            "mutability_normalized",
+                            Value::String(s.clone()),
+                            Value::String(normalized.to_string()),
+                        );
+                        *s = normalized.to_string();
+                    }
+                    Some(_) => {}
+                    None => report.uncertain(
+                        &child_path,
+                        Value::String(s.clone()),
+                        "unknown shared-object mutability",
+                    ),
+                }
             }
 
-            for child in map.values_mut() {
-                normalize_transaction_json_value(child);
+            for (key, child) in map.iter_mut() {
+                normalize_transaction_json_value(child, &json_pointer(path, key), report);
             }
         }
         Value::Array(arr) => {
-            for item in arr {
-                normalize_transaction_json_value(item);
+            for (idx, item) in arr.iter_mut().enumerate() {
+                normalize_transaction_json_value(
+                    item,
+                    &json_pointer(path, &idx.to_string()),
+                    report,
+                );
             }
         }
         _ => {}
     }
 }
 
-fn normalize_hex_address_in_place(s: &mut String) {
+/// Append `token` to a JSON pointer, escaping `~` and `/` (RFC 6901).
+fn json_pointer(parent: &str, token: &str) -> String {
+    format!("{}/{}", parent, token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Rewrite an address to `0x`-prefixed lowercase hex: trims whitespace, adds a
+/// missing `0x`, lowercases a `0X` prefix and the digits.
+fn normalize_hex_address_in_place(
+    s: &mut String,
+    path: &str,
+    report: &mut TransactionJsonNormalizationReport,
+) {
     let trimmed = s.trim();
-    if trimmed.starts_with("0x") || trimmed.starts_with("0X") {
-        if trimmed != s {
-            *s = trimmed.to_string();
-        }
+    if trimmed != s {
+        report.applied(
+            path,
+            "whitespace_trimmed",
+            Value::String(s.clone()),
+            Value::String(trimmed.to_string()),
+        );
+        *s = trimmed.to_string();
+    }
+    let (prefix, digits) = match s.get(..2) {
+        Some(prefix @ ("0x" | "0X")) => (Some(prefix), &s[2..]),
+        _ => (None, s.as_str()),
+    };
+    if !is_hex_address_like(digits) {
+        report.uncertain(
+            path,
+            Value::String(s.clone()),
+            "not a hex address of at most 32 bytes; left unchanged",
+        );
         return;
     }
-    if is_hex_address_like(trimmed) {
-        *s = format!("0x{trimmed}");
+    let normalized = format!("0x{}", digits.to_ascii_lowercase());
+    if normalized == *s {
+        return;
     }
+    let kind = match prefix {
+        None => "hex_prefix_added",
+        Some("0X") => "hex_prefix_lowercased",
+        Some(_) => "hex_lowercased",
+    };
+    report.applied(
+        path,
+        kind,
+        Value::String(s.clone()),
+        Value::String(normalized.clone()),
+    );
+    *s = normalized;
 }
 
 fn is_hex_address_like(s: &str) -> bool {
     !s.is_empty() && s.len() <= 64 && s.chars().all(|c| c.is_ascii_hexdigit())
 }
 
-fn normalize_mutability_string(s: &str) -> String {
+/// Replace a type tag given as a string (`0x2::coin::Coin<0x2::sui::SUI>`) with its
+/// serde shape; nested generics are parsed along with it.
+fn parse_type_tag_string_in_place(
+    tag: &mut Value,
+    path: &str,
+    report: &mut TransactionJsonNormalizationReport,
+) {
+    let Value::String(text) = tag else {
+        return;
+    };
+    let parsed = TypeTag::from_str(text.trim())
+        .map_err(|e| e.to_string())
+        .and_then(|type_tag| serde_json::to_value(type_tag).map_err(|e| e.to_string()));
+    match parsed {
+        Ok(mut value) => {
+            // The serde shape is canonical already; normalize it without reporting.
+            normalize_transaction_json_value(
+                &mut value,
+                path,
+                &mut TransactionJsonNormalizationReport::default(),
+            );
+            report.applied(path, "type_tag_parsed", tag.clone(), value.clone());
+            *tag = value;
+        }
+        Err(err) => report.uncertain(
+            path,
+            tag.clone(),
+            format!("type tag string does not parse: {}", err),
+        ),
+    }
+}
+
+/// Canonical spelling of a shared-object mutability; `None` when unrecognized.
+fn normalize_mutability_string(s: &str) -> Option<&'static str> {
     let collapsed: String = s
         .chars()
         .filter(|c| !c.is_ascii_whitespace() && *c != '_' && *c != '-')
         .collect::<String>()
         .to_ascii_lowercase();
     match collapsed.as_str() {
-        "mutable" => "Mutable".to_string(),
-        "immutable" => "Immutable".to_string(),
-        "nonexclusivewrite" => "NonExclusiveWrite".to_string(),
-        _ => s.to_string(),
+        "mutable" => Some("Mutable"),
+        "immutable" => Some("Immutable"),
+        "nonexclusivewrite" => Some("NonExclusiveWrite"),
+        _ => None,
     }
 }
 
@@ -321,6 +565,36 @@ mod tests {
     use sui_types::base_types::SuiAddress;
     use sui_types::transaction::{ProgrammableTransaction, TransactionData, TransactionKind};
 
+    #[test]
+    fn object_digest_is_computed_only_for_matching_contents() {
+        use sui_types::base_types::{ObjectID, SequenceNumber};
+        use sui_types::digests::TransactionDigest;
+        use sui_types::object::{MoveObject, Object, Owner};
+
+        let coin = MoveObject::new_gas_coin(
+            SequenceNumber::from_u64(7),
+            ObjectID::from_hex_literal("0xc0").expect("id"),
+            1_000,
+        );
+        let obj = Object::new_move(
+            coin,
+            Owner::AddressOwner(SuiAddress::ZERO),
+            TransactionDigest::genesis_marker(),
+        );
+        let full = bcs::to_bytes(&obj).expect("object bcs");
+        let contents = obj.data.try_as_move().expect("move object").contents();
+
+        assert_eq!(
+            object_digest_for_contents(&full, contents),
+            Some(obj.digest().to_string())
+        );
+        // Bytes from elsewhere (e.g. a `contents` fallback) are not vouched for.
+        let mut other = contents.to_vec();
+        *other.last_mut().expect("balance byte") ^= 1;
+        assert_eq!(object_digest_for_contents(&full, &other), None);
+        assert_eq!(object_digest_for_contents(contents, contents), None);
+    }
+
     #[test]
     fn transaction_json_to_bcs_roundtrip_canonical() {
         let sender = SuiAddress::from(AccountAddress::from_hex_literal("0x1").expect("sender"));
@@ -425,6 +699,146 @@ mod tests {
         );
     }
 
+    fn report_kinds(report: &TransactionJsonNormalizationReport) -> Vec<(&str, &str)> {
+        report
+            .normalizations
+            .iter()
+            .map(|n| (n.path.as_str(), n.kind))
+            .collect()
+    }
+
+    #[test]
+    fn transaction_json_to_bcs_with_report_handles_mixed_case_and_whitespace_addresses() {
+        let sender = SuiAddress::from(AccountAddress::from_hex_literal("0xabc").expect("sender"));
+        let tx_data = TransactionData::new_with_gas_coins(
+            TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![],
+                commands: vec![],
+            }),
+            sender,
+            vec![],
+            123,
+            9,
+        );
+        let expected_bcs = bcs::to_bytes(&tx_data).expect("tx bcs");
+        let canonical = serde_json::to_string(&tx_data).expect("tx json");
+        let (_, report) = transaction_json_to_bcs_with_report(&canonical).expect("canonical");
+        assert_eq!(report, TransactionJsonNormalizationReport::default());
+
+        let sender_hex = sender.to_string();
+        let quirky = canonical
+            .replacen(
+                &format!("\"sender\":\"{}\"", sender_hex),
+                &format!("\"sender\":\"0X{}\"", sender_hex[2..].to_ascii_uppercase()),
+                1,
+            )
+            .replacen(
+                &format!("\"owner\":\"{}\"", sender_hex),
+                &format!("\"owner\":\" {} \"", &sender_hex[2..]),
+                1,
+            );
+        assert_ne!(quirky, canonical);
+        let (got_bcs, report) = transaction_json_to_bcs_with_report(&quirky).expect("quirky");
+        assert_eq!(got_bcs, expected_bcs);
+        let kinds: Vec<&str> = report.normalizations.iter().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "hex_prefix_lowercased",
+                "whitespace_trimmed",
+                "hex_prefix_added"
+            ]
+        );
+        assert!(report.normalizations[0].path.ends_with("/sender"));
+        assert!(report.normalizations[1].path.ends_with("/owner"));
+        assert!(report.uncertain.is_empty());
+    }
+
+    #[test]
+    fn normalize_transaction_json_with_report_renames_nested_type_args() {
+        let input = serde_json::json!({
+            "type_arguments": [{
+                "struct": {
+                    "address": "0x2",
+                    "module": "coin",
+                    "name": "Coin",
+                    "type_args": [{
+                        "struct": {
+                            "address": "2",
+                            "module": "sui",
+                            "name": "SUI",
+                            "type_args": []
+                        }
+                    }]
+                }
+            }]
+        });
+        let (normalized, report) = normalize_transaction_json_with_report(&input);
+        let inner = &normalized["type_arguments"][0]["struct"]["type_params"][0]["struct"];
+        assert_eq!(inner["address"], "0x2");
+        assert!(inner.get("type_params").is_some());
+        assert_eq!(
+            report_kinds(&report),
+            vec![
+                ("/type_arguments/0/struct/type_args", "type_args_renamed"),
+                (
+                    "/type_arguments/0/struct/type_params/0/struct/type_args",
+                    "type_args_renamed"
+                ),
+                (
+                    "/type_arguments/0/struct/type_params/0/struct/address",
+                    "hex_prefix_added"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_transaction_json_with_report_parses_type_tag_strings() {
+        let input = serde_json::json!({
+            "type_arguments": ["0x2::coin::Coin<0x2::sui::SUI>", "0x2::coin::Coin<"]
+        });
+        let (normalized, report) = normalize_transaction_json_with_report(&input);
+        let expected = normalize_transaction_json(
+            &serde_json::to_value(
+                TypeTag::from_str("0x2::coin::Coin<0x2::sui::SUI>").expect("type tag"),
+            )
+            .expect("type tag json"),
+        );
+        assert_eq!(normalized["type_arguments"][0], expected);
+        assert_eq!(
+            report_kinds(&report),
+            vec![("/type_arguments/0", "type_tag_parsed")]
+        );
+        assert_eq!(report.uncertain.len(), 1);
+        assert_eq!(report.uncertain[0].path, "/type_arguments/1");
+        assert_eq!(normalized["type_arguments"][1], "0x2::coin::Coin<");
+    }
+
+    #[test]
+    fn normalize_transaction_json_with_report_flags_unrecognized_fields() {
+        let input = serde_json::json!({
+            "SharedObject": {
+                "id": "not-an-address",
+                "initial_shared_version": 1,
+                "mutability": "mutable"
+            },
+            "other": {"mutability": "sometimes", "type_args": [], "type_params": []}
+        });
+        let (normalized, report) = normalize_transaction_json_with_report(&input);
+        assert_eq!(normalized["SharedObject"]["mutability"], "Mutable");
+        assert_eq!(
+            report_kinds(&report),
+            vec![("/SharedObject/mutability", "mutability_normalized")]
+        );
+        let uncertain: Vec<&str> = report.uncertain.iter().map(|u| u.path.as_str()).collect();
+        assert_eq!(
+            uncertain,
+            vec!["/SharedObject/id", "/other/type_args", "/other/mutability"]
+        );
+        assert_eq!(normalized["SharedObject"]["id"], "not-an-address");
+    }
+
     fn strip_hex_prefix_for_key_recursively(value: &mut Value, key: &str) {
         match value {
             Value::Object(map) => {
@@ -0,0 +1,190 @@
+//! Per-operation memoization for versioned object lookups.
+//!
+//! A single replay or view call can ask for the same `(object_id, version)`
+//! many times — a shared pool read by several commands, or a dynamic-field
+//! child the VM loads once per command. [`FetchMemo`] remembers each answer
+//! (including "not found") for the lifetime of one operation so the network is
+//! hit once per key. Failed fetches are not remembered: the next lookup retries.
+//!
+//! This is deliberately separate from [`crate::VersionedCache`]: the memo is
+//! created per operation, never persisted, and also records negative results.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Fetch counters for a [`FetchMemo`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FetchMemoStats {
+    /// Lookups that ran the underlying fetch.
+    pub fetched: u64,
+    /// Lookups answered from the memo.
+    pub memo_hits: u64,
+}
+
+/// One key's memoized answer; `None` until a fetch succeeds.
+type Slot<V> = Arc<Mutex<Option<Option<V>>>>;
+
+/// Memo of fetch results keyed by `K` (typically `(object_id, version)`).
+#[derive(Debug)]
+pub struct FetchMemo<K, V> {
+    slots: Mutex<HashMap<K, Slot<V>>>,
+    fetched: AtomicU64,
+    memo_hits: AtomicU64,
+}
+
+impl<K, V> Default for FetchMemo<K, V> {
+    fn default() -> Self {
+        Self {
+            slots: Mutex::new(HashMap::new()),
+            fetched: AtomicU64::new(0),
+            memo_hits: AtomicU64::new(0),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> FetchMemo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the memoized result for `key`, running `fetch` on first use.
+    ///
+    /// `fetch` answers `Ok(None)` for an object that does not exist; that answer
+    /// is memoized like a found one. An `Err` is returned to the caller and not
+    /// memoized. Only callers asking for the same key wait on each other's fetch;
+    /// other keys are fetched concurrently.
+    pub fn get_or_fetch<E>(
+        &self,
+        key: K,
+        fetch: impl FnOnce() -> Result<Option<V>, E>,
+    ) -> Result<Option<V>, E> {
+        let slot = Arc::clone(self.slots.lock().entry(key).or_default());
+        let mut answer = slot.lock();
+        if let Some(found) = answer.as_ref() {
+            self.memo_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(found.clone());
+        }
+        self.fetched.fetch_add(1, Ordering::Relaxed);
+        let value = fetch()?;
+        *answer = Some(value.clone());
+        Ok(value)
+    }
+
+    pub fn stats(&self) -> FetchMemoStats {
+        FetchMemoStats {
+            fetched: self.fetched.load(Ordering::Relaxed),
+            memo_hits: self.memo_hits.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+    use std::cell::Cell;
+
+    #[test]
+    fn repeated_lookups_fetch_once_per_version() {
+        let memo: FetchMemo<(AccountAddress, u64), Vec<u8>> = FetchMemo::new();
+        let pool = AccountAddress::from_hex_literal("0xdee9").unwrap();
+        let calls = Cell::new(0);
+        let fetch = |bytes: Vec<u8>| {
+            calls.set(calls.get() + 1);
+            Ok::<_, ()>(Some(bytes))
+        };
+
+        for _ in 0..3 {
+            assert_eq!(
+                memo.get_or_fetch((pool, 7), || fetch(vec![7])),
+                Ok(Some(vec![7]))
+            );
+        }
+        assert_eq!(
+            memo.get_or_fetch((pool, 8), || fetch(vec![8])),
+            Ok(Some(vec![8]))
+        );
+
+        assert_eq!(calls.get(), 2);
+        assert_eq!(
+            memo.stats(),
+            FetchMemoStats {
+                fetched: 2,
+                memo_hits: 2
+            }
+        );
+    }
+
+    #[test]
+    fn misses_are_memoized() {
+        let memo: FetchMemo<(AccountAddress, u64), Vec<u8>> = FetchMemo::new();
+        let id = AccountAddress::from_hex_literal("0x5").unwrap();
+        let calls = Cell::new(0);
+        for _ in 0..2 {
+            let found = memo.get_or_fetch((id, 1), || {
+                calls.set(calls.get() + 1);
+                Ok::<_, ()>(None)
+            });
+            assert_eq!(found, Ok(None));
+        }
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn failed_fetches_are_retried() {
+        let memo: FetchMemo<(AccountAddress, u64), Vec<u8>> = FetchMemo::new();
+        let id = AccountAddress::from_hex_literal("0x5").unwrap();
+        assert_eq!(
+            memo.get_or_fetch((id, 1), || Err("timeout")),
+            Err("timeout")
+        );
+        assert_eq!(
+            memo.get_or_fetch((id, 1), || Ok(Some(vec![1]))),
+            Ok(Some(vec![1]))
+        );
+        assert_eq!(
+            memo.get_or_fetch((id, 1), || Err("unreachable")),
+            Ok(Some(vec![1]))
+        );
+        assert_eq!(
+            memo.stats(),
+            FetchMemoStats {
+                fetched: 2,
+                memo_hits: 1
+            }
+        );
+    }
+
+    #[test]
+    fn distinct_keys_fetch_concurrently() {
+        use std::sync::mpsc::channel;
+        use std::time::Duration;
+
+        let memo: FetchMemo<u64, u64> = FetchMemo::new();
+        let (started, wait_started) = channel();
+        let (done, wait_done) = channel();
+        std::thread::scope(|scope| {
+            // Key 1's fetch only succeeds if key 2 is fetched while it is in flight.
+            let slow = scope.spawn(|| {
+                memo.get_or_fetch(1, || {
+                    started.send(()).unwrap();
+                    wait_done
+                        .recv_timeout(Duration::from_secs(5))
+                        .map(|_| Some(1))
+                })
+            });
+            wait_started.recv().unwrap();
+            let fast = memo.get_or_fetch(2, || {
+                done.send(()).unwrap();
+                Ok::<_, std::sync::mpsc::RecvTimeoutError>(Some(2))
+            });
+            assert_eq!(fast, Ok(Some(2)));
+            assert_eq!(slow.join().unwrap(), Ok(Some(1)));
+        });
+    }
+}
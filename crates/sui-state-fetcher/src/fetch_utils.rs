@@ -8,12 +8,102 @@ use std::collections::HashMap;
 use base64::Engine;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::TypeTag;
+use sui_resolver::address::normalize_address;
 use sui_sandbox_types::env_bool;
-use sui_transport::grpc::GrpcClient;
+use sui_transport::graphql::GraphQLObject;
+use sui_transport::grpc::global_pool;
+use tracing::debug;
 
-use crate::types::PackageData;
+use crate::types::{ObjectID, PackageData, VersionedObject};
 use crate::HistoricalStateProvider;
 
+/// Object ID of the `SuiSystemState` inner object, given the BCS of the `0x5` wrapper.
+///
+/// `0x5` holds only `id` and `version`; the actual state lives in a dynamic field of
+/// the wrapper keyed by that `u64` version, so its ID changes on system upgrades.
+pub fn system_state_inner_id(system_state_bcs: &[u8]) -> Option<AccountAddress> {
+    let version_bytes: [u8; 8] = system_state_bcs.get(32..40)?.try_into().ok()?;
+    let key_bcs = bcs::to_bytes(&u64::from_le_bytes(version_bytes)).ok()?;
+    let key_type_bcs = bcs::to_bytes(&TypeTag::U64).ok()?;
+    let id = sui_prefetch::compute_dynamic_field_id(
+        &sui_sandbox_types::SYSTEM_STATE_OBJECT_ID.to_hex_literal(),
+        &key_bcs,
+        &key_type_bcs,
+    )?;
+    AccountAddress::from_hex_literal(&id).ok()
+}
+
+/// How [`hydrate_system_state_inner`] reads the `SuiSystemState` inner object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SystemStateInnerRead {
+    /// At the version the transaction's effects recorded for it.
+    AtVersion(u64),
+    /// As of the end of the transaction's checkpoint, when no version was recorded.
+    AtCheckpoint(u64),
+}
+
+/// Add the `SuiSystemState` inner object to `objects` when `0x5` is among them but its
+/// inner state is not, reading it through `fetch`.
+///
+/// The inner object is read like any other child: at its recorded version when
+/// effects list it, otherwise as of `checkpoint`. It is never synthesized, so
+/// staking and validator transactions read the historical system state.
+pub(crate) fn hydrate_system_state_inner(
+    objects: &mut HashMap<ObjectID, VersionedObject>,
+    historical_versions: &HashMap<String, u64>,
+    checkpoint: Option<u64>,
+    fetch: impl FnOnce(&str, SystemStateInnerRead) -> anyhow::Result<GraphQLObject>,
+) {
+    let Some(wrapper) = objects.get(&sui_sandbox_types::SYSTEM_STATE_OBJECT_ID) else {
+        return;
+    };
+    let Some(inner_id) = system_state_inner_id(&wrapper.bcs_bytes) else {
+        return;
+    };
+    if objects.contains_key(&inner_id) {
+        return;
+    }
+
+    let inner_hex = inner_id.to_hex_literal();
+    let read = match historical_versions.get(&normalize_address(&inner_hex)) {
+        Some(version) => SystemStateInnerRead::AtVersion(*version),
+        None => match checkpoint {
+            Some(cp) => SystemStateInnerRead::AtCheckpoint(cp),
+            None => return,
+        },
+    };
+    let obj = match fetch(&inner_hex, read) {
+        Ok(obj) => obj,
+        Err(e) => {
+            debug!(
+                object_id = %inner_hex,
+                error = %e,
+                "failed to fetch SuiSystemState inner object"
+            );
+            return;
+        }
+    };
+    let Some(bcs_bytes) = obj
+        .bcs_base64
+        .as_deref()
+        .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+    else {
+        return;
+    };
+    objects.insert(
+        inner_id,
+        VersionedObject {
+            id: inner_id,
+            version: obj.version,
+            digest: obj.digest,
+            type_tag: obj.type_string,
+            bcs_bytes,
+            is_shared: false,
+            is_immutable: false,
+        },
+    );
+}
+
 /// Result of building package aliases.
 #[derive(Debug, Default)]
 pub struct PackageAliases {
@@ -228,7 +318,7 @@ pub fn fetch_object_via_grpc(
 ) -> Option<(TypeTag, Vec<u8>, u64)> {
     let endpoint = provider.grpc_endpoint().to_string();
     let fut = async {
-        let client = GrpcClient::new(&endpoint).await.ok()?;
+        let client = global_pool().client(&endpoint, None).await.ok()?;
         client
             .get_object_at_version(object_id, version)
             .await
@@ -238,8 +328,7 @@ pub fn fetch_object_via_grpc(
     let grpc_obj = if let Ok(handle) = tokio::runtime::Handle::try_current() {
         tokio::task::block_in_place(|| handle.block_on(fut))
     } else {
-        let rt = tokio::runtime::Runtime::new().ok()?;
-        rt.block_on(fut)
+        global_pool().block_on(fut)
     }?;
 
     let bcs_bytes = grpc_obj.bcs?;
@@ -252,6 +341,85 @@ pub fn fetch_object_via_grpc(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_system_state_inner_id_matches_dynamic_field_derivation() {
+        let mut wrapper = sui_sandbox_types::SYSTEM_STATE_OBJECT_ID.to_vec();
+        wrapper.extend_from_slice(&2u64.to_le_bytes());
+
+        let expected = sui_types::dynamic_field::derive_dynamic_field_id(
+            sui_types::SUI_SYSTEM_STATE_OBJECT_ID,
+            &TypeTag::U64,
+            &bcs::to_bytes(&2u64).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            system_state_inner_id(&wrapper),
+            Some(AccountAddress::new(expected.into_bytes()))
+        );
+        assert_eq!(system_state_inner_id(&wrapper[..32]), None);
+    }
+
+    #[test]
+    fn test_system_state_inner_falls_back_to_checkpoint_end() {
+        use sui_sandbox_types::SYSTEM_STATE_OBJECT_ID;
+        use sui_transport::graphql::ObjectOwner;
+
+        let mut wrapper = SYSTEM_STATE_OBJECT_ID.to_vec();
+        wrapper.extend_from_slice(&2u64.to_le_bytes());
+        let inner_id = system_state_inner_id(&wrapper).unwrap();
+        let system_state = VersionedObject {
+            id: SYSTEM_STATE_OBJECT_ID,
+            version: 900,
+            digest: None,
+            type_tag: Some("0x3::sui_system::SuiSystemState".to_string()),
+            bcs_bytes: wrapper,
+            is_shared: true,
+            is_immutable: false,
+        };
+        let inner_at = |version: u64| GraphQLObject {
+            address: inner_id.to_hex_literal(),
+            version,
+            digest: Some("inner-digest".to_string()),
+            type_string: Some("0x3::sui_system_state_inner::SuiSystemStateInnerV2".to_string()),
+            owner: ObjectOwner::Parent(SYSTEM_STATE_OBJECT_ID.to_hex_literal()),
+            bcs_base64: Some(base64::engine::general_purpose::STANDARD.encode([7u8; 8])),
+            content_json: None,
+            previous_transaction: None,
+        };
+        let hydrate = |historical_versions: &HashMap<String, u64>, checkpoint: Option<u64>| {
+            let mut objects = HashMap::from([(SYSTEM_STATE_OBJECT_ID, system_state.clone())]);
+            let mut reads = Vec::new();
+            hydrate_system_state_inner(
+                &mut objects,
+                historical_versions,
+                checkpoint,
+                |id, read| {
+                    assert_eq!(id, inner_id.to_hex_literal());
+                    reads.push(read);
+                    Ok(inner_at(880))
+                },
+            );
+            (objects.remove(&inner_id), reads)
+        };
+
+        // Effects don't list the inner object: read it as of the checkpoint's end.
+        let (inner, reads) = hydrate(&HashMap::new(), Some(4_200));
+        assert_eq!(reads, vec![SystemStateInnerRead::AtCheckpoint(4_200)]);
+        let inner = inner.expect("inner state hydrated");
+        assert_eq!(inner.version, 880);
+        assert_eq!(inner.bcs_bytes, vec![7u8; 8]);
+        assert!(!inner.is_shared);
+
+        // A recorded version wins over the checkpoint.
+        let recorded = HashMap::from([(normalize_address(&inner_id.to_hex_literal()), 880)]);
+        let (_, reads) = hydrate(&recorded, Some(4_200));
+        assert_eq!(reads, vec![SystemStateInnerRead::AtVersion(880)]);
+
+        // Neither: nothing to pin the read to, so nothing is fetched or synthesized.
+        let (inner, reads) = hydrate(&HashMap::new(), None);
+        assert!(inner.is_none() && reads.is_empty());
+    }
+
     #[test]
     fn test_build_aliases_empty() {
         let packages = HashMap::new();
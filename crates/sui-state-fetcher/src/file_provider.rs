@@ -2,7 +2,10 @@
 
 use std::collections::HashMap;
 use std::fs;
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
 
 use anyhow::{anyhow, Context, Result};
 use move_core_types::account_address::AccountAddress;
@@ -18,10 +21,12 @@ use crate::bcs_codec::{
 use crate::replay_builder::ReplayStateConfig;
 use crate::replay_provider::ReplayStateProvider;
 use crate::state_json::parse_replay_states_file;
+use crate::state_snapshot::{read_replay_states_file, ReplayStateFormat};
 use crate::types::{PackageData, ReplayState, VersionedObject};
 
 const STATES_DIR_NAME: &str = "states";
 const INDEX_FILE_NAME: &str = "index.json";
+const INDEX_LOCK_FILE_NAME: &str = "index.lock";
 
 /// Import request for file-based replay state ingestion.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -34,6 +39,9 @@ pub struct ImportSpec {
     pub objects: Option<PathBuf>,
     /// Packages rows (JSON/JSONL/CSV).
     pub packages: Option<PathBuf>,
+    /// Format of `state` (JSON or binary snapshot; detected by default).
+    #[serde(default)]
+    pub state_format: ReplayStateFormat,
 }
 
 /// Result summary for import operations.
@@ -46,6 +54,59 @@ pub struct ImportSummary {
     pub digests: Vec<String>,
 }
 
+/// Size and content statistics for a file-backed replay cache.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub cache_dir: PathBuf,
+    /// Number of cached replay state files.
+    pub states: usize,
+    /// Objects across all cached states.
+    pub objects: usize,
+    /// Packages across all cached states.
+    pub packages: usize,
+    /// On-disk size of the state files.
+    pub state_bytes: u64,
+    /// Raw BCS bytes of the cached objects.
+    pub object_bytes: u64,
+    /// Raw module bytes of the cached packages.
+    pub package_bytes: u64,
+    /// On-disk size of the whole cache (state files + index).
+    pub total_bytes: u64,
+    /// State files that could not be parsed (still counted in `state_bytes`).
+    pub unreadable: Vec<PathBuf>,
+}
+
+/// Selection filter for [`FileStateProvider::clear`].
+///
+/// Filters are combined with AND; an empty spec clears every cached state.
+#[derive(Debug, Clone, Default)]
+pub struct CacheClearSpec {
+    /// Only remove state files last modified at least this long ago.
+    pub older_than: Option<Duration>,
+    /// Only remove the state for this transaction digest.
+    pub digest: Option<String>,
+}
+
+/// One state file removed from the cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedCacheEntry {
+    /// Transaction digest, when the file was tracked by the index.
+    pub digest: Option<String>,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Result summary for cache clear operations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheClearSummary {
+    pub cache_dir: PathBuf,
+    /// Entries actually deleted by this call.
+    pub removed: Vec<RemovedCacheEntry>,
+    pub bytes_removed: u64,
+    /// Digests still present in the index afterwards.
+    pub states_remaining: usize,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct StateIndex {
     /// digest -> relative path (within cache dir)
@@ -61,6 +122,7 @@ pub struct FileStateProvider {
     cache_dir: PathBuf,
     states_dir: PathBuf,
     index_path: PathBuf,
+    index_lock_path: PathBuf,
 }
 
 impl FileStateProvider {
@@ -69,6 +131,7 @@ impl FileStateProvider {
         let cache_dir = cache_dir.as_ref().to_path_buf();
         let states_dir = cache_dir.join(STATES_DIR_NAME);
         let index_path = cache_dir.join(INDEX_FILE_NAME);
+        let index_lock_path = cache_dir.join(INDEX_LOCK_FILE_NAME);
 
         fs::create_dir_all(&states_dir).with_context(|| {
             format!(
@@ -81,10 +144,11 @@ impl FileStateProvider {
             cache_dir,
             states_dir,
             index_path,
+            index_lock_path,
         };
 
         if !this.index_path.exists() {
-            this.write_index(&StateIndex::default())?;
+            this.update_index(|_| Ok(()))?;
         }
 
         Ok(this)
@@ -103,7 +167,7 @@ impl FileStateProvider {
         let mut packages_imported = 0usize;
 
         if let Some(state_file) = &spec.state {
-            for state in parse_replay_states_file(state_file)? {
+            for state in read_replay_states_file(state_file, spec.state_format)? {
                 let digest = canonical_digest(&state.transaction.digest.0)?;
                 states.insert(digest, state);
             }
@@ -182,16 +246,17 @@ impl FileStateProvider {
 
         let json =
             serde_json::to_string_pretty(state).context("Failed to serialize replay state")?;
-        fs::write(&abs_path, json).with_context(|| {
-            format!("Failed to write replay state file: {}", abs_path.display())
+        // The file is written under the index lock too, so a concurrent `clear`
+        // never sees it before its index entry.
+        self.update_index(|index| {
+            write_atomic(&abs_path, json.as_bytes()).with_context(|| {
+                format!("Failed to write replay state file: {}", abs_path.display())
+            })?;
+            index
+                .states
+                .insert(digest.to_string(), rel_path.to_string_lossy().to_string());
+            Ok(())
         })?;
-
-        let mut index = self.read_index()?;
-        index
-            .states
-            .insert(digest.to_string(), rel_path.to_string_lossy().to_string());
-        self.write_index(&index)?;
-
         Ok(abs_path)
     }
 
@@ -241,6 +306,173 @@ impl FileStateProvider {
         Ok(digests)
     }
 
+    /// Count cached states, objects and packages and measure their size.
+    pub fn stats(&self) -> Result<CacheStats> {
+        let mut stats = CacheStats {
+            cache_dir: self.cache_dir.clone(),
+            ..CacheStats::default()
+        };
+        if let Ok(meta) = fs::metadata(&self.index_path) {
+            stats.total_bytes += meta.len();
+        }
+
+        for file in self.state_files()? {
+            stats.states += 1;
+            stats.state_bytes += file.bytes;
+            stats.total_bytes += file.bytes;
+            match parse_replay_states_file(&file.path) {
+                Ok(states) => {
+                    for state in states {
+                        stats.objects += state.objects.len();
+                        stats.packages += state.packages.len();
+                        stats.object_bytes += state
+                            .objects
+                            .values()
+                            .map(|obj| obj.bcs_bytes.len() as u64)
+                            .sum::<u64>();
+                        stats.package_bytes += state
+                            .packages
+                            .values()
+                            .flat_map(|pkg| pkg.modules.iter())
+                            .map(|(_, bytes)| bytes.len() as u64)
+                            .sum::<u64>();
+                    }
+                }
+                // A concurrent clear may delete the file between listing and parsing.
+                Err(_) if !file.path.exists() => {
+                    stats.states -= 1;
+                    stats.state_bytes -= file.bytes;
+                    stats.total_bytes -= file.bytes;
+                }
+                Err(_) => stats.unreadable.push(file.path),
+            }
+        }
+
+        Ok(stats)
+    }
+
+    /// Remove cached states matching `spec` and report exactly what was deleted.
+    ///
+    /// The index is rewritten (atomically) before any file is unlinked, so a
+    /// concurrent reader either still resolves the old path or no longer sees
+    /// the digest at all; it never sees a half-written index. Selection and the
+    /// index rewrite run under the index lock, so a concurrent `put_state` is
+    /// never lost. Files already removed by a concurrent clear are skipped and
+    /// not reported.
+    pub fn clear(&self, spec: &CacheClearSpec) -> Result<CacheClearSummary> {
+        let digest_filter = spec.digest.as_deref().map(canonical_digest).transpose()?;
+        let now = SystemTime::now();
+
+        let _lock = self.lock_index()?;
+        let mut index = self.read_index()?;
+        let digest_by_path: HashMap<PathBuf, String> = index
+            .states
+            .iter()
+            .map(|(digest, rel)| (self.cache_dir.join(rel), digest.clone()))
+            .collect();
+
+        let mut selected = Vec::new();
+        for file in self.state_files()? {
+            let digest = digest_by_path.get(&file.path).cloned();
+            if let Some(wanted) = &digest_filter {
+                let file_name = file.path.file_name().and_then(|n| n.to_str());
+                let matches = digest.as_deref() == Some(wanted.as_str())
+                    || file_name == Some(digest_filename(wanted).as_str())
+                    || file_name == Some(format!("{}.json", wanted).as_str());
+                if !matches {
+                    continue;
+                }
+            }
+            if let Some(min_age) = spec.older_than {
+                let age = file
+                    .modified
+                    .and_then(|modified| now.duration_since(modified).ok())
+                    .unwrap_or_default();
+                if age < min_age {
+                    continue;
+                }
+            }
+            selected.push(RemovedCacheEntry {
+                digest,
+                path: file.path,
+                bytes: file.bytes,
+            });
+        }
+
+        let before = index.states.len();
+        for entry in &selected {
+            if let Some(digest) = &entry.digest {
+                index.states.remove(digest);
+            }
+        }
+        if index.states.len() != before {
+            self.write_index(&index)?;
+        }
+
+        let mut removed = Vec::with_capacity(selected.len());
+        for entry in selected {
+            match fs::remove_file(&entry.path) {
+                Ok(()) => removed.push(entry),
+                Err(err) if err.kind() == ErrorKind::NotFound => {}
+                Err(err) => {
+                    return Err(err).with_context(|| {
+                        format!("Failed to remove cached state: {}", entry.path.display())
+                    })
+                }
+            }
+        }
+
+        Ok(CacheClearSummary {
+            cache_dir: self.cache_dir.clone(),
+            bytes_removed: removed.iter().map(|entry| entry.bytes).sum(),
+            removed,
+            states_remaining: index.states.len(),
+        })
+    }
+
+    fn state_files(&self) -> Result<Vec<StateFile>> {
+        let entries = match fs::read_dir(&self.states_dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!(
+                        "Failed to list replay cache directory: {}",
+                        self.states_dir.display()
+                    )
+                })
+            }
+        };
+
+        let mut files = Vec::new();
+        for entry in entries {
+            let entry = entry.with_context(|| {
+                format!(
+                    "Failed to list replay cache directory: {}",
+                    self.states_dir.display()
+                )
+            })?;
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            // Skip files removed concurrently between listing and stat.
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if !meta.is_file() {
+                continue;
+            }
+            files.push(StateFile {
+                path,
+                bytes: meta.len(),
+                modified: meta.modified().ok(),
+            });
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
     fn read_index(&self) -> Result<StateIndex> {
         if !self.index_path.exists() {
             return Ok(StateIndex::default());
@@ -255,10 +487,42 @@ impl FileStateProvider {
             .with_context(|| format!("Invalid file replay index: {}", self.index_path.display()))
     }
 
+    /// Take an exclusive lock on `index.lock`, held until the returned file is
+    /// dropped. Every index update runs under it, so concurrent writers (threads
+    /// or processes) never overwrite each other's entries.
+    fn lock_index(&self) -> Result<fs::File> {
+        let lock = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.index_lock_path)
+            .with_context(|| {
+                format!(
+                    "Failed to open replay index lock: {}",
+                    self.index_lock_path.display()
+                )
+            })?;
+        lock.lock().with_context(|| {
+            format!(
+                "Failed to lock replay index: {}",
+                self.index_lock_path.display()
+            )
+        })?;
+        Ok(lock)
+    }
+
+    /// Read, modify and rewrite the index under [`Self::lock_index`].
+    fn update_index(&self, update: impl FnOnce(&mut StateIndex) -> Result<()>) -> Result<()> {
+        let _lock = self.lock_index()?;
+        let mut index = self.read_index()?;
+        update(&mut index)?;
+        self.write_index(&index)
+    }
+
     fn write_index(&self, index: &StateIndex) -> Result<()> {
         let raw =
             serde_json::to_string_pretty(index).context("Failed to serialize replay index")?;
-        fs::write(&self.index_path, raw).with_context(|| {
+        write_atomic(&self.index_path, raw.as_bytes()).with_context(|| {
             format!(
                 "Failed to write replay index: {}",
                 self.index_path.display()
@@ -291,6 +555,49 @@ pub fn import_replay_states(
     provider.import(spec)
 }
 
+/// Collect size/count statistics for a file-backed replay cache directory.
+pub fn replay_cache_stats(cache_dir: impl AsRef<Path>) -> Result<CacheStats> {
+    let provider = FileStateProvider::new(cache_dir)?;
+    provider.stats()
+}
+
+/// Remove cached replay states from a file-backed cache directory.
+pub fn clear_replay_cache(
+    cache_dir: impl AsRef<Path>,
+    spec: &CacheClearSpec,
+) -> Result<CacheClearSummary> {
+    let provider = FileStateProvider::new(cache_dir)?;
+    provider.clear(spec)
+}
+
+#[derive(Debug, Clone)]
+struct StateFile {
+    path: PathBuf,
+    bytes: u64,
+    modified: Option<SystemTime>,
+}
+
+/// Write via a sibling temp file + rename so readers never observe a partial file.
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name,
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    fs::write(&tmp_path, contents)?;
+    if let Err(err) = fs::rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 struct ObjectRow {
     tx_digest: Option<String>,
@@ -399,6 +706,11 @@ fn parse_transaction_row(row: &Value) -> Result<ReplayState> {
             effects: None,
             timestamp_ms,
             checkpoint,
+            gas_payment: parse_vec_from_row::<sui_sandbox_types::GasPaymentObject>(
+                obj,
+                &["gas_payment", "gas_payment_json"],
+            )?
+            .unwrap_or_default(),
         }
     };
 
@@ -769,6 +1081,7 @@ mod tests {
             effects: None,
             timestamp_ms: None,
             checkpoint: Some(7),
+            gas_payment: vec![],
         };
 
         ReplayState {
@@ -794,6 +1107,91 @@ mod tests {
         assert_eq!(loaded.protocol_version, 107);
     }
 
+    #[test]
+    fn stats_and_clear_by_digest() {
+        let tmp = TempDir::new().unwrap();
+        let provider = FileStateProvider::new(tmp.path()).unwrap();
+        provider.put_state(&sample_state("digest-a")).unwrap();
+        provider.put_state(&sample_state("digest-b")).unwrap();
+
+        let stats = provider.stats().unwrap();
+        assert_eq!(stats.states, 2);
+        assert!(stats.state_bytes > 0);
+        assert!(stats.total_bytes > stats.state_bytes);
+        assert!(stats.unreadable.is_empty());
+
+        let summary = provider
+            .clear(&CacheClearSpec {
+                digest: Some("digest-a".to_string()),
+                ..CacheClearSpec::default()
+            })
+            .unwrap();
+        assert_eq!(summary.removed.len(), 1);
+        assert_eq!(summary.removed[0].digest.as_deref(), Some("digest-a"));
+        assert_eq!(summary.bytes_removed, summary.removed[0].bytes);
+        assert_eq!(summary.states_remaining, 1);
+        assert!(provider.get_state("digest-a").is_err());
+        assert!(provider.get_state("digest-b").is_ok());
+    }
+
+    #[test]
+    fn concurrent_puts_and_clears_keep_every_index_entry() {
+        let tmp = TempDir::new().unwrap();
+        std::thread::scope(|scope| {
+            for writer in 0..4 {
+                let dir = tmp.path();
+                scope.spawn(move || {
+                    // Separate providers, as separate processes would open the cache.
+                    let provider = FileStateProvider::new(dir).unwrap();
+                    for i in 0..10 {
+                        let digest = format!("digest-{}-{}", writer, i);
+                        provider.put_state(&sample_state(&digest)).unwrap();
+                    }
+                });
+            }
+            let dir = tmp.path();
+            scope.spawn(move || {
+                let provider = FileStateProvider::new(dir).unwrap();
+                for _ in 0..10 {
+                    provider.put_state(&sample_state("digest-scratch")).unwrap();
+                    let summary = provider
+                        .clear(&CacheClearSpec {
+                            digest: Some("digest-scratch".to_string()),
+                            ..CacheClearSpec::default()
+                        })
+                        .unwrap();
+                    assert_eq!(summary.removed.len(), 1);
+                }
+            });
+        });
+
+        let provider = FileStateProvider::new(tmp.path()).unwrap();
+        assert_eq!(provider.list_digests().unwrap().len(), 40);
+    }
+
+    #[test]
+    fn clear_respects_age_filter() {
+        let tmp = TempDir::new().unwrap();
+        let provider = FileStateProvider::new(tmp.path()).unwrap();
+        provider.put_state(&sample_state("digest-new")).unwrap();
+
+        let summary = provider
+            .clear(&CacheClearSpec {
+                older_than: Some(Duration::from_secs(86_400)),
+                ..CacheClearSpec::default()
+            })
+            .unwrap();
+        assert!(summary.removed.is_empty());
+        assert_eq!(
+            provider.list_digests().unwrap(),
+            vec!["digest-new".to_string()]
+        );
+
+        let summary = provider.clear(&CacheClearSpec::default()).unwrap();
+        assert_eq!(summary.removed.len(), 1);
+        assert_eq!(summary.states_remaining, 0);
+    }
+
     #[test]
     fn import_state_file_round_trip() {
         let tmp = TempDir::new().unwrap();
@@ -809,6 +1207,7 @@ mod tests {
                 transactions: None,
                 objects: None,
                 packages: None,
+                state_format: ReplayStateFormat::Auto,
             })
             .unwrap();
 
@@ -841,6 +1240,7 @@ mod tests {
                 transactions: Some(tx_file),
                 objects: Some(obj_file),
                 packages: None,
+                state_format: ReplayStateFormat::Auto,
             })
             .unwrap();
 
@@ -885,6 +1285,7 @@ mod tests {
                 transactions: Some(tx_file),
                 objects: None,
                 packages: None,
+                state_format: ReplayStateFormat::Auto,
             })
             .unwrap();
 
@@ -0,0 +1,352 @@
+//! JSON-RPC to ReplayState conversion.
+//!
+//! Builds a `ReplayState` using only a full node's JSON-RPC API. This is useful
+//! for endpoints that expose neither the gRPC archive nor GraphQL.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! use sui_transport::jsonrpc::JsonRpcClient;
+//! use sui_state_fetcher::jsonrpc_replay::fetch_replay_state_via_jsonrpc;
+//!
+//! let client = JsonRpcClient::new("https://my-node.example:443");
+//! let state = fetch_replay_state_via_jsonrpc(&client, "D9sMA7x...")?;
+//! ```
+//!
+//! # Limitations
+//!
+//! JSON-RPC only reports the node's current protocol version, so
+//! `ReplayState::protocol_version` is only set for transactions of the current
+//! epoch; it is `0` (unknown) otherwise and can be backfilled from GraphQL.
+//! Dynamic fields are not prefetched; rely on self-heal child fetchers instead.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::{anyhow, Context, Result};
+use move_core_types::account_address::AccountAddress;
+use sui_sandbox_types::{
+    is_framework_address, GasSummary, PtbCommand, TransactionEffectsSummary, TransactionInput,
+    TransactionStatus,
+};
+use sui_transport::graphql::ObjectOwner;
+use sui_transport::jsonrpc::{JsonRpcClient, JsonRpcEffects, JsonRpcPackage};
+use sui_types::transaction::SenderSignedData;
+
+use crate::bcs_codec::{decode_base64_bytes, transaction_data_to_fetched_transaction};
+use crate::types::{PackageData, ReplayState, VersionedObject};
+
+/// Maximum number of packages fetched while walking linkage tables.
+const MAX_PACKAGE_FETCHES: usize = 64;
+
+/// Fetch everything needed to replay `digest` via JSON-RPC.
+///
+/// This:
+/// 1. Fetches the transaction with raw BCS input and effects
+/// 2. Fetches every input object at its pre-transaction version
+/// 3. Fetches called packages and walks their linkage tables
+pub fn fetch_replay_state_via_jsonrpc(client: &JsonRpcClient, digest: &str) -> Result<ReplayState> {
+    let tx = client
+        .get_transaction_block(digest)
+        .with_context(|| format!("Failed to fetch transaction {} via JSON-RPC", digest))?;
+    let raw = tx
+        .raw_transaction_base64
+        .as_deref()
+        .ok_or_else(|| anyhow!("JSON-RPC response for {} missing rawTransaction", digest))?;
+    let raw = decode_base64_bytes(raw).context("Failed to decode rawTransaction base64")?;
+    let signed: SenderSignedData =
+        bcs::from_bytes(&raw).context("Failed to parse rawTransaction as SenderSignedData")?;
+
+    let effects_summary = tx.effects.as_ref().map(effects_summary_from_jsonrpc);
+    let transaction = transaction_data_to_fetched_transaction(
+        signed.transaction_data(),
+        tx.digest.clone(),
+        effects_summary,
+        tx.timestamp_ms,
+        tx.checkpoint,
+    );
+
+    let input_versions = collect_input_versions(&transaction.inputs, tx.effects.as_ref());
+    let mut objects = HashMap::new();
+    for (id, version) in input_versions {
+        let id_hex = id.to_hex_literal();
+        let obj = client
+            .try_get_past_object(&id_hex, version)
+            .with_context(|| format!("Failed to fetch object {} at version {}", id_hex, version))?;
+        let Some(bcs_bytes) = obj.decode_bcs() else {
+            // Package inputs carry no Move object contents.
+            continue;
+        };
+        objects.insert(
+            id,
+            VersionedObject {
+                id,
+                version: obj.version,
+                digest: obj.digest.clone(),
+                type_tag: obj.type_string.clone(),
+                bcs_bytes,
+                is_shared: matches!(obj.owner, ObjectOwner::Shared { .. }),
+                is_immutable: matches!(obj.owner, ObjectOwner::Immutable),
+            },
+        );
+    }
+
+    let mut roots = Vec::new();
+    for cmd in &transaction.commands {
+        if let PtbCommand::MoveCall {
+            package,
+            type_arguments,
+            ..
+        } = cmd
+        {
+            roots.push(package.clone());
+            for type_arg in type_arguments {
+                roots.extend(sui_resolver::extract_package_ids_from_type(type_arg));
+            }
+        }
+    }
+    for obj in objects.values() {
+        if let Some(type_tag) = &obj.type_tag {
+            roots.extend(sui_resolver::extract_package_ids_from_type(type_tag));
+        }
+    }
+    let packages = fetch_package_closure(client, roots)?;
+
+    let epoch = tx
+        .effects
+        .as_ref()
+        .and_then(|e| e.executed_epoch)
+        .unwrap_or(0);
+    let protocol_version = client
+        .get_epoch_protocol_version(epoch)
+        .ok()
+        .flatten()
+        .unwrap_or(0);
+
+    Ok(ReplayState {
+        transaction,
+        objects,
+        packages,
+        protocol_version,
+        epoch,
+        reference_gas_price: None,
+        checkpoint: tx.checkpoint,
+    })
+}
+
+/// Collect `(object_id, input_version)` pairs for every object the transaction read.
+///
+/// Owned/receiving inputs carry their version in the transaction itself; shared
+/// inputs are resolved from the effects' consensus versions.
+fn collect_input_versions(
+    inputs: &[TransactionInput],
+    effects: Option<&JsonRpcEffects>,
+) -> Vec<(AccountAddress, u64)> {
+    let mut versions: Vec<(AccountAddress, u64)> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut push = |id: &str, version: u64| {
+        if let Ok(addr) = AccountAddress::from_hex_literal(id) {
+            if seen.insert(addr) {
+                versions.push((addr, version));
+            }
+        }
+    };
+
+    for input in inputs {
+        match input {
+            TransactionInput::Object {
+                object_id, version, ..
+            }
+            | TransactionInput::ImmutableObject {
+                object_id, version, ..
+            }
+            | TransactionInput::Receiving {
+                object_id, version, ..
+            } => push(object_id, *version),
+            _ => {}
+        }
+    }
+
+    if let Some(effects) = effects {
+        for (id, version) in effects
+            .shared_objects
+            .iter()
+            .chain(effects.modified_at_versions.iter())
+        {
+            push(id, *version);
+        }
+    }
+    versions
+}
+
+fn fetch_package_closure(
+    client: &JsonRpcClient,
+    roots: Vec<String>,
+) -> Result<HashMap<AccountAddress, PackageData>> {
+    let mut packages = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut queue: VecDeque<AccountAddress> = roots
+        .iter()
+        .filter_map(|id| AccountAddress::from_hex_literal(id).ok())
+        .collect();
+
+    while let Some(addr) = queue.pop_front() {
+        if is_framework_address(&addr) || !visited.insert(addr) {
+            continue;
+        }
+        if packages.len() >= MAX_PACKAGE_FETCHES {
+            return Err(anyhow!(
+                "package closure exceeds {} packages (next: {}); refusing to replay with a \
+                 truncated closure",
+                MAX_PACKAGE_FETCHES,
+                addr.to_hex_literal()
+            ));
+        }
+        let pkg = client
+            .get_package(&addr.to_hex_literal())
+            .with_context(|| format!("Failed to fetch package {}", addr.to_hex_literal()))?;
+        let data = jsonrpc_package_to_data(addr, pkg);
+        queue.extend(data.linkage.values().copied());
+        packages.insert(addr, data);
+    }
+    Ok(packages)
+}
+
+fn jsonrpc_package_to_data(address: AccountAddress, pkg: JsonRpcPackage) -> PackageData {
+    let linkage = pkg
+        .linkage
+        .iter()
+        .filter_map(|entry| {
+            Some((
+                AccountAddress::from_hex_literal(&entry.original_id).ok()?,
+                AccountAddress::from_hex_literal(&entry.upgraded_id).ok()?,
+            ))
+        })
+        .collect();
+
+    // The module self-address is the original (runtime) package ID.
+    let original_id = pkg.modules.first().and_then(|(_, bytes)| {
+        use move_binary_format::CompiledModule;
+        CompiledModule::deserialize_with_defaults(bytes)
+            .ok()
+            .map(|m| *m.self_id().address())
+    });
+
+    PackageData {
+        address,
+        version: pkg.version,
+        modules: pkg.modules,
+        linkage,
+        original_id,
+    }
+}
+
+fn effects_summary_from_jsonrpc(effects: &JsonRpcEffects) -> TransactionEffectsSummary {
+    let status = if effects.is_success() {
+        TransactionStatus::Success
+    } else {
+        TransactionStatus::Failure {
+            error: effects
+                .error
+                .clone()
+                .unwrap_or_else(|| effects.status.clone()),
+        }
+    };
+
+    let shared_object_versions = effects
+        .shared_objects
+        .iter()
+        .map(|(id, version)| (id.clone(), *version))
+        .collect();
+
+    TransactionEffectsSummary {
+        status,
+        created: effects.created.clone(),
+        mutated: effects.mutated.clone(),
+        deleted: effects.deleted.clone(),
+        wrapped: effects.wrapped.clone(),
+        unwrapped: effects.unwrapped.clone(),
+        gas_used: GasSummary {
+            computation_cost: effects.computation_cost,
+            storage_cost: effects.storage_cost,
+            storage_rebate: effects.storage_rebate,
+            non_refundable_storage_fee: effects.non_refundable_storage_fee,
+        },
+        events_count: effects.events_count,
+        shared_object_versions,
+        object_changes: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effects_with(shared: Vec<(&str, u64)>, modified: Vec<(&str, u64)>) -> JsonRpcEffects {
+        JsonRpcEffects {
+            status: "success".to_string(),
+            error: None,
+            executed_epoch: Some(1),
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+            created: vec![],
+            mutated: vec![],
+            deleted: vec![],
+            wrapped: vec![],
+            unwrapped: vec![],
+            modified_at_versions: modified
+                .into_iter()
+                .map(|(id, v)| (id.to_string(), v))
+                .collect(),
+            shared_objects: shared
+                .into_iter()
+                .map(|(id, v)| (id.to_string(), v))
+                .collect(),
+            events_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_collect_input_versions_prefers_tx_versions_and_dedups() {
+        let inputs = vec![
+            TransactionInput::Object {
+                object_id: "0xa".to_string(),
+                version: 5,
+                digest: "d".to_string(),
+            },
+            TransactionInput::SharedObject {
+                object_id: "0x6".to_string(),
+                initial_shared_version: 1,
+                mutable: false,
+            },
+        ];
+        let effects = effects_with(vec![("0x6", 77)], vec![("0xa", 5), ("0xb", 9)]);
+        let versions = collect_input_versions(&inputs, Some(&effects));
+        let addr = |s: &str| AccountAddress::from_hex_literal(s).unwrap();
+        assert_eq!(
+            versions,
+            vec![(addr("0xa"), 5), (addr("0x6"), 77), (addr("0xb"), 9)]
+        );
+    }
+
+    #[test]
+    fn test_effects_summary_failure_status() {
+        let mut effects = effects_with(vec![("0x6", 3)], vec![]);
+        effects.status = "failure".to_string();
+        effects.error = Some("MoveAbort".to_string());
+        let summary = effects_summary_from_jsonrpc(&effects);
+        assert!(matches!(
+            summary.status,
+            TransactionStatus::Failure { ref error } if error == "MoveAbort"
+        ));
+        assert_eq!(summary.shared_object_versions.get("0x6"), Some(&3));
+    }
+
+    #[test]
+    fn test_effects_summary_counts_events() {
+        let mut effects = effects_with(vec![], vec![]);
+        effects.events_count = 3;
+        assert_eq!(effects_summary_from_jsonrpc(&effects).events_count, 3);
+    }
+}
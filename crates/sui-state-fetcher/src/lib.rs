@@ -18,23 +18,38 @@
 
 pub mod bcs_codec;
 pub mod cache;
+pub mod fetch_memo;
 pub mod fetch_utils;
 pub mod file_provider;
+pub mod jsonrpc_replay;
+pub mod oracle;
 pub mod provider;
 pub mod replay;
 pub mod replay_builder;
 pub mod replay_provider;
 pub mod sparse_replay;
 pub mod state_json;
+pub mod state_snapshot;
 pub mod types;
 pub mod vm_integration;
 pub mod walrus_replay;
 
 // Re-export main types
 pub use cache::VersionedCache;
-pub use fetch_utils::{build_aliases, fetch_child_object, fetch_object_via_grpc, PackageAliases};
-pub use file_provider::{import_replay_states, FileStateProvider, ImportSpec, ImportSummary};
-pub use provider::{package_data_from_move_package, HistoricalStateProvider};
+pub use fetch_memo::{FetchMemo, FetchMemoStats};
+pub use fetch_utils::{
+    build_aliases, fetch_child_object, fetch_object_via_grpc, system_state_inner_id, PackageAliases,
+};
+pub use file_provider::{
+    clear_replay_cache, import_replay_states, replay_cache_stats, CacheClearSpec,
+    CacheClearSummary, CacheStats, FileStateProvider, ImportSpec, ImportSummary, RemovedCacheEntry,
+};
+pub use jsonrpc_replay::fetch_replay_state_via_jsonrpc;
+pub use oracle::{OracleConfig, OracleHydration};
+pub use provider::{
+    graphql_package_to_data, grpc_object_to_package, package_data_from_move_package,
+    HistoricalStateProvider,
+};
 pub use replay::{
     build_address_aliases, get_historical_versions, to_raw_objects, to_replay_data, ReplayData,
 };
@@ -49,5 +64,13 @@ pub use state_json::{
     parse_replay_state_value, parse_replay_states_file, parse_replay_states_json,
     parse_replay_states_value,
 };
+pub use state_snapshot::{
+    decode_replay_states, encode_replay_states, export_replay_state, export_replay_states,
+    is_replay_state_snapshot, load_replay_state, load_replay_states, read_replay_states_file,
+    ReplayStateFormat,
+};
 pub use types::{FetchStats, ObjectID, PackageData, ReplayState, VersionedObject};
-pub use walrus_replay::{checkpoint_to_replay_state, find_tx_in_checkpoint};
+pub use walrus_replay::{
+    apply_checkpoint_prologue, checkpoint_to_replay_state, find_tx_in_checkpoint,
+    CheckpointPrologueTransaction,
+};
@@ -0,0 +1,198 @@
+//! Pyth / Wormhole oracle state for replay.
+//!
+//! Oracle-dependent transactions read shared state that is rarely part of the
+//! transaction's own input set: the Pyth `State` object and its dynamic fields (the
+//! price-feed registry), the Wormhole `State` object and its guardian sets (needed to
+//! verify VAAs), and the `PriceInfoObject` of every feed the transaction consumes.
+//!
+//! [`HistoricalStateProvider::hydrate_oracle_state`](crate::HistoricalStateProvider::hydrate_oracle_state)
+//! fetches these at a checkpoint-pinned version and returns an [`OracleHydration`] that
+//! can be merged into a [`ReplayState`]. `fetch_replay_state` does this automatically
+//! when a transaction touches one of the packages in the provider's [`OracleConfig`].
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{anyhow, Result};
+use move_core_types::account_address::AccountAddress;
+
+use crate::types::{ObjectID, ReplayState, VersionedObject};
+
+/// Pyth package (original ID) on mainnet; price types are `<this>::price_info::*`.
+const PYTH_MAINNET_PACKAGE: &str =
+    "0x8d97f1cd6ac663735be08d1d2b6d02a159e711586461306ce60a2b7a6a565a9e";
+/// Latest upgraded Pyth package on mainnet (the one PTBs call).
+const PYTH_MAINNET_PACKAGE_LATEST: &str =
+    "0x04e20ddf36af412a4096f9014f4a565af9e812db9a05cc40254846cf6ed0ad91";
+/// Pyth `State` object on mainnet.
+const PYTH_MAINNET_STATE: &str =
+    "0x1f9310238ee9298fb703c3419030b35b22bb1cc37113e3bb5007c99aec79e5b8";
+/// Wormhole package (original ID) on mainnet.
+const WORMHOLE_MAINNET_PACKAGE: &str =
+    "0x5306f64e312b581766351c07af79c72fcb1cd25147157fdc2f8ad76de9a3fb6a";
+/// Wormhole `State` object on mainnet.
+const WORMHOLE_MAINNET_STATE: &str =
+    "0xaeab97f96cf9877fee2883315d459552b2b921edc16d7ceac6eab944dd88919c";
+
+/// BCS length of `Field<PriceIdentifier, ID>`: UID, `vector<u8>` of 32 bytes, ID.
+const PRICE_INFO_ENTRY_LEN: usize = 32 + 1 + 32 + 32;
+/// Offset of `guardian_sets: Table<u32, GuardianSet>` in the Wormhole `State` BCS:
+/// `id: UID`, `governance_chain: u16`, `governance_contract: ExternalAddress`
+/// (a 32-byte `vector<u8>`), `guardian_set_index: u32`.
+const GUARDIAN_SETS_OFFSET: usize = 32 + 2 + 1 + 32 + 4;
+
+/// Oracle deployments recognized by the provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OracleConfig {
+    /// Every Pyth package ID (original and upgrades) that marks a transaction as
+    /// oracle-dependent.
+    pub pyth_packages: Vec<AccountAddress>,
+    /// Pyth `State` object.
+    pub pyth_state: ObjectID,
+    /// Every Wormhole package ID (original and upgrades).
+    pub wormhole_packages: Vec<AccountAddress>,
+    /// Wormhole `State` object.
+    pub wormhole_state: ObjectID,
+}
+
+impl OracleConfig {
+    /// Pyth and Wormhole as deployed on Sui mainnet.
+    pub fn mainnet() -> Self {
+        let addr = |hex: &str| AccountAddress::from_hex_literal(hex).expect("valid address");
+        Self {
+            pyth_packages: vec![
+                addr(PYTH_MAINNET_PACKAGE),
+                addr(PYTH_MAINNET_PACKAGE_LATEST),
+            ],
+            pyth_state: addr(PYTH_MAINNET_STATE),
+            wormhole_packages: vec![addr(WORMHOLE_MAINNET_PACKAGE)],
+            wormhole_state: addr(WORMHOLE_MAINNET_STATE),
+        }
+    }
+
+    /// Whether any of `package_ids` is a known Pyth or Wormhole package.
+    pub fn touches<'a>(&self, package_ids: impl IntoIterator<Item = &'a AccountAddress>) -> bool {
+        package_ids
+            .into_iter()
+            .any(|id| self.pyth_packages.contains(id) || self.wormhole_packages.contains(id))
+    }
+}
+
+/// Oracle objects fetched at a checkpoint.
+#[derive(Debug, Clone, Default)]
+pub struct OracleHydration {
+    /// Checkpoint the objects were read at.
+    pub checkpoint: u64,
+    /// Pyth/Wormhole state, their dynamic fields and the requested `PriceInfoObject`s.
+    pub objects: HashMap<ObjectID, VersionedObject>,
+    /// Requested price feed ID (`0x`-prefixed hex) -> its `PriceInfoObject`.
+    pub price_info_objects: BTreeMap<String, ObjectID>,
+    /// Requested price feeds with no registry entry or no object at the checkpoint.
+    pub missing_price_feeds: Vec<String>,
+}
+
+impl OracleHydration {
+    /// Add the oracle objects to `objects`, keeping any version already present
+    /// (the transaction's own input versions win). Returns the number added.
+    pub fn merge_into_objects(&self, objects: &mut HashMap<ObjectID, VersionedObject>) -> usize {
+        let mut added = 0;
+        for (id, object) in &self.objects {
+            if !objects.contains_key(id) {
+                objects.insert(*id, object.clone());
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Merge into a replay state; see [`Self::merge_into_objects`].
+    pub fn merge_into(&self, state: &mut ReplayState) -> usize {
+        self.merge_into_objects(&mut state.objects)
+    }
+}
+
+/// Parse a 32-byte Pyth price feed ID, with or without `0x`.
+pub fn parse_price_feed_id(feed_id: &str) -> Result<[u8; 32]> {
+    let trimmed = feed_id.trim().trim_start_matches("0x");
+    let bytes =
+        hex::decode(trimmed).map_err(|e| anyhow!("invalid price feed id {feed_id}: {e}"))?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("price feed id {feed_id} must be 32 bytes"))
+}
+
+/// Decode a Pyth price-registry entry (`Field<PriceIdentifier, ID>`) into the feed ID
+/// and its `PriceInfoObject` ID.
+pub fn decode_price_info_entry(type_tag: &str, bcs_bytes: &[u8]) -> Option<([u8; 32], ObjectID)> {
+    if !type_tag.contains("::price_identifier::PriceIdentifier")
+        || bcs_bytes.len() != PRICE_INFO_ENTRY_LEN
+        || bcs_bytes[32] != 32
+    {
+        return None;
+    }
+    let feed_id = bcs_bytes[33..65].try_into().ok()?;
+    let object_id = AccountAddress::from_bytes(&bcs_bytes[65..]).ok()?;
+    Some((feed_id, object_id))
+}
+
+/// Table ID of the guardian sets in a Wormhole `State` object's BCS.
+pub fn wormhole_guardian_sets_table_id(state_bcs: &[u8]) -> Option<ObjectID> {
+    if state_bcs.get(32 + 2) != Some(&32) {
+        return None;
+    }
+    let id = state_bcs.get(GUARDIAN_SETS_OFFSET..GUARDIAN_SETS_OFFSET + 32)?;
+    AccountAddress::from_bytes(id).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_price_info_entry() {
+        let feed = [0xab; 32];
+        let price_info = AccountAddress::new([0xcd; 32]);
+        let mut bytes = vec![0x11; 32];
+        bytes.push(32);
+        bytes.extend_from_slice(&feed);
+        bytes.extend_from_slice(price_info.as_ref());
+        let type_tag = format!(
+            "0x2::dynamic_field::Field<{PYTH_MAINNET_PACKAGE}::price_identifier::PriceIdentifier, 0x2::object::ID>"
+        );
+
+        assert_eq!(
+            decode_price_info_entry(&type_tag, &bytes),
+            Some((feed, price_info))
+        );
+        assert_eq!(
+            decode_price_info_entry("0x2::coin::Coin<0x2::sui::SUI>", &bytes),
+            None
+        );
+        assert_eq!(
+            parse_price_feed_id(&format!("0x{}", hex::encode(feed))).unwrap(),
+            feed
+        );
+        assert!(parse_price_feed_id("0xabcd").is_err());
+    }
+
+    #[test]
+    fn test_wormhole_guardian_sets_table_id() {
+        let table = AccountAddress::new([0x42; 32]);
+        let mut state = vec![0x01; 32];
+        state.extend_from_slice(&1u16.to_le_bytes());
+        state.push(32);
+        state.extend_from_slice(&[0x04; 32]);
+        state.extend_from_slice(&4u32.to_le_bytes());
+        state.extend_from_slice(table.as_ref());
+        state.extend_from_slice(&5u64.to_le_bytes());
+
+        assert_eq!(wormhole_guardian_sets_table_id(&state), Some(table));
+        assert_eq!(wormhole_guardian_sets_table_id(&state[..40]), None);
+    }
+
+    #[test]
+    fn test_mainnet_config_touches_pyth_packages() {
+        let config = OracleConfig::mainnet();
+        assert!(config.touches(&config.pyth_packages.clone()));
+        assert!(!config.touches(&[AccountAddress::TWO]));
+    }
+}
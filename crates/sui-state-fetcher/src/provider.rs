@@ -22,7 +22,7 @@
 //! }
 //! ```
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -38,11 +38,15 @@ use tracing::debug;
 
 use sui_prefetch::grpc_to_fetched_transaction;
 use sui_resolver::address::normalize_address;
+use sui_transport::cassette::Cassette;
 use sui_transport::graphql::{
     GraphQLClient, GraphQLCommand, GraphQLPackage, GraphQLTransaction, GraphQLTransactionInput,
     ObjectOwner,
 };
-use sui_transport::grpc::{GrpcArgument, GrpcClient, GrpcCommand, GrpcInput, GrpcTransaction};
+use sui_transport::grpc::{
+    global_pool, GrpcArgument, GrpcClient, GrpcCommand, GrpcInput, GrpcTransaction,
+};
+use sui_transport::latency::{LatencyRecorder, LatencySummary};
 use sui_transport::walrus::WalrusClient;
 use sui_types::move_package::MovePackage;
 
@@ -52,6 +56,10 @@ use sui_historical_cache::{
 };
 
 use crate::cache::VersionedCache;
+use crate::oracle::{
+    decode_price_info_entry, parse_price_feed_id, wormhole_guardian_sets_table_id, OracleConfig,
+    OracleHydration,
+};
 use crate::types::{ObjectID, PackageData, ReplayState, VersionedObject};
 
 /// Unified provider for historical state fetching.
@@ -96,8 +104,22 @@ pub struct HistoricalStateProvider {
 
     /// When true, skip gRPC calls and use GraphQL as the primary data source.
     graphql_only: bool,
+
+    /// Per-request network latencies (`objects`, `packages`, Walrus `checkpoints`).
+    fetch_latency: Arc<LatencyRecorder>,
+
+    /// Oracle deployments hydrated automatically for oracle-dependent transactions.
+    oracle_config: Option<OracleConfig>,
+
+    /// Cassette the provider's clients record to or play back from.
+    cassette: Option<Arc<Cassette>>,
 }
 
+/// Dynamic-field depth for oracle state: Pyth `State` -> registry table -> entries.
+const ORACLE_DF_DEPTH: usize = 3;
+/// Dynamic fields listed per oracle parent; covers every Pyth price feed on Sui.
+const ORACLE_DF_LIMIT: usize = 2_000;
+
 /// Default mainnet gRPC endpoint
 const MAINNET_GRPC: &str = "https://archive.mainnet.sui.io:443";
 /// Default testnet gRPC endpoint
@@ -555,6 +577,9 @@ impl HistoricalStateProvider {
             local_package_index: None,
             walrus_pool: Arc::new(WalrusCheckpointPool::new()),
             graphql_only: false,
+            fetch_latency: Arc::new(LatencyRecorder::new()),
+            oracle_config: Some(OracleConfig::mainnet()),
+            cassette: None,
         })
     }
 
@@ -576,6 +601,9 @@ impl HistoricalStateProvider {
             local_package_index: None,
             walrus_pool: Arc::new(WalrusCheckpointPool::new()),
             graphql_only: false,
+            fetch_latency: Arc::new(LatencyRecorder::new()),
+            oracle_config: None,
+            cassette: None,
         })
     }
 
@@ -604,6 +632,9 @@ impl HistoricalStateProvider {
             local_package_index: None,
             walrus_pool: Arc::new(WalrusCheckpointPool::new()),
             graphql_only: false,
+            fetch_latency: Arc::new(LatencyRecorder::new()),
+            oracle_config: Some(OracleConfig::mainnet()),
+            cassette: None,
         })
     }
 
@@ -626,6 +657,9 @@ impl HistoricalStateProvider {
             local_package_index: None,
             walrus_pool: Arc::new(WalrusCheckpointPool::new()),
             graphql_only: false,
+            fetch_latency: Arc::new(LatencyRecorder::new()),
+            oracle_config: Some(OracleConfig::mainnet()),
+            cassette: None,
         }
     }
 
@@ -635,6 +669,37 @@ impl HistoricalStateProvider {
         self
     }
 
+    /// Record per-request latencies into a caller-owned recorder (shared with the
+    /// Walrus client), e.g. to aggregate several providers into one report.
+    pub fn with_latency_recorder(mut self, recorder: Arc<LatencyRecorder>) -> Self {
+        self.walrus = self
+            .walrus
+            .take()
+            .map(|walrus| walrus.with_latency_recorder(Arc::clone(&recorder)));
+        self.fetch_latency = recorder;
+        self
+    }
+
+    /// Latency distribution of the network requests made so far, keyed by kind
+    /// (`objects`, `packages`, and `checkpoints`/`checkpoint_metadata` for Walrus).
+    pub fn fetch_latency(&self) -> BTreeMap<String, LatencySummary> {
+        self.fetch_latency.summary()
+    }
+
+    /// Recognize (and auto-hydrate) a different oracle deployment, e.g. on testnet or
+    /// a private network. Defaults to [`OracleConfig::mainnet`] except on
+    /// [`Self::testnet`].
+    pub fn with_oracle_config(mut self, config: OracleConfig) -> Self {
+        self.oracle_config = Some(config);
+        self
+    }
+
+    /// Do not hydrate oracle state automatically in `fetch_replay_state`.
+    pub fn without_oracle_hydration(mut self) -> Self {
+        self.oracle_config = None;
+        self
+    }
+
     /// Returns true if this provider is in graphql-only mode (no gRPC).
     pub fn is_graphql_only(&self) -> bool {
         self.graphql_only
@@ -654,7 +719,24 @@ impl HistoricalStateProvider {
 
     /// Enable Walrus checkpoint fetching with a custom client.
     pub fn with_walrus(mut self, walrus: WalrusClient) -> Self {
-        self.walrus = Some(walrus);
+        let walrus = walrus.with_latency_recorder(Arc::clone(&self.fetch_latency));
+        self.walrus = Some(match &self.cassette {
+            Some(cassette) => walrus.with_cassette(Arc::clone(cassette)),
+            None => walrus,
+        });
+        self
+    }
+
+    /// Route the provider's gRPC, GraphQL and Walrus requests through `cassette` (see
+    /// [`sui_transport::cassette`]), including a Walrus client enabled later.
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.grpc = self.grpc.with_cassette(Arc::clone(&cassette));
+        self.graphql = self.graphql.with_cassette(Arc::clone(&cassette));
+        self.walrus = self
+            .walrus
+            .take()
+            .map(|walrus| walrus.with_cassette(Arc::clone(&cassette)));
+        self.cassette = Some(cassette);
         self
     }
 
@@ -667,7 +749,7 @@ impl HistoricalStateProvider {
     /// - `SUI_WALRUS_NETWORK` (optional: mainnet|testnet)
     pub fn with_walrus_from_env(mut self) -> Self {
         if let Some(client) = walrus_from_env() {
-            self.walrus = Some(client);
+            self = self.with_walrus(client);
         }
         self
     }
@@ -1143,6 +1225,20 @@ impl HistoricalStateProvider {
                 }
             }
         }
+        // The injected Clock must read the checkpoint time, not DEFAULT_CLOCK_BASE_MS.
+        if grpc_tx.timestamp_ms.is_none() {
+            if let Some(cp) = grpc_tx.checkpoint {
+                match self.graphql.fetch_checkpoint_timestamp_ms(cp) {
+                    Ok(ts) => grpc_tx.timestamp_ms = ts,
+                    Err(e) => debug!(
+                        digest = digest,
+                        checkpoint = cp,
+                        error = %e,
+                        "checkpoint timestamp lookup failed"
+                    ),
+                }
+            }
+        }
         if std::env::var("SUI_DUMP_TX_OBJECTS").ok().as_deref() == Some("1") {
             eprintln!(
                 "[tx_objects] digest={} objects_len={}",
@@ -1374,6 +1470,13 @@ impl HistoricalStateProvider {
             historical_versions.insert(normalized, *version);
         }
 
+        // From gas payment (every coin is needed to smash them into the primary gas coin)
+        for (id_str, version) in &grpc_tx.gas_payment {
+            historical_versions
+                .entry(normalize_address(id_str))
+                .or_insert(*version);
+        }
+
         if std::env::var("SUI_DUMP_RUNTIME_OBJECTS").ok().as_deref() == Some("1") {
             let mut sorted: Vec<_> = historical_versions.iter().collect();
             sorted.sort_by_key(|(k, _)| k.as_str());
@@ -1633,6 +1736,10 @@ impl HistoricalStateProvider {
             objects.entry(id).or_insert(obj);
         }
 
+        // 5a. Hydrate the SuiSystemState inner object at its historical version. It is a
+        // dynamic field of 0x5, so it is easy to miss when prefetch is off or shallow.
+        self.hydrate_system_state_inner(&mut objects, &historical_versions, grpc_tx.checkpoint);
+
         // 5b. Ensure system objects (Clock/Random) are available for replay.
         // These are often omitted from unchanged_* sets, but required for execution.
         if auto_system_objects {
@@ -1644,6 +1751,32 @@ impl HistoricalStateProvider {
             );
         }
 
+        // 5c. Hydrate Pyth/Wormhole state for oracle-dependent transactions. The price
+        // registry and guardian sets are read dynamically, so they are rarely inputs.
+        if let (Some(config), Some(cp)) = (self.oracle_config.as_ref(), grpc_tx.checkpoint) {
+            let touched: Vec<AccountAddress> = extract_package_ids_from_tx(&grpc_tx)
+                .into_iter()
+                .chain(
+                    objects
+                        .values()
+                        .filter_map(|obj| obj.type_tag.as_deref())
+                        .flat_map(extract_package_ids_from_type)
+                        .filter_map(|id| parse_object_id(&id).ok()),
+                )
+                .collect();
+            if config.touches(&touched) {
+                match self.hydrate_oracle_state(cp, &[]).await {
+                    Ok(hydration) => {
+                        let added = hydration.merge_into_objects(&mut objects);
+                        debug!(digest = digest, added = added, "merged oracle state");
+                    }
+                    Err(e) => {
+                        debug!(digest = digest, error = %e, "oracle state hydration failed")
+                    }
+                }
+            }
+        }
+
         // 6. Extract package IDs from commands AND from object type strings
         let mut package_ids: HashSet<AccountAddress> =
             extract_package_ids_from_tx(&grpc_tx).into_iter().collect();
@@ -2211,6 +2344,10 @@ impl HistoricalStateProvider {
             to_fetch.extend(requests.iter().copied());
             cache_misses = requests.len();
         }
+        // The same (id, version) can be requested several times in one call
+        // (e.g. a shared pool used by multiple commands); fetch it once.
+        let mut requested = HashSet::with_capacity(to_fetch.len());
+        to_fetch.retain(|key| requested.insert(*key));
 
         // Check local Walrus-backed object store before network fetches.
         if let Some(store) = self.local_object_store.as_deref() {
@@ -2547,6 +2684,10 @@ impl HistoricalStateProvider {
             gql_fail += gql_fail_inc;
             grpc_elapsed += grpc_ms;
             gql_elapsed += gql_ms;
+            self.fetch_latency.record(
+                "objects",
+                std::time::Duration::from_millis((grpc_ms + gql_ms) as u64),
+            );
 
             if let Some(obj) = maybe_object {
                 if use_cache {
@@ -2745,6 +2886,14 @@ impl HistoricalStateProvider {
                 gql_fetches += outcome.stats.gql_fetches;
                 grpc_elapsed += outcome.stats.grpc_elapsed;
                 gql_elapsed += outcome.stats.gql_elapsed;
+                if outcome.stats.grpc_ok + outcome.stats.grpc_fail + outcome.stats.gql_fetches > 0 {
+                    self.fetch_latency.record(
+                        "packages",
+                        std::time::Duration::from_millis(
+                            (outcome.stats.grpc_elapsed + outcome.stats.gql_elapsed) as u64,
+                        ),
+                    );
+                }
 
                 if let Some(pkg) = outcome.package {
                     result.insert(outcome.pkg_id, pkg);
@@ -3385,17 +3534,13 @@ impl HistoricalStateProvider {
                 return Some(obj);
             }
 
-            // Fetch from gRPC (blocking) - create a new client for each call
+            // Fetch from gRPC (blocking) over the process-wide connection pool
             let id_str = format!("0x{}", hex::encode(id.as_ref()));
             let endpoint_clone = endpoint.clone();
 
-            let rt = match tokio::runtime::Runtime::new() {
-                Ok(rt) => rt,
-                Err(_) => return None,
-            };
-
-            let result = rt.block_on(async {
-                let client = match GrpcClient::new(&endpoint_clone).await {
+            let pool = global_pool();
+            let result = pool.block_on(async {
+                let client = match pool.client(&endpoint_clone, None).await {
                     Ok(c) => c,
                     Err(_) => return None,
                 };
@@ -3417,6 +3562,151 @@ impl HistoricalStateProvider {
         }
     }
 
+    /// Fetch the `SuiSystemState` inner object over GraphQL when `0x5` is among
+    /// `objects` but its inner state is not; see
+    /// [`crate::fetch_utils::hydrate_system_state_inner`].
+    fn hydrate_system_state_inner(
+        &self,
+        objects: &mut HashMap<ObjectID, VersionedObject>,
+        historical_versions: &HashMap<String, u64>,
+        checkpoint: Option<u64>,
+    ) {
+        use crate::fetch_utils::SystemStateInnerRead;
+
+        crate::fetch_utils::hydrate_system_state_inner(
+            objects,
+            historical_versions,
+            checkpoint,
+            |id, read| match read {
+                SystemStateInnerRead::AtVersion(version) => {
+                    self.graphql.fetch_object_at_version(id, version)
+                }
+                SystemStateInnerRead::AtCheckpoint(cp) => {
+                    self.graphql.fetch_object_at_checkpoint(id, cp)
+                }
+            },
+        );
+    }
+
+    /// Fetch Pyth/Wormhole oracle state at `checkpoint`.
+    ///
+    /// Resolves the Pyth and Wormhole `State` objects, their dynamic fields (the Pyth
+    /// price-feed registry, the Wormhole guardian sets) and the `PriceInfoObject` of
+    /// each of `price_feed_ids` (32-byte hex feed IDs). Feeds without a registry entry
+    /// or object at the checkpoint are reported in `missing_price_feeds`.
+    pub async fn hydrate_oracle_state(
+        &self,
+        checkpoint: u64,
+        price_feed_ids: &[String],
+    ) -> Result<OracleHydration> {
+        let config = self
+            .oracle_config
+            .as_ref()
+            .ok_or_else(|| anyhow!("no oracle deployment configured for this provider"))?;
+        let feeds = price_feed_ids
+            .iter()
+            .map(|id| {
+                parse_price_feed_id(id).map(|bytes| (format!("0x{}", hex::encode(bytes)), bytes))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut hydration = OracleHydration {
+            checkpoint,
+            ..Default::default()
+        };
+        let mut parents: HashMap<String, u64> = HashMap::new();
+        for state_id in [config.pyth_state, config.wormhole_state] {
+            let state = self.fetch_object_at_checkpoint_versioned(state_id, checkpoint)?;
+            parents.insert(normalize_address(&state_id.to_hex_literal()), state.version);
+            // Guardian sets live in a table inside the Wormhole state, not in its dynamic fields.
+            if state_id == config.wormhole_state {
+                if let Some(table_id) = wormhole_guardian_sets_table_id(&state.bcs_bytes) {
+                    parents.insert(normalize_address(&table_id.to_hex_literal()), state.version);
+                }
+            }
+            hydration.objects.insert(state_id, state);
+        }
+
+        let children = self
+            .prefetch_dynamic_fields_internal(
+                &parents,
+                ORACLE_DF_DEPTH,
+                ORACLE_DF_LIMIT,
+                Some(checkpoint),
+            )
+            .await;
+        let mut registry: HashMap<[u8; 32], ObjectID> = HashMap::new();
+        for (id_str, version, type_str, bcs_bytes) in children {
+            let Ok(id) = parse_object_id(&id_str) else {
+                continue;
+            };
+            if let Some((feed, price_info)) = decode_price_info_entry(&type_str, &bcs_bytes) {
+                registry.insert(feed, price_info);
+            }
+            hydration.objects.insert(
+                id,
+                VersionedObject {
+                    id,
+                    version,
+                    digest: None,
+                    type_tag: Some(type_str),
+                    bcs_bytes,
+                    is_shared: false,
+                    is_immutable: false,
+                },
+            );
+        }
+
+        for (feed_hex, feed) in feeds {
+            let Some(price_info) = registry.get(&feed).copied() else {
+                hydration.missing_price_feeds.push(feed_hex);
+                continue;
+            };
+            match self.fetch_object_at_checkpoint_versioned(price_info, checkpoint) {
+                Ok(object) => {
+                    hydration.objects.insert(price_info, object);
+                    hydration.price_info_objects.insert(feed_hex, price_info);
+                }
+                Err(e) => {
+                    debug!(
+                        feed = %feed_hex,
+                        object_id = %price_info.to_hex_literal(),
+                        error = %e,
+                        "failed to fetch PriceInfoObject"
+                    );
+                    hydration.missing_price_feeds.push(feed_hex);
+                }
+            }
+        }
+        Ok(hydration)
+    }
+
+    /// Fetch an object as of `checkpoint` via GraphQL.
+    fn fetch_object_at_checkpoint_versioned(
+        &self,
+        id: ObjectID,
+        checkpoint: u64,
+    ) -> Result<VersionedObject> {
+        let id_hex = id.to_hex_literal();
+        let obj = self
+            .graphql
+            .fetch_object_at_checkpoint(&id_hex, checkpoint)?;
+        let bcs_bytes = obj
+            .bcs_base64
+            .as_deref()
+            .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+            .ok_or_else(|| anyhow!("object {} has no BCS at checkpoint {}", id_hex, checkpoint))?;
+        Ok(VersionedObject {
+            id,
+            version: obj.version,
+            digest: obj.digest,
+            type_tag: obj.type_string,
+            bcs_bytes,
+            is_shared: matches!(obj.owner, ObjectOwner::Shared { .. }),
+            is_immutable: matches!(obj.owner, ObjectOwner::Immutable),
+        })
+    }
+
     // ==================== Accessors ====================
 
     /// Get a reference to the gRPC client.
@@ -4017,19 +4307,27 @@ fn grpc_object_to_versioned(
         _ => (false, false),
     };
 
+    let bcs_bytes = grpc_obj.bcs.clone().unwrap_or_default();
+    // Recompute the digest from the full object rather than trusting the server's:
+    // `bcs` may have fallen back to current `contents` for a historical version.
+    let digest = grpc_obj
+        .bcs_full
+        .as_deref()
+        .and_then(|full| crate::bcs_codec::object_digest_for_contents(full, &bcs_bytes));
+
     Ok(VersionedObject {
         id,
         version,
-        digest: Some(grpc_obj.digest.clone()),
+        digest,
         type_tag: grpc_obj.type_string.clone(),
-        bcs_bytes: grpc_obj.bcs.clone().unwrap_or_default(),
+        bcs_bytes,
         is_shared,
         is_immutable,
     })
 }
 
-/// Convert a gRPC object (package) to PackageData.
-fn grpc_object_to_package(
+/// Convert a gRPC object (package) to PackageData, including its linkage table.
+pub fn grpc_object_to_package(
     grpc_obj: &sui_transport::grpc::GrpcObject,
     address: AccountAddress,
 ) -> Result<PackageData> {
@@ -4092,7 +4390,8 @@ pub fn package_data_from_move_package(pkg: &MovePackage) -> PackageData {
     }
 }
 
-fn graphql_package_to_data(pkg_id: AccountAddress, pkg: GraphQLPackage) -> Result<PackageData> {
+/// Convert a GraphQL package (base64 modules, linkage entries) into [`PackageData`].
+pub fn graphql_package_to_data(pkg_id: AccountAddress, pkg: GraphQLPackage) -> Result<PackageData> {
     let modules = sui_transport::decode_graphql_modules(&pkg_id.to_string(), &pkg.modules)?;
 
     // Convert GraphQL linkage entries: original_id (runtime) -> upgraded_id (storage)
@@ -4414,5 +4713,7 @@ fn graphql_tx_to_grpc_tx(gql: &GraphQLTransaction) -> GrpcTransaction {
             .as_ref()
             .map(|e| e.unchanged_consensus_objects.clone())
             .unwrap_or_default(),
+        gas_payment: Vec::new(),
+        changed_object_details: Vec::new(),
     }
 }
@@ -5,19 +5,19 @@
 //! - extended external schema with base64 BCS blobs
 
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
 use anyhow::{anyhow, Context, Result};
 use move_core_types::account_address::AccountAddress;
 use serde_json::{Map, Value};
-use sui_sandbox_types::{FetchedTransaction, PtbCommand, TransactionInput};
+use sui_sandbox_types::{FetchedTransaction, GasPaymentObject, PtbCommand, TransactionInput};
 
 use crate::bcs_codec::{
     deserialize_package_base64, deserialize_transaction_base64,
     deserialize_transaction_data_json_str, deserialize_transaction_data_json_value,
     transaction_data_to_fetched_transaction,
 };
+use crate::state_snapshot::{read_replay_states_file, ReplayStateFormat};
 use crate::types::{PackageData, ReplayState, VersionedObject};
 
 /// Parse one or many replay states from a JSON string.
@@ -32,10 +32,11 @@ pub fn parse_replay_states_json(contents: &str) -> Result<Vec<ReplayState>> {
 }
 
 /// Parse one or many replay states from a JSON file.
+///
+/// Binary snapshots (see [`crate::state_snapshot`]) are detected by their header and
+/// decoded instead.
 pub fn parse_replay_states_file(path: &Path) -> Result<Vec<ReplayState>> {
-    let contents = fs::read_to_string(path)
-        .with_context(|| format!("Failed to read replay state file: {}", path.display()))?;
-    parse_replay_states_json(&contents)
+    read_replay_states_file(path, ReplayStateFormat::Auto)
 }
 
 /// Parse one or many replay states from a JSON value.
@@ -156,6 +157,10 @@ fn parse_transaction(
         effects,
         timestamp_ms,
         checkpoint: tx_checkpoint,
+        gas_payment: parse_optional_vec::<GasPaymentObject>(
+            obj.get("gas_payment"),
+            "transaction.gas_payment",
+        )?,
     })
 }
 
@@ -0,0 +1,314 @@
+//! Binary replay-state snapshots.
+//!
+//! JSON replay states spell every object and module out as base64 and are slow to
+//! parse once they reach hundreds of megabytes. A snapshot stores the same
+//! [`ReplayState`]s as zstd-compressed BCS behind a small header:
+//!
+//! ```text
+//! b"SUISNAP\0" | format version (u32 LE) | zstd(BCS(Vec<SnapshotState>))
+//! ```
+//!
+//! Objects and packages are written sorted by ID, so exporting the same state twice
+//! produces identical bytes. The transaction is embedded as JSON: its serde
+//! representation (tagged enums, skipped defaults) is not BCS-compatible, and it is a
+//! small part of the state.
+//!
+//! [`parse_replay_states_file`](crate::parse_replay_states_file) recognises snapshots by
+//! their header, so every API that takes a replay-state file accepts either format.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::state_json::parse_replay_states_json;
+use crate::types::{PackageData, ReplayState, VersionedObject};
+
+/// Leading bytes of every snapshot file.
+pub const SNAPSHOT_MAGIC: &[u8; 8] = b"SUISNAP\0";
+
+/// Snapshot format version written by [`encode_replay_states`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+const HEADER_LEN: usize = SNAPSHOT_MAGIC.len() + 4;
+const ZSTD_LEVEL: i32 = 3;
+
+/// On-disk format of a replay-state file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayStateFormat {
+    /// Binary snapshot if the file starts with [`SNAPSHOT_MAGIC`], JSON otherwise.
+    #[default]
+    Auto,
+    Json,
+    Binary,
+}
+
+impl FromStr for ReplayStateFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "auto" => Ok(Self::Auto),
+            "json" => Ok(Self::Json),
+            "binary" | "snapshot" => Ok(Self::Binary),
+            other => Err(anyhow!(
+                "unknown replay state format '{}' (expected auto, json or binary)",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotState {
+    transaction_json: Vec<u8>,
+    objects: Vec<VersionedObject>,
+    packages: Vec<PackageData>,
+    protocol_version: u64,
+    epoch: u64,
+    reference_gas_price: Option<u64>,
+    checkpoint: Option<u64>,
+}
+
+impl SnapshotState {
+    fn from_state(state: &ReplayState) -> Result<Self> {
+        let mut objects: Vec<VersionedObject> = state.objects.values().cloned().collect();
+        objects.sort_by_key(|object| object.cache_key());
+        let mut packages: Vec<PackageData> = state.packages.values().cloned().collect();
+        packages.sort_by_key(|package| package.address);
+        Ok(Self {
+            transaction_json: serde_json::to_vec(&state.transaction)
+                .context("Failed to serialize snapshot transaction")?,
+            objects,
+            packages,
+            protocol_version: state.protocol_version,
+            epoch: state.epoch,
+            reference_gas_price: state.reference_gas_price,
+            checkpoint: state.checkpoint,
+        })
+    }
+
+    fn into_state(self) -> Result<ReplayState> {
+        Ok(ReplayState {
+            transaction: serde_json::from_slice(&self.transaction_json)
+                .context("Failed to parse snapshot transaction")?,
+            objects: self
+                .objects
+                .into_iter()
+                .map(|object| (object.id, object))
+                .collect(),
+            packages: self
+                .packages
+                .into_iter()
+                .map(|package| (package.address, package))
+                .collect(),
+            protocol_version: self.protocol_version,
+            epoch: self.epoch,
+            reference_gas_price: self.reference_gas_price,
+            checkpoint: self.checkpoint,
+        })
+    }
+}
+
+/// Whether `bytes` start with the snapshot header.
+pub fn is_replay_state_snapshot(bytes: &[u8]) -> bool {
+    bytes.starts_with(SNAPSHOT_MAGIC)
+}
+
+/// Encode replay states as a binary snapshot.
+pub fn encode_replay_states(states: &[ReplayState]) -> Result<Vec<u8>> {
+    let snapshot = states
+        .iter()
+        .map(SnapshotState::from_state)
+        .collect::<Result<Vec<_>>>()?;
+    let body = bcs::to_bytes(&snapshot).context("Failed to BCS-encode replay state snapshot")?;
+    let compressed = zstd::stream::encode_all(body.as_slice(), ZSTD_LEVEL)
+        .context("Failed to compress replay state snapshot")?;
+
+    let mut bytes = Vec::with_capacity(HEADER_LEN + compressed.len());
+    bytes.extend_from_slice(SNAPSHOT_MAGIC);
+    bytes.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&compressed);
+    Ok(bytes)
+}
+
+/// Decode a binary snapshot produced by [`encode_replay_states`].
+pub fn decode_replay_states(bytes: &[u8]) -> Result<Vec<ReplayState>> {
+    if !is_replay_state_snapshot(bytes) || bytes.len() < HEADER_LEN {
+        bail!("Not a replay state snapshot (missing header)");
+    }
+    let version = u32::from_le_bytes(
+        bytes[SNAPSHOT_MAGIC.len()..HEADER_LEN]
+            .try_into()
+            .expect("4-byte version"),
+    );
+    if version != SNAPSHOT_FORMAT_VERSION {
+        bail!(
+            "Unsupported replay state snapshot version {} (this build reads version {})",
+            version,
+            SNAPSHOT_FORMAT_VERSION
+        );
+    }
+    let body = zstd::stream::decode_all(&bytes[HEADER_LEN..])
+        .context("Failed to decompress replay state snapshot")?;
+    let snapshot: Vec<SnapshotState> =
+        bcs::from_bytes(&body).context("Failed to decode replay state snapshot")?;
+    snapshot
+        .into_iter()
+        .enumerate()
+        .map(|(i, state)| {
+            state
+                .into_state()
+                .with_context(|| format!("Failed to decode replay state at index {i}"))
+        })
+        .collect()
+}
+
+/// Write one replay state to `path` as a binary snapshot.
+pub fn export_replay_state(state: &ReplayState, path: &Path) -> Result<()> {
+    export_replay_states(std::slice::from_ref(state), path)
+}
+
+/// Write replay states to `path` as a binary snapshot.
+pub fn export_replay_states(states: &[ReplayState], path: &Path) -> Result<()> {
+    let bytes = encode_replay_states(states)?;
+    fs::write(path, bytes)
+        .with_context(|| format!("Failed to write replay state snapshot: {}", path.display()))
+}
+
+/// Read a binary snapshot holding exactly one replay state.
+pub fn load_replay_state(path: &Path) -> Result<ReplayState> {
+    let mut states = load_replay_states(path)?;
+    match states.len() {
+        1 => Ok(states.remove(0)),
+        n => Err(anyhow!(
+            "Replay state snapshot '{}' holds {} states; use load_replay_states",
+            path.display(),
+            n
+        )),
+    }
+}
+
+/// Read every replay state in a binary snapshot.
+pub fn load_replay_states(path: &Path) -> Result<Vec<ReplayState>> {
+    read_replay_states_file(path, ReplayStateFormat::Binary)
+}
+
+/// Read replay states from a JSON or snapshot file.
+pub fn read_replay_states_file(path: &Path, format: ReplayStateFormat) -> Result<Vec<ReplayState>> {
+    let bytes = fs::read(path)
+        .with_context(|| format!("Failed to read replay state file: {}", path.display()))?;
+    let binary = match format {
+        ReplayStateFormat::Auto => is_replay_state_snapshot(&bytes),
+        ReplayStateFormat::Json => false,
+        ReplayStateFormat::Binary => true,
+    };
+    if binary {
+        return decode_replay_states(&bytes)
+            .with_context(|| format!("Failed to read replay state snapshot: {}", path.display()));
+    }
+    let contents = String::from_utf8(bytes).map_err(|_| {
+        anyhow!(
+            "Replay state file '{}' is not UTF-8 JSON (binary snapshot? use format=binary)",
+            path.display()
+        )
+    })?;
+    parse_replay_states_json(&contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::account_address::AccountAddress;
+    use std::collections::HashMap;
+    use sui_sandbox_types::{FetchedTransaction, PtbCommand, TransactionDigest};
+
+    fn sample_state() -> ReplayState {
+        let object_id = AccountAddress::from_hex_literal("0x5").unwrap();
+        let package_id = AccountAddress::from_hex_literal("0xabc").unwrap();
+        ReplayState {
+            transaction: FetchedTransaction {
+                digest: TransactionDigest("snapshot-digest".to_string()),
+                sender: AccountAddress::from_hex_literal("0x1").unwrap(),
+                gas_budget: 1_000,
+                gas_price: 750,
+                commands: vec![PtbCommand::MergeCoins {
+                    destination: sui_sandbox_types::PtbArgument::GasCoin,
+                    sources: vec![],
+                }],
+                inputs: vec![],
+                effects: None,
+                timestamp_ms: Some(1_700_000_000_000),
+                checkpoint: Some(42),
+                gas_payment: vec![],
+            },
+            objects: HashMap::from([(
+                object_id,
+                VersionedObject {
+                    id: object_id,
+                    version: 7,
+                    digest: Some("digest".to_string()),
+                    type_tag: Some("0x2::coin::Coin<0x2::sui::SUI>".to_string()),
+                    bcs_bytes: vec![1, 2, 3, 4],
+                    is_shared: false,
+                    is_immutable: false,
+                },
+            )]),
+            packages: HashMap::from([(
+                package_id,
+                PackageData {
+                    address: package_id,
+                    version: 2,
+                    modules: vec![("pool".to_string(), vec![0xa1, 0x1c, 0xeb, 0x0b])],
+                    linkage: HashMap::from([(package_id, package_id)]),
+                    original_id: Some(package_id),
+                },
+            )]),
+            protocol_version: 70,
+            epoch: 500,
+            reference_gas_price: Some(750),
+            checkpoint: Some(42),
+        }
+    }
+
+    #[test]
+    fn snapshot_round_trips_through_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.snap");
+        let state = sample_state();
+        export_replay_state(&state, &path).unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        assert!(is_replay_state_snapshot(&bytes));
+        assert_eq!(
+            encode_replay_states(std::slice::from_ref(&state)).unwrap(),
+            bytes
+        );
+
+        let loaded = load_replay_state(&path).unwrap();
+        assert_eq!(loaded.content_hash(), state.content_hash());
+        assert_eq!(loaded.checkpoint, Some(42));
+
+        // The auto-detecting JSON entry point accepts snapshots too.
+        let parsed = crate::parse_replay_states_file(&path).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].content_hash(), state.content_hash());
+
+        let err = read_replay_states_file(&path, ReplayStateFormat::Json).unwrap_err();
+        assert!(err.to_string().contains("not UTF-8 JSON"));
+    }
+
+    #[test]
+    fn snapshot_rejects_unknown_version() {
+        let mut bytes = encode_replay_states(&[sample_state()]).unwrap();
+        bytes[SNAPSHOT_MAGIC.len()] = 99;
+        let err = decode_replay_states(&bytes).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unsupported replay state snapshot version 99"));
+        assert!(decode_replay_states(b"{}").is_err());
+    }
+}
@@ -39,6 +39,99 @@ pub struct ReplayState {
     pub checkpoint: Option<u64>,
 }
 
+impl ReplayState {
+    /// SHA-256 over everything execution reads from this state, as lowercase hex.
+    ///
+    /// Objects, packages and linkage tables are hashed in sorted order, so the hash
+    /// only depends on content, not on map iteration order. Any change that can alter
+    /// the replay outcome (an object's bytes, a substituted module, the transaction
+    /// timestamp, the protocol version) changes the hash.
+    pub fn content_hash(&self) -> String {
+        let mut buf = Vec::new();
+        let transaction = serde_json::to_value(&self.transaction)
+            .map(canonical_json)
+            .and_then(|value| serde_json::to_vec(&value))
+            .unwrap_or_default();
+        write_bytes(&mut buf, &transaction);
+
+        let mut objects: Vec<&VersionedObject> = self.objects.values().collect();
+        objects.sort_by_key(|object| object.cache_key());
+        write_u64(&mut buf, objects.len() as u64);
+        for object in objects {
+            write_bytes(&mut buf, object.id.as_ref());
+            write_u64(&mut buf, object.version);
+            write_bytes(&mut buf, object.digest.as_deref().unwrap_or("").as_bytes());
+            write_bytes(
+                &mut buf,
+                object.type_tag.as_deref().unwrap_or("").as_bytes(),
+            );
+            write_bytes(&mut buf, &object.bcs_bytes);
+            buf.push(u8::from(object.is_shared));
+            buf.push(u8::from(object.is_immutable));
+        }
+
+        let mut packages: Vec<&PackageData> = self.packages.values().collect();
+        packages.sort_by_key(|package| package.address);
+        write_u64(&mut buf, packages.len() as u64);
+        for package in packages {
+            write_bytes(&mut buf, package.address.as_ref());
+            write_u64(&mut buf, package.version);
+            write_bytes(&mut buf, package.runtime_id().as_ref());
+            let mut modules: Vec<&(String, Vec<u8>)> = package.modules.iter().collect();
+            modules.sort_by(|a, b| a.0.cmp(&b.0));
+            write_u64(&mut buf, modules.len() as u64);
+            for (name, bytes) in modules {
+                write_bytes(&mut buf, name.as_bytes());
+                write_bytes(&mut buf, bytes);
+            }
+            let mut linkage: Vec<(&AccountAddress, &AccountAddress)> =
+                package.linkage.iter().collect();
+            linkage.sort();
+            write_u64(&mut buf, linkage.len() as u64);
+            for (runtime_id, storage_id) in linkage {
+                write_bytes(&mut buf, runtime_id.as_ref());
+                write_bytes(&mut buf, storage_id.as_ref());
+            }
+        }
+
+        write_u64(&mut buf, self.protocol_version);
+        write_u64(&mut buf, self.epoch);
+        write_u64(&mut buf, self.reference_gas_price.unwrap_or(0));
+        write_u64(&mut buf, self.checkpoint.unwrap_or(0));
+        hex::encode(sui_package_extractor::utils::sha256_32(&buf))
+    }
+}
+
+/// Rebuild JSON objects with sorted keys so serialization is deterministic even when
+/// the source struct holds a `HashMap`.
+fn canonical_json(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<(String, serde_json::Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonical_json(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(canonical_json).collect())
+        }
+        other => other,
+    }
+}
+
+fn write_u64(buf: &mut Vec<u8>, value: u64) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_u64(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
 /// Object data with version information for cache keying.
 ///
 /// The key insight: for replay, we need objects at their *exact* historical versions,
@@ -51,7 +144,9 @@ pub struct VersionedObject {
     /// Object version (sequence number).
     pub version: u64,
 
-    /// Object digest (for verification).
+    /// Object digest of the object `bcs_bytes` came from (for verification).
+    ///
+    /// `None` when the source can't vouch for the bytes, e.g. prefetched children.
     pub digest: Option<String>,
 
     /// Move type tag (e.g., "0x2::coin::Coin<0x2::sui::SUI>").
@@ -146,3 +241,73 @@ impl FetchStats {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sandbox_types::TransactionDigest;
+
+    fn state() -> ReplayState {
+        let transaction = FetchedTransaction {
+            digest: TransactionDigest::new("digest"),
+            sender: AccountAddress::ONE,
+            gas_budget: 1_000,
+            gas_price: 750,
+            commands: Vec::new(),
+            inputs: Vec::new(),
+            effects: None,
+            timestamp_ms: Some(1),
+            checkpoint: Some(7),
+            gas_payment: Vec::new(),
+        };
+        let objects = (1u8..=8)
+            .map(|n| {
+                let id = AccountAddress::new([n; 32]);
+                let object = VersionedObject {
+                    id,
+                    version: n as u64,
+                    digest: None,
+                    type_tag: None,
+                    bcs_bytes: vec![n; 4],
+                    is_shared: false,
+                    is_immutable: false,
+                };
+                (id, object)
+            })
+            .collect();
+        ReplayState {
+            transaction,
+            objects,
+            packages: HashMap::new(),
+            protocol_version: 70,
+            epoch: 3,
+            reference_gas_price: Some(750),
+            checkpoint: Some(7),
+        }
+    }
+
+    #[test]
+    fn test_content_hash_tracks_content_not_map_order() {
+        let base = state();
+        let mut reordered = state();
+        reordered.objects = base
+            .objects
+            .iter()
+            .rev()
+            .map(|(id, object)| (*id, object.clone()))
+            .collect();
+        assert_eq!(base.content_hash(), reordered.content_hash());
+
+        let mut changed = state();
+        changed
+            .objects
+            .get_mut(&AccountAddress::new([3; 32]))
+            .unwrap()
+            .bcs_bytes[0] = 0xff;
+        assert_ne!(base.content_hash(), changed.content_hash());
+
+        let mut clock = state();
+        clock.transaction.timestamp_ms = Some(2);
+        assert_ne!(base.content_hash(), clock.content_hash());
+    }
+}
@@ -173,6 +173,7 @@ mod tests {
                 effects: None,
                 timestamp_ms: None,
                 checkpoint: None,
+                gas_payment: vec![],
             },
             objects: HashMap::new(),
             packages,
@@ -21,8 +21,9 @@ use std::collections::HashMap;
 use anyhow::{anyhow, Context, Result};
 use move_core_types::account_address::AccountAddress;
 use sui_sandbox_types::{
-    encoding::base64_encode, FetchedTransaction, GasSummary, PtbArgument, PtbCommand,
-    TransactionDigest, TransactionEffectsSummary, TransactionInput, TransactionStatus,
+    encoding::base64_encode, FetchedTransaction, GasSummary, ObjectChangeDetail, ObjectChangeKind,
+    PtbArgument, PtbCommand, TransactionDigest, TransactionEffectsSummary, TransactionInput,
+    TransactionStatus, CLOCK_OBJECT_ID,
 };
 use sui_types::effects::TransactionEffectsAPI;
 use sui_types::full_checkpoint_content::CheckpointData;
@@ -32,6 +33,7 @@ use sui_types::transaction::{
     TransactionDataAPI, TransactionKind,
 };
 
+use crate::bcs_codec::gas_payment_objects;
 use crate::provider::package_data_from_move_package;
 use crate::types::{PackageData, ReplayState, VersionedObject};
 
@@ -89,7 +91,9 @@ pub fn checkpoint_to_replay_state(
                     VersionedObject {
                         id,
                         version,
-                        digest: None,
+                        // Full object is available here, so the reference digest
+                        // can be recomputed for input validation.
+                        digest: Some(obj.digest().to_string()),
                         type_tag,
                         bcs_bytes,
                         is_shared,
@@ -140,6 +144,92 @@ pub fn find_tx_in_checkpoint(checkpoint_data: &CheckpointData, digest: &str) ->
         .position(|tx| tx.transaction.digest().to_string() == digest)
 }
 
+/// A system transaction whose effects [`apply_checkpoint_prologue`] carried into a replay.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CheckpointPrologueTransaction {
+    pub digest: String,
+    /// Transaction kind, e.g. `ConsensusCommitPrologueV3` or `AuthenticatorStateUpdate`.
+    pub kind: &'static str,
+    /// Objects whose post-transaction version was added to or replaced in the state.
+    pub objects: Vec<String>,
+}
+
+/// Apply the output objects of the system transactions that precede `digest` in the
+/// checkpoint (consensus commit prologue, authenticator state and randomness updates)
+/// to `state`, as if they had run before the target.
+///
+/// Objects the state already holds at the same or a later version are kept. When the
+/// prologue wrote the Clock, the transaction timestamp becomes the consensus commit
+/// timestamp instead of the checkpoint timestamp.
+pub fn apply_checkpoint_prologue(
+    checkpoint_data: &CheckpointData,
+    digest: &str,
+    state: &mut ReplayState,
+) -> Result<Vec<CheckpointPrologueTransaction>> {
+    let tx_index = find_tx_in_checkpoint(checkpoint_data, digest).ok_or_else(|| {
+        anyhow!(
+            "Transaction {} not found in checkpoint {}",
+            digest,
+            checkpoint_data.checkpoint_summary.sequence_number
+        )
+    })?;
+
+    let mut applied = Vec::new();
+    for checkpoint_tx in &checkpoint_data.transactions[..tx_index] {
+        let kind = checkpoint_tx.transaction.data().transaction_data().kind();
+        if !kind.is_system_tx() {
+            continue;
+        }
+        let mut objects = Vec::new();
+        for obj in &checkpoint_tx.output_objects {
+            let SuiData::Move(move_obj) = &obj.data else {
+                continue;
+            };
+            let id = AccountAddress::from(obj.id());
+            let version = obj.version().value();
+            if state
+                .objects
+                .get(&id)
+                .is_some_and(|existing| existing.version >= version)
+            {
+                continue;
+            }
+            let (is_shared, is_immutable) = owner_flags(&obj.owner);
+            let bcs_bytes = move_obj.contents().to_vec();
+            if id == CLOCK_OBJECT_ID {
+                if let Some(timestamp_ms) = clock_timestamp_ms(&bcs_bytes) {
+                    state.transaction.timestamp_ms = Some(timestamp_ms);
+                }
+            }
+            state.objects.insert(
+                id,
+                VersionedObject {
+                    id,
+                    version,
+                    digest: Some(obj.digest().to_string()),
+                    type_tag: Some(move_obj.type_().to_string()),
+                    bcs_bytes,
+                    is_shared,
+                    is_immutable,
+                },
+            );
+            objects.push(id.to_hex_literal());
+        }
+        applied.push(CheckpointPrologueTransaction {
+            digest: checkpoint_tx.transaction.digest().to_string(),
+            kind: kind.name(),
+            objects,
+        });
+    }
+    Ok(applied)
+}
+
+/// `timestamp_ms` of a BCS-encoded `0x2::clock::Clock` (`{ id: UID, timestamp_ms: u64 }`).
+fn clock_timestamp_ms(bytes: &[u8]) -> Option<u64> {
+    let timestamp = bytes.get(32..40)?;
+    Some(u64::from_le_bytes(timestamp.try_into().ok()?))
+}
+
 /// Convert a `CheckpointTransaction` to a `FetchedTransaction`.
 fn checkpoint_tx_to_fetched_transaction(
     checkpoint_tx: &sui_types::full_checkpoint_content::CheckpointTransaction,
@@ -177,6 +267,7 @@ fn checkpoint_tx_to_fetched_transaction(
         effects: Some(effects),
         timestamp_ms: Some(timestamp_ms),
         checkpoint: Some(checkpoint_seq),
+        gas_payment: gas_payment_objects(tx_data),
     })
 }
 
@@ -322,6 +413,64 @@ fn build_effects_summary(
         gas_used,
         events_count: 0,
         shared_object_versions: HashMap::new(),
+        object_changes: effects_object_changes(effects),
+    }
+}
+
+/// Per-object references from `TransactionEffects`, ordered by change kind.
+fn effects_object_changes(
+    effects: &sui_types::effects::TransactionEffects,
+) -> Vec<ObjectChangeDetail> {
+    let input_versions: HashMap<_, _> = effects
+        .modified_at_versions()
+        .into_iter()
+        .map(|(id, version)| (id, version.value()))
+        .collect();
+    let detail = |change, obj_ref: sui_types::base_types::ObjectRef, owner: Option<&Owner>| {
+        ObjectChangeDetail {
+            object_id: obj_ref.0.to_hex_literal(),
+            change,
+            input_version: input_versions.get(&obj_ref.0).copied(),
+            output_version: Some(obj_ref.1.value()),
+            input_digest: None,
+            output_digest: owner.map(|_| obj_ref.2.to_string()),
+            owner: owner.map(owner_label),
+        }
+    };
+
+    let mut changes = Vec::new();
+    for (kind, written) in [
+        (ObjectChangeKind::Created, effects.created()),
+        (ObjectChangeKind::Mutated, effects.mutated()),
+        (ObjectChangeKind::Unwrapped, effects.unwrapped()),
+    ] {
+        changes.extend(
+            written
+                .into_iter()
+                .map(|(obj_ref, owner)| detail(kind, obj_ref, Some(&owner))),
+        );
+    }
+    for (kind, removed) in [
+        (ObjectChangeKind::Deleted, effects.deleted()),
+        (ObjectChangeKind::Wrapped, effects.wrapped()),
+    ] {
+        changes.extend(
+            removed
+                .into_iter()
+                .map(|obj_ref| detail(kind, obj_ref, None)),
+        );
+    }
+    changes
+}
+
+/// Owner label used by [`ObjectChangeDetail::owner`].
+fn owner_label(owner: &Owner) -> String {
+    match owner {
+        Owner::AddressOwner(address) => format!("address:{}", address),
+        Owner::ConsensusAddressOwner { owner, .. } => format!("address:{}", owner),
+        Owner::ObjectOwner(parent) => format!("object:{}", parent),
+        Owner::Shared { .. } => "shared".to_string(),
+        Owner::Immutable => "immutable".to_string(),
     }
 }
 
@@ -338,6 +487,14 @@ fn owner_flags(owner: &Owner) -> (bool, bool) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_clock_timestamp_ms() {
+        let mut bytes = CLOCK_OBJECT_ID.to_vec();
+        bytes.extend_from_slice(&1_700_000_000_123u64.to_le_bytes());
+        assert_eq!(clock_timestamp_ms(&bytes), Some(1_700_000_000_123));
+        assert_eq!(clock_timestamp_ms(&bytes[..36]), None);
+    }
+
     #[test]
     fn test_convert_sui_argument() {
         assert!(matches!(
@@ -58,6 +58,84 @@ async fn test_fetch_replay_state_success_tx() {
     println!("Checkpoint: {:?}", state.checkpoint);
 }
 
+/// Replays a recent transaction that takes the SuiSystemState (0x5) as input (e.g. a
+/// staking call) and checks the system state is hydrated from history, inner state
+/// included, rather than injected with defaults.
+#[tokio::test]
+#[ignore = "requires network access to Sui mainnet"]
+async fn test_fetch_replay_state_hydrates_system_state() {
+    use move_core_types::account_address::AccountAddress;
+    use sui_sandbox_types::SYSTEM_STATE_OBJECT_ID;
+    use sui_transport::grpc::GrpcInput;
+
+    let provider = HistoricalStateProvider::mainnet()
+        .await
+        .expect("Failed to create provider");
+    let latest = provider
+        .grpc()
+        .get_latest_checkpoint()
+        .await
+        .expect("Failed to fetch latest checkpoint")
+        .expect("Latest checkpoint should exist");
+
+    let reads_system_state = |input: &GrpcInput| {
+        matches!(input, GrpcInput::SharedObject { object_id, .. }
+            if AccountAddress::from_hex_literal(object_id).ok() == Some(SYSTEM_STATE_OBJECT_ID))
+    };
+    let mut digest = None;
+    for seq in (latest.sequence_number.saturating_sub(200)..=latest.sequence_number).rev() {
+        let Ok(Some(checkpoint)) = provider.grpc().get_checkpoint(seq).await else {
+            continue;
+        };
+        digest = checkpoint
+            .transactions
+            .iter()
+            .find(|tx| tx.inputs.iter().any(reads_system_state))
+            .map(|tx| tx.digest.clone());
+        if digest.is_some() {
+            break;
+        }
+    }
+    let Some(digest) = digest else {
+        println!("No transaction reading 0x5 in the last 200 checkpoints; skipping");
+        return;
+    };
+
+    let state: ReplayState = provider
+        .fetch_replay_state(&digest)
+        .await
+        .expect("Failed to fetch replay state");
+
+    let system_state = state
+        .objects
+        .get(&SYSTEM_STATE_OBJECT_ID)
+        .expect("0x5 should be hydrated");
+    let inner_id = sui_state_fetcher::system_state_inner_id(&system_state.bcs_bytes)
+        .expect("0x5 should carry its inner state version");
+    let inner = state
+        .objects
+        .get(&inner_id)
+        .expect("SuiSystemState inner object should be hydrated");
+    assert!(
+        inner
+            .type_tag
+            .as_deref()
+            .is_some_and(|t| t.contains("SuiSystemStateInner")),
+        "unexpected inner type: {:?}",
+        inner.type_tag
+    );
+    // Children are never newer than the wrapper that was read alongside them.
+    assert!(inner.version <= system_state.version);
+
+    println!(
+        "Transaction {}: 0x5 at version {}, inner {} at version {}",
+        digest,
+        system_state.version,
+        inner_id.to_hex_literal(),
+        inner.version
+    );
+}
+
 #[tokio::test]
 #[ignore = "requires network access to Sui mainnet"]
 async fn test_fetch_replay_state_failure_tx() {
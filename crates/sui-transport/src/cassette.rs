@@ -0,0 +1,492 @@
+//! Request/response cassettes for reproducing network-dependent runs offline.
+//!
+//! A recording [`Cassette`] captures every GraphQL query, unary gRPC ledger call and
+//! Walrus request made while it is active, together with its response (or error). A
+//! cassette loaded for playback serves those responses back and fails any request it
+//! has no recording for, so nothing reaches the network.
+//!
+//! A cassette is attached to the clients of one run with their `with_cassette`
+//! builders ([`crate::graphql::GraphQLClient`], [`crate::grpc::GrpcClient`],
+//! [`crate::walrus::WalrusClient`]). Clones share it, so concurrent runs in the same
+//! process record or play back independently. Identical requests are served in
+//! recording order (the last response repeats once they run out), which keeps
+//! retries and refetches deterministic.
+//!
+//! API keys never reach the file: request headers are not recorded, and values under
+//! key-like JSON fields or URL query parameters, as well as the configured gRPC API
+//! keys, are replaced with `<redacted>` in requests and error messages.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the cassette file format.
+pub const CASSETTE_FORMAT_VERSION: u32 = 1;
+
+const REDACTED: &str = "<redacted>";
+
+/// Environment variables holding API keys that must never be recorded.
+const SECRET_ENV_VARS: &[&str] = &["SUI_GRPC_API_KEY", "SURFLUX_API_KEY"];
+
+/// Field and query-parameter name fragments whose values are redacted.
+const SECRET_NAMES: &[&str] = &[
+    "api_key",
+    "apikey",
+    "authorization",
+    "token",
+    "secret",
+    "password",
+];
+
+/// Whether a cassette records live traffic or serves recorded traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record,
+    Playback,
+}
+
+/// A recorded response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedResponse {
+    Json {
+        body: Value,
+    },
+    Bytes {
+        base64: String,
+    },
+    Error {
+        message: String,
+        /// gRPC status code, for gRPC errors.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        code: Option<i32>,
+    },
+}
+
+/// One request and the response it received.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// `graphql`, `grpc`, or `walrus`.
+    pub transport: String,
+    pub request: Value,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteFile {
+    version: u32,
+    #[serde(default)]
+    metadata: Value,
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    /// Playback: interaction indices per request key, in recording order.
+    by_key: HashMap<String, Vec<usize>>,
+    /// Playback: how many times each key has been served.
+    served: HashMap<String, usize>,
+}
+
+/// Recorded network traffic, in record or playback mode.
+#[derive(Debug)]
+pub struct Cassette {
+    mode: CassetteMode,
+    path: PathBuf,
+    metadata: Value,
+    state: Mutex<CassetteState>,
+}
+
+impl Cassette {
+    /// Start an empty recording that [`Self::save`] writes to `path`. `metadata` is
+    /// stored alongside the interactions (e.g. the replay parameters).
+    pub fn record(path: impl Into<PathBuf>, metadata: Value) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            path: path.into(),
+            metadata,
+            state: Mutex::new(CassetteState::default()),
+        }
+    }
+
+    /// Load a cassette file for playback.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes =
+            std::fs::read(path).with_context(|| format!("read cassette {}", path.display()))?;
+        let file: CassetteFile = serde_json::from_slice(&bytes)
+            .with_context(|| format!("parse cassette {}", path.display()))?;
+        if file.version != CASSETTE_FORMAT_VERSION {
+            return Err(anyhow!(
+                "cassette {} has format version {}, expected {}",
+                path.display(),
+                file.version,
+                CASSETTE_FORMAT_VERSION
+            ));
+        }
+        let mut state = CassetteState::default();
+        for (index, interaction) in file.interactions.iter().enumerate() {
+            state
+                .by_key
+                .entry(request_key(&interaction.transport, &interaction.request))
+                .or_default()
+                .push(index);
+        }
+        state.interactions = file.interactions;
+        Ok(Self {
+            mode: CassetteMode::Playback,
+            path: path.to_path_buf(),
+            metadata: file.metadata,
+            state: Mutex::new(state),
+        })
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn is_playback(&self) -> bool {
+        self.mode == CassetteMode::Playback
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn metadata(&self) -> &Value {
+        &self.metadata
+    }
+
+    /// Number of recorded interactions.
+    pub fn len(&self) -> usize {
+        self.lock().interactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Record `response` to `request`. A no-op in playback mode.
+    pub fn record_response(&self, transport: &str, request: Value, response: RecordedResponse) {
+        if self.is_playback() {
+            return;
+        }
+        let response = match response {
+            RecordedResponse::Error { message, code } => RecordedResponse::Error {
+                message: redact_str(&message),
+                code,
+            },
+            other => other,
+        };
+        self.lock().interactions.push(Interaction {
+            transport: transport.to_string(),
+            request: redact(request),
+            response,
+        });
+    }
+
+    /// Record a JSON response, or the error the request failed with.
+    pub fn record_json(&self, transport: &str, request: Value, response: &Result<Value>) {
+        let response = match response {
+            Ok(body) => RecordedResponse::Json { body: body.clone() },
+            Err(err) => RecordedResponse::Error {
+                message: format!("{:#}", err),
+                code: None,
+            },
+        };
+        self.record_response(transport, request, response);
+    }
+
+    /// Record a binary response, or the error the request failed with.
+    pub fn record_bytes(&self, transport: &str, request: Value, response: &Result<Vec<u8>>) {
+        let response = match response {
+            Ok(bytes) => RecordedResponse::Bytes {
+                base64: base64::engine::general_purpose::STANDARD.encode(bytes),
+            },
+            Err(err) => RecordedResponse::Error {
+                message: format!("{:#}", err),
+                code: None,
+            },
+        };
+        self.record_response(transport, request, response);
+    }
+
+    /// The recorded response to `request`, failing when none was recorded.
+    pub fn playback(&self, transport: &str, request: &Value) -> Result<RecordedResponse> {
+        let key = request_key(transport, &redact(request.clone()));
+        let mut state = self.lock();
+        let Some(indices) = state.by_key.get(&key) else {
+            return Err(anyhow!(
+                "cassette {} has no recorded {} response for {}",
+                self.path.display(),
+                transport,
+                truncate(&key, 400)
+            ));
+        };
+        let served = state.served.get(&key).copied().unwrap_or(0);
+        let index = indices[served.min(indices.len() - 1)];
+        *state.served.entry(key).or_default() += 1;
+        Ok(state.interactions[index].response.clone())
+    }
+
+    /// Play back a JSON response; recorded errors are returned as errors.
+    pub fn playback_json(&self, transport: &str, request: &Value) -> Result<Value> {
+        match self.playback(transport, request)? {
+            RecordedResponse::Json { body } => Ok(body),
+            RecordedResponse::Error { message, .. } => Err(anyhow!(message)),
+            RecordedResponse::Bytes { .. } => Err(anyhow!(
+                "cassette recorded a binary {} response where JSON was expected",
+                transport
+            )),
+        }
+    }
+
+    /// Play back a binary response; recorded errors are returned as errors.
+    pub fn playback_bytes(&self, transport: &str, request: &Value) -> Result<Vec<u8>> {
+        match self.playback(transport, request)? {
+            RecordedResponse::Bytes { base64 } => base64::engine::general_purpose::STANDARD
+                .decode(base64)
+                .context("decode recorded response bytes"),
+            RecordedResponse::Error { message, .. } => Err(anyhow!(message)),
+            RecordedResponse::Json { .. } => Err(anyhow!(
+                "cassette recorded a JSON {} response where bytes were expected",
+                transport
+            )),
+        }
+    }
+
+    /// Write the recording to its path and return the number of interactions.
+    pub fn save(&self) -> Result<usize> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create cassette directory {}", parent.display()))?;
+        }
+        let state = self.lock();
+        let file = CassetteFile {
+            version: CASSETTE_FORMAT_VERSION,
+            metadata: self.metadata.clone(),
+            interactions: state.interactions.clone(),
+        };
+        let bytes = serde_json::to_vec(&file).context("serialize cassette")?;
+        std::fs::write(&self.path, bytes)
+            .with_context(|| format!("write cassette {}", self.path.display()))?;
+        Ok(file.interactions.len())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Run a JSON request through `cassette`: served from the recording in playback
+/// mode, fetched and recorded in record mode, fetched when there is no cassette.
+pub fn json_through(
+    cassette: Option<&Cassette>,
+    transport: &str,
+    request: impl FnOnce() -> Value,
+    fetch: impl FnOnce() -> Result<Value>,
+) -> Result<Value> {
+    match cassette {
+        Some(cassette) if cassette.is_playback() => cassette.playback_json(transport, &request()),
+        Some(cassette) => {
+            let response = fetch();
+            cassette.record_json(transport, request(), &response);
+            response
+        }
+        None => fetch(),
+    }
+}
+
+/// Like [`json_through`], for binary responses.
+pub fn bytes_through(
+    cassette: Option<&Cassette>,
+    transport: &str,
+    request: impl FnOnce() -> Value,
+    fetch: impl FnOnce() -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    match cassette {
+        Some(cassette) if cassette.is_playback() => cassette.playback_bytes(transport, &request()),
+        Some(cassette) => {
+            let response = fetch();
+            cassette.record_bytes(transport, request(), &response);
+            response
+        }
+        None => fetch(),
+    }
+}
+
+fn request_key(transport: &str, request: &Value) -> String {
+    format!("{} {}", transport, request)
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase().replace('-', "_");
+    SECRET_NAMES.iter().any(|needle| name.contains(needle))
+}
+
+/// Replace secret values in a request.
+fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(name, value)| {
+                    let value = if is_secret_name(&name) && !value.is_null() {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact(value)
+                    };
+                    (name, value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        Value::String(text) => Value::String(redact_str(&text)),
+        other => other,
+    }
+}
+
+/// Replace configured API keys and secret-named URL query parameters in `text`.
+fn redact_str(text: &str) -> String {
+    let mut text = text.to_string();
+    for var in SECRET_ENV_VARS {
+        if let Ok(secret) = std::env::var(var) {
+            let secret = secret.trim();
+            if !secret.is_empty() {
+                text = text.replace(secret, REDACTED);
+            }
+        }
+    }
+    if !text.contains('=') {
+        return text;
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text.as_str();
+    while let Some(pos) = rest.find(['?', '&']) {
+        out.push_str(&rest[..=pos]);
+        rest = &rest[pos + 1..];
+        let end = rest.find(['&', '#', ' ', '"']).unwrap_or(rest.len());
+        match rest[..end].split_once('=') {
+            Some((name, _)) if is_secret_name(name) => {
+                out.push_str(name);
+                out.push('=');
+                out.push_str(REDACTED);
+            }
+            _ => out.push_str(&rest[..end]),
+        }
+        rest = &rest[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn playback_serves_recordings_in_order_and_rejects_unknown_requests() {
+        let path = std::env::temp_dir().join(format!(
+            "sui-transport-cassette-{}.json",
+            std::process::id()
+        ));
+        let request = serde_json::json!({"query": "q", "variables": {"id": "0x1"}});
+        let recorder = Cassette::record(&path, serde_json::json!({"digest": "abc"}));
+        recorder.record_json("graphql", request.clone(), &Err(anyhow!("timed out")));
+        recorder.record_json(
+            "graphql",
+            request.clone(),
+            &Ok(serde_json::json!({"ok": 1})),
+        );
+        recorder.record_bytes(
+            "walrus",
+            serde_json::json!({"blob": "b"}),
+            &Ok(vec![1, 2, 3]),
+        );
+        assert_eq!(recorder.save().unwrap(), 3);
+
+        let cassette = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(cassette.is_playback());
+        assert_eq!(cassette.metadata()["digest"], "abc");
+        let err = cassette.playback_json("graphql", &request).unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        assert_eq!(
+            cassette.playback_json("graphql", &request).unwrap()["ok"],
+            1
+        );
+        assert_eq!(
+            cassette.playback_json("graphql", &request).unwrap()["ok"],
+            1,
+            "the last recording repeats"
+        );
+        assert_eq!(
+            cassette
+                .playback_bytes("walrus", &serde_json::json!({"blob": "b"}))
+                .unwrap(),
+            vec![1, 2, 3]
+        );
+        let missing = cassette
+            .playback("grpc", &serde_json::json!({"method": "GetObject"}))
+            .unwrap_err();
+        assert!(missing.to_string().contains("no recorded grpc response"));
+    }
+
+    #[test]
+    fn concurrent_cassettes_record_only_their_own_requests() {
+        let first = Cassette::record("first.json", Value::Null);
+        let second = Cassette::record("second.json", Value::Null);
+        std::thread::scope(|scope| {
+            for (cassette, id) in [(&first, "0x1"), (&second, "0x2")] {
+                scope.spawn(move || {
+                    for _ in 0..3 {
+                        json_through(
+                            Some(cassette),
+                            "graphql",
+                            || serde_json::json!({"id": id}),
+                            || Ok(serde_json::json!({"object": id})),
+                        )
+                        .unwrap();
+                    }
+                });
+            }
+        });
+        json_through(None, "graphql", || Value::Null, || Ok(Value::Null)).unwrap();
+
+        for (cassette, id) in [(&first, "0x1"), (&second, "0x2")] {
+            let state = cassette.lock();
+            assert_eq!(state.interactions.len(), 3);
+            assert!(state
+                .interactions
+                .iter()
+                .all(|interaction| interaction.request["id"] == id));
+        }
+    }
+
+    #[test]
+    fn redacts_secret_fields_and_query_parameters() {
+        let redacted = redact(serde_json::json!({
+            "url": "https://host/v1/x?checkpoint=5&api_key=hunter2&b=1",
+            "headers": {"X-Api-Key": "hunter2", "accept": "json"},
+        }));
+        assert_eq!(
+            redacted["url"],
+            "https://host/v1/x?checkpoint=5&api_key=<redacted>&b=1"
+        );
+        assert_eq!(redacted["headers"]["X-Api-Key"], REDACTED);
+        assert_eq!(redacted["headers"]["accept"], "json");
+    }
+}
@@ -0,0 +1,201 @@
+//! Opt-in wire compression for gRPC and Walrus transfers.
+//!
+//! Compression is negotiated, never assumed: the client only advertises the
+//! encodings it can decode, and a server that ignores the request simply
+//! answers uncompressed. Configure per client via `with_compression`, or
+//! globally with `SUI_TRANSPORT_COMPRESSION=gzip|zstd|none` (default: none).
+
+use crate::error::TransportError;
+use anyhow::{anyhow, Context, Result};
+use std::io::Read;
+use std::str::FromStr;
+
+/// Wire encoding requested for response bodies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// No compression (identity encoding).
+    #[default]
+    None,
+    /// gzip (per-message for gRPC, `Content-Encoding: gzip` for HTTP).
+    Gzip,
+    /// zstd (per-message for gRPC, `Content-Encoding: zstd` for HTTP).
+    Zstd,
+}
+
+impl Compression {
+    /// Read `SUI_TRANSPORT_COMPRESSION`, falling back to [`Compression::None`]
+    /// when unset or unrecognized.
+    pub fn from_env() -> Self {
+        std::env::var("SUI_TRANSPORT_COMPRESSION")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if any encoding other than identity is requested.
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// Value for the HTTP `Accept-Encoding` header, if compression is enabled.
+    ///
+    /// `identity` is always listed so servers without the requested encoding
+    /// can still answer.
+    pub fn accept_encoding(&self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Gzip => Some("gzip, identity"),
+            Self::Zstd => Some("zstd, gzip, identity"),
+        }
+    }
+
+    /// Encodings tonic should advertise via `grpc-accept-encoding`.
+    pub(crate) fn grpc_encodings(&self) -> &'static [tonic::codec::CompressionEncoding] {
+        use tonic::codec::CompressionEncoding;
+        match self {
+            Self::None => &[],
+            Self::Gzip => &[CompressionEncoding::Gzip],
+            Self::Zstd => &[CompressionEncoding::Zstd, CompressionEncoding::Gzip],
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "none" | "identity" | "off" => Ok(Self::None),
+            "gzip" => Ok(Self::Gzip),
+            "zstd" => Ok(Self::Zstd),
+            other => Err(anyhow!(
+                "unsupported compression '{}': expected gzip, zstd, or none",
+                other
+            )),
+        }
+    }
+}
+
+/// Decode an HTTP response body according to its `Content-Encoding` header.
+///
+/// Missing or `identity` encodings pass the bytes through unchanged.
+pub fn decode_http_body(content_encoding: Option<&str>, body: Vec<u8>) -> Result<Vec<u8>> {
+    decode_http_body_limited(content_encoding, body, u64::MAX)
+}
+
+/// [`decode_http_body`] that fails with [`TransportError::ResponseTooLarge`] once the
+/// decoded body exceeds `max_len` bytes, so a small compressed response can't expand
+/// without bound.
+pub fn decode_http_body_limited(
+    content_encoding: Option<&str>,
+    body: Vec<u8>,
+    max_len: u64,
+) -> Result<Vec<u8>> {
+    let encoding = content_encoding
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    match encoding.as_str() {
+        "" | "identity" => {
+            if body.len() as u64 > max_len {
+                return Err(too_large(Some(body.len() as u64), max_len).into());
+            }
+            Ok(body)
+        }
+        "gzip" | "x-gzip" => {
+            read_to_end_limited(flate2::read::GzDecoder::new(body.as_slice()), max_len)
+                .context("Failed to gunzip response body")
+        }
+        "zstd" => {
+            let decoder = zstd::stream::read::Decoder::new(body.as_slice())
+                .context("Failed to zstd-decode response body")?;
+            read_to_end_limited(decoder, max_len).context("Failed to zstd-decode response body")
+        }
+        other => Err(anyhow!("unsupported Content-Encoding '{}'", other)),
+    }
+}
+
+/// Read `reader` to the end, failing with [`TransportError::ResponseTooLarge`] once
+/// more than `max_len` bytes arrive.
+pub(crate) fn read_to_end_limited(reader: impl Read, max_len: u64) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    reader
+        .take(max_len.saturating_add(1))
+        .read_to_end(&mut out)?;
+    if out.len() as u64 > max_len {
+        return Err(too_large(None, max_len).into());
+    }
+    Ok(out)
+}
+
+fn too_large(size: Option<u64>, limit: u64) -> TransportError {
+    TransportError::ResponseTooLarge {
+        resource: "response body".to_string(),
+        size,
+        limit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn parses_compression_names() {
+        assert_eq!("gzip".parse::<Compression>().unwrap(), Compression::Gzip);
+        assert_eq!(" ZSTD ".parse::<Compression>().unwrap(), Compression::Zstd);
+        assert_eq!("none".parse::<Compression>().unwrap(), Compression::None);
+        assert!("brotli".parse::<Compression>().is_err());
+    }
+
+    #[test]
+    fn decodes_gzip_zstd_and_identity_bodies() {
+        let payload = b"checkpoint bytes".repeat(16);
+
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(&payload).unwrap();
+        let gzipped = gz.finish().unwrap();
+        assert_eq!(decode_http_body(Some("gzip"), gzipped).unwrap(), payload);
+
+        let zstded = zstd::stream::encode_all(payload.as_slice(), 0).unwrap();
+        assert_eq!(decode_http_body(Some("zstd"), zstded).unwrap(), payload);
+
+        assert_eq!(decode_http_body(None, payload.clone()).unwrap(), payload);
+        assert!(decode_http_body(Some("br"), payload).is_err());
+    }
+
+    #[test]
+    fn limited_decode_rejects_oversized_bodies() {
+        let payload = vec![0u8; 64 * 1024];
+        let zstded = zstd::stream::encode_all(payload.as_slice(), 0).unwrap();
+        assert!(zstded.len() < 1024);
+
+        let err = decode_http_body_limited(Some("zstd"), zstded.clone(), 1024).unwrap_err();
+        assert!(
+            matches!(
+                TransportError::find(&err),
+                Some(TransportError::ResponseTooLarge {
+                    size: None,
+                    limit: 1024,
+                    ..
+                })
+            ),
+            "{err:#}"
+        );
+        let err = decode_http_body_limited(None, payload.clone(), 1024).unwrap_err();
+        assert!(
+            matches!(
+                TransportError::find(&err),
+                Some(TransportError::ResponseTooLarge {
+                    size: Some(65536),
+                    ..
+                })
+            ),
+            "{err:#}"
+        );
+        assert_eq!(
+            decode_http_body_limited(Some("zstd"), zstded, payload.len() as u64).unwrap(),
+            payload
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Typed transport errors.
+//!
+//! Fetch paths return `anyhow::Result`; conditions callers need to tell apart are
+//! wrapped as a [`TransportError`] so they can be recovered with
+//! [`TransportError::find`] (or `anyhow::Error::downcast_ref`) anywhere up the chain.
+//!
+//! ```ignore
+//! match grpc.get_object_at_version(id, Some(version)).await {
+//!     Err(err) => match TransportError::find(&err) {
+//!         // `version` came from the transaction's input refs, so it existed.
+//!         Some(TransportError::ObjectNotFound { .. }) => {
+//!             let gap = grpc.missing_version_error(id, version, true);
+//!             eprintln!("{} (try: {:?})", gap, gap.remediation());
+//!         }
+//!         _ => return Err(err),
+//!     },
+//!     Ok(object) => { /* ... */ }
+//! }
+//! ```
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// A transport failure with a specific, diagnosable cause.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportError {
+    /// The object exists, but `endpoint` has no copy of `requested_version`: the
+    /// archive behind it has pruned or partial history. Another archive or the
+    /// Walrus checkpoint archive usually has it.
+    ArchiveVersionGap {
+        object_id: String,
+        requested_version: u64,
+        endpoint: String,
+    },
+    /// The endpoint has no record of the object at the requested version (or at all),
+    /// and nothing suggests a history gap.
+    ObjectNotFound {
+        object_id: String,
+        version: Option<u64>,
+    },
+    /// `resource` is larger than the client's size limit; `size` is its length
+    /// when known (requested or declared), `None` when the limit was hit mid-read.
+    ResponseTooLarge {
+        resource: String,
+        size: Option<u64>,
+        limit: u64,
+    },
+    /// The request for `resource` did not complete within the client's timeout.
+    RequestTimeout { resource: String, timeout: Duration },
+    /// `endpoint` could not be reached (transport or HTTP failure, or an open circuit
+    /// breaker); `detail` is the underlying cause. Query-level errors are not this.
+    EndpointUnreachable { endpoint: String, detail: String },
+}
+
+/// A machine-readable next step for recovering from a [`TransportError`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Remediation {
+    /// Retry against another historical gRPC endpoint, selected via `env_var`.
+    AlternateEndpoint {
+        endpoint: String,
+        env_var: &'static str,
+    },
+    /// Hydrate from the Walrus checkpoint archive (`source="walrus"` plus the
+    /// transaction's checkpoint), which does not depend on gRPC archive retention.
+    Walrus,
+}
+
+impl TransportError {
+    /// Stable identifier for the error kind.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TransportError::ArchiveVersionGap { .. } => "archive_version_gap",
+            TransportError::ObjectNotFound { .. } => "object_not_found",
+            TransportError::ResponseTooLarge { .. } => "response_too_large",
+            TransportError::RequestTimeout { .. } => "request_timeout",
+            TransportError::EndpointUnreachable { .. } => "endpoint_unreachable",
+        }
+    }
+
+    /// What to try next; empty when retrying elsewhere will not help.
+    pub fn remediation(&self) -> Vec<Remediation> {
+        match self {
+            TransportError::ArchiveVersionGap { endpoint, .. } => vec![
+                Remediation::AlternateEndpoint {
+                    endpoint: crate::grpc::alternate_archive_endpoint(endpoint).to_string(),
+                    env_var: "SUI_GRPC_HISTORICAL_ENDPOINT",
+                },
+                Remediation::Walrus,
+            ],
+            TransportError::ObjectNotFound { .. }
+            | TransportError::ResponseTooLarge { .. }
+            | TransportError::RequestTimeout { .. }
+            | TransportError::EndpointUnreachable { .. } => Vec::new(),
+        }
+    }
+
+    /// JSON form for reports: `code`, the error's fields, `message` and `remediation`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut value = match self {
+            TransportError::ArchiveVersionGap {
+                object_id,
+                requested_version,
+                endpoint,
+            } => serde_json::json!({
+                "object_id": object_id,
+                "requested_version": requested_version,
+                "endpoint": endpoint,
+            }),
+            TransportError::ObjectNotFound { object_id, version } => serde_json::json!({
+                "object_id": object_id,
+                "version": version,
+            }),
+            TransportError::ResponseTooLarge {
+                resource,
+                size,
+                limit,
+            } => serde_json::json!({
+                "resource": resource,
+                "size": size,
+                "limit": limit,
+            }),
+            TransportError::RequestTimeout { resource, timeout } => serde_json::json!({
+                "resource": resource,
+                "timeout_ms": timeout.as_millis() as u64,
+            }),
+            TransportError::EndpointUnreachable { endpoint, detail } => serde_json::json!({
+                "endpoint": endpoint,
+                "detail": detail,
+            }),
+        };
+        value["code"] = serde_json::json!(self.code());
+        value["message"] = serde_json::json!(self.to_string());
+        value["remediation"] = serde_json::json!(self.remediation());
+        value
+    }
+
+    /// The first `TransportError` in `err`'s chain.
+    pub fn find(err: &anyhow::Error) -> Option<&TransportError> {
+        err.chain()
+            .find_map(|cause| cause.downcast_ref::<TransportError>())
+    }
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransportError::ArchiveVersionGap {
+                object_id,
+                requested_version,
+                endpoint,
+            } => write!(
+                f,
+                "object {} exists but version {} is not archived at {}",
+                object_id, requested_version, endpoint
+            ),
+            TransportError::ObjectNotFound {
+                object_id,
+                version: Some(version),
+            } => write!(f, "object {} not found at version {}", object_id, version),
+            TransportError::ObjectNotFound {
+                object_id,
+                version: None,
+            } => write!(f, "object {} not found", object_id),
+            TransportError::ResponseTooLarge {
+                resource,
+                size: Some(size),
+                limit,
+            } => write!(
+                f,
+                "{} of {} bytes exceeds the {}-byte limit",
+                resource, size, limit
+            ),
+            TransportError::ResponseTooLarge {
+                resource,
+                size: None,
+                limit,
+            } => write!(f, "{} exceeds the {}-byte limit", resource, limit),
+            TransportError::RequestTimeout { resource, timeout } => {
+                write!(f, "{} timed out after {}ms", resource, timeout.as_millis())
+            }
+            TransportError::EndpointUnreachable { endpoint, detail } => {
+                write!(f, "{} unreachable: {}", endpoint, detail)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_gap_is_recoverable_through_context() {
+        let gap = TransportError::ArchiveVersionGap {
+            object_id: "0xabc".to_string(),
+            requested_version: 7,
+            endpoint: "https://archive.mainnet.sui.io:443".to_string(),
+        };
+        let err = anyhow::Error::new(gap.clone()).context("fetch input objects");
+        assert_eq!(TransportError::find(&err), Some(&gap));
+
+        let json = gap.to_json();
+        assert_eq!(json["code"], "archive_version_gap");
+        assert_eq!(json["requested_version"], 7);
+        assert_eq!(json["remediation"][0]["action"], "alternate_endpoint");
+        assert_ne!(
+            json["remediation"][0]["endpoint"],
+            "https://archive.mainnet.sui.io:443"
+        );
+        assert_eq!(json["remediation"][1]["action"], "walrus");
+
+        let not_found = TransportError::ObjectNotFound {
+            object_id: "0xabc".to_string(),
+            version: Some(7),
+        };
+        assert!(not_found.remediation().is_empty());
+        assert!(TransportError::find(&anyhow::anyhow!("plain error")).is_none());
+    }
+
+    #[test]
+    fn limit_errors_report_codes_and_limits() {
+        let too_large = TransportError::ResponseTooLarge {
+            resource: "Walrus blob b1 response".to_string(),
+            size: Some(4096),
+            limit: 1024,
+        };
+        let json = too_large.to_json();
+        assert_eq!(json["code"], "response_too_large");
+        assert_eq!(json["size"], 4096);
+        assert_eq!(json["limit"], 1024);
+        assert_eq!(
+            too_large.to_string(),
+            "Walrus blob b1 response of 4096 bytes exceeds the 1024-byte limit"
+        );
+
+        let timeout = TransportError::RequestTimeout {
+            resource: "Walrus blob b1".to_string(),
+            timeout: Duration::from_secs(2),
+        };
+        let json = timeout.to_json();
+        assert_eq!(json["code"], "request_timeout");
+        assert_eq!(json["timeout_ms"], 2000);
+        assert!(timeout.remediation().is_empty());
+    }
+
+    #[test]
+    fn unreachable_endpoint_reports_endpoint_and_cause() {
+        let unreachable = TransportError::EndpointUnreachable {
+            endpoint: "https://graphql.mainnet.sui.io/graphql".to_string(),
+            detail: "GraphQL request failed: connection refused".to_string(),
+        };
+        let err = anyhow::Error::new(unreachable.clone()).context("fetch package");
+        assert_eq!(TransportError::find(&err), Some(&unreachable));
+        let json = unreachable.to_json();
+        assert_eq!(json["code"], "endpoint_unreachable");
+        assert_eq!(json["endpoint"], "https://graphql.mainnet.sui.io/graphql");
+        assert!(unreachable.remediation().is_empty());
+    }
+}
@@ -33,10 +33,15 @@
 //! let pkg = client.fetch_package("0x2")?;
 //! ```
 
+use crate::cassette::Cassette;
+use crate::error::TransportError;
+use crate::network::Network;
 use anyhow::{anyhow, Result};
 use base64::Engine;
+use move_core_types::account_address::AccountAddress;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -53,13 +58,123 @@ fn env_var_or<T: FromStr>(key: &str, default: T) -> T {
 /// Maximum items per GraphQL page (Sui's server limit).
 const MAX_PAGE_SIZE: usize = 50;
 
+/// Default page bound for [`GraphQLClient::scan_dynamic_field_by_bcs`].
+pub const DEFAULT_DF_SCAN_MAX_PAGES: usize = 200;
+
+/// Max pages scanned when searching a parent's dynamic fields by key
+/// (`SUI_DF_SCAN_MAX_PAGES`, default [`DEFAULT_DF_SCAN_MAX_PAGES`]).
+pub fn dynamic_field_scan_max_pages() -> usize {
+    env_var_or("SUI_DF_SCAN_MAX_PAGES", DEFAULT_DF_SCAN_MAX_PAGES).max(1)
+}
+
+/// Outcome of [`GraphQLClient::scan_dynamic_field_by_bcs`].
+#[derive(Debug, Clone)]
+pub enum DynamicFieldScan {
+    Found(DynamicFieldInfo),
+    /// Every page was scanned and no field matched the key.
+    Absent,
+    /// The page bound was reached with pages left; the field may still exist.
+    Truncated {
+        pages_scanned: usize,
+    },
+}
+
+impl DynamicFieldScan {
+    /// The matched field, if any.
+    pub fn found(self) -> Option<DynamicFieldInfo> {
+        match self {
+            DynamicFieldScan::Found(field) => Some(field),
+            DynamicFieldScan::Absent | DynamicFieldScan::Truncated { .. } => None,
+        }
+    }
+}
+
+/// Walk dynamic-field pages via `fetch_page` until an entry's name BCS equals `key_bcs`.
+fn scan_pages_for_key<F>(
+    key_bcs: &[u8],
+    max_pages: usize,
+    mut fetch_page: F,
+) -> Result<DynamicFieldScan>
+where
+    F: FnMut(Option<&str>) -> Result<(Vec<DynamicFieldInfo>, PageInfo)>,
+{
+    let key_b64 = base64::engine::general_purpose::STANDARD.encode(key_bcs);
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..max_pages {
+        let (items, page_info) = fetch_page(cursor.as_deref())?;
+        for item in items {
+            if let Some(name_bcs) = item.name_bcs.as_deref() {
+                if name_bcs == key_b64.as_str()
+                    || GraphQLClient::b64_matches_bytes(name_bcs, key_bcs)
+                {
+                    return Ok(DynamicFieldScan::Found(item));
+                }
+            }
+        }
+        if !page_info.has_next_page {
+            return Ok(DynamicFieldScan::Absent);
+        }
+        cursor = page_info.end_cursor;
+        if cursor.is_none() {
+            return Ok(DynamicFieldScan::Absent);
+        }
+    }
+
+    Ok(DynamicFieldScan::Truncated {
+        pages_scanned: max_pages,
+    })
+}
+
+/// Follow cursors via `fetch_page(cursor, page_size)` until the pages run out or
+/// `limit` items have been collected. The flag is `true` when the limit cut the
+/// listing short (more items were reported by the server).
+fn collect_pages_up_to<T, F>(limit: Option<usize>, mut fetch_page: F) -> Result<(Vec<T>, bool)>
+where
+    F: FnMut(Option<&str>, usize) -> Result<(Vec<T>, PageInfo)>,
+{
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page_size = match limit {
+            Some(limit) => (limit - items.len()).min(MAX_PAGE_SIZE),
+            None => MAX_PAGE_SIZE,
+        };
+        let (page, page_info) = fetch_page(cursor.as_deref(), page_size)?;
+        let page_len = page.len();
+        items.extend(page);
+
+        if let Some(limit) = limit {
+            if items.len() >= limit {
+                let truncated = items.len() > limit || page_info.has_next_page;
+                items.truncate(limit);
+                return Ok((items, truncated));
+            }
+        }
+        if page_len == 0 || !page_info.has_next_page {
+            return Ok((items, false));
+        }
+        cursor = page_info.end_cursor;
+        if cursor.is_none() {
+            return Ok((items, false));
+        }
+    }
+}
+
 /// GraphQL client for Sui network queries.
 #[derive(Clone)]
 pub struct GraphQLClient {
     endpoint: String,
+    /// Shared connection pool (see [`crate::http`]); `timeout` is applied per request.
     agent: ureq::Agent,
+    timeout: Duration,
     circuit_state: Arc<GraphQLCircuitState>,
     request_count: Arc<AtomicU64>,
+    retry_policy: GraphQLRetryPolicy,
+    retry_state: Arc<GraphQLRetryState>,
+    /// Records or serves this client's queries (see [`crate::cassette`]).
+    cassette: Option<Arc<Cassette>>,
 }
 
 #[derive(Debug, Default)]
@@ -68,6 +183,133 @@ struct GraphQLCircuitState {
     open_until_epoch_ms: AtomicU64,
 }
 
+/// Retry policy for transient GraphQL HTTP failures.
+///
+/// Transport errors, `429 Too Many Requests` and `500`/`502`/`503`/`504` responses are
+/// retried with exponential backoff; other HTTP errors and query-level (`errors`)
+/// responses are not. The defaults can be overridden with `SUI_GRAPHQL_MAX_ATTEMPTS`,
+/// `SUI_GRAPHQL_RETRY_BACKOFF_MS` and `SUI_GRAPHQL_RETRY_MAX_BACKOFF_MS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphQLRetryPolicy {
+    /// Total attempts per request, including the first (1 disables retries).
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles per retry.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Randomize each delay within `[delay / 2, delay]` so concurrent callers spread out.
+    pub jitter: bool,
+    /// Wait for a 429 response's `Retry-After` (capped at `max_retry_after`) instead
+    /// of the backoff when it is longer.
+    pub respect_retry_after: bool,
+    pub max_retry_after: Duration,
+}
+
+impl Default for GraphQLRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(8),
+            jitter: true,
+            respect_retry_after: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+
+impl GraphQLRetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// The default policy with env overrides applied.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            max_attempts: env_var_or("SUI_GRAPHQL_MAX_ATTEMPTS", default.max_attempts).max(1),
+            initial_backoff: Duration::from_millis(env_var_or(
+                "SUI_GRAPHQL_RETRY_BACKOFF_MS",
+                default.initial_backoff.as_millis() as u64,
+            )),
+            max_backoff: Duration::from_millis(env_var_or(
+                "SUI_GRAPHQL_RETRY_MAX_BACKOFF_MS",
+                default.max_backoff.as_millis() as u64,
+            )),
+            ..default
+        }
+    }
+
+    /// Backoff before retry `retry` (1-based), before jitter.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32 << retry.saturating_sub(1).min(16);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Delay before retry `retry`, given the server's `Retry-After` if any.
+    fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = self.backoff(retry);
+        let backoff = if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(jitter_fraction())
+        } else {
+            backoff
+        };
+        match retry_after {
+            Some(wait) if self.respect_retry_after => backoff.max(wait.min(self.max_retry_after)),
+            _ => backoff,
+        }
+    }
+}
+
+/// Pseudo-random value in `[0, 1)`; good enough to decorrelate retries.
+fn jitter_fraction() -> f64 {
+    use std::hash::BuildHasher;
+    let seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let hash = std::collections::hash_map::RandomState::new().hash_one(seed);
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Parse a `Retry-After` header given in seconds (HTTP-date values are ignored).
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// Retry counters shared by a client and its clones.
+#[derive(Debug, Default)]
+struct GraphQLRetryState {
+    retries: AtomicU64,
+    rate_limited: AtomicU64,
+    exhausted: AtomicU64,
+}
+
+/// Snapshot of a client's retry counters (see [`GraphQLClient::retry_stats`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct GraphQLRetryStats {
+    /// Retries performed (attempts after the first).
+    pub retries: u64,
+    /// Responses that were `429 Too Many Requests`.
+    pub rate_limited: u64,
+    /// Requests that still failed after exhausting their attempts.
+    pub exhausted: u64,
+}
+
+/// A failed HTTP attempt and whether it is worth retrying.
+struct AttemptFailure {
+    error: anyhow::Error,
+    retryable: bool,
+    rate_limited: bool,
+    retry_after: Option<Duration>,
+}
+
 /// Relay-style pagination info from GraphQL responses.
 #[derive(Debug, Clone, Default)]
 pub struct PageInfo {
@@ -330,6 +572,119 @@ pub fn decode_graphql_modules(
     Ok(decoded)
 }
 
+/// Parse a single-module package query result into a package holding only that
+/// module. `header` carries the package `address` and `version`; `pkg` carries the
+/// `module(name:)` and `linkage` fields.
+fn parse_single_module_package(
+    header: &Value,
+    pkg: &Value,
+    address: &str,
+    module_name: &str,
+) -> Result<GraphQLPackage> {
+    let module = pkg
+        .get("module")
+        .filter(|m| !m.is_null())
+        .ok_or_else(|| anyhow!("Module {}::{} not found", address, module_name))?;
+    let module = GraphQLModule {
+        name: module
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or(module_name)
+            .to_string(),
+        bytecode_base64: module
+            .get("bytes")
+            .and_then(|b| b.as_str())
+            .map(|s| s.to_string()),
+    };
+    let linkage = pkg
+        .get("linkage")
+        .and_then(|l| l.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let original_id = entry.get("originalId")?.as_str()?.to_string();
+            let upgraded_id = entry.get("upgradedId")?.as_str()?.to_string();
+            let version = entry.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+            Some(GraphQLLinkage {
+                original_id,
+                upgraded_id,
+                version,
+            })
+        })
+        .collect();
+    Ok(GraphQLPackage {
+        address: header
+            .get("address")
+            .and_then(|a| a.as_str())
+            .unwrap_or(address)
+            .to_string(),
+        version: header.get("version").and_then(|v| v.as_u64()).unwrap_or(1),
+        modules: vec![module],
+        linkage,
+        type_origins: Vec::new(),
+    })
+}
+
+type ModulePageFn = Box<dyn FnMut(Option<&str>) -> Result<(Vec<GraphQLModule>, PageInfo)> + Send>;
+
+/// Iterator over a package's modules that fetches one GraphQL page at a time.
+///
+/// Yields decoded `(name, bytecode)` pairs, holding at most one page of modules in
+/// memory. Iteration stops after the first error.
+pub struct PackageModuleStream {
+    package_id: String,
+    fetch_page: ModulePageFn,
+    cursor: Option<String>,
+    buffered: std::collections::VecDeque<GraphQLModule>,
+    done: bool,
+}
+
+impl PackageModuleStream {
+    fn new(package_id: &str, fetch_page: ModulePageFn) -> Self {
+        Self {
+            package_id: package_id.to_string(),
+            fetch_page,
+            cursor: None,
+            buffered: std::collections::VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn fetch_next_page(&mut self) -> Result<()> {
+        let (modules, page_info) = (self.fetch_page)(self.cursor.as_deref())?;
+        match page_info.end_cursor {
+            Some(cursor) if page_info.has_next_page => self.cursor = Some(cursor),
+            _ => self.done = true,
+        }
+        self.buffered.extend(modules);
+        Ok(())
+    }
+}
+
+impl Iterator for PackageModuleStream {
+    type Item = Result<(String, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.buffered.is_empty() {
+            if self.done {
+                return None;
+            }
+            if let Err(err) = self.fetch_next_page() {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+        let module = self.buffered.pop_front()?;
+        let decoded = decode_graphql_modules(&self.package_id, std::slice::from_ref(&module))
+            .map(|mut modules| modules.remove(0));
+        if decoded.is_err() {
+            self.done = true;
+            self.buffered.clear();
+        }
+        Some(decoded)
+    }
+}
+
 /// Dynamic field information returned from GraphQL.
 ///
 /// Dynamic fields store child objects under a parent object using a key.
@@ -354,6 +709,41 @@ pub struct DynamicFieldInfo {
     pub value_bcs: Option<String>,
 }
 
+/// Dynamic fields of a parent, as returned by [`GraphQLClient::list_dynamic_fields`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DynamicFieldListing {
+    pub fields: Vec<DynamicFieldInfo>,
+    /// The listing stopped at the requested limit before the parent's fields ran out.
+    pub truncated: bool,
+    /// Listings of the tables/bags stored as field values, keyed by collection ID
+    /// (filled by [`GraphQLClient::list_dynamic_fields_nested`]).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub nested: BTreeMap<String, DynamicFieldListing>,
+}
+
+/// Framework collections that keep their entries as dynamic fields of a leading `UID`
+/// (`TableVec` wraps a `Table`, so its first 32 bytes are that table's ID).
+const NESTED_COLLECTION_TYPES: &[(&str, &str)] = &[
+    ("table", "Table"),
+    ("bag", "Bag"),
+    ("object_table", "ObjectTable"),
+    ("object_bag", "ObjectBag"),
+    ("linked_table", "LinkedTable"),
+    ("table_vec", "TableVec"),
+];
+
+fn is_nested_collection_type(type_repr: &str) -> bool {
+    let base = type_repr.split('<').next().unwrap_or(type_repr);
+    let mut parts = base.split("::");
+    let (Some(address), Some(module), Some(name), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return false;
+    };
+    AccountAddress::from_hex_literal(address).is_ok_and(|addr| addr == AccountAddress::TWO)
+        && NESTED_COLLECTION_TYPES.contains(&(module, name))
+}
+
 fn parse_dynamic_field_info(node: &Value) -> Option<DynamicFieldInfo> {
     let name = node.get("name")?;
     let value = node.get("value")?;
@@ -440,6 +830,17 @@ impl DynamicFieldInfo {
             .as_ref()
             .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
     }
+
+    /// ID of the `Table`/`Bag` (or object, linked and vector variants) stored as
+    /// this field's value. Its entries are dynamic fields of that ID.
+    pub fn nested_collection_id(&self) -> Option<String> {
+        if !is_nested_collection_type(self.value_type.as_deref()?) {
+            return None;
+        }
+        let bytes = self.decode_value_bcs()?;
+        let uid = bytes.get(..AccountAddress::LENGTH)?;
+        Some(format!("0x{}", hex::encode(uid)))
+    }
 }
 
 /// Full transaction block data with PTB details.
@@ -467,6 +868,16 @@ pub struct GraphQLTransactionMeta {
     pub timestamp_ms: Option<u64>,
 }
 
+/// Epoch context of a checkpoint: when it was produced and the epoch it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphQLCheckpointEpoch {
+    pub checkpoint: u64,
+    pub timestamp_ms: Option<u64>,
+    pub epoch: Option<u64>,
+    pub protocol_version: Option<u64>,
+    pub reference_gas_price: Option<u64>,
+}
+
 /// Transaction input (Pure, Object, SharedObject, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GraphQLTransactionInput {
@@ -589,13 +1000,6 @@ impl GraphQLClient {
         )
     }
 
-    fn build_agent(timeout: Duration, connect_timeout: Duration) -> ureq::Agent {
-        ureq::AgentBuilder::new()
-            .timeout(timeout)
-            .timeout_connect(connect_timeout)
-            .build()
-    }
-
     fn circuit_breaker_enabled() -> bool {
         !matches!(
             std::env::var("SUI_GRAPHQL_CIRCUIT_BREAKER")
@@ -690,6 +1094,11 @@ impl GraphQLClient {
         Self::new("https://graphql.testnet.sui.io/graphql")
     }
 
+    /// Create a client for `network`'s GraphQL endpoint.
+    pub fn for_network(network: &Network) -> Self {
+        Self::new(network.graphql_url())
+    }
+
     /// Create a client with a custom endpoint.
     pub fn new(endpoint: &str) -> Self {
         let (timeout, connect_timeout) = Self::default_timeouts();
@@ -697,60 +1106,187 @@ impl GraphQLClient {
     }
 
     /// Create a client with explicit timeouts.
+    ///
+    /// Connections are pooled process-wide; a `connect_timeout` other than the shared
+    /// agent's gets a dedicated agent.
     pub fn with_timeouts(endpoint: &str, timeout: Duration, connect_timeout: Duration) -> Self {
         Self {
             endpoint: endpoint.to_string(),
-            agent: Self::build_agent(timeout, connect_timeout),
+            agent: crate::http::agent_with_connect_timeout(connect_timeout),
+            timeout,
             circuit_state: Arc::new(GraphQLCircuitState::default()),
             request_count: Arc::new(AtomicU64::new(0)),
+            retry_policy: GraphQLRetryPolicy::from_env(),
+            retry_state: Arc::new(GraphQLRetryState::default()),
+            cassette: None,
         }
     }
 
+    /// Use `policy` for transient failures.
+    ///
+    /// Retry counters stay shared with the client this was cloned from, so
+    /// `client.clone().with_retry_policy(..)` works as a per-call override.
+    pub fn with_retry_policy(mut self, policy: GraphQLRetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// The retry policy in effect.
+    pub fn retry_policy(&self) -> GraphQLRetryPolicy {
+        self.retry_policy
+    }
+
+    /// Record every query to `cassette`, or serve queries from it in playback mode.
+    /// Clones of this client share the cassette.
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// The cassette this client records to or plays back from, if any.
+    pub fn cassette(&self) -> Option<&Arc<Cassette>> {
+        self.cassette.as_ref()
+    }
+
+    /// Retry counters accumulated by this client and its clones.
+    pub fn retry_stats(&self) -> GraphQLRetryStats {
+        GraphQLRetryStats {
+            retries: self.retry_state.retries.load(Ordering::Relaxed),
+            rate_limited: self.retry_state.rate_limited.load(Ordering::Relaxed),
+            exhausted: self.retry_state.exhausted.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Whether `err` means the endpoint could not be reached (transport/HTTP failure or
+    /// an open circuit) rather than a query-level error such as a missing object.
+    ///
+    /// The client raises these as [`TransportError::EndpointUnreachable`].
+    pub fn is_unreachable_error(err: &anyhow::Error) -> bool {
+        matches!(
+            TransportError::find(err),
+            Some(TransportError::EndpointUnreachable { .. })
+        )
+    }
+
+    /// A [`TransportError::EndpointUnreachable`] for this client's endpoint.
+    fn unreachable(&self, detail: String) -> anyhow::Error {
+        anyhow::Error::new(TransportError::EndpointUnreachable {
+            endpoint: self.endpoint.clone(),
+            detail,
+        })
+    }
+
     /// Total number of GraphQL HTTP requests made through this client.
     pub fn request_count(&self) -> u64 {
         self.request_count.load(Ordering::Relaxed)
     }
 
-    /// Execute a GraphQL query.
-    fn query(&self, query: &str, variables: Option<Value>) -> Result<Value> {
-        self.request_count.fetch_add(1, Ordering::Relaxed);
-        if Self::circuit_breaker_enabled() {
-            if let Some(remaining_ms) = self.circuit_open_remaining_ms() {
-                return Err(anyhow!(
-                    "GraphQL circuit open ({}ms remaining)",
-                    remaining_ms
-                ));
+    /// POST a query body, retrying transient failures per the retry policy.
+    fn post_query(&self, body: &Value) -> Result<Value> {
+        let policy = self.retry_policy;
+        let mut attempt = 1u32;
+        loop {
+            let failure = match self.post_query_once(body) {
+                Ok(response) => return Ok(response),
+                Err(failure) => failure,
+            };
+            if failure.rate_limited {
+                self.retry_state
+                    .rate_limited
+                    .fetch_add(1, Ordering::Relaxed);
             }
+            let circuit_open =
+                Self::circuit_breaker_enabled() && self.circuit_open_remaining_ms().is_some();
+            if !failure.retryable || circuit_open || attempt >= policy.max_attempts.max(1) {
+                if failure.retryable && attempt > 1 {
+                    self.retry_state.exhausted.fetch_add(1, Ordering::Relaxed);
+                    return Err(failure
+                        .error
+                        .context(format!("gave up after {} attempts", attempt)));
+                }
+                return Err(failure.error);
+            }
+            let delay = policy.delay(attempt, failure.retry_after);
+            self.retry_state.retries.fetch_add(1, Ordering::Relaxed);
+            std::thread::sleep(delay);
+            attempt += 1;
         }
+    }
 
-        let body = serde_json::json!({
-            "query": query,
-            "variables": variables.unwrap_or(Value::Null)
-        });
-
+    /// One POST of a query body, parsing the JSON response and tracking the circuit breaker.
+    fn post_query_once(&self, body: &Value) -> std::result::Result<Value, AttemptFailure> {
         let response = self
             .agent
             .post(&self.endpoint)
+            .timeout(self.timeout)
             .set("Content-Type", "application/json")
-            .send_json(&body)
+            .send_json(body)
             .map_err(|e| {
                 if Self::circuit_breaker_enabled() {
                     self.record_circuit_error(&e.to_string());
                 }
-                anyhow!("GraphQL request failed: {}", e)
+                let (retryable, rate_limited, retry_after) = match &e {
+                    ureq::Error::Status(429, response) => (
+                        true,
+                        true,
+                        response.header("Retry-After").and_then(parse_retry_after),
+                    ),
+                    ureq::Error::Status(code, _) => {
+                        (matches!(code, 500 | 502 | 503 | 504), false, None)
+                    }
+                    ureq::Error::Transport(_) => (true, false, None),
+                };
+                AttemptFailure {
+                    error: self.unreachable(format!("GraphQL request failed: {}", e)),
+                    retryable,
+                    rate_limited,
+                    retry_after,
+                }
             })?;
 
         let response: Value = response.into_json().map_err(|e| {
             if Self::circuit_breaker_enabled() {
                 self.record_circuit_error(&e.to_string());
             }
-            anyhow!("Failed to parse GraphQL response: {}", e)
+            AttemptFailure {
+                error: anyhow!("Failed to parse GraphQL response: {}", e),
+                retryable: false,
+                rate_limited: false,
+                retry_after: None,
+            }
         })?;
 
         if Self::circuit_breaker_enabled() {
             self.record_circuit_success();
         }
 
+        Ok(response)
+    }
+
+    /// Execute a GraphQL query.
+    fn query(&self, query: &str, variables: Option<Value>) -> Result<Value> {
+        self.request_count.fetch_add(1, Ordering::Relaxed);
+        if Self::circuit_breaker_enabled() {
+            if let Some(remaining_ms) = self.circuit_open_remaining_ms() {
+                return Err(self.unreachable(format!(
+                    "GraphQL circuit open ({}ms remaining)",
+                    remaining_ms
+                )));
+            }
+        }
+
+        let body = serde_json::json!({
+            "query": query,
+            "variables": variables.unwrap_or(Value::Null)
+        });
+
+        let response = crate::cassette::json_through(
+            self.cassette.as_deref(),
+            "graphql",
+            || body.clone(),
+            || self.post_query(&body),
+        )?;
+
         // Check for GraphQL errors
         if let Some(errors) = response.get("errors") {
             if let Some(arr) = errors.as_array() {
@@ -818,7 +1354,11 @@ impl GraphQLClient {
             .ok_or_else(|| anyhow!("Object not found: {}", address))?;
 
         if obj.is_null() {
-            return Err(anyhow!("Object not found: {}", address));
+            return Err(anyhow::Error::new(TransportError::ObjectNotFound {
+                object_id: address.to_string(),
+                version: None,
+            })
+            .context(format!("Object not found: {}", address)));
         }
 
         // Parse owner
@@ -1003,11 +1543,14 @@ impl GraphQLClient {
             .ok_or_else(|| anyhow!("Object not found at checkpoint {}: {}", checkpoint, address))?;
 
         if obj.is_null() {
-            return Err(anyhow!(
+            return Err(anyhow::Error::new(TransportError::ObjectNotFound {
+                object_id: address.to_string(),
+                version: None,
+            })
+            .context(format!(
                 "Object not found at checkpoint {}: {}",
-                checkpoint,
-                address
-            ));
+                checkpoint, address
+            )));
         }
 
         let owner = self.parse_owner(obj.get("owner"));
@@ -1332,6 +1875,158 @@ impl GraphQLClient {
         })
     }
 
+    /// Stream a package's modules one GraphQL page at a time.
+    ///
+    /// Unlike [`Self::fetch_package`], which collects every module (plus linkage and
+    /// type origins) up front, this keeps memory bounded to a single page so callers
+    /// can decode modules of very large packages while the rest are still fetched.
+    pub fn stream_package_modules(&self, address: &str) -> PackageModuleStream {
+        let client = self.clone();
+        let package_id = address.to_string();
+        PackageModuleStream::new(
+            address,
+            Box::new(move |cursor| client.fetch_package_module_page(&package_id, cursor)),
+        )
+    }
+
+    fn fetch_package_module_page(
+        &self,
+        address: &str,
+        cursor: Option<&str>,
+    ) -> Result<(Vec<GraphQLModule>, PageInfo)> {
+        let query = r#"
+            query GetPackageModules($address: SuiAddress!, $after: String) {
+                object(address: $address) {
+                    asMovePackage {
+                        modules(first: 50, after: $after) {
+                            nodes {
+                                name
+                                bytes
+                            }
+                            pageInfo {
+                                hasNextPage
+                                endCursor
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+        let variables = serde_json::json!({
+            "address": address,
+            "after": cursor,
+        });
+        let data = self.query(query, Some(variables))?;
+        let obj = data
+            .get("object")
+            .filter(|obj| !obj.is_null())
+            .ok_or_else(|| anyhow!("Package not found: {}", address))?;
+        let modules_data = obj
+            .get("asMovePackage")
+            .filter(|pkg| !pkg.is_null())
+            .ok_or_else(|| anyhow!("Object is not a package: {}", address))?
+            .get("modules")
+            .ok_or_else(|| anyhow!("No modules field in package: {}", address))?;
+        let modules = modules_data
+            .get("nodes")
+            .and_then(|n| n.as_array())
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .map(|m| GraphQLModule {
+                        name: m
+                            .get("name")
+                            .and_then(|n| n.as_str())
+                            .unwrap_or("")
+                            .to_string(),
+                        bytecode_base64: m
+                            .get("bytes")
+                            .and_then(|b| b.as_str())
+                            .map(|s| s.to_string()),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok((modules, PageInfo::from_value(modules_data.get("pageInfo"))))
+    }
+
+    /// Fetch a single module of a package by name.
+    ///
+    /// Cheaper than [`Self::fetch_package`] for wide packages when only one module is
+    /// needed. The returned package holds just that module, plus the package's
+    /// linkage table so upgraded dependencies resolve to their storage ids; type
+    /// origins are not fetched.
+    pub fn fetch_package_module(&self, address: &str, module_name: &str) -> Result<GraphQLPackage> {
+        let query = r#"
+            query GetPackageModule($address: SuiAddress!, $name: String!) {
+                object(address: $address) {
+                    address
+                    version
+                    asMovePackage {
+                        module(name: $name) {
+                            name
+                            bytes
+                        }
+                        linkage { originalId upgradedId version }
+                    }
+                }
+            }
+        "#;
+        let variables = serde_json::json!({
+            "address": address,
+            "name": module_name,
+        });
+        let data = self.query(query, Some(variables))?;
+        let obj = data
+            .get("object")
+            .filter(|obj| !obj.is_null())
+            .ok_or_else(|| anyhow!("Package not found: {}", address))?;
+        let pkg = obj
+            .get("asMovePackage")
+            .filter(|pkg| !pkg.is_null())
+            .ok_or_else(|| anyhow!("Object is not a package: {}", address))?;
+        parse_single_module_package(obj, pkg, address, module_name)
+    }
+
+    /// Fetch a single module of a package, with its linkage table, as of a checkpoint.
+    pub fn fetch_package_module_at_checkpoint(
+        &self,
+        address: &str,
+        module_name: &str,
+        checkpoint: u64,
+    ) -> Result<GraphQLPackage> {
+        let query = r#"
+            query GetPackageModuleAtCheckpoint($address: SuiAddress!, $name: String!, $checkpoint: UInt53!) {
+                package(address: $address, atCheckpoint: $checkpoint) {
+                    address
+                    version
+                    module(name: $name) {
+                        name
+                        bytes
+                    }
+                    linkage { originalId upgradedId version }
+                }
+            }
+        "#;
+        let variables = serde_json::json!({
+            "address": address,
+            "name": module_name,
+            "checkpoint": checkpoint,
+        });
+        let data = self.query(query, Some(variables))?;
+        let pkg = data
+            .get("package")
+            .filter(|pkg| !pkg.is_null())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Package not found at checkpoint {}: {}",
+                    checkpoint,
+                    address
+                )
+            })?;
+        parse_single_module_package(pkg, pkg, address, module_name)
+    }
+
     /// Get the upgrade chain for a package, from current version to latest.
     ///
     /// Returns a list of (address, version) pairs representing all upgrades
@@ -1821,6 +2516,33 @@ impl GraphQLClient {
         })
     }
 
+    /// Fetch the kind of a transaction (the GraphQL `__typename` of its `kind`, e.g.
+    /// `ProgrammableTransaction` or `ConsensusCommitPrologueTransaction`).
+    pub fn fetch_transaction_kind(&self, digest: &str) -> Result<String> {
+        let query = r#"
+            query GetTransactionKind($digest: String!) {
+                transaction(digest: $digest) {
+                    kind { __typename }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "digest": digest
+        });
+
+        let data = self.query(query, Some(variables))?;
+
+        data.get("transaction")
+            .filter(|tx| !tx.is_null())
+            .ok_or_else(|| anyhow!("Transaction not found: {}", digest))?
+            .get("kind")
+            .and_then(|k| k.get("__typename"))
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string())
+            .ok_or_else(|| anyhow!("Transaction kind missing for {}", digest))
+    }
+
     /// Fetch minimal transaction metadata (checkpoint + timestamp) by digest.
     pub fn fetch_transaction_meta(&self, digest: &str) -> Result<GraphQLTransactionMeta> {
         let query = r#"
@@ -1878,6 +2600,113 @@ impl GraphQLClient {
         })
     }
 
+    /// Fetch a checkpoint's timestamp (ms since epoch), or `None` if the checkpoint is unknown.
+    pub fn fetch_checkpoint_timestamp_ms(&self, checkpoint: u64) -> Result<Option<u64>> {
+        let query = r#"
+            query GetCheckpointTimestamp($checkpoint: UInt53!) {
+                checkpoint(sequenceNumber: $checkpoint) {
+                    timestamp
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "checkpoint": checkpoint
+        });
+
+        let data = self.query(query, Some(variables))?;
+
+        Ok(data
+            .get("checkpoint")
+            .and_then(|c| c.get("timestamp"))
+            .and_then(|t| t.as_str())
+            .and_then(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .ok()
+                    .map(|dt| dt.timestamp_millis() as u64)
+            }))
+    }
+
+    /// Fetch the protocol version that was active during `epoch`, or `None` if the epoch is unknown.
+    pub fn fetch_epoch_protocol_version(&self, epoch: u64) -> Result<Option<u64>> {
+        let query = r#"
+            query GetEpochProtocolVersion($epoch: UInt53!) {
+                epoch(epochId: $epoch) {
+                    protocolConfigs {
+                        protocolVersion
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "epoch": epoch
+        });
+
+        let data = self.query(query, Some(variables))?;
+
+        Ok(data
+            .get("epoch")
+            .and_then(|e| e.get("protocolConfigs"))
+            .and_then(|pc| pc.get("protocolVersion"))
+            .and_then(|v| v.as_u64()))
+    }
+
+    /// Fetch the timestamp, epoch, protocol version and reference gas price of
+    /// `checkpoint` (the latest checkpoint when `None`), or `None` if the checkpoint is
+    /// unknown.
+    pub fn fetch_checkpoint_epoch(
+        &self,
+        checkpoint: Option<u64>,
+    ) -> Result<Option<GraphQLCheckpointEpoch>> {
+        let query = r#"
+            query GetCheckpointEpoch($checkpoint: UInt53) {
+                checkpoint(sequenceNumber: $checkpoint) {
+                    sequenceNumber
+                    timestamp
+                    epoch {
+                        epochId
+                        referenceGasPrice
+                        protocolConfigs { protocolVersion }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "checkpoint": checkpoint
+        });
+
+        let data = self.query(query, Some(variables))?;
+
+        let Some(cp) = data.get("checkpoint").filter(|cp| !cp.is_null()) else {
+            return Ok(None);
+        };
+        let Some(sequence_number) = cp.get("sequenceNumber").and_then(|v| v.as_u64()) else {
+            return Ok(None);
+        };
+        let epoch = cp.get("epoch");
+        Ok(Some(GraphQLCheckpointEpoch {
+            checkpoint: sequence_number,
+            timestamp_ms: cp.get("timestamp").and_then(|t| t.as_str()).and_then(|s| {
+                chrono::DateTime::parse_from_rfc3339(s)
+                    .ok()
+                    .map(|dt| dt.timestamp_millis() as u64)
+            }),
+            epoch: epoch
+                .and_then(|e| e.get("epochId"))
+                .and_then(|v| v.as_u64()),
+            protocol_version: epoch
+                .and_then(|e| e.get("protocolConfigs"))
+                .and_then(|pc| pc.get("protocolVersion"))
+                .and_then(|v| v.as_u64()),
+            reference_gas_price: epoch
+                .and_then(|e| e.get("referenceGasPrice"))
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse().ok()),
+        }))
+    }
+
     /// Parse transaction from transactionJson field (has complete type arguments).
     fn parse_transaction_json(
         &self,
@@ -3076,6 +3905,65 @@ impl GraphQLClient {
         paginator.collect_all()
     }
 
+    /// List every dynamic field of `parent_address`, optionally at `checkpoint`.
+    ///
+    /// Pages through the parent's fields internally. With `limit` set, stops
+    /// once that many entries are collected and reports whether more remained.
+    pub fn list_dynamic_fields(
+        &self,
+        parent_address: &str,
+        checkpoint: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<DynamicFieldListing> {
+        if limit == Some(0) {
+            return Ok(DynamicFieldListing::default());
+        }
+        let (fields, truncated) =
+            collect_pages_up_to(limit, |cursor, page_size| match checkpoint {
+                Some(cp) => self.fetch_dynamic_fields_page_at_checkpoint(
+                    parent_address,
+                    cursor,
+                    page_size,
+                    cp,
+                ),
+                None => self.fetch_dynamic_fields_page(parent_address, cursor, page_size),
+            })?;
+        Ok(DynamicFieldListing {
+            fields,
+            truncated,
+            nested: BTreeMap::new(),
+        })
+    }
+
+    /// [`Self::list_dynamic_fields`], then list the entries of every table/bag stored
+    /// as a field value, descending at most `max_depth` levels below `parent_address`.
+    ///
+    /// `limit` applies to each listing separately.
+    pub fn list_dynamic_fields_nested(
+        &self,
+        parent_address: &str,
+        checkpoint: Option<u64>,
+        limit: Option<usize>,
+        max_depth: usize,
+    ) -> Result<DynamicFieldListing> {
+        let mut listing = self.list_dynamic_fields(parent_address, checkpoint, limit)?;
+        if max_depth == 0 {
+            return Ok(listing);
+        }
+        let collection_ids: Vec<String> = listing
+            .fields
+            .iter()
+            .filter_map(DynamicFieldInfo::nested_collection_id)
+            .collect();
+        for collection_id in collection_ids {
+            let nested = self
+                .list_dynamic_fields_nested(&collection_id, checkpoint, limit, max_depth - 1)
+                .map_err(|e| e.context(format!("list nested collection {}", collection_id)))?;
+            listing.nested.insert(collection_id, nested);
+        }
+        Ok(listing)
+    }
+
     /// Fetch a single dynamic field by name (type + BCS key).
     ///
     /// This is useful when the computed child ID doesn't match on-chain (e.g. upgrades),
@@ -3202,9 +4090,6 @@ impl GraphQLClient {
         Ok(node.and_then(parse_dynamic_field_info))
     }
 
-    /// Find a dynamic field by key BCS, scanning pages until a match is found.
-    ///
-    /// This avoids full enumeration when the key is known but the type is not.
     fn b64_matches_bytes(encoded: &str, expected: &[u8]) -> bool {
         if let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(encoded) {
             return decoded == expected;
@@ -3215,6 +4100,10 @@ impl GraphQLClient {
         false
     }
 
+    /// Find a dynamic field by key BCS, scanning pages until a match is found.
+    ///
+    /// This avoids full enumeration when the key is known but the type is not.
+    /// At most `limit` fields are scanned.
     pub fn find_dynamic_field_by_bcs(
         &self,
         parent_address: &str,
@@ -3264,6 +4153,30 @@ impl GraphQLClient {
         Ok(None)
     }
 
+    /// Find a dynamic field by key BCS, following cursors page by page.
+    ///
+    /// Stops when the key is found, the parent's fields are exhausted, or
+    /// `max_pages` pages have been scanned (see [`dynamic_field_scan_max_pages`]
+    /// for the env-configurable default). A scan cut short by the bound is
+    /// reported as [`DynamicFieldScan::Truncated`], not as absent.
+    pub fn scan_dynamic_field_by_bcs(
+        &self,
+        parent_address: &str,
+        key_bcs: &[u8],
+        checkpoint: Option<u64>,
+        max_pages: usize,
+    ) -> Result<DynamicFieldScan> {
+        scan_pages_for_key(key_bcs, max_pages, |cursor| match checkpoint {
+            Some(cp) => self.fetch_dynamic_fields_page_at_checkpoint(
+                parent_address,
+                cursor,
+                MAX_PAGE_SIZE,
+                cp,
+            ),
+            None => self.fetch_dynamic_fields_page(parent_address, cursor, MAX_PAGE_SIZE),
+        })
+    }
+
     /// Fetch a single page of dynamic fields (internal helper).
     fn fetch_dynamic_fields_page(
         &self,
@@ -3462,6 +4375,399 @@ impl GraphQLClient {
 mod tests {
     use super::*;
 
+    fn df_item(name: &[u8]) -> DynamicFieldInfo {
+        DynamicFieldInfo {
+            name_type: "u64".to_string(),
+            name_bcs: Some(base64::engine::general_purpose::STANDARD.encode(name)),
+            name_json: None,
+            object_id: None,
+            version: None,
+            digest: None,
+            value_type: None,
+            value_bcs: None,
+        }
+    }
+
+    fn df_page(names: &[u8], next: Option<&str>) -> (Vec<DynamicFieldInfo>, PageInfo) {
+        let items = names.iter().map(|n| df_item(&[*n])).collect();
+        let page_info = PageInfo {
+            has_next_page: next.is_some(),
+            has_previous_page: false,
+            start_cursor: None,
+            end_cursor: next.map(str::to_string),
+        };
+        (items, page_info)
+    }
+
+    /// Serve `responses` (raw HTTP) to consecutive connections on a local port.
+    fn serve_http(responses: Vec<&'static str>) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        format!("http://{}/graphql", addr)
+    }
+
+    fn fast_retries(max_attempts: u32) -> GraphQLRetryPolicy {
+        GraphQLRetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            jitter: false,
+            ..GraphQLRetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_retry_delay_backoff_jitter_and_retry_after() {
+        let policy = GraphQLRetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(300),
+            jitter: false,
+            ..GraphQLRetryPolicy::default()
+        };
+        assert_eq!(policy.delay(1, None), Duration::from_millis(100));
+        assert_eq!(policy.delay(2, None), Duration::from_millis(200));
+        assert_eq!(policy.delay(3, None), Duration::from_millis(300));
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(2))),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(600))),
+            policy.max_retry_after
+        );
+        let ignore = GraphQLRetryPolicy {
+            respect_retry_after: false,
+            ..policy
+        };
+        assert_eq!(
+            ignore.delay(1, Some(Duration::from_secs(2))),
+            Duration::from_millis(100)
+        );
+
+        let jittered = GraphQLRetryPolicy {
+            jitter: true,
+            ..policy
+        };
+        for _ in 0..20 {
+            let delay = jittered.delay(2, None);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+        }
+
+        assert_eq!(parse_retry_after(" 3 "), Some(Duration::from_secs(3)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+    }
+
+    #[test]
+    fn test_post_query_retries_rate_limit_then_succeeds() {
+        let endpoint = serve_http(vec![
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 18\r\nConnection: close\r\n\r\n{\"data\":{\"ok\":1}}\n",
+        ]);
+        let client = GraphQLClient::new(&endpoint).with_retry_policy(fast_retries(3));
+        let data = client.post_query(&serde_json::json!({})).unwrap();
+        assert_eq!(data["data"]["ok"], 1);
+        assert_eq!(
+            client.retry_stats(),
+            GraphQLRetryStats {
+                retries: 2,
+                rate_limited: 1,
+                exhausted: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_cassette_is_scoped_to_the_client_it_is_attached_to() {
+        let endpoint = serve_http(vec![
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 18\r\nConnection: close\r\n\r\n{\"data\":{\"ok\":1}}\n",
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 18\r\nConnection: close\r\n\r\n{\"data\":{\"ok\":2}}\n",
+        ]);
+        let path = std::env::temp_dir().join(format!(
+            "sui-transport-graphql-cassette-{}.json",
+            std::process::id()
+        ));
+        let recorder = Arc::new(Cassette::record(&path, Value::Null));
+        let recording = GraphQLClient::new(&endpoint).with_cassette(Arc::clone(&recorder));
+        assert_eq!(recording.raw_query("{ ok }").unwrap()["ok"], 1);
+        // A client without the cassette in the same process is not recorded.
+        assert_eq!(
+            GraphQLClient::new(&endpoint).raw_query("{ ok }").unwrap()["ok"],
+            2
+        );
+        assert_eq!(recorder.save().unwrap(), 1);
+
+        let playback = Arc::new(Cassette::load(&path).unwrap());
+        std::fs::remove_file(&path).ok();
+        let client = GraphQLClient::new("http://127.0.0.1:9/graphql").with_cassette(playback);
+        assert_eq!(client.raw_query("{ ok }").unwrap()["ok"], 1);
+    }
+
+    #[test]
+    fn test_post_query_does_not_retry_client_errors_and_counts_exhaustion() {
+        let endpoint = serve_http(vec![
+            "HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let client = GraphQLClient::new(&endpoint).with_retry_policy(fast_retries(5));
+        let err = client.post_query(&serde_json::json!({})).unwrap_err();
+        assert!(GraphQLClient::is_unreachable_error(&err));
+        assert_eq!(client.retry_stats().retries, 0);
+
+        // Per-call override on a clone shares the counters.
+        let err = client
+            .clone()
+            .with_retry_policy(fast_retries(2))
+            .post_query(&serde_json::json!({}))
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("gave up after 2 attempts"));
+        assert_eq!(
+            client.retry_stats(),
+            GraphQLRetryStats {
+                retries: 1,
+                rate_limited: 0,
+                exhausted: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fetch_checkpoint_epoch_reads_epoch_context() {
+        fn ok(body: &str) -> &'static str {
+            Box::leak(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .into_boxed_str(),
+            )
+        }
+        let endpoint = serve_http(vec![
+            ok(
+                r#"{"data":{"checkpoint":{"sequenceNumber":1000,"timestamp":"2024-01-01T00:00:00Z","epoch":{"epochId":300,"referenceGasPrice":"750","protocolConfigs":{"protocolVersion":42}}}}}"#,
+            ),
+            ok(r#"{"data":{"checkpoint":null}}"#),
+        ]);
+        let client = GraphQLClient::new(&endpoint).with_retry_policy(fast_retries(1));
+        assert_eq!(
+            client.fetch_checkpoint_epoch(Some(1000)).unwrap(),
+            Some(GraphQLCheckpointEpoch {
+                checkpoint: 1000,
+                timestamp_ms: Some(1_704_067_200_000),
+                epoch: Some(300),
+                protocol_version: Some(42),
+                reference_gas_price: Some(750),
+            })
+        );
+        assert_eq!(
+            client.fetch_checkpoint_epoch(Some(u64::MAX >> 11)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_unreachable_error_ignores_query_errors() {
+        let client = GraphQLClient::new("http://127.0.0.1:9/graphql");
+        let transport = client.unreachable("GraphQL request failed: connection refused".into());
+        assert!(GraphQLClient::is_unreachable_error(
+            &transport.context("fetch package")
+        ));
+        // Only the typed error counts, not a message that merely looks like one.
+        assert!(!GraphQLClient::is_unreachable_error(&anyhow!(
+            "GraphQL request failed: connection refused"
+        )));
+        assert!(!GraphQLClient::is_unreachable_error(&anyhow!(
+            "GraphQL error: object not found"
+        )));
+    }
+
+    fn module_page(names: &[&str], next: Option<&str>) -> (Vec<GraphQLModule>, PageInfo) {
+        let modules = names
+            .iter()
+            .map(|name| GraphQLModule {
+                name: name.to_string(),
+                bytecode_base64: Some(base64::engine::general_purpose::STANDARD.encode(name)),
+            })
+            .collect();
+        let page_info = PageInfo {
+            has_next_page: next.is_some(),
+            has_previous_page: false,
+            start_cursor: None,
+            end_cursor: next.map(str::to_string),
+        };
+        (modules, page_info)
+    }
+
+    #[test]
+    fn test_package_module_stream_fetches_pages_lazily() {
+        let fetched = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fetched);
+        let mut stream = PackageModuleStream::new(
+            "0xabc",
+            Box::new(move |cursor| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Ok(match cursor {
+                    None => module_page(&["a", "b"], Some("c1")),
+                    _ => module_page(&["c"], None),
+                })
+            }),
+        );
+        let (name, bytes) = stream.next().unwrap().unwrap();
+        assert_eq!((name.as_str(), bytes.as_slice()), ("a", b"a".as_slice()));
+        assert_eq!(fetched.load(Ordering::Relaxed), 1);
+        let rest: Vec<String> = stream.map(|m| m.unwrap().0).collect();
+        assert_eq!(rest, vec!["b", "c"]);
+        assert_eq!(fetched.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_package_module_stream_stops_after_error() {
+        let mut stream = PackageModuleStream::new(
+            "0xabc",
+            Box::new(|cursor| match cursor {
+                None => Ok(module_page(&["a"], Some("c1"))),
+                _ => Err(anyhow!("GraphQL request failed: timeout")),
+            }),
+        );
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn test_single_module_package_keeps_linkage() {
+        let obj = serde_json::json!({
+            "address": "0xb2",
+            "version": 3,
+            "asMovePackage": {
+                "module": { "name": "pool", "bytes": "AAEC" },
+                "linkage": [
+                    { "originalId": "0xc1", "upgradedId": "0xc3", "version": 2 },
+                    { "originalId": "0xd1" }
+                ]
+            }
+        });
+        let pkg = parse_single_module_package(&obj, &obj["asMovePackage"], "0xb1", "pool").unwrap();
+        assert_eq!(pkg.address, "0xb2");
+        assert_eq!(pkg.version, 3);
+        assert_eq!(pkg.modules.len(), 1);
+        assert_eq!(pkg.modules[0].name, "pool");
+        assert_eq!(pkg.modules[0].bytecode_base64.as_deref(), Some("AAEC"));
+        assert_eq!(pkg.linkage.len(), 1);
+        assert_eq!(pkg.linkage[0].original_id, "0xc1");
+        assert_eq!(pkg.linkage[0].upgraded_id, "0xc3");
+
+        let missing = serde_json::json!({ "module": null });
+        let err = parse_single_module_package(&missing, &missing, "0xb1", "pool").unwrap_err();
+        assert!(err.to_string().contains("0xb1::pool"));
+    }
+
+    #[test]
+    fn test_nested_collection_id_reads_leading_uid() {
+        let mut bytes = vec![0xab; 32];
+        bytes.extend_from_slice(&5u64.to_le_bytes());
+        let mut field = df_item(&[1]);
+        field.value_bcs = Some(base64::engine::general_purpose::STANDARD.encode(&bytes));
+
+        field.value_type = Some(
+            "0x0000000000000000000000000000000000000000000000000000000000000002::table::Table<u64, u64>"
+                .to_string(),
+        );
+        assert_eq!(
+            field.nested_collection_id(),
+            Some(format!("0x{}", "ab".repeat(32)))
+        );
+
+        field.value_type = Some("0x2::bag::Bag".to_string());
+        assert!(field.nested_collection_id().is_some());
+
+        field.value_type = Some("0x3::table::Table<u64, u64>".to_string());
+        assert_eq!(field.nested_collection_id(), None);
+        field.value_type = Some("0x2::balance::Balance<0x2::sui::SUI>".to_string());
+        assert_eq!(field.nested_collection_id(), None);
+    }
+
+    #[test]
+    fn test_scan_pages_follows_cursors_until_found() {
+        let mut cursors = Vec::new();
+        let found = scan_pages_for_key(&[7], 10, |cursor| {
+            cursors.push(cursor.map(str::to_string));
+            Ok(match cursor {
+                None => df_page(&[1, 2], Some("c1")),
+                Some("c1") => df_page(&[3, 4], Some("c2")),
+                _ => df_page(&[7], None),
+            })
+        })
+        .unwrap();
+        assert!(matches!(found, DynamicFieldScan::Found(_)));
+        assert_eq!(
+            cursors,
+            vec![None, Some("c1".to_string()), Some("c2".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_scan_pages_respects_max_pages() {
+        let mut pages = 0;
+        let found = scan_pages_for_key(&[9], 3, |_| {
+            pages += 1;
+            Ok(df_page(&[1], Some("more")))
+        })
+        .unwrap();
+        assert!(matches!(
+            found,
+            DynamicFieldScan::Truncated { pages_scanned: 3 }
+        ));
+        assert_eq!(pages, 3);
+
+        let exhausted = scan_pages_for_key(&[9], 3, |_| Ok(df_page(&[1], None))).unwrap();
+        assert!(matches!(exhausted, DynamicFieldScan::Absent));
+    }
+
+    #[test]
+    fn test_collect_pages_up_to_reads_every_page() {
+        let (items, truncated) = collect_pages_up_to(None, |cursor, _| {
+            Ok(match cursor {
+                None => df_page(&[1, 2], Some("c1")),
+                _ => df_page(&[3], None),
+            })
+        })
+        .unwrap();
+        assert_eq!(items.len(), 3);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_collect_pages_up_to_flags_truncation_at_limit() {
+        let mut sizes = Vec::new();
+        let (items, truncated) = collect_pages_up_to(Some(3), |cursor, page_size| {
+            sizes.push(page_size);
+            Ok(match cursor {
+                None => df_page(&[1, 2], Some("c1")),
+                _ => df_page(&[3], Some("c2")),
+            })
+        })
+        .unwrap();
+        assert_eq!(items.len(), 3);
+        assert!(truncated);
+        assert_eq!(sizes, vec![3, 1]);
+
+        let (_, truncated) =
+            collect_pages_up_to(Some(2), |_, _| Ok(df_page(&[1, 2], None))).unwrap();
+        assert!(!truncated);
+    }
+
     #[test]
     fn test_client_creation() {
         let mainnet = GraphQLClient::mainnet();
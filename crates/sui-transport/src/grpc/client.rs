@@ -13,6 +13,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tonic::transport::Channel;
 
+use crate::cassette::Cassette;
+use crate::compression::Compression;
+use crate::error::TransportError;
+use crate::network::Network;
+
 use super::generated::sui_rpc_v2::{
     self as proto, ledger_service_client::LedgerServiceClient,
     subscription_service_client::SubscriptionServiceClient,
@@ -29,11 +34,16 @@ use super::generated::sui_rpc_v2::{
 /// - `https://archive.mainnet.sui.io:443` - Historical queries (default)
 /// - `https://fullnode.mainnet.sui.io:443` - Live streaming + queries
 /// - `https://fullnode.testnet.sui.io:443` - Testnet
+///
+/// Clones share the underlying channel, request counter and cassette.
+#[derive(Clone)]
 pub struct GrpcClient {
     endpoint: String,
     channel: Channel,
     api_key: Option<String>,
     request_count: Arc<AtomicU64>,
+    compression: Compression,
+    cassette: Option<Arc<Cassette>>,
 }
 
 const MAINNET_ENDPOINT: &str = "https://archive.mainnet.sui.io:443";
@@ -92,6 +102,36 @@ pub fn historical_endpoint_and_api_key_from_env() -> (String, Option<String>) {
     (endpoint, api_key)
 }
 
+/// Resolve the historical gRPC endpoint and API key for `network`.
+///
+/// Mainnet follows [`historical_endpoint_and_api_key_from_env`]. Testnet and devnet
+/// read `SUI_GRPC_TESTNET_ENDPOINT` / `SUI_GRPC_DEVNET_ENDPOINT` and otherwise use the
+/// network's public full node; custom networks use their own gRPC URL.
+pub fn historical_endpoint_and_api_key_for_network(network: &Network) -> (String, Option<String>) {
+    let endpoint = match network {
+        Network::Mainnet => return historical_endpoint_and_api_key_from_env(),
+        Network::Testnet => env_nonempty("SUI_GRPC_TESTNET_ENDPOINT"),
+        Network::Devnet => env_nonempty("SUI_GRPC_DEVNET_ENDPOINT"),
+        Network::Custom(_) => None,
+    }
+    .unwrap_or_else(|| network.grpc_url().to_string());
+    let api_key = resolve_api_key_for_endpoint(&endpoint);
+    (endpoint, api_key)
+}
+
+/// Another well-known historical endpoint to try when `endpoint` lacks an object
+/// version: Surflux for the Mysten archive (requires `SURFLUX_API_KEY`), and the
+/// Mysten archive for anything else.
+pub fn alternate_archive_endpoint(endpoint: &str) -> &'static str {
+    let endpoint = endpoint.to_ascii_lowercase();
+    if endpoint.contains("archive.mainnet.sui.io") || endpoint.contains(MAINNET_LIVE_ENDPOINT_HOST)
+    {
+        SURFLUX_ARCHIVE_ENDPOINT
+    } else {
+        MYSTEN_ARCHIVE_ENDPOINT
+    }
+}
+
 /// Resolve endpoint and API key for historical fetch paths.
 ///
 /// Behavior:
@@ -155,6 +195,13 @@ impl GrpcClient {
         Self::new(&endpoint).await
     }
 
+    /// Create a client for `network`, with the endpoint and API key from
+    /// [`historical_endpoint_and_api_key_for_network`].
+    pub async fn for_network(network: &Network) -> Result<Self> {
+        let (endpoint, api_key) = historical_endpoint_and_api_key_for_network(network);
+        Self::with_api_key(&endpoint, api_key).await
+    }
+
     /// Create a client for Sui mainnet archive (historical data).
     ///
     /// The archive has full history from checkpoint 0 but doesn't support streaming.
@@ -185,9 +232,23 @@ impl GrpcClient {
             channel,
             api_key,
             request_count: Arc::new(AtomicU64::new(0)),
+            compression: Compression::from_env(),
+            cassette: None,
         })
     }
 
+    /// Wrap a channel connected elsewhere (see [`super::GrpcClientPool`]).
+    pub(super) fn from_channel(endpoint: &str, channel: Channel, api_key: Option<String>) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            channel,
+            api_key,
+            request_count: Arc::new(AtomicU64::new(0)),
+            compression: Compression::from_env(),
+            cassette: None,
+        }
+    }
+
     /// Create a client with a custom endpoint and API key.
     /// The API key is included as an `x-api-key` header on all requests.
     pub async fn with_api_key(endpoint: &str, api_key: Option<String>) -> Result<Self> {
@@ -216,9 +277,40 @@ impl GrpcClient {
             channel,
             api_key,
             request_count: Arc::new(AtomicU64::new(0)),
+            compression: Compression::from_env(),
+            cassette: None,
         })
     }
 
+    /// Request compressed responses (per-message gzip/zstd).
+    ///
+    /// Only advertised via `grpc-accept-encoding`; servers that don't support
+    /// the encoding reply uncompressed, so this is always safe to enable.
+    /// Defaults to `SUI_TRANSPORT_COMPRESSION` (or none when unset).
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Record every unary ledger call to `cassette`, or serve them from it in playback
+    /// mode. A playback client never uses its channel, so it can be [`Self::lazy`].
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Compression requested for responses.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    fn ledger_client(&self) -> LedgerServiceClient<Channel> {
+        self.compression.grpc_encodings().iter().fold(
+            LedgerServiceClient::new(self.channel.clone()),
+            |client, encoding| client.accept_compressed(*encoding),
+        )
+    }
+
     /// Total number of gRPC requests made through this client.
     pub fn request_count(&self) -> u64 {
         self.request_count.load(Ordering::Relaxed)
@@ -236,6 +328,72 @@ impl GrpcClient {
         request
     }
 
+    /// Run a unary call, through the client's cassette when it has one (see
+    /// [`crate::cassette`]). Recordings store the encoded request and response protos;
+    /// playback never touches the channel.
+    async fn unary<Req, Resp, Fut>(
+        &self,
+        method: &str,
+        request: Req,
+        call: impl FnOnce(tonic::Request<Req>) -> Fut,
+    ) -> std::result::Result<Resp, tonic::Status>
+    where
+        Req: prost::Message,
+        Resp: prost::Message + Default,
+        Fut:
+            std::future::Future<Output = std::result::Result<tonic::Response<Resp>, tonic::Status>>,
+    {
+        use crate::cassette::RecordedResponse;
+        use base64::Engine;
+
+        let Some(cassette) = self.cassette.as_deref() else {
+            return call(self.wrap_request(request))
+                .await
+                .map(tonic::Response::into_inner);
+        };
+        let key = serde_json::json!({
+            "method": method,
+            "request": base64::engine::general_purpose::STANDARD.encode(request.encode_to_vec()),
+        });
+        if cassette.is_playback() {
+            return match cassette.playback("grpc", &key) {
+                Ok(RecordedResponse::Bytes { base64 }) => base64::engine::general_purpose::STANDARD
+                    .decode(base64)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| Resp::decode(bytes.as_slice()).map_err(|e| e.to_string()))
+                    .map_err(|e| {
+                        tonic::Status::internal(format!(
+                            "cassette {} response is not decodable: {}",
+                            method, e
+                        ))
+                    }),
+                Ok(RecordedResponse::Error { message, code }) => Err(tonic::Status::new(
+                    code.map(tonic::Code::from).unwrap_or(tonic::Code::Unknown),
+                    message,
+                )),
+                Ok(RecordedResponse::Json { .. }) => Err(tonic::Status::internal(format!(
+                    "cassette recorded a JSON response for {}",
+                    method
+                ))),
+                Err(err) => Err(tonic::Status::unavailable(err.to_string())),
+            };
+        }
+        let result = call(self.wrap_request(request))
+            .await
+            .map(tonic::Response::into_inner);
+        let recorded = match &result {
+            Ok(response) => RecordedResponse::Bytes {
+                base64: base64::engine::general_purpose::STANDARD.encode(response.encode_to_vec()),
+            },
+            Err(status) => RecordedResponse::Error {
+                message: status.message().to_string(),
+                code: Some(status.code() as i32),
+            },
+        };
+        cassette.record_response("grpc", key, recorded);
+        result
+    }
+
     /// Get the endpoint URL.
     pub fn endpoint(&self) -> &str {
         &self.endpoint
@@ -247,15 +405,17 @@ impl GrpcClient {
 
     /// Get service info (chain ID, current epoch, checkpoint height).
     pub async fn get_service_info(&self) -> Result<ServiceInfo> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
-
-        let response = client
-            .get_service_info(self.wrap_request(proto::GetServiceInfoRequest {}))
+        let mut client = self.ledger_client();
+
+        let info = self
+            .unary(
+                "GetServiceInfo",
+                proto::GetServiceInfoRequest {},
+                move |req| async move { client.get_service_info(req).await },
+            )
             .await
             .map_err(|e| anyhow!("gRPC error: {}", e))?;
 
-        let info = response.into_inner();
-
         Ok(ServiceInfo {
             chain_id: info.chain_id.unwrap_or_default(),
             chain: info.chain.unwrap_or_default(),
@@ -307,7 +467,10 @@ impl GrpcClient {
     ///
     /// Returns a stream of checkpoints with full transaction data.
     pub async fn subscribe_checkpoints(&self) -> Result<CheckpointStream> {
-        let mut client = SubscriptionServiceClient::new(self.channel.clone());
+        let mut client = self.compression.grpc_encodings().iter().fold(
+            SubscriptionServiceClient::new(self.channel.clone()),
+            |client, encoding| client.accept_compressed(*encoding),
+        );
 
         // Request full checkpoint data including transactions
         let request = proto::SubscribeCheckpointsRequest {
@@ -344,7 +507,7 @@ impl GrpcClient {
         object_id: &str,
         version: Option<u64>,
     ) -> Result<Option<GrpcObject>> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
+        let mut client = self.ledger_client();
 
         let request = proto::GetObjectRequest {
             object_id: Some(object_id.to_string()),
@@ -364,13 +527,77 @@ impl GrpcClient {
             }),
         };
 
-        let response = client
-            .get_object(self.wrap_request(request))
+        let response = match self
+            .unary("GetObject", request, move |req| async move {
+                client.get_object(req).await
+            })
             .await
-            .map_err(|e| anyhow!("gRPC error fetching object: {}", e))?;
+        {
+            Ok(response) => response,
+            Err(status) => match version {
+                Some(version) if status.code() == tonic::Code::NotFound => {
+                    return Err(self.missing_version_error(object_id, version, false).into());
+                }
+                _ => return Err(anyhow!("gRPC error fetching object: {}", status)),
+            },
+        };
+
+        Ok(response.object.map(GrpcObject::from_proto))
+    }
+
+    /// The error for `object_id` at `version` being absent from this endpoint.
+    ///
+    /// Object versions are Lamport timestamps with gaps, so the endpoint alone cannot
+    /// tell an unarchived version from one that never existed. Callers that know the
+    /// version existed (from a transaction's input or changed refs) pass
+    /// `known_to_exist` and get [`TransportError::ArchiveVersionGap`]; otherwise the
+    /// error is a plain [`TransportError::ObjectNotFound`].
+    pub fn missing_version_error(
+        &self,
+        object_id: &str,
+        version: u64,
+        known_to_exist: bool,
+    ) -> TransportError {
+        if known_to_exist {
+            TransportError::ArchiveVersionGap {
+                object_id: object_id.to_string(),
+                requested_version: version,
+                endpoint: self.endpoint.clone(),
+            }
+        } else {
+            TransportError::ObjectNotFound {
+                object_id: object_id.to_string(),
+                version: Some(version),
+            }
+        }
+    }
+
+    /// Check whether an object exists at `version` (or at all, if `None`) without
+    /// fetching its BCS or package contents.
+    ///
+    /// Returns `Ok(false)` when the node reports the object/version as not found;
+    /// transport and other server errors are returned as `Err`.
+    pub async fn object_exists_at(&self, object_id: &str, version: Option<u64>) -> Result<bool> {
+        let mut client = self.ledger_client();
+
+        let request = proto::GetObjectRequest {
+            object_id: Some(object_id.to_string()),
+            version,
+            read_mask: Some(prost_types::FieldMask {
+                paths: vec!["object_id".to_string(), "version".to_string()],
+            }),
+        };
 
-        let inner = response.into_inner();
-        Ok(inner.object.map(GrpcObject::from_proto))
+        match self
+            .unary("GetObject", request, move |req| async move {
+                client.get_object(req).await
+            })
+            .await
+        {
+            Ok(response) => Ok(response.object.is_some()),
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(false),
+            Err(status) => Err(anyhow!("gRPC error probing object: {}", status)),
+        }
     }
 
     /// Batch fetch multiple objects at specific versions with parallel execution.
@@ -435,12 +662,13 @@ impl GrpcClient {
             channel: self.channel.clone(),
             api_key: self.api_key.clone(),
             request_count: self.request_count.clone(),
+            compression: self.compression,
         }
     }
 
     /// Batch fetch multiple objects.
     pub async fn batch_get_objects(&self, object_ids: &[&str]) -> Result<Vec<Option<GrpcObject>>> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
+        let mut client = self.ledger_client();
 
         let requests: Vec<proto::GetObjectRequest> = object_ids
             .iter()
@@ -458,13 +686,14 @@ impl GrpcClient {
             }),
         };
 
-        let response = client
-            .batch_get_objects(self.wrap_request(request))
+        let response = self
+            .unary("BatchGetObjects", request, move |req| async move {
+                client.batch_get_objects(req).await
+            })
             .await
             .map_err(|e| anyhow!("gRPC batch error: {}", e))?;
 
         let results = response
-            .into_inner()
             .objects
             .into_iter()
             .map(|r| match r.result {
@@ -484,7 +713,7 @@ impl GrpcClient {
 
     /// Fetch a single transaction by digest.
     pub async fn get_transaction(&self, digest: &str) -> Result<Option<GrpcTransaction>> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
+        let mut client = self.ledger_client();
 
         let request = proto::GetTransactionRequest {
             digest: Some(digest.to_string()),
@@ -493,13 +722,14 @@ impl GrpcClient {
             }),
         };
 
-        let response = client
-            .get_transaction(self.wrap_request(request))
+        let response = self
+            .unary("GetTransaction", request, move |req| async move {
+                client.get_transaction(req).await
+            })
             .await
             .map_err(|e| anyhow!("gRPC error fetching transaction: {}", e))?;
 
-        let inner = response.into_inner();
-        Ok(inner.transaction.map(GrpcTransaction::from_proto))
+        Ok(response.transaction.map(GrpcTransaction::from_proto))
     }
 
     /// Batch fetch multiple transactions.
@@ -507,7 +737,7 @@ impl GrpcClient {
         &self,
         digests: &[&str],
     ) -> Result<Vec<Option<GrpcTransaction>>> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
+        let mut client = self.ledger_client();
 
         let request = proto::BatchGetTransactionsRequest {
             digests: digests.iter().map(|s| s.to_string()).collect(),
@@ -516,13 +746,14 @@ impl GrpcClient {
             }),
         };
 
-        let response = client
-            .batch_get_transactions(self.wrap_request(request))
+        let response = self
+            .unary("BatchGetTransactions", request, move |req| async move {
+                client.batch_get_transactions(req).await
+            })
             .await
             .map_err(|e| anyhow!("gRPC batch error: {}", e))?;
 
         let results = response
-            .into_inner()
             .transactions
             .into_iter()
             .map(|r| match r.result {
@@ -542,7 +773,7 @@ impl GrpcClient {
 
     /// Fetch a checkpoint by sequence number.
     pub async fn get_checkpoint(&self, sequence_number: u64) -> Result<Option<GrpcCheckpoint>> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
+        let mut client = self.ledger_client();
 
         let request = proto::GetCheckpointRequest {
             checkpoint_id: Some(proto::get_checkpoint_request::CheckpointId::SequenceNumber(
@@ -553,20 +784,21 @@ impl GrpcClient {
             }),
         };
 
-        let response = client
-            .get_checkpoint(self.wrap_request(request))
+        let response = self
+            .unary("GetCheckpoint", request, move |req| async move {
+                client.get_checkpoint(req).await
+            })
             .await
             .map_err(|e| anyhow!("gRPC error fetching checkpoint: {}", e))?;
 
-        let inner = response.into_inner();
-        Ok(inner.checkpoint.map(GrpcCheckpoint::from_proto))
+        Ok(response.checkpoint.map(GrpcCheckpoint::from_proto))
     }
 
     /// Fetch epoch information (protocol version, reference gas price, etc.).
     ///
     /// If `epoch` is None, returns the current epoch.
     pub async fn get_epoch(&self, epoch: Option<u64>) -> Result<Option<GrpcEpoch>> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
+        let mut client = self.ledger_client();
 
         let request = proto::GetEpochRequest {
             epoch,
@@ -582,13 +814,14 @@ impl GrpcClient {
             }),
         };
 
-        let response = client
-            .get_epoch(self.wrap_request(request))
+        let response = self
+            .unary("GetEpoch", request, move |req| async move {
+                client.get_epoch(req).await
+            })
             .await
             .map_err(|e| anyhow!("gRPC error fetching epoch: {}", e))?;
 
-        let inner = response.into_inner();
-        Ok(inner.epoch.map(GrpcEpoch::from_proto))
+        Ok(response.epoch.map(GrpcEpoch::from_proto))
     }
 
     /// Fetch a package's modules at a specific version.
@@ -611,7 +844,7 @@ impl GrpcClient {
 
     /// Fetch the latest checkpoint.
     pub async fn get_latest_checkpoint(&self) -> Result<Option<GrpcCheckpoint>> {
-        let mut client = LedgerServiceClient::new(self.channel.clone());
+        let mut client = self.ledger_client();
 
         let request = proto::GetCheckpointRequest {
             checkpoint_id: None, // None = latest
@@ -620,13 +853,14 @@ impl GrpcClient {
             }),
         };
 
-        let response = client
-            .get_checkpoint(self.wrap_request(request))
+        let response = self
+            .unary("GetCheckpoint", request, move |req| async move {
+                client.get_checkpoint(req).await
+            })
             .await
             .map_err(|e| anyhow!("gRPC error fetching latest checkpoint: {}", e))?;
 
-        let inner = response.into_inner();
-        Ok(inner.checkpoint.map(GrpcCheckpoint::from_proto))
+        Ok(response.checkpoint.map(GrpcCheckpoint::from_proto))
     }
 }
 
@@ -778,6 +1012,52 @@ pub struct GrpcTransaction {
     /// Format: (object_id, version) - this is the ACTUAL version used during execution,
     /// not the initial_shared_version from the transaction input.
     pub unchanged_consensus_objects: Vec<(String, u64)>,
+    /// Gas payment coins in transaction order (primary coin first).
+    /// Format: (object_id, version)
+    pub gas_payment: Vec<(String, u64)>,
+    /// Every entry of the effects' changed objects, with input and output references.
+    pub changed_object_details: Vec<GrpcChangedObject>,
+}
+
+/// One object changed by a transaction, as reported in its effects.
+#[derive(Debug, Clone)]
+pub struct GrpcChangedObject {
+    pub object_id: String,
+    /// Whether the object existed before the transaction.
+    pub input_exists: bool,
+    pub input_version: Option<u64>,
+    pub input_digest: Option<String>,
+    /// Whether the object exists (as a top-level object or package) afterwards.
+    pub output_exists: bool,
+    pub output_version: Option<u64>,
+    pub output_digest: Option<String>,
+    pub output_owner: Option<GrpcOwner>,
+    /// The transaction created this object's ID.
+    pub id_created: bool,
+    /// The transaction deleted this object's ID.
+    pub id_deleted: bool,
+}
+
+impl GrpcChangedObject {
+    fn from_proto(proto: &proto::ChangedObject) -> Option<Self> {
+        use proto::changed_object::{IdOperation, InputObjectState, OutputObjectState};
+
+        let output_state = proto.output_state.unwrap_or(0);
+        let id_operation = proto.id_operation.unwrap_or(0);
+        Some(Self {
+            object_id: proto.object_id.clone()?,
+            input_exists: proto.input_state == Some(InputObjectState::Exists as i32),
+            input_version: proto.input_version,
+            input_digest: proto.input_digest.clone(),
+            output_exists: output_state == OutputObjectState::ObjectWrite as i32
+                || output_state == OutputObjectState::PackageWrite as i32,
+            output_version: proto.output_version,
+            output_digest: proto.output_digest.clone(),
+            output_owner: proto.output_owner.as_ref().map(GrpcOwner::from_proto),
+            id_created: id_operation == IdOperation::Created as i32,
+            id_deleted: id_operation == IdOperation::Deleted as i32,
+        })
+    }
 }
 
 /// Detailed execution error from a failed transaction.
@@ -995,6 +1275,22 @@ impl GrpcTransaction {
             changed_objects,
             created_objects,
             unchanged_consensus_objects,
+            gas_payment: gas_payment
+                .map(|g| {
+                    g.objects
+                        .iter()
+                        .filter_map(|obj| Some((obj.object_id.clone()?, obj.version?)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            changed_object_details: effects
+                .map(|e| {
+                    e.changed_objects
+                        .iter()
+                        .filter_map(GrpcChangedObject::from_proto)
+                        .collect()
+                })
+                .unwrap_or_default(),
         }
     }
 
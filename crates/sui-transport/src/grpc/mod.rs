@@ -7,7 +7,8 @@
 //!
 //! ## Capabilities
 //!
-//! - **Streaming subscriptions** - Subscribe to new checkpoints as they're finalized
+//! - **Streaming subscriptions** - Subscribe to new checkpoints as they're finalized,
+//!   optionally resumable from a cursor with automatic reconnect
 //! - **Batch fetching** - Efficiently fetch multiple objects/transactions at once
 //! - **Full PTB data** - Complete transaction inputs, commands, effects with type arguments
 //!
@@ -55,8 +56,12 @@ pub mod google {
 }
 
 mod client;
+mod pool;
+mod subscription;
 
 #[cfg(test)]
 pub mod test_utils;
 
 pub use client::*;
+pub use pool::{configure_global_pool, global_pool, GrpcClientPool, GrpcPoolConfig, GrpcPoolStats};
+pub use subscription::{CheckpointSubscription, SubscriptionConfig};
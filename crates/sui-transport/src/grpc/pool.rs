@@ -0,0 +1,323 @@
+//! Process-wide gRPC client pool.
+//!
+//! Connecting a [`GrpcClient`] costs a TLS handshake, and callers outside async code
+//! used to pair every connection with a fresh tokio runtime whose drop tore the
+//! connection down again. The pool owns one runtime and keeps up to
+//! [`GrpcPoolConfig::max_connections`] HTTP/2 channels per (endpoint, API key) alive
+//! for the life of the process; [`GrpcClientPool::client`] hands out cheap clones
+//! that share them.
+//!
+//! ```ignore
+//! use sui_transport::grpc::global_pool;
+//!
+//! let pool = global_pool();
+//! let object = pool.block_on(async {
+//!     let client = pool.client("https://archive.mainnet.sui.io:443", None).await?;
+//!     client.get_object_at_version("0x6", None).await
+//! })?;
+//! ```
+//!
+//! Settings come from the environment (`SUI_GRPC_POOL_MAX_CONNECTIONS`,
+//! `SUI_GRPC_POOL_KEEPALIVE_SECS`) unless [`configure_global_pool`] runs first.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use tonic::transport::{Channel, ClientTlsConfig};
+
+use super::GrpcClient;
+use crate::cassette::Cassette;
+
+/// Connection settings for a [`GrpcClientPool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrpcPoolConfig {
+    /// Channels kept per (endpoint, API key). Each one multiplexes many concurrent
+    /// requests over HTTP/2; more spread heavy load across connections.
+    pub max_connections: usize,
+    /// Interval between HTTP/2 and TCP keepalive pings; `None` disables them.
+    pub keepalive_interval: Option<Duration>,
+    /// How long to wait for a keepalive ack before dropping the connection.
+    pub keepalive_timeout: Duration,
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+}
+
+impl Default for GrpcPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 1,
+            keepalive_interval: Some(Duration::from_secs(30)),
+            keepalive_timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl GrpcPoolConfig {
+    /// Defaults overridden by `SUI_GRPC_POOL_MAX_CONNECTIONS` and
+    /// `SUI_GRPC_POOL_KEEPALIVE_SECS` (`0` disables keepalive).
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        let env_u64 = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        if let Some(max) = env_u64("SUI_GRPC_POOL_MAX_CONNECTIONS") {
+            config.max_connections = (max as usize).max(1);
+        }
+        if let Some(secs) = env_u64("SUI_GRPC_POOL_KEEPALIVE_SECS") {
+            config.keepalive_interval = (secs > 0).then(|| Duration::from_secs(secs));
+        }
+        config
+    }
+}
+
+/// Pool counters, for diagnosing connection churn.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GrpcPoolStats {
+    /// Distinct (endpoint, API key) pairs with open channels.
+    pub endpoints: usize,
+    /// Open channels across all endpoints.
+    pub connections: usize,
+    /// Channels opened since the pool was created.
+    pub connects: u64,
+    /// Clients served from an existing channel.
+    pub reuses: u64,
+}
+
+type PoolKey = (String, Option<String>);
+
+#[derive(Default)]
+struct PoolSlot {
+    clients: Vec<GrpcClient>,
+    next: usize,
+}
+
+/// Long-lived gRPC channels shared by every caller in the process.
+pub struct GrpcClientPool {
+    config: GrpcPoolConfig,
+    runtime: tokio::runtime::Runtime,
+    slots: Mutex<HashMap<PoolKey, PoolSlot>>,
+    connects: AtomicU64,
+    reuses: AtomicU64,
+}
+
+impl GrpcClientPool {
+    pub fn new(config: GrpcPoolConfig) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("sui-grpc-pool")
+            .enable_all()
+            .build()
+            .context("create gRPC pool runtime")?;
+        Ok(Self {
+            config,
+            runtime,
+            slots: Mutex::new(HashMap::new()),
+            connects: AtomicU64::new(0),
+            reuses: AtomicU64::new(0),
+        })
+    }
+
+    pub fn config(&self) -> &GrpcPoolConfig {
+        &self.config
+    }
+
+    /// Run `future` to completion on the pool's runtime.
+    ///
+    /// For synchronous callers; like any `block_on`, it must not be called from
+    /// inside an async context.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// A client for `endpoint`, reusing a pooled channel when one is available.
+    ///
+    /// New channels are opened until the endpoint has `max_connections`, after which
+    /// clients rotate over the existing ones. Channels are connected on the pool's
+    /// runtime, so they outlive whatever runtime the caller is on.
+    pub async fn client(&self, endpoint: &str, api_key: Option<String>) -> Result<GrpcClient> {
+        let key = (endpoint.to_string(), api_key.clone());
+        if let Some(client) = self.checkout(&key) {
+            return Ok(client);
+        }
+
+        let config = self.config.clone();
+        let target = endpoint.to_string();
+        let channel = self
+            .runtime
+            .spawn(async move { connect_channel(&target, &config).await })
+            .await
+            .map_err(|e| anyhow!("gRPC connect task for {} failed: {}", endpoint, e))??;
+        self.connects.fetch_add(1, Ordering::Relaxed);
+
+        let client = GrpcClient::from_channel(endpoint, channel, api_key);
+        let mut slots = self.lock_slots();
+        let slot = slots.entry(key).or_default();
+        if slot.clients.len() < self.max_connections() {
+            slot.clients.push(client.clone());
+        }
+        Ok(client)
+    }
+
+    /// Like [`Self::client`], with the client's calls going through `cassette` (see
+    /// [`GrpcClient::with_cassette`]). A playback cassette bypasses the pool and gets a
+    /// lazy client, since every call is served from the recording.
+    pub async fn client_with_cassette(
+        &self,
+        endpoint: &str,
+        api_key: Option<String>,
+        cassette: Option<Arc<Cassette>>,
+    ) -> Result<GrpcClient> {
+        let Some(cassette) = cassette else {
+            return self.client(endpoint, api_key).await;
+        };
+        let client = if cassette.is_playback() {
+            GrpcClient::lazy(endpoint, api_key)?
+        } else {
+            self.client(endpoint, api_key).await?
+        };
+        Ok(client.with_cassette(cassette))
+    }
+
+    /// Drop every pooled channel; later calls reconnect.
+    pub fn clear(&self) {
+        self.lock_slots().clear();
+    }
+
+    pub fn stats(&self) -> GrpcPoolStats {
+        let slots = self.lock_slots();
+        GrpcPoolStats {
+            endpoints: slots.len(),
+            connections: slots.values().map(|slot| slot.clients.len()).sum(),
+            connects: self.connects.load(Ordering::Relaxed),
+            reuses: self.reuses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn checkout(&self, key: &PoolKey) -> Option<GrpcClient> {
+        let mut slots = self.lock_slots();
+        let slot = slots.get_mut(key)?;
+        if slot.clients.len() < self.max_connections() {
+            return None;
+        }
+        let client = slot.clients[slot.next % slot.clients.len()].clone();
+        slot.next = slot.next.wrapping_add(1);
+        self.reuses.fetch_add(1, Ordering::Relaxed);
+        Some(client)
+    }
+
+    fn max_connections(&self) -> usize {
+        self.config.max_connections.max(1)
+    }
+
+    fn lock_slots(&self) -> std::sync::MutexGuard<'_, HashMap<PoolKey, PoolSlot>> {
+        self.slots
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+async fn connect_channel(endpoint: &str, config: &GrpcPoolConfig) -> Result<Channel> {
+    let mut builder = Channel::from_shared(endpoint.to_string())?
+        .timeout(config.request_timeout)
+        .connect_timeout(config.connect_timeout)
+        .tcp_keepalive(config.keepalive_interval);
+    if let Some(interval) = config.keepalive_interval {
+        builder = builder
+            .http2_keep_alive_interval(interval)
+            .keep_alive_timeout(config.keepalive_timeout)
+            .keep_alive_while_idle(true);
+    }
+    if endpoint.starts_with("https://") {
+        builder = builder.tls_config(ClientTlsConfig::new().with_webpki_roots())?;
+    }
+    builder
+        .connect()
+        .await
+        .map_err(|e| anyhow!("Failed to connect to gRPC endpoint {}: {}", endpoint, e))
+}
+
+static GLOBAL_POOL: OnceLock<GrpcClientPool> = OnceLock::new();
+
+/// The process-wide pool, created from [`GrpcPoolConfig::from_env`] on first use.
+pub fn global_pool() -> &'static GrpcClientPool {
+    GLOBAL_POOL.get_or_init(|| {
+        GrpcClientPool::new(GrpcPoolConfig::from_env()).expect("create global gRPC pool")
+    })
+}
+
+/// Install the process-wide pool with `config`. Fails once the pool has been used.
+pub fn configure_global_pool(config: GrpcPoolConfig) -> Result<()> {
+    GLOBAL_POOL.set(GrpcClientPool::new(config)?).map_err(|_| {
+        anyhow!("the global gRPC pool is already in use; configure it before the first gRPC call")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pooled_clients_share_channels_up_to_max_connections() {
+        let pool = GrpcClientPool::new(GrpcPoolConfig {
+            max_connections: 2,
+            ..GrpcPoolConfig::default()
+        })
+        .expect("pool");
+        let key: PoolKey = ("http://127.0.0.1:1".to_string(), None);
+        assert!(pool.checkout(&key).is_none());
+
+        {
+            let _guard = pool.runtime.enter();
+            let client = GrpcClient::lazy(&key.0, None).expect("lazy client");
+            pool.lock_slots()
+                .entry(key.clone())
+                .or_default()
+                .clients
+                .push(client);
+        }
+        // One channel is below the cap: callers still open a second one.
+        assert!(pool.checkout(&key).is_none());
+
+        {
+            let _guard = pool.runtime.enter();
+            let client = GrpcClient::lazy(&key.0, None).expect("lazy client");
+            pool.lock_slots()
+                .get_mut(&key)
+                .unwrap()
+                .clients
+                .push(client);
+        }
+        let first = pool.checkout(&key).expect("rotated client");
+        let second = pool.checkout(&key).expect("rotated client");
+        assert_eq!(first.endpoint(), key.0);
+        assert_eq!(second.endpoint(), key.0);
+
+        let stats = pool.stats();
+        assert_eq!(stats.endpoints, 1);
+        assert_eq!(stats.connections, 2);
+        assert_eq!(stats.reuses, 2);
+
+        pool.clear();
+        assert_eq!(pool.stats().connections, 0);
+    }
+
+    #[test]
+    fn config_from_env_overrides_defaults() {
+        std::env::set_var("SUI_GRPC_POOL_MAX_CONNECTIONS", "0");
+        std::env::set_var("SUI_GRPC_POOL_KEEPALIVE_SECS", "0");
+        let config = GrpcPoolConfig::from_env();
+        std::env::remove_var("SUI_GRPC_POOL_MAX_CONNECTIONS");
+        std::env::remove_var("SUI_GRPC_POOL_KEEPALIVE_SECS");
+        assert_eq!(config.max_connections, 1);
+        assert_eq!(config.keepalive_interval, None);
+    }
+}
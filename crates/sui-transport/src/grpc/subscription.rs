@@ -0,0 +1,313 @@
+//! Resumable checkpoint subscription.
+//!
+//! [`GrpcClient::subscribe_checkpoints`] is a single server stream: it ends on the
+//! first network error and always starts at the live tip. A
+//! [`CheckpointSubscription`] wraps it in a background task that:
+//!
+//! - **reconnects** with exponential backoff when the stream errors or ends,
+//! - **resumes from a cursor**: checkpoints already delivered are dropped, and any
+//!   gap between the cursor and the live stream (a reconnect, or a `from_seq`
+//!   behind the tip) is backfilled with `GetCheckpoint`, so delivery is gap-free
+//!   and in order,
+//! - **buffers at most `buffer` checkpoints**: once the consumer falls behind, the
+//!   task stops polling the server stream until there is room again.
+
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::client::{GrpcCheckpoint, GrpcClient};
+
+/// A buffered subscription item and the checkpoint it stands for (`None` for the final
+/// error after reconnects are exhausted).
+type Buffered = (Option<u64>, Result<GrpcCheckpoint>);
+
+/// Tuning for [`GrpcClient::subscribe_checkpoints_from`].
+#[derive(Debug, Clone)]
+pub struct SubscriptionConfig {
+    /// Checkpoints buffered ahead of the consumer.
+    pub buffer: usize,
+    /// Consecutive failed (re)connects before the subscription gives up;
+    /// `None` retries forever.
+    pub max_reconnect_attempts: Option<u32>,
+    /// Delay before the first reconnect; doubles per consecutive failure.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for SubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            buffer: 64,
+            max_reconnect_attempts: Some(10),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl SubscriptionConfig {
+    /// Delay before reconnect attempt `attempt` (1-based).
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << attempt.saturating_sub(1).min(16);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// What to do with checkpoint `received` when `next` is the next one owed to the consumer.
+#[derive(Debug, PartialEq, Eq)]
+enum Delivery {
+    /// Already delivered before a reconnect.
+    Duplicate,
+    Deliver,
+    /// Fetch these first, then deliver.
+    Backfill(Range<u64>),
+}
+
+fn plan_delivery(next: Option<u64>, received: u64) -> Delivery {
+    match next {
+        Some(next) if received < next => Delivery::Duplicate,
+        Some(next) if received > next => Delivery::Backfill(next..received),
+        _ => Delivery::Deliver,
+    }
+}
+
+/// In-order, gap-free checkpoint stream that survives disconnects.
+///
+/// Yields `Err` items for checkpoints that could not be backfilled (the stream
+/// continues after them) and a final `Err` when reconnects are exhausted.
+/// Dropping the subscription stops the background task.
+pub struct CheckpointSubscription {
+    rx: mpsc::Receiver<Buffered>,
+    cursor: Option<u64>,
+    reconnects: Arc<AtomicU64>,
+    task: JoinHandle<()>,
+}
+
+impl CheckpointSubscription {
+    /// Next checkpoint, or None once the subscription has given up.
+    pub async fn next(&mut self) -> Option<Result<GrpcCheckpoint>> {
+        let buffered = self.rx.recv().await;
+        self.consume(buffered)
+    }
+
+    /// Hand `buffered` to the caller, moving the cursor past the checkpoint it stands for.
+    fn consume(&mut self, buffered: Option<Buffered>) -> Option<Result<GrpcCheckpoint>> {
+        let (sequence_number, item) = buffered?;
+        if let Some(sequence_number) = sequence_number {
+            self.cursor = Some(sequence_number + 1);
+        }
+        Some(item)
+    }
+
+    /// Sequence number of the checkpoint after the last one yielded to the caller
+    /// (None before the first one when subscribed from the live tip). Checkpoints
+    /// buffered but not yet yielded do not count. Pass it as `from_seq` to resume a
+    /// later subscription where this one stopped.
+    pub fn cursor(&self) -> Option<u64> {
+        self.cursor
+    }
+
+    /// Number of reconnects so far.
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+}
+
+impl futures::Stream for CheckpointSubscription {
+    type Item = Result<GrpcCheckpoint>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let buffered = std::task::ready!(self.rx.poll_recv(cx));
+        Poll::Ready(self.consume(buffered))
+    }
+}
+
+impl Drop for CheckpointSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl GrpcClient {
+    /// Subscribe to checkpoints starting at `from_seq` (the live tip if None), with
+    /// automatic reconnect, cursor resume, and bounded buffering.
+    ///
+    /// Must be called from within a Tokio runtime, which drives the subscription.
+    pub fn subscribe_checkpoints_from(
+        &self,
+        from_seq: Option<u64>,
+        config: SubscriptionConfig,
+    ) -> CheckpointSubscription {
+        let (tx, rx) = mpsc::channel(config.buffer.max(1));
+        let reconnects = Arc::new(AtomicU64::new(0));
+        let task = tokio::spawn(run_subscription(
+            self.clone(),
+            from_seq,
+            config,
+            tx,
+            reconnects.clone(),
+        ));
+        CheckpointSubscription {
+            rx,
+            cursor: from_seq,
+            reconnects,
+            task,
+        }
+    }
+}
+
+async fn run_subscription(
+    client: GrpcClient,
+    mut next: Option<u64>,
+    config: SubscriptionConfig,
+    tx: mpsc::Sender<Buffered>,
+    reconnects: Arc<AtomicU64>,
+) {
+    let mut failures = 0u32;
+    loop {
+        let error = match client.subscribe_checkpoints().await {
+            Ok(mut stream) => loop {
+                let checkpoint = match stream.next().await {
+                    Some(Ok(checkpoint)) => checkpoint,
+                    Some(Err(e)) => break e,
+                    None => break anyhow!("checkpoint stream ended"),
+                };
+                failures = 0;
+                let sequence_number = checkpoint.sequence_number;
+                match plan_delivery(next, sequence_number) {
+                    Delivery::Duplicate => continue,
+                    Delivery::Deliver => {}
+                    Delivery::Backfill(missing) => {
+                        for missing_seq in missing {
+                            let item = match client.get_checkpoint(missing_seq).await {
+                                Ok(Some(checkpoint)) => Ok(checkpoint),
+                                Ok(None) => Err(anyhow!(
+                                    "checkpoint {} is not available from {}",
+                                    missing_seq,
+                                    client.endpoint()
+                                )),
+                                Err(e) => Err(e.context(format!(
+                                    "failed to backfill checkpoint {}",
+                                    missing_seq
+                                ))),
+                            };
+                            if tx.send((Some(missing_seq), item)).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+                next = Some(sequence_number + 1);
+                if tx
+                    .send((Some(sequence_number), Ok(checkpoint)))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+            },
+            Err(e) => e,
+        };
+
+        failures += 1;
+        if config
+            .max_reconnect_attempts
+            .is_some_and(|max| failures > max)
+        {
+            let _ = tx
+                .send((
+                    None,
+                    Err(error.context(format!(
+                        "checkpoint subscription gave up after {} reconnect attempts",
+                        failures - 1
+                    ))),
+                ))
+                .await;
+            return;
+        }
+        reconnects.fetch_add(1, Ordering::Relaxed);
+        tokio::time::sleep(config.backoff(failures)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_delivery_drops_duplicates_and_backfills_gaps() {
+        assert_eq!(plan_delivery(None, 100), Delivery::Deliver);
+        assert_eq!(plan_delivery(Some(100), 100), Delivery::Deliver);
+        assert_eq!(plan_delivery(Some(100), 99), Delivery::Duplicate);
+        assert_eq!(plan_delivery(Some(100), 103), Delivery::Backfill(100..103));
+    }
+
+    fn checkpoint(sequence_number: u64) -> GrpcCheckpoint {
+        GrpcCheckpoint {
+            sequence_number,
+            digest: String::new(),
+            timestamp_ms: None,
+            epoch: 0,
+            transactions: vec![],
+            objects: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn cursor_advances_only_as_checkpoints_are_yielded() {
+        let (tx, rx) = mpsc::channel(4);
+        let mut subscription = CheckpointSubscription {
+            rx,
+            cursor: Some(10),
+            reconnects: Arc::default(),
+            task: tokio::spawn(async {}),
+        };
+        tx.send((Some(10), Ok(checkpoint(10)))).await.unwrap();
+        tx.send((Some(11), Err(anyhow!("backfill failed"))))
+            .await
+            .unwrap();
+        tx.send((Some(12), Ok(checkpoint(12)))).await.unwrap();
+        // Buffered checkpoints have not been seen by the caller yet.
+        assert_eq!(subscription.cursor(), Some(10));
+
+        let first = subscription.next().await.unwrap().unwrap();
+        assert_eq!(first.sequence_number, 10);
+        assert_eq!(subscription.cursor(), Some(11));
+        assert!(subscription.next().await.unwrap().is_err());
+        assert_eq!(subscription.cursor(), Some(12));
+        let third = futures::StreamExt::next(&mut subscription)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(third.sequence_number, 12);
+        assert_eq!(subscription.cursor(), Some(13));
+
+        tx.send((None, Err(anyhow!("gave up")))).await.unwrap();
+        assert!(subscription.next().await.unwrap().is_err());
+        assert_eq!(subscription.cursor(), Some(13));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let config = SubscriptionConfig {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(700),
+            ..SubscriptionConfig::default()
+        };
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(400));
+        assert_eq!(config.backoff(4), Duration::from_millis(700));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_millis(700));
+    }
+}
@@ -154,6 +154,8 @@ impl GrpcTransactionBuilder {
             changed_objects: vec![],
             created_objects: vec![],
             unchanged_consensus_objects: vec![],
+            gas_payment: vec![],
+            changed_object_details: vec![],
         }
     }
 }
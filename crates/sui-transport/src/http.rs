@@ -0,0 +1,52 @@
+//! Process-wide HTTP agent shared by the GraphQL and Walrus clients.
+//!
+//! `ureq::Agent` owns a connection pool, so building one per client loses
+//! keep-alive whenever clients are short-lived (as `GraphQLClient::new` often
+//! is). Clients clone [`shared_agent`] instead and bound each request with
+//! [`ureq::Request::timeout`]; headers stay per request.
+//!
+//! The shared agent's connect timeout comes from `SUI_HTTP_CONNECT_TIMEOUT_SECS`
+//! (default 10s), read once on first use.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Default connect timeout of the shared agent.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+static SHARED_AGENT: OnceLock<(ureq::Agent, Duration)> = OnceLock::new();
+
+fn shared() -> &'static (ureq::Agent, Duration) {
+    SHARED_AGENT.get_or_init(|| {
+        let connect_timeout = std::env::var("SUI_HTTP_CONNECT_TIMEOUT_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT);
+        let agent = ureq::AgentBuilder::new()
+            .timeout_connect(connect_timeout)
+            .build();
+        (agent, connect_timeout)
+    })
+}
+
+/// Connection-pooled agent shared across clients (cheap to clone).
+pub fn shared_agent() -> ureq::Agent {
+    shared().0.clone()
+}
+
+/// Connect timeout configured on [`shared_agent`].
+pub fn shared_connect_timeout() -> Duration {
+    shared().1
+}
+
+/// The shared agent, or a dedicated one when `connect_timeout` differs from it.
+pub fn agent_with_connect_timeout(connect_timeout: Duration) -> ureq::Agent {
+    if connect_timeout == shared_connect_timeout() {
+        shared_agent()
+    } else {
+        ureq::AgentBuilder::new()
+            .timeout_connect(connect_timeout)
+            .build()
+    }
+}
@@ -0,0 +1,641 @@
+//! JSON-RPC Client for Sui Full Nodes
+//!
+//! Minimal blocking client for the Sui JSON-RPC API. Many operators only expose
+//! the JSON-RPC interface (no gRPC archive, no GraphQL), so this client covers
+//! the object/package/transaction reads needed to construct a replay state.
+//!
+//! ## Endpoints
+//! - Mainnet: `https://fullnode.mainnet.sui.io:443`
+//! - Testnet: `https://fullnode.testnet.sui.io:443`
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! let client = JsonRpcClient::mainnet();
+//! let tx = client.get_transaction_block("8JTTa...")?;
+//! let obj = client.try_get_past_object("0x6", 1234)?;
+//! let pkg = client.get_package("0x2")?;
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::graphql::ObjectOwner;
+
+/// Parse an environment variable with a default value.
+fn env_var_or<T: FromStr>(key: &str, default: T) -> T {
+    std::env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// JSON-RPC client for Sui full node queries.
+#[derive(Clone)]
+pub struct JsonRpcClient {
+    endpoint: String,
+    agent: ureq::Agent,
+    request_id: Arc<AtomicU64>,
+}
+
+/// Object data returned from `sui_getObject` / `sui_tryGetPastObject`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcObject {
+    pub address: String,
+    pub version: u64,
+    pub digest: Option<String>,
+    pub type_string: Option<String>,
+    pub owner: ObjectOwner,
+    /// BCS bytes of the Move struct contents (base64), absent for packages.
+    pub bcs_base64: Option<String>,
+    /// Digest of the transaction that last modified this object.
+    pub previous_transaction: Option<String>,
+}
+
+impl JsonRpcObject {
+    /// Decode the BCS contents from base64.
+    pub fn decode_bcs(&self) -> Option<Vec<u8>> {
+        self.bcs_base64
+            .as_ref()
+            .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+    }
+}
+
+/// Package data returned from `sui_getObject` with `showBcs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcPackage {
+    pub address: String,
+    pub version: u64,
+    /// Module bytecode: (module_name, bytecode).
+    pub modules: Vec<(String, Vec<u8>)>,
+    /// Package dependency linkage table.
+    pub linkage: Vec<JsonRpcLinkage>,
+}
+
+/// Linkage entry for package dependency resolution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcLinkage {
+    /// The original (runtime) package ID of the dependency.
+    pub original_id: String,
+    /// The upgraded (storage) package ID this version depends on.
+    pub upgraded_id: String,
+    /// The version of the dependency.
+    pub version: u64,
+}
+
+/// Transaction block returned from `sui_getTransactionBlock`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcTransaction {
+    pub digest: String,
+    /// BCS-encoded `SenderSignedData` (base64), from `showRawInput`.
+    pub raw_transaction_base64: Option<String>,
+    pub timestamp_ms: Option<u64>,
+    pub checkpoint: Option<u64>,
+    pub effects: Option<JsonRpcEffects>,
+}
+
+/// Subset of transaction effects needed to hydrate replay inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonRpcEffects {
+    /// "success" or "failure".
+    pub status: String,
+    pub error: Option<String>,
+    pub executed_epoch: Option<u64>,
+    pub computation_cost: u64,
+    pub storage_cost: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+    pub created: Vec<String>,
+    pub mutated: Vec<String>,
+    pub deleted: Vec<String>,
+    pub wrapped: Vec<String>,
+    pub unwrapped: Vec<String>,
+    /// Input versions of objects modified by this transaction: (object_id, version).
+    pub modified_at_versions: Vec<(String, u64)>,
+    /// Shared objects (mutated and read-only) at their consensus versions.
+    pub shared_objects: Vec<(String, u64)>,
+    /// Number of events the transaction emitted (from the response's `events`).
+    #[serde(default)]
+    pub events_count: usize,
+}
+
+impl JsonRpcEffects {
+    /// Whether the transaction succeeded on-chain.
+    pub fn is_success(&self) -> bool {
+        self.status == "success"
+    }
+}
+
+/// Parse a u64 that JSON-RPC may encode as either a string or a number.
+fn parse_u64(value: Option<&Value>) -> Option<u64> {
+    match value? {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+fn parse_owner(value: Option<&Value>) -> ObjectOwner {
+    let Some(owner) = value else {
+        return ObjectOwner::Unknown;
+    };
+    if owner.as_str() == Some("Immutable") {
+        return ObjectOwner::Immutable;
+    }
+    if let Some(addr) = owner.get("AddressOwner").and_then(|v| v.as_str()) {
+        return ObjectOwner::Address(addr.to_string());
+    }
+    if let Some(parent) = owner.get("ObjectOwner").and_then(|v| v.as_str()) {
+        return ObjectOwner::Parent(parent.to_string());
+    }
+    if let Some(shared) = owner.get("Shared") {
+        return ObjectOwner::Shared {
+            initial_version: parse_u64(shared.get("initial_shared_version")).unwrap_or(0),
+        };
+    }
+    if let Some(consensus) = owner.get("ConsensusAddressOwner") {
+        if let Some(addr) = consensus.get("owner").and_then(|v| v.as_str()) {
+            return ObjectOwner::Address(addr.to_string());
+        }
+    }
+    ObjectOwner::Unknown
+}
+
+/// Parse the `data`/`details` payload of an object response.
+fn parse_object_data(data: &Value) -> Result<JsonRpcObject> {
+    let address = data
+        .get("objectId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("JSON-RPC object response missing objectId"))?
+        .to_string();
+    let version = parse_u64(data.get("version"))
+        .ok_or_else(|| anyhow!("JSON-RPC object {} missing version", address))?;
+    let bcs = data.get("bcs");
+    let bcs_base64 = bcs
+        .filter(|b| b.get("dataType").and_then(|v| v.as_str()) == Some("moveObject"))
+        .and_then(|b| b.get("bcsBytes"))
+        .and_then(|v| v.as_str())
+        .map(String::from);
+
+    Ok(JsonRpcObject {
+        address,
+        version,
+        digest: data
+            .get("digest")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        type_string: data.get("type").and_then(|v| v.as_str()).map(String::from),
+        owner: parse_owner(data.get("owner")),
+        bcs_base64,
+        previous_transaction: data
+            .get("previousTransaction")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+    })
+}
+
+/// Parse a package from the `bcs` payload of a `sui_getObject` response.
+fn parse_package_data(address: &str, data: &Value) -> Result<JsonRpcPackage> {
+    let bcs = data
+        .get("bcs")
+        .filter(|b| b.get("dataType").and_then(|v| v.as_str()) == Some("package"))
+        .ok_or_else(|| anyhow!("Object {} is not a package", address))?;
+
+    let module_map = bcs
+        .get("moduleMap")
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| anyhow!("Package {} response missing moduleMap", address))?;
+    let mut modules = Vec::with_capacity(module_map.len());
+    for (name, encoded) in module_map {
+        let encoded = encoded
+            .as_str()
+            .ok_or_else(|| anyhow!("Package {} module {} is not base64", address, name))?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .with_context(|| format!("Failed to decode module {}::{}", address, name))?;
+        modules.push((name.clone(), bytes));
+    }
+
+    let linkage = bcs
+        .get("linkageTable")
+        .and_then(|v| v.as_object())
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(original_id, info)| {
+                    Some(JsonRpcLinkage {
+                        original_id: original_id.clone(),
+                        upgraded_id: info.get("upgraded_id")?.as_str()?.to_string(),
+                        version: parse_u64(info.get("upgraded_version")).unwrap_or(0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(JsonRpcPackage {
+        address: bcs
+            .get("id")
+            .and_then(|v| v.as_str())
+            .unwrap_or(address)
+            .to_string(),
+        version: parse_u64(bcs.get("version"))
+            .or_else(|| parse_u64(data.get("version")))
+            .unwrap_or(0),
+        modules,
+        linkage,
+    })
+}
+
+fn parse_object_ids(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|entry| {
+                    entry
+                        .get("reference")
+                        .unwrap_or(entry)
+                        .get("objectId")
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_versioned_ids(value: Option<&Value>, version_key: &str) -> Vec<(String, u64)> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|entry| {
+                    let id = entry.get("objectId")?.as_str()?.to_string();
+                    let version = parse_u64(entry.get(version_key))?;
+                    Some((id, version))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_effects(effects: &Value, events: Option<&Value>) -> JsonRpcEffects {
+    let status = effects.get("status");
+    let gas = effects.get("gasUsed");
+    let gas_field = |key: &str| parse_u64(gas.and_then(|g| g.get(key))).unwrap_or(0);
+    JsonRpcEffects {
+        status: status
+            .and_then(|s| s.get("status"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        error: status
+            .and_then(|s| s.get("error"))
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        executed_epoch: parse_u64(effects.get("executedEpoch")),
+        computation_cost: gas_field("computationCost"),
+        storage_cost: gas_field("storageCost"),
+        storage_rebate: gas_field("storageRebate"),
+        non_refundable_storage_fee: gas_field("nonRefundableStorageFee"),
+        created: parse_object_ids(effects.get("created")),
+        mutated: parse_object_ids(effects.get("mutated")),
+        deleted: parse_object_ids(effects.get("deleted")),
+        wrapped: parse_object_ids(effects.get("wrapped")),
+        unwrapped: parse_object_ids(effects.get("unwrapped")),
+        modified_at_versions: parse_versioned_ids(
+            effects.get("modifiedAtVersions"),
+            "sequenceNumber",
+        ),
+        shared_objects: parse_versioned_ids(effects.get("sharedObjects"), "version"),
+        events_count: events.and_then(|v| v.as_array()).map_or(0, Vec::len),
+    }
+}
+
+/// The system state's protocol version, if the state is for `epoch`.
+fn epoch_protocol_version(system_state: &Value, epoch: u64) -> Option<u64> {
+    (parse_u64(system_state.get("epoch"))? == epoch)
+        .then(|| parse_u64(system_state.get("protocolVersion")))
+        .flatten()
+}
+
+impl JsonRpcClient {
+    /// Default request timeout in seconds (can be overridden by env).
+    const DEFAULT_TIMEOUT_SECS: u64 = 30;
+    /// Default connect timeout in seconds (can be overridden by env).
+    const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+
+    fn default_timeouts() -> (Duration, Duration) {
+        let timeout_secs: u64 = env_var_or("SUI_JSONRPC_TIMEOUT_SECS", Self::DEFAULT_TIMEOUT_SECS);
+        let connect_secs: u64 = env_var_or(
+            "SUI_JSONRPC_CONNECT_TIMEOUT_SECS",
+            Self::DEFAULT_CONNECT_TIMEOUT_SECS,
+        );
+        (
+            Duration::from_secs(timeout_secs),
+            Duration::from_secs(connect_secs),
+        )
+    }
+
+    /// Create a client for mainnet.
+    pub fn mainnet() -> Self {
+        Self::new("https://fullnode.mainnet.sui.io:443")
+    }
+
+    /// Create a client for testnet.
+    pub fn testnet() -> Self {
+        Self::new("https://fullnode.testnet.sui.io:443")
+    }
+
+    /// Create a client with a custom endpoint.
+    pub fn new(endpoint: &str) -> Self {
+        let (timeout, connect_timeout) = Self::default_timeouts();
+        Self::with_timeouts(endpoint, timeout, connect_timeout)
+    }
+
+    /// Create a client with explicit timeouts.
+    pub fn with_timeouts(endpoint: &str, timeout: Duration, connect_timeout: Duration) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(timeout)
+                .timeout_connect(connect_timeout)
+                .build(),
+            request_id: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// The endpoint this client talks to.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Total number of JSON-RPC requests made through this client.
+    pub fn request_count(&self) -> u64 {
+        self.request_id.load(Ordering::Relaxed)
+    }
+
+    /// Execute a JSON-RPC call and return the `result` field.
+    pub fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.request_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+
+        let response: Value = self
+            .agent
+            .post(&self.endpoint)
+            .set("Content-Type", "application/json")
+            .send_json(&body)
+            .map_err(|e| anyhow!("JSON-RPC request {} failed: {}", method, e))?
+            .into_json()
+            .map_err(|e| anyhow!("Failed to parse JSON-RPC response for {}: {}", method, e))?;
+
+        if let Some(error) = response.get("error") {
+            let msg = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("unknown error");
+            return Err(anyhow!("JSON-RPC error from {}: {}", method, msg));
+        }
+
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| anyhow!("No result in JSON-RPC response for {}", method))
+    }
+
+    fn object_options() -> Value {
+        serde_json::json!({
+            "showType": true,
+            "showOwner": true,
+            "showBcs": true,
+            "showPreviousTransaction": true,
+        })
+    }
+
+    /// Fetch the latest version of an object (`sui_getObject`).
+    pub fn get_object(&self, object_id: &str) -> Result<JsonRpcObject> {
+        let result = self.call(
+            "sui_getObject",
+            serde_json::json!([object_id, Self::object_options()]),
+        )?;
+        let data = result
+            .get("data")
+            .ok_or_else(|| anyhow!("Object not found: {}", object_id))?;
+        parse_object_data(data)
+    }
+
+    /// Fetch an object at a specific historical version (`sui_tryGetPastObject`).
+    pub fn try_get_past_object(&self, object_id: &str, version: u64) -> Result<JsonRpcObject> {
+        let result = self.call(
+            "sui_tryGetPastObject",
+            serde_json::json!([object_id, version, Self::object_options()]),
+        )?;
+        let status = result
+            .get("status")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+        if status != "VersionFound" {
+            return Err(anyhow!(
+                "Object {} at version {} not available: {}",
+                object_id,
+                version,
+                status
+            ));
+        }
+        let details = result
+            .get("details")
+            .ok_or_else(|| anyhow!("JSON-RPC past object response missing details"))?;
+        parse_object_data(details)
+    }
+
+    /// Fetch package bytecode and linkage (`sui_getObject` with `showBcs`).
+    pub fn get_package(&self, package_id: &str) -> Result<JsonRpcPackage> {
+        let result = self.call(
+            "sui_getObject",
+            serde_json::json!([package_id, { "showBcs": true }]),
+        )?;
+        let data = result
+            .get("data")
+            .ok_or_else(|| anyhow!("Package not found: {}", package_id))?;
+        parse_package_data(package_id, data)
+    }
+
+    /// Fetch normalized module signatures for a package
+    /// (`sui_getNormalizedMoveModulesByPackage`).
+    ///
+    /// Returns the raw JSON map of module name to normalized module. This does not
+    /// include bytecode; use [`JsonRpcClient::get_package`] for VM loading.
+    pub fn get_normalized_move_modules_by_package(&self, package_id: &str) -> Result<Value> {
+        self.call(
+            "sui_getNormalizedMoveModulesByPackage",
+            serde_json::json!([package_id]),
+        )
+    }
+
+    /// Fetch a transaction block with raw input and effects (`sui_getTransactionBlock`).
+    pub fn get_transaction_block(&self, digest: &str) -> Result<JsonRpcTransaction> {
+        let result = self.call(
+            "sui_getTransactionBlock",
+            serde_json::json!([digest, {
+                "showRawInput": true,
+                "showEffects": true,
+                "showEvents": true,
+            }]),
+        )?;
+        Ok(JsonRpcTransaction {
+            digest: result
+                .get("digest")
+                .and_then(|v| v.as_str())
+                .unwrap_or(digest)
+                .to_string(),
+            raw_transaction_base64: result
+                .get("rawTransaction")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            timestamp_ms: parse_u64(result.get("timestampMs")),
+            checkpoint: parse_u64(result.get("checkpoint")),
+            effects: result
+                .get("effects")
+                .map(|effects| parse_effects(effects, result.get("events"))),
+        })
+    }
+
+    /// Fetch the protocol version reported by the node (`sui_getProtocolConfig`).
+    ///
+    /// JSON-RPC only exposes the current protocol config, so for historical
+    /// transactions this is a best-effort approximation.
+    pub fn get_protocol_version(&self) -> Result<u64> {
+        let result = self.call("sui_getProtocolConfig", serde_json::json!([]))?;
+        parse_u64(result.get("protocolVersion"))
+            .ok_or_else(|| anyhow!("JSON-RPC protocol config missing protocolVersion"))
+    }
+
+    /// Fetch the protocol version `epoch` ran with (`suix_getLatestSuiSystemState`).
+    ///
+    /// JSON-RPC only reports the current epoch's version, so this is `None` for
+    /// any earlier epoch.
+    pub fn get_epoch_protocol_version(&self, epoch: u64) -> Result<Option<u64>> {
+        let result = self.call("suix_getLatestSuiSystemState", serde_json::json!([]))?;
+        Ok(epoch_protocol_version(&result, epoch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_creation() {
+        let mainnet = JsonRpcClient::mainnet();
+        assert!(mainnet.endpoint().contains("mainnet"));
+
+        let custom = JsonRpcClient::new("https://custom.endpoint");
+        assert_eq!(custom.endpoint(), "https://custom.endpoint");
+        assert_eq!(custom.request_count(), 0);
+    }
+
+    #[test]
+    fn test_parse_object_data() {
+        let data = serde_json::json!({
+            "objectId": "0x6",
+            "version": "42",
+            "digest": "abc",
+            "type": "0x2::clock::Clock",
+            "owner": { "Shared": { "initial_shared_version": 1 } },
+            "previousTransaction": "txdigest",
+            "bcs": { "dataType": "moveObject", "bcsBytes": "AQID" }
+        });
+        let obj = parse_object_data(&data).unwrap();
+        assert_eq!(obj.address, "0x6");
+        assert_eq!(obj.version, 42);
+        assert!(matches!(
+            obj.owner,
+            ObjectOwner::Shared { initial_version: 1 }
+        ));
+        assert_eq!(obj.decode_bcs(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_parse_package_data() {
+        let data = serde_json::json!({
+            "objectId": "0xabc",
+            "version": "3",
+            "bcs": {
+                "dataType": "package",
+                "id": "0xabc",
+                "version": 3,
+                "moduleMap": { "pool": "AQID" },
+                "linkageTable": {
+                    "0xdef": { "upgraded_id": "0x123", "upgraded_version": 2 }
+                }
+            }
+        });
+        let pkg = parse_package_data("0xabc", &data).unwrap();
+        assert_eq!(pkg.version, 3);
+        assert_eq!(pkg.modules, vec![("pool".to_string(), vec![1, 2, 3])]);
+        assert_eq!(pkg.linkage.len(), 1);
+        assert_eq!(pkg.linkage[0].original_id, "0xdef");
+        assert_eq!(pkg.linkage[0].upgraded_id, "0x123");
+
+        let not_pkg = serde_json::json!({ "bcs": { "dataType": "moveObject" } });
+        assert!(parse_package_data("0xabc", &not_pkg).is_err());
+    }
+
+    #[test]
+    fn test_parse_effects() {
+        let effects = serde_json::json!({
+            "status": { "status": "failure", "error": "MoveAbort(..., 1)" },
+            "executedEpoch": "500",
+            "gasUsed": {
+                "computationCost": "1000",
+                "storageCost": "2000",
+                "storageRebate": "500",
+                "nonRefundableStorageFee": "5"
+            },
+            "modifiedAtVersions": [{ "objectId": "0xa", "sequenceNumber": "7" }],
+            "sharedObjects": [{ "objectId": "0x6", "version": 99, "digest": "d" }],
+            "mutated": [{ "owner": "Immutable", "reference": { "objectId": "0xa" } }],
+            "deleted": [{ "objectId": "0xb", "version": 8 }]
+        });
+        let events = serde_json::json!([{ "type": "0x2::m::E" }, { "type": "0x2::m::F" }]);
+        let parsed = parse_effects(&effects, Some(&events));
+        assert!(!parsed.is_success());
+        assert_eq!(parsed.error.as_deref(), Some("MoveAbort(..., 1)"));
+        assert_eq!(parsed.executed_epoch, Some(500));
+        assert_eq!(parsed.computation_cost, 1000);
+        assert_eq!(parsed.modified_at_versions, vec![("0xa".to_string(), 7)]);
+        assert_eq!(parsed.shared_objects, vec![("0x6".to_string(), 99)]);
+        assert_eq!(parsed.mutated, vec!["0xa".to_string()]);
+        assert_eq!(parsed.deleted, vec!["0xb".to_string()]);
+        assert_eq!(parsed.events_count, 2);
+        assert_eq!(parse_effects(&effects, None).events_count, 0);
+    }
+
+    #[test]
+    fn test_epoch_protocol_version_only_for_current_epoch() {
+        let state = serde_json::json!({ "epoch": "812", "protocolVersion": "107" });
+        assert_eq!(epoch_protocol_version(&state, 812), Some(107));
+        assert_eq!(epoch_protocol_version(&state, 811), None);
+    }
+
+    /// Run with: cargo test test_fetch_framework_package_jsonrpc -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn test_fetch_framework_package_jsonrpc() {
+        let client = JsonRpcClient::mainnet();
+        let pkg = client.get_package("0x2").expect("fetch 0x2");
+        assert!(pkg.modules.iter().any(|(name, _)| name == "coin"));
+    }
+}
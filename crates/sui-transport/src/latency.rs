@@ -0,0 +1,125 @@
+//! Per-request latency collection for batch fetches.
+//!
+//! Fetch primitives record one sample per network request under a kind
+//! (`objects`, `packages`, `checkpoints`, ...); callers turn the samples into a
+//! [`LatencySummary`] per kind to tune concurrency empirically. Recording is a
+//! timestamp diff plus a push under a short-lived lock.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// Collects request durations grouped by fetch kind.
+#[derive(Debug, Default)]
+pub struct LatencyRecorder {
+    samples: Mutex<BTreeMap<String, Vec<Duration>>>,
+}
+
+impl LatencyRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one request of `kind` that took `elapsed`.
+    pub fn record(&self, kind: &str, elapsed: Duration) {
+        let mut samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples.entry(kind.to_string()).or_default().push(elapsed);
+    }
+
+    /// Record one request of `kind` that started at `start`.
+    pub fn record_since(&self, kind: &str, start: Instant) {
+        self.record(kind, start.elapsed());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    /// Latency distribution per kind; kinds without samples are omitted.
+    pub fn summary(&self) -> BTreeMap<String, LatencySummary> {
+        let samples = self.samples.lock().unwrap_or_else(|e| e.into_inner());
+        samples
+            .iter()
+            .filter_map(|(kind, durations)| {
+                LatencySummary::from_samples(durations).map(|summary| (kind.clone(), summary))
+            })
+            .collect()
+    }
+
+    /// Drop all recorded samples.
+    pub fn clear(&self) {
+        self.samples
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Request latency distribution in milliseconds (nearest-rank percentiles).
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencySummary {
+    /// Summarize `samples`, or `None` when there are none.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let ms = |d: Duration| d.as_nanos() as f64 / 1_000_000.0;
+        let percentile = |p: usize| {
+            let rank = (p * sorted.len()).div_ceil(100).max(1);
+            ms(sorted[rank - 1])
+        };
+        let total: Duration = sorted.iter().sum();
+        Some(Self {
+            count: sorted.len(),
+            mean_ms: ms(total) / sorted.len() as f64,
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            p99_ms: percentile(99),
+            max_ms: ms(sorted[sorted.len() - 1]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_reports_nearest_rank_percentiles_per_kind() {
+        let recorder = LatencyRecorder::new();
+        for ms in 1..=100u64 {
+            recorder.record("objects", Duration::from_millis(ms));
+        }
+        recorder.record("packages", Duration::from_millis(7));
+
+        let summary = recorder.summary();
+        let objects = &summary["objects"];
+        assert_eq!(objects.count, 100);
+        assert_eq!(objects.mean_ms, 50.5);
+        assert_eq!(objects.p50_ms, 50.0);
+        assert_eq!(objects.p95_ms, 95.0);
+        assert_eq!(objects.p99_ms, 99.0);
+        assert_eq!(objects.max_ms, 100.0);
+        assert_eq!(summary["packages"].p99_ms, 7.0);
+
+        recorder.clear();
+        assert!(recorder.is_empty());
+        assert!(LatencySummary::from_samples(&[]).is_none());
+    }
+}
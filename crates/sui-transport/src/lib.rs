@@ -1,10 +1,11 @@
 //! Sui Transport Layer
 //!
-//! Network transport for Sui data fetching via gRPC, GraphQL, and Walrus.
+//! Network transport for Sui data fetching via gRPC, GraphQL, JSON-RPC, and Walrus.
 //!
 //! This crate provides:
 //! - [`grpc`]: gRPC client for real-time streaming and batch fetching
 //! - [`graphql`]: GraphQL client for querying packages, objects, and transactions
+//! - [`jsonrpc`]: JSON-RPC client for full nodes that expose neither gRPC nor GraphQL
 //! - [`walrus`]: Walrus client for historical checkpoint archival data
 //!
 //! # Example
@@ -27,14 +28,27 @@
 //! ```
 
 pub mod blob;
+pub mod cassette;
+pub mod compression;
+pub mod error;
 pub mod graphql;
 pub mod grpc;
+pub mod http;
+pub mod jsonrpc;
+pub mod latency;
 pub mod network;
 pub mod walrus;
 
 // Re-export main types for convenience
-pub use graphql::{decode_graphql_modules, GraphQLClient};
+pub use compression::Compression;
+pub use error::{Remediation, TransportError};
+pub use graphql::{
+    decode_graphql_modules, GraphQLClient, GraphQLRetryPolicy, GraphQLRetryStats,
+    PackageModuleStream,
+};
 pub use grpc::GrpcClient;
+pub use jsonrpc::JsonRpcClient;
+pub use latency::{LatencyRecorder, LatencySummary};
 pub use walrus::WalrusClient;
 
 /// Create a Tokio runtime and connect to a gRPC endpoint.
@@ -1,7 +1,142 @@
+//! Network selection and default endpoints.
+//!
+//! [`Network`] names a Sui network and its public endpoints; client constructors such
+//! as [`GraphQLClient::for_network`](crate::graphql::GraphQLClient::for_network),
+//! [`GrpcClient::for_network`](crate::grpc::GrpcClient::for_network) and
+//! [`WalrusClient::for_network`](crate::walrus::WalrusClient::for_network) build on it.
+//! The `infer_*` / `resolve_*` helpers derive a network from endpoint URLs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
 const MAINNET_GRAPHQL: &str = "https://graphql.mainnet.sui.io/graphql";
 const TESTNET_GRAPHQL: &str = "https://graphql.testnet.sui.io/graphql";
 const DEVNET_GRAPHQL: &str = "https://graphql.devnet.sui.io/graphql";
 
+const MAINNET_RPC: &str = "https://fullnode.mainnet.sui.io:443";
+const TESTNET_RPC: &str = "https://fullnode.testnet.sui.io:443";
+const DEVNET_RPC: &str = "https://fullnode.devnet.sui.io:443";
+
+const MAINNET_GRPC: &str = "https://archive.mainnet.sui.io:443";
+const TESTNET_GRPC: &str = "https://fullnode.testnet.sui.io:443";
+const DEVNET_GRPC: &str = "https://fullnode.devnet.sui.io:443";
+
+pub(crate) const MAINNET_WALRUS_CACHING: &str = "https://walrus-sui-archival.mainnet.walrus.space";
+pub(crate) const MAINNET_WALRUS_AGGREGATOR: &str = "https://aggregator.walrus-mainnet.walrus.space";
+pub(crate) const TESTNET_WALRUS_CACHING: &str = "https://walrus-sui-archival.testnet.walrus.space";
+pub(crate) const TESTNET_WALRUS_AGGREGATOR: &str = "https://aggregator.walrus-testnet.walrus.space";
+
+/// A Sui network: one of the public ones, or a custom set of endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+    /// Devnet has no Walrus checkpoint archive; replays hydrate over gRPC/GraphQL.
+    Devnet,
+    /// Self-hosted or third-party endpoints, e.g. a localnet.
+    Custom(CustomNetwork),
+}
+
+/// Endpoints of a [`Network::Custom`] network.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CustomNetwork {
+    pub rpc_url: String,
+    pub graphql_url: String,
+    pub grpc_url: String,
+    /// Walrus checkpoint archive as (caching server, aggregator), if the network has one.
+    pub walrus: Option<(String, String)>,
+}
+
+impl Network {
+    /// Parse `mainnet`, `testnet` or `devnet` (case-insensitive).
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "mainnet" => Ok(Self::Mainnet),
+            "testnet" => Ok(Self::Testnet),
+            "devnet" => Ok(Self::Devnet),
+            other => Err(anyhow!(
+                "unknown network '{}': expected 'mainnet', 'testnet' or 'devnet'",
+                other
+            )),
+        }
+    }
+
+    /// The public network an endpoint URL belongs to, if its host names one.
+    pub fn from_url(url: &str) -> Option<Self> {
+        infer_network_from_url(url).and_then(|name| Self::parse(name).ok())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Testnet => "testnet",
+            Self::Devnet => "devnet",
+            Self::Custom(_) => "custom",
+        }
+    }
+
+    /// Full node JSON-RPC endpoint.
+    pub fn rpc_url(&self) -> &str {
+        match self {
+            Self::Mainnet => MAINNET_RPC,
+            Self::Testnet => TESTNET_RPC,
+            Self::Devnet => DEVNET_RPC,
+            Self::Custom(custom) => &custom.rpc_url,
+        }
+    }
+
+    pub fn graphql_url(&self) -> &str {
+        match self {
+            Self::Mainnet => MAINNET_GRAPHQL,
+            Self::Testnet => TESTNET_GRAPHQL,
+            Self::Devnet => DEVNET_GRAPHQL,
+            Self::Custom(custom) => &custom.graphql_url,
+        }
+    }
+
+    /// Default gRPC endpoint; the mainnet archive for mainnet, the full node elsewhere.
+    pub fn grpc_url(&self) -> &str {
+        match self {
+            Self::Mainnet => MAINNET_GRPC,
+            Self::Testnet => TESTNET_GRPC,
+            Self::Devnet => DEVNET_GRPC,
+            Self::Custom(custom) => &custom.grpc_url,
+        }
+    }
+
+    /// Walrus checkpoint archive as (caching server, aggregator), if the network has one.
+    pub fn walrus_urls(&self) -> Option<(&str, &str)> {
+        match self {
+            Self::Mainnet => Some((MAINNET_WALRUS_CACHING, MAINNET_WALRUS_AGGREGATOR)),
+            Self::Testnet => Some((TESTNET_WALRUS_CACHING, TESTNET_WALRUS_AGGREGATOR)),
+            Self::Devnet => None,
+            Self::Custom(custom) => custom
+                .walrus
+                .as_ref()
+                .map(|(caching, aggregator)| (caching.as_str(), aggregator.as_str())),
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        Self::parse(value)
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 pub fn infer_network_from_url(url: &str) -> Option<&'static str> {
     let lower = url.to_lowercase();
     if lower.contains("testnet") {
@@ -31,11 +166,10 @@ pub fn infer_network(rpc_url: &str, graphql_url: &str) -> String {
 }
 
 pub fn default_graphql_endpoint(network: &str) -> String {
-    match network {
-        "testnet" => TESTNET_GRAPHQL.to_string(),
-        "devnet" => DEVNET_GRAPHQL.to_string(),
-        _ => MAINNET_GRAPHQL.to_string(),
-    }
+    Network::parse(network)
+        .unwrap_or_default()
+        .graphql_url()
+        .to_string()
 }
 
 pub fn resolve_graphql_endpoint(rpc_url: &str) -> String {
@@ -50,9 +184,38 @@ pub fn resolve_graphql_endpoint(rpc_url: &str) -> String {
         return rpc_url.to_string();
     }
 
-    match infer_network_from_url(rpc_url) {
-        Some("testnet") => TESTNET_GRAPHQL.to_string(),
-        Some("devnet") => DEVNET_GRAPHQL.to_string(),
-        _ => MAINNET_GRAPHQL.to_string(),
+    Network::from_url(rpc_url)
+        .unwrap_or_default()
+        .graphql_url()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn networks_parse_and_expose_endpoints() {
+        let testnet: Network = "TestNet".parse().expect("testnet");
+        assert_eq!(testnet, Network::Testnet);
+        assert_eq!(testnet.graphql_url(), TESTNET_GRAPHQL);
+        assert_eq!(testnet.rpc_url(), TESTNET_RPC);
+        assert!(testnet.walrus_urls().is_some());
+        assert!(Network::Devnet.walrus_urls().is_none());
+        assert!(Network::parse("localnet").is_err());
+
+        assert_eq!(Network::from_url(DEVNET_RPC), Some(Network::Devnet));
+        assert_eq!(Network::from_url("http://127.0.0.1:9000"), None);
+        assert_eq!(default_graphql_endpoint("bogus"), MAINNET_GRAPHQL);
+
+        let custom = Network::Custom(CustomNetwork {
+            rpc_url: "http://127.0.0.1:9000".to_string(),
+            graphql_url: "http://127.0.0.1:9125/graphql".to_string(),
+            grpc_url: "http://127.0.0.1:9000".to_string(),
+            walrus: None,
+        });
+        assert_eq!(custom.name(), "custom");
+        assert_eq!(custom.graphql_url(), "http://127.0.0.1:9125/graphql");
+        assert!(custom.walrus_urls().is_none());
     }
 }
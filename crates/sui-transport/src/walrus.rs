@@ -19,17 +19,37 @@
 //! ```
 
 use crate::blob::Blob;
-use anyhow::{anyhow, Result};
+use crate::cassette::Cassette;
+use crate::compression::{decode_http_body_limited, read_to_end_limited, Compression};
+use crate::error::TransportError;
+use crate::latency::LatencyRecorder;
+use crate::network::{
+    Network, MAINNET_WALRUS_AGGREGATOR, MAINNET_WALRUS_CACHING, TESTNET_WALRUS_AGGREGATOR,
+    TESTNET_WALRUS_CACHING,
+};
+use anyhow::{anyhow, Context, Result};
 use base64::Engine;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::io::Read;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sui_types::base_types::{MoveObjectType, ObjectID, SequenceNumber, SuiAddress};
 use sui_types::digests::TransactionDigest;
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::object::{MoveObject, Object, Owner};
 
+/// Default per-request timeout (connect + full body read).
+///
+/// Override with `SUI_WALRUS_REQUEST_TIMEOUT_SECS` or [`WalrusClient::with_timeout`].
+pub const DEFAULT_WALRUS_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Default cap on a single aggregator blob response (512 MiB), applied both to
+/// the bytes on the wire and to the decompressed body.
+///
+/// Override with `SUI_WALRUS_MAX_BLOB_BYTES` or [`WalrusClient::with_max_blob_bytes`].
+pub const DEFAULT_WALRUS_MAX_BLOB_BYTES: u64 = 512 * 1024 * 1024;
+
 /// Walrus archival client for fetching historical checkpoint data.
 #[derive(Clone, Debug)]
 pub struct WalrusClient {
@@ -37,8 +57,18 @@ pub struct WalrusClient {
     caching_url: String,
     /// Base URL for the Walrus aggregator (blob data)
     aggregator_url: String,
-    /// HTTP client for requests
+    /// Connection-pooled HTTP agent shared with other clients (see [`crate::http`])
     http_client: ureq::Agent,
+    /// Encoding requested for aggregator blob transfers
+    compression: Compression,
+    /// Timeout applied to every request, including the body read
+    timeout: Duration,
+    /// Largest blob response accepted from the aggregator
+    max_blob_bytes: u64,
+    /// Optional sink for per-request latencies (`checkpoint_metadata`, `checkpoints`)
+    latency: Option<Arc<LatencyRecorder>>,
+    /// Records or serves this client's requests (see [`crate::cassette`])
+    cassette: Option<Arc<Cassette>>,
 }
 
 /// Response from /v1/app_checkpoint endpoint
@@ -57,44 +87,114 @@ pub struct CheckpointInfoResponse {
 impl WalrusClient {
     /// Create a client for Sui mainnet archival.
     pub fn mainnet() -> Self {
-        Self {
-            caching_url: "https://walrus-sui-archival.mainnet.walrus.space".to_string(),
-            aggregator_url: "https://aggregator.walrus-mainnet.walrus.space".to_string(),
-            http_client: ureq::Agent::new(),
-        }
+        Self::new(
+            MAINNET_WALRUS_CACHING.to_string(),
+            MAINNET_WALRUS_AGGREGATOR.to_string(),
+        )
     }
 
     /// Create a client for Sui testnet archival.
     pub fn testnet() -> Self {
-        Self {
-            caching_url: "https://walrus-sui-archival.testnet.walrus.space".to_string(),
-            aggregator_url: "https://aggregator.walrus-testnet.walrus.space".to_string(),
-            http_client: ureq::Agent::new(),
-        }
+        Self::new(
+            TESTNET_WALRUS_CACHING.to_string(),
+            TESTNET_WALRUS_AGGREGATOR.to_string(),
+        )
+    }
+
+    /// Create a client for `network`'s checkpoint archive.
+    ///
+    /// Fails for networks without one (devnet, custom networks without Walrus URLs).
+    pub fn for_network(network: &Network) -> Result<Self> {
+        let (caching, aggregator) = network.walrus_urls().ok_or_else(|| {
+            anyhow!(
+                "{} has no Walrus checkpoint archive; hydrate over gRPC/GraphQL instead",
+                network
+            )
+        })?;
+        Ok(Self::new(caching.to_string(), aggregator.to_string()))
     }
 
     /// Create a custom client with specific endpoints.
     pub fn new(caching_url: String, aggregator_url: String) -> Self {
+        let timeout = env_u64("SUI_WALRUS_REQUEST_TIMEOUT_SECS")
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_WALRUS_REQUEST_TIMEOUT);
         Self {
             caching_url,
             aggregator_url,
-            http_client: ureq::Agent::new(),
+            http_client: crate::http::shared_agent(),
+            compression: Compression::from_env(),
+            timeout,
+            max_blob_bytes: env_u64("SUI_WALRUS_MAX_BLOB_BYTES")
+                .unwrap_or(DEFAULT_WALRUS_MAX_BLOB_BYTES),
+            latency: None,
+            cassette: None,
+        }
+    }
+
+    /// Bound every request (metadata and blob) to `timeout`, including the body read.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Reject aggregator blob responses larger than `max_blob_bytes`.
+    pub fn with_max_blob_bytes(mut self, max_blob_bytes: u64) -> Self {
+        self.max_blob_bytes = max_blob_bytes;
+        self
+    }
+
+    /// Record each metadata and blob request's duration into `recorder`.
+    pub fn with_latency_recorder(mut self, recorder: Arc<LatencyRecorder>) -> Self {
+        self.latency = Some(recorder);
+        self
+    }
+
+    /// Record every metadata and blob request to `cassette`, or serve them from it in
+    /// playback mode.
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// GET on the shared agent, bounded by this client's timeout.
+    fn get(&self, url: &str) -> ureq::Request {
+        self.http_client.get(url).timeout(self.timeout)
+    }
+
+    fn record_latency(&self, kind: &str, start: Instant) {
+        if let Some(latency) = &self.latency {
+            latency.record_since(kind, start);
         }
     }
 
+    /// Request compressed blob transfers from the aggregator.
+    ///
+    /// Responses are decoded transparently from their `Content-Encoding`;
+    /// aggregators that don't compress simply return the raw bytes.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     /// Get the latest archived checkpoint number.
     ///
     /// Queries the homepage API to find the most recent checkpoint in Walrus.
     pub fn get_latest_checkpoint(&self) -> Result<u64> {
         let url = format!("{}/v1/app_info_for_homepage", self.caching_url);
 
-        let response: serde_json::Value = self
-            .http_client
-            .get(&url)
-            .call()
-            .map_err(|e| anyhow!("Failed to fetch homepage info: {}", e))?
-            .into_json()
-            .map_err(|e| anyhow!("Failed to parse homepage response: {}", e))?;
+        let response = crate::cassette::json_through(
+            self.cassette.as_deref(),
+            "walrus",
+            || serde_json::json!({ "url": url }),
+            || {
+                self.get(&url)
+                    .call()
+                    .map_err(|e| anyhow!("Failed to fetch homepage info: {}", e))?
+                    .into_json()
+                    .map_err(|e| anyhow!("Failed to parse homepage response: {}", e))
+            },
+        )?;
 
         let latest = response
             .get("latest_checkpoint")
@@ -113,13 +213,22 @@ impl WalrusClient {
             self.caching_url, checkpoint
         );
 
-        let response: CheckpointInfoResponse = self
-            .http_client
-            .get(&url)
-            .call()
-            .map_err(|e| anyhow!("Failed to fetch checkpoint metadata: {}", e))?
-            .into_json()
+        let start = Instant::now();
+        let response = crate::cassette::json_through(
+            self.cassette.as_deref(),
+            "walrus",
+            || serde_json::json!({ "url": url }),
+            || {
+                self.get(&url)
+                    .call()
+                    .map_err(|e| anyhow!("Failed to fetch checkpoint metadata: {}", e))?
+                    .into_json()
+                    .map_err(|e| anyhow!("Failed to parse checkpoint metadata: {}", e))
+            },
+        )?;
+        let response: CheckpointInfoResponse = serde_json::from_value(response)
             .map_err(|e| anyhow!("Failed to parse checkpoint metadata: {}", e))?;
+        self.record_latency("checkpoint_metadata", start);
 
         Ok(response)
     }
@@ -133,24 +242,76 @@ impl WalrusClient {
         offset: u64,
         length: u64,
     ) -> Result<Vec<u8>> {
+        if length > self.max_blob_bytes {
+            return Err(TransportError::ResponseTooLarge {
+                resource: format!("Walrus blob {} range", blob_id),
+                size: Some(length),
+                limit: self.max_blob_bytes,
+            }
+            .into());
+        }
+        crate::cassette::bytes_through(
+            self.cassette.as_deref(),
+            "walrus",
+            || serde_json::json!({ "blob_id": blob_id, "offset": offset, "length": length }),
+            || self.fetch_blob_range(blob_id, offset, length),
+        )
+    }
+
+    fn fetch_blob_range(&self, blob_id: &str, offset: u64, length: u64) -> Result<Vec<u8>> {
         let url = format!(
             "{}/v1/blobs/{}/byte-range?start={}&length={}",
             self.aggregator_url, blob_id, offset, length
         );
 
-        let response = self
-            .http_client
-            .get(&url)
-            .call()
-            .map_err(|e| anyhow!("Failed to fetch from Walrus aggregator: {}", e))?;
+        let start = Instant::now();
+        let mut request = self.get(&url);
+        if let Some(accept) = self.compression.accept_encoding() {
+            request = request.set("Accept-Encoding", accept);
+        }
+        let response = request.call().map_err(|e| {
+            if error_chain_timed_out(&e) {
+                self.timeout_error(blob_id)
+            } else {
+                anyhow!("Failed to fetch from Walrus aggregator: {}", e)
+            }
+        })?;
+        let content_encoding = response.header("Content-Encoding").map(str::to_string);
+        if let Some(declared) = response
+            .header("Content-Length")
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|declared| *declared > self.max_blob_bytes)
+        {
+            return Err(TransportError::ResponseTooLarge {
+                resource: format!("Walrus blob {} response", blob_id),
+                size: Some(declared),
+                limit: self.max_blob_bytes,
+            }
+            .into());
+        }
 
-        let mut bytes = Vec::new();
-        response
-            .into_reader()
-            .read_to_end(&mut bytes)
-            .map_err(|e| anyhow!("Failed to read response body: {}", e))?;
+        let bytes = read_to_end_limited(response.into_reader(), self.max_blob_bytes)
+            .map_err(|e| {
+                if e.chain().any(is_timeout_io_error) {
+                    self.timeout_error(blob_id)
+                } else {
+                    e
+                }
+            })
+            .with_context(|| format!("Failed to read Walrus blob {} response body", blob_id))?;
 
-        Ok(bytes)
+        self.record_latency("checkpoints", start);
+
+        decode_http_body_limited(content_encoding.as_deref(), bytes, self.max_blob_bytes)
+            .with_context(|| format!("Failed to decode Walrus blob {} response body", blob_id))
+    }
+
+    fn timeout_error(&self, blob_id: &str) -> anyhow::Error {
+        TransportError::RequestTimeout {
+            resource: format!("Walrus blob {}", blob_id),
+            timeout: self.timeout,
+        }
+        .into()
     }
 
     /// Get full checkpoint data from Walrus.
@@ -197,7 +358,6 @@ impl WalrusClient {
         );
 
         let response: CheckpointInfoResponse = self
-            .http_client
             .get(&url)
             .call()
             .map_err(|e| anyhow!("Failed to fetch checkpoint with content: {}", e))?
@@ -304,6 +464,44 @@ impl WalrusClient {
             .collect()
     }
 
+    /// Stream checkpoints `start..=end` in order, downloading ahead of the consumer.
+    ///
+    /// Uses [`CheckpointRangeOptions::default`]; see
+    /// [`get_checkpoint_range_with_options`](Self::get_checkpoint_range_with_options).
+    pub fn get_checkpoint_range(&self, start: u64, end: u64) -> CheckpointRangeStream {
+        self.get_checkpoint_range_with_options(start, end, CheckpointRangeOptions::default())
+    }
+
+    /// Stream checkpoints `start..=end` in order with explicit download settings.
+    ///
+    /// `options.parallel_downloads` worker threads fetch checkpoints concurrently while
+    /// the caller processes earlier ones, staying at most `options.prefetch` checkpoints
+    /// ahead of the last one consumed. A failed fetch is yielded as an `Err` for that
+    /// checkpoint and the stream continues; dropping the stream stops the workers.
+    ///
+    /// ```ignore
+    /// let walrus = WalrusClient::mainnet();
+    /// for item in walrus.get_checkpoint_range(239_615_000, 239_615_099) {
+    ///     let (sequence, checkpoint) = item?;
+    ///     println!("{}: {} transactions", sequence, checkpoint.transactions.len());
+    /// }
+    /// ```
+    pub fn get_checkpoint_range_with_options(
+        &self,
+        start: u64,
+        end: u64,
+        options: CheckpointRangeOptions,
+    ) -> CheckpointRangeStream {
+        let client = self.clone();
+        CheckpointRangeStream {
+            inner: OrderedPrefetch::spawn(start, end, options, move |checkpoint| {
+                client
+                    .get_checkpoint(checkpoint)
+                    .with_context(|| format!("failed to fetch checkpoint {}", checkpoint))
+            }),
+        }
+    }
+
     /// List available checkpoint blobs.
     ///
     /// Returns metadata about all archived checkpoint blobs including
@@ -316,7 +514,6 @@ impl WalrusClient {
         };
 
         let response: BlobListResponse = self
-            .http_client
             .get(&url)
             .call()
             .map_err(|e| anyhow!("Failed to list blobs: {}", e))?
@@ -609,6 +806,26 @@ struct BlobChunk {
     segments: Vec<CheckpointSegment>,
 }
 
+fn env_u64(key: &str) -> Option<u64> {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Whether `err` (or any of its sources) is the agent's timeout firing.
+fn error_chain_timed_out(err: &(dyn std::error::Error + 'static)) -> bool {
+    std::iter::successors(Some(err), |current| current.source()).any(is_timeout_io_error)
+}
+
+fn is_timeout_io_error(cause: &(dyn std::error::Error + 'static)) -> bool {
+    cause.downcast_ref::<std::io::Error>().is_some_and(|io| {
+        matches!(
+            io.kind(),
+            std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+        )
+    })
+}
+
 fn merge_segments_into_chunks(segs: &[CheckpointSegment], max_chunk_bytes: u64) -> Vec<BlobChunk> {
     let mut out: Vec<BlobChunk> = Vec::new();
     let mut current: Option<BlobChunk> = None;
@@ -655,6 +872,181 @@ fn merge_segments_into_chunks(segs: &[CheckpointSegment], max_chunk_bytes: u64)
     out
 }
 
+/// Download settings for [`WalrusClient::get_checkpoint_range_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointRangeOptions {
+    /// Checkpoints downloaded concurrently.
+    pub parallel_downloads: usize,
+    /// How far downloads may run ahead of the consumer; bounds the checkpoints held
+    /// in memory at once. Clamped to at least `parallel_downloads`.
+    pub prefetch: usize,
+}
+
+impl Default for CheckpointRangeOptions {
+    fn default() -> Self {
+        Self {
+            parallel_downloads: 4,
+            prefetch: 16,
+        }
+    }
+}
+
+/// In-order iterator over a checkpoint range, fed by background downloads.
+///
+/// Created by [`WalrusClient::get_checkpoint_range`].
+pub struct CheckpointRangeStream {
+    inner: OrderedPrefetch<CheckpointData>,
+}
+
+impl Iterator for CheckpointRangeStream {
+    type Item = Result<(u64, CheckpointData)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Fetches `start..=end` on worker threads and yields results in sequence order.
+///
+/// Workers claim sequence numbers from a shared window that only advances as the
+/// consumer takes items, so at most `prefetch` results are ever in flight or buffered.
+struct OrderedPrefetch<T> {
+    /// Next sequence number to yield; `None` once the range is exhausted.
+    next: Option<u64>,
+    end: u64,
+    window: Arc<PrefetchWindow>,
+    results: std::sync::mpsc::Receiver<(u64, Result<T>)>,
+    buffered: std::collections::BTreeMap<u64, Result<T>>,
+}
+
+struct PrefetchWindow {
+    state: std::sync::Mutex<PrefetchState>,
+    advanced: std::sync::Condvar,
+    prefetch: u64,
+}
+
+struct PrefetchState {
+    /// Next sequence number a worker may claim; `None` once all are claimed.
+    next_claim: Option<u64>,
+    /// Next sequence number the consumer will take.
+    next_yield: u64,
+    cancelled: bool,
+}
+
+impl PrefetchWindow {
+    fn lock(&self) -> std::sync::MutexGuard<'_, PrefetchState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Block until a sequence number inside the window is free, then claim it.
+    fn claim(&self, end: u64) -> Option<u64> {
+        let mut state = self.lock();
+        loop {
+            if state.cancelled {
+                return None;
+            }
+            let claim = state.next_claim?;
+            if claim < state.next_yield.saturating_add(self.prefetch) {
+                state.next_claim = (claim < end).then(|| claim + 1);
+                return Some(claim);
+            }
+            state = self.advanced.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+impl<T: Send + 'static> OrderedPrefetch<T> {
+    fn spawn<F>(start: u64, end: u64, options: CheckpointRangeOptions, fetch: F) -> Self
+    where
+        F: Fn(u64) -> Result<T> + Send + Sync + 'static,
+    {
+        let workers = options.parallel_downloads.max(1);
+        let window = Arc::new(PrefetchWindow {
+            state: std::sync::Mutex::new(PrefetchState {
+                next_claim: (start <= end).then_some(start),
+                next_yield: start,
+                cancelled: false,
+            }),
+            advanced: std::sync::Condvar::new(),
+            prefetch: options.prefetch.max(workers) as u64,
+        });
+        let (sender, results) = std::sync::mpsc::channel();
+        let fetch = Arc::new(fetch);
+        let span = end.saturating_sub(start).saturating_add(1);
+        for worker in 0..(workers as u64).min(span) {
+            let window = Arc::clone(&window);
+            let sender = sender.clone();
+            let fetch = Arc::clone(&fetch);
+            let spawned = std::thread::Builder::new()
+                .name(format!("walrus-range-{}", worker))
+                .spawn(move || {
+                    while let Some(sequence) = window.claim(end) {
+                        if sender.send((sequence, fetch(sequence))).is_err() {
+                            break;
+                        }
+                    }
+                });
+            if spawned.is_err() {
+                // Fewer workers only slow the stream down; the last sender is dropped
+                // below, so a stream with no workers ends with an error instead of hanging.
+                break;
+            }
+        }
+        drop(sender);
+
+        Self {
+            next: (start <= end).then_some(start),
+            end,
+            window,
+            results,
+            buffered: std::collections::BTreeMap::new(),
+        }
+    }
+
+    fn advance(&mut self, yielded: u64) {
+        self.next = (yielded < self.end).then(|| yielded + 1);
+        self.window.lock().next_yield = yielded.saturating_add(1);
+        self.window.advanced.notify_all();
+    }
+}
+
+impl<T: Send + 'static> Iterator for OrderedPrefetch<T> {
+    type Item = Result<(u64, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sequence = self.next?;
+        loop {
+            if let Some(result) = self.buffered.remove(&sequence) {
+                self.advance(sequence);
+                return Some(result.map(|value| (sequence, value)));
+            }
+            match self.results.recv() {
+                Ok((fetched, result)) => {
+                    self.buffered.insert(fetched, result);
+                }
+                Err(_) => {
+                    self.next = None;
+                    return Some(Err(anyhow!(
+                        "checkpoint download workers stopped before checkpoint {}",
+                        sequence
+                    )));
+                }
+            }
+        }
+    }
+}
+
+impl<T> Drop for OrderedPrefetch<T> {
+    fn drop(&mut self) {
+        self.window
+            .state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .cancelled = true;
+        self.window.advanced.notify_all();
+    }
+}
+
 /// Metadata about a checkpoint blob.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BlobInfo {
@@ -1076,6 +1468,100 @@ impl WalrusClient {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_blob_range_over_limit_is_rejected_before_fetch() {
+        // Unroutable aggregator: the size guard must fail before any request is made.
+        let client = WalrusClient::new(
+            "http://127.0.0.1:9".to_string(),
+            "http://127.0.0.1:9".to_string(),
+        )
+        .with_timeout(Duration::from_millis(50))
+        .with_max_blob_bytes(1024);
+
+        let err = client.fetch_checkpoint_bytes("blob", 0, 4096).unwrap_err();
+        assert_eq!(
+            TransportError::find(&err),
+            Some(&TransportError::ResponseTooLarge {
+                resource: "Walrus blob blob range".to_string(),
+                size: Some(4096),
+                limit: 1024,
+            })
+        );
+        assert!(
+            err.to_string().contains("exceeds the 1024-byte limit"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_timed_out_io_errors_are_detected_through_sources() {
+        #[derive(Debug)]
+        struct Wrapper(std::io::Error);
+        impl std::fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "transport: {}", self.0)
+            }
+        }
+        impl std::error::Error for Wrapper {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let timed_out = Wrapper(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "timed out",
+        ));
+        assert!(error_chain_timed_out(&timed_out));
+        let refused = Wrapper(std::io::Error::new(
+            std::io::ErrorKind::ConnectionRefused,
+            "refused",
+        ));
+        assert!(!error_chain_timed_out(&refused));
+    }
+
+    #[test]
+    fn test_ordered_prefetch_yields_in_order_within_window() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let in_flight = Arc::new(AtomicU64::new(0));
+        let peak = Arc::new(AtomicU64::new(0));
+        let options = CheckpointRangeOptions {
+            parallel_downloads: 3,
+            prefetch: 4,
+        };
+        let (counter, high) = (Arc::clone(&in_flight), Arc::clone(&peak));
+        let stream = OrderedPrefetch::spawn(10, 29, options, move |sequence| {
+            let now = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            high.fetch_max(now, Ordering::SeqCst);
+            // Later checkpoints finish first, so results arrive out of order.
+            std::thread::sleep(Duration::from_millis(30 - sequence));
+            if sequence == 15 {
+                return Err(anyhow!("checkpoint {} unavailable", sequence));
+            }
+            Ok(sequence * 2)
+        });
+
+        let mut seen = Vec::new();
+        for item in stream {
+            match item {
+                Ok((sequence, value)) => {
+                    assert_eq!(value, sequence * 2);
+                    seen.push(sequence);
+                }
+                Err(err) => assert!(err.to_string().contains("15 unavailable"), "{err}"),
+            }
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        }
+        let expected: Vec<u64> = (10..=29).filter(|sequence| *sequence != 15).collect();
+        assert_eq!(seen, expected);
+        // One over `prefetch`: the window advances before this loop's decrement runs.
+        assert!(peak.load(Ordering::SeqCst) <= 5, "window exceeded");
+
+        let empty = OrderedPrefetch::spawn(5, 4, options, |sequence| Ok(sequence));
+        assert_eq!(empty.count(), 0);
+    }
+
     #[test]
     #[ignore] // Requires network access
     fn test_get_latest_checkpoint() {
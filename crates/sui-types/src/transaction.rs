@@ -50,6 +50,18 @@ pub struct FetchedTransaction {
 
     /// Checkpoint that included this transaction
     pub checkpoint: Option<u64>,
+
+    /// Gas payment coins, in transaction order. The first coin is the primary
+    /// gas coin; any others are smashed into it before execution.
+    #[serde(default)]
+    pub gas_payment: Vec<GasPaymentObject>,
+}
+
+/// A coin referenced by the transaction's gas payment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasPaymentObject {
+    pub object_id: String,
+    pub version: u64,
 }
 
 /// A command in a Programmable Transaction Block.
@@ -190,6 +202,48 @@ pub struct TransactionEffectsSummary {
     /// This is extracted from effects.sharedObjects for historical replay.
     #[serde(default)]
     pub shared_object_versions: HashMap<String, u64>,
+
+    /// Per-object versions, digests and owners, when the effects source provides them.
+    /// Empty for sources that only report object IDs.
+    #[serde(default)]
+    pub object_changes: Vec<ObjectChangeDetail>,
+}
+
+/// How a transaction changed an object.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectChangeKind {
+    Created,
+    Mutated,
+    Deleted,
+    Wrapped,
+    Unwrapped,
+}
+
+/// One object in a transaction's effects, with the references on both sides.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ObjectChangeDetail {
+    pub object_id: String,
+    pub change: ObjectChangeKind,
+
+    /// Version before the transaction (None for created/unwrapped objects)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_version: Option<u64>,
+
+    /// Version after the transaction (the lamport version for written objects)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_version: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_digest: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_digest: Option<String>,
+
+    /// Owner after the transaction: `address:0x..`, `object:0x..`, `shared` or
+    /// `immutable` (None for objects that no longer exist).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
 }
 
 /// Transaction execution status.
@@ -200,7 +254,7 @@ pub enum TransactionStatus {
 }
 
 /// Gas usage summary.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct GasSummary {
     pub computation_cost: u64,
     pub storage_cost: u64,
@@ -208,6 +262,25 @@ pub struct GasSummary {
     pub non_refundable_storage_fee: u64,
 }
 
+impl GasSummary {
+    /// Net gas charged: computation + storage - rebate (negative when the rebate wins).
+    pub fn total(&self) -> i64 {
+        (self.computation_cost as i128 + self.storage_cost as i128 - self.storage_rebate as i128)
+            .clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+}
+
+/// Net change of one owner's balance of one coin type, as in RPC `balanceChanges`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    /// Address whose balance changed.
+    pub owner: String,
+    /// Coin type, e.g. `0x2::sui::SUI`.
+    pub coin_type: String,
+    /// Signed balance delta (negative when the owner's balance went down).
+    pub amount: i128,
+}
+
 /// Result of replaying a transaction locally.
 #[derive(Debug, Clone, Serialize)]
 pub struct ReplayResult {
@@ -1035,6 +1108,23 @@ mod tests {
         assert_eq!(digest.0, "abc123");
     }
 
+    #[test]
+    fn test_gas_summary_total_nets_rebate() {
+        let gas = GasSummary {
+            computation_cost: 1_000,
+            storage_cost: 2_000,
+            storage_rebate: 500,
+            non_refundable_storage_fee: 5,
+        };
+        assert_eq!(gas.total(), 2_500);
+
+        let rebate_heavy = GasSummary {
+            storage_rebate: 4_000,
+            ..gas
+        };
+        assert_eq!(rebate_heavy.total(), -1_000);
+    }
+
     #[test]
     fn test_cached_transaction_new() {
         let tx = FetchedTransaction {
@@ -1047,6 +1137,7 @@ mod tests {
             effects: None,
             timestamp_ms: None,
             checkpoint: None,
+            gas_payment: vec![],
         };
 
         let cached = CachedTransaction::new(tx);
@@ -1067,6 +1158,7 @@ mod tests {
             effects: None,
             timestamp_ms: None,
             checkpoint: None,
+            gas_payment: vec![],
         };
 
         let mut cached = CachedTransaction::new(tx);
@@ -1093,6 +1185,7 @@ mod tests {
             effects: None,
             timestamp_ms: None,
             checkpoint: None,
+            gas_payment: vec![],
         };
 
         let mut cached = CachedTransaction::new(tx);
@@ -1114,6 +1207,7 @@ mod tests {
             gas_used: GasSummary::default(),
             events_count: 0,
             shared_object_versions: HashMap::new(),
+            object_changes: vec![],
         };
 
         let comparison = EffectsComparison::compare(&effects, true, 1, 1, 0);
@@ -1136,6 +1230,7 @@ mod tests {
             gas_used: GasSummary::default(),
             events_count: 0,
             shared_object_versions: HashMap::new(),
+            object_changes: vec![],
         };
 
         let comparison = EffectsComparison::compare(&effects, false, 0, 0, 0);
@@ -0,0 +1,546 @@
+#!/usr/bin/env python3
+"""Generate crates/sui-python/sui_sandbox.pyi from the pyo3 bindings.
+
+Parses the `#[pyfunction]`, `#[pyclass]` and `#[pymethods]` definitions in
+crates/sui-python/src together with their `#[pyo3(signature = ...)]` attributes
+and `///` doc comments, then emits stubs in the order the module registers them
+(crates/sui-python/src/module_registration.rs).
+
+Usage:
+    python scripts/generate_python_stubs.py          # rewrite the .pyi
+    python scripts/generate_python_stubs.py --check  # exit 1 if the .pyi is stale
+"""
+
+from __future__ import annotations
+
+import argparse
+import re
+import sys
+from dataclasses import dataclass, field
+from pathlib import Path
+
+
+ROOT = Path(__file__).resolve().parents[1]
+PY_CRATE = ROOT / "crates" / "sui-python"
+SRC_DIR = PY_CRATE / "src"
+REGISTRATION = SRC_DIR / "module_registration.rs"
+STUB_PATH = PY_CRATE / "sui_sandbox.pyi"
+
+HEADER = (
+    "# Generated by scripts/generate_python_stubs.py from the pyo3 bindings in\n"
+    "# crates/sui-python/src. Do not edit by hand; rerun the script instead.\n"
+)
+
+# `PyObject` results are JSON payloads converted by `json_value_to_py`; almost all
+# of them are dicts. These are the exceptions.
+RETURN_OVERRIDES = {
+    "snapshot_list": "List[Dict[str, Any]]",
+    "protocol_adapters": "List[Dict[str, Any]]",
+    "call_view_functions": "List[Dict[str, Any]]",
+    "historical_decode_returns_typed": "Optional[List[Dict[str, Any]]]",
+    "AnalyzedTransaction.inputs": "List[Dict[str, Any]]",
+    "AnalyzedTransaction.commands": "List[Dict[str, Any]]",
+    "ForkSession.get_object": "Optional[Dict[str, Any]]",
+    "stream_package_modules": "PackageModuleIterator",
+    "subscribe_checkpoints": "CheckpointSubscription",
+    "PackageModuleIterator.__iter__": "PackageModuleIterator",
+    "CheckpointSubscription.__iter__": "CheckpointSubscription",
+}
+
+# Arguments taken as `PyDict`/`PyAny` and walked by hand in Rust; spell out the
+# shape the binding actually accepts.
+PARAM_OVERRIDES = {
+    "call_view_function.child_fetcher": "Optional[Callable[[str, str], Optional[Tuple[str, bytes]]]]",
+    "call_view_function.child_objects": "Optional[Dict[str, List[Dict[str, Any]]]]",
+    "call_view_function.historical_versions": "Optional[Dict[str, int]]",
+    "call_view_functions.child_fetcher": "Optional[Callable[[str, str], Optional[Tuple[str, bytes]]]]",
+    "call_view_functions.child_objects": "Optional[Dict[str, List[Dict[str, Any]]]]",
+    "call_view_functions.historical_versions": "Optional[Dict[str, int]]",
+    "discover_and_replay.on_result": "Optional[Callable[[Dict[str, Any]], Any]]",
+    "ForkSession.execute_ptb.inputs": "List[Dict[str, Any]]",
+    "ForkSession.execute_ptb.commands": "List[Dict[str, Any]]",
+    "PtbBuilder.split_coins.amounts": "List[Any]",
+    "PtbBuilder.merge_coins.sources": "List[Any]",
+    "PtbBuilder.transfer_objects.objects": "List[Any]",
+    "PtbBuilder.move_call.args": "Optional[List[Any]]",
+    "PtbBuilder.make_move_vec.elements": "List[Any]",
+    "historical_decode_with_schema.schema": "List[Dict[str, Any]]",
+    "historical_series_from_points.points": "List[Dict[str, Any]]",
+    "historical_series_from_points.schema": "Optional[List[Dict[str, Any]]]",
+    "replay_ab.config_a": "Optional[Dict[str, Any]]",
+    "replay_ab.config_b": "Optional[Dict[str, Any]]",
+}
+
+SKIPPED_PARAM_TYPES = ("Python<", "PyRef<", "PyRefMut<", "Bound<'_, PyModule>")
+LINE_WIDTH = 88
+
+INT_TYPES = {"u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"}
+STR_TYPES = {"str", "String", "PathBuf", "Path", "OsString"}
+PY_OBJECT_TYPES = {
+    "PyDict": "Dict[str, Any]",
+    "PyList": "List[Any]",
+    "PyBytes": "bytes",
+    "PyString": "str",
+    "PyAny": "Any",
+    "PyModule": "Any",
+}
+
+
+@dataclass
+class Param:
+    name: str
+    annotation: str
+    has_default: bool = False
+
+
+@dataclass
+class Function:
+    rust_name: str
+    py_name: str
+    doc: list[str]
+    params: list[Param]
+    keyword_only_at: int | None
+    returns: str
+    is_async: bool = False
+
+
+@dataclass
+class PyClass:
+    rust_name: str
+    py_name: str
+    doc: list[str]
+    methods: list[Function] = field(default_factory=list)
+
+
+# ---------------------------------------------------------------------------
+# Rust source scanning
+# ---------------------------------------------------------------------------
+
+
+def split_top_level(text: str, sep: str = ",") -> list[str]:
+    """Split on `sep` outside of (), [], <> and string literals."""
+    parts: list[str] = []
+    depth = 0
+    in_str = False
+    current: list[str] = []
+    prev = ""
+    for ch in text:
+        if in_str:
+            current.append(ch)
+            if ch == '"' and prev != "\\":
+                in_str = False
+        elif ch == '"':
+            in_str = True
+            current.append(ch)
+        elif ch in "([<{":
+            depth += 1
+            current.append(ch)
+        elif ch in ")]}" or (ch == ">" and prev != "-"):
+            depth -= 1
+            current.append(ch)
+        elif ch == sep and depth == 0:
+            parts.append("".join(current).strip())
+            current = []
+        else:
+            current.append(ch)
+        prev = ch
+    tail = "".join(current).strip()
+    if tail:
+        parts.append(tail)
+    return [p for p in parts if p]
+
+
+def matching_close(text: str, open_idx: int) -> int:
+    """Index of the bracket closing the one at `open_idx`."""
+    pairs = {"(": ")", "[": "]", "{": "}", "<": ">"}
+    opener = text[open_idx]
+    closer = pairs[opener]
+    depth = 0
+    in_str = False
+    for idx in range(open_idx, len(text)):
+        ch = text[idx]
+        if in_str:
+            if ch == '"' and text[idx - 1] != "\\":
+                in_str = False
+            continue
+        if ch == '"':
+            in_str = True
+        elif ch == opener:
+            depth += 1
+        elif ch == closer and not (closer == ">" and text[idx - 1] == "-"):
+            depth -= 1
+            if depth == 0:
+                return idx
+    raise ValueError(f"unbalanced {opener!r} in: {text[open_idx:open_idx + 80]!r}")
+
+
+def strip_generics_prefix(ty: str) -> str:
+    ty = ty.strip()
+    ty = re.sub(r"^&\s*('\w+\s+)?(mut\s+)?", "", ty)
+    return ty.strip()
+
+
+def rust_to_py(ty: str) -> str:
+    ty = strip_generics_prefix(ty)
+    if ty in ("()", ""):
+        return "None"
+    if ty.startswith("(") and ty.endswith(")"):
+        inner = [rust_to_py(t) for t in split_top_level(ty[1:-1])]
+        return f"Tuple[{', '.join(inner)}]"
+    if ty.startswith("[") and ty.endswith("]"):
+        inner = ty[1:-1].strip()
+        return "bytes" if inner == "u8" else f"List[{rust_to_py(inner)}]"
+
+    match = re.match(r"^([\w:]+)\s*(?:<(.*)>)?$", ty, re.S)
+    if not match:
+        return "Any"
+    path, generic = match.group(1), match.group(2)
+    name = path.split("::")[-1]
+    args = split_top_level(generic) if generic else []
+    args = [a for a in args if not a.startswith("'")]
+
+    if name in INT_TYPES:
+        return "int"
+    if name in ("f32", "f64"):
+        return "float"
+    if name == "bool":
+        return "bool"
+    if name in STR_TYPES:
+        return "str"
+    if name in ("PyResult", "Result") and args:
+        return rust_to_py(args[0])
+    if name == "Option" and args:
+        inner = rust_to_py(args[0])
+        return inner if inner.startswith("Optional[") else f"Optional[{inner}]"
+    if name == "Vec" and args:
+        return "bytes" if strip_generics_prefix(args[0]) == "u8" else f"List[{rust_to_py(args[0])}]"
+    if name in ("HashMap", "BTreeMap", "IndexMap") and len(args) == 2:
+        return f"Dict[{rust_to_py(args[0])}, {rust_to_py(args[1])}]"
+    if name in ("HashSet", "BTreeSet") and args:
+        return f"List[{rust_to_py(args[0])}]"
+    if name in ("Bound", "Borrowed", "Py") and args:
+        return PY_OBJECT_TYPES.get(strip_generics_prefix(args[-1]), "Any")
+    if name in ("PyObject", "PyAny"):
+        return "Any"
+    if name in PY_OBJECT_TYPES:
+        return PY_OBJECT_TYPES[name]
+    return "Any"
+
+
+def doc_text(lines: list[str]) -> list[str]:
+    out = []
+    for line in lines:
+        text = line.strip()[3:]
+        out.append(text[1:] if text.startswith(" ") else text)
+    while out and not out[-1].strip():
+        out.pop()
+    return out
+
+
+def attr_value(attrs: list[str], key: str) -> str | None:
+    for attr in attrs:
+        match = re.search(rf'\b{key}\s*=\s*"([^"]+)"', attr)
+        if match:
+            return match.group(1)
+    return None
+
+
+def parse_signature_attr(attrs: list[str]) -> list[str] | None:
+    for attr in attrs:
+        idx = attr.find("signature")
+        if not attr.startswith("#[pyo3") or idx < 0:
+            continue
+        open_idx = attr.index("(", idx)
+        return split_top_level(attr[open_idx + 1 : matching_close(attr, open_idx)])
+    return None
+
+
+def build_function(
+    rust_name: str,
+    sig_text: str,
+    attrs: list[str],
+    doc: list[str],
+    owner: str | None,
+) -> Function:
+    open_idx = sig_text.index("(")
+    close_idx = matching_close(sig_text, open_idx)
+    raw_params = split_top_level(sig_text[open_idx + 1 : close_idx])
+    ret_match = re.match(r"\s*->\s*(.+?)\s*(where\b.*)?$", sig_text[close_idx + 1 :], re.S)
+    rust_ret = ret_match.group(1) if ret_match else "()"
+
+    typed: dict[str, str] = {}
+    order: list[str] = []
+    for raw in raw_params:
+        if raw in ("self", "&self", "&mut self") or raw.startswith("mut self"):
+            continue
+        name, _, ty = raw.partition(":")
+        name = name.replace("mut ", "").strip()
+        ty = ty.strip()
+        if name == "slf" or any(marker in ty for marker in SKIPPED_PARAM_TYPES):
+            continue
+        typed[name] = ty
+        order.append(name)
+
+    params: list[Param] = []
+    keyword_only_at: int | None = None
+    signature = parse_signature_attr(attrs)
+    if signature is not None:
+        for item in signature:
+            if item == "*":
+                keyword_only_at = len(params)
+                continue
+            name, eq, _default = item.partition("=")
+            name = name.strip()
+            if name.startswith("*"):
+                stars = "**" if name.startswith("**") else "*"
+                params.append(Param(f"{stars}{name.lstrip('*')}", "Any"))
+                continue
+            params.append(Param(name, rust_to_py(typed.get(name, "PyAny")), bool(eq)))
+    else:
+        # Without an explicit signature pyo3 makes trailing `Option<T>` args optional.
+        trailing_optional = True
+        for name in reversed(order):
+            is_option = re.match(r"^Option\s*<", strip_generics_prefix(typed[name])) is not None
+            trailing_optional = trailing_optional and is_option
+            params.insert(0, Param(name, rust_to_py(typed[name]), trailing_optional))
+
+    is_new = "#[new]" in attrs
+    py_name = "__init__" if is_new else (attr_value(attrs, "name") or rust_name)
+    key = f"{owner}.{py_name}" if owner else py_name
+    for param in params:
+        param.annotation = PARAM_OVERRIDES.get(f"{key}.{param.name}", param.annotation)
+    if is_new:
+        returns = "None"
+    elif key in RETURN_OVERRIDES:
+        returns = RETURN_OVERRIDES[key]
+    elif re.search(r"\bPyObject\b|Py<PyAny>", rust_ret):
+        returns = rust_to_py(rust_ret).replace("Any", "Dict[str, Any]", 1)
+    else:
+        returns = rust_to_py(rust_ret)
+    return Function(rust_name, py_name, doc, params, keyword_only_at, returns)
+
+
+def scan_source(path: Path, functions: dict[str, Function], classes: dict[str, PyClass]) -> None:
+    lines = path.read_text().splitlines()
+    docs: list[str] = []
+    attrs: list[str] = []
+    impl_owner: str | None = None
+    pending_pymethods = False
+    idx = 0
+    while idx < len(lines):
+        line = lines[idx]
+        stripped = line.strip()
+
+        if impl_owner is not None and line.startswith("}"):
+            impl_owner = None
+
+        if stripped.startswith("///"):
+            docs.append(stripped)
+            idx += 1
+            continue
+        if stripped.startswith("#["):
+            attr = stripped
+            while attr.count("[") > attr.count("]") or attr.count("(") > attr.count(")"):
+                idx += 1
+                attr += " " + lines[idx].strip()
+            attrs.append(attr)
+            if attr == "#[pymethods]":
+                pending_pymethods = True
+            idx += 1
+            continue
+
+        struct_match = re.match(r"^(?:pub(?:\([\w:]+\))?\s+)?struct\s+(\w+)", stripped)
+        if struct_match and any(a.startswith("#[pyclass") for a in attrs):
+            rust_name = struct_match.group(1)
+            py_name = attr_value([a for a in attrs if a.startswith("#[pyclass")], "name")
+            classes[rust_name] = PyClass(rust_name, py_name or rust_name, doc_text(docs))
+
+        impl_match = re.match(r"^impl\s+(\w+)\s*\{", stripped)
+        if impl_match and pending_pymethods:
+            impl_owner = impl_match.group(1)
+            pending_pymethods = False
+
+        fn_match = re.match(r"^(?:pub(?:\([\w:]+\))?\s+)?fn\s+(\w+)", stripped)
+        is_binding = "#[pyfunction]" in attrs or (impl_owner is not None and line.startswith("    fn"))
+        if fn_match and is_binding:
+            sig_lines = [stripped]
+            while "{" not in sig_lines[-1] and not sig_lines[-1].endswith(";"):
+                idx += 1
+                sig_lines.append(lines[idx].strip())
+            sig_text = " ".join(sig_lines)
+            sig_text = sig_text[sig_text.index("fn ") :]
+            sig_text = sig_text[: sig_text.rindex("{")].strip()
+            if impl_owner is None:
+                fn = build_function(fn_match.group(1), sig_text, attrs, doc_text(docs), None)
+                functions[fn.rust_name] = fn
+            else:
+                owner_py = classes[impl_owner].py_name if impl_owner in classes else impl_owner
+                fn = build_function(fn_match.group(1), sig_text, attrs, doc_text(docs), owner_py)
+                classes.setdefault(impl_owner, PyClass(impl_owner, owner_py, [])).methods.append(fn)
+
+        docs = []
+        attrs = []
+        idx += 1
+
+
+# ---------------------------------------------------------------------------
+# Registration order
+# ---------------------------------------------------------------------------
+
+
+def registration_order(text: str) -> list[tuple[str, str, str | None]]:
+    """Entries of ("function" | "class" | "alias" | "attr", name, target)."""
+    entries: list[tuple[str, str, str | None]] = []
+    getattr_vars: dict[str, str] = {}
+    for raw in text.splitlines():
+        line = raw.strip()
+        if m := re.search(r"wrap_pyfunction!\((\w+)", line):
+            entries.append(("function", m.group(1), None))
+        elif m := re.search(r"add_class::<(\w+)>", line):
+            entries.append(("class", m.group(1), None))
+        elif m := re.match(r'let\s+(\w+)\s*=\s*m\.getattr\("(\w+)"\)', line):
+            getattr_vars[m.group(1)] = m.group(2)
+        elif m := re.match(r'm\.add\("(\w+)",\s*(\w+)', line):
+            name, value = m.group(1), m.group(2)
+            if value in getattr_vars:
+                entries.append(("alias", name, getattr_vars[value]))
+            else:
+                entries.append(("attr", name, None))
+    return entries
+
+
+# ---------------------------------------------------------------------------
+# Rendering
+# ---------------------------------------------------------------------------
+
+
+def render_params(fn: Function, is_method: bool) -> list[str]:
+    rendered = ["self"] if is_method else []
+    for idx, param in enumerate(fn.params):
+        if fn.keyword_only_at == idx:
+            rendered.append("*")
+        if param.name.startswith("*"):
+            rendered.append(param.name)
+            continue
+        text = f"{param.name}: {param.annotation}"
+        rendered.append(text + (" = ..." if param.has_default else ""))
+    if fn.keyword_only_at is not None and fn.keyword_only_at == len(fn.params):
+        rendered.append("*")
+    return rendered
+
+
+def render_docstring(doc: list[str], indent: str) -> list[str]:
+    if not doc:
+        return []
+    body = [line.replace("\\", "\\\\").replace('"""', '\\"\\"\\"') for line in doc]
+    if len(body) == 1:
+        return [f'{indent}"""{body[0]}"""']
+    out = [f'{indent}"""{body[0]}']
+    out.extend(f"{indent}{line}".rstrip() for line in body[1:])
+    out.append(f'{indent}"""')
+    return out
+
+
+def render_function(fn: Function, indent: str = "", is_method: bool = False) -> list[str]:
+    params = render_params(fn, is_method)
+    body_indent = indent + "    "
+    tail = ":" if fn.doc else ": ..."
+    keyword = "async def" if fn.is_async else "def"
+    one_line = f"{indent}{keyword} {fn.py_name}({', '.join(params)}) -> {fn.returns}{tail}"
+    if len(one_line) <= LINE_WIDTH:
+        out = [one_line]
+    else:
+        out = [f"{indent}{keyword} {fn.py_name}("]
+        out.extend(f"{body_indent}{param}," for param in params)
+        out.append(f"{indent}) -> {fn.returns}{tail}")
+    out.extend(render_docstring(fn.doc, body_indent))
+    return out
+
+
+def render_stub(
+    functions: dict[str, Function],
+    classes: dict[str, PyClass],
+    order: list[tuple[str, str, str | None]],
+) -> str:
+    blocks: list[list[str]] = []
+    attrs = [f"{name}: str" for kind, name, _ in order if kind == "attr"]
+    if attrs:
+        blocks.append(attrs)
+
+    for kind, name, target in order:
+        if kind == "class":
+            cls = classes.get(name)
+            if cls is None:
+                raise SystemExit(f"registered class {name} has no #[pyclass] definition")
+            lines = [f"class {cls.py_name}:"]
+            lines.extend(render_docstring(cls.doc, "    "))
+            methods = sorted(cls.methods, key=lambda m: m.py_name != "__init__")
+            for method in methods:
+                lines.extend(render_function(method, "    ", is_method=True))
+            if len(lines) == 1:
+                lines[0] += " ..."
+            blocks.append(lines)
+        elif kind == "alias":
+            base = classes.get(target or "")
+            base_name = base.py_name if base else target
+            blocks.append([f"class {name}({base_name}): ..."])
+
+    for kind, name, _ in order:
+        if kind != "function":
+            continue
+        fn = functions.get(name)
+        if fn is None:
+            raise SystemExit(f"registered function {name} has no #[pyfunction] definition")
+        blocks.append(render_function(fn))
+
+    body = "\n\n\n".join("\n".join(block) for block in blocks)
+    used = [name for name in ("Any", "Callable", "Dict", "List", "Optional", "Tuple") if re.search(rf"\b{name}\b", body)]
+    imports = f"from typing import {', '.join(used)}\n\n" if used else ""
+    return f"{HEADER}\n{imports}{body}\n"
+
+
+def resolve_async_variants(functions: dict[str, Function]) -> None:
+    """`foo_async` mirrors `foo`'s signature and forwards to it: stub it as `async def`
+    with foo's parameters (including their overrides) and return type."""
+    for fn in functions.values():
+        sync = functions.get(fn.rust_name.removesuffix("_async"))
+        if sync is None or sync is fn or [p.name for p in fn.params] != [p.name for p in sync.params]:
+            continue
+        fn.params = sync.params
+        fn.keyword_only_at = sync.keyword_only_at
+        fn.returns = sync.returns
+        fn.is_async = True
+
+
+def generate() -> str:
+    functions: dict[str, Function] = {}
+    classes: dict[str, PyClass] = {}
+    for path in sorted(SRC_DIR.glob("*.rs")):
+        scan_source(path, functions, classes)
+    resolve_async_variants(functions)
+    order = registration_order(REGISTRATION.read_text())
+    return render_stub(functions, classes, order)
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__.splitlines()[0])
+    parser.add_argument("--check", action="store_true", help="fail if the stub file is stale")
+    parser.add_argument("--output", type=Path, default=STUB_PATH, help="stub file to write")
+    args = parser.parse_args()
+
+    generated = generate()
+    if args.check:
+        current = args.output.read_text() if args.output.exists() else ""
+        if current != generated:
+            print(f"{args.output} is out of date; run python scripts/generate_python_stubs.py")
+            return 1
+        print(f"Python stubs up to date: {args.output}")
+        return 0
+
+    args.output.write_text(generated)
+    print(f"Wrote {args.output}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
@@ -338,6 +338,9 @@ impl ReplayExecutionArgs {
             vm_only: self.vm_only,
             strict: self.strict,
             compare: self.compare,
+            diff_objects: false,
+            gas_profile: false,
+            reconcile: false,
             analyze_only: self.analyze_only,
             verbose: false,
             fetch_strategy: self.fetch_strategy,
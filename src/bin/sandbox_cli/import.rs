@@ -8,7 +8,7 @@ use std::path::PathBuf;
 use super::network::sandbox_home;
 use super::output::format_error;
 use super::SandboxState;
-use sui_state_fetcher::{import_replay_states, ImportSpec};
+use sui_state_fetcher::{import_replay_states, ImportSpec, ReplayStateFormat};
 
 #[derive(Parser, Debug)]
 pub struct ImportCmd {
@@ -64,6 +64,7 @@ impl ImportCmd {
             transactions: self.transactions.clone(),
             objects: self.objects.clone(),
             packages: self.packages.clone(),
+            state_format: ReplayStateFormat::Auto,
         };
 
         let result = import_replay_states(&output_dir, &spec);
@@ -21,7 +21,12 @@ use super::SandboxState;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::TypeTag;
 use sui_prefetch::compute_dynamic_field_id;
-use sui_sandbox_core::tx_replay::{self, EffectsReconcilePolicy};
+use sui_sandbox_core::effects_reconciliation::EffectsReconciliation;
+use sui_sandbox_core::error_context::TransactionAbortInfo;
+use sui_sandbox_core::gas_profile::GasProfile;
+use sui_sandbox_core::object_diff::{build_object_diffs, ObjectDiff};
+use sui_sandbox_core::sandbox_error::{SandboxError, SandboxErrorKind};
+use sui_sandbox_core::tx_replay::{self, BalanceChange, EffectsReconcilePolicy};
 use sui_sandbox_core::types::parse_type_tag;
 use sui_sandbox_types::{PtbCommand, TransactionInput};
 use sui_state_fetcher::{
@@ -57,12 +62,13 @@ use self::deps::{
 use self::dynamic_fields::{
     fetch_child_object_by_key, resolve_key_type_via_graphql, ChildFetchOptions, MissEntry,
 };
-use self::effects::{build_effects_summary, build_execution_path};
+use self::effects::{build_effects_summary, build_execution_path, build_reconciliation};
 use self::execute_state::{execute_from_json, execute_replay_state};
 use self::mutate::ReplayMutateCmd;
 use self::support::{
-    build_replay_object_maps, build_simulation_config, emit_linkage_debug_info,
-    ensure_system_objects, hydrate_resolver_from_replay_state, maybe_patch_replay_objects,
+    backfill_replay_clock_timestamp, build_replay_object_maps, build_simulation_config,
+    emit_linkage_debug_info, ensure_system_objects, hydrate_resolver_from_replay_state,
+    maybe_patch_replay_objects,
 };
 use self::synthesis::synthesize_missing_inputs;
 #[cfg(feature = "walrus")]
@@ -128,6 +134,18 @@ pub struct ReplayCmd {
     #[arg(long)]
     pub compare: bool,
 
+    /// Decode mutated objects before and after execution and report field-level diffs
+    #[arg(long, default_value_t = false)]
+    pub diff_objects: bool,
+
+    /// Report gas per PTB command and the most expensive Move functions
+    #[arg(long, default_value_t = false)]
+    pub gas_profile: bool,
+
+    /// Reconcile local and on-chain effects object by object and classify each mismatch
+    #[arg(long, default_value_t = false)]
+    pub reconcile: bool,
+
     /// Run GraphQL-only and hybrid replays concurrently and compare results
     #[arg(long, default_value_t = false)]
     pub compare_sources: bool,
@@ -185,6 +203,9 @@ pub struct ReplayOutput {
     pub local_success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub local_error: Option<String>,
+    /// Typed category/code of `local_error`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_error_kind: Option<SandboxErrorKind>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<ReplayDiagnostics>,
     pub execution_path: ReplayExecutionPath,
@@ -196,6 +217,12 @@ pub struct ReplayOutput {
     pub effects: Option<ReplayEffectsSummary>,
     #[serde(skip)]
     pub effects_full: Option<sui_sandbox_core::ptb::TransactionEffects>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object_diffs: Option<Vec<ObjectDiff>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_profile: Option<GasProfile>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reconciliation: Option<EffectsReconciliation>,
     pub commands_executed: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_comparison: Option<SourceComparisonResult>,
@@ -204,6 +231,11 @@ pub struct ReplayOutput {
     pub batch_summary_printed: bool,
 }
 
+/// Typed kind of an execution error string (`effects.error` / `ReplayResult::local_error`).
+pub(super) fn local_error_kind(local_error: Option<&str>) -> Option<SandboxErrorKind> {
+    local_error.map(|err| SandboxError::from_message(err).kind())
+}
+
 #[derive(Debug, Serialize, Default)]
 pub struct ReplayExecutionPath {
     pub requested_source: String,
@@ -252,6 +284,12 @@ pub struct ReplayEffectsSummary {
     pub commands_succeeded: usize,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub return_values: Vec<usize>,
+    /// Abort location and decoded error constant of the failing MoveCall.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abort: Option<TransactionAbortInfo>,
+    /// Per-owner coin balance deltas, mirroring on-chain `BalanceChanges`.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub balance_changes: Vec<BalanceChange>,
 }
 
 #[derive(Debug, Serialize)]
@@ -923,7 +961,8 @@ impl ReplayCmd {
         };
         let make_harness =
             |version_map: &HashMap<String, u64>| -> Result<sui_sandbox_core::vm::VMHarness> {
-                let config = build_simulation_config(&replay_state);
+                let config =
+                    build_simulation_config(&replay_state).with_gas_profile(self.gas_profile);
                 let mut harness =
                     sui_sandbox_core::vm::VMHarness::with_config(&resolver, false, config)?;
                 harness.set_address_aliases_with_versions(
@@ -1164,10 +1203,19 @@ impl ReplayCmd {
                         allow_fallback,
                     )
                 };
+                let object_diffs = self
+                    .diff_objects
+                    .then(|| build_object_diffs(&replay_state, &execution.effects, &resolver));
+                let gas_profile = execution.effects.gas_profile.clone();
+                let reconciliation = self
+                    .reconcile
+                    .then(|| build_reconciliation(&replay_state, &execution.effects))
+                    .flatten();
 
                 Ok(ReplayOutput {
                     digest: self.digest_display().to_string(),
                     local_success: result.local_success,
+                    local_error_kind: local_error_kind(result.local_error.as_deref()),
                     local_error: result.local_error,
                     diagnostics,
                     execution_path,
@@ -1175,6 +1223,9 @@ impl ReplayCmd {
                     analysis: None,
                     effects: Some(effects_summary),
                     effects_full: Some(execution.effects),
+                    object_diffs,
+                    gas_profile,
+                    reconciliation,
                     commands_executed: result.commands_executed,
                     source_comparison: None,
                     batch_summary_printed: false,
@@ -1191,12 +1242,16 @@ impl ReplayCmd {
                     digest: self.digest_display().to_string(),
                     local_success: false,
                     local_error: Some(e.to_string()),
+                    local_error_kind: Some(SandboxError::classify(&e).kind()),
                     diagnostics,
                     execution_path,
                     comparison: None,
                     analysis: None,
                     effects: None,
                     effects_full: None,
+                    object_diffs: None,
+                    gas_profile: None,
+                    reconciliation: None,
                     commands_executed: 0,
                     source_comparison: None,
                     batch_summary_printed: false,
@@ -1524,8 +1579,9 @@ impl ReplayCmd {
         }
         emit_linkage_debug_info(&resolver, &pkg_aliases.aliases);
 
-        // Inject system objects (Clock, Random)
+        // Inject system objects (Clock, Random); the Clock reads the checkpoint timestamp
         if self.hydration.auto_system_objects {
+            backfill_replay_clock_timestamp(&mut replay_state, &graphql_client);
             ensure_system_objects(
                 &mut replay_state.objects,
                 &HashMap::new(),
@@ -1686,7 +1742,7 @@ impl ReplayCmd {
         };
 
         // Build VM harness and execute
-        let config = build_simulation_config(&replay_state);
+        let config = build_simulation_config(&replay_state).with_gas_profile(self.gas_profile);
         let mut harness = sui_sandbox_core::vm::VMHarness::with_config(&resolver, false, config)?;
         harness
             .set_address_aliases_with_versions(pkg_aliases.aliases.clone(), versions_str.clone());
@@ -2149,10 +2205,19 @@ impl ReplayCmd {
                         allow_fallback,
                     )
                 };
+                let object_diffs = self
+                    .diff_objects
+                    .then(|| build_object_diffs(&replay_state, &execution.effects, &resolver));
+                let gas_profile = execution.effects.gas_profile.clone();
+                let reconciliation = self
+                    .reconcile
+                    .then(|| build_reconciliation(&replay_state, &execution.effects))
+                    .flatten();
 
                 Ok(ReplayOutput {
                     digest: digest.to_string(),
                     local_success: result.local_success,
+                    local_error_kind: local_error_kind(result.local_error.as_deref()),
                     local_error: result.local_error,
                     diagnostics,
                     execution_path: ReplayExecutionPath {
@@ -2179,6 +2244,9 @@ impl ReplayCmd {
                     analysis: None,
                     effects: Some(effects_summary),
                     effects_full: Some(execution.effects),
+                    object_diffs,
+                    gas_profile,
+                    reconciliation,
                     commands_executed: result.commands_executed,
                     source_comparison: None,
                     batch_summary_printed: false,
@@ -2195,6 +2263,7 @@ impl ReplayCmd {
                     digest: digest.to_string(),
                     local_success: false,
                     local_error: Some(e.to_string()),
+                    local_error_kind: Some(SandboxError::classify(&e).kind()),
                     diagnostics,
                     execution_path: ReplayExecutionPath {
                         requested_source: self
@@ -2220,6 +2289,9 @@ impl ReplayCmd {
                     analysis: None,
                     effects: None,
                     effects_full: None,
+                    object_diffs: None,
+                    gas_profile: None,
+                    reconciliation: None,
                     commands_executed: 0,
                     source_comparison: None,
                     batch_summary_printed: false,
@@ -2290,6 +2362,7 @@ mod tests {
             digest: "test123".to_string(),
             local_success: true,
             local_error: None,
+            local_error_kind: None,
             diagnostics: None,
             execution_path: ReplayExecutionPath::default(),
             comparison: Some(ComparisonResult {
@@ -2304,6 +2377,9 @@ mod tests {
             analysis: None,
             effects: None,
             effects_full: None,
+            object_diffs: None,
+            gas_profile: None,
+            reconciliation: None,
             commands_executed: 3,
             source_comparison: None,
             batch_summary_printed: false,
@@ -2346,4 +2422,22 @@ mod tests {
         let fast = parse_replay_cmd(&["replay", "dummy-digest", "--profile", "fast"]);
         assert_eq!(fast.profile, ReplayProfile::Fast);
     }
+
+    #[test]
+    fn test_replay_cmd_diff_objects_flag() {
+        assert!(!parse_replay_cmd(&["replay", "dummy-digest"]).diff_objects);
+        assert!(parse_replay_cmd(&["replay", "dummy-digest", "--diff-objects"]).diff_objects);
+    }
+
+    #[test]
+    fn test_replay_cmd_gas_profile_flag() {
+        assert!(!parse_replay_cmd(&["replay", "dummy-digest"]).gas_profile);
+        assert!(parse_replay_cmd(&["replay", "dummy-digest", "--gas-profile"]).gas_profile);
+    }
+
+    #[test]
+    fn test_replay_cmd_reconcile_flag() {
+        assert!(!parse_replay_cmd(&["replay", "dummy-digest"]).reconcile);
+        assert!(parse_replay_cmd(&["replay", "dummy-digest", "--reconcile"]).reconcile);
+    }
 }
@@ -35,6 +35,7 @@ pub(super) fn build_analyze_replay_output(
         digest: replay_state.transaction.digest.0.clone(),
         local_success: true,
         local_error: None,
+        local_error_kind: None,
         diagnostics: None,
         execution_path: ReplayExecutionPath {
             requested_source: requested_source.to_string(),
@@ -56,6 +57,9 @@ pub(super) fn build_analyze_replay_output(
         analysis: Some(analysis),
         effects: None,
         effects_full: None,
+        object_diffs: None,
+        gas_profile: None,
+        reconciliation: None,
         commands_executed: 0,
         source_comparison: None,
         batch_summary_printed: false,
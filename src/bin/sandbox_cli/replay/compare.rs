@@ -2,6 +2,7 @@ use anyhow::Result;
 use std::sync::Arc;
 use std::time::Instant;
 
+use sui_sandbox_core::sandbox_error::SandboxError;
 use sui_sandbox_core::tx_replay::{self, EffectsReconcilePolicy};
 use sui_state_fetcher::{
     build_aliases as build_aliases_shared, fetch_child_object, HistoricalStateProvider,
@@ -18,8 +19,8 @@ use super::support::{
     hydrate_resolver_from_replay_state, maybe_patch_replay_objects,
 };
 use super::{
-    ComparisonResult, FetchStrategy, ReplayCmd, ReplayExecutionPath, ReplayOutput, ReplaySource,
-    SourceApiCalls, SourceComparisonResult,
+    local_error_kind, ComparisonResult, FetchStrategy, ReplayCmd, ReplayExecutionPath,
+    ReplayOutput, ReplaySource, SourceApiCalls, SourceComparisonResult,
 };
 
 /// Lightweight result from a single-source replay pipeline.
@@ -168,6 +169,7 @@ impl ReplayCmd {
                 digest: digest.to_string(),
                 local_success: false,
                 local_error: Some(e.to_string()),
+                local_error_kind: Some(SandboxError::classify(&e).kind()),
                 diagnostics: None,
                 execution_path: ReplayExecutionPath {
                     requested_source: "compare".to_string(),
@@ -178,6 +180,9 @@ impl ReplayCmd {
                 analysis: None,
                 effects: None,
                 effects_full: None,
+                object_diffs: None,
+                gas_profile: None,
+                reconciliation: None,
                 commands_executed: 0,
                 source_comparison: None,
                 batch_summary_printed: false,
@@ -307,6 +312,7 @@ fn run_pipeline(
                 output: ReplayOutput {
                     digest: replay_state.transaction.digest.0.clone(),
                     local_success: result.local_success,
+                    local_error_kind: local_error_kind(result.local_error.as_deref()),
                     local_error: result.local_error,
                     diagnostics: None,
                     execution_path: ReplayExecutionPath {
@@ -324,6 +330,9 @@ fn run_pipeline(
                     analysis: None,
                     effects: Some(effects_summary),
                     effects_full: Some(execution.effects),
+                    object_diffs: None,
+                    gas_profile: None,
+                    reconciliation: None,
                     commands_executed: result.commands_executed,
                     source_comparison: None,
                     batch_summary_printed: false,
@@ -338,6 +347,7 @@ fn run_pipeline(
                 digest: replay_state.transaction.digest.0.clone(),
                 local_success: false,
                 local_error: Some(e.to_string()),
+                local_error_kind: Some(SandboxError::classify(&e).kind()),
                 diagnostics: None,
                 execution_path: ReplayExecutionPath {
                     requested_source: source_label.to_string(),
@@ -354,6 +364,9 @@ fn run_pipeline(
                 analysis: None,
                 effects: None,
                 effects_full: None,
+                object_diffs: None,
+                gas_profile: None,
+                reconciliation: None,
                 commands_executed: 0,
                 source_comparison: None,
                 batch_summary_printed: false,
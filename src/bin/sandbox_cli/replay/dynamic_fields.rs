@@ -13,7 +13,7 @@ use sui_state_fetcher::{
     fetch_child_object as fetch_child_object_shared,
     fetch_object_via_grpc as fetch_object_via_grpc_shared, HistoricalStateProvider,
 };
-use sui_transport::graphql::GraphQLClient;
+use sui_transport::graphql::{DynamicFieldScan, GraphQLClient};
 
 fn b64_matches_bytes(encoded: &str, expected: &[u8]) -> bool {
     if let Ok(decoded) = base64::engine::general_purpose::STANDARD.decode(encoded) {
@@ -82,24 +82,29 @@ pub(super) fn resolve_key_type_via_graphql(
             return Some(tag.clone());
         }
     }
-    let enum_limit = std::env::var("SUI_DF_ENUM_LIMIT")
-        .ok()
-        .and_then(|v| v.parse::<usize>().ok())
-        .unwrap_or(1000);
+    let max_pages = sui_transport::graphql::dynamic_field_scan_max_pages();
     let field = match checkpoint {
         Some(cp) => gql
-            .find_dynamic_field_by_bcs(&parent_hex, key_bytes, Some(cp), enum_limit)
+            .scan_dynamic_field_by_bcs(&parent_hex, key_bytes, Some(cp), max_pages)
             .or_else(|err| {
                 if strict_checkpoint {
                     Err(err)
                 } else {
-                    gql.find_dynamic_field_by_bcs(&parent_hex, key_bytes, None, enum_limit)
+                    gql.scan_dynamic_field_by_bcs(&parent_hex, key_bytes, None, max_pages)
                 }
             }),
-        None => gql.find_dynamic_field_by_bcs(&parent_hex, key_bytes, None, enum_limit),
+        None => gql.scan_dynamic_field_by_bcs(&parent_hex, key_bytes, None, max_pages),
     };
     let df = match field {
-        Ok(Some(df)) => df,
+        Ok(DynamicFieldScan::Found(df)) => df,
+        Ok(DynamicFieldScan::Truncated { pages_scanned }) => {
+            eprintln!(
+                "[df_fetch] key lookup under parent={} stopped after {} pages \
+                 (SUI_DF_SCAN_MAX_PAGES); the key may lie beyond the bound",
+                parent_hex, pages_scanned
+            );
+            return None;
+        }
         _ => return None,
     };
     let tag = match parse_type_tag(&df.name_type) {
@@ -1,4 +1,6 @@
 use clap::ValueEnum;
+use sui_sandbox_core::effects_reconciliation::{reconcile_effects, EffectsReconciliation};
+use sui_state_fetcher::ReplayState;
 
 use super::{ReplayCmd, ReplayEffectsSummary, ReplayExecutionPath};
 
@@ -93,5 +95,26 @@ pub(super) fn build_effects_summary(
             .iter()
             .map(|vals| vals.len())
             .collect(),
+        abort: effects
+            .error_context
+            .as_ref()
+            .and_then(|ctx| ctx.abort_info.clone()),
+        balance_changes: effects.balance_changes.clone(),
     }
 }
+
+/// Object-by-object reconciliation against the transaction's on-chain effects, if it
+/// carries them.
+pub(super) fn build_reconciliation(
+    replay_state: &ReplayState,
+    effects: &sui_sandbox_core::ptb::TransactionEffects,
+) -> Option<EffectsReconciliation> {
+    let on_chain = replay_state.transaction.effects.as_ref()?;
+    let gas_objects: Vec<String> = replay_state
+        .transaction
+        .gas_payment
+        .iter()
+        .map(|gas| gas.object_id.clone())
+        .collect();
+    Some(reconcile_effects(on_chain, effects, &gas_objects))
+}
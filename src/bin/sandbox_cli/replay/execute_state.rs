@@ -3,12 +3,14 @@ use std::path::Path;
 
 use super::super::SandboxState;
 use super::analysis::{build_analyze_replay_output, build_replay_diagnostics};
-use super::effects::build_effects_summary;
+use super::effects::{build_effects_summary, build_reconciliation};
 use super::support::{
     build_replay_object_maps, build_simulation_config, emit_linkage_debug_info,
     hydrate_resolver_from_replay_state, maybe_patch_replay_objects,
 };
-use super::{ComparisonResult, ReplayCmd, ReplayExecutionPath, ReplayOutput};
+use super::{local_error_kind, ComparisonResult, ReplayCmd, ReplayExecutionPath, ReplayOutput};
+use sui_sandbox_core::object_diff::build_object_diffs;
+use sui_sandbox_core::sandbox_error::SandboxError;
 use sui_sandbox_core::tx_replay::EffectsReconcilePolicy;
 use sui_state_fetcher::{
     build_aliases as build_aliases_shared, parse_replay_states_file, ReplayState,
@@ -113,7 +115,7 @@ pub(super) fn execute_replay_state(
         EffectsReconcilePolicy::Strict
     };
 
-    let config = build_simulation_config(replay_state);
+    let config = build_simulation_config(replay_state).with_gas_profile(cmd.gas_profile);
     let mut harness = sui_sandbox_core::vm::VMHarness::with_config(&resolver, false, config)?;
     harness.set_address_aliases_with_versions(pkg_aliases.aliases.clone(), versions_str.clone());
 
@@ -159,10 +161,19 @@ pub(super) fn execute_replay_state(
             } else {
                 build_replay_diagnostics(replay_state, &cached_objects, &resolver, allow_fallback)
             };
+            let object_diffs = cmd
+                .diff_objects
+                .then(|| build_object_diffs(replay_state, &execution.effects, &resolver));
+            let gas_profile = execution.effects.gas_profile.clone();
+            let reconciliation = cmd
+                .reconcile
+                .then(|| build_reconciliation(replay_state, &execution.effects))
+                .flatten();
 
             Ok(ReplayOutput {
                 digest: replay_state.transaction.digest.0.clone(),
                 local_success: result.local_success,
+                local_error_kind: local_error_kind(result.local_error.as_deref()),
                 local_error: result.local_error,
                 diagnostics,
                 execution_path: ReplayExecutionPath {
@@ -185,6 +196,9 @@ pub(super) fn execute_replay_state(
                 analysis: None,
                 effects: Some(effects_summary),
                 effects_full: Some(execution.effects),
+                object_diffs,
+                gas_profile,
+                reconciliation,
                 commands_executed: result.commands_executed,
                 source_comparison: None,
                 batch_summary_printed: false,
@@ -197,6 +211,7 @@ pub(super) fn execute_replay_state(
                 digest: replay_state.transaction.digest.0.clone(),
                 local_success: false,
                 local_error: Some(e.to_string()),
+                local_error_kind: Some(SandboxError::classify(&e).kind()),
                 diagnostics,
                 execution_path: ReplayExecutionPath {
                     requested_source: requested_source.to_string(),
@@ -218,6 +233,9 @@ pub(super) fn execute_replay_state(
                 analysis: None,
                 effects: None,
                 effects_full: None,
+                object_diffs: None,
+                gas_profile: None,
+                reconciliation: None,
                 commands_executed: 0,
                 source_comparison: None,
                 batch_summary_printed: false,
@@ -20,6 +20,7 @@ use sui_sandbox_types::{
     PtbCommand, TransactionDigest as SandboxTransactionDigest, TransactionEffectsSummary,
     TransactionInput, TransactionStatus,
 };
+use sui_state_fetcher::bcs_codec::gas_payment_objects;
 use sui_state_fetcher::{
     package_data_from_move_package, HistoricalStateProvider, PackageData, ReplayState,
     VersionedObject,
@@ -252,6 +253,7 @@ impl ReplayCmd {
                 effects: effects_summary,
                 timestamp_ms: timestamp_ms_opt,
                 checkpoint: Some(checkpoint),
+                gas_payment: gas_payment_objects(&tx_data),
             };
 
             let mut package_ids = collect_package_ids_from_commands(&ptb.commands);
@@ -961,6 +963,7 @@ fn build_effects_summary(
         gas_used: GasSummary::default(),
         events_count: 0,
         shared_object_versions: shared_versions.clone(),
+        object_changes: Vec::new(),
     })
 }
 
@@ -45,6 +45,85 @@ pub(super) fn print_replay_result(result: &ReplayOutput, show_comparison: bool,
         println!("  Commands executed: {}", result.commands_executed);
     }
 
+    if let Some(diffs) = result.object_diffs.as_ref() {
+        println!("\n\x1b[1mObject Diffs:\x1b[0m");
+        if diffs.is_empty() {
+            println!("  (no mutated objects)");
+        }
+        for diff in diffs {
+            println!(
+                "  {} {}",
+                diff.object_id,
+                diff.type_tag.as_deref().unwrap_or("<unknown type>")
+            );
+            if let Some(err) = &diff.error {
+                println!("    \x1b[33m! {}\x1b[0m", err);
+            } else if diff.changes.is_empty() {
+                println!("    (no field changes)");
+            }
+            for change in &diff.changes {
+                println!("    {}: {} -> {}", change.path, change.before, change.after);
+            }
+        }
+    }
+
+    if let Some(profile) = result.gas_profile.as_ref() {
+        println!("\n\x1b[1mGas Profile:\x1b[0m");
+        println!(
+            "  Total: {} (computation={} storage={})",
+            profile.gas_used, profile.computation_gas, profile.storage_gas
+        );
+        if let Some(summary) = profile.summary.as_ref() {
+            println!(
+                "  Cost: computation={} storage={} rebate={}",
+                summary.computation_cost, summary.storage_cost, summary.storage_rebate
+            );
+        }
+        for command in &profile.commands {
+            println!(
+                "  [{}] {:<16} {:>12}  {}",
+                command.index, command.command_type, command.gas_used, command.description
+            );
+        }
+        if !profile.top_functions.is_empty() {
+            println!("  Top functions (self / inclusive / calls):");
+        }
+        for function in &profile.top_functions {
+            println!(
+                "    {:>12} {:>12} {:>6}  {}",
+                function.self_gas, function.inclusive_gas, function.calls, function.function
+            );
+        }
+    }
+
+    if let Some(reconciliation) = result.reconciliation.as_ref() {
+        println!("\n\x1b[1mEffects Reconciliation:\x1b[0m");
+        println!(
+            "  Objects: {} compared, {} matched",
+            reconciliation.objects_compared, reconciliation.objects_matched
+        );
+        if reconciliation.is_match() {
+            println!("  \x1b[32m✓ No mismatches\x1b[0m");
+        }
+        for mismatch in &reconciliation.mismatches {
+            println!(
+                "  \x1b[31m✗\x1b[0m {:<22} {}  {}",
+                mismatch.kind.as_str(),
+                mismatch.object_id,
+                mismatch.detail
+            );
+        }
+        if !reconciliation.ignored_gas_objects.is_empty() {
+            println!(
+                "  Ignored gas coins: {}",
+                reconciliation.ignored_gas_objects.join(", ")
+            );
+        }
+        for note in &reconciliation.notes {
+            println!("  Note: {}", note);
+        }
+    }
+
     println!("\n\x1b[1mExecution Path:\x1b[0m");
     println!(
         "  Source: requested={} effective={}",
@@ -336,6 +415,7 @@ mod tests {
                 "ContractAbort { location: Undefined, abort_code: 1 } missing runtime object"
                     .to_string(),
             ),
+            local_error_kind: None,
             diagnostics: None,
             execution_path: ReplayExecutionPath {
                 allow_fallback: true,
@@ -345,6 +425,9 @@ mod tests {
             analysis: None,
             effects: None,
             effects_full: None,
+            object_diffs: None,
+            gas_profile: None,
+            reconciliation: None,
             commands_executed: 0,
             source_comparison: None,
             batch_summary_printed: false,
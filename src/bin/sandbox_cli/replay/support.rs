@@ -11,6 +11,7 @@ use sui_sandbox_types::{
     CLOCK_OBJECT_ID, CLOCK_TYPE_STR, DEFAULT_CLOCK_BASE_MS, RANDOM_OBJECT_ID, RANDOM_TYPE_STR,
 };
 use sui_state_fetcher::{PackageData, ReplayState, VersionedObject};
+use sui_transport::graphql::GraphQLClient;
 
 use super::super::SandboxState;
 
@@ -81,6 +82,13 @@ pub(super) fn build_simulation_config(replay_state: &ReplayState) -> SimulationC
     replay_support::build_simulation_config(replay_state)
 }
 
+pub(super) fn backfill_replay_clock_timestamp(
+    replay_state: &mut ReplayState,
+    graphql: &GraphQLClient,
+) -> Option<u64> {
+    replay_support::backfill_replay_clock_timestamp(replay_state, Some(graphql))
+}
+
 pub(super) fn ensure_system_objects(
     objects: &mut HashMap<AccountAddress, VersionedObject>,
     historical_versions: &HashMap<String, u64>,
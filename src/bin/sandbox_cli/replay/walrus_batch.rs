@@ -194,6 +194,9 @@ pub(super) async fn execute_walrus_batch_v2(
                 vm_only: cmd.vm_only,
                 strict: false, // don't fail-fast in batch mode
                 compare: cmd.compare,
+                diff_objects: cmd.diff_objects,
+                gas_profile: cmd.gas_profile,
+                reconcile: cmd.reconcile,
                 analyze_only: cmd.analyze_only,
                 verbose: cmd.verbose,
                 fetch_strategy: cmd.fetch_strategy,
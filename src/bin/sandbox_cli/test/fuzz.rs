@@ -1,11 +1,14 @@
 //! Fuzz testing CLI command.
 
+use std::path::PathBuf;
+
 use anyhow::{anyhow, Context, Result};
 use clap::Parser;
 use move_core_types::account_address::AccountAddress;
 
 use sui_sandbox_core::fuzz::{
-    classify_params, ClassifiedFunction, FuzzConfig, FuzzReport, FuzzRunner, Outcome, ParamClass,
+    classify_params, ClassifiedFunction, FuzzConfig, FuzzCorpus, FuzzReport, FuzzRunner,
+    MoveInvariant, Outcome, ParamClass,
 };
 use sui_sandbox_core::shared::parsing::parse_type_tag_string;
 
@@ -60,6 +63,22 @@ pub struct FuzzCmd {
     /// Maximum vector length for generated vector inputs
     #[arg(long, default_value = "32")]
     pub max_vector_len: usize,
+
+    /// Coverage-guided mode: trace executed basic blocks and mutate inputs
+    /// that reach new ones
+    #[arg(long)]
+    pub coverage: bool,
+
+    /// Directory for per-function corpus files, loaded before and saved after
+    /// each run so campaigns can resume (implies --coverage)
+    #[arg(long, value_name = "DIR")]
+    pub corpus_dir: Option<PathBuf>,
+
+    /// Move function that must hold after every successful iteration
+    /// ("0xPKG::module::function", repeatable). It is called with the target's
+    /// inputs followed by its return values and fails if it aborts or returns false.
+    #[arg(long = "invariant", value_name = "FUNCTION")]
+    pub invariants: Vec<String>,
 }
 
 impl FuzzCmd {
@@ -240,16 +259,41 @@ impl FuzzCmd {
             type_args: type_args.to_vec(),
             fail_fast: self.fail_fast,
             max_vector_len: self.max_vector_len,
+            coverage_guided: self.coverage || self.corpus_dir.is_some(),
         };
 
-        let runner = FuzzRunner::new(&state.resolver);
-        let report = runner.run(
-            package,
-            module_name,
-            function_name,
-            &classification,
-            &config,
-        )?;
+        let mut runner = FuzzRunner::new(&state.resolver);
+        for invariant in &self.invariants {
+            runner = runner.with_invariant(MoveInvariant::parse(invariant)?);
+        }
+        let report = match &self.corpus_dir {
+            Some(dir) => {
+                let corpus_path = dir.join(format!(
+                    "{}__{}__{}.json",
+                    package.to_hex_literal(),
+                    module_name,
+                    function_name
+                ));
+                let mut corpus = FuzzCorpus::load(&corpus_path)?;
+                let report = runner.run_with_corpus(
+                    package,
+                    module_name,
+                    function_name,
+                    &classification,
+                    &config,
+                    &mut corpus,
+                )?;
+                corpus.save(&corpus_path)?;
+                report
+            }
+            None => runner.run(
+                package,
+                module_name,
+                function_name,
+                &classification,
+                &config,
+            )?,
+        };
 
         if json_output {
             println!("{}", serde_json::to_string_pretty(&report)?);
@@ -320,7 +364,13 @@ fn print_report(report: &FuzzReport) {
             ParamClass::SystemInjected { .. } => "System",
             _ => "Other",
         };
-        println!("  [{i}] {type_str:30} -> {label}");
+        let bounded = report
+            .bounded_params
+            .iter()
+            .find(|b| b.param_index == i)
+            .map(|b| format!("  (bounded: {})", b.constants.join(", ")))
+            .unwrap_or_default();
+        println!("  [{i}] {type_str:30} -> {label}{bounded}");
     }
     println!();
     println!(
@@ -362,6 +412,38 @@ fn print_report(report: &FuzzReport) {
         println!("  Errors:         {:>6} ({:.1}%)", error_total, err_pct);
     }
 
+    let violation_total: u64 = report
+        .outcomes
+        .invariant_violations
+        .iter()
+        .map(|v| v.count)
+        .sum();
+    if violation_total > 0 {
+        let violation_pct = violation_total as f64 / total as f64 * 100.0;
+        println!(
+            "  Invariant violations: {:>6} ({:.1}%)",
+            violation_total, violation_pct
+        );
+        for violation in &report.outcomes.invariant_violations {
+            println!(
+                "    {}: {:>6}  ({})",
+                violation.invariant, violation.count, violation.message
+            );
+        }
+    }
+
+    if let Some(coverage) = &report.coverage {
+        println!();
+        println!(
+            "Coverage: {} blocks in {} functions (corpus: {} entries, {} new, {} seeded)",
+            coverage.blocks,
+            coverage.functions,
+            coverage.corpus_size,
+            coverage.new_entries,
+            coverage.seeded_entries
+        );
+    }
+
     // Gas profile
     println!();
     println!("Gas profile:");
@@ -399,6 +481,9 @@ fn print_report(report: &FuzzReport) {
                     format!("error: {short}")
                 }
                 Outcome::GasExhaustion => "gas exhaustion".into(),
+                Outcome::InvariantViolation { invariant, .. } => {
+                    format!("invariant violated: {invariant}")
+                }
                 Outcome::Success => "success".into(),
             };
             println!(
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 use sui_sandbox_core::checkpoint_discovery::{
     build_walrus_client as core_build_walrus_client,
     discover_checkpoint_targets as core_discover_checkpoint_targets,
@@ -34,6 +35,10 @@ use sui_sandbox_core::workflow_planner::{
 use sui_sandbox_core::workflow_runner::{
     run_prepared_workflow_steps, WorkflowPreparedStep, WorkflowRunReport, WorkflowStepExecution,
 };
+use sui_sandbox_core::workflow_watch::{
+    default_watch_cursor_path as core_default_watch_cursor_path,
+    watch_workflow as core_watch_workflow, WorkflowWatchConfig as CoreWorkflowWatchConfig,
+};
 use sui_transport::decode_graphql_modules;
 use sui_transport::graphql::GraphQLClient;
 
@@ -65,6 +70,8 @@ enum WorkflowSubcommand {
     Validate(WorkflowValidateCmd),
     /// Run a workflow spec deterministically
     Run(WorkflowRunCmd),
+    /// Re-run a workflow for each new Walrus transaction touching a package
+    Watch(WorkflowWatchCmd),
 }
 
 #[derive(Args, Debug)]
@@ -290,6 +297,58 @@ pub struct WorkflowRunCmd {
     pub report: Option<PathBuf>,
 }
 
+#[derive(Args, Debug)]
+pub struct WorkflowWatchCmd {
+    /// Path to workflow spec (JSON or YAML)
+    #[arg(long)]
+    pub spec: PathBuf,
+
+    /// Package whose new transactions trigger a run
+    #[arg(long)]
+    pub package_id: String,
+
+    /// Cursor file with the last processed checkpoint and recently run digests
+    /// (default: `<spec>.watch.json` next to the spec)
+    #[arg(long)]
+    pub cursor: Option<PathBuf>,
+
+    /// Seconds to wait between polls once caught up with the Walrus tip
+    #[arg(long, default_value_t = 30)]
+    pub interval_secs: u64,
+
+    /// Maximum checkpoints scanned per poll
+    #[arg(long, default_value_t = 20)]
+    pub max_checkpoints_per_poll: u64,
+
+    /// First checkpoint to scan when the cursor is new (default: current tip)
+    #[arg(long)]
+    pub from_checkpoint: Option<u64>,
+
+    /// Stop after this many polls (default: run until interrupted)
+    #[arg(long)]
+    pub max_polls: Option<usize>,
+
+    /// Continue executing later steps when one fails
+    #[arg(long, default_value_t = false)]
+    pub continue_on_error: bool,
+
+    /// Write each run's report to `<dir>/<checkpoint>_<digest>.json`
+    #[arg(long)]
+    pub report_dir: Option<PathBuf>,
+
+    /// Walrus archive network to poll
+    #[arg(long, value_enum, default_value = "mainnet")]
+    pub walrus_network: WorkflowWalrusNetwork,
+
+    /// Override Walrus caching endpoint (requires --walrus-aggregator-url)
+    #[arg(long)]
+    pub walrus_caching_url: Option<String>,
+
+    /// Override Walrus aggregator endpoint (requires --walrus-caching-url)
+    #[arg(long)]
+    pub walrus_aggregator_url: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct WorkflowValidateOutput {
     spec_file: String,
@@ -389,6 +448,9 @@ impl WorkflowCmd {
             WorkflowSubcommand::Run(cmd) => {
                 cmd.execute(state_file, rpc_url, json_output, verbose).await
             }
+            WorkflowSubcommand::Watch(cmd) => {
+                cmd.execute(state_file, rpc_url, json_output, verbose)
+            }
         }
     }
 }
@@ -1112,139 +1174,15 @@ impl WorkflowRunCmd {
             }
         }
 
-        let prepared_steps = spec
-            .steps
-            .iter()
-            .enumerate()
-            .map(|(idx, step)| WorkflowPreparedStep {
-                index: idx + 1,
-                id: step.id.clone(),
-                name: step.name.clone(),
-                kind: core_workflow_step_kind(&step.action).to_string(),
-                continue_on_error: step.continue_on_error,
-                command: core_workflow_build_step_command(&spec.defaults, step)
-                    .map_err(|err| err.to_string()),
-            })
-            .collect::<Vec<_>>();
-        let mut executable: Option<PathBuf> = None;
-
-        let report = run_prepared_workflow_steps(
-            self.spec.display().to_string(),
+        let report = run_workflow_spec(
             &spec,
-            prepared_steps,
+            self.spec.display().to_string(),
             self.dry_run,
             self.continue_on_error,
-            |step, prepared| {
-                if !json_output {
-                    let label = core_workflow_step_label(step, prepared.index);
-                    println!("[workflow:{label}] {}", prepared.command_display());
-                }
-            },
-            |step, prepared| {
-                let argv = prepared.command.clone().map_err(anyhow::Error::msg)?;
-                let display_cmd = argv.join(" ");
-                if !json_output {
-                    match &step.action {
-                        WorkflowStepAction::Replay(_) => {
-                            return execute_workflow_replay_step_native(
-                                &argv,
-                                state_file,
-                                rpc_url,
-                                false,
-                                verbose,
-                                prepared.index,
-                            );
-                        }
-                        WorkflowStepAction::AnalyzeReplay(_) => {
-                            #[cfg(feature = "analysis")]
-                            {
-                                return execute_workflow_analyze_step_native(
-                                    &argv,
-                                    state_file,
-                                    rpc_url,
-                                    false,
-                                    verbose,
-                                    prepared.index,
-                                );
-                            }
-                            #[cfg(not(feature = "analysis"))]
-                            {
-                                return Ok(WorkflowStepExecution {
-                                    exit_code: 1,
-                                    output: None,
-                                    error: Some("workflow analyze_replay step requires the `analysis` feature".to_string()),
-                                });
-                            }
-                        }
-                        WorkflowStepAction::Command(_) => {}
-                    }
-                }
-
-                let executable = if let Some(path) = executable.as_ref() {
-                    path.clone()
-                } else {
-                    let path =
-                        std::env::current_exe().context("Failed to resolve current executable")?;
-                    executable = Some(path.clone());
-                    path
-                };
-
-                let mut cmd = Command::new(&executable);
-                cmd.arg("--state-file")
-                    .arg(state_file)
-                    .arg("--rpc-url")
-                    .arg(rpc_url);
-                if verbose {
-                    cmd.arg("--verbose");
-                }
-                cmd.args(&argv);
-
-                let output = cmd.output().with_context(|| {
-                    format!(
-                        "Failed to execute workflow step {} ({})",
-                        prepared.index, display_cmd
-                    )
-                })?;
-
-                let ok = output.status.success();
-                let exit_code = output.status.code().unwrap_or(-1);
-                let failure_summary = if ok {
-                    None
-                } else {
-                    core_summarize_failure_output(&output.stdout, &output.stderr)
-                };
-
-                if !json_output {
-                    let stdout = String::from_utf8_lossy(&output.stdout);
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    if !stdout.trim().is_empty() {
-                        print!("{stdout}");
-                    }
-                    if !stderr.trim().is_empty() {
-                        eprint!("{stderr}");
-                    }
-                }
-
-                let error = if ok {
-                    None
-                } else {
-                    Some(match failure_summary.as_deref() {
-                        Some(summary) => format!(
-                            "step {} failed with exit code {}: {}",
-                            prepared.index, exit_code, summary
-                        ),
-                        None => format!(
-                            "step {} failed with exit code {}",
-                            prepared.index, exit_code
-                        ),
-                    })
-                };
-                Ok(WorkflowStepExecution {
-                    exit_code,
-                    output: None,
-                    error,
-                })
-            },
+            state_file,
+            rpc_url,
+            json_output,
+            verbose,
         );
 
         maybe_write_report(self.report.as_ref(), &report, json_output)?;
@@ -1279,6 +1217,249 @@ impl WorkflowRunCmd {
     }
 }
 
+impl WorkflowWatchCmd {
+    fn execute(
+        &self,
+        state_file: &Path,
+        rpc_url: &str,
+        json_output: bool,
+        verbose: bool,
+    ) -> Result<()> {
+        let spec = WorkflowSpec::load_from_path(&self.spec)?;
+        let network = CoreWalrusArchiveNetwork::parse(self.walrus_network.as_cli_value())?;
+        let walrus = core_build_walrus_client(
+            network,
+            self.walrus_caching_url.as_deref(),
+            self.walrus_aggregator_url.as_deref(),
+        )?;
+        let cursor_path = self
+            .cursor
+            .clone()
+            .unwrap_or_else(|| core_default_watch_cursor_path(&self.spec));
+        let config = CoreWorkflowWatchConfig {
+            poll_interval: Duration::from_secs(self.interval_secs),
+            max_checkpoints_per_poll: self.max_checkpoints_per_poll,
+            start_checkpoint: self.from_checkpoint,
+            max_polls: self.max_polls,
+            ..CoreWorkflowWatchConfig::new(&self.package_id)
+        };
+        if !json_output {
+            println!(
+                "Watching package {} (cursor: {})",
+                self.package_id,
+                cursor_path.display()
+            );
+        }
+
+        let summary = core_watch_workflow(
+            &walrus,
+            &spec,
+            &config,
+            Some(&cursor_path),
+            |target, target_spec| {
+                if !json_output {
+                    println!(
+                        "[watch] checkpoint {} tx {}",
+                        target.checkpoint, target.digest
+                    );
+                }
+                let report = run_workflow_spec(
+                    target_spec,
+                    self.spec.display().to_string(),
+                    false,
+                    self.continue_on_error,
+                    state_file,
+                    rpc_url,
+                    json_output,
+                    verbose,
+                );
+                let report_path = self
+                    .report_dir
+                    .as_ref()
+                    .map(|dir| dir.join(format!("{}_{}.json", target.checkpoint, target.digest)));
+                maybe_write_report(report_path.as_ref(), &report, json_output)?;
+                if !json_output {
+                    println!(
+                        "[watch] {}: {}/{} succeeded ({} failed)",
+                        target.digest,
+                        report.succeeded_steps,
+                        report.total_steps,
+                        report.failed_steps
+                    );
+                }
+                Ok(report)
+            },
+        )?;
+
+        if json_output {
+            println!("{}", serde_json::to_string_pretty(&summary)?);
+        } else {
+            let failed_runs = summary.runs.iter().filter(|run| !run.success).count();
+            println!(
+                "Watch complete: {} poll(s), {} checkpoint(s) scanned, {} run(s) ({} failed)",
+                summary.polls,
+                summary.checkpoints_scanned,
+                summary.runs.len(),
+                failed_runs
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Run a loaded workflow spec: replay/analyze steps natively, command steps as
+/// `sui-sandbox` subprocesses.
+#[allow(clippy::too_many_arguments)]
+fn run_workflow_spec(
+    spec: &WorkflowSpec,
+    spec_label: String,
+    dry_run: bool,
+    continue_on_error: bool,
+    state_file: &Path,
+    rpc_url: &str,
+    json_output: bool,
+    verbose: bool,
+) -> WorkflowRunReport {
+    let prepared_steps = spec
+        .steps
+        .iter()
+        .enumerate()
+        .map(|(idx, step)| WorkflowPreparedStep {
+            index: idx + 1,
+            id: step.id.clone(),
+            name: step.name.clone(),
+            kind: core_workflow_step_kind(&step.action).to_string(),
+            continue_on_error: step.continue_on_error,
+            command: core_workflow_build_step_command(&spec.defaults, step)
+                .map_err(|err| err.to_string()),
+        })
+        .collect::<Vec<_>>();
+    let mut executable: Option<PathBuf> = None;
+
+    run_prepared_workflow_steps(
+        spec_label,
+        spec,
+        prepared_steps,
+        dry_run,
+        continue_on_error,
+        |step, prepared| {
+            if !json_output {
+                let label = core_workflow_step_label(step, prepared.index);
+                println!("[workflow:{label}] {}", prepared.command_display());
+            }
+        },
+        |step, prepared| {
+            let argv = prepared.command.clone().map_err(anyhow::Error::msg)?;
+            let display_cmd = argv.join(" ");
+            if !json_output {
+                match &step.action {
+                    WorkflowStepAction::Replay(_) => {
+                        return execute_workflow_replay_step_native(
+                            &argv,
+                            state_file,
+                            rpc_url,
+                            false,
+                            verbose,
+                            prepared.index,
+                        );
+                    }
+                    WorkflowStepAction::AnalyzeReplay(_) => {
+                        #[cfg(feature = "analysis")]
+                        {
+                            return execute_workflow_analyze_step_native(
+                                &argv,
+                                state_file,
+                                rpc_url,
+                                false,
+                                verbose,
+                                prepared.index,
+                            );
+                        }
+                        #[cfg(not(feature = "analysis"))]
+                        {
+                            return Ok(WorkflowStepExecution {
+                                exit_code: 1,
+                                output: None,
+                                error: Some(
+                                    "workflow analyze_replay step requires the `analysis` feature"
+                                        .to_string(),
+                                ),
+                            });
+                        }
+                    }
+                    WorkflowStepAction::Command(_) => {}
+                }
+            }
+
+            let executable = if let Some(path) = executable.as_ref() {
+                path.clone()
+            } else {
+                let path =
+                    std::env::current_exe().context("Failed to resolve current executable")?;
+                executable = Some(path.clone());
+                path
+            };
+
+            let mut cmd = Command::new(&executable);
+            cmd.arg("--state-file")
+                .arg(state_file)
+                .arg("--rpc-url")
+                .arg(rpc_url);
+            if verbose {
+                cmd.arg("--verbose");
+            }
+            cmd.args(&argv);
+
+            let output = cmd.output().with_context(|| {
+                format!(
+                    "Failed to execute workflow step {} ({})",
+                    prepared.index, display_cmd
+                )
+            })?;
+
+            let ok = output.status.success();
+            let exit_code = output.status.code().unwrap_or(-1);
+            let failure_summary = if ok {
+                None
+            } else {
+                core_summarize_failure_output(&output.stdout, &output.stderr)
+            };
+
+            if !json_output {
+                let stdout = String::from_utf8_lossy(&output.stdout);
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                if !stdout.trim().is_empty() {
+                    print!("{stdout}");
+                }
+                if !stderr.trim().is_empty() {
+                    eprint!("{stderr}");
+                }
+            }
+
+            let error = if ok {
+                None
+            } else {
+                Some(match failure_summary.as_deref() {
+                    Some(summary) => format!(
+                        "step {} failed with exit code {}: {}",
+                        prepared.index, exit_code, summary
+                    ),
+                    None => format!(
+                        "step {} failed with exit code {}",
+                        prepared.index, exit_code
+                    ),
+                })
+            };
+            Ok(WorkflowStepExecution {
+                exit_code,
+                // JSON stdout (e.g. `--json` subcommands) feeds `${steps.<id>...}`.
+                output: serde_json::from_slice(&output.stdout).ok(),
+                error,
+            })
+        },
+    )
+}
+
 fn maybe_write_report(
     report_path: Option<&PathBuf>,
     report: &WorkflowRunReport,